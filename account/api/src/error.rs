@@ -1,4 +1,6 @@
+use starcoin_crypto::HashValue;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::genesis_config::ChainId;
 use thiserror::Error;
 
 /// wallet error is used in wallet impl, to decouple from service.
@@ -11,6 +13,14 @@ pub enum AccountError {
     AccountAlreadyExist(AccountAddress),
     #[error("account {0} is locked")]
     AccountLocked(AccountAddress),
+    #[error("txn of account {address} spends {amount} nanoSTC, exceeds the remaining {remaining} nanoSTC of the unlock session, please unlock it again")]
+    UnlockLimitExceeded {
+        address: AccountAddress,
+        amount: u128,
+        remaining: u128,
+    },
+    #[error("the spend amount of the txn of account {0} can not be known before executing, it is rejected by the spend limited unlock session")]
+    UnknownSpendAmount(AccountAddress),
 
     #[error("cannot remove default account {0}")]
     RemoveDefaultAccountError(AccountAddress),
@@ -23,11 +33,39 @@ pub enum AccountError {
 
     #[error("invalid public key: {0:?}")]
     InvalidPublicKey(starcoin_crypto::CryptoMaterialError),
+
+    #[error("invalid mnemonic or derivation path: {0:?}")]
+    InvalidMnemonic(anyhow::Error),
+
+    #[error("restore {count} accounts exceeds the max count {max}")]
+    RestoreCountExceeded { count: u32, max: u32 },
+
+    #[error("invalid account metadata: {0}")]
+    InvalidMetadata(anyhow::Error),
+
+    #[error("invalid keystore entry: {0}")]
+    InvalidKeystore(anyhow::Error),
+    #[error(
+        "account {address} is saved on chain {account_chain_id}, mismatch with chain {chain_id}"
+    )]
+    ChainIdMismatch {
+        address: AccountAddress,
+        account_chain_id: ChainId,
+        chain_id: ChainId,
+    },
     // logic error
     #[error("transaction sign error, {0:?}")]
     TransactionSignError(anyhow::Error),
     #[error("message sign error, {0:?}")]
     MessageSignError(anyhow::Error),
+    #[error("txn {txn_hash} is denied by the sign policy: {reason}")]
+    SignPolicyDenied { txn_hash: HashValue, reason: String },
+    #[error("txn {txn_hash} requires approval by the sign policy: {reason}, ask the node operator to approve it and sign it again")]
+    SignPolicyApprovalRequired { txn_hash: HashValue, reason: String },
+    #[error("txn of approval hash {0} is not waiting for approval")]
+    TxnNotPendingApproval(HashValue),
+    #[error("sign policy error, {0:?}")]
+    SignPolicyError(anyhow::Error),
     // #[error("decrypt private key error, {0:?}")]
     // DecryptPrivateKeyError(anyhow::Error),
     #[error("no private key data associate with address {0}")]
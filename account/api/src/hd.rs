@@ -0,0 +1,169 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic key derivation for ed25519 accounts.
+//!
+//! The mnemonic and seed follow BIP39, and the child keys are derived by SLIP-0010,
+//! which only supports hardened derivation for ed25519, so every level of the path is
+//! treated as hardened, whether it is marked with `'` or not.
+
+use anyhow::{bail, format_err, Result};
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// The registered coin type of Starcoin in BIP44.
+pub const STARCOIN_COIN_TYPE: u32 = 101010;
+pub const DEFAULT_MNEMONIC_WORD_COUNT: usize = 12;
+
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// Generate a new random english mnemonic with `word_count` words.
+pub fn generate_mnemonic(word_count: usize) -> Result<String> {
+    let mnemonic_type = MnemonicType::for_word_count(word_count)?;
+    Ok(Mnemonic::new(mnemonic_type, Language::English)
+        .phrase()
+        .to_string())
+}
+
+/// A BIP32 style derivation path, such as `m/44'/101010'/0'/0'/0'`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The BIP44 path of account `index`: `m/44'/101010'/0'/0'/{index}'`
+    pub fn bip44(index: u32) -> Self {
+        Self(vec![44, STARCOIN_COIN_TYPE, 0, 0, index])
+    }
+
+    pub fn indexes(&self) -> &[u32] {
+        self.0.as_slice()
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            bail!("Derivation path should start with `m`, got: {}", s);
+        }
+        let indexes = parts
+            .map(|part| {
+                let index = part.trim_end_matches('\'').trim_end_matches('h');
+                let index = u32::from_str(index)
+                    .map_err(|e| format_err!("Invalid derivation path index {}: {}", part, e))?;
+                if index >= HARDENED_OFFSET {
+                    bail!("Derivation path index {} is too large", part);
+                }
+                Ok(index)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self(indexes))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Derive the ed25519 private key from the english `mnemonic` by `path`.
+pub fn derive_private_key(mnemonic: &str, path: &DerivationPath) -> Result<Ed25519PrivateKey> {
+    derive_private_key_from_seed(mnemonic_to_seed(mnemonic)?.as_slice(), path)
+}
+
+/// Convert the english `mnemonic` to the BIP39 seed, it is slow, derive the keys of
+/// multi paths from the same seed by `derive_private_key_from_seed`.
+pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Vec<u8>> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic.trim(), Language::English)?;
+    Ok(Seed::new(&mnemonic, "").as_bytes().to_vec())
+}
+
+/// Derive the ed25519 private key from the BIP39 `seed` by `path`, follow SLIP-0010.
+pub fn derive_private_key_from_seed(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Ed25519PrivateKey> {
+    let (mut key, mut chain_code) = hmac_sha512(ED25519_SEED_KEY, seed)?;
+    for index in path.indexes() {
+        let mut data = Vec::with_capacity(37);
+        data.push(0u8);
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&(index | HARDENED_OFFSET).to_be_bytes());
+        let (child_key, child_chain_code) = hmac_sha512(&chain_code, &data)?;
+        key = child_key;
+        chain_code = child_chain_code;
+    }
+    Ok(Ed25519PrivateKey::try_from(&key[..])?)
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Result<([u8; 32], [u8; 32])> {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).map_err(|e| format_err!("{:?}", e))?;
+    mac.update(data);
+    let output = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    Ok((left, right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::ValidCryptoMaterial;
+
+    #[test]
+    fn test_derivation_path() {
+        let path = DerivationPath::from_str("m/44'/101010'/0'/0/3").unwrap();
+        assert_eq!(path, DerivationPath::bip44(3));
+        assert_eq!(path.to_string(), "m/44'/101010'/0'/0'/3'");
+        assert!(DerivationPath::from_str("44'/101010'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+    }
+
+    #[test]
+    fn test_slip10_vector() {
+        // SLIP-0010 test vector 1 for ed25519.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let key = derive_private_key_from_seed(&seed, &DerivationPath(vec![])).unwrap();
+        assert_eq!(
+            hex::encode(key.to_bytes()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        let key =
+            derive_private_key_from_seed(&seed, &DerivationPath(vec![0, 1, 2, 2, 1000000000]))
+                .unwrap();
+        assert_eq!(
+            hex::encode(key.to_bytes()),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+    }
+
+    #[test]
+    fn test_derive_from_mnemonic() {
+        let mnemonic = generate_mnemonic(DEFAULT_MNEMONIC_WORD_COUNT).unwrap();
+        let key1 = derive_private_key(&mnemonic, &DerivationPath::bip44(0)).unwrap();
+        let key2 = derive_private_key(&mnemonic, &DerivationPath::bip44(0)).unwrap();
+        let key3 = derive_private_key(&mnemonic, &DerivationPath::bip44(1)).unwrap();
+        assert_eq!(key1.to_bytes(), key2.to_bytes());
+        assert_ne!(key1.to_bytes(), key3.to_bytes());
+        assert!(derive_private_key("invalid mnemonic", &DerivationPath::bip44(0)).is_err());
+
+        let seed = mnemonic_to_seed(&mnemonic).unwrap();
+        let key4 = derive_private_key_from_seed(&seed, &DerivationPath::bip44(1)).unwrap();
+        assert_eq!(key3.to_bytes(), key4.to_bytes());
+    }
+}
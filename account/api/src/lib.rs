@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod error;
+pub mod hd;
 pub mod message;
 mod rich_wallet;
 mod service;
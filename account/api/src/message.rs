@@ -1,8 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::AccountInfo;
+use crate::{AccountInfo, AccountMetadataUpdate, KeystoreEntry, PendingTxnApproval};
 use anyhow::Result;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
@@ -30,7 +31,8 @@ pub enum AccountRequest {
     AccountAcceptedTokens {
         address: AccountAddress,
     },
-    UnlockAccount(AccountAddress, String, Duration),
+    /// Unlock the account for the duration, the session can spend at most `max_amount` nanoSTC if it is some.
+    UnlockAccount(AccountAddress, String, Duration, Option<u128>),
     LockAccount(AccountAddress),
     ImportAccount {
         address: AccountAddress,
@@ -45,10 +47,30 @@ pub enum AccountRequest {
         address: AccountAddress,
         password: String,
     },
+    DeriveAccount {
+        mnemonic: String,
+        path: String,
+        password: String,
+    },
+    RestoreAccounts {
+        mnemonic: String,
+        password: String,
+        count: u32,
+    },
+    ExportKeystore,
+    ImportKeystore(Vec<KeystoreEntry>),
     ChangePassword {
         address: AccountAddress,
         new_password: String,
     },
+    UpdateAccountMetadata {
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    },
+    /// Approve the txn the sign policy asks for, by the approval hash of the raw txn.
+    ApproveTxn(HashValue),
+    /// The txns waiting for approval of the sign policy.
+    PendingTxnApprovals,
 }
 
 impl ServiceRequest for AccountRequest {
@@ -63,7 +85,9 @@ pub enum AccountResponse {
     SignedTxn(Box<SignedUserTransaction>),
     UnlockAccountResponse,
     ExportAccountResponse(Vec<u8>),
+    Keystore(Vec<KeystoreEntry>),
     AcceptedTokens(Vec<TokenCode>),
     MessageSignature(Box<AccountSignature>),
+    PendingTxnApprovals(Vec<PendingTxnApproval>),
     None,
 }
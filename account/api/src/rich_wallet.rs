@@ -1,3 +1,4 @@
+use crate::AccountMetadata;
 use anyhow::Result;
 use futures::Stream;
 use serde::Deserialize;
@@ -59,6 +60,9 @@ pub struct Setting {
     pub is_default: bool,
     /// this account is readonly
     pub is_readonly: bool,
+    /// notes and tags of this account.
+    #[serde(default)]
+    pub metadata: AccountMetadata,
 }
 
 impl Setting {
@@ -69,6 +73,7 @@ impl Setting {
             default_gas_token: STC_TOKEN_CODE.clone(),
             is_default: false,
             is_readonly: false,
+            metadata: AccountMetadata::default(),
         }
     }
 
@@ -79,6 +84,7 @@ impl Setting {
             default_gas_token: STC_TOKEN_CODE.clone(),
             is_default: false,
             is_readonly: true,
+            metadata: AccountMetadata::default(),
         }
     }
 }
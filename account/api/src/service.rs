@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::message::{AccountRequest, AccountResponse};
-use crate::AccountInfo;
+use crate::{AccountInfo, AccountMetadataUpdate, KeystoreEntry, PendingTxnApproval};
 use anyhow::Result;
 use starcoin_crypto::multi_ed25519::MultiEd25519Signature;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
@@ -42,6 +43,14 @@ pub trait AccountAsyncService:
         password: String,
         duration: std::time::Duration,
     ) -> Result<AccountInfo>;
+    /// Unlock the account for `duration`, the txns signed in the session can spend at most `max_amount` nanoSTC.
+    async fn unlock_account_with_limit(
+        &self,
+        address: AccountAddress,
+        password: String,
+        duration: std::time::Duration,
+        max_amount: u128,
+    ) -> Result<AccountInfo>;
     async fn lock_account(&self, address: AccountAddress) -> Result<AccountInfo>;
     async fn import_account(
         &self,
@@ -59,6 +68,28 @@ pub trait AccountAsyncService:
     /// Return the private key as bytes for `address`
     async fn export_account(&self, address: AccountAddress, password: String) -> Result<Vec<u8>>;
 
+    /// Derive an account from mnemonic by the hd path, such as `m/44'/101010'/0'/0'/0'`.
+    async fn derive_account(
+        &self,
+        mnemonic: String,
+        path: String,
+        password: String,
+    ) -> Result<AccountInfo>;
+
+    /// Restore the first `count` BIP44 accounts derived from mnemonic.
+    async fn restore_accounts(
+        &self,
+        mnemonic: String,
+        password: String,
+        count: u32,
+    ) -> Result<Vec<AccountInfo>>;
+
+    /// Export all accounts of the wallet, the private keys are kept encrypted by the account passwords.
+    async fn export_keystore(&self) -> Result<Vec<KeystoreEntry>>;
+
+    /// Import the accounts exported by `export_keystore`, the exist accounts are kept.
+    async fn import_keystore(&self, entries: Vec<KeystoreEntry>) -> Result<Vec<AccountInfo>>;
+
     async fn accepted_tokens(&self, address: AccountAddress) -> Result<Vec<TokenCode>>;
 
     /// change account password, user need to unlock account first.
@@ -73,6 +104,19 @@ pub trait AccountAsyncService:
         address: AccountAddress,
         password: Option<String>,
     ) -> Result<AccountInfo>;
+
+    /// Update the notes and tags of the account.
+    async fn update_account_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> Result<AccountInfo>;
+
+    /// Approve the txn the sign policy asks for by its approval hash, so it is signed next time.
+    async fn approve_txn(&self, approval_hash: HashValue) -> Result<()>;
+
+    /// The txns waiting for approval of the sign policy.
+    async fn pending_txn_approvals(&self) -> Result<Vec<PendingTxnApproval>>;
 }
 
 #[async_trait::async_trait]
@@ -181,7 +225,31 @@ where
         duration: std::time::Duration,
     ) -> Result<AccountInfo> {
         let response = self
-            .send(AccountRequest::UnlockAccount(address, password, duration))
+            .send(AccountRequest::UnlockAccount(
+                address, password, duration, None,
+            ))
+            .await??;
+        if let AccountResponse::AccountInfo(account_info) = response {
+            Ok(*account_info)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn unlock_account_with_limit(
+        &self,
+        address: AccountAddress,
+        password: String,
+        duration: std::time::Duration,
+        max_amount: u128,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::UnlockAccount(
+                address,
+                password,
+                duration,
+                Some(max_amount),
+            ))
             .await??;
         if let AccountResponse::AccountInfo(account_info) = response {
             Ok(*account_info)
@@ -248,6 +316,64 @@ where
         }
     }
 
+    async fn derive_account(
+        &self,
+        mnemonic: String,
+        path: String,
+        password: String,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::DeriveAccount {
+                mnemonic,
+                path,
+                password,
+            })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn restore_accounts(
+        &self,
+        mnemonic: String,
+        password: String,
+        count: u32,
+    ) -> Result<Vec<AccountInfo>> {
+        let response = self
+            .send(AccountRequest::RestoreAccounts {
+                mnemonic,
+                password,
+                count,
+            })
+            .await??;
+        if let AccountResponse::AccountList(accounts) = response {
+            Ok(accounts)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn export_keystore(&self) -> Result<Vec<KeystoreEntry>> {
+        let response = self.send(AccountRequest::ExportKeystore).await??;
+        if let AccountResponse::Keystore(entries) = response {
+            Ok(entries)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn import_keystore(&self, entries: Vec<KeystoreEntry>) -> Result<Vec<AccountInfo>> {
+        let response = self.send(AccountRequest::ImportKeystore(entries)).await??;
+        if let AccountResponse::AccountList(accounts) = response {
+            Ok(accounts)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn accepted_tokens(&self, address: AccountAddress) -> Result<Vec<TokenCode>> {
         let response = self
             .send(AccountRequest::AccountAcceptedTokens { address })
@@ -291,4 +417,39 @@ where
             panic!("Unexpect response type.")
         }
     }
+
+    async fn update_account_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::UpdateAccountMetadata { address, update })
+            .await??;
+        if let AccountResponse::AccountInfo(account_info) = response {
+            Ok(*account_info)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn approve_txn(&self, approval_hash: HashValue) -> Result<()> {
+        let response = self
+            .send(AccountRequest::ApproveTxn(approval_hash))
+            .await??;
+        if let AccountResponse::None = response {
+            Ok(())
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn pending_txn_approvals(&self) -> Result<Vec<PendingTxnApproval>> {
+        let response = self.send(AccountRequest::PendingTxnApprovals).await??;
+        if let AccountResponse::PendingTxnApprovals(approvals) = response {
+            Ok(approvals)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
 }
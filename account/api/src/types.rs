@@ -1,17 +1,24 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::keygen::KeyGen;
+use starcoin_crypto::HashValue;
 use starcoin_types::{
     account_address::{self, AccountAddress},
+    genesis_config::ChainId,
     transaction::authenticator::AuthenticationKey,
 };
 
+use crate::Setting;
+use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::receipt_identifier::ReceiptIdentifier;
 pub use starcoin_types::transaction::authenticator::{
     AccountPrivateKey, AccountPublicKey, AccountSignature,
 };
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccountInfo {
@@ -22,6 +29,9 @@ pub struct AccountInfo {
     pub is_readonly: bool,
     pub public_key: AccountPublicKey,
     pub receipt_identifier: ReceiptIdentifier,
+    /// Notes and tags of this account, only kept in local wallet.
+    #[serde(default)]
+    pub metadata: AccountMetadata,
 }
 
 impl AccountInfo {
@@ -38,9 +48,19 @@ impl AccountInfo {
             is_default,
             is_readonly,
             receipt_identifier: ReceiptIdentifier::V1(address, Some(auth_key)),
+            metadata: AccountMetadata::default(),
         }
     }
 
+    pub fn with_metadata(mut self, metadata: AccountMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.metadata.tags.iter().any(|t| t == tag.trim())
+    }
+
     pub fn auth_key(&self) -> AuthenticationKey {
         self.public_key.authentication_key()
     }
@@ -61,10 +81,123 @@ impl AccountInfo {
             is_readonly: false,
             public_key: account_public_key,
             receipt_identifier: ReceiptIdentifier::V1(address, Some(auth_key)),
+            metadata: AccountMetadata::default(),
+        }
+    }
+}
+
+/// The notes and tags attached to an account, such as tag `exchange` or `payroll`,
+/// to keep the context of operational accounts in wallet.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMetadata {
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// A transfer of a token whose amount is above the threshold of the token requires interactive
+    /// confirmation in cli, even if the account is unlocked. The key is the token code, such as
+    /// `0x1::STC::STC`, the threshold is in the smallest unit of the token.
+    #[serde(default)]
+    pub transfer_confirm_thresholds: BTreeMap<String, u128>,
+    /// The chain id of the network the account is saved on, the account is refused on other networks.
+    /// None for the accounts saved before the chain id is recorded.
+    #[serde(default)]
+    pub chain_id: Option<ChainId>,
+}
+
+impl AccountMetadata {
+    /// The confirm threshold of the transfers of the token, None if the token has no threshold.
+    pub fn transfer_confirm_threshold(&self, token_code: &TokenCode) -> Option<u128> {
+        self.transfer_confirm_thresholds
+            .get(&token_code.to_string())
+            .copied()
+    }
+
+    /// Apply the update, the tags are kept sorted and deduplicated.
+    pub fn apply(&mut self, update: AccountMetadataUpdate) -> Result<()> {
+        if let Some(notes) = update.notes {
+            let notes = notes.trim();
+            self.notes = if notes.is_empty() {
+                None
+            } else {
+                Some(notes.to_string())
+            };
+        }
+        for tag in update.add_tags {
+            let tag = tag.trim();
+            ensure!(!tag.is_empty(), "Account tag should not be empty");
+            ensure!(
+                !tag.chars().any(char::is_whitespace),
+                "Account tag should not contain whitespace: {}",
+                tag
+            );
+            self.tags.push(tag.to_string());
+        }
+        self.tags
+            .retain(|tag| !update.remove_tags.iter().any(|t| t.trim() == tag));
+        self.tags.sort();
+        self.tags.dedup();
+        for (token_code, threshold) in update.transfer_confirm_thresholds {
+            // normalize the token code, so `0x1::STC::STC` and the full address are the same key.
+            let token_code = TokenCode::from_str(token_code.trim())?.to_string();
+            if threshold == 0 {
+                self.transfer_confirm_thresholds.remove(&token_code);
+            } else {
+                self.transfer_confirm_thresholds
+                    .insert(token_code, threshold);
+            }
         }
+        Ok(())
     }
 }
 
+/// The update of account metadata, the notes is kept if it is None, and cleared if it is empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMetadataUpdate {
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+    /// The confirm thresholds of the tokens to set, the threshold of a token is cleared if it is zero,
+    /// the thresholds of the other tokens are kept.
+    #[serde(default)]
+    pub transfer_confirm_thresholds: BTreeMap<String, u128>,
+}
+
+impl From<AccountMetadata> for AccountMetadataUpdate {
+    fn from(metadata: AccountMetadata) -> Self {
+        Self {
+            notes: metadata.notes,
+            add_tags: metadata.tags,
+            remove_tags: vec![],
+            transfer_confirm_thresholds: metadata.transfer_confirm_thresholds,
+        }
+    }
+}
+
+/// An account of the local wallet in the keystore backup.
+/// A txn the sign policy asks the node operator to approve before signing it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTxnApproval {
+    /// The hash to approve the txn by, the sequence number and the expiration are excluded.
+    pub approval_hash: HashValue,
+    /// The hash of the raw txn which asked for the approval.
+    pub txn_hash: HashValue,
+    pub sender: AccountAddress,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub address: AccountAddress,
+    pub public_key: AccountPublicKey,
+    /// The private key encrypted by the account password, none for the readonly account.
+    pub encrypted_private_key: Option<Vec<u8>>,
+    pub setting: Setting,
+    pub accepted_tokens: Vec<TokenCode>,
+}
+
 #[derive(Clone, Debug)]
 pub struct DefaultAccountChangeEvent {
     pub new_account: AccountInfo,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use starcoin_account::sign_policy::SignPolicy;
 use starcoin_account::{account_storage::AccountStorage, AccountManager};
 use starcoin_account_api::message::{AccountRequest, AccountResponse};
 use starcoin_account_api::DefaultAccountChangeEvent;
@@ -91,7 +92,20 @@ impl ActorService for AccountService {
 impl ServiceFactory<AccountService> for AccountService {
     fn create(ctx: &mut ServiceContext<AccountService>) -> Result<AccountService> {
         let account_storage = ctx.get_shared::<AccountStorage>()?;
-        let manager = AccountManager::new(account_storage)?;
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let sign_policy = match config.vault.sign_policy() {
+            Some(path) => {
+                let sign_policy = SignPolicy::load(path.as_path())?;
+                info!("Load the sign policy from {:?}", path);
+                Some(sign_policy)
+            }
+            None => None,
+        };
+        let manager = AccountManager::new(account_storage)?
+            .with_unlock_idle_timeout(config.vault.unlock_idle_timeout())
+            .with_allow_unknown_spend(config.vault.allow_unknown_spend())
+            .with_sign_policy(sign_policy)
+            .with_chain_id(Some(config.net().chain_id()));
         Ok(Self { manager })
     }
 }
@@ -142,10 +156,13 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             AccountRequest::SignMessage { message, signer } => AccountResponse::MessageSignature(
                 Box::new(self.manager.sign_message(signer, message)?),
             ),
-            AccountRequest::UnlockAccount(address, password, duration) => {
-                let account_info =
-                    self.manager
-                        .unlock_account(address, password.as_str(), duration)?;
+            AccountRequest::UnlockAccount(address, password, duration, max_amount) => {
+                let account_info = self.manager.unlock_account_with_limit(
+                    address,
+                    password.as_str(),
+                    duration,
+                    max_amount,
+                )?;
                 AccountResponse::AccountInfo(Box::new(account_info))
             }
             AccountRequest::LockAccount(address) => {
@@ -165,6 +182,33 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
                         .import_account(address, private_key, password.as_str())?;
                 AccountResponse::AccountInfo(Box::new(account.info()))
             }
+            AccountRequest::DeriveAccount {
+                mnemonic,
+                path,
+                password,
+            } => {
+                let account = self.manager.derive_account(
+                    mnemonic.as_str(),
+                    path.as_str(),
+                    password.as_str(),
+                )?;
+                AccountResponse::AccountInfo(Box::new(account.info()))
+            }
+            AccountRequest::RestoreAccounts {
+                mnemonic,
+                password,
+                count,
+            } => AccountResponse::AccountList(self.manager.restore_accounts(
+                mnemonic.as_str(),
+                password.as_str(),
+                count,
+            )?),
+            AccountRequest::ExportKeystore => {
+                AccountResponse::Keystore(self.manager.export_keystore()?)
+            }
+            AccountRequest::ImportKeystore(entries) => {
+                AccountResponse::AccountList(self.manager.import_keystore(entries)?)
+            }
             AccountRequest::ImportReadonlyAccount {
                 address,
                 public_key,
@@ -186,6 +230,18 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             } => AccountResponse::AccountInfo(Box::new(
                 self.manager.change_password(address, new_password)?,
             )),
+            AccountRequest::UpdateAccountMetadata { address, update } => {
+                AccountResponse::AccountInfo(Box::new(
+                    self.manager.update_account_metadata(address, update)?,
+                ))
+            }
+            AccountRequest::ApproveTxn(approval_hash) => {
+                self.manager.approve_txn(approval_hash)?;
+                AccountResponse::None
+            }
+            AccountRequest::PendingTxnApprovals => {
+                AccountResponse::PendingTxnApprovals(self.manager.pending_txn_approvals())
+            }
         };
         Ok(response)
     }
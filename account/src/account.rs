@@ -13,6 +13,7 @@ use starcoin_logger::prelude::*;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_types::account_address;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::authenticator::{AccountSignature, AuthenticationKey};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
@@ -109,6 +110,18 @@ impl Account {
         Ok(())
     }
 
+    /// Record the chain id of the network the account is saved on.
+    pub fn set_chain_id(&mut self, chain_id: ChainId) -> Result<()> {
+        self.setting.metadata.chain_id = Some(chain_id);
+        self.store.update_setting(self.addr, self.setting.clone())?;
+        Ok(())
+    }
+
+    /// The chain id the account is saved on, None if it is not recorded.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.setting.metadata.chain_id
+    }
+
     pub fn info(&self) -> AccountInfo {
         AccountInfo::new(
             self.addr,
@@ -116,6 +129,7 @@ impl Account {
             self.setting.is_default,
             self.setting.is_readonly,
         )
+        .with_metadata(self.setting.metadata.clone())
     }
 
     pub fn sign_message(&self, message: SigningMessage) -> Result<AccountSignature> {
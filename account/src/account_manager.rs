@@ -3,24 +3,33 @@
 
 use crate::account::Account;
 use crate::account_storage::AccountStorage;
+use crate::sign_policy::{SignDecision, SignPolicy};
 use anyhow::format_err;
 use parking_lot::RwLock;
 use rand::prelude::*;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult};
+use starcoin_account_api::hd::{self, DerivationPath};
+use starcoin_account_api::{
+    AccountInfo, AccountMetadataUpdate, AccountPrivateKey, AccountPublicKey, AccountResult,
+    KeystoreEntry, PendingTxnApproval,
+};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
-use starcoin_crypto::{Uniform, ValidCryptoMaterial};
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_crypto::{HashValue, Uniform, ValidCryptoMaterial};
 use starcoin_logger::prelude::*;
+use starcoin_types::account_config::{core_code_address, stc_type_tag, STC_TOKEN_CODE_STR};
+use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::authenticator::AccountSignature;
 use starcoin_types::{
     account_address::AccountAddress,
     account_config::token_code::TokenCode,
-    transaction::{RawUserTransaction, SignedUserTransaction},
+    transaction::{RawUserTransaction, SignedUserTransaction, TransactionPayload},
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::Add;
+use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -28,37 +37,242 @@ use std::time::Instant;
 pub struct AccountManager {
     store: AccountStorage,
     key_cache: RwLock<PasswordCache>,
+    sign_policy: Option<SignPolicy>,
+    txn_approvals: RwLock<TxnApprovals>,
+    chain_id: Option<ChainId>,
+}
+
+/// The max count of the txns waiting for approval, and of the approved txns not signed yet.
+const MAX_TXN_APPROVALS: usize = 256;
+/// The txns waiting for approval and the approvals not used are dropped after the ttl.
+const TXN_APPROVAL_TTL: Duration = Duration::from_secs(3600);
+/// The max count of the accounts restored from a mnemonic at once.
+pub const MAX_RESTORE_ACCOUNTS: u32 = 1000;
+
+/// The hash to approve a txn the sign policy asks for, the sequence number and the expiration
+/// are excluded, so the approved txn can be rebuilt by the wallet with a new sequence number
+/// or expiration and signed again.
+pub fn txn_approval_hash(raw_txn: &RawUserTransaction) -> HashValue {
+    let approval = (
+        raw_txn.sender(),
+        raw_txn.payload(),
+        raw_txn.max_gas_amount(),
+        raw_txn.gas_unit_price(),
+        raw_txn.gas_token_code(),
+        raw_txn.chain_id(),
+    );
+    HashValue::sha3_256_of(
+        &bcs_ext::to_bytes(&approval).expect("Serialize the txn approval should success."),
+    )
+}
+
+/// The txns the sign policy asks to approve, by the approval hash of the raw txn.
+#[derive(Default, Debug)]
+struct TxnApprovals {
+    /// The txns waiting for approval, with the time of the asking.
+    pending: HashMap<HashValue, (PendingTxnApproval, Instant)>,
+    /// The approved txns with the time of the approval, an approval is used by one signing.
+    approved: HashMap<HashValue, Instant>,
+}
+
+impl TxnApprovals {
+    fn remove_expired(&mut self, now: Instant) {
+        self.pending
+            .retain(|_, (_, asked_at)| now.duration_since(*asked_at) < TXN_APPROVAL_TTL);
+        self.approved
+            .retain(|_, approved_at| now.duration_since(*approved_at) < TXN_APPROVAL_TTL);
+    }
+
+    fn ask(&mut self, approval: PendingTxnApproval, now: Instant) {
+        let approval_hash = approval.approval_hash;
+        self.remove_expired(now);
+        if !self.pending.contains_key(&approval_hash) && self.pending.len() >= MAX_TXN_APPROVALS {
+            if let Some(oldest) = self
+                .pending
+                .iter()
+                .min_by_key(|(_, (_, asked_at))| *asked_at)
+                .map(|(hash, _)| *hash)
+            {
+                self.pending.remove(&oldest);
+            }
+        }
+        self.pending.insert(approval_hash, (approval, now));
+    }
+
+    fn approve(&mut self, approval_hash: HashValue, now: Instant) -> bool {
+        self.remove_expired(now);
+        if self.pending.remove(&approval_hash).is_none() {
+            return false;
+        }
+        if self.approved.len() >= MAX_TXN_APPROVALS {
+            if let Some(oldest) = self
+                .approved
+                .iter()
+                .min_by_key(|(_, approved_at)| *approved_at)
+                .map(|(hash, _)| *hash)
+            {
+                self.approved.remove(&oldest);
+            }
+        }
+        self.approved.insert(approval_hash, now);
+        true
+    }
+
+    /// Use the approval of the txn, return false if the txn is not approved.
+    fn take_approval(&mut self, approval_hash: &HashValue, now: Instant) -> bool {
+        self.remove_expired(now);
+        self.approved.remove(approval_hash).is_some()
+    }
+}
+
+/// The unlock session of an account.
+#[derive(Debug, PartialEq, Eq)]
+struct UnlockSession {
+    password: String,
+    ttl: Instant,
+    last_active: Instant,
+    /// The max amount(in nanoSTC) the txns signed in the session can spend, unlimited if None.
+    max_amount: Option<u128>,
+    spent: u128,
+}
+
+impl UnlockSession {
+    fn is_expired(&self, now: Instant, idle_timeout: Option<Duration>) -> bool {
+        now >= self.ttl
+            || idle_timeout
+                .map(|idle_timeout| now.duration_since(self.last_active) >= idle_timeout)
+                .unwrap_or(false)
+    }
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
 struct PasswordCache {
-    cache: HashMap<AccountAddress, (Instant, String)>,
+    cache: HashMap<AccountAddress, UnlockSession>,
+    /// Lock the account if it is not used for the duration.
+    idle_timeout: Option<Duration>,
+    /// Allow the spend limited sessions to sign the txns whose spend amount is unknown.
+    allow_unknown_spend: bool,
 }
 impl PasswordCache {
-    pub fn cache_pass(&mut self, account: AccountAddress, pass: String, ttl: Instant) {
-        self.cache.insert(account, (ttl, pass));
+    pub fn cache_pass(
+        &mut self,
+        account: AccountAddress,
+        pass: String,
+        ttl: Instant,
+        max_amount: Option<u128>,
+    ) {
+        self.cache.insert(
+            account,
+            UnlockSession {
+                password: pass,
+                ttl,
+                last_active: Instant::now(),
+                max_amount,
+                spent: 0,
+            },
+        );
     }
     pub fn remove_pass(&mut self, account: &AccountAddress) {
         self.cache.remove(account);
     }
     pub fn get_pass(&mut self, account: &AccountAddress) -> Option<String> {
-        match self.cache.remove(account) {
-            None => None,
-            Some((ttl, kp)) => {
-                if Instant::now() < ttl {
-                    self.cache.insert(*account, (ttl, kp));
-                    self.cache.get(account).map(|t| t.1.to_string())
-                } else {
-                    None
-                }
+        let now = Instant::now();
+        let expired = self.cache.get(account)?.is_expired(now, self.idle_timeout);
+        if expired {
+            self.cache.remove(account);
+            return None;
+        }
+        self.cache.get_mut(account).map(|session| {
+            session.last_active = now;
+            session.password.clone()
+        })
+    }
+
+    /// Record the amount spent by the account, reject it if exceeds the limit of the unlock session.
+    /// The unknown amount is treated as exceeding the limit, unless `allow_unknown_spend` is set.
+    pub fn spend(&mut self, account: &AccountAddress, amount: Option<u128>) -> AccountResult<()> {
+        let allow_unknown_spend = self.allow_unknown_spend;
+        let session = self
+            .cache
+            .get_mut(account)
+            .ok_or(AccountError::AccountLocked(*account))?;
+        if let Some(max_amount) = session.max_amount {
+            let amount = match amount {
+                Some(amount) => amount,
+                None if allow_unknown_spend => 0,
+                None => return Err(AccountError::UnknownSpendAmount(*account)),
+            };
+            let remaining = max_amount.saturating_sub(session.spent);
+            if amount > remaining {
+                return Err(AccountError::UnlockLimitExceeded {
+                    address: *account,
+                    amount,
+                    remaining,
+                });
             }
+            session.spent = session.spent.saturating_add(amount);
         }
+        Ok(())
     }
 
     #[allow(dead_code)]
     pub fn clean_expired(&mut self) {
         let cur_instant = Instant::now();
-        self.cache.retain(|_account, (ttl, _)| &cur_instant < ttl);
+        let idle_timeout = self.idle_timeout;
+        self.cache
+            .retain(|_account, session| !session.is_expired(cur_instant, idle_timeout));
+    }
+}
+
+/// The max STC amount(in nanoSTC) the txn can spend, include the max gas fee and
+/// the STC transferred by `TransferScripts`.
+/// Return None if the amount can not be known before executing, such as the scripts, the packages,
+/// other script functions, and the txns paying gas or transferring by other tokens.
+pub fn txn_spend_amount(raw_txn: &RawUserTransaction) -> Option<u128> {
+    if raw_txn.gas_token_code() != STC_TOKEN_CODE_STR {
+        return None;
+    }
+    let gas_fee =
+        u128::from(raw_txn.max_gas_amount()).saturating_mul(u128::from(raw_txn.gas_unit_price()));
+    match raw_txn.payload() {
+        TransactionPayload::ScriptFunction(script_function)
+            if script_function.module().address() == &core_code_address()
+                && script_function.module().name().as_str() == "TransferScripts"
+                && script_function.ty_args().first() == Some(&stc_type_tag()) =>
+        {
+            transfer_amount(script_function.function().as_str(), script_function.args())
+                .map(|amount| amount.saturating_add(gas_fee))
+        }
+        _ => None,
+    }
+}
+
+/// Decode the total transfer amount from the args of `TransferScripts` function,
+/// return None if the function is unknown or the args can not be decoded.
+fn transfer_amount(function: &str, args: &[Vec<u8>]) -> Option<u128> {
+    let decode_u128 =
+        |index: usize| -> Option<u128> { bcs_ext::from_bytes::<u128>(args.get(index)?).ok() };
+    let decode_u128_vec = |index: usize| -> Option<u128> {
+        bcs_ext::from_bytes::<Vec<u128>>(args.get(index)?)
+            .ok()
+            .map(|amounts| {
+                amounts
+                    .into_iter()
+                    .fold(0u128, |sum, amount| sum.saturating_add(amount))
+            })
+    };
+    match function {
+        "peer_to_peer" | "peer_to_peer_with_metadata" => decode_u128(2),
+        "peer_to_peer_v2" | "peer_to_peer_with_metadata_v2" => decode_u128(1),
+        "batch_peer_to_peer" => decode_u128_vec(2),
+        "batch_peer_to_peer_v2" => decode_u128_vec(1),
+        "peer_to_peer_batch" => {
+            // every payee receives the same amount, the payees are concatenated address bytes.
+            let payees =
+                bcs_ext::from_bytes::<Vec<u8>>(args.get(0)?).ok()?.len() / AccountAddress::LENGTH;
+            decode_u128(2).map(|amount| amount.saturating_mul(payees as u128))
+        }
+        _ => None,
     }
 }
 
@@ -67,10 +281,38 @@ impl AccountManager {
         let manager = Self {
             store: storage,
             key_cache: RwLock::new(PasswordCache::default()),
+            sign_policy: None,
+            txn_approvals: RwLock::new(TxnApprovals::default()),
+            chain_id: None,
         };
         Ok(manager)
     }
 
+    /// Record the chain id with the saved accounts, and refuse the accounts saved on other chains.
+    pub fn with_chain_id(mut self, chain_id: Option<ChainId>) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Check the txns by the sign policy before signing them.
+    pub fn with_sign_policy(mut self, sign_policy: Option<SignPolicy>) -> Self {
+        self.sign_policy = sign_policy;
+        self
+    }
+
+    /// Lock the unlocked account after it is not used for `idle_timeout`.
+    pub fn with_unlock_idle_timeout(self, idle_timeout: Option<Duration>) -> Self {
+        self.key_cache.write().idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Allow the spend limited unlock sessions to sign the txns whose spend amount is unknown,
+    /// the unknown amount is not counted by the sessions.
+    pub fn with_allow_unknown_spend(self, allow_unknown_spend: bool) -> Self {
+        self.key_cache.write().allow_unknown_spend = allow_unknown_spend;
+        self
+    }
+
     pub fn create_account(&self, password: &str) -> AccountResult<Account> {
         let private_key = gen_private_key();
         let private_key = AccountPrivateKey::Single(private_key);
@@ -87,13 +329,26 @@ impl AccountManager {
         address: AccountAddress,
         password: &str,
         duration: Duration,
+    ) -> AccountResult<AccountInfo> {
+        self.unlock_account_with_limit(address, password, duration, None)
+    }
+
+    /// Unlock the account for `duration`, the txns signed in the session can spend at most
+    /// `max_amount` nanoSTC, see `txn_spend_amount`. The txns whose spend amount is unknown are rejected
+    /// by the session, unless `with_allow_unknown_spend` is set.
+    pub fn unlock_account_with_limit(
+        &self,
+        address: AccountAddress,
+        password: &str,
+        duration: Duration,
+        max_amount: Option<u128>,
     ) -> AccountResult<AccountInfo> {
         let account = Account::load(address, Some(password.to_string()), self.store.clone())?
             .ok_or(AccountError::AccountNotExist(address))?;
         let ttl = std::time::Instant::now().add(duration);
         self.key_cache
             .write()
-            .cache_pass(address, password.to_string(), ttl);
+            .cache_pass(address, password.to_string(), ttl, max_amount);
         Ok(account.info())
     }
 
@@ -130,6 +385,141 @@ impl AccountManager {
         self.save_account(address, public_key, None)
     }
 
+    /// Derive the account from `mnemonic` by the hd `path`, and save it with `password`.
+    pub fn derive_account(
+        &self,
+        mnemonic: &str,
+        path: &str,
+        password: &str,
+    ) -> AccountResult<Account> {
+        let path = DerivationPath::from_str(path).map_err(AccountError::InvalidMnemonic)?;
+        let private_key =
+            hd::derive_private_key(mnemonic, &path).map_err(AccountError::InvalidMnemonic)?;
+        let private_key = AccountPrivateKey::Single(private_key);
+        let address = private_key.public_key().derived_address();
+        self.save_account(
+            address,
+            private_key.public_key(),
+            Some((private_key, password.to_string())),
+        )
+    }
+
+    /// Restore the first `count` BIP44 accounts derived from `mnemonic`, `count` is at most `MAX_RESTORE_ACCOUNTS`,
+    /// the accounts already exist in wallet are kept as it is.
+    pub fn restore_accounts(
+        &self,
+        mnemonic: &str,
+        password: &str,
+        count: u32,
+    ) -> AccountResult<Vec<AccountInfo>> {
+        if count > MAX_RESTORE_ACCOUNTS {
+            return Err(AccountError::RestoreCountExceeded {
+                count,
+                max: MAX_RESTORE_ACCOUNTS,
+            });
+        }
+        let seed = hd::mnemonic_to_seed(mnemonic).map_err(AccountError::InvalidMnemonic)?;
+        let mut account_infos = vec![];
+        for index in 0..count {
+            let private_key =
+                hd::derive_private_key_from_seed(seed.as_slice(), &DerivationPath::bip44(index))
+                    .map_err(AccountError::InvalidMnemonic)?;
+            let private_key = AccountPrivateKey::Single(private_key);
+            let address = private_key.public_key().derived_address();
+            let account_info = match self.account_info(address)? {
+                Some(account_info) => account_info,
+                None => self
+                    .save_account(
+                        address,
+                        private_key.public_key(),
+                        Some((private_key, password.to_string())),
+                    )?
+                    .info(),
+            };
+            account_infos.push(account_info);
+        }
+        Ok(account_infos)
+    }
+
+    /// Export all accounts of the wallet, the private keys are kept encrypted by the account passwords.
+    pub fn export_keystore(&self) -> AccountResult<Vec<KeystoreEntry>> {
+        let mut entries = vec![];
+        for address in self.store.list_addresses()? {
+            let public_key = match self.store.public_key(address)? {
+                Some(public_key) => public_key,
+                None => continue,
+            };
+            entries.push(KeystoreEntry {
+                address,
+                public_key,
+                encrypted_private_key: self.store.encrypted_private_key(address)?,
+                setting: self.store.load_setting(address)?,
+                accepted_tokens: self.store.get_accepted_tokens(address)?,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Import the accounts exported by `export_keystore`, they keep the passwords of the exported wallet.
+    /// The accounts already exist in wallet are kept as it is.
+    pub fn import_keystore(&self, entries: Vec<KeystoreEntry>) -> AccountResult<Vec<AccountInfo>> {
+        let mut account_infos = Vec::with_capacity(entries.len());
+        let mut backup_default = None;
+        for entry in entries {
+            let address = entry.address;
+            if entry.public_key.derived_address() != address {
+                return Err(AccountError::InvalidKeystore(format_err!(
+                    "the public key of account {} does not derive its address",
+                    address
+                )));
+            }
+            if entry.setting.is_default {
+                backup_default = Some(address);
+            }
+            if let Some(account_info) = self.account_info(address)? {
+                account_infos.push(account_info);
+                continue;
+            }
+            self.check_chain_id(address, entry.setting.metadata.chain_id)?;
+            if entry.setting.is_readonly != entry.encrypted_private_key.is_none() {
+                return Err(AccountError::InvalidKeystore(format_err!(
+                    "the private key of account {} does not match its readonly setting",
+                    address
+                )));
+            }
+            if let Some(encrypted_private_key) = entry.encrypted_private_key {
+                self.store
+                    .update_encrypted_private_key(address, encrypted_private_key)?;
+            }
+            self.store
+                .update_public_key(address, entry.public_key.clone())?;
+            let mut setting = entry.setting;
+            setting.is_default = false;
+            setting.metadata.chain_id = setting.metadata.chain_id.or(self.chain_id);
+            self.store.update_setting(address, setting.clone())?;
+            for token_code in entry.accepted_tokens {
+                self.store.add_accepted_token(address, token_code)?;
+            }
+            self.store.add_address(address)?;
+            account_infos.push(
+                AccountInfo::new(address, entry.public_key, false, setting.is_readonly)
+                    .with_metadata(setting.metadata),
+            );
+        }
+        // keep the default account of the wallet, or use the default of the backup.
+        if self.store.default_address()?.is_none() {
+            if let Some(address) =
+                backup_default.or_else(|| account_infos.first().map(|a| a.address))
+            {
+                let default_info = self.set_default_account(address)?;
+                for account_info in account_infos.iter_mut() {
+                    account_info.is_default = account_info.address == default_info.address;
+                }
+            }
+        }
+        Ok(account_infos)
+    }
+
     fn save_account(
         &self,
         address: AccountAddress,
@@ -145,6 +535,9 @@ impl AccountManager {
             }
             None => Account::create_readonly(address, public_key, self.store.clone())?,
         };
+        if let Some(chain_id) = self.chain_id {
+            account.set_chain_id(chain_id)?;
+        }
 
         self.store.add_address(*account.address())?;
 
@@ -203,12 +596,10 @@ impl AccountManager {
         match self.store.public_key(address)? {
             Some(p) => {
                 let setting = self.store.load_setting(address)?;
-                Ok(Some(AccountInfo::new(
-                    address,
-                    p,
-                    setting.is_default,
-                    setting.is_readonly,
-                )))
+                Ok(Some(
+                    AccountInfo::new(address, p, setting.is_default, setting.is_readonly)
+                        .with_metadata(setting.metadata),
+                ))
             }
             None => Ok(None),
         }
@@ -243,6 +634,20 @@ impl AccountManager {
             Some(p) => {
                 let account = Account::load(signer_address, Some(p), self.store.clone())?
                     .ok_or(AccountError::AccountNotExist(signer_address))?;
+                self.check_chain_id(signer_address, account.chain_id())?;
+                if let Some(account_chain_id) = account.chain_id() {
+                    if account_chain_id != raw_txn.chain_id() {
+                        return Err(AccountError::ChainIdMismatch {
+                            address: signer_address,
+                            account_chain_id,
+                            chain_id: raw_txn.chain_id(),
+                        });
+                    }
+                }
+                self.check_sign_policy(&raw_txn)?;
+                self.key_cache
+                    .write()
+                    .spend(&signer_address, txn_spend_amount(&raw_txn))?;
                 account
                     .sign_txn(raw_txn)
                     .map_err(AccountError::TransactionSignError)
@@ -250,6 +655,87 @@ impl AccountManager {
         }
     }
 
+    /// Check the chain id the account is saved on matches the chain id of the manager,
+    /// the account without recorded chain id is not checked.
+    fn check_chain_id(
+        &self,
+        address: AccountAddress,
+        account_chain_id: Option<ChainId>,
+    ) -> AccountResult<()> {
+        match (account_chain_id, self.chain_id) {
+            (Some(account_chain_id), Some(chain_id)) if account_chain_id != chain_id => {
+                Err(AccountError::ChainIdMismatch {
+                    address,
+                    account_chain_id,
+                    chain_id,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_sign_policy(&self, raw_txn: &RawUserTransaction) -> AccountResult<()> {
+        let sign_policy = match self.sign_policy.as_ref() {
+            Some(sign_policy) => sign_policy,
+            None => return Ok(()),
+        };
+        let txn_hash = raw_txn.crypto_hash();
+        let approval_hash = txn_approval_hash(raw_txn);
+        match sign_policy
+            .check(raw_txn)
+            .map_err(AccountError::SignPolicyError)?
+        {
+            SignDecision::Allow => Ok(()),
+            SignDecision::Deny(reason) => {
+                info!("Txn {} is denied by the sign policy: {}", txn_hash, reason);
+                Err(AccountError::SignPolicyDenied { txn_hash, reason })
+            }
+            SignDecision::Ask(reason) => {
+                let now = Instant::now();
+                let mut txn_approvals = self.txn_approvals.write();
+                if txn_approvals.take_approval(&approval_hash, now) {
+                    return Ok(());
+                }
+                info!(
+                    "Txn {} waits for approval {}: {}",
+                    txn_hash, approval_hash, reason
+                );
+                txn_approvals.ask(
+                    PendingTxnApproval {
+                        approval_hash,
+                        txn_hash,
+                        sender: raw_txn.sender(),
+                        reason: reason.clone(),
+                    },
+                    now,
+                );
+                // the approval hash is not returned, the txn is only approved by the node operator.
+                Err(AccountError::SignPolicyApprovalRequired { txn_hash, reason })
+            }
+        }
+    }
+
+    /// Approve the txn the sign policy asks for by the approval hash, so it is signed next time.
+    pub fn approve_txn(&self, approval_hash: HashValue) -> AccountResult<()> {
+        if !self
+            .txn_approvals
+            .write()
+            .approve(approval_hash, Instant::now())
+        {
+            return Err(AccountError::TxnNotPendingApproval(approval_hash));
+        }
+        Ok(())
+    }
+
+    /// The txns waiting for approval, the oldest first.
+    pub fn pending_txn_approvals(&self) -> Vec<PendingTxnApproval> {
+        let mut txn_approvals = self.txn_approvals.write();
+        txn_approvals.remove_expired(Instant::now());
+        let mut pending: Vec<_> = txn_approvals.pending.values().cloned().collect();
+        pending.sort_by_key(|(_, asked_at)| *asked_at);
+        pending.into_iter().map(|(approval, _)| approval).collect()
+    }
+
     pub fn set_default_account(&self, address: AccountAddress) -> AccountResult<AccountInfo> {
         let mut account_info = self
             .account_info(address)?
@@ -342,6 +828,26 @@ impl AccountManager {
         }
     }
 
+    /// Update the notes and tags of the account.
+    pub fn update_account_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> AccountResult<AccountInfo> {
+        let account_info = self
+            .account_info(address)?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        let mut setting = self.store.load_setting(address)?;
+        self.check_chain_id(address, setting.metadata.chain_id)?;
+        setting
+            .metadata
+            .apply(update)
+            .map_err(AccountError::InvalidMetadata)?;
+        setting.metadata.chain_id = setting.metadata.chain_id.or(self.chain_id);
+        self.store.update_setting(address, setting.clone())?;
+        Ok(account_info.with_metadata(setting.metadata))
+    }
+
     pub fn accepted_tokens(&self, address: AccountAddress) -> AccountResult<Vec<TokenCode>> {
         self.store
             .get_accepted_tokens(address)
@@ -308,6 +308,22 @@ impl AccountStorage {
         }
     }
 
+    /// The private key encrypted by the account password, as it is stored.
+    pub fn encrypted_private_key(&self, address: AccountAddress) -> Result<Option<Vec<u8>>> {
+        self.private_key_store
+            .get(address.into())
+            .map(|key| key.map(|key| key.0))
+    }
+
+    pub fn update_encrypted_private_key(
+        &self,
+        address: AccountAddress,
+        encrypted_private_key: Vec<u8>,
+    ) -> Result<()> {
+        self.private_key_store
+            .put(address.into(), encrypted_private_key.into())
+    }
+
     pub fn update_public_key(
         &self,
         address: AccountAddress,
@@ -1,23 +1,30 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_storage::AccountStorage;
+use crate::account_storage::{AccountStorage, GLOBAL_PREFIX_NAME};
+use crate::sign_policy::SignPolicy;
 use crate::Account;
-use crate::AccountManager;
+use crate::{txn_approval_hash, txn_spend_amount, AccountManager};
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::AccountPublicKey;
+use starcoin_account_api::hd;
+use starcoin_account_api::{AccountMetadataUpdate, AccountPublicKey};
 use starcoin_config::RocksdbConfig;
+use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
+use starcoin_storage::fault_injection::FaultInjector;
+use starcoin_storage::storage::StorageInstance;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{core_code_address, stc_type_tag, STC_TOKEN_CODE};
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::identifier::{IdentStr, Identifier};
-use starcoin_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use starcoin_types::language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS};
 use starcoin_types::transaction::{
-    RawUserTransaction, Script, SignedUserTransaction, TransactionPayload,
+    RawUserTransaction, Script, ScriptFunction, SignedUserTransaction, TransactionPayload,
 };
+use std::sync::Arc;
 use std::time::Duration;
 
 #[test]
@@ -43,6 +50,98 @@ pub fn test_import_account() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_keystore_recover_from_write_failure() -> Result<()> {
+    let injector = Arc::new(FaultInjector::new());
+    let storage = AccountStorage::new(StorageInstance::new_fault_injection_instance(
+        StorageInstance::new_cache_instance(),
+        injector.clone(),
+    ));
+    let manager = AccountManager::new(storage)?;
+
+    injector.fail_writes(0, 1);
+    assert!(manager.create_account("hello").is_err());
+    assert!(manager.list_account_infos()?.is_empty());
+
+    // the keys are saved, but adding the address fails.
+    injector.only_prefix(Some(GLOBAL_PREFIX_NAME));
+    injector.fail_writes(0, 1);
+    let address = AccountAddress::random();
+    let private_key = super::account_manager::gen_private_key();
+    assert!(manager
+        .import_account(address, private_key.to_bytes().to_vec(), "abc")
+        .is_err());
+    assert!(manager.list_account_infos()?.is_empty());
+    assert_eq!(injector.injected_faults(), 2);
+
+    // the keys left by the failed save are not overwritten, even the address is not listed.
+    let other_private_key = super::account_manager::gen_private_key();
+    let result = manager.import_account(address, other_private_key.to_bytes().to_vec(), "def");
+    assert!(matches!(result, Err(AccountError::AccountAlreadyExist(addr)) if addr == address));
+    assert!(manager.list_account_infos()?.is_empty());
+    let account = Account::load(address, Some("abc".to_string()), manager.store.clone())?.unwrap();
+    assert_eq!(
+        account.public_key(),
+        AccountPublicKey::Single((&private_key).into())
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_account_chain_id() -> Result<()> {
+    let storage = AccountStorage::mock();
+    let manager = AccountManager::new(storage.clone())?.with_chain_id(Some(ChainId::new(1)));
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    assert_eq!(wallet.info().metadata.chain_id, Some(ChainId::new(1)));
+    assert_eq!(
+        manager.account_info(address)?.unwrap().metadata.chain_id,
+        Some(ChainId::new(1))
+    );
+    let fake_txn = |chain_id: u8| {
+        RawUserTransaction::new_with_default_gas_token(
+            address,
+            1,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            1,
+            100000,
+            ChainId::new(chain_id),
+        )
+    };
+    manager.unlock_account(address, "hello", Duration::from_secs(10))?;
+    manager.sign_txn(address, fake_txn(1))?;
+    let result = manager.sign_txn(address, fake_txn(2));
+    assert!(matches!(
+        result,
+        Err(AccountError::ChainIdMismatch { account_chain_id, chain_id, .. })
+            if account_chain_id == ChainId::new(1) && chain_id == ChainId::new(2)
+    ));
+
+    // the account saved on chain 1 is refused by the wallet of chain 2.
+    let other_manager = AccountManager::new(storage)?.with_chain_id(Some(ChainId::new(2)));
+    other_manager.unlock_account(address, "hello", Duration::from_secs(10))?;
+    let result = other_manager.sign_txn(address, fake_txn(2));
+    assert!(matches!(result, Err(AccountError::ChainIdMismatch { .. })));
+    let result = other_manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            notes: Some("hot wallet".to_string()),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(result, Err(AccountError::ChainIdMismatch { .. })));
+
+    // the keystore exported on chain 1 can not be imported on chain 2.
+    let entries = manager.export_keystore()?;
+    let other_manager =
+        AccountManager::new(AccountStorage::mock())?.with_chain_id(Some(ChainId::new(2)));
+    let result = other_manager.import_keystore(entries);
+    assert!(matches!(result, Err(AccountError::ChainIdMismatch { .. })));
+    assert!(other_manager.list_account_infos()?.is_empty());
+    Ok(())
+}
+
 #[test]
 pub fn test_wallet() -> Result<()> {
     let tempdir = tempfile::tempdir()?;
@@ -142,8 +241,364 @@ pub fn test_wallet_unlock() -> Result<()> {
     Ok(())
 }
 
+fn transfer_txn(sender: AccountAddress, seq_num: u64, amount: u128) -> RawUserTransaction {
+    RawUserTransaction::new_with_default_gas_token(
+        sender,
+        seq_num,
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("TransferScripts").unwrap(),
+            ),
+            Identifier::new("peer_to_peer_v2").unwrap(),
+            vec![stc_type_tag()],
+            vec![
+                bcs_ext::to_bytes(&AccountAddress::random()).unwrap(),
+                bcs_ext::to_bytes(&amount).unwrap(),
+            ],
+        )),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    )
+}
+
+#[test]
+pub fn test_unlock_with_limit() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+
+    // max gas fee 1000 nanoSTC and transfer 100 nanoSTC.
+    assert_eq!(txn_spend_amount(&transfer_txn(address, 0, 100)), Some(1100));
+
+    manager.unlock_account_with_limit(
+        address,
+        "hello",
+        Duration::from_secs(100),
+        Some(1_000_000_000),
+    )?;
+    manager.sign_txn(address, transfer_txn(address, 0, 600_000_000))?;
+    let result = manager.sign_txn(address, transfer_txn(address, 1, 600_000_000));
+    assert!(matches!(
+        result,
+        Err(AccountError::UnlockLimitExceeded { .. })
+    ));
+    manager.sign_txn(address, transfer_txn(address, 1, 300_000_000))?;
+
+    // unlock again to reset the session.
+    manager.unlock_account_with_limit(
+        address,
+        "hello",
+        Duration::from_secs(100),
+        Some(1_000_000_000),
+    )?;
+    manager.sign_txn(address, transfer_txn(address, 2, 600_000_000))?;
+    Ok(())
+}
+
+fn script_txn(sender: AccountAddress, seq_num: u64) -> RawUserTransaction {
+    RawUserTransaction::new_with_default_gas_token(
+        sender,
+        seq_num,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    )
+}
+
+#[test]
+pub fn test_unlock_with_limit_unknown_spend() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+
+    assert_eq!(txn_spend_amount(&script_txn(address, 0)), None);
+    let token_transfer_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        0,
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("TransferScripts").unwrap(),
+            ),
+            Identifier::new("peer_to_peer_v2").unwrap(),
+            vec![TypeTag::Struct(StructTag {
+                address: AccountAddress::random(),
+                module: Identifier::new("Token").unwrap(),
+                name: Identifier::new("Token").unwrap(),
+                type_params: vec![],
+            })],
+            vec![
+                bcs_ext::to_bytes(&AccountAddress::random()).unwrap(),
+                bcs_ext::to_bytes(&100u128).unwrap(),
+            ],
+        )),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    assert_eq!(txn_spend_amount(&token_transfer_txn), None);
+
+    // the unknown spend is rejected by the spend limited session.
+    manager.unlock_account_with_limit(
+        address,
+        "hello",
+        Duration::from_secs(100),
+        Some(1_000_000_000),
+    )?;
+    let result = manager.sign_txn(address, script_txn(address, 0));
+    assert!(matches!(result, Err(AccountError::UnknownSpendAmount(_))));
+    let result = manager.sign_txn(address, token_transfer_txn);
+    assert!(matches!(result, Err(AccountError::UnknownSpendAmount(_))));
+
+    // the session without limit signs it.
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+    manager.sign_txn(address, script_txn(address, 0))?;
+
+    // the user explicitly allows the unknown spend.
+    let manager = manager.with_allow_unknown_spend(true);
+    manager.unlock_account_with_limit(
+        address,
+        "hello",
+        Duration::from_secs(100),
+        Some(1_000_000_000),
+    )?;
+    manager.sign_txn(address, script_txn(address, 1))?;
+    Ok(())
+}
+
+#[test]
+pub fn test_unlock_idle_timeout() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager =
+        AccountManager::new(storage)?.with_unlock_idle_timeout(Some(Duration::from_millis(500)));
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+    manager.sign_txn(address, transfer_txn(address, 0, 1))?;
+    std::thread::sleep(Duration::from_millis(600));
+    let result = manager.sign_txn(address, transfer_txn(address, 1, 1));
+    assert!(matches!(result, Err(AccountError::AccountLocked(_))));
+    Ok(())
+}
+
+#[test]
+pub fn test_sign_policy() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let policy = SignPolicy::from_script(
+        r#"
+        fn check(txn) {
+            if txn.spend_amount > 1000000000 { return "deny"; }
+            if txn.spend_amount > 1000000 { return "ask"; }
+            "allow"
+        }
+        "#,
+    )?;
+    let manager = AccountManager::new(storage)?.with_sign_policy(Some(policy));
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+
+    manager.sign_txn(address, transfer_txn(address, 0, 1))?;
+    let result = manager.sign_txn(address, transfer_txn(address, 1, 10_000_000_000));
+    assert!(matches!(result, Err(AccountError::SignPolicyDenied { .. })));
+
+    let txn = transfer_txn(address, 1, 10_000_000);
+    let approval_hash = txn_approval_hash(&txn);
+    let result = manager.sign_txn(address, txn.clone());
+    assert!(matches!(
+        result,
+        Err(AccountError::SignPolicyApprovalRequired { txn_hash, .. }) if txn_hash == txn.crypto_hash()
+    ));
+    // the node operator finds the txn in the pending approvals.
+    let pending = manager.pending_txn_approvals();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].approval_hash, approval_hash);
+    assert_eq!(pending[0].sender, address);
+    manager.approve_txn(approval_hash)?;
+    assert!(manager.pending_txn_approvals().is_empty());
+    manager.sign_txn(address, txn.clone())?;
+    // the approval is used once.
+    assert!(manager.sign_txn(address, txn).is_err());
+    assert!(matches!(
+        manager.approve_txn(txn_approval_hash(&transfer_txn(address, 2, 1))),
+        Err(AccountError::TxnNotPendingApproval(_))
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn test_sign_policy_approval_rebuilt_txn() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let policy = SignPolicy::from_script(r#"fn check(txn) { "ask" }"#)?;
+    let manager = AccountManager::new(storage)?.with_sign_policy(Some(policy));
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+
+    let txn = transfer_txn(address, 1, 10_000_000);
+    assert!(manager.sign_txn(address, txn.clone()).is_err());
+    manager.approve_txn(txn_approval_hash(&txn))?;
+    // the approved txn is rebuilt with a new sequence number and expiration.
+    let rebuilt_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        2,
+        txn.payload().clone(),
+        txn.max_gas_amount(),
+        txn.gas_unit_price(),
+        txn.expiration_timestamp_secs() + 100,
+        txn.chain_id(),
+    );
+    assert_ne!(rebuilt_txn.crypto_hash(), txn.crypto_hash());
+    assert_eq!(txn_approval_hash(&rebuilt_txn), txn_approval_hash(&txn));
+    manager.sign_txn(address, rebuilt_txn)?;
+
+    // the gas price is covered by the approval.
+    let repriced_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        2,
+        txn.payload().clone(),
+        txn.max_gas_amount(),
+        txn.gas_unit_price() + 1,
+        txn.expiration_timestamp_secs(),
+        txn.chain_id(),
+    );
+    assert_ne!(txn_approval_hash(&repriced_txn), txn_approval_hash(&txn));
+    Ok(())
+}
+
+#[test]
+pub fn test_sign_policy_pending_approvals_bounded() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let policy = SignPolicy::from_script(r#"fn check(txn) { "ask" }"#)?;
+    let manager = AccountManager::new(storage)?.with_sign_policy(Some(policy));
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+
+    let txns: Vec<_> = (0..300).map(|i| transfer_txn(address, i, 1)).collect();
+    for txn in txns.iter() {
+        assert!(manager.sign_txn(address, txn.clone()).is_err());
+    }
+    // the oldest asks are dropped.
+    assert!(matches!(
+        manager.approve_txn(txn_approval_hash(&txns[0])),
+        Err(AccountError::TxnNotPendingApproval(_))
+    ));
+    manager.approve_txn(txn_approval_hash(&txns[299]))?;
+    Ok(())
+}
+
 // ignore for now.
 #[ignore]
+#[test]
+pub fn test_update_account_metadata() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+
+    let info = manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            notes: Some(" hot wallet ".to_string()),
+            add_tags: vec!["payroll".to_string(), "exchange".to_string()],
+            remove_tags: vec![],
+            transfer_confirm_thresholds: vec![
+                ("0x1::STC::STC".to_string(), 1000),
+                ("0x1::DummyToken::DummyToken".to_string(), 10),
+            ]
+            .into_iter()
+            .collect(),
+        },
+    )?;
+    assert_eq!(info.metadata.notes.as_deref(), Some("hot wallet"));
+    assert_eq!(info.metadata.tags, vec!["exchange", "payroll"]);
+    assert!(info.has_tag("payroll"));
+    assert_eq!(
+        info.metadata.transfer_confirm_threshold(&STC_TOKEN_CODE),
+        Some(1000)
+    );
+    assert_eq!(
+        info.metadata
+            .transfer_confirm_threshold(&"0x1::DummyToken::DummyToken".parse()?),
+        Some(10)
+    );
+
+    let info = manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            notes: Some("".to_string()),
+            add_tags: vec!["payroll".to_string()],
+            remove_tags: vec!["exchange".to_string()],
+            transfer_confirm_thresholds: Default::default(),
+        },
+    )?;
+    assert_eq!(info.metadata.notes, None);
+    assert_eq!(info.metadata.tags, vec!["payroll"]);
+    assert_eq!(
+        info.metadata.transfer_confirm_threshold(&STC_TOKEN_CODE),
+        Some(1000)
+    );
+    let info = manager.account_info(address)?.unwrap();
+    assert_eq!(info.metadata.tags, vec!["payroll"]);
+
+    let info = manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            transfer_confirm_thresholds: vec![(STC_TOKEN_CODE.to_string(), 0)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        info.metadata.transfer_confirm_threshold(&STC_TOKEN_CODE),
+        None
+    );
+    assert_eq!(
+        info.metadata
+            .transfer_confirm_threshold(&"0x1::DummyToken::DummyToken".parse()?),
+        Some(10)
+    );
+
+    let result = manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            transfer_confirm_thresholds: vec![("STC".to_string(), 1000)].into_iter().collect(),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(result, Err(AccountError::InvalidMetadata(_))));
+
+    let result = manager.update_account_metadata(
+        address,
+        AccountMetadataUpdate {
+            notes: None,
+            add_tags: vec!["bad tag".to_string()],
+            remove_tags: vec![],
+            transfer_confirm_thresholds: Default::default(),
+        },
+    );
+    assert!(matches!(result, Err(AccountError::InvalidMetadata(_))));
+    Ok(())
+}
+
 #[test]
 pub fn test_wallet_account() -> Result<()> {
     use bcs_ext::BCSCodec;
@@ -244,3 +699,99 @@ pub fn test_wallet_account() -> Result<()> {
     println!("txn hash is {:?}", stxn.id());
     Ok(())
 }
+
+#[test]
+pub fn test_restore_accounts() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+
+    let mnemonic = hd::generate_mnemonic(hd::DEFAULT_MNEMONIC_WORD_COUNT)?;
+    let account = manager.derive_account(
+        mnemonic.as_str(),
+        hd::DerivationPath::bip44(1).to_string().as_str(),
+        "hello",
+    )?;
+    let accounts = manager.restore_accounts(mnemonic.as_str(), "hello", 3)?;
+    assert_eq!(accounts.len(), 3);
+    assert_eq!(accounts[1].address, *account.address());
+    assert_eq!(manager.list_account_infos()?.len(), 3);
+
+    // restore again should keep the exist accounts.
+    let restored = manager.restore_accounts(mnemonic.as_str(), "hello", 3)?;
+    assert_eq!(
+        restored.iter().map(|a| a.address).collect::<Vec<_>>(),
+        accounts.iter().map(|a| a.address).collect::<Vec<_>>()
+    );
+    assert!(manager
+        .derive_account(mnemonic.as_str(), "44'/101010'", "hello")
+        .is_err());
+    assert!(matches!(
+        manager.restore_accounts(mnemonic.as_str(), "hello", u32::MAX),
+        Err(AccountError::RestoreCountExceeded { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn test_export_import_keystore() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let first = manager.create_account("hello")?;
+    let second = manager.create_account("world")?;
+    let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+    let public_key = AccountPublicKey::Single(public_key);
+    let readonly =
+        manager.import_readonly_account(public_key.derived_address(), public_key.to_bytes())?;
+    manager.set_default_account(*second.address())?;
+
+    let entries = manager.export_keystore()?;
+    assert_eq!(entries.len(), 3);
+    assert!(entries[2].encrypted_private_key.is_none());
+
+    let restore_dir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(restore_dir.path(), RocksdbConfig::default())?;
+    let restored = AccountManager::new(storage)?;
+    let account_infos = restored.import_keystore(entries.clone())?;
+    assert_eq!(account_infos.len(), 3);
+    assert_eq!(
+        restored.default_account_info()?.map(|a| a.address),
+        Some(*second.address())
+    );
+    assert!(account_infos[2].is_readonly);
+    assert_eq!(account_infos[2].address, *readonly.address());
+    // the accounts keep the passwords of the exported wallet.
+    assert!(restored
+        .unlock_account(*first.address(), "world", Duration::from_secs(10))
+        .is_err());
+    restored.unlock_account(*first.address(), "hello", Duration::from_secs(10))?;
+    assert_eq!(
+        restored.export_account(*second.address(), "world")?,
+        manager.export_account(*second.address(), "world")?
+    );
+
+    // import again should keep the exist accounts.
+    let imported = restored.import_keystore(entries.clone())?;
+    assert_eq!(imported, account_infos);
+    assert_eq!(restored.list_account_infos()?.len(), 3);
+
+    let mut invalid = entries[0].clone();
+    invalid.address = AccountAddress::random();
+    invalid.encrypted_private_key = None;
+    assert!(matches!(
+        restored.import_keystore(vec![invalid]),
+        Err(AccountError::InvalidKeystore(_))
+    ));
+
+    // the public key should derive the address.
+    let (_private_key, other_public_key) = KeyGen::from_os_rng().generate_keypair();
+    let mut mismatched = entries[0].clone();
+    mismatched.address = AccountPublicKey::Single(other_public_key).derived_address();
+    assert!(matches!(
+        restored.import_keystore(vec![mismatched.clone()]),
+        Err(AccountError::InvalidKeystore(_))
+    ));
+    assert!(restored.account_info(mismatched.address)?.is_none());
+    Ok(())
+}
@@ -5,8 +5,9 @@ mod account;
 mod account_manager;
 
 pub use account::Account;
-pub use account_manager::AccountManager;
+pub use account_manager::{txn_approval_hash, txn_spend_amount, AccountManager};
 pub mod account_storage;
+pub mod sign_policy;
 
 #[cfg(test)]
 mod account_test;
@@ -0,0 +1,294 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_manager::txn_spend_amount;
+use anyhow::{bail, format_err, Result};
+use bcs_ext::Sample;
+use rhai::{Array, Dynamic, Engine, ImmutableString, Map, Scope, AST};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{RawUserTransaction, TransactionPayload};
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+
+/// The function of the policy script called before signing a txn.
+const CHECK_FN: &str = "check";
+/// Limit the operations of a check, so a buggy script can not hang the account service.
+const MAX_OPERATIONS: u64 = 100_000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignDecision {
+    Allow,
+    Deny(String),
+    /// The txn is signed after the operator approves it.
+    Ask(String),
+}
+
+/// Operator supplied rhai script deciding whether a txn is signed.
+///
+/// The script defines `fn check(txn)`, the `txn` is a map of the decoded raw txn, and returns
+/// "allow", "deny", "ask" or a map like `#{ decision: "deny", reason: "..." }`.
+pub struct SignPolicy {
+    path: Option<PathBuf>,
+    engine: Engine,
+    ast: AST,
+}
+
+impl SignPolicy {
+    pub fn load(path: &Path) -> Result<Self> {
+        let script = std::fs::read_to_string(path)
+            .map_err(|e| format_err!("Read sign policy {:?} error: {}", path, e))?;
+        let mut policy = Self::from_script(script.as_str())?;
+        policy.path = Some(path.to_path_buf());
+        Ok(policy)
+    }
+
+    pub fn from_script(script: &str) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine
+            .compile(script)
+            .map_err(|e| format_err!("Compile sign policy error: {}", e))?;
+        let policy = Self {
+            path: None,
+            engine,
+            ast,
+        };
+        // fail early if the script misses the check function or returns an invalid decision.
+        policy
+            .check(&RawUserTransaction::sample())
+            .map_err(|e| format_err!("Check sample txn by the sign policy error: {}", e))?;
+        Ok(policy)
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn check(&self, raw_txn: &RawUserTransaction) -> Result<SignDecision> {
+        let result: Dynamic = self
+            .engine
+            .call_fn(
+                &mut Scope::new(),
+                &self.ast,
+                CHECK_FN,
+                (Dynamic::from(txn_to_map(raw_txn)),),
+            )
+            .map_err(|e| format_err!("Run sign policy error: {}", e))?;
+        parse_decision(result)
+    }
+}
+
+fn parse_decision(result: Dynamic) -> Result<SignDecision> {
+    let decision = result.as_str().ok().map(|decision| decision.to_string());
+    let (decision, reason) = if let Some(decision) = decision {
+        (decision, None)
+    } else if let Some(map) = result.try_cast::<Map>() {
+        let get_str = |key: &str| {
+            map.get(key)
+                .and_then(|value| value.as_str().ok().map(|value| value.to_string()))
+        };
+        (
+            get_str("decision")
+                .ok_or_else(|| format_err!("The sign policy result misses the decision"))?,
+            get_str("reason"),
+        )
+    } else {
+        bail!("The sign policy should return a string or a map");
+    };
+    let reason = reason.unwrap_or_else(|| format!("{} by the sign policy", decision));
+    match decision.as_str() {
+        "allow" => Ok(SignDecision::Allow),
+        "deny" => Ok(SignDecision::Deny(reason)),
+        "ask" => Ok(SignDecision::Ask(reason)),
+        other => bail!("Unknown sign policy decision: {}", other),
+    }
+}
+
+fn to_int(value: u128) -> Dynamic {
+    Dynamic::from(i64::try_from(value).unwrap_or(i64::MAX))
+}
+
+fn to_str(value: impl ToString) -> Dynamic {
+    Dynamic::from(value.to_string())
+}
+
+fn insert(map: &mut Map, key: &str, value: Dynamic) {
+    map.insert(ImmutableString::from(key), value);
+}
+
+/// Decode the raw txn to the map passed to the policy script, the amounts are in the smallest unit.
+fn txn_to_map(raw_txn: &RawUserTransaction) -> Map {
+    let mut map = Map::new();
+    insert(&mut map, "sender", to_str(raw_txn.sender()));
+    insert(
+        &mut map,
+        "sequence_number",
+        to_int(u128::from(raw_txn.sequence_number())),
+    );
+    insert(
+        &mut map,
+        "max_gas_amount",
+        to_int(u128::from(raw_txn.max_gas_amount())),
+    );
+    insert(
+        &mut map,
+        "gas_unit_price",
+        to_int(u128::from(raw_txn.gas_unit_price())),
+    );
+    insert(&mut map, "gas_token_code", to_str(raw_txn.gas_token_code()));
+    insert(
+        &mut map,
+        "expiration_timestamp_secs",
+        to_int(u128::from(raw_txn.expiration_timestamp_secs())),
+    );
+    insert(
+        &mut map,
+        "chain_id",
+        to_int(u128::from(raw_txn.chain_id().id())),
+    );
+    let spend_amount = txn_spend_amount(raw_txn);
+    insert(
+        &mut map,
+        "spend_amount",
+        to_int(spend_amount.unwrap_or_default()),
+    );
+    insert(
+        &mut map,
+        "spend_amount_known",
+        Dynamic::from(spend_amount.is_some()),
+    );
+    let to_hex_array =
+        |args: &[Vec<u8>]| -> Array { args.iter().map(|arg| to_str(hex::encode(arg))).collect() };
+    match raw_txn.payload() {
+        TransactionPayload::Script(script) => {
+            insert(&mut map, "payload_type", to_str("script"));
+            insert(
+                &mut map,
+                "ty_args",
+                Dynamic::from(script.ty_args().iter().map(to_str).collect::<Array>()),
+            );
+            insert(&mut map, "args", Dynamic::from(to_hex_array(script.args())));
+        }
+        TransactionPayload::Package(package) => {
+            insert(&mut map, "payload_type", to_str("package"));
+            insert(
+                &mut map,
+                "package_address",
+                to_str(package.package_address()),
+            );
+        }
+        TransactionPayload::ScriptFunction(script_function) => {
+            insert(&mut map, "payload_type", to_str("script_function"));
+            insert(&mut map, "module", to_str(script_function.module()));
+            insert(&mut map, "function", to_str(script_function.function()));
+            insert(
+                &mut map,
+                "ty_args",
+                Dynamic::from(
+                    script_function
+                        .ty_args()
+                        .iter()
+                        .map(to_str)
+                        .collect::<Array>(),
+                ),
+            );
+            insert(
+                &mut map,
+                "args",
+                Dynamic::from(to_hex_array(script_function.args())),
+            );
+            // the payee is the first arg of the single transfer functions.
+            if script_function
+                .function()
+                .as_str()
+                .starts_with("peer_to_peer")
+                && !script_function.function().as_str().ends_with("batch")
+            {
+                if let Some(receiver) = script_function
+                    .args()
+                    .first()
+                    .and_then(|arg| bcs_ext::from_bytes::<AccountAddress>(arg).ok())
+                {
+                    insert(&mut map, "receiver", to_str(receiver));
+                }
+            }
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_types::account_config::{core_code_address, stc_type_tag};
+    use starcoin_types::genesis_config::ChainId;
+    use starcoin_types::identifier::Identifier;
+    use starcoin_types::language_storage::ModuleId;
+    use starcoin_types::transaction::ScriptFunction;
+
+    fn transfer_txn(amount: u128) -> RawUserTransaction {
+        let payload = TransactionPayload::ScriptFunction(ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("TransferScripts").unwrap(),
+            ),
+            Identifier::new("peer_to_peer_v2").unwrap(),
+            vec![stc_type_tag()],
+            vec![
+                bcs_ext::to_bytes(&AccountAddress::random()).unwrap(),
+                bcs_ext::to_bytes(&amount).unwrap(),
+            ],
+        ));
+        RawUserTransaction::new_with_default_gas_token(
+            AccountAddress::random(),
+            0,
+            payload,
+            10000,
+            1,
+            3600,
+            ChainId::test(),
+        )
+    }
+
+    #[test]
+    fn test_sign_policy() {
+        let policy = SignPolicy::from_script(
+            r#"
+            fn check(txn) {
+                if txn.payload_type == "package" {
+                    return #{ decision: "deny", reason: "no module upgrade" };
+                }
+                if txn.function == "peer_to_peer_v2" && txn.spend_amount > 1000000 {
+                    return #{ decision: "ask", reason: "transfer to " + txn.receiver };
+                }
+                "allow"
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            policy.check(&transfer_txn(100)).unwrap(),
+            SignDecision::Allow
+        );
+        assert!(matches!(
+            policy.check(&transfer_txn(1_000_000_000)).unwrap(),
+            SignDecision::Ask(reason) if reason.starts_with("transfer to ")
+        ));
+        assert!(matches!(
+            policy.check(&RawUserTransaction::sample()).unwrap(),
+            SignDecision::Deny(reason) if reason == "no module upgrade"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_sign_policy() {
+        assert!(SignPolicy::from_script("fn other(txn) { \"allow\" }").is_err());
+        assert!(SignPolicy::from_script("fn check(txn) { \"maybe\" }").is_err());
+        assert!(SignPolicy::from_script("fn check(txn) { loop {} }").is_err());
+        let policy = SignPolicy::from_script(
+            "fn check(txn) { if txn.payload_type == \"package\" { \"allow\" } else { 1 } }",
+        )
+        .unwrap();
+        assert!(policy.check(&transfer_txn(1)).is_err());
+    }
+}
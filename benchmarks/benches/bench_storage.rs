@@ -1,8 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use benchmarks::storage::StorageBencher;
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use crypto::HashValue;
+use starcoin_accumulator::tree_store::mock::MockAccumulatorStore;
 use starcoin_accumulator::{accumulator_info::AccumulatorInfo, Accumulator, MerkleAccumulator};
 use starcoin_config::RocksdbConfig;
 use starcoin_storage::cache_storage::CacheStorage;
@@ -63,6 +64,55 @@ fn accumulator_append(c: &mut Criterion) {
     });
 }
 
+/// compare appending leaves one by one, in one batch, and in one batch with parallel hashing.
+fn accumulator_append_batch(c: &mut Criterion) {
+    ::logger::init_for_test();
+    let mut group = c.benchmark_group("accumulator_append_batch");
+    for leaves_count in [100usize, 1000, 10000].iter() {
+        let leaves = create_leaves(0..*leaves_count);
+        let new_accumulator =
+            || MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+        group.bench_with_input(
+            BenchmarkId::new("per_leaf", leaves_count),
+            &leaves,
+            |b, leaves| {
+                b.iter_batched(
+                    new_accumulator,
+                    |accumulator| {
+                        for leaf in leaves {
+                            accumulator.append(&[*leaf]).unwrap();
+                        }
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("batch", leaves_count),
+            &leaves,
+            |b, leaves| {
+                b.iter_batched(
+                    new_accumulator,
+                    |accumulator| accumulator.append_batch(leaves, false).unwrap(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("parallel_batch", leaves_count),
+            &leaves,
+            |b, leaves| {
+                b.iter_batched(
+                    new_accumulator,
+                    |accumulator| accumulator.append_batch(leaves, true).unwrap(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
 fn create_leaves(nums: std::ops::Range<usize>) -> Vec<HashValue> {
     nums.map(|x| HashValue::sha3_256_of(x.to_be_bytes().as_ref()))
         .collect()
@@ -70,6 +120,7 @@ fn create_leaves(nums: std::ops::Range<usize>) -> Vec<HashValue> {
 criterion_group!(
     starcoin_storage_benches,
     storage_transaction,
-    accumulator_append
+    accumulator_append,
+    accumulator_append_batch
 );
 criterion_main!(starcoin_storage_benches);
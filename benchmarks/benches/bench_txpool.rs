@@ -0,0 +1,75 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use benchmarks::random_txn;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use starcoin_config::NodeConfig;
+use starcoin_genesis::Genesis;
+use starcoin_storage::BlockStore;
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::TxPoolSyncService;
+use std::sync::Arc;
+
+fn new_txpool() -> (TxPoolService, Arc<NodeConfig>) {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, _, genesis) =
+        Genesis::init_storage_for_test(node_config.net()).expect("init storage by genesis fail.");
+    let chain_header = storage
+        .get_block_header_by_hash(genesis.block().id())
+        .unwrap()
+        .unwrap();
+    (
+        TxPoolService::new(node_config.clone(), storage, chain_header),
+        node_config,
+    )
+}
+
+/// txpool benchmarks
+fn txpool_add_txns(c: &mut Criterion) {
+    ::logger::init_for_test();
+    let (_, node_config) = new_txpool();
+    let mut group = c.benchmark_group("txpool_add_txns");
+    for count in [10u64, 100, 500].iter() {
+        let txns = (0..*count)
+            .map(|seq_num| random_txn(seq_num, node_config.net()))
+            .collect::<Vec<_>>();
+        group.throughput(Throughput::Elements(*count));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &txns, |b, txns| {
+            b.iter_batched(
+                || (new_txpool().0, txns.clone()),
+                |(txpool, txns)| {
+                    for result in txpool.add_txns(txns) {
+                        result.unwrap();
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn txpool_get_pending_txns(c: &mut Criterion) {
+    ::logger::init_for_test();
+    let (txpool, node_config) = new_txpool();
+    let count = 500u64;
+    let txns = (0..count)
+        .map(|seq_num| random_txn(seq_num, node_config.net()))
+        .collect::<Vec<_>>();
+    for result in txpool.add_txns(txns) {
+        result.unwrap();
+    }
+    let mut group = c.benchmark_group("txpool_get_pending_txns");
+    group.throughput(Throughput::Elements(count));
+    group.bench_function(BenchmarkId::from_parameter(count), |b| {
+        b.iter(|| txpool.get_pending_txns(Some(count), None))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    name=txpool_benches;
+    config = Criterion::default().sample_size(10);
+    targets=txpool_add_txns, txpool_get_pending_txns
+);
+criterion_main!(txpool_benches);
@@ -0,0 +1,127 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use benchmarks::report::{
+    current_revision, BenchmarkRecord, ComparisonReport, ThresholdOverride, Thresholds,
+    DEFAULT_THRESHOLD_PERCENT,
+};
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "bench_report",
+    about = "Record the benchmark results by git revision, and compare the results of two revisions"
+)]
+enum Opt {
+    /// Record the latest criterion results as the results of a revision.
+    Record {
+        /// the git revision, default to the current HEAD.
+        #[structopt(long)]
+        revision: Option<String>,
+        #[structopt(long, default_value = "target/criterion", parse(from_os_str))]
+        criterion_dir: PathBuf,
+        #[structopt(long, default_value = "target/bench-results", parse(from_os_str))]
+        results_dir: PathBuf,
+        /// keep the criterion output dir after recording, by default it is removed,
+        /// so the stale results of the removed or renamed benchmarks are not recorded to the next revision.
+        #[structopt(long)]
+        keep_criterion_dir: bool,
+    },
+    /// Compare the results of the head revision with the base revision.
+    Compare {
+        #[structopt(long)]
+        base: String,
+        /// the head git revision, default to the current HEAD.
+        #[structopt(long)]
+        head: Option<String>,
+        #[structopt(long, default_value = "target/bench-results", parse(from_os_str))]
+        results_dir: PathBuf,
+        /// the threshold of the mean time change in percent.
+        #[structopt(long)]
+        threshold: Option<f64>,
+        /// the threshold of the benchmarks with the prefix, as <prefix>=<percent>, such as `block_apply=10`.
+        #[structopt(long = "threshold-for")]
+        threshold_for: Vec<ThresholdOverride>,
+        /// write the markdown report to the file, besides the stdout.
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+        /// exit with an error if any benchmark regressed.
+        #[structopt(long)]
+        fail_on_regression: bool,
+    },
+    /// List the recorded revisions.
+    List {
+        #[structopt(long, default_value = "target/bench-results", parse(from_os_str))]
+        results_dir: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    match Opt::from_args() {
+        Opt::Record {
+            revision,
+            criterion_dir,
+            results_dir,
+            keep_criterion_dir,
+        } => {
+            let revision = match revision {
+                Some(revision) => revision,
+                None => current_revision()?,
+            };
+            let record = BenchmarkRecord::collect(revision, criterion_dir.as_path())?;
+            let path = record.save(results_dir.as_path())?;
+            println!(
+                "Recorded {} benchmark results of revision {} to {}",
+                record.results.len(),
+                record.revision,
+                path.display()
+            );
+            if !keep_criterion_dir {
+                fs::remove_dir_all(criterion_dir.as_path())?;
+            }
+        }
+        Opt::Compare {
+            base,
+            head,
+            results_dir,
+            threshold,
+            threshold_for,
+            output,
+            fail_on_regression,
+        } => {
+            let head = match head {
+                Some(head) => head,
+                None => current_revision()?,
+            };
+            let base = BenchmarkRecord::load(results_dir.as_path(), base.as_str())?;
+            let head = BenchmarkRecord::load(results_dir.as_path(), head.as_str())?;
+            let thresholds = Thresholds {
+                default_percent: threshold.unwrap_or(DEFAULT_THRESHOLD_PERCENT),
+                overrides: threshold_for,
+            };
+            let report = ComparisonReport::compare(&base, &head, &thresholds);
+            let markdown = report.to_markdown();
+            println!("{}", markdown);
+            if let Some(output) = output {
+                fs::write(output, markdown)?;
+            }
+            if fail_on_regression && report.has_regression() {
+                bail!(
+                    "{} benchmarks regressed from {} to {}",
+                    report.regressions().len(),
+                    report.base_revision,
+                    report.head_revision
+                );
+            }
+        }
+        Opt::List { results_dir } => {
+            for revision in BenchmarkRecord::revisions(results_dir.as_path())? {
+                println!("{}", revision);
+            }
+        }
+    }
+    Ok(())
+}
@@ -11,6 +11,7 @@ use types::transaction::SignedUserTransaction;
 
 pub mod chain;
 pub mod helper;
+pub mod report;
 pub mod storage;
 
 pub fn random_txn(seq_num: u64, net: &ChainNetwork) -> SignedUserTransaction {
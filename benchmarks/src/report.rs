@@ -0,0 +1,455 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keep the benchmark results of each git revision, and compare the results of two revisions.
+//!
+//! The results are collected from the criterion output dir (`target/criterion` by default) after
+//! `cargo bench`, and saved as `<results_dir>/<revision>.json`. A comparison reports every
+//! benchmark whose mean time changed by more than its threshold as a regression or an improvement.
+
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The default threshold of the mean time change, in percent.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// The result of a benchmark, as the criterion estimates.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// The mean time of an iteration, in nanoseconds.
+    pub mean_ns: f64,
+    /// The standard error of the mean time, in nanoseconds.
+    pub std_error_ns: f64,
+}
+
+/// The benchmark results of a git revision.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkRecord {
+    pub revision: String,
+    /// The unix timestamp in seconds when the record is made.
+    pub timestamp: u64,
+    /// The results by the full benchmark id, such as `txpool_add_txns/100`.
+    pub results: BTreeMap<String, BenchmarkResult>,
+}
+
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    point_estimate: f64,
+    standard_error: f64,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: CriterionEstimate,
+}
+
+impl BenchmarkRecord {
+    pub fn new(revision: String, results: BTreeMap<String, BenchmarkResult>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            revision,
+            timestamp,
+            results,
+        }
+    }
+
+    /// Collect the latest results in the criterion output dir.
+    pub fn collect(revision: String, criterion_dir: &Path) -> Result<Self> {
+        ensure!(
+            criterion_dir.is_dir(),
+            "Criterion output dir {} does not exist, please run `cargo bench` first",
+            criterion_dir.display()
+        );
+        let mut results = BTreeMap::new();
+        collect_results(criterion_dir, &mut results)?;
+        Ok(Self::new(revision, results))
+    }
+
+    fn path(results_dir: &Path, revision: &str) -> PathBuf {
+        results_dir.join(format!("{}.json", revision))
+    }
+
+    /// Save the record to the results dir, the record of the same revision is replaced.
+    pub fn save(&self, results_dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(results_dir)?;
+        let path = Self::path(results_dir, self.revision.as_str());
+        fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn load(results_dir: &Path, revision: &str) -> Result<Self> {
+        let path = Self::path(results_dir, revision);
+        ensure!(
+            path.exists(),
+            "Benchmark record of revision {} does not exist in {}",
+            revision,
+            results_dir.display()
+        );
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// The revisions of the saved records.
+    pub fn revisions(results_dir: &Path) -> Result<Vec<String>> {
+        if !results_dir.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut revisions = vec![];
+        for entry in fs::read_dir(results_dir)? {
+            let path = entry?.path();
+            if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+                if let Some(revision) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    revisions.push(revision.to_string());
+                }
+            }
+        }
+        revisions.sort();
+        Ok(revisions)
+    }
+}
+
+/// Walk the criterion output dir, every benchmark has a `new` dir with the latest estimates.
+fn collect_results(dir: &Path, results: &mut BTreeMap<String, BenchmarkResult>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().map(|name| name == "new").unwrap_or(false) {
+            let benchmark_file = path.join("benchmark.json");
+            let estimates_file = path.join("estimates.json");
+            if benchmark_file.exists() && estimates_file.exists() {
+                let benchmark: CriterionBenchmark =
+                    serde_json::from_slice(&fs::read(benchmark_file)?)?;
+                let estimates: CriterionEstimates =
+                    serde_json::from_slice(&fs::read(estimates_file)?)?;
+                results.insert(
+                    benchmark.full_id,
+                    BenchmarkResult {
+                        mean_ns: estimates.mean.point_estimate,
+                        std_error_ns: estimates.mean.standard_error,
+                    },
+                );
+            }
+        } else {
+            collect_results(path.as_path(), results)?;
+        }
+    }
+    Ok(())
+}
+
+/// The current git revision of the work dir.
+pub fn current_revision() -> Result<String> {
+    let output = Command::new("git")
+        .args(&["rev-parse", "--short", "HEAD"])
+        .output()?;
+    ensure!(
+        output.status.success(),
+        "Get git revision failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// The threshold of a benchmark, the threshold of the longest matched prefix is used,
+/// so the noisy benchmarks can have a looser threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThresholdOverride {
+    pub prefix: String,
+    pub percent: f64,
+}
+
+impl FromStr for ThresholdOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let position = s
+            .rfind('=')
+            .ok_or_else(|| format_err!("Invalid threshold {}, expect <prefix>=<percent>", s))?;
+        let percent = f64::from_str(&s[position + 1..])?;
+        ensure!(percent >= 0.0, "Threshold should not be negative");
+        Ok(Self {
+            prefix: s[..position].to_string(),
+            percent,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Thresholds {
+    pub default_percent: f64,
+    pub overrides: Vec<ThresholdOverride>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            default_percent: DEFAULT_THRESHOLD_PERCENT,
+            overrides: vec![],
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn threshold_of(&self, benchmark: &str) -> f64 {
+        self.overrides
+            .iter()
+            .filter(|threshold| benchmark.starts_with(threshold.prefix.as_str()))
+            .max_by_key(|threshold| threshold.prefix.len())
+            .map(|threshold| threshold.percent)
+            .unwrap_or(self.default_percent)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonStatus {
+    Improved,
+    Regressed,
+    Unchanged,
+    /// The benchmark only exists in the head revision.
+    New,
+    /// The benchmark only exists in the base revision.
+    Removed,
+}
+
+impl Display for ComparisonStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ComparisonStatus::Improved => "improved",
+            ComparisonStatus::Regressed => "REGRESSED",
+            ComparisonStatus::Unchanged => "unchanged",
+            ComparisonStatus::New => "new",
+            ComparisonStatus::Removed => "removed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    pub benchmark: String,
+    pub base_mean_ns: Option<f64>,
+    pub head_mean_ns: Option<f64>,
+    /// The change of the mean time in percent, positive means slower.
+    pub change_percent: Option<f64>,
+    pub threshold_percent: f64,
+    pub status: ComparisonStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub base_revision: String,
+    pub head_revision: String,
+    pub comparisons: Vec<BenchmarkComparison>,
+}
+
+impl ComparisonReport {
+    pub fn compare(
+        base: &BenchmarkRecord,
+        head: &BenchmarkRecord,
+        thresholds: &Thresholds,
+    ) -> Self {
+        let mut benchmarks = base.results.keys().collect::<Vec<_>>();
+        benchmarks.extend(head.results.keys());
+        benchmarks.sort();
+        benchmarks.dedup();
+
+        let comparisons = benchmarks
+            .into_iter()
+            .map(|benchmark| {
+                let threshold_percent = thresholds.threshold_of(benchmark.as_str());
+                let base_mean_ns = base.results.get(benchmark).map(|result| result.mean_ns);
+                let head_mean_ns = head.results.get(benchmark).map(|result| result.mean_ns);
+                let (change_percent, status) = match (base_mean_ns, head_mean_ns) {
+                    (Some(base_mean), Some(head_mean)) => {
+                        let change = if base_mean > 0.0 {
+                            (head_mean - base_mean) / base_mean * 100.0
+                        } else {
+                            0.0
+                        };
+                        let status = if change > threshold_percent {
+                            ComparisonStatus::Regressed
+                        } else if change < -threshold_percent {
+                            ComparisonStatus::Improved
+                        } else {
+                            ComparisonStatus::Unchanged
+                        };
+                        (Some(change), status)
+                    }
+                    (None, _) => (None, ComparisonStatus::New),
+                    (_, None) => (None, ComparisonStatus::Removed),
+                };
+                BenchmarkComparison {
+                    benchmark: benchmark.clone(),
+                    base_mean_ns,
+                    head_mean_ns,
+                    change_percent,
+                    threshold_percent,
+                    status,
+                }
+            })
+            .collect();
+        Self {
+            base_revision: base.revision.clone(),
+            head_revision: head.revision.clone(),
+            comparisons,
+        }
+    }
+
+    pub fn regressions(&self) -> Vec<&BenchmarkComparison> {
+        self.comparisons
+            .iter()
+            .filter(|comparison| comparison.status == ComparisonStatus::Regressed)
+            .collect()
+    }
+
+    pub fn has_regression(&self) -> bool {
+        !self.regressions().is_empty()
+    }
+
+    /// Render the report as a markdown table, so it can be posted to the merge request.
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "## Benchmark comparison: {} -> {}",
+                self.base_revision, self.head_revision
+            ),
+            String::new(),
+            format!(
+                "{} regressed, {} improved, {} benchmarks in total.",
+                self.regressions().len(),
+                self.comparisons
+                    .iter()
+                    .filter(|comparison| comparison.status == ComparisonStatus::Improved)
+                    .count(),
+                self.comparisons.len()
+            ),
+            String::new(),
+            "| benchmark | base | head | change | threshold | status |".to_string(),
+            "|---|---|---|---|---|---|".to_string(),
+        ];
+        for comparison in &self.comparisons {
+            lines.push(format!(
+                "| {} | {} | {} | {} | ±{:.1}% | {} |",
+                comparison.benchmark,
+                format_duration(comparison.base_mean_ns),
+                format_duration(comparison.head_mean_ns),
+                comparison
+                    .change_percent
+                    .map(|change| format!("{:+.2}%", change))
+                    .unwrap_or_else(|| "-".to_string()),
+                comparison.threshold_percent,
+                comparison.status,
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn format_duration(ns: Option<f64>) -> String {
+    match ns {
+        None => "-".to_string(),
+        Some(ns) if ns >= 1_000_000_000.0 => format!("{:.3} s", ns / 1_000_000_000.0),
+        Some(ns) if ns >= 1_000_000.0 => format!("{:.3} ms", ns / 1_000_000.0),
+        Some(ns) if ns >= 1_000.0 => format!("{:.3} µs", ns / 1_000.0),
+        Some(ns) => format!("{:.1} ns", ns),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(revision: &str, results: &[(&str, f64)]) -> BenchmarkRecord {
+        BenchmarkRecord::new(
+            revision.to_string(),
+            results
+                .iter()
+                .map(|(id, mean_ns)| {
+                    (
+                        id.to_string(),
+                        BenchmarkResult {
+                            mean_ns: *mean_ns,
+                            std_error_ns: 0.0,
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_compare() {
+        let base = record(
+            "base",
+            &[
+                ("block_apply", 1000.0),
+                ("txpool_add_txns/100", 1000.0),
+                ("get_with_proof/db_store", 1000.0),
+                ("accumulator_append", 1000.0),
+            ],
+        );
+        let head = record(
+            "head",
+            &[
+                ("block_apply", 1200.0),
+                ("txpool_add_txns/100", 800.0),
+                ("get_with_proof/db_store", 1150.0),
+                ("txpool_get_pending_txns/500", 1000.0),
+            ],
+        );
+        let thresholds = Thresholds {
+            default_percent: 10.0,
+            overrides: vec!["get_with_proof=20".parse().unwrap()],
+        };
+        let report = ComparisonReport::compare(&base, &head, &thresholds);
+        let status_of = |benchmark: &str| {
+            report
+                .comparisons
+                .iter()
+                .find(|comparison| comparison.benchmark == benchmark)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status_of("block_apply"), ComparisonStatus::Regressed);
+        assert_eq!(status_of("txpool_add_txns/100"), ComparisonStatus::Improved);
+        assert_eq!(
+            status_of("get_with_proof/db_store"),
+            ComparisonStatus::Unchanged
+        );
+        assert_eq!(status_of("accumulator_append"), ComparisonStatus::Removed);
+        assert_eq!(
+            status_of("txpool_get_pending_txns/500"),
+            ComparisonStatus::New
+        );
+        assert_eq!(report.regressions().len(), 1);
+        assert!(report.to_markdown().contains("block_apply"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = starcoin_config::temp_path();
+        let record = record("abc123", &[("block_apply", 1000.0)]);
+        record.save(dir.path()).unwrap();
+        assert_eq!(BenchmarkRecord::load(dir.path(), "abc123").unwrap(), record);
+        assert_eq!(
+            BenchmarkRecord::revisions(dir.path()).unwrap(),
+            vec!["abc123".to_string()]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metrics::BLOCK_RELAYER_METRICS;
+use crate::verify_queue::{BlockPriority, BlockVerifyQueue};
 use anyhow::{format_err, Result};
 use config::NodeConfig;
 use crypto::HashValue;
@@ -32,10 +33,22 @@ use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::sync::Arc;
 
+/// Max count of compact blocks processed concurrently.
+const MAX_VERIFY_WORKERS: usize = 4;
+/// Max count of compact blocks waiting to be processed, the lowest priority block is dropped when full.
+const MAX_VERIFY_QUEUE_SIZE: usize = 256;
+
+/// Notify the relayer a compact block worker is finished.
+#[derive(Clone, Debug)]
+pub struct BlockVerifyDone;
+
 pub struct BlockRelayer {
     txpool: TxPoolService,
     sync_status: Option<SyncStatus>,
     time_service: Arc<dyn TimeService>,
+    head_id: Option<HashValue>,
+    verify_queue: BlockVerifyQueue,
+    running_workers: usize,
 }
 
 impl ServiceFactory<Self> for BlockRelayer {
@@ -52,6 +65,9 @@ impl BlockRelayer {
             txpool,
             sync_status: None,
             time_service,
+            head_id: None,
+            verify_queue: BlockVerifyQueue::new(MAX_VERIFY_QUEUE_SIZE),
+            running_workers: 0,
         }
     }
 
@@ -155,11 +171,48 @@ impl BlockRelayer {
         Ok(block)
     }
 
+    /// Put the compact block to verify queue, the lowest priority block is dropped if the queue is full.
+    /// The peer of the dropped block is not penalized, the relayer is busy by the local load.
+    fn enqueue_block_event(
+        &mut self,
+        compact_block_msg: PeerCompactBlockMessage,
+        ctx: &mut ServiceContext<BlockRelayer>,
+    ) -> Result<()> {
+        let priority = BlockPriority::new(self.head_id, &compact_block_msg);
+        if let Some(dropped) = self.verify_queue.push(priority, compact_block_msg) {
+            BLOCK_RELAYER_METRICS.block_verify_dropped.inc();
+            warn!(
+                "[block-relay] Verify queue is full, drop block {:?} from peer {}",
+                dropped.message.compact_block.header.id(),
+                dropped.peer_id
+            );
+        }
+        self.schedule_workers(ctx)
+    }
+
+    /// Spawn workers for the highest priority blocks until the worker pool is full.
+    fn schedule_workers(&mut self, ctx: &mut ServiceContext<BlockRelayer>) -> Result<()> {
+        while self.running_workers < MAX_VERIFY_WORKERS {
+            match self.verify_queue.pop() {
+                Some(compact_block_msg) => {
+                    self.handle_block_event(compact_block_msg, ctx)?;
+                    self.running_workers += 1;
+                }
+                None => break,
+            }
+        }
+        BLOCK_RELAYER_METRICS
+            .block_verify_queue_len
+            .set(self.verify_queue.len() as u64);
+        Ok(())
+    }
+
     fn handle_block_event(
         &self,
         compact_block_msg: PeerCompactBlockMessage,
         ctx: &mut ServiceContext<BlockRelayer>,
     ) -> Result<()> {
+        let self_ref = ctx.self_ref();
         let network = ctx.get_shared::<NetworkServiceRef>()?;
         let block_connector_service = ctx.service_ref::<BlockConnectorService>()?.clone();
         let txpool = self.txpool.clone();
@@ -186,14 +239,20 @@ impl BlockRelayer {
                 )
                 .await?;
                 timer.observe_duration();
-                block_connector_service.notify(PeerNewBlock::new(peer_id, block))?;
+                // wait the block is verified, so the worker is released after the verification.
+                block_connector_service
+                    .send(PeerNewBlock::new(peer_id, block))
+                    .await?;
             }
             Ok(())
         };
-        ctx.spawn(fut.then(|result: Result<()>| async move {
+        ctx.spawn(fut.then(move |result: Result<()>| async move {
             if let Err(e) = result {
                 error!("[block-relay] process PeerCmpctBlockEvent error {:?}", e);
             }
+            if let Err(e) = self_ref.notify(BlockVerifyDone) {
+                error!("[block-relay] notify BlockVerifyDone error {:?}", e);
+            }
         }));
         Ok(())
     }
@@ -227,6 +286,7 @@ impl EventHandler<Self, NewHeadBlock> for BlockRelayer {
             "[block-relay] Handle new head block event, block_id: {:?}",
             event.0.block().id()
         );
+        self.head_id = Some(event.0.block().id());
         let network = match ctx.get_shared::<NetworkServiceRef>() {
             Ok(network) => network,
             Err(e) => {
@@ -274,7 +334,7 @@ impl EventHandler<Self, PeerCompactBlockMessage> for BlockRelayer {
         );
         //TODO should filter too old block?
 
-        if let Err(e) = self.handle_block_event(compact_block_msg, ctx) {
+        if let Err(e) = self.enqueue_block_event(compact_block_msg, ctx) {
             BLOCK_RELAYER_METRICS
                 .txns_filled_failed
                 .with_label_values(&["error"])
@@ -286,3 +346,12 @@ impl EventHandler<Self, PeerCompactBlockMessage> for BlockRelayer {
         }
     }
 }
+
+impl EventHandler<Self, BlockVerifyDone> for BlockRelayer {
+    fn handle_event(&mut self, _msg: BlockVerifyDone, ctx: &mut ServiceContext<BlockRelayer>) {
+        self.running_workers = self.running_workers.saturating_sub(1);
+        if let Err(e) = self.schedule_workers(ctx) {
+            error!("[block-relay] schedule block verify workers error: {:?}", e);
+        }
+    }
+}
@@ -3,4 +3,5 @@
 
 mod block_relayer;
 mod metrics;
-pub use block_relayer::BlockRelayer;
+mod verify_queue;
+pub use block_relayer::{BlockRelayer, BlockVerifyDone};
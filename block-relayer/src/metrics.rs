@@ -1,7 +1,7 @@
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
     default_registry, register_histogram, register_int_gauge, register_uint_gauge, Histogram,
-    IntGauge, Opts, PrometheusError, UIntCounterVec, UIntGauge,
+    IntGauge, Opts, PrometheusError, UIntCounter, UIntCounterVec, UIntGauge,
 };
 
 pub static BLOCK_RELAYER_METRICS: Lazy<BlockRelayerMetrics> =
@@ -16,6 +16,8 @@ pub struct BlockRelayerMetrics {
     pub block_broadcast_time: Histogram,
     pub txns_filled_failed: UIntCounterVec,
     pub block_txns_count: UIntGauge,
+    pub block_verify_queue_len: UIntGauge,
+    pub block_verify_dropped: UIntCounter,
 }
 
 impl BlockRelayerMetrics {
@@ -50,6 +52,18 @@ impl BlockRelayerMetrics {
         let broadcast_txns_count =
             register_uint_gauge!("starcoin_broadcast_txns_count", "broadcast txns count.")?;
         default_registry().register(Box::new(txns_filled_failed.clone()))?;
+        let block_verify_queue_len = register_uint_gauge!(
+            "starcoin_block_verify_queue_len",
+            "block relayer verify queue length."
+        )?;
+        let block_verify_dropped = UIntCounter::with_opts(
+            Opts::new(
+                "starcoin_block_verify_dropped",
+                "Count of compact blocks dropped because verify queue is full.",
+            )
+            .namespace("starcoin"),
+        )?;
+        default_registry().register(Box::new(block_verify_dropped.clone()))?;
         Ok(Self {
             txns_filled_from_network,
             txns_filled_from_txpool,
@@ -58,6 +72,8 @@ impl BlockRelayerMetrics {
             block_broadcast_time,
             txns_filled_failed,
             block_txns_count: broadcast_txns_count,
+            block_verify_queue_len,
+            block_verify_dropped,
         })
     }
 }
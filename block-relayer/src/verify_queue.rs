@@ -0,0 +1,161 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::HashValue;
+use network_api::messages::PeerCompactBlockMessage;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// The priority of a compact block waiting for verification.
+/// Blocks which extend the current head are processed first, then the higher block first.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct BlockPriority {
+    extends_head: bool,
+    number: u64,
+}
+
+impl BlockPriority {
+    pub fn new(head_id: Option<HashValue>, msg: &PeerCompactBlockMessage) -> Self {
+        let header = &msg.message.compact_block.header;
+        Self {
+            extends_head: head_id == Some(header.parent_hash()),
+            number: header.number(),
+        }
+    }
+}
+
+/// The key of a queued block, the earlier received block first when the priority is equal.
+type QueueKey = (BlockPriority, Reverse<u64>);
+
+/// A bounded priority queue of the compact blocks waiting for verification.
+/// When the queue is full, the lowest priority block is dropped, so a storm of fork blocks
+/// can not push out the blocks which extend the current head.
+pub(crate) struct BlockVerifyQueue {
+    capacity: usize,
+    next_seq: u64,
+    /// Ordered by the key, so both the highest and the lowest block are taken in O(log n).
+    items: BTreeMap<QueueKey, PeerCompactBlockMessage>,
+}
+
+impl BlockVerifyQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_seq: 0,
+            items: BTreeMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Push the block to queue, return the dropped block if the queue is full.
+    pub fn push(
+        &mut self,
+        priority: BlockPriority,
+        msg: PeerCompactBlockMessage,
+    ) -> Option<PeerCompactBlockMessage> {
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.items.insert((priority, Reverse(seq)), msg);
+        if self.items.len() <= self.capacity {
+            return None;
+        }
+        // drop the lowest priority block.
+        let lowest = self.items.keys().next().copied()?;
+        self.items.remove(&lowest)
+    }
+
+    pub fn pop(&mut self) -> Option<PeerCompactBlockMessage> {
+        let highest = self.items.keys().next_back().copied()?;
+        self.items.remove(&highest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcs_ext::Sample;
+    use network_api::messages::CompactBlockMessage;
+    use network_api::PeerId;
+    use starcoin_types::block::{Block, BlockBody, BlockHeaderBuilder, BlockInfo};
+
+    fn compact_block_msg(parent_hash: HashValue, number: u64) -> PeerCompactBlockMessage {
+        let header = BlockHeaderBuilder::random()
+            .with_parent_hash(parent_hash)
+            .with_number(number)
+            .build();
+        let block = Block::new(header, BlockBody::new_empty());
+        PeerCompactBlockMessage::new(
+            PeerId::random(),
+            CompactBlockMessage::new(block.into(), BlockInfo::sample()),
+        )
+    }
+
+    #[test]
+    fn test_block_verify_queue() {
+        let head_id = HashValue::random();
+        let mut queue = BlockVerifyQueue::new(2);
+        let fork_block = compact_block_msg(HashValue::random(), 10);
+        let fork_block_id = fork_block.message.compact_block.header.id();
+        assert!(queue
+            .push(BlockPriority::new(Some(head_id), &fork_block), fork_block)
+            .is_none());
+        let head_block = compact_block_msg(head_id, 2);
+        let head_block_id = head_block.message.compact_block.header.id();
+        assert!(queue
+            .push(BlockPriority::new(Some(head_id), &head_block), head_block)
+            .is_none());
+        let low_fork_block = compact_block_msg(HashValue::random(), 1);
+        let low_fork_block_id = low_fork_block.message.compact_block.header.id();
+        let dropped = queue
+            .push(
+                BlockPriority::new(Some(head_id), &low_fork_block),
+                low_fork_block,
+            )
+            .unwrap();
+        assert_eq!(dropped.message.compact_block.header.id(), low_fork_block_id);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            queue.pop().unwrap().message.compact_block.header.id(),
+            head_block_id
+        );
+        assert_eq!(
+            queue.pop().unwrap().message.compact_block.header.id(),
+            fork_block_id
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_block_verify_queue_same_priority() {
+        let parent_hash = HashValue::random();
+        let mut queue = BlockVerifyQueue::new(2);
+        let blocks: Vec<_> = (0..3).map(|_| compact_block_msg(parent_hash, 5)).collect();
+        let ids: Vec<_> = blocks
+            .iter()
+            .map(|block| block.message.compact_block.header.id())
+            .collect();
+        let mut dropped = None;
+        for block in blocks {
+            dropped = queue.push(BlockPriority::new(None, &block), block);
+        }
+        // the latest received block is dropped, the earliest one is popped first.
+        assert_eq!(dropped.unwrap().message.compact_block.header.id(), ids[2]);
+        assert_eq!(
+            queue.pop().unwrap().message.compact_block.header.id(),
+            ids[0]
+        );
+        assert_eq!(
+            queue.pop().unwrap().message.compact_block.header.id(),
+            ids[1]
+        );
+        assert!(queue.pop().is_none());
+    }
+}
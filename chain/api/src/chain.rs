@@ -1,10 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::TotalSupplyInfo;
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_state_api::{ChainState, ChainStateReader};
 use starcoin_types::block::BlockIdAndNumber;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -42,6 +44,14 @@ pub trait ChainReader {
     fn get_transaction_info_by_version(&self, version: u64)
         -> Result<Option<BlockTransactionInfo>>;
 
+    /// Get the transaction info of `txn_hash` with its proof in the txn accumulator of block `block_id`.
+    /// Return None if the txn is not included in the main chain up to the block.
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>>;
+
     fn chain_state_reader(&self) -> &dyn ChainStateReader;
     fn get_block_info(&self, block_id: Option<HashValue>) -> Result<Option<BlockInfo>>;
     fn get_total_difficulty(&self) -> Result<U256>;
@@ -50,6 +60,7 @@ pub trait ChainReader {
     fn epoch(&self) -> &Epoch;
     fn get_epoch_info_by_number(&self, number: Option<BlockNumber>) -> Result<EpochInfo>;
     fn get_global_time_by_number(&self, number: BlockNumber) -> Result<GlobalTimeOnChain>;
+    fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo>;
     /// Get block id vec by BlockNumber, `start_number`'s block id is include.
     fn get_block_ids(
         &self,
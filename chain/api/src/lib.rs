@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 #![deny(clippy::integer_arithmetic)]
 
+use starcoin_types::block::BlockNumber;
 use starcoin_vm_types::transaction::SignedUserTransaction;
 
 mod chain;
@@ -15,6 +16,16 @@ pub struct ExcludedTxns {
     pub untouched_txns: Vec<SignedUserTransaction>,
 }
 
+/// The STC supply at the state of a block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TotalSupplyInfo {
+    pub block_number: BlockNumber,
+    /// The total value of the STC token info.
+    pub total_supply: u128,
+    /// The STC balance of the treasury, None if the treasury is not initialized.
+    pub treasury_balance: Option<u128>,
+}
+
 pub use chain::{Chain, ChainReader, ChainWriter, ExecutedBlock, MintedUncleNumber, VerifiedBlock};
 pub use errors::*;
 pub use service::{ChainAsyncService, ReadableChainService, WriteableChainService};
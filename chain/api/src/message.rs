@@ -1,11 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::TotalSupplyInfo;
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::block::BlockSummary;
 use starcoin_types::block::EpochUncleSummary;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::stress_test::TPS;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -33,9 +35,14 @@ pub enum ChainRequest {
     GetEpochInfo(),
     GetEpochInfoByNumber(u64),
     GetGlobalTimeByNumber(u64),
+    GetTotalSupplyByNumber(BlockNumber),
     GetTransactionBlock(HashValue),
     GetTransaction(HashValue),
     GetTransactionInfo(HashValue),
+    GetTransactionProof {
+        block_id: HashValue,
+        txn_hash: HashValue,
+    },
     GetBlockTransactionInfos(HashValue),
     GetTransactionInfoByBlockAndIndex {
         block_hash: HashValue,
@@ -80,12 +87,14 @@ pub enum ChainResponse {
     BlockHeaderVec(Vec<BlockHeader>),
     TransactionInfos(Vec<BlockTransactionInfo>),
     TransactionInfo(Option<BlockTransactionInfo>),
+    TransactionProof(Box<Option<TransactionInfoWithProof>>),
     Events(Vec<ContractEventInfo>),
     MainEvents(Vec<ContractEventInfo>),
     None,
     Conn(Result<()>),
     EpochInfo(EpochInfo),
     GlobalTime(GlobalTimeOnChain),
+    TotalSupply(TotalSupplyInfo),
     HashVec(Vec<HashValue>),
     TPS(TPS),
     BlockSummaries(Vec<BlockSummary>),
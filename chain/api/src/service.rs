@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::message::{ChainRequest, ChainResponse};
+use crate::TotalSupplyInfo;
 use anyhow::{bail, Result};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_types::block::{BlockSummary, EpochUncleSummary};
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::filter::Filter;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
 use starcoin_types::{
@@ -25,6 +27,11 @@ pub trait ReadableChainService {
     fn get_block_info_by_hash(&self, hash: HashValue) -> Result<Option<BlockInfo>>;
     fn get_transaction(&self, hash: HashValue) -> Result<Option<Transaction>>;
     fn get_transaction_info(&self, txn_hash: HashValue) -> Result<Option<BlockTransactionInfo>>;
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>>;
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>>;
     fn get_txn_info_by_block_and_index(
         &self,
@@ -46,6 +53,7 @@ pub trait ReadableChainService {
     fn epoch_info(&self) -> Result<EpochInfo>;
     fn get_epoch_info_by_number(&self, number: BlockNumber) -> Result<EpochInfo>;
     fn get_global_time_by_number(&self, number: BlockNumber) -> Result<GlobalTimeOnChain>;
+    fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo>;
     fn get_main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>>;
     fn get_block_ids(
         &self,
@@ -87,6 +95,12 @@ pub trait ChainAsyncService:
         txn_hash: HashValue,
     ) -> Result<Option<BlockTransactionInfo>>;
     async fn get_transaction_block(&self, txn_hash: HashValue) -> Result<Option<Block>>;
+    /// Get the txn info with its accumulator proof in the block `block_hash`.
+    async fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>>;
     async fn get_block_txn_infos(&self, block_hash: HashValue)
         -> Result<Vec<BlockTransactionInfo>>;
     async fn get_txn_info_by_block_and_index(
@@ -111,6 +125,7 @@ pub trait ChainAsyncService:
     async fn epoch_info(&self) -> Result<EpochInfo>;
     async fn get_epoch_info_by_number(&self, number: BlockNumber) -> Result<EpochInfo>;
     async fn get_global_time_by_number(&self, number: BlockNumber) -> Result<GlobalTimeOnChain>;
+    async fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo>;
     async fn main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>>;
     async fn get_block_ids(
         &self,
@@ -230,6 +245,24 @@ where
         }
     }
 
+    async fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        let response = self
+            .send(ChainRequest::GetTransactionProof {
+                block_id: block_hash,
+                txn_hash,
+            })
+            .await??;
+        if let ChainResponse::TransactionProof(proof) = response {
+            Ok(*proof)
+        } else {
+            bail!("get transaction proof error:{:?}", txn_hash)
+        }
+    }
+
     async fn get_block_txn_infos(
         &self,
         block_hash: HashValue,
@@ -376,6 +409,17 @@ where
             bail!("get global time error.")
         }
     }
+
+    async fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo> {
+        let response = self
+            .send(ChainRequest::GetTotalSupplyByNumber(number))
+            .await??;
+        if let ChainResponse::TotalSupply(total_supply) = response {
+            Ok(total_supply)
+        } else {
+            bail!("get total supply error.")
+        }
+    }
     async fn main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
         let response = self.send(ChainRequest::MainEvents(filter)).await??;
         if let ChainResponse::MainEvents(evts) = response {
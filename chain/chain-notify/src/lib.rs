@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod message;
+pub mod replay;
 
-use crate::message::{ContractEventNotification, Event, Notification, ThinBlock};
+use crate::message::{
+    ContractEventNotification, Event, GetNotificationsSince, Notification, NotificationRecord,
+    ThinBlock,
+};
+use crate::replay::{ReplayBuffer, DEFAULT_REPLAY_WINDOW_SIZE};
 use anyhow::{format_err, Result};
 use starcoin_logger::prelude::*;
-use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
-use starcoin_storage::{Storage, Store};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
+};
+use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_types::block::Block;
 use starcoin_types::system_events::NewHeadBlock;
 use std::sync::Arc;
@@ -15,13 +22,71 @@ use std::sync::Arc;
 /// ChainNotify watch `NewHeadBlock` message from bus,
 /// and then reproduce `Notification<ThinBlock>` and `Notification<Arc<[Event]>>` message to bus.
 /// User can subscribe the two notification to watch onchain events.
+/// It also reproduces a `Notification<NotificationRecord>` with a sequence number for every new head,
+/// and keeps the latest records in a replay window, so reconnecting subscribers can get the missed ones.
+/// The sequence number and the blocks of the window are persisted, the window is restored on restart.
 pub struct ChainNotifyHandlerService {
     store: Arc<dyn Store>,
+    storage: Arc<Storage>,
+    replay_buffer: ReplayBuffer,
 }
 
 impl ChainNotifyHandlerService {
-    pub fn new(store: Arc<dyn Store>) -> Self {
-        Self { store }
+    pub fn new(storage: Arc<Storage>) -> Result<Self> {
+        let replay_buffer = Self::restore_replay_buffer(storage.as_ref())?;
+        Ok(Self {
+            store: storage.clone(),
+            storage,
+            replay_buffer,
+        })
+    }
+
+    /// Restore the replay window from the persisted notifications, the window starts after the
+    /// latest notification whose block can not be loaded, so the restored window has no gap.
+    fn restore_replay_buffer(storage: &Storage) -> Result<ReplayBuffer> {
+        let latest_seq = match storage.get_notification_seq()? {
+            Some(latest_seq) => latest_seq,
+            None => return Ok(ReplayBuffer::default()),
+        };
+        let mut records = vec![];
+        let mut seq = latest_seq;
+        while seq > 0 && records.len() < DEFAULT_REPLAY_WINDOW_SIZE {
+            let block = match storage.get_notification_block(seq)? {
+                Some(block_id) => storage.get_block_by_hash(block_id)?,
+                None => None,
+            };
+            let block = match block {
+                Some(block) => block,
+                None => break,
+            };
+            let events = match load_events(&block, storage) {
+                Ok(events) => events,
+                Err(e) => {
+                    warn!("Fail to load the events of notification {}: {}", seq, e);
+                    break;
+                }
+            };
+            records.push(NotificationRecord::new(
+                seq,
+                ThinBlock::new(
+                    block.header().clone(),
+                    block.transactions().iter().map(|t| t.id()).collect(),
+                ),
+                events,
+            ));
+            seq -= 1;
+        }
+        records.reverse();
+        info!(
+            "Restore {} chain notifications, the latest seq is {}",
+            records.len(),
+            latest_seq
+        );
+        Ok(ReplayBuffer::restore(
+            DEFAULT_REPLAY_WINDOW_SIZE,
+            latest_seq,
+            records,
+        ))
     }
 }
 
@@ -30,7 +95,7 @@ impl ServiceFactory<Self> for ChainNotifyHandlerService {
         ctx: &mut ServiceContext<ChainNotifyHandlerService>,
     ) -> Result<ChainNotifyHandlerService> {
         let storage = ctx.get_shared::<Arc<Storage>>()?;
-        Ok(Self::new(storage))
+        Self::new(storage)
     }
 }
 
@@ -55,22 +120,48 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
         let NewHeadBlock(block_detail) = item;
         let block = block_detail.block();
         // notify header.
-        self.notify_new_block(block, ctx);
+        let thin_block = self.notify_new_block(block, ctx);
 
         // notify events
-        if let Err(e) = self.notify_events(block, self.store.clone(), ctx) {
-            error!(target: "pubsub", "fail to notify events to client, err: {}", &e);
+        let events = match self.notify_events(block, self.store.clone(), ctx) {
+            Ok(events) => events,
+            Err(e) => {
+                error!(target: "pubsub", "fail to notify events to client, err: {}", &e);
+                Vec::new().into()
+            }
+        };
+
+        // notify the sequenced record and keep it for replay.
+        let record = self.replay_buffer.push(thin_block, events);
+        if let Err(e) = self.storage.save_notification(
+            record.seq,
+            block.id(),
+            DEFAULT_REPLAY_WINDOW_SIZE as u64,
+        ) {
+            error!(target: "pubsub", "fail to save the notification {}, err: {}", record.seq, e);
         }
+        ctx.broadcast(Notification(record));
+    }
+}
+
+impl ServiceHandler<Self, GetNotificationsSince> for ChainNotifyHandlerService {
+    fn handle(
+        &mut self,
+        msg: GetNotificationsSince,
+        _ctx: &mut ServiceContext<ChainNotifyHandlerService>,
+    ) -> Result<Vec<NotificationRecord>> {
+        self.replay_buffer.since(msg.since_seq)
     }
 }
 
 impl ChainNotifyHandlerService {
-    pub fn notify_new_block(&self, block: &Block, ctx: &mut ServiceContext<Self>) {
+    pub fn notify_new_block(&self, block: &Block, ctx: &mut ServiceContext<Self>) -> ThinBlock {
         let thin_block = ThinBlock::new(
             block.header().clone(),
             block.transactions().iter().map(|t| t.id()).collect(),
         );
-        ctx.broadcast(Notification(thin_block));
+        ctx.broadcast(Notification(thin_block.clone()));
+        thin_block
     }
 
     pub fn notify_events(
@@ -78,26 +169,96 @@ impl ChainNotifyHandlerService {
         block: &Block,
         store: Arc<dyn Store>,
         ctx: &mut ServiceContext<Self>,
-    ) -> Result<()> {
-        let block_number = block.header().number();
-        let block_id = block.id();
-        let txn_info_ids = store.get_block_txn_info_ids(block_id)?;
-        let mut all_events: Vec<Event> = vec![];
-        for (i, txn_info_id) in txn_info_ids.into_iter().enumerate().rev() {
-            let txn_hash = store
-                .get_transaction_info(txn_info_id)?
-                .map(|info| info.transaction_hash())
-                .ok_or_else(|| format_err!("cannot find txn info by it's id {}", &txn_info_id))?;
-            // get events directly by txn_info_id
-            let events = store.get_contract_events(txn_info_id)?.unwrap_or_default();
-            all_events.extend(
-                events
-                    .into_iter()
-                    .map(|evt| Event::new(block_id, block_number, txn_hash, Some(i as u32), evt)),
-            );
-        }
-        let events_notification: ContractEventNotification = Notification(all_events.into());
+    ) -> Result<Arc<[Event]>> {
+        let all_events = load_events(block, store.as_ref())?;
+        let events_notification: ContractEventNotification = Notification(all_events.clone());
         ctx.broadcast(events_notification);
-        Ok(())
+        Ok(all_events)
+    }
+}
+
+/// Load the events of the block from the store.
+fn load_events(block: &Block, store: &dyn Store) -> Result<Arc<[Event]>> {
+    let block_number = block.header().number();
+    let block_id = block.id();
+    let txn_info_ids = store.get_block_txn_info_ids(block_id)?;
+    let mut all_events: Vec<Event> = vec![];
+    for (i, txn_info_id) in txn_info_ids.into_iter().enumerate().rev() {
+        let txn_hash = store
+            .get_transaction_info(txn_info_id)?
+            .map(|info| info.transaction_hash())
+            .ok_or_else(|| format_err!("cannot find txn info by it's id {}", &txn_info_id))?;
+        // get events directly by txn_info_id
+        let events = store.get_contract_events(txn_info_id)?.unwrap_or_default();
+        all_events.extend(
+            events
+                .into_iter()
+                .map(|evt| Event::new(block_id, block_number, txn_hash, Some(i as u32), evt)),
+        );
+    }
+    Ok(all_events.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::HashValue;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_types::block::{BlockBody, BlockHeader};
+
+    fn save_notifications(storage: &Storage, count: u64) -> Vec<Block> {
+        (1..=count)
+            .map(|seq| {
+                let block = Block::new(BlockHeader::random(), BlockBody::new(vec![], None));
+                storage.commit_block(block.clone()).unwrap();
+                storage.save_block_txn_info_ids(block.id(), vec![]).unwrap();
+                storage
+                    .save_notification(seq, block.id(), DEFAULT_REPLAY_WINDOW_SIZE as u64)
+                    .unwrap();
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_replay_buffer() {
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        let service = ChainNotifyHandlerService::new(storage.clone()).unwrap();
+        assert_eq!(service.replay_buffer.latest_seq(), 0);
+
+        let blocks = save_notifications(storage.as_ref(), 3);
+        let service = ChainNotifyHandlerService::new(storage).unwrap();
+        assert_eq!(service.replay_buffer.latest_seq(), 3);
+        let records = service.replay_buffer.since(0).unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.seq, record.block.header.id()))
+                .collect::<Vec<_>>(),
+            blocks
+                .iter()
+                .enumerate()
+                .map(|(i, block)| (i as u64 + 1, block.id()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_restore_replay_buffer_without_gap() {
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        let blocks = save_notifications(storage.as_ref(), 3);
+        // the block of seq 2 is not in the storage.
+        storage
+            .save_notification(2, HashValue::random(), DEFAULT_REPLAY_WINDOW_SIZE as u64)
+            .unwrap();
+        storage
+            .save_notification(3, blocks[2].id(), DEFAULT_REPLAY_WINDOW_SIZE as u64)
+            .unwrap();
+        let service = ChainNotifyHandlerService::new(storage).unwrap();
+        assert_eq!(service.replay_buffer.latest_seq(), 3);
+        // the notifications before the missing block are dropped from the window.
+        assert!(service.replay_buffer.since(0).is_err());
+        assert!(service.replay_buffer.since(1).is_err());
+        assert_eq!(service.replay_buffer.since(2).unwrap().len(), 1);
     }
 }
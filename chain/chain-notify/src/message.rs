@@ -1,7 +1,9 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::Result;
 use starcoin_crypto::HashValue;
+use starcoin_service_registry::ServiceRequest;
 use starcoin_types::block::BlockHeader;
 use starcoin_types::{block::BlockNumber, contract_event::ContractEvent};
 use std::sync::Arc;
@@ -11,6 +13,7 @@ pub struct Notification<T>(pub T);
 
 pub type ContractEventNotification = Notification<Arc<[Event]>>;
 pub type NewHeadEventNotification = Notification<ThinBlock>;
+pub type SequencedNotification = Notification<NotificationRecord>;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event {
@@ -60,3 +63,28 @@ impl ThinBlock {
         &self.body
     }
 }
+
+/// The new head block and its events, with a sequence number assigned by the ChainNotify service.
+/// The sequence number increases by one for every new head, and it is persisted, so it continues after the node restarts.
+#[derive(Debug, Clone)]
+pub struct NotificationRecord {
+    pub seq: u64,
+    pub block: ThinBlock,
+    pub events: Arc<[Event]>,
+}
+
+impl NotificationRecord {
+    pub fn new(seq: u64, block: ThinBlock, events: Arc<[Event]>) -> Self {
+        Self { seq, block, events }
+    }
+}
+
+/// Get the notifications after `since_seq` from the replay window.
+#[derive(Debug, Clone)]
+pub struct GetNotificationsSince {
+    pub since_seq: u64,
+}
+
+impl ServiceRequest for GetNotificationsSince {
+    type Response = Result<Vec<NotificationRecord>>;
+}
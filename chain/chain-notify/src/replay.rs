@@ -0,0 +1,145 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::message::{Event, NotificationRecord, ThinBlock};
+use anyhow::{bail, Result};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Default count of the latest notifications kept for replay.
+pub const DEFAULT_REPLAY_WINDOW_SIZE: usize = 1024;
+
+/// A bounded buffer of the latest sequenced notifications,
+/// reconnecting subscribers can replay the notifications they missed within the window.
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    capacity: usize,
+    latest_seq: u64,
+    records: VecDeque<NotificationRecord>,
+}
+
+impl ReplayBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            latest_seq: 0,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Restore the window with the latest sequence number and the records before it, in order.
+    pub fn restore(capacity: usize, latest_seq: u64, records: Vec<NotificationRecord>) -> Self {
+        let skip = records.len().saturating_sub(capacity);
+        Self {
+            capacity,
+            latest_seq,
+            records: records.into_iter().skip(skip).collect(),
+        }
+    }
+
+    /// The sequence number of the latest notification, 0 if no notification has been pushed.
+    pub fn latest_seq(&self) -> u64 {
+        self.latest_seq
+    }
+
+    /// The sequence number of the oldest notification kept in the window.
+    pub fn first_seq(&self) -> Option<u64> {
+        self.records.front().map(|record| record.seq)
+    }
+
+    /// Assign the next sequence number to the notification and keep it in the window.
+    pub fn push(&mut self, block: ThinBlock, events: Arc<[Event]>) -> NotificationRecord {
+        self.latest_seq += 1;
+        let record = NotificationRecord::new(self.latest_seq, block, events);
+        if self.capacity == 0 {
+            return record;
+        }
+        if self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record.clone());
+        record
+    }
+
+    /// Get the notifications after `since_seq`,
+    /// return error if some of the notifications after `since_seq` are out of the window.
+    pub fn since(&self, since_seq: u64) -> Result<Vec<NotificationRecord>> {
+        if since_seq > self.latest_seq {
+            bail!(
+                "seq {} is greater than latest seq {}, the node may be restarted",
+                since_seq,
+                self.latest_seq
+            );
+        }
+        let first_seq = self.first_seq().unwrap_or(self.latest_seq + 1);
+        if since_seq + 1 < first_seq {
+            bail!(
+                "seq {} is out of replay window [{}, {}]",
+                since_seq,
+                first_seq,
+                self.latest_seq
+            );
+        }
+        Ok(self
+            .records
+            .iter()
+            .filter(|record| record.seq > since_seq)
+            .cloned()
+            .collect())
+    }
+}
+
+impl Default for ReplayBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_WINDOW_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_types::block::BlockHeader;
+
+    fn push_blocks(buffer: &mut ReplayBuffer, count: usize) {
+        for _ in 0..count {
+            buffer.push(
+                ThinBlock::new(BlockHeader::random(), vec![]),
+                Vec::new().into(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_window() {
+        let mut buffer = ReplayBuffer::new(3);
+        assert!(buffer.since(0).unwrap().is_empty());
+        assert!(buffer.since(1).is_err());
+
+        push_blocks(&mut buffer, 5);
+        assert_eq!(buffer.latest_seq(), 5);
+        assert_eq!(buffer.first_seq(), Some(3));
+
+        let records = buffer.since(2).unwrap();
+        assert_eq!(
+            records.iter().map(|record| record.seq).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(buffer.since(4).unwrap().len(), 1);
+        assert!(buffer.since(5).unwrap().is_empty());
+        // seq 2 has been dropped from the window.
+        assert!(buffer.since(1).is_err());
+        assert!(buffer.since(6).is_err());
+    }
+
+    #[test]
+    fn test_restore_window() {
+        let mut buffer = ReplayBuffer::new(5);
+        push_blocks(&mut buffer, 5);
+        let records = buffer.since(0).unwrap();
+        let restored = ReplayBuffer::restore(3, buffer.latest_seq(), records);
+        assert_eq!(restored.latest_seq(), 5);
+        assert_eq!(restored.first_seq(), Some(3));
+        assert!(restored.since(1).is_err());
+        assert_eq!(restored.since(2).unwrap().len(), 3);
+    }
+}
@@ -34,6 +34,8 @@ pub struct OpenedBlock {
     txn_accumulator: MerkleAccumulator,
 
     gas_used: u64,
+    /// The sum of gas_used * gas_unit_price of included user txns.
+    total_fee: u128,
     included_user_txns: Vec<SignedUserTransaction>,
     uncles: Vec<BlockHeader>,
     chain_id: ChainId,
@@ -84,6 +86,7 @@ impl OpenedBlock {
             state: chain_state,
             txn_accumulator,
             gas_used: 0,
+            total_fee: 0,
             included_user_txns: vec![],
             uncles,
             chain_id,
@@ -98,6 +101,10 @@ impl OpenedBlock {
         self.gas_used
     }
 
+    pub fn total_fee(&self) -> u128 {
+        self.total_fee
+    }
+
     pub fn gas_limit(&self) -> u64 {
         self.gas_limit
     }
@@ -176,8 +183,11 @@ impl OpenedBlock {
                     let gas_used = output.gas_used();
                     self.push_txn_and_state(txn_hash, output)?;
                     self.gas_used += gas_used;
-                    self.included_user_txns
-                        .push(txn.try_into().expect("user txn"));
+                    let user_txn: SignedUserTransaction = txn.try_into().expect("user txn");
+                    self.total_fee = self.total_fee.saturating_add(
+                        u128::from(gas_used) * u128::from(user_txn.gas_unit_price()),
+                    );
+                    self.included_user_txns.push(user_txn);
                 }
             };
         }
@@ -256,6 +266,7 @@ impl OpenedBlock {
             accumulator_root,
             state_root,
             self.gas_used,
+            self.total_fee,
             body,
             self.chain_id,
             self.difficulty,
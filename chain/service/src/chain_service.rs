@@ -4,7 +4,7 @@
 use anyhow::{format_err, Error, Result};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::message::{ChainRequest, ChainResponse};
-use starcoin_chain_api::{ChainReader, ChainWriter, ReadableChainService};
+use starcoin_chain_api::{ChainReader, ChainWriter, ReadableChainService, TotalSupplyInfo};
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
@@ -15,6 +15,7 @@ use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_types::block::{BlockSummary, EpochUncleSummary, ExecutedBlock, UncleSummary};
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::system_events::NewHeadBlock;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -146,6 +147,11 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::GetTransactionInfo(hash) => Ok(ChainResponse::TransactionInfo(
                 self.inner.get_transaction_info(hash)?,
             )),
+            ChainRequest::GetTransactionProof { block_id, txn_hash } => {
+                Ok(ChainResponse::TransactionProof(Box::new(
+                    self.inner.get_transaction_proof(block_id, txn_hash)?,
+                )))
+            }
             ChainRequest::GetBlocksByNumber(number, count) => Ok(ChainResponse::BlockVec(
                 self.inner.main_blocks_by_number(number, count)?,
             )),
@@ -208,6 +214,9 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::GetGlobalTimeByNumber(number) => Ok(ChainResponse::GlobalTime(
                 self.inner.get_global_time_by_number(number)?,
             )),
+            ChainRequest::GetTotalSupplyByNumber(number) => Ok(ChainResponse::TotalSupply(
+                self.inner.get_total_supply_by_number(number)?,
+            )),
             ChainRequest::MainEvents(filter) => Ok(ChainResponse::MainEvents(
                 self.inner.get_main_events(filter)?,
             )),
@@ -337,6 +346,14 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.main.get_transaction_info(txn_hash)
     }
 
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>, Error> {
+        self.main.get_transaction_proof(block_id, txn_hash)
+    }
+
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>, Error> {
         self.storage.get_block_transaction_infos(block_id)
     }
@@ -395,6 +412,10 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.main.get_global_time_by_number(number)
     }
 
+    fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo> {
+        self.main.get_total_supply_by_number(number)
+    }
+
     fn get_main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
         self.main.filter_events(filter)
     }
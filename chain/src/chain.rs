@@ -1,6 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::metrics::CHAIN_METRICS;
+use crate::prefetch::{prefetch_paths, PrefetchHandle};
 use crate::verifier::{BlockVerifier, FullVerifier};
 use anyhow::{ensure, format_err, Result};
 use consensus::Consensus;
@@ -12,14 +14,17 @@ use starcoin_accumulator::{
 };
 use starcoin_chain_api::{
     verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
-    MintedUncleNumber, VerifiedBlock, VerifyBlockField,
+    MintedUncleNumber, TotalSupplyInfo, VerifiedBlock, VerifyBlockField,
 };
 use starcoin_open_block::OpenedBlock;
-use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
+use starcoin_state_api::{
+    AccountStateReader, ChainState, ChainStateReader, ChainStateWriter, StateReaderExt,
+};
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::block::BlockIdAndNumber;
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
@@ -56,6 +61,10 @@ pub struct BlockChain {
     time_service: Arc<dyn TimeService>,
     uncles: HashMap<HashValue, MintedUncleNumber>,
     epoch: Epoch,
+    /// How many threads to execute the block transactions, 1 means execute serially.
+    execution_concurrency: usize,
+    /// Whether to prefetch the state read by the block in the background when executing it.
+    state_prefetch: bool,
 }
 
 impl BlockChain {
@@ -110,6 +119,8 @@ impl BlockChain {
             storage,
             uncles: HashMap::new(),
             epoch,
+            execution_concurrency: 1,
+            state_prefetch: false,
         };
         watch(CHAIN_WATCH_NAME, "n1251");
         match uncles {
@@ -142,10 +153,22 @@ impl BlockChain {
             &genesis_epoch,
             None,
             genesis_block,
+            1,
+            false,
         )?;
         Self::new(time_service, executed_block.block.id(), storage)
     }
 
+    /// Execute the independent transactions of block in parallel by `concurrency` threads.
+    pub fn set_execution_concurrency(&mut self, concurrency: usize) {
+        self.execution_concurrency = concurrency;
+    }
+
+    /// Prefetch the state of the block senders and receivers in the background when executing the block.
+    pub fn set_state_prefetch(&mut self, state_prefetch: bool) {
+        self.state_prefetch = state_prefetch;
+    }
+
     pub fn current_epoch_uncles_size(&self) -> u64 {
         self.uncles.len() as u64
     }
@@ -357,6 +380,8 @@ impl BlockChain {
         epoch: &Epoch,
         parent_status: Option<ChainStatus>,
         block: Block,
+        execution_concurrency: usize,
+        state_prefetch: bool,
     ) -> Result<ExecutedBlock> {
         let header = block.header();
         debug_assert!(header.is_genesis() || parent_status.is_some());
@@ -381,9 +406,34 @@ impl BlockChain {
             t
         };
 
+        // genesis block is executed on an empty state, nothing to prefetch.
+        let prefetch = if state_prefetch && parent_status.is_some() {
+            Some(PrefetchHandle::spawn(
+                statedb.fork(),
+                prefetch_paths(&block),
+            ))
+        } else {
+            None
+        };
+
         watch(CHAIN_WATCH_NAME, "n21");
-        let executed_data =
-            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?;
+        let executed_data = if execution_concurrency > 1 {
+            starcoin_executor::block_execute_parallel(
+                &statedb,
+                txns.clone(),
+                epoch.block_gas_limit(),
+                execution_concurrency,
+            )?
+        } else {
+            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?
+        };
+        if let Some(prefetch) = prefetch {
+            let prefetched = prefetch.finish();
+            debug!(
+                "Prefetched {} state paths of block {}",
+                prefetched, block_id
+            );
+        }
         watch(CHAIN_WATCH_NAME, "n22");
         let state_root = executed_data.state_root;
         let vec_transaction_info = &executed_data.txn_infos;
@@ -412,7 +462,7 @@ impl BlockChain {
         let executed_accumulator_root = {
             let included_txn_info_hashes: Vec<_> =
                 vec_transaction_info.iter().map(|info| info.id()).collect();
-            txn_accumulator.append(&included_txn_info_hashes)?
+            txn_accumulator.append_batch(&included_txn_info_hashes, true)?
         };
 
         verify_block!(
@@ -460,6 +510,7 @@ impl BlockChain {
             (executed_data.txn_infos, executed_data.txn_events),
         )?;
         watch(CHAIN_WATCH_NAME, "n26");
+        CHAIN_METRICS.executed_blocks.inc();
         Ok(ExecutedBlock { block, block_info })
     }
 
@@ -599,6 +650,76 @@ impl ChainReader for BlockChain {
         }
     }
 
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        txn_hash: HashValue,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        let block_info = match self.storage.get_block_info(block_id)? {
+            Some(block_info) => block_info,
+            None => return Ok(None),
+        };
+        let txn_info = match self.get_transaction_info(txn_hash)? {
+            Some(txn_info) => txn_info,
+            None => return Ok(None),
+        };
+        let txn_block_info = self
+            .storage
+            .get_block_info(txn_info.block_id())?
+            .ok_or_else(|| format_err!("Can not find block info by id {}", txn_info.block_id()))?;
+        let block_txn_infos = self
+            .storage
+            .get_block_transaction_infos(txn_info.block_id())?;
+        let txn_index_in_block = block_txn_infos
+            .iter()
+            .position(|info| info.transaction_hash() == txn_hash)
+            .ok_or_else(|| {
+                format_err!(
+                    "Can not find txn {} in block {}",
+                    txn_hash,
+                    txn_info.block_id()
+                )
+            })?;
+        // txn infos of a block are appended to the accumulator in order.
+        let transaction_global_index = txn_block_info
+            .txn_accumulator_info
+            .num_leaves
+            .checked_sub(block_txn_infos.len() as u64)
+            .map(|start_index| start_index.saturating_add(txn_index_in_block as u64))
+            .ok_or_else(|| {
+                format_err!(
+                    "Invalid txn accumulator info of block {}",
+                    txn_info.block_id()
+                )
+            })?;
+        if transaction_global_index >= block_info.txn_accumulator_info.num_leaves {
+            return Ok(None);
+        }
+
+        let accumulator = info_2_accumulator(
+            block_info.txn_accumulator_info,
+            AccumulatorStoreType::Transaction,
+            self.storage.as_ref(),
+        );
+        if accumulator.get_leaf(transaction_global_index)? != Some(txn_info.id()) {
+            return Ok(None);
+        }
+        let proof = accumulator
+            .get_proof(transaction_global_index)?
+            .ok_or_else(|| {
+                format_err!(
+                    "Can not get txn accumulator proof at index {}",
+                    transaction_global_index
+                )
+            })?;
+        let (_, txn_info): (HashValue, TransactionInfo) = txn_info.into();
+        Ok(Some(TransactionInfoWithProof::new(
+            txn_info,
+            transaction_global_index,
+            proof,
+        )))
+    }
+
     fn chain_state_reader(&self) -> &dyn ChainStateReader {
         &self.statedb
     }
@@ -667,6 +788,28 @@ impl ChainReader for BlockChain {
         }
     }
 
+    fn get_total_supply_by_number(&self, number: BlockNumber) -> Result<TotalSupplyInfo> {
+        let header = self
+            .get_header_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        let chain_state = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(header.state_root()),
+        );
+        let total_supply = chain_state
+            .get_stc_info()?
+            .ok_or_else(|| format_err!("STC token info is none."))?
+            .total_value();
+        let treasury_balance = chain_state
+            .get_stc_treasury()?
+            .map(|treasury| treasury.balance);
+        Ok(TotalSupplyInfo {
+            block_number: number,
+            total_supply,
+            treasury_balance,
+        })
+    }
+
     fn get_block_ids(
         &self,
         start_number: BlockNumber,
@@ -711,12 +854,15 @@ impl ChainReader for BlockChain {
         } else {
             None
         };
-        BlockChain::new_with_uncles(
+        let mut chain = BlockChain::new_with_uncles(
             self.time_service.clone(),
             head,
             uncles,
             self.storage.clone(),
-        )
+        )?;
+        chain.set_execution_concurrency(self.execution_concurrency);
+        chain.set_state_prefetch(self.state_prefetch);
+        Ok(chain)
     }
 
     fn epoch_uncles(&self) -> &HashMap<HashValue, MintedUncleNumber> {
@@ -759,6 +905,8 @@ impl ChainReader for BlockChain {
             &self.epoch,
             Some(self.status.status.clone()),
             verified_block.0,
+            self.execution_concurrency,
+            self.state_prefetch,
         )
     }
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 #![deny(clippy::integer_arithmetic)]
 mod chain;
+mod metrics;
+pub mod prefetch;
 pub mod verifier;
 pub use chain::BlockChain;
 pub use starcoin_chain_api::{ChainReader, ChainWriter};
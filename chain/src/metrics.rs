@@ -0,0 +1,42 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{
+    register_histogram, register_int_counter, Histogram, HistogramOpts, IntCounter, Opts,
+    PrometheusError,
+};
+
+pub static CHAIN_METRICS: Lazy<ChainMetrics> = Lazy::new(|| ChainMetrics::register().unwrap());
+
+#[derive(Clone)]
+pub struct ChainMetrics {
+    pub verify_header_time: Histogram,
+    pub state_prefetched_paths: IntCounter,
+    pub executed_blocks: IntCounter,
+}
+
+impl ChainMetrics {
+    pub fn register() -> Result<Self, PrometheusError> {
+        let verify_header_time = register_histogram!(HistogramOpts::new(
+            "chain_verify_header_time",
+            "Histogram of block header verify time, include the consensus verify"
+        )
+        .namespace("starcoin"))?;
+        let state_prefetched_paths = register_int_counter!(Opts::new(
+            "chain_state_prefetched_paths",
+            "Counter of the state paths prefetched before the block execution"
+        )
+        .namespace("starcoin"))?;
+        let executed_blocks = register_int_counter!(Opts::new(
+            "chain_executed_blocks",
+            "Counter of the executed blocks, include the blocks of the side chains"
+        )
+        .namespace("starcoin"))?;
+        Ok(Self {
+            verify_header_time,
+            state_prefetched_paths,
+            executed_blocks,
+        })
+    }
+}
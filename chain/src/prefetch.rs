@@ -0,0 +1,145 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prefetch the state of a block before it is executed.
+//!
+//! The accounts and resources the block will most likely read, such as the balance of the
+//! transaction senders and the transfer receivers, are loaded by a background thread while the
+//! block is executing. The state nodes read by the prefetcher are kept in the storage cache, so
+//! the storage reads of the execution are overlapped with the execution of the prior transactions.
+
+use crate::metrics::CHAIN_METRICS;
+use logger::prelude::*;
+use starcoin_statedb::ChainStateDB;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{AccountResource, BalanceResource};
+use starcoin_types::block::Block;
+use starcoin_types::transaction::{SignedUserTransaction, TransactionPayload};
+use starcoin_vm_types::account_config::{core_code_address, stc_type_tag};
+use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+const TRANSFER_SCRIPTS_MODULE_NAME: &str = "TransferScripts";
+/// The transfer script functions which take the receiver as the first argument.
+const TRANSFER_FUNCTION_NAMES: [&str; 4] = [
+    "peer_to_peer",
+    "peer_to_peer_v2",
+    "peer_to_peer_with_metadata",
+    "peer_to_peer_with_metadata_v2",
+];
+
+fn account_paths(address: AccountAddress, token: TypeTag) -> Vec<AccessPath> {
+    vec![
+        AccessPath::resource_access_path(address, AccountResource::struct_tag()),
+        AccessPath::resource_access_path(address, BalanceResource::struct_tag_for_token(token)),
+    ]
+}
+
+/// The paths read by the known payload patterns, only the transfer for now.
+fn payload_paths(txn: &SignedUserTransaction) -> Vec<AccessPath> {
+    let script_function = match txn.payload() {
+        TransactionPayload::ScriptFunction(script_function) => script_function,
+        _ => return vec![],
+    };
+    let module = script_function.module();
+    if module.address() != &core_code_address()
+        || module.name().as_str() != TRANSFER_SCRIPTS_MODULE_NAME
+        || !TRANSFER_FUNCTION_NAMES.contains(&script_function.function().as_str())
+    {
+        return vec![];
+    }
+    let receiver = match script_function
+        .args()
+        .first()
+        .and_then(|arg| bcs_ext::from_bytes::<AccountAddress>(arg).ok())
+    {
+        Some(receiver) => receiver,
+        None => return vec![],
+    };
+    match script_function.ty_args().first() {
+        Some(token) => account_paths(receiver, token.clone()),
+        None => vec![],
+    }
+}
+
+/// The state paths the block will most likely read when it is executed, in the execution order.
+pub fn prefetch_paths(block: &Block) -> Vec<AccessPath> {
+    let mut paths = account_paths(block.header().author(), stc_type_tag());
+    for txn in block.transactions() {
+        let gas_token = TokenCode::from_str(txn.gas_token_code())
+            .map(|token_code| token_code.into())
+            .unwrap_or_else(|_| stc_type_tag());
+        paths.extend(account_paths(txn.sender(), gas_token));
+        paths.extend(payload_paths(txn));
+    }
+    let mut visited = HashSet::new();
+    paths.retain(|path| visited.insert(path.clone()));
+    paths
+}
+
+/// The handle of the prefetch running in the background.
+pub struct PrefetchHandle {
+    finished: Arc<AtomicBool>,
+    handle: Option<JoinHandle<usize>>,
+}
+
+impl PrefetchHandle {
+    /// Load the paths by a forked statedb of the block's parent state in a background thread.
+    pub fn spawn(statedb: ChainStateDB, paths: Vec<AccessPath>) -> Self {
+        let finished = Arc::new(AtomicBool::new(false));
+        let thread_finished = finished.clone();
+        let handle = std::thread::Builder::new()
+            .name("state-prefetch".to_string())
+            .spawn(move || {
+                let mut prefetched = 0usize;
+                for path in paths {
+                    // the execution is done, the rest paths are useless.
+                    if thread_finished.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Err(e) = statedb.get(&path) {
+                        debug!("Prefetch state {} failed: {:?}", path, e);
+                        continue;
+                    }
+                    prefetched = prefetched.saturating_add(1);
+                }
+                prefetched
+            });
+        let handle = match handle {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("Spawn state prefetch thread failed: {:?}", e);
+                None
+            }
+        };
+        Self { finished, handle }
+    }
+
+    /// Stop the prefetch once the execution is done, return the count of the prefetched paths.
+    pub fn finish(mut self) -> usize {
+        self.finished.store(true, Ordering::Relaxed);
+        let prefetched = self
+            .handle
+            .take()
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+        CHAIN_METRICS
+            .state_prefetched_paths
+            .inc_by(prefetched as u64);
+        prefetched
+    }
+}
+
+impl Drop for PrefetchHandle {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+}
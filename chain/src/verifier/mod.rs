@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::metrics::CHAIN_METRICS;
 use anyhow::{format_err, Result};
 use clap::arg_enum;
 use consensus::{Consensus, ConsensusVerifyError};
@@ -32,7 +33,9 @@ pub trait BlockVerifier {
         watch(CHAIN_WATCH_NAME, "n11");
         //verify header
         let new_block_header = new_block.header();
+        let timer = CHAIN_METRICS.verify_header_time.start_timer();
         Self::verify_header(current_chain, new_block_header)?;
+        timer.observe_duration();
         watch(CHAIN_WATCH_NAME, "n12");
         //verify body
         let body_hash = new_block.body.hash();
@@ -5,13 +5,16 @@ use anyhow::Result;
 use consensus::Consensus;
 use crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
 use starcoin_account_api::AccountInfo;
+use starcoin_chain::prefetch::prefetch_paths;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
 use starcoin_chain_mock::MockChain;
 use starcoin_config::NodeConfig;
 use starcoin_config::{BuiltinNetworkID, ChainNetwork};
 use starcoin_executor::{build_transfer_from_association, DEFAULT_EXPIRATION_TIME};
+use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address;
+use starcoin_types::account_config::{association_address, stc_type_tag, BalanceResource};
 use starcoin_types::block::{Block, BlockHeader};
 use starcoin_types::filter::Filter;
 use starcoin_vm_types::account_config::genesis_address;
@@ -30,6 +33,8 @@ fn test_chain_filter_events() {
             from_block: 1,
             to_block: 5,
             event_keys: vec![evt_key],
+            addrs: vec![],
+            type_tags: vec![],
             limit: None,
             reverse: false,
         };
@@ -41,11 +46,50 @@ fn test_chain_filter_events() {
         assert_eq!(evt.event.key(), &evt_key);
     }
 
+    // test filter by address and move type.
+    {
+        let evt_key = EventKey::new_from_address(&genesis_address(), 4);
+        let type_tag = mock_chain
+            .head()
+            .filter_events(Filter {
+                from_block: 1,
+                to_block: 1,
+                event_keys: vec![evt_key],
+                addrs: vec![],
+                type_tags: vec![],
+                limit: None,
+                reverse: false,
+            })
+            .unwrap()
+            .first()
+            .unwrap()
+            .event
+            .type_tag()
+            .clone();
+        let event_filter = Filter {
+            from_block: 1,
+            to_block: 5,
+            event_keys: vec![],
+            addrs: vec![genesis_address()],
+            type_tags: vec![type_tag.clone()],
+            limit: None,
+            reverse: false,
+        };
+        let evts = mock_chain.head().filter_events(event_filter).unwrap();
+        assert!(evts.len() >= 5);
+        for evt in evts {
+            assert_eq!(evt.event.type_tag(), &type_tag);
+            assert_eq!(evt.event.key().get_creator_address(), genesis_address());
+        }
+    }
+
     {
         let event_filter = Filter {
             from_block: 1,
             to_block: 10,
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+            addrs: vec![],
+            type_tags: vec![],
             limit: Some(5),
             reverse: false,
         };
@@ -60,6 +104,8 @@ fn test_chain_filter_events() {
             from_block: 1,
             to_block: 10,
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+            addrs: vec![],
+            type_tags: vec![],
             limit: Some(5),
             reverse: true,
         };
@@ -76,6 +122,8 @@ fn test_chain_filter_events() {
             from_block: 0,
             to_block: 10,
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+            addrs: vec![],
+            type_tags: vec![],
             limit: Some(20),
             reverse: true,
         };
@@ -92,6 +140,8 @@ fn test_chain_filter_events() {
             from_block: 0,
             to_block: 20,
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
+            addrs: vec![],
+            type_tags: vec![],
             limit: Some(20),
             reverse: true,
         };
@@ -407,7 +457,7 @@ async fn test_block_chain_txn_info_fork_mapping() -> Result<()> {
         .consensus()
         .create_block(template_b2, config.net().time_service().as_ref())?;
 
-    block_chain.apply(block_b2)?;
+    block_chain.apply(block_b2.clone())?;
     let (template_b3, excluded) = block_chain2.create_block_template(
         *miner_account.address(),
         Some(miner_account.public_key.authentication_key()),
@@ -430,6 +480,15 @@ async fn test_block_chain_txn_info_fork_mapping() -> Result<()> {
     let txn_info = block_chain.get_transaction_info(tnx_hash)?;
     assert!(txn_info.is_some());
     assert_eq!(txn_info.unwrap().transaction_hash(), tnx_hash);
+
+    let txn_proof = block_chain
+        .get_transaction_proof(block_b2.id(), tnx_hash)?
+        .expect("txn proof should exist.");
+    txn_proof.verify(block_b2.header(), tnx_hash)?;
+    assert!(txn_proof.verify(block_b1.header(), tnx_hash).is_err());
+    assert!(block_chain
+        .get_transaction_proof(block_b1.id(), tnx_hash)?
+        .is_none());
     Ok(())
 }
 
@@ -449,3 +508,52 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test(timeout = 120)]
+fn test_apply_block_with_state_prefetch() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let mut block_chain = test_helper::gen_blockchain_for_test(config.net())?;
+    block_chain.set_state_prefetch(true);
+    let miner_account = AccountInfo::random();
+
+    let public_key = Ed25519PrivateKey::genesis().public_key();
+    let receiver = account_address::from_public_key(&public_key);
+    let signed_txn = build_transfer_from_association(
+        receiver,
+        Some(AuthenticationKey::ed25519(&public_key)),
+        0,
+        10000,
+        config.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        config.net(),
+    )
+    .as_signed_user_txn()?
+    .clone();
+    let (template, excluded) = block_chain.create_block_template(
+        *miner_account.address(),
+        Some(miner_account.public_key.authentication_key()),
+        None,
+        vec![signed_txn],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = block_chain
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+
+    let paths = prefetch_paths(&block);
+    for address in &[*miner_account.address(), association_address(), receiver] {
+        assert!(
+            paths.contains(&AccessPath::resource_access_path(
+                *address,
+                BalanceResource::struct_tag_for_token(stc_type_tag()),
+            )),
+            "balance of {} should be prefetched",
+            address
+        );
+    }
+
+    block_chain.apply(block.clone())?;
+    assert_eq!(block_chain.current_header().id(), block.id());
+    Ok(())
+}
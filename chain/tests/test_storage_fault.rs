@@ -0,0 +1,106 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use consensus::Consensus;
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+use starcoin_config::ChainNetwork;
+use starcoin_genesis::Genesis;
+use starcoin_types::block::Block;
+use std::sync::Arc;
+use storage::fault_injection::FaultInjector;
+use storage::storage::StorageInstance;
+use storage::Storage;
+
+fn new_chain(net: &ChainNetwork) -> Result<(BlockChain, Arc<FaultInjector>)> {
+    let injector = Arc::new(FaultInjector::new());
+    let storage = Arc::new(Storage::new(
+        StorageInstance::new_fault_injection_instance(
+            StorageInstance::new_cache_instance(),
+            injector.clone(),
+        ),
+    )?);
+    let chain_info = Genesis::load_or_build(net)?.execute_genesis_block(net, storage.clone())?;
+    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage)?;
+    Ok((chain, injector))
+}
+
+fn new_block(net: &ChainNetwork, chain: &BlockChain) -> Result<Block> {
+    let miner = AccountInfo::random();
+    let (template, _) = chain.create_block_template(
+        *miner.address(),
+        Some(miner.public_key.authentication_key()),
+        None,
+        vec![],
+        vec![],
+        None,
+    )?;
+    chain
+        .consensus()
+        .create_block(template, net.time_service().as_ref())
+}
+
+/// Restart the chain from the head, as the node does after a crash.
+fn restart(net: &ChainNetwork, chain: &BlockChain) -> Result<BlockChain> {
+    BlockChain::new(
+        net.time_service(),
+        chain.current_header().id(),
+        chain.get_storage(),
+    )
+}
+
+#[stest::test]
+fn test_block_import_recover_from_write_failure() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let (mut chain, injector) = new_chain(&net)?;
+    let genesis_id = chain.current_header().id();
+    let block = new_block(&net, &chain)?;
+
+    // fail a write in the middle of the import.
+    injector.fail_writes(3, 1);
+    assert!(chain.apply(block.clone()).is_err());
+    assert_eq!(injector.injected_faults(), 1);
+    assert_eq!(chain.current_header().id(), genesis_id);
+
+    // retry on the same chain.
+    let executed_block = chain.apply(block.clone())?;
+    assert_eq!(chain.current_header().id(), block.id());
+
+    // and after a restart.
+    let (mut chain, injector) = new_chain(&net)?;
+    injector.fail_writes(3, 1);
+    assert!(chain.apply(block.clone()).is_err());
+    let mut chain = restart(&net, &chain)?;
+    assert_eq!(
+        chain.apply(block.clone())?.block_info,
+        executed_block.block_info
+    );
+    assert_eq!(
+        chain.get_block_info(Some(block.id()))?,
+        Some(executed_block.block_info)
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_block_import_recover_from_torn_batch() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let (mut chain, injector) = new_chain(&net)?;
+    let genesis_id = chain.current_header().id();
+    let block = new_block(&net, &chain)?;
+
+    injector.tear_batches(0, 1);
+    assert!(chain.apply(block.clone()).is_err());
+    assert_eq!(injector.injected_faults(), 1);
+    assert_eq!(chain.current_header().id(), genesis_id);
+
+    let mut chain = restart(&net, &chain)?;
+    chain.apply(block.clone())?;
+    assert_eq!(chain.current_header().id(), block.id());
+    // the next block is built on the recovered state.
+    let next_block = new_block(&net, &chain)?;
+    chain.apply(next_block)?;
+    assert_eq!(chain.current_header().number(), 2);
+    Ok(())
+}
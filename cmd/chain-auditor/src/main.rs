@@ -0,0 +1,298 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use starcoin_block_file::BlockFileReader;
+use starcoin_chain::verifier::FullVerifier;
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_chain_auditor::report::{
+    now_secs, ChainVerificationReport, SignedChainVerificationReport, VerifiedHead,
+    CHAIN_VERIFICATION_REPORT_VERSION,
+};
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, RocksdbConfig};
+use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
+use starcoin_genesis::Genesis;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_client::RpcClient;
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{BlockInfoStore, BlockStore, Storage};
+use starcoin_types::block::{Block, BlockNumber};
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_vm_types::transaction::authenticator::AccountPrivateKey;
+use std::convert::TryFrom;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+// A standalone binary rather than a `starcoin` subcommand, because the `starcoin` commands run
+// against a node, and start a local node if no node is connected.
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "chain-auditor",
+    about = "Verify the chain offline, without serving rpc or joining the network"
+)]
+pub enum Cmd {
+    /// Download the blocks, verify the consensus and re-execute them from the genesis,
+    /// then write a report of the verified head signed by the auditor key.
+    VerifyChain(VerifyChainOpt),
+    /// Check the signature of a chain verification report.
+    VerifyReport(VerifyReportOpt),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockSource {
+    /// A block file exported by the db-exporter.
+    Archive,
+    /// The rpc of a full node.
+    Rpc,
+}
+
+impl fmt::Display for BlockSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Archive => write!(f, "archive"),
+            Self::Rpc => write!(f, "rpc"),
+        }
+    }
+}
+
+impl FromStr for BlockSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "archive" => Ok(Self::Archive),
+            "rpc" => Ok(Self::Rpc),
+            _ => bail!("Unknown block source {}, expect archive or rpc", s),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyChainOpt {
+    #[structopt(long, short = "n")]
+    /// Chain Network to verify.
+    pub net: BuiltinNetworkID,
+    #[structopt(long, short = "d", parse(from_os_str))]
+    /// Data dir of the verified chain, it is initialized with the genesis if not exists,
+    /// and the verification resumes from its head.
+    pub data_dir: PathBuf,
+    #[structopt(long, short = "s")]
+    /// Where the blocks come from: archive, rpc.
+    pub source: BlockSource,
+    #[structopt(long, parse(from_os_str), required_if("source", "archive"))]
+    /// Block file exported by `starcoin_db_exporter export`, it can be compressed by zstd.
+    pub archive: Option<PathBuf>,
+    #[structopt(long, required_if("source", "rpc"))]
+    /// Rpc url of the node to download the blocks, such as ws://127.0.0.1:9870 or http://127.0.0.1:9850.
+    pub rpc: Option<String>,
+    #[structopt(long)]
+    /// Last block number to verify, default is the end of the archive or the head of the rpc node.
+    pub to: Option<BlockNumber>,
+    #[structopt(long, short = "k", parse(from_os_str))]
+    /// File of the auditor private key in hex, which signs the report.
+    pub auditor_key_file: PathBuf,
+    #[structopt(long, short = "o", parse(from_os_str))]
+    /// Output file of the signed report.
+    pub output: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyReportOpt {
+    #[structopt(parse(from_os_str))]
+    /// The signed report file written by `verify-chain`.
+    pub report: PathBuf,
+}
+
+struct ChainVerifier {
+    storage: Arc<Storage>,
+    genesis_hash: HashValue,
+    chain: BlockChain,
+    end: Option<BlockNumber>,
+    blocks: u64,
+    txns: u64,
+}
+
+impl ChainVerifier {
+    fn new(net: &ChainNetwork, data_dir: &Path, end: Option<BlockNumber>) -> Result<Self> {
+        let db_storage = DBStorage::new(data_dir.join("starcoindb/db"), RocksdbConfig::default())?;
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            db_storage,
+        ))?);
+        let (chain_info, _) = Genesis::init_and_check_storage(net, storage.clone(), data_dir)?;
+        let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+        Ok(Self {
+            storage,
+            genesis_hash: chain_info.genesis_hash(),
+            chain,
+            end,
+            blocks: 0,
+            txns: 0,
+        })
+    }
+
+    fn head_number(&self) -> BlockNumber {
+        self.chain.current_header().number()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.end
+            .map(|end| self.head_number() >= end)
+            .unwrap_or(false)
+    }
+
+    /// Verify the consensus of the block and re-execute it on the current head.
+    fn verify(&mut self, block: Block) -> Result<()> {
+        let number = block.header().number();
+        if self.storage.get_block_info(block.id())?.is_some() {
+            // the block is verified by the previous run, or it is the genesis block.
+            return Ok(());
+        }
+        let head = self.chain.current_header();
+        ensure!(
+            block.header().parent_hash() == head.id(),
+            "Block {}({}) is not the child of verified head {}({})",
+            number,
+            block.id(),
+            head.number(),
+            head.id()
+        );
+        let txns = block.transactions().len() as u64;
+        self.chain.apply_with_verifier::<FullVerifier>(block)?;
+        self.storage
+            .save_startup_info(StartupInfo::new(self.chain.current_header().id()))?;
+        self.blocks += 1;
+        self.txns += txns;
+        if number % 1000 == 0 {
+            info!("Verified to block {}", number);
+        }
+        Ok(())
+    }
+
+    fn verify_archive(&mut self, net: &ChainNetwork, archive: &Path) -> Result<()> {
+        let reader: BlockFileReader<_> = BlockFileReader::open(archive)?;
+        reader
+            .header()
+            .check_chain(net.chain_id(), self.genesis_hash)?;
+        for block in reader {
+            if self.is_finished() {
+                break;
+            }
+            self.verify(block?)?;
+        }
+        Ok(())
+    }
+
+    fn verify_rpc(&mut self, net: &ChainNetwork, url: &str) -> Result<()> {
+        let client = if url.starts_with("ws") {
+            RpcClient::connect_websocket(url)?
+        } else {
+            RpcClient::connect_http(url)?
+        };
+        let chain_info = client.chain_info()?;
+        ensure!(
+            chain_info.chain_id == net.chain_id().id(),
+            "The rpc node is on chain {}, but the network is {}",
+            chain_info.chain_id,
+            net
+        );
+        ensure!(
+            chain_info.genesis_hash == self.genesis_hash,
+            "The genesis {} of the rpc node is not the genesis {} of the network",
+            chain_info.genesis_hash,
+            self.genesis_hash
+        );
+        let end = self.end.unwrap_or(chain_info.head.number.0);
+        for number in self.head_number().saturating_add(1)..=end {
+            let block = client
+                .chain_get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Block {} is not found on the rpc node", number))?;
+            self.verify(Block::try_from(block)?)?;
+        }
+        client.close();
+        Ok(())
+    }
+}
+
+fn verify_chain(opt: VerifyChainOpt) -> Result<()> {
+    let started_at = now_secs()?;
+    let net = ChainNetwork::new_builtin(opt.net);
+    let key = std::fs::read_to_string(opt.auditor_key_file.as_path())?;
+    let key = AccountPrivateKey::from_encoded_string(key.trim())?;
+    let mut verifier = ChainVerifier::new(&net, opt.data_dir.as_path(), opt.to)?;
+    let start_number = verifier.head_number().saturating_add(1);
+    let source = match opt.source {
+        BlockSource::Archive => {
+            let archive = opt
+                .archive
+                .ok_or_else(|| format_err!("The archive is required by the archive source"))?;
+            verifier.verify_archive(&net, &archive)?;
+            format!("archive:{}", archive.display())
+        }
+        BlockSource::Rpc => {
+            let url = opt
+                .rpc
+                .ok_or_else(|| format_err!("The rpc url is required by the rpc source"))?;
+            verifier.verify_rpc(&net, url.as_str())?;
+            format!("rpc:{}", url)
+        }
+    };
+    let head = verifier.chain.current_header();
+    if let Some(end) = opt.to {
+        ensure!(
+            head.number() >= end,
+            "The source ends at block {}, before the expected block {}",
+            head.number(),
+            end
+        );
+    }
+    let block_info = verifier
+        .chain
+        .get_block_info(None)?
+        .ok_or_else(|| format_err!("The block info of the head {} is missing", head.id()))?;
+    let report = ChainVerificationReport {
+        version: CHAIN_VERIFICATION_REPORT_VERSION,
+        net: net.to_string(),
+        chain_id: net.chain_id().id(),
+        genesis_hash: verifier.genesis_hash,
+        source,
+        start_number,
+        blocks: verifier.blocks,
+        txns: verifier.txns,
+        head: VerifiedHead::new(&head, &block_info),
+        started_at,
+        finished_at: now_secs()?,
+        auditor: key.public_key().derived_address(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    SignedChainVerificationReport::sign(report, &key)?.save(opt.output.as_path())?;
+    println!(
+        "verified {} blocks and {} txns, head: {}({}), report: {}",
+        verifier.blocks,
+        verifier.txns,
+        head.number(),
+        head.id(),
+        opt.output.display()
+    );
+    Ok(())
+}
+
+fn verify_report(opt: VerifyReportOpt) -> Result<()> {
+    let signed = SignedChainVerificationReport::load(opt.report.as_path())?;
+    signed.verify()?;
+    println!("{}", serde_json::to_string_pretty(&signed.report)?);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let _logger = starcoin_logger::init();
+    match Cmd::from_args() {
+        Cmd::VerifyChain(opt) => verify_chain(opt),
+        Cmd::VerifyReport(opt) => verify_report(opt),
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{BlockHeader, BlockInfo, BlockNumber};
+use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::U256;
+use starcoin_vm_types::transaction::authenticator::{
+    AccountPrivateKey, AccountPublicKey, AccountSignature,
+};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const CHAIN_VERIFICATION_REPORT_VERSION: u8 = 1;
+/// The prefix of the signed message of the report, so it can not be replayed as other messages.
+const CHAIN_VERIFICATION_MESSAGE_PREFIX: &str = "STARCOIN::ChainVerificationReport::";
+
+/// The verified head of the chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VerifiedHead {
+    pub number: BlockNumber,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+    pub txn_accumulator_root: HashValue,
+    pub block_accumulator_root: HashValue,
+    pub total_difficulty: U256,
+}
+
+impl VerifiedHead {
+    pub fn new(header: &BlockHeader, block_info: &BlockInfo) -> Self {
+        Self {
+            number: header.number(),
+            block_hash: header.id(),
+            state_root: header.state_root(),
+            txn_accumulator_root: *block_info.get_txn_accumulator_info().get_accumulator_root(),
+            block_accumulator_root: *block_info
+                .get_block_accumulator_info()
+                .get_accumulator_root(),
+            total_difficulty: block_info.get_total_difficulty(),
+        }
+    }
+}
+
+/// The facts of a chain verification, from the genesis to the verified head.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ChainVerificationReport {
+    pub version: u8,
+    pub net: String,
+    pub chain_id: u8,
+    pub genesis_hash: HashValue,
+    /// Where the blocks come from, the archive file or the rpc url.
+    pub source: String,
+    /// The first block verified by this run, the blocks before it are verified by the previous runs in the same data dir.
+    pub start_number: BlockNumber,
+    /// The count of the blocks and the user txns verified by this run.
+    pub blocks: u64,
+    pub txns: u64,
+    pub head: VerifiedHead,
+    /// The start and finish time of the verification in seconds.
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub auditor: AccountAddress,
+    /// The version of the tool which verifies the chain.
+    pub tool_version: String,
+}
+
+impl ChainVerificationReport {
+    fn signing_message(&self) -> Result<SigningMessage> {
+        SigningMessage::from_str(
+            format!(
+                "{}{}",
+                CHAIN_VERIFICATION_MESSAGE_PREFIX,
+                hex::encode(bcs_ext::to_bytes(self)?)
+            )
+            .as_str(),
+        )
+    }
+}
+
+/// The report signed by the auditor key.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedChainVerificationReport {
+    pub report: ChainVerificationReport,
+    pub public_key: AccountPublicKey,
+    pub signature: AccountSignature,
+}
+
+impl SignedChainVerificationReport {
+    pub fn sign(report: ChainVerificationReport, private_key: &AccountPrivateKey) -> Result<Self> {
+        let public_key = private_key.public_key();
+        ensure!(
+            report.auditor == public_key.derived_address(),
+            "The report auditor {} is not the address of the signing key",
+            report.auditor
+        );
+        let signature = private_key.sign_message(report.signing_message()?);
+        Ok(Self {
+            report,
+            public_key,
+            signature,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check the report is signed by the auditor.
+    pub fn verify(&self) -> Result<()> {
+        let report = &self.report;
+        ensure!(
+            report.version == CHAIN_VERIFICATION_REPORT_VERSION,
+            "Unsupported chain verification report version {}",
+            report.version
+        );
+        ensure!(
+            report.auditor == self.public_key.derived_address(),
+            "The auditor {} is not derived from the public key",
+            report.auditor
+        );
+        let signer = match &self.signature {
+            AccountSignature::Single(public_key, _) => AccountPublicKey::single(public_key.clone()),
+            AccountSignature::Multi(_, _) => bail!("The report should be signed by a single key"),
+        };
+        ensure!(
+            signer == self.public_key,
+            "The report is not signed by the auditor key"
+        );
+        self.signature.verify(&report.signing_message()?)
+    }
+}
+
+pub fn now_secs() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::keygen::KeyGen;
+
+    fn mock_report(auditor: AccountAddress) -> ChainVerificationReport {
+        ChainVerificationReport {
+            version: CHAIN_VERIFICATION_REPORT_VERSION,
+            net: "halley".to_string(),
+            chain_id: 253,
+            genesis_hash: HashValue::random(),
+            source: "blocks.bin".to_string(),
+            start_number: 1,
+            blocks: 10,
+            txns: 3,
+            head: VerifiedHead {
+                number: 10,
+                block_hash: HashValue::random(),
+                state_root: HashValue::random(),
+                txn_accumulator_root: HashValue::random(),
+                block_accumulator_root: HashValue::random(),
+                total_difficulty: U256::from(100u64),
+            },
+            started_at: 1,
+            finished_at: 2,
+            auditor,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify_report() {
+        let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let private_key = AccountPrivateKey::Single(private_key);
+        let auditor = AccountPublicKey::single(public_key).derived_address();
+
+        let signed =
+            SignedChainVerificationReport::sign(mock_report(auditor), &private_key).unwrap();
+        signed.verify().unwrap();
+
+        let mut tampered = signed.clone();
+        tampered.report.head.number += 1;
+        assert!(tampered.verify().is_err());
+
+        let (other_key, _) = KeyGen::from_os_rng().generate_keypair();
+        let other_key = AccountPrivateKey::Single(other_key);
+        assert!(SignedChainVerificationReport::sign(mock_report(auditor), &other_key).is_err());
+    }
+}
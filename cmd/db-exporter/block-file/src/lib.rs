@@ -0,0 +1,274 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{Block, BlockNumber};
+use starcoin_types::block_metadata::BlockMetadata;
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::transaction::TransactionInfo;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// The magic bytes at the beginning of an uncompressed block file.
+pub const BLOCK_FILE_MAGIC: [u8; 4] = *b"STCB";
+/// The magic bytes at the beginning of an uncompressed block execution file.
+pub const BLOCK_EXECUTION_FILE_MAGIC: [u8; 4] = *b"STCE";
+pub const BLOCK_FILE_VERSION: u8 = 2;
+/// The magic bytes of a zstd frame, a compressed block file starts with it.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+/// A single block should never be larger than this.
+const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+/// The record type of a block file, every kind of file starts with its own magic bytes.
+pub trait BlockFileRecord: Serialize + DeserializeOwned {
+    const MAGIC: [u8; 4];
+    const KIND: &'static str;
+}
+
+impl BlockFileRecord for Block {
+    const MAGIC: [u8; 4] = BLOCK_FILE_MAGIC;
+    const KIND: &'static str = "block";
+}
+
+/// A block with its execution output, so the block can be indexed without executing it again.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockExecutionData {
+    pub block: Block,
+    pub block_metadata: BlockMetadata,
+    /// The txn infos in the block order, the first one is of the block metadata txn.
+    pub txn_infos: Vec<TransactionInfo>,
+    /// The events of every txn in `txn_infos`.
+    pub events: Vec<Vec<ContractEvent>>,
+}
+
+impl BlockFileRecord for BlockExecutionData {
+    const MAGIC: [u8; 4] = BLOCK_EXECUTION_FILE_MAGIC;
+    const KIND: &'static str = "block execution";
+}
+
+/// The header of a block file, the blocks in range [start, end] follow it in ascending order.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockFileHeader {
+    pub version: u8,
+    pub chain_id: ChainId,
+    /// The genesis hash of the chain, the chain id is not enough to tell a reset chain.
+    pub genesis_hash: HashValue,
+    pub start: BlockNumber,
+    pub end: BlockNumber,
+}
+
+impl BlockFileHeader {
+    pub fn new(
+        chain_id: ChainId,
+        genesis_hash: HashValue,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> Self {
+        Self {
+            version: BLOCK_FILE_VERSION,
+            chain_id,
+            genesis_hash,
+            start,
+            end,
+        }
+    }
+
+    /// Check the blocks of the file belong to the chain, before importing them.
+    pub fn check_chain(&self, chain_id: ChainId, genesis_hash: HashValue) -> Result<()> {
+        ensure!(
+            self.chain_id == chain_id,
+            "The block file is exported from chain {}, but the chain is {}",
+            self.chain_id,
+            chain_id
+        );
+        ensure!(
+            self.genesis_hash == genesis_hash,
+            "The block file is exported from the chain of genesis {}, but the genesis is {}",
+            self.genesis_hash,
+            genesis_hash
+        );
+        Ok(())
+    }
+}
+
+fn write_record<W: Write>(writer: &mut W, data: &[u8]) -> Result<()> {
+    ensure!(data.len() <= MAX_RECORD_SIZE, "Record is too large");
+    writer.write_all(&(data.len() as u32).to_le_bytes())?;
+    writer.write_all(data)?;
+    Ok(())
+}
+
+/// Read a length prefixed record, return None at the end of the file.
+fn read_record<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    let mut len_bytes = [0u8; 4];
+    let mut read = 0;
+    while read < len_bytes.len() {
+        let n = reader.read(&mut len_bytes[read..])?;
+        if n == 0 {
+            if read == 0 {
+                return Ok(None);
+            }
+            bail!("Unexpected end of block file");
+        }
+        read += n;
+    }
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    ensure!(len <= MAX_RECORD_SIZE, "Invalid record length {}", len);
+    let mut data = vec![0u8; len];
+    reader.read_exact(&mut data)?;
+    Ok(Some(data))
+}
+
+/// Write blocks in the canonical block file format:
+/// the magic bytes of the record type, then the bcs encoded `BlockFileHeader` and records,
+/// every one is prefixed with its length as a little endian u32.
+pub struct BlockFileWriter<W: Write, T: BlockFileRecord = Block> {
+    writer: W,
+    record: PhantomData<T>,
+}
+
+impl<W: Write, T: BlockFileRecord> BlockFileWriter<W, T> {
+    pub fn new(mut writer: W, header: &BlockFileHeader) -> Result<Self> {
+        writer.write_all(&T::MAGIC)?;
+        write_record(&mut writer, &bcs_ext::to_bytes(header)?)?;
+        Ok(Self {
+            writer,
+            record: PhantomData,
+        })
+    }
+
+    pub fn append(&mut self, record: &T) -> Result<()> {
+        write_record(&mut self.writer, &bcs_ext::to_bytes(record)?)
+    }
+
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Read blocks from a block file written by `BlockFileWriter`.
+pub struct BlockFileReader<R: Read, T: BlockFileRecord = Block> {
+    reader: R,
+    header: BlockFileHeader,
+    record: PhantomData<T>,
+}
+
+impl<T: BlockFileRecord> BlockFileReader<Box<dyn Read + Send>, T> {
+    /// Open the block file, the zstd compressed file is detected by its magic bytes.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let compressed = reader.fill_buf()?.starts_with(&ZSTD_MAGIC);
+        let reader: Box<dyn Read + Send> = if compressed {
+            Box::new(zstd::Decoder::with_buffer(reader)?)
+        } else {
+            Box::new(reader)
+        };
+        Self::new(reader)
+    }
+}
+
+impl<R: Read, T: BlockFileRecord> BlockFileReader<R, T> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        ensure!(
+            magic == T::MAGIC,
+            "Invalid block file magic, it is not a {} file",
+            T::KIND
+        );
+        let header: BlockFileHeader = match read_record(&mut reader)? {
+            Some(data) => bcs_ext::from_bytes(&data)?,
+            None => bail!("Block file header is missing"),
+        };
+        ensure!(
+            header.version == BLOCK_FILE_VERSION,
+            "Unsupported block file version {}",
+            header.version
+        );
+        Ok(Self {
+            reader,
+            header,
+            record: PhantomData,
+        })
+    }
+
+    pub fn header(&self) -> &BlockFileHeader {
+        &self.header
+    }
+}
+
+impl<R: Read, T: BlockFileRecord> Iterator for BlockFileReader<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match read_record(&mut self.reader) {
+            Ok(Some(data)) => Some(bcs_ext::from_bytes(&data)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcs_ext::Sample;
+
+    fn write_blocks<W: Write>(writer: W, blocks: &[Block]) -> W {
+        let header =
+            BlockFileHeader::new(ChainId::test(), blocks[0].id(), 0, blocks.len() as u64 - 1);
+        let mut writer = BlockFileWriter::new(writer, &header).unwrap();
+        for block in blocks {
+            writer.append(block).unwrap();
+        }
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_block_file() {
+        let blocks = vec![Block::sample(), Block::sample()];
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let path = tempdir.path().join("blocks.bcs");
+        write_blocks(File::create(&path).unwrap(), &blocks);
+        let reader: BlockFileReader<_> = BlockFileReader::open(&path).unwrap();
+        assert_eq!(reader.header().start, 0);
+        assert_eq!(reader.header().end, 1);
+        reader
+            .header()
+            .check_chain(ChainId::test(), blocks[0].id())
+            .unwrap();
+        assert!(reader
+            .header()
+            .check_chain(ChainId::test(), HashValue::random())
+            .is_err());
+        assert!(reader
+            .header()
+            .check_chain(ChainId::new(254), blocks[0].id())
+            .is_err());
+        let read_blocks = reader.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(read_blocks, blocks);
+
+        let path = tempdir.path().join("blocks.bcs.zst");
+        let encoder = zstd::Encoder::new(File::create(&path).unwrap(), 0).unwrap();
+        write_blocks(encoder, &blocks).finish().unwrap();
+        let reader: BlockFileReader<_> = BlockFileReader::open(&path).unwrap();
+        let read_blocks = reader.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(read_blocks, blocks);
+
+        // a truncated file is rejected.
+        let data = std::fs::read(tempdir.path().join("blocks.bcs")).unwrap();
+        let reader: BlockFileReader<_> = BlockFileReader::new(&data[..data.len() - 1]).unwrap();
+        assert!(reader.collect::<Result<Vec<_>>>().is_err());
+
+        // a block file is not a block execution file.
+        assert!(BlockFileReader::<_, BlockExecutionData>::new(&data[..]).is_err());
+    }
+}
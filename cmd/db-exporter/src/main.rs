@@ -0,0 +1,344 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use starcoin_block_file::{
+    BlockExecutionData, BlockFileHeader, BlockFileReader, BlockFileRecord, BlockFileWriter,
+};
+use starcoin_chain::verifier::{
+    BasicVerifier, ConsensusVerifier, FullVerifier, NoneVerifier, Verifier,
+};
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, RocksdbConfig};
+use starcoin_genesis::Genesis;
+use starcoin_logger::prelude::*;
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{
+    BlockInfoStore, BlockStore, ContractEventStore, Storage, Store, TransactionStore,
+    VEC_PREFIX_NAME,
+};
+use starcoin_types::block::{Block, BlockHeader, BlockNumber};
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::transaction::{Transaction, TransactionInfo};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "db-exporter",
+    about = "Export the blocks of the chain to a block file, or import them from it"
+)]
+pub enum Cmd {
+    /// Export the main chain blocks in range [from, to] to a block file.
+    Export(ExportOpt),
+    /// Import the blocks of a block file to the main chain.
+    Import(ImportOpt),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ExportOpt {
+    #[structopt(long, short = "n")]
+    /// Chain Network of the data dir.
+    pub net: BuiltinNetworkID,
+    #[structopt(long, short = "d", parse(from_os_str))]
+    /// Data dir of the network, such as `~/.starcoin/main`,
+    /// it is opened read-only, so the node can keep running.
+    pub data_dir: PathBuf,
+    #[structopt(long, default_value = "0")]
+    /// First block number to export.
+    pub from: BlockNumber,
+    #[structopt(long)]
+    /// Last block number to export, default is the head of the main chain.
+    pub to: Option<BlockNumber>,
+    #[structopt(long, short = "o", parse(from_os_str))]
+    /// Output block file.
+    pub output: PathBuf,
+    #[structopt(long)]
+    /// Compress the block file with zstd.
+    pub compress: bool,
+    #[structopt(long)]
+    /// Export the txn infos and events with the blocks to a block execution file,
+    /// it is ingested by the indexer without rpc, but can not be imported.
+    pub with_execution_data: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ImportOpt {
+    #[structopt(long, short = "n")]
+    /// Chain Network of the data dir.
+    pub net: BuiltinNetworkID,
+    #[structopt(long, short = "d", parse(from_os_str))]
+    /// Data dir of the network to import to, the node should be stopped.
+    /// It is initialized with the genesis if not exists.
+    pub data_dir: PathBuf,
+    #[structopt(long, short = "i", parse(from_os_str))]
+    /// Input block file, it can be compressed by zstd.
+    pub input: PathBuf,
+    #[structopt(
+        long,
+        possible_values = &Verifier::variants(),
+        case_insensitive = true,
+        default_value = "Full"
+    )]
+    /// Verify type of the imported blocks: Basic, Consensus, Full, None.
+    pub verifier: Verifier,
+    #[structopt(long)]
+    /// Only verify the body hashes and the parent links of the blocks in the file,
+    /// do not execute and write them to the data dir.
+    pub no_execute: bool,
+}
+
+fn block_execution_data(storage: &Storage, block: Block) -> Result<BlockExecutionData> {
+    let txn_infos = storage
+        .get_block_transaction_infos(block.id())?
+        .into_iter()
+        .map(|txn_info| {
+            let (_, txn_info): (_, TransactionInfo) = txn_info.into();
+            txn_info
+        })
+        .collect::<Vec<_>>();
+    ensure!(
+        txn_infos.len() == block.transactions().len() + 1,
+        "The txn infos of block {} mismatch with its txns",
+        block.id()
+    );
+    let block_metadata = match storage.get_transaction(txn_infos[0].transaction_hash())? {
+        Some(Transaction::BlockMetadata(block_metadata)) => block_metadata,
+        _ => bail!(
+            "Can not find the block metadata txn of block {}",
+            block.id()
+        ),
+    };
+    let mut events = vec![];
+    for txn_info in &txn_infos {
+        events.push(
+            storage
+                .get_contract_events(txn_info.id())?
+                .ok_or_else(|| format_err!("Can not find events of txn info {}", txn_info.id()))?,
+        );
+    }
+    Ok(BlockExecutionData {
+        block,
+        block_metadata,
+        txn_infos,
+        events,
+    })
+}
+
+fn export_blocks<W, T, F>(
+    chain: &BlockChain,
+    from: BlockNumber,
+    to: BlockNumber,
+    mut writer: BlockFileWriter<W, T>,
+    to_record: F,
+) -> Result<W>
+where
+    W: Write,
+    T: BlockFileRecord,
+    F: Fn(Block) -> Result<T>,
+{
+    for number in from..=to {
+        let block = chain
+            .get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        writer.append(&to_record(block)?)?;
+        if number % 10000 == 0 {
+            info!("Exported to block {}", number);
+        }
+    }
+    writer.into_inner()
+}
+
+fn export(opt: ExportOpt) -> Result<()> {
+    let net = ChainNetwork::new_builtin(opt.net);
+    let db_storage = DBStorage::open_with_cfs(
+        opt.data_dir.join("starcoindb/db").join("starcoindb"),
+        VEC_PREFIX_NAME.to_vec(),
+        true,
+        RocksdbConfig::default(),
+    )?;
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        db_storage,
+    ))?);
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info is none, the data dir is not initialized."))?;
+    let chain = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+    let to = opt.to.unwrap_or_else(|| chain.current_header().number());
+    ensure!(opt.from <= to, "Invalid block range [{}, {}]", opt.from, to);
+
+    let genesis_hash = storage
+        .get_genesis()?
+        .ok_or_else(|| format_err!("Genesis is none, the data dir is not initialized."))?;
+    let header = BlockFileHeader::new(net.chain_id(), genesis_hash, opt.from, to);
+    if opt.with_execution_data {
+        write_block_file(&chain, &opt, &header, |block| {
+            block_execution_data(storage.as_ref(), block)
+        })?;
+    } else {
+        write_block_file(&chain, &opt, &header, Ok)?;
+    }
+    println!(
+        "exported blocks [{}, {}] to {}",
+        opt.from,
+        to,
+        opt.output.display()
+    );
+    Ok(())
+}
+
+fn write_block_file<T, F>(
+    chain: &BlockChain,
+    opt: &ExportOpt,
+    header: &BlockFileHeader,
+    to_record: F,
+) -> Result<()>
+where
+    T: BlockFileRecord,
+    F: Fn(Block) -> Result<T>,
+{
+    let file = BufWriter::new(File::create(&opt.output)?);
+    if opt.compress {
+        let writer = BlockFileWriter::new(zstd::Encoder::new(file, 0)?, header)?;
+        export_blocks(chain, header.start, header.end, writer, to_record)?
+            .finish()?
+            .flush()?;
+    } else {
+        let writer = BlockFileWriter::new(file, header)?;
+        export_blocks(chain, header.start, header.end, writer, to_record)?;
+    }
+    Ok(())
+}
+
+/// Verify the bodies match the headers and the blocks are continuous, without executing them.
+fn verify_blocks<R: Read>(reader: BlockFileReader<R>) -> Result<()> {
+    let file_header = reader.header().clone();
+    let mut parent: Option<BlockHeader> = None;
+    let mut count = 0u64;
+    for block in reader {
+        let block = block?;
+        let header = block.header();
+        ensure!(
+            header.chain_id() == file_header.chain_id,
+            "Block {} chain id {} mismatch with the block file",
+            header.id(),
+            header.chain_id()
+        );
+        ensure!(
+            header.body_hash() == block.body.hash(),
+            "Block {} body hash mismatch",
+            header.id()
+        );
+        ensure!(
+            header.number() != 0 || header.id() == file_header.genesis_hash,
+            "The genesis block {} mismatch with the block file genesis {}",
+            header.id(),
+            file_header.genesis_hash
+        );
+        match &parent {
+            Some(parent) => ensure!(
+                header.parent_hash() == parent.id() && header.number() == parent.number() + 1,
+                "Block {}({}) is not the child of block {}({})",
+                header.number(),
+                header.id(),
+                parent.number(),
+                parent.id()
+            ),
+            None => ensure!(
+                header.number() == file_header.start,
+                "The first block number {} mismatch with the block file start {}",
+                header.number(),
+                file_header.start
+            ),
+        }
+        parent = Some(header.clone());
+        count += 1;
+    }
+    let last = parent.ok_or_else(|| format_err!("There is no block in the block file"))?;
+    ensure!(
+        last.number() == file_header.end,
+        "The block file is truncated, expect the last block {}, but got {}",
+        file_header.end,
+        last.number()
+    );
+    println!(
+        "verified {} blocks in range [{}, {}]",
+        count, file_header.start, file_header.end
+    );
+    Ok(())
+}
+
+fn import(opt: ImportOpt) -> Result<()> {
+    let net = ChainNetwork::new_builtin(opt.net);
+    let reader: BlockFileReader<_> = BlockFileReader::open(opt.input.as_path())?;
+    let genesis = Genesis::load_or_build(&net)?;
+    reader
+        .header()
+        .check_chain(net.chain_id(), genesis.block().id())?;
+    if opt.no_execute {
+        return verify_blocks(reader);
+    }
+
+    let db_storage = DBStorage::new(opt.data_dir.join("starcoindb/db"), RocksdbConfig::default())?;
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        db_storage,
+    ))?);
+    let (chain_info, _) =
+        Genesis::init_and_check_storage(&net, storage.clone(), opt.data_dir.as_ref())?;
+    let mut chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    let mut imported = 0u64;
+    let mut skipped = 0u64;
+    for block in reader {
+        let block = block?;
+        let number = block.header().number();
+        if storage.get_block_info(block.id())?.is_some() {
+            // the block is already executed, such as the genesis block.
+            skipped += 1;
+            continue;
+        }
+        let head = chain.current_header();
+        ensure!(
+            block.header().parent_hash() == head.id(),
+            "Block {}({}) is not the child of current head {}({}), blocks should be imported continuously",
+            number,
+            block.id(),
+            head.number(),
+            head.id()
+        );
+        match &opt.verifier {
+            Verifier::Basic => chain.apply_with_verifier::<BasicVerifier>(block)?,
+            Verifier::Consensus => chain.apply_with_verifier::<ConsensusVerifier>(block)?,
+            Verifier::Full => chain.apply_with_verifier::<FullVerifier>(block)?,
+            Verifier::None => chain.apply_with_verifier::<NoneVerifier>(block)?,
+        };
+        storage.save_startup_info(StartupInfo::new(chain.current_header().id()))?;
+        imported += 1;
+        if number % 1000 == 0 {
+            info!("Imported to block {}", number);
+        }
+    }
+    let head = chain.current_header();
+    println!(
+        "imported {} blocks, skipped {} existing blocks, head: {}({})",
+        imported,
+        skipped,
+        head.number(),
+        head.id()
+    );
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let _logger = starcoin_logger::init();
+    match Cmd::from_args() {
+        Cmd::Export(opt) => export(opt),
+        Cmd::Import(opt) => import(opt),
+    }
+}
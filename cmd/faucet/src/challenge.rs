@@ -0,0 +1,123 @@
+use anyhow::{bail, format_err, Result};
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+pub const HCAPTCHA_VERIFY_URL: &str = "https://hcaptcha.com/siteverify";
+pub const RECAPTCHA_VERIFY_URL: &str = "https://www.google.com/recaptcha/api/siteverify";
+
+/// Challenge hook to verify a fund request is sent by a human.
+pub trait Challenge: Send + Sync {
+    /// Verify the challenge `response` submitted with the fund request.
+    fn verify(&self, response: Option<&str>, remote_ip: Option<IpAddr>) -> Result<()>;
+}
+
+/// No challenge, all requests pass.
+pub struct NoChallenge;
+
+impl Challenge for NoChallenge {
+    fn verify(&self, _response: Option<&str>, _remote_ip: Option<IpAddr>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// Verify the captcha response by the siteverify api, hCaptcha and reCAPTCHA share the same protocol.
+pub struct SiteVerifyChallenge {
+    verify_url: String,
+    secret: String,
+    client: reqwest::blocking::Client,
+}
+
+impl SiteVerifyChallenge {
+    pub fn new(verify_url: String, secret: String) -> Self {
+        Self {
+            verify_url,
+            secret,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    pub fn hcaptcha(secret: String) -> Self {
+        Self::new(HCAPTCHA_VERIFY_URL.to_string(), secret)
+    }
+
+    pub fn recaptcha(secret: String) -> Self {
+        Self::new(RECAPTCHA_VERIFY_URL.to_string(), secret)
+    }
+}
+
+impl Challenge for SiteVerifyChallenge {
+    fn verify(&self, response: Option<&str>, remote_ip: Option<IpAddr>) -> Result<()> {
+        let response = response.ok_or_else(|| format_err!("Captcha response is required"))?;
+        let mut params = vec![
+            ("secret", self.secret.clone()),
+            ("response", response.to_string()),
+        ];
+        if let Some(remote_ip) = remote_ip {
+            params.push(("remoteip", remote_ip.to_string()));
+        }
+        let verify_response = self
+            .client
+            .post(self.verify_url.as_str())
+            .form(&params)
+            .send()?
+            .text()?;
+        let verify_response: SiteVerifyResponse = serde_json::from_str(&verify_response)?;
+        if !verify_response.success {
+            bail!(
+                "Captcha verification failed: {:?}",
+                verify_response.error_codes
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChallengeType {
+    None,
+    HCaptcha,
+    ReCaptcha,
+}
+
+impl FromStr for ChallengeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(ChallengeType::None),
+            "hcaptcha" => Ok(ChallengeType::HCaptcha),
+            "recaptcha" => Ok(ChallengeType::ReCaptcha),
+            _ => bail!("Unknown challenge type: {}", s),
+        }
+    }
+}
+
+impl Default for ChallengeType {
+    fn default() -> Self {
+        ChallengeType::None
+    }
+}
+
+pub fn create_challenge(
+    challenge_type: ChallengeType,
+    secret: Option<String>,
+) -> Result<Box<dyn Challenge>> {
+    let secret = || {
+        secret
+            .clone()
+            .ok_or_else(|| format_err!("Challenge secret is required for {:?}", challenge_type))
+    };
+    Ok(match challenge_type {
+        ChallengeType::None => Box::new(NoChallenge),
+        ChallengeType::HCaptcha => Box::new(SiteVerifyChallenge::hcaptcha(secret()?)),
+        ChallengeType::ReCaptcha => Box::new(SiteVerifyChallenge::recaptcha(secret()?)),
+    })
+}
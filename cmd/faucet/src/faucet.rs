@@ -1,11 +1,13 @@
 use anyhow::{format_err, Result};
 
+use crate::policy::FundingPolicy;
 use starcoin_account_api::AccountInfo;
 use starcoin_crypto::ed25519::Ed25519PublicKey;
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_rpc_client::{RemoteStateReader, RpcClient};
 use starcoin_state_api::AccountStateReader;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::genesis_config::ChainId;
 use starcoin_types::transaction::authenticator::AuthenticationKey;
 use starcoin_types::transaction::helpers::get_current_timestamp;
 use std::convert::TryFrom;
@@ -13,21 +15,61 @@ use std::convert::TryFrom;
 pub struct Faucet {
     client: RpcClient,
     faucet_account: AccountInfo,
+    policy: FundingPolicy,
 }
 
 const DEFAULT_GAS_PRICE: u64 = 1;
 const MAX_GAS: u64 = 10000;
 
 impl Faucet {
-    pub fn new(client: RpcClient, faucet_account: AccountInfo) -> Self {
+    pub fn new(client: RpcClient, faucet_account: AccountInfo, policy: FundingPolicy) -> Self {
         Faucet {
             client,
             faucet_account,
+            policy,
         }
     }
 
+    /// Transfer to the receiver, the amount is decided by the funding policy of current network.
+    /// Return the transferred amount.
     pub fn transfer(
         &self,
+        amount: Option<u128>,
+        receiver: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> Result<u128> {
+        let net = self.client.node_info()?.net;
+        let amount = self
+            .policy
+            .policy(net.to_string().as_str())
+            .fund_amount(amount)?;
+        self.do_transfer(net.chain_id(), amount, receiver, public_key)?;
+        Ok(amount)
+    }
+
+    /// Transfer the exact amount to the receiver, the amount is decided by the caller.
+    pub fn transfer_exact(
+        &self,
+        amount: u128,
+        receiver: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> Result<()> {
+        let net = self.client.node_info()?.net;
+        self.do_transfer(net.chain_id(), amount, receiver, public_key)
+    }
+
+    /// The balance of the faucet account.
+    pub fn balance(&self) -> Result<u128> {
+        let chain_state_reader = RemoteStateReader::new(&self.client)?;
+        let account_state_reader = AccountStateReader::new(&chain_state_reader);
+        Ok(account_state_reader
+            .get_balance(self.faucet_account.address())?
+            .unwrap_or_default())
+    }
+
+    fn do_transfer(
+        &self,
+        chain_id: ChainId,
         amount: u128,
         receiver: AccountAddress,
         public_key: Vec<u8>,
@@ -52,7 +94,7 @@ impl Faucet {
             DEFAULT_GAS_PRICE,
             MAX_GAS,
             get_current_timestamp() + DEFAULT_EXPIRATION_TIME,
-            self.client.node_info()?.net.chain_id(),
+            chain_id,
         );
         let signed_tx = self.client.account_sign_txn(raw_tx)?;
         self.client.submit_transaction(signed_tx)?;
@@ -1,4 +1,9 @@
+pub mod challenge;
 pub mod faucet;
+pub mod limiter;
+pub mod policy;
+pub mod project;
+pub mod treasury;
 pub mod web;
 
 #[macro_export]
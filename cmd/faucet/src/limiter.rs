@@ -0,0 +1,209 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::helpers::get_current_timestamp;
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    /// The limit window in seconds.
+    pub window_secs: u64,
+    /// Max fund times of an address in a window.
+    pub max_per_address: u32,
+    /// Max fund times of an ip in a window.
+    pub max_per_ip: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 24 * 60 * 60,
+            max_per_address: 1,
+            max_per_ip: 5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LimitRecord {
+    /// Fund times in current window.
+    pub count: u32,
+    /// Start timestamp of current window in seconds.
+    pub window_start: u64,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LimitRecords {
+    pub addresses: HashMap<AccountAddress, LimitRecord>,
+    pub ips: HashMap<IpAddr, LimitRecord>,
+}
+
+/// Per address and per ip rate limiter of fund requests,
+/// the records are persisted to a file if the path is set, so the limits survive restarts.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    path: Option<PathBuf>,
+    records: LimitRecords,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig, path: Option<&Path>) -> Result<Self> {
+        let records = match path {
+            Some(path) if path.exists() => serde_json::from_slice(&fs::read(path)?)?,
+            _ => LimitRecords::default(),
+        };
+        Ok(Self {
+            config,
+            path: path.map(|path| path.to_path_buf()),
+            records,
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            fs::write(path, serde_json::to_vec_pretty(&self.records)?)?;
+        }
+        Ok(())
+    }
+
+    fn is_exceeded(&self, record: Option<&LimitRecord>, max: u32, now: u64) -> bool {
+        match record {
+            Some(record) if record.window_start + self.config.window_secs > now => {
+                record.count >= max
+            }
+            _ => false,
+        }
+    }
+
+    fn increase(record: &mut LimitRecord, window_secs: u64, now: u64) {
+        if record.window_start + window_secs <= now {
+            record.window_start = now;
+            record.count = 0;
+        }
+        record.count += 1;
+    }
+
+    /// Check the address and ip have not exceeded the limit, without recording.
+    pub fn check(&self, address: AccountAddress, ip: Option<IpAddr>) -> Result<()> {
+        let now = get_current_timestamp();
+        if self.is_exceeded(
+            self.records.addresses.get(&address),
+            self.config.max_per_address,
+            now,
+        ) {
+            bail!(
+                "Address {} exceeded the limit of {} fund(s) per {} seconds",
+                address,
+                self.config.max_per_address,
+                self.config.window_secs
+            );
+        }
+        if let Some(ip) = ip {
+            if self.is_exceeded(self.records.ips.get(&ip), self.config.max_per_ip, now) {
+                bail!(
+                    "IP {} exceeded the limit of {} fund(s) per {} seconds",
+                    ip,
+                    self.config.max_per_ip,
+                    self.config.window_secs
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a successful fund of the address and ip.
+    pub fn record(&mut self, address: AccountAddress, ip: Option<IpAddr>) -> Result<()> {
+        let now = get_current_timestamp();
+        let window_secs = self.config.window_secs;
+        let new_record = LimitRecord {
+            count: 0,
+            window_start: now,
+        };
+        Self::increase(
+            self.records.addresses.entry(address).or_insert(new_record),
+            window_secs,
+            now,
+        );
+        if let Some(ip) = ip {
+            Self::increase(
+                self.records.ips.entry(ip).or_insert(new_record),
+                window_secs,
+                now,
+            );
+        }
+        self.save()
+    }
+
+    pub fn records(&self) -> &LimitRecords {
+        &self.records
+    }
+
+    /// Reset the limit of the address or ip, reset all limits if `key` is None.
+    /// Return the count of removed records.
+    pub fn reset(&mut self, key: Option<&str>) -> Result<usize> {
+        let removed = match key {
+            None => {
+                let removed = self.records.addresses.len() + self.records.ips.len();
+                self.records = LimitRecords::default();
+                removed
+            }
+            Some(key) => {
+                if let Ok(ip) = key.parse::<IpAddr>() {
+                    self.records.ips.remove(&ip).map_or(0, |_| 1)
+                } else {
+                    let address = key.parse::<AccountAddress>()?;
+                    self.records.addresses.remove(&address).map_or(0, |_| 1)
+                }
+            }
+        };
+        if removed > 0 {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faucet_limits.json");
+        let config = RateLimitConfig {
+            window_secs: 3600,
+            max_per_address: 1,
+            max_per_ip: 2,
+        };
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let address1 = AccountAddress::random();
+        let address2 = AccountAddress::random();
+        let address3 = AccountAddress::random();
+        {
+            let mut limiter = RateLimiter::new(config.clone(), Some(path.as_path())).unwrap();
+            limiter.check(address1, Some(ip)).unwrap();
+            limiter.record(address1, Some(ip)).unwrap();
+            assert!(limiter.check(address1, None).is_err());
+            limiter.check(address2, Some(ip)).unwrap();
+            limiter.record(address2, Some(ip)).unwrap();
+        }
+        let mut limiter = RateLimiter::new(config, Some(path.as_path())).unwrap();
+        assert!(limiter.check(address1, None).is_err());
+        assert!(limiter.check(address3, Some(ip)).is_err());
+        limiter.check(address3, None).unwrap();
+
+        assert_eq!(limiter.reset(Some("127.0.0.1")).unwrap(), 1);
+        limiter.check(address3, Some(ip)).unwrap();
+        assert_eq!(
+            limiter.reset(Some(address1.to_string().as_str())).unwrap(),
+            1
+        );
+        limiter.check(address1, None).unwrap();
+        assert_eq!(limiter.reset(None).unwrap(), 1);
+        assert!(limiter.records().addresses.is_empty());
+    }
+}
@@ -1,4 +1,10 @@
 use futures::executor;
+use starcoin_faucet::challenge::{create_challenge, ChallengeType};
+use starcoin_faucet::limiter::{RateLimitConfig, RateLimiter};
+use starcoin_faucet::policy::FundingPolicy;
+use starcoin_faucet::project::ProjectRegistry;
+use starcoin_faucet::treasury::{Treasury, TreasuryConfig};
+use starcoin_faucet::web::{FaucetGuard, FaucetProjects};
 use starcoin_faucet::{faucet::Faucet, web};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
@@ -16,6 +22,50 @@ pub struct FaucetOpt {
     pub server_addr: String,
     #[structopt(long, short = "d")]
     pub faucet_address: String,
+    /// Rate limit window in seconds.
+    #[structopt(long, default_value = "86400")]
+    pub limit_window: u64,
+    /// Max fund times of an address in a rate limit window.
+    #[structopt(long, default_value = "1")]
+    pub max_per_address: u32,
+    /// Max fund times of an ip in a rate limit window.
+    #[structopt(long, default_value = "5")]
+    pub max_per_ip: u32,
+    /// The file to persist the rate limit records, the records are kept in memory if absent.
+    #[structopt(long, parse(from_os_str))]
+    pub limit_store: Option<PathBuf>,
+    /// Challenge type of fund requests, none, hcaptcha or recaptcha.
+    #[structopt(long, default_value = "none")]
+    pub challenge: ChallengeType,
+    /// The secret key to verify the captcha response.
+    #[structopt(long)]
+    pub challenge_secret: Option<String>,
+    /// The json file of funding policy, configure the fund amount per network.
+    #[structopt(long, parse(from_os_str))]
+    pub policy: Option<PathBuf>,
+    /// The token to access admin api by the `X-Admin-Token` header, the admin api is disabled if absent.
+    #[structopt(long)]
+    pub admin_token: Option<String>,
+    /// The count of the trusted proxies in front of the faucet, the client ip is taken from the
+    /// `X-Forwarded-For` entry appended by them. 0 means no proxy, the ip of the connection is used.
+    #[structopt(long, default_value = "0")]
+    pub trusted_proxies: usize,
+    /// The json file of the projects sharing the faucet, each with `name`, `api_key`, `budget` and `drip_amount`.
+    /// The fund requests of a project are authorized by the `X-Api-Key` header.
+    #[structopt(long, parse(from_os_str))]
+    pub projects: Option<PathBuf>,
+    /// The file to persist the project stats, the stats are kept in memory if absent.
+    #[structopt(long, parse(from_os_str))]
+    pub project_store: Option<PathBuf>,
+    /// Emit a refill alert when the faucet balance falls below it, in nanoSTC.
+    #[structopt(long, default_value = "1000000000000")]
+    pub low_balance: u128,
+    /// The webhook to post the refill alerts to.
+    #[structopt(long)]
+    pub alert_webhook: Option<String>,
+    /// Poll the faucet balance in the interval in seconds.
+    #[structopt(long, default_value = "60")]
+    pub balance_poll_interval: u64,
 }
 
 fn main() {
@@ -30,8 +80,42 @@ fn main() {
         .account_get(account_address)
         .unwrap()
         .expect("Invalid faucet account address");
-    let faucet = Faucet::new(client, account);
-    let fut = web::run(server, faucet);
+    let policy = opts
+        .policy
+        .as_ref()
+        .map(|path| FundingPolicy::load(path.as_path()).expect("Invalid funding policy file"))
+        .unwrap_or_default();
+    let limiter = RateLimiter::new(
+        RateLimitConfig {
+            window_secs: opts.limit_window,
+            max_per_address: opts.max_per_address,
+            max_per_ip: opts.max_per_ip,
+        },
+        opts.limit_store.as_deref(),
+    )
+    .expect("Failed to load rate limit records");
+    let challenge = create_challenge(opts.challenge, opts.challenge_secret.clone())
+        .expect("Invalid challenge config");
+    let guard = FaucetGuard {
+        limiter,
+        challenge,
+        admin_token: opts.admin_token.clone(),
+        trusted_proxies: opts.trusted_proxies,
+    };
+    let registry = match opts.projects.as_ref() {
+        Some(path) => ProjectRegistry::load(path.as_path(), opts.project_store.as_deref()),
+        None => ProjectRegistry::new(vec![], opts.project_store.as_deref()),
+    }
+    .expect("Invalid projects config");
+    let treasury = Treasury::new(TreasuryConfig {
+        low_balance: opts.low_balance,
+        alert_webhook: opts.alert_webhook.clone(),
+        poll_interval: opts.balance_poll_interval,
+    });
+    let mut projects = FaucetProjects { registry, treasury };
+    let faucet = Faucet::new(client, account, policy);
+    projects.update_treasury(&faucet);
+    let fut = web::run(server, faucet, guard, projects);
     println!(
         "Faucet serve on: {}, with faucet account: {}",
         opts.server_addr, opts.faucet_address
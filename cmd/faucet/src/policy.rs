@@ -0,0 +1,79 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 1 STC in nanoSTC.
+const STC_SCALING_FACTOR: u128 = 1_000_000_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FundPolicy {
+    /// The amount to fund if the request does not specify one.
+    pub amount: u128,
+    /// The max amount a request can ask for.
+    pub max_amount: u128,
+}
+
+impl Default for FundPolicy {
+    fn default() -> Self {
+        Self {
+            amount: 10 * STC_SCALING_FACTOR,
+            max_amount: 100 * STC_SCALING_FACTOR,
+        }
+    }
+}
+
+impl FundPolicy {
+    /// Get the amount to fund for the `requested` amount.
+    pub fn fund_amount(&self, requested: Option<u128>) -> Result<u128> {
+        match requested {
+            None => Ok(self.amount),
+            Some(0) => bail!("Fund amount should be greater than 0"),
+            Some(amount) if amount > self.max_amount => bail!(
+                "Fund amount {} exceeds the max amount {}",
+                amount,
+                self.max_amount
+            ),
+            Some(amount) => Ok(amount),
+        }
+    }
+}
+
+/// Fund policies of networks, the key of `networks` is the network name, such as `halley`, `barnard`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FundingPolicy {
+    #[serde(default)]
+    pub default: FundPolicy,
+    #[serde(default)]
+    pub networks: HashMap<String, FundPolicy>,
+}
+
+impl FundingPolicy {
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn policy(&self, network: &str) -> &FundPolicy {
+        self.networks.get(network).unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_funding_policy() {
+        let policy: FundingPolicy = serde_json::from_str(
+            r#"{"networks": {"halley": {"amount": 100, "max_amount": 1000}}}"#,
+        )
+        .unwrap();
+        let halley = policy.policy("halley");
+        assert_eq!(halley.fund_amount(None).unwrap(), 100);
+        assert_eq!(halley.fund_amount(Some(1000)).unwrap(), 1000);
+        assert!(halley.fund_amount(Some(1001)).is_err());
+        assert!(halley.fund_amount(Some(0)).is_err());
+        assert_eq!(policy.policy("barnard"), &FundPolicy::default());
+    }
+}
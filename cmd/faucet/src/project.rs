@@ -0,0 +1,261 @@
+use crate::policy::FundPolicy;
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_types::transaction::helpers::get_current_timestamp;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A project sharing the faucet, the fund requests of the project are authorized by its api key.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    pub name: String,
+    pub api_key: String,
+    /// The total amount the project can fund, in nanoSTC.
+    pub budget: u128,
+    /// The amount of a fund request, in nanoSTC, a request can ask for less.
+    pub drip_amount: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStats {
+    pub fund_count: u64,
+    pub failed_count: u64,
+    /// The funded amount, in nanoSTC.
+    pub spent: u128,
+    /// The timestamp of the last fund in seconds.
+    pub last_fund_time: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ProjectView {
+    pub name: String,
+    pub budget: u128,
+    pub drip_amount: u128,
+    pub remaining: u128,
+    pub stats: ProjectStats,
+}
+
+/// The projects sharing the faucet, each with its own api key and budget.
+/// The stats are persisted to a file if the path is set, so the spent budgets survive restarts.
+pub struct ProjectRegistry {
+    /// The projects by api key.
+    projects: HashMap<String, ProjectConfig>,
+    path: Option<PathBuf>,
+    /// The stats by project name.
+    stats: HashMap<String, ProjectStats>,
+}
+
+impl ProjectRegistry {
+    pub fn new(projects: Vec<ProjectConfig>, path: Option<&Path>) -> Result<Self> {
+        let mut by_key = HashMap::new();
+        for project in projects {
+            ensure!(
+                !project.api_key.is_empty(),
+                "The api key of project {} is empty",
+                project.name
+            );
+            ensure!(
+                project.drip_amount > 0,
+                "The drip amount of project {} should be greater than 0",
+                project.name
+            );
+            ensure!(
+                by_key
+                    .values()
+                    .all(|p: &ProjectConfig| p.name != project.name),
+                "Duplicate project name {}",
+                project.name
+            );
+            if by_key.contains_key(&project.api_key) {
+                bail!(
+                    "Project {} reuses the api key of other project",
+                    project.name
+                );
+            }
+            by_key.insert(project.api_key.clone(), project);
+        }
+        let stats = match path {
+            Some(path) if path.exists() => serde_json::from_slice(&fs::read(path)?)?,
+            _ => HashMap::new(),
+        };
+        Ok(Self {
+            projects: by_key,
+            path: path.map(|path| path.to_path_buf()),
+            stats,
+        })
+    }
+
+    /// Load the projects from a json file of the project list.
+    pub fn load(config: &Path, path: Option<&Path>) -> Result<Self> {
+        let projects: Vec<ProjectConfig> = serde_json::from_slice(&fs::read(config)?)?;
+        Self::new(projects, path)
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            fs::write(path, serde_json::to_vec_pretty(&self.stats)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn authorize(&self, api_key: &str) -> Result<&ProjectConfig> {
+        self.projects
+            .get(api_key)
+            .ok_or_else(|| format_err!("Invalid api key"))
+    }
+
+    fn stats(&self, name: &str) -> ProjectStats {
+        self.stats.get(name).copied().unwrap_or_default()
+    }
+
+    fn remaining(&self, project: &ProjectConfig) -> u128 {
+        project
+            .budget
+            .saturating_sub(self.stats(&project.name).spent)
+    }
+
+    /// Get the amount to fund for the `requested` amount of the project, within its remaining budget.
+    pub fn fund_amount(&self, project: &ProjectConfig, requested: Option<u128>) -> Result<u128> {
+        let amount = FundPolicy {
+            amount: project.drip_amount,
+            max_amount: project.drip_amount,
+        }
+        .fund_amount(requested)?;
+        let remaining = self.remaining(project);
+        if amount > remaining {
+            bail!(
+                "Project {} exhausted its budget, the remaining budget is {}",
+                project.name,
+                remaining
+            );
+        }
+        Ok(amount)
+    }
+
+    /// Record a successful fund of the project.
+    pub fn record(&mut self, name: &str, amount: u128) -> Result<()> {
+        let stats = self.stats.entry(name.to_string()).or_default();
+        stats.fund_count = stats.fund_count.saturating_add(1);
+        stats.spent = stats.spent.saturating_add(amount);
+        stats.last_fund_time = get_current_timestamp();
+        self.save()
+    }
+
+    /// Record a failed fund of the project, the budget is not spent.
+    pub fn record_failure(&mut self, name: &str) -> Result<()> {
+        let stats = self.stats.entry(name.to_string()).or_default();
+        stats.failed_count = stats.failed_count.saturating_add(1);
+        self.save()
+    }
+
+    pub fn view(&self, project: &ProjectConfig) -> ProjectView {
+        ProjectView {
+            name: project.name.clone(),
+            budget: project.budget,
+            drip_amount: project.drip_amount,
+            remaining: self.remaining(project),
+            stats: self.stats(&project.name),
+        }
+    }
+
+    /// The views of all projects, sorted by name.
+    pub fn views(&self) -> Vec<ProjectView> {
+        let mut views: Vec<ProjectView> = self
+            .projects
+            .values()
+            .map(|project| self.view(project))
+            .collect();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        views
+    }
+
+    /// The remaining budgets of all projects, the treasury should cover them.
+    pub fn committed(&self) -> u128 {
+        self.projects.values().fold(0u128, |committed, project| {
+            committed.saturating_add(self.remaining(project))
+        })
+    }
+
+    /// Reset the stats of the project by name, reset all stats if `name` is None, the budgets are
+    /// restored. Return the count of removed stats.
+    pub fn reset(&mut self, name: Option<&str>) -> Result<usize> {
+        let removed = match name {
+            None => {
+                let removed = self.stats.len();
+                self.stats.clear();
+                removed
+            }
+            Some(name) => self.stats.remove(name).map_or(0, |_| 1),
+        };
+        if removed > 0 {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, api_key: &str) -> ProjectConfig {
+        ProjectConfig {
+            name: name.to_string(),
+            api_key: api_key.to_string(),
+            budget: 250,
+            drip_amount: 100,
+        }
+    }
+
+    #[test]
+    fn test_project_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faucet_projects.json");
+        let projects = vec![project("dapp", "key1"), project("wallet", "key2")];
+        {
+            let mut registry =
+                ProjectRegistry::new(projects.clone(), Some(path.as_path())).unwrap();
+            assert!(registry.authorize("key3").is_err());
+            let dapp = registry.authorize("key1").unwrap().clone();
+            assert!(registry.fund_amount(&dapp, Some(101)).is_err());
+            assert_eq!(registry.fund_amount(&dapp, None).unwrap(), 100);
+            registry.record("dapp", 100).unwrap();
+            registry.record("dapp", 100).unwrap();
+            registry.record_failure("dapp").unwrap();
+            assert_eq!(registry.committed(), 300);
+        }
+        let mut registry = ProjectRegistry::new(projects, Some(path.as_path())).unwrap();
+        let dapp = registry.authorize("key1").unwrap().clone();
+        // the remaining budget 50 can not cover the drip amount.
+        assert!(registry.fund_amount(&dapp, None).is_err());
+        assert_eq!(registry.fund_amount(&dapp, Some(50)).unwrap(), 50);
+        let wallet = registry.authorize("key2").unwrap().clone();
+        assert_eq!(registry.fund_amount(&wallet, None).unwrap(), 100);
+
+        let views = registry.views();
+        assert_eq!(views[0].name, "dapp");
+        assert_eq!(views[0].remaining, 50);
+        assert_eq!(views[0].stats.fund_count, 2);
+        assert_eq!(views[0].stats.failed_count, 1);
+        assert_eq!(views[1].stats, ProjectStats::default());
+
+        assert_eq!(registry.reset(Some("dapp")).unwrap(), 1);
+        assert_eq!(registry.fund_amount(&dapp, None).unwrap(), 100);
+        assert_eq!(registry.committed(), 500);
+    }
+
+    #[test]
+    fn test_invalid_projects() {
+        assert!(ProjectRegistry::new(vec![project("dapp", "")], None).is_err());
+        assert!(
+            ProjectRegistry::new(vec![project("dapp", "key1"), project("dapp", "key2")], None)
+                .is_err()
+        );
+        assert!(ProjectRegistry::new(
+            vec![project("dapp", "key1"), project("wallet", "key1")],
+            None
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use starcoin_logger::prelude::*;
+use starcoin_types::transaction::helpers::get_current_timestamp;
+
+#[derive(Clone, Debug, Default)]
+pub struct TreasuryConfig {
+    /// Emit a refill alert when the balance falls below it, in nanoSTC.
+    pub low_balance: u128,
+    /// The webhook to post the refill alert to, as a json `{"text": "..."}`.
+    pub alert_webhook: Option<String>,
+    /// Poll the balance in the interval in seconds, so the external drains are picked up.
+    pub poll_interval: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TreasuryView {
+    /// The balance of the faucet account, in nanoSTC.
+    pub balance: Option<u128>,
+    pub low_balance: u128,
+    /// The remaining budgets of the projects, in nanoSTC.
+    pub committed: u128,
+    /// The timestamp of the last balance update in seconds.
+    pub updated_at: u64,
+    pub low: bool,
+}
+
+/// The shared treasury of the faucet projects, track the balance of the faucet account and
+/// alert the operator to refill it.
+pub struct Treasury {
+    config: TreasuryConfig,
+    balance: Option<u128>,
+    updated_at: u64,
+    /// The timestamp of the last poll in seconds, the poll may fail.
+    polled_at: u64,
+    /// The alert is emitted once until the treasury is refilled.
+    alerted: bool,
+    client: reqwest::blocking::Client,
+}
+
+impl Treasury {
+    pub fn new(config: TreasuryConfig) -> Self {
+        Self {
+            config,
+            balance: None,
+            updated_at: 0,
+            polled_at: 0,
+            alerted: false,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Whether the balance is not polled in the poll interval.
+    pub fn is_outdated(&self, now: u64) -> bool {
+        now >= self.polled_at.saturating_add(self.config.poll_interval)
+    }
+
+    /// Record the failed poll, the balance is polled again after the poll interval.
+    pub fn poll_failed(&mut self) {
+        self.polled_at = get_current_timestamp();
+    }
+
+    /// Update the balance, return true if a refill alert is emitted.
+    pub fn update(&mut self, balance: u128, committed: u128) -> bool {
+        self.balance = Some(balance);
+        self.updated_at = get_current_timestamp();
+        self.polled_at = self.updated_at;
+        if balance >= self.config.low_balance {
+            self.alerted = false;
+            return false;
+        }
+        if self.alerted {
+            return false;
+        }
+        self.alerted = true;
+        self.alert(format!(
+            "Faucet treasury balance {} is below the refill threshold {}, the remaining project budgets are {}",
+            balance, self.config.low_balance, committed
+        ));
+        true
+    }
+
+    /// Post the alert to the webhook on a separate thread, so a slow webhook does not block the requests.
+    fn alert(&self, message: String) {
+        warn!("{}", message);
+        if let Some(webhook) = self.config.alert_webhook.clone() {
+            let client = self.client.clone();
+            std::thread::spawn(move || {
+                let result = client
+                    .post(webhook.as_str())
+                    .json(&serde_json::json!({ "text": message }))
+                    .send()
+                    .and_then(|response| response.error_for_status());
+                if let Err(e) = result {
+                    error!("Failed to post the refill alert to {}: {}", webhook, e);
+                }
+            });
+        }
+    }
+
+    pub fn view(&self, committed: u128) -> TreasuryView {
+        TreasuryView {
+            balance: self.balance,
+            low_balance: self.config.low_balance,
+            committed,
+            updated_at: self.updated_at,
+            low: self
+                .balance
+                .map_or(false, |balance| balance < self.config.low_balance),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_treasury_alert() {
+        let mut treasury = Treasury::new(TreasuryConfig {
+            low_balance: 1000,
+            alert_webhook: None,
+            poll_interval: 60,
+        });
+        assert!(treasury.is_outdated(get_current_timestamp()));
+        assert_eq!(treasury.view(0).balance, None);
+        assert!(!treasury.update(2000, 500));
+        assert!(treasury.update(900, 500));
+        // alert once until refilled.
+        assert!(!treasury.update(800, 500));
+        assert!(treasury.view(500).low);
+        assert!(!treasury.update(5000, 500));
+        assert!(!treasury.view(500).low);
+        assert!(treasury.update(100, 500));
+
+        let now = get_current_timestamp();
+        assert!(!treasury.is_outdated(now));
+        assert!(treasury.is_outdated(now + 60));
+
+        let mut treasury = Treasury::new(TreasuryConfig {
+            low_balance: 1000,
+            alert_webhook: None,
+            poll_interval: 60,
+        });
+        treasury.poll_failed();
+        assert!(!treasury.is_outdated(get_current_timestamp()));
+        assert_eq!(treasury.view(0).balance, None);
+    }
+
+    #[test]
+    fn test_treasury_alert_webhook() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::time::{Duration, Instant};
+
+        // a slow webhook, it does not respond until the test ends.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let webhook = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let len = stream.read(&mut buf).unwrap();
+            sender
+                .send(String::from_utf8_lossy(&buf[..len]).to_string())
+                .unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+        });
+        let mut treasury = Treasury::new(TreasuryConfig {
+            low_balance: 1000,
+            alert_webhook: Some(webhook),
+            poll_interval: 60,
+        });
+        let start = Instant::now();
+        assert!(treasury.update(900, 500));
+        // the update returns without waiting for the webhook.
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let request = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(request.starts_with("POST"));
+    }
+}
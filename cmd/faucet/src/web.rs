@@ -1,13 +1,58 @@
+use crate::challenge::Challenge;
+use crate::limiter::RateLimiter;
+use crate::project::{ProjectConfig, ProjectRegistry};
+use crate::treasury::Treasury;
 use crate::{faucet::Faucet, unwrap_or_return};
 use anyhow::Result;
 use ascii::AsciiString;
 use rust_embed::RustEmbed;
 use starcoin_logger::prelude::*;
-use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_address::{parse_address, AccountAddress};
+use starcoin_types::transaction::helpers::get_current_timestamp;
 use std::fmt::{Debug, Formatter};
 use std::io::Cursor;
+use std::net::IpAddr;
 use std::str::FromStr;
-use tiny_http::{Header, Response, Server};
+use std::time::Duration;
+use subtle::ConstantTimeEq;
+use tiny_http::{Header, Request, Response, Server};
+
+const ADMIN_TOKEN_HEADER: &str = "X-Admin-Token";
+const API_KEY_HEADER: &str = "X-Api-Key";
+/// The max time to wait for a request, then check whether the treasury balance is outdated.
+const TREASURY_POLL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The projects sharing the faucet and their shared treasury.
+pub struct FaucetProjects {
+    pub registry: ProjectRegistry,
+    pub treasury: Treasury,
+}
+
+impl FaucetProjects {
+    /// Update the treasury by the balance of the faucet account.
+    pub fn update_treasury(&mut self, faucet: &Faucet) {
+        match faucet.balance() {
+            Ok(balance) => {
+                self.treasury.update(balance, self.registry.committed());
+            }
+            Err(e) => {
+                error!("Failed to get the treasury balance: {}", e);
+                self.treasury.poll_failed();
+            }
+        }
+    }
+}
+
+/// Abuse protection of the faucet.
+pub struct FaucetGuard {
+    pub limiter: RateLimiter,
+    pub challenge: Box<dyn Challenge>,
+    /// The token to access the admin api, the admin api is disabled if none.
+    pub admin_token: Option<String>,
+    /// The count of the trusted proxies in front of the faucet, the client ip is the `X-Forwarded-For`
+    /// entry appended by the outermost one. 0 means no proxy, the ip of the connection is used.
+    pub trusted_proxies: usize,
+}
 
 #[derive(RustEmbed)]
 #[folder = "src/static/"]
@@ -27,22 +72,220 @@ fn response_custom(status_code: u16, data: &str) -> Response<Cursor<String>> {
         .with_header(Header::from_str("Access-Control-Allow-Origin: *").unwrap())
 }
 
-async fn handle_fund(faucet: &Faucet, query: &str) -> Response<Cursor<String>> {
+fn header_value<'a>(request: &'a Request, field: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(field))
+        .map(|header| header.value.as_str())
+}
+
+fn remote_ip(request: &Request, trusted_proxies: usize) -> Option<IpAddr> {
+    Some(client_ip(
+        header_value(request, "X-Forwarded-For"),
+        request.remote_addr().ip(),
+        trusted_proxies,
+    ))
+}
+
+/// Each proxy appends the ip it receives from to the `X-Forwarded-For`, the entries before the
+/// ones of the trusted proxies are controlled by the client, so the client ip is the
+/// `trusted_proxies`th entry from the last. Fall back to the `peer_ip` if the entry is absent.
+fn client_ip(forwarded_for: Option<&str>, peer_ip: IpAddr, trusted_proxies: usize) -> IpAddr {
+    if trusted_proxies == 0 {
+        return peer_ip;
+    }
+    let entries: Vec<&str> = forwarded_for
+        .map(|value| value.split(',').map(str::trim).collect())
+        .unwrap_or_default();
+    entries
+        .len()
+        .checked_sub(trusted_proxies)
+        .and_then(|index| entries.get(index))
+        .and_then(|ip| ip.parse().ok())
+        .unwrap_or(peer_ip)
+}
+
+/// Get the url decoded value of the `key` in the query.
+fn query_value(query: &str, key: &str) -> Option<String> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+/// Compare the admin token in constant time, so the token can not be guessed by the timing.
+fn is_admin_token(expected: &str, token: Option<&str>) -> bool {
+    match token {
+        Some(token) => bool::from(expected.as_bytes().ct_eq(token.as_bytes())),
+        None => false,
+    }
+}
+
+fn authorize_project(
+    projects: &FaucetProjects,
+    request: &Request,
+) -> Result<Option<ProjectConfig>, Response<Cursor<String>>> {
+    match header_value(request, API_KEY_HEADER) {
+        Some(api_key) => projects
+            .registry
+            .authorize(api_key)
+            .map(|project| Some(project.clone()))
+            .map_err(|e| response_custom(401, &e.to_string())),
+        None => Ok(None),
+    }
+}
+
+async fn handle_fund(
+    faucet: &Faucet,
+    guard: &mut FaucetGuard,
+    projects: &mut FaucetProjects,
+    request: &Request,
+    query: &str,
+) -> Response<Cursor<String>> {
+    let project = match authorize_project(projects, request) {
+        Ok(project) => project,
+        Err(response) => return response,
+    };
+    // the requests of a project are authorized by its api key, so skip the captcha and the ip
+    // limit, the ip is the backend of the project.
+    let ip = match project {
+        Some(_) => None,
+        None => remote_ip(request, guard.trusted_proxies),
+    };
     let query_param =
         unwrap_or_return!(parse_query(query), response_custom(400, "Invalid request"));
-    info!("Fund query params: {:?}", query_param);
-    match faucet.transfer(
-        query_param.amount,
-        query_param.address,
-        query_param.public_key,
-    ) {
-        Ok(_) => response_custom(200, "Success"),
+    info!(
+        "Fund query params: {:?}, ip: {:?}, project: {:?}",
+        query_param,
+        ip,
+        project.as_ref().map(|project| project.name.as_str())
+    );
+    if let Err(e) = guard.limiter.check(query_param.address, ip) {
+        return response_custom(429, &e.to_string());
+    }
+    if project.is_none() {
+        if let Err(e) = guard.challenge.verify(query_param.captcha.as_deref(), ip) {
+            return response_custom(403, &e.to_string());
+        }
+    }
+    let address = query_param.address;
+    let public_key = query_param.public_key;
+    let result = match &project {
+        Some(project) => projects
+            .registry
+            .fund_amount(project, query_param.amount)
+            .and_then(|amount| {
+                faucet
+                    .transfer_exact(amount, address, public_key)
+                    .map(|_| amount)
+            }),
+        None => faucet.transfer(query_param.amount, address, public_key),
+    };
+    match result {
+        Ok(amount) => {
+            info!("Fund {} to {}", amount, address);
+            if let Err(e) = guard.limiter.record(address, ip) {
+                error!("Failed to record fund limit: {}", e);
+            }
+            if let Some(project) = &project {
+                if let Err(e) = projects.registry.record(&project.name, amount) {
+                    error!("Failed to record fund of project {}: {}", project.name, e);
+                }
+            }
+            projects.update_treasury(faucet);
+            response_custom(200, "Success")
+        }
+        Err(e) => {
+            if let Some(project) = &project {
+                if let Err(e) = projects.registry.record_failure(&project.name) {
+                    error!("Failed to record fund of project {}: {}", project.name, e);
+                }
+            }
+            // the fund may fail for the drained treasury.
+            projects.update_treasury(faucet);
+            response_custom(400, &e.to_string())
+        }
+    }
+}
+
+/// The stats of the project authorized by the api key.
+fn handle_project_stats(projects: &FaucetProjects, request: &Request) -> Response<Cursor<String>> {
+    let project = match authorize_project(projects, request) {
+        Ok(Some(project)) => project,
+        Ok(None) => return response_custom(401, "Api key is required"),
+        Err(response) => return response,
+    };
+    match serde_json::to_string(&projects.registry.view(&project)) {
+        Ok(data) => response_custom(200, &data),
+        Err(e) => response_custom(500, &e.to_string()),
+    }
+}
+
+fn handle_admin(
+    guard: &mut FaucetGuard,
+    projects: &mut FaucetProjects,
+    request: &Request,
+    url: &str,
+    query: &str,
+) -> Response<Cursor<String>> {
+    match guard.admin_token.as_deref() {
+        Some(token) if is_admin_token(token, header_value(request, ADMIN_TOKEN_HEADER)) => {}
+        Some(_) => return response_custom(401, "Unauthorized"),
+        None => return response_custom(404, "Not found"),
+    }
+    let result = match url {
+        "/admin/limits" => serde_json::to_string(guard.limiter.records()).map_err(Into::into),
+        // reset the limit of the address or ip by `key`, reset all limits if `key` is absent.
+        "/admin/limits/reset" => {
+            let key = query_value(query, "key");
+            guard
+                .limiter
+                .reset(key.as_deref())
+                .map(|removed| format!("{{\"removed\":{}}}", removed))
+        }
+        "/admin/projects" => {
+            let committed = projects.registry.committed();
+            serde_json::to_string(&serde_json::json!({
+                "treasury": projects.treasury.view(committed),
+                "projects": projects.registry.views(),
+            }))
+            .map_err(Into::into)
+        }
+        // reset the stats of the project by `name`, reset all projects if `name` is absent.
+        "/admin/projects/reset" => {
+            let name = query_value(query, "name");
+            projects
+                .registry
+                .reset(name.as_deref())
+                .map(|removed| format!("{{\"removed\":{}}}", removed))
+        }
+        _ => return response_custom(404, "Not found"),
+    };
+    match result {
+        Ok(data) => response_custom(200, &data),
         Err(e) => response_custom(400, &e.to_string()),
     }
 }
 
-pub async fn run(server: Server, faucet: Faucet) {
-    for request in server.incoming_requests() {
+pub async fn run(
+    server: Server,
+    faucet: Faucet,
+    mut guard: FaucetGuard,
+    mut projects: FaucetProjects,
+) {
+    loop {
+        // poll the treasury balance while waiting for the requests, to pick up the external drains.
+        if projects.treasury.is_outdated(get_current_timestamp()) {
+            projects.update_treasury(&faucet);
+        }
+        let request = match server.recv_timeout(TREASURY_POLL_TIMEOUT) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(e) => {
+                error!("Failed to receive the request, stop the faucet: {}", e);
+                break;
+            }
+        };
         let pos = request
             .url()
             .find('?')
@@ -59,10 +302,21 @@ pub async fn run(server: Server, faucet: Faucet) {
                 request.respond(response).unwrap();
             }
             "/api/fund" => {
-                let resp = handle_fund(&faucet, query).await;
+                let resp = handle_fund(&faucet, &mut guard, &mut projects, &request, query).await;
                 //todo:: handle io error
                 request.respond(resp).unwrap();
             }
+            "/api/project/stats" => {
+                let resp = handle_project_stats(&projects, &request);
+                let _ = request.respond(resp);
+            }
+            "/admin/limits"
+            | "/admin/limits/reset"
+            | "/admin/projects"
+            | "/admin/projects/reset" => {
+                let resp = handle_admin(&mut guard, &mut projects, &request, url, query);
+                let _ = request.respond(resp);
+            }
             _ => {
                 let _ = request.respond(response_custom(404, "Not found"));
             }
@@ -72,8 +326,10 @@ pub async fn run(server: Server, faucet: Faucet) {
 
 struct QueryParam {
     address: AccountAddress,
-    amount: u128,
+    amount: Option<u128>,
     public_key: Vec<u8>,
+    /// The captcha response for the challenge.
+    captcha: Option<String>,
 }
 
 impl Debug for QueryParam {
@@ -89,29 +345,73 @@ impl Debug for QueryParam {
 }
 
 fn parse_query(query: &str) -> Result<QueryParam> {
-    let mut pairs: Vec<&str> = query.split('&').collect();
-    pairs.sort_unstable();
-    let mut address = "";
-    let mut amount = "";
-    let mut public_key = "";
-    for pair in pairs {
-        let kv: Vec<&str> = pair.split('=').collect();
-        if kv.len() == 2 {
-            match kv[0] {
-                "address" => address = kv[1],
-                "amount" => amount = kv[1],
-                "public_key" => public_key = kv[1],
-                _ => {}
-            };
-        }
-    }
-    let address = AccountAddress::from_str(address)?;
-    let amount = u128::from_str(amount)?;
+    let address = query_value(query, "address").unwrap_or_default();
+    let amount = query_value(query, "amount").unwrap_or_default();
+    let public_key = query_value(query, "public_key").unwrap_or_default();
+    // the captcha response is url encoded by the page, decode it before the verification.
+    let captcha = query_value(query, "captcha");
+    let address = parse_address(address.as_str())?;
+    let amount = if amount.is_empty() {
+        None
+    } else {
+        Some(u128::from_str(amount.as_str())?)
+    };
     let public_key = hex::decode(public_key).unwrap_or_default();
     let query_param = QueryParam {
         address,
         amount,
         public_key,
+        captcha,
     };
     Ok(query_param)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_decode() {
+        let query_param =
+            parse_query("address=0x1&amount=100&captcha=P1_eyJ0%2Bx%2F%3D%3D&public_key=").unwrap();
+        assert_eq!(query_param.address, parse_address("0x1").unwrap());
+        assert_eq!(query_param.amount, Some(100));
+        assert_eq!(query_param.captcha.as_deref(), Some("P1_eyJ0+x/=="));
+        assert!(query_param.public_key.is_empty());
+
+        assert_eq!(
+            query_value("name=my%20project&key=a", "name").as_deref(),
+            Some("my project")
+        );
+        assert_eq!(query_value("name=a", "key"), None);
+    }
+
+    #[test]
+    fn test_is_admin_token() {
+        assert!(is_admin_token("secret", Some("secret")));
+        assert!(!is_admin_token("secret", Some("secreT")));
+        assert!(!is_admin_token("secret", Some("secret1")));
+        assert!(!is_admin_token("secret", Some("")));
+        assert!(!is_admin_token("secret", None));
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let forwarded_for = Some("1.1.1.1, 2.2.2.2, 3.3.3.3");
+        // no proxy, the client controlled header is ignored.
+        assert_eq!(client_ip(forwarded_for, peer_ip, 0), peer_ip);
+        // the entry appended by the only proxy, not the first one set by the client.
+        assert_eq!(
+            client_ip(forwarded_for, peer_ip, 1),
+            "3.3.3.3".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(
+            client_ip(forwarded_for, peer_ip, 2),
+            "2.2.2.2".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(client_ip(forwarded_for, peer_ip, 4), peer_ip);
+        assert_eq!(client_ip(None, peer_ip, 1), peer_ip);
+        assert_eq!(client_ip(Some("1.1.1.1, invalid"), peer_ip, 1), peer_ip);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::EsSinker;
+use anyhow::{bail, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use starcoin_logger::prelude::*;
+use starcoin_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub const DEFAULT_QUERY_LIMIT: u64 = 20;
+pub const MAX_QUERY_LIMIT: u64 = 1000;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Route {
+    AddressTransfers(AccountAddress),
+    TokenHolders(TokenCode),
+}
+
+fn parse_route(path: &str) -> Result<Option<Route>> {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .map(|segment| Ok(percent_decode_str(segment).decode_utf8()?.to_string()))
+        .collect::<Result<Vec<_>>>()?;
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    Ok(match segments.as_slice() {
+        ["v1", "address", address, "transfers"] => {
+            Some(Route::AddressTransfers(parse_address(address)?))
+        }
+        ["v1", "token", token_code, "holders"] => {
+            Some(Route::TokenHolders(TokenCode::from_str(token_code)?))
+        }
+        _ => None,
+    })
+}
+
+fn parse_query(query: Option<&str>) -> HashMap<String, String> {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let mut kv = pair.splitn(2, '=');
+            let key = kv.next()?;
+            let value = kv.next().unwrap_or_default();
+            Some((
+                percent_decode_str(key).decode_utf8_lossy().to_string(),
+                percent_decode_str(value).decode_utf8_lossy().to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn query_u64(query: &HashMap<String, String>, key: &str, default: u64) -> Result<u64> {
+    match query.get(key) {
+        Some(value) => Ok(value.parse()?),
+        None => Ok(default),
+    }
+}
+
+fn query_limit(query: &HashMap<String, String>) -> Result<u64> {
+    let limit = query_u64(query, "limit", DEFAULT_QUERY_LIMIT)?;
+    if limit == 0 || limit > MAX_QUERY_LIMIT {
+        bail!("limit should be in range [1, {}]", MAX_QUERY_LIMIT);
+    }
+    Ok(limit)
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .expect("build response should success")
+}
+
+fn error_response(status: StatusCode, err: impl ToString) -> Response<Body> {
+    json_response(status, &serde_json::json!({ "error": err.to_string() }))
+}
+
+async fn handle(sinker: Arc<EsSinker>, req: Request<Body>) -> Response<Body> {
+    if req.method() != Method::GET {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported");
+    }
+    let route = match parse_route(req.uri().path()) {
+        Ok(Some(route)) => route,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "not found"),
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let query = parse_query(req.uri().query());
+    let result = match route {
+        Route::AddressTransfers(address) => {
+            let limit = match query_limit(&query) {
+                Ok(limit) => limit,
+                Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+            };
+            let offset = match query_u64(&query, "offset", 0) {
+                Ok(offset) => offset,
+                Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+            };
+            sinker
+                .get_address_transfers(address.to_string().as_str(), offset, limit)
+                .await
+                .map(|transfers| json_response(StatusCode::OK, &transfers))
+        }
+        Route::TokenHolders(token_code) => {
+            let limit = match query_limit(&query) {
+                Ok(limit) => limit,
+                Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+            };
+            sinker
+                .get_token_holders(token_code.to_string().as_str(), limit)
+                .await
+                .map(|holders| json_response(StatusCode::OK, &holders))
+        }
+    };
+    result.unwrap_or_else(|e| {
+        error!("[indexer-api] query error: {:?}", e);
+        error_response(StatusCode::INTERNAL_SERVER_ERROR, e)
+    })
+}
+
+/// Serve the token query api:
+/// `GET /v1/address/:addr/transfers?limit=&offset=` and `GET /v1/token/:code/holders?limit=`.
+pub async fn serve(address: SocketAddr, sinker: Arc<EsSinker>) -> Result<()> {
+    let make_service = make_service_fn(move |_conn| {
+        let sinker = sinker.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let sinker = sinker.clone();
+                async move { Ok::<_, Infallible>(handle(sinker, req).await) }
+            }))
+        }
+    });
+    info!("Indexer api listen on: {}", address);
+    Server::bind(&address).serve(make_service).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_route() {
+        let address = AccountAddress::random();
+        assert_eq!(
+            parse_route(format!("/v1/address/{}/transfers", address).as_str()).unwrap(),
+            Some(Route::AddressTransfers(address))
+        );
+        let token_code = TokenCode::from_str("0x1::STC::STC").unwrap();
+        assert_eq!(
+            parse_route("/v1/token/0x1::STC::STC/holders").unwrap(),
+            Some(Route::TokenHolders(token_code.clone()))
+        );
+        assert_eq!(
+            parse_route("/v1/token/0x1%3A%3ASTC%3A%3ASTC/holders").unwrap(),
+            Some(Route::TokenHolders(token_code))
+        );
+        assert!(parse_route("/v1/address/invalid/transfers").is_err());
+        assert_eq!(parse_route("/v1/blocks").unwrap(), None);
+
+        let query = parse_query(Some("limit=10&offset=5"));
+        assert_eq!(query_limit(&query).unwrap(), 10);
+        assert_eq!(query_u64(&query, "offset", 0).unwrap(), 5);
+        assert!(query_limit(&parse_query(Some("limit=0"))).is_err());
+        assert_eq!(
+            query_limit(&parse_query(None)).unwrap(),
+            DEFAULT_QUERY_LIMIT
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{BlockData, TransactionData};
+use anyhow::{ensure, Result};
+use starcoin_block_file::BlockExecutionData;
+use starcoin_rpc_api::types::{BlockView, TransactionEventView, TransactionInfoView};
+use std::convert::TryInto;
+
+/// Build the block data from a record of the block execution file,
+/// it is the same as the one fetched by `BlockClient::get_block_whole_by_height`.
+pub fn block_data_from_execution(data: BlockExecutionData) -> Result<BlockData> {
+    let BlockExecutionData {
+        block,
+        block_metadata,
+        txn_infos,
+        events,
+    } = data;
+    ensure!(
+        txn_infos.len() == block.transactions().len() + 1 && events.len() == txn_infos.len(),
+        "The execution data of block {} mismatch with its txns",
+        block.id()
+    );
+    let block_hash = block.id();
+    let block_number = block.header().number();
+    let timestamp = block.header().timestamp();
+    let mut block_metadata = Some(block_metadata);
+    let mut txns_data = vec![];
+    for (index, (txn_info, events)) in txn_infos.into_iter().zip(events).enumerate() {
+        let info = TransactionInfoView::new(txn_info, &block)?;
+        let events = events
+            .iter()
+            .map(|event| {
+                TransactionEventView::new(
+                    Some(block_hash),
+                    Some(block_number),
+                    Some(info.transaction_hash),
+                    Some(info.transaction_index),
+                    event,
+                )
+                .into()
+            })
+            .collect();
+        // the first txn is the block metadata txn.
+        let user_transaction = match index {
+            0 => None,
+            _ => Some(block.transactions()[index - 1].clone().try_into()?),
+        };
+        txns_data.push(TransactionData {
+            info: info.into(),
+            block_metadata: block_metadata.take().map(Into::into),
+            user_transaction,
+            events,
+            timestamp,
+        });
+    }
+    Ok(BlockData {
+        block: BlockView::try_from_block(block, false)?,
+        txns_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcs_ext::Sample;
+    use starcoin_crypto::HashValue;
+    use starcoin_types::block::Block;
+    use starcoin_types::transaction::TransactionInfo;
+    use starcoin_types::vm_error::KeptVMStatus;
+
+    #[test]
+    fn test_block_data_from_execution() {
+        let block = Block::sample();
+        let block_metadata = block.to_metadata(0);
+        let txn_info = TransactionInfo::new(
+            block_metadata.id(),
+            HashValue::zero(),
+            &[],
+            0,
+            KeptVMStatus::Executed,
+        );
+        let block_data = block_data_from_execution(BlockExecutionData {
+            block: block.clone(),
+            block_metadata,
+            txn_infos: vec![txn_info.clone()],
+            events: vec![vec![]],
+        })
+        .unwrap();
+        assert_eq!(block_data.block.header.block_hash, block.id());
+        assert_eq!(block_data.txns_data.len(), 1);
+        let txn_data = &block_data.txns_data[0];
+        assert_eq!(txn_data.info.transaction_hash, txn_info.transaction_hash());
+        assert_eq!(txn_data.info.transaction_index, 0);
+        assert!(txn_data.block_metadata.is_some());
+        assert!(txn_data.user_transaction.is_none());
+
+        // the txn infos should match the txns of the block.
+        assert!(block_data_from_execution(BlockExecutionData {
+            block: block.clone(),
+            block_metadata: block.to_metadata(0),
+            txn_infos: vec![],
+            events: vec![],
+        })
+        .is_err());
+    }
+}
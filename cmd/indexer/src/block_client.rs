@@ -80,4 +80,7 @@ impl BlockClient {
         let chain_info: ChainInfoView = self.node_client.info().await?;
         Ok(chain_info.head)
     }
+    pub async fn get_chain_info(&self) -> Result<ChainInfoView, RpcError> {
+        self.node_client.info().await
+    }
 }
@@ -1,3 +1,6 @@
+use crate::token::{
+    decode_token_transfers, holder_balance_deltas, TokenHolderEsView, TokenTransferEsView,
+};
 use crate::{BlockData, BlockWithMetadata};
 use anyhow::Result;
 use elasticsearch::indices::{
@@ -5,18 +8,52 @@ use elasticsearch::indices::{
 };
 use elasticsearch::{
     BulkOperation, BulkOperations, BulkParts, DeleteByQueryParts, DeleteParts, Elasticsearch,
-    GetParts,
+    GetParts, SearchParts,
 };
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::StrView;
+use starcoin_types::block::BlockNumber;
 use tokio::sync::RwLock;
 
+const MAX_SEARCH_SIZE: u64 = 10000;
+
+/// The token transfers of a block.
+pub type BlockTransfers = (BlockNumber, Vec<TokenTransferEsView>);
+
+/// Apply the balance delta of a block to the holder, skip it if the block is already applied.
+const APPLY_HOLDER_DELTA_SCRIPT: &str = r#"
+if (ctx._source.last_block_number >= params.block_number) {
+  ctx.op = 'noop';
+} else {
+  BigInteger balance = new BigInteger(ctx._source.balance).add(new BigInteger(params.delta));
+  ctx._source.balance = balance.toString();
+  ctx._source.balance_value = balance.doubleValue();
+  ctx._source.last_block_number = params.block_number;
+}
+"#;
+
+/// Revert the balance delta of a rollbacked block from the holder, skip it if the block is not applied.
+const REVERT_HOLDER_DELTA_SCRIPT: &str = r#"
+if (ctx._source.last_block_number < params.block_number) {
+  ctx.op = 'noop';
+} else {
+  BigInteger balance = new BigInteger(ctx._source.balance).subtract(new BigInteger(params.delta));
+  ctx._source.balance = balance.toString();
+  ctx._source.balance_value = balance.doubleValue();
+  ctx._source.last_block_number = params.block_number - 1;
+}
+"#;
+
 #[derive(Clone, Debug)]
 pub struct IndexConfig {
     pub block_index: String,
     pub txn_info_index: String,
+    pub token_transfer_index: String,
+    pub token_holder_index: String,
 }
 
 impl IndexConfig {
@@ -24,6 +61,8 @@ impl IndexConfig {
         Self {
             block_index: format!("{}.blocks", prefix.as_ref()),
             txn_info_index: format!("{}.txn_infos", prefix.as_ref()),
+            token_transfer_index: format!("{}.token_transfers", prefix.as_ref()),
+            token_holder_index: format!("{}.token_holders", prefix.as_ref()),
         }
     }
 }
@@ -33,6 +72,8 @@ impl Default for IndexConfig {
         Self {
             block_index: "blocks".to_string(),
             txn_info_index: "txn_infos".to_string(),
+            token_transfer_index: "token_transfers".to_string(),
+            token_holder_index: "token_holders".to_string(),
         }
     }
 }
@@ -64,7 +105,7 @@ impl EsSinker {
         }
     }
 
-    async fn create_index_if_not_exists(&self, index: &str) -> Result<()> {
+    async fn create_index_if_not_exists(&self, index: &str, mappings: Option<Value>) -> Result<()> {
         let exists = self
             .es
             .indices()
@@ -74,12 +115,12 @@ impl EsSinker {
             .status_code()
             .is_success();
         if !exists {
-            self.es
-                .indices()
-                .create(IndicesCreateParts::Index(index))
-                .send()
-                .await?
-                .error_for_status_code()?;
+            let create = self.es.indices().create(IndicesCreateParts::Index(index));
+            let create = match mappings {
+                Some(mappings) => create.body(serde_json::json!({ "mappings": mappings })),
+                None => create,
+            };
+            create.send().await?.error_for_status_code()?;
         }
         Ok(())
     }
@@ -87,8 +128,38 @@ impl EsSinker {
     pub async fn init_indices(&self) -> Result<()> {
         let block_index = self.config.block_index.as_str();
         let txn_info_index = self.config.txn_info_index.as_str();
-        self.create_index_if_not_exists(block_index).await?;
-        self.create_index_if_not_exists(txn_info_index).await?;
+        self.create_index_if_not_exists(block_index, None).await?;
+        self.create_index_if_not_exists(txn_info_index, None)
+            .await?;
+        self.create_index_if_not_exists(
+            self.config.token_transfer_index.as_str(),
+            Some(serde_json::json!({
+                "properties": {
+                    "block_hash": {"type": "keyword"},
+                    "block_number": {"type": "long"},
+                    "transaction_hash": {"type": "keyword"},
+                    "timestamp": {"type": "long"},
+                    "address": {"type": "keyword"},
+                    "token_code": {"type": "keyword"},
+                    "amount": {"type": "keyword"},
+                    "kind": {"type": "keyword"},
+                }
+            })),
+        )
+        .await?;
+        self.create_index_if_not_exists(
+            self.config.token_holder_index.as_str(),
+            Some(serde_json::json!({
+                "properties": {
+                    "address": {"type": "keyword"},
+                    "token_code": {"type": "keyword"},
+                    "balance": {"type": "keyword"},
+                    "balance_value": {"type": "double"},
+                    "last_block_number": {"type": "long"},
+                }
+            })),
+        )
+        .await?;
         let tip = self.get_remote_tip_header().await?;
         self.state.write().await.tip = tip.clone();
         if let Some(tip_info) = tip {
@@ -186,7 +257,12 @@ impl EsSinker {
             anyhow::bail!("cannot get block data with id {}", block_id);
         };
 
-        // first, rollback tip header
+        // revert token holder balances and delete token transfers of the block,
+        // the revert is skipped if it is already done, so it is safe to retry.
+        self.rollback_token_transfers(block_id.as_str(), tip_header.block_number)
+            .await?;
+
+        // then, rollback tip header
         let rollback_to = (parent_hash, tip_header.block_number - 1);
         self.update_remote_tip_header(rollback_to.0, rollback_to.1)
             .await?;
@@ -248,6 +324,193 @@ impl EsSinker {
         Ok(())
     }
 
+    async fn rollback_token_transfers(
+        &self,
+        block_id: &str,
+        block_number: BlockNumber,
+    ) -> Result<()> {
+        let token_transfer_index = self.config.token_transfer_index.as_str();
+        let (total, transfers): (u64, Vec<TokenTransferEsView>) = self
+            .search_with_total(
+                token_transfer_index,
+                serde_json::json!({
+                    "query": {
+                        "term": {
+                            "block_hash": block_id,
+                        }
+                    },
+                    "size": MAX_SEARCH_SIZE,
+                    "track_total_hits": true,
+                }),
+            )
+            .await?;
+        // the holders can not be reverted partially, so do not rollback a block with too many transfers.
+        anyhow::ensure!(
+            total <= MAX_SEARCH_SIZE,
+            "block {} has {} token transfers, exceeds the max {} of a rollback",
+            block_id,
+            total,
+            MAX_SEARCH_SIZE
+        );
+        if transfers.is_empty() {
+            return Ok(());
+        }
+        let mut bulk_operations = BulkOperations::new();
+        for ((address, token_code), delta) in holder_balance_deltas(&transfers) {
+            let holder = TokenHolderEsView {
+                address,
+                token_code,
+                balance: StrView(0),
+                last_block_number: block_number.saturating_sub(1),
+            };
+            bulk_operations.push(self.holder_update_operation(
+                holder,
+                REVERT_HOLDER_DELTA_SCRIPT,
+                delta,
+                block_number,
+            ))?;
+        }
+        self.send_bulk(bulk_operations).await?;
+
+        let resp = self
+            .es
+            .delete_by_query(DeleteByQueryParts::Index(&[token_transfer_index]))
+            .body(serde_json::json!({
+                "query": {
+                    "term": {
+                        "block_hash": block_id,
+                    }
+                }
+            }))
+            .send()
+            .await?;
+        let exception = resp.exception().await?;
+        if let Some(ex) = exception {
+            anyhow::bail!("{}", serde_json::to_string(&ex)?);
+        }
+        info!(
+            "cleanup block {}, delete {} token transfers",
+            block_id,
+            transfers.len()
+        );
+        Ok(())
+    }
+
+    /// Build the holder balance update by the `script`, the `holder` is inserted if not exists.
+    fn holder_update_operation(
+        &self,
+        holder: TokenHolderEsView,
+        script: &str,
+        delta: i128,
+        block_number: BlockNumber,
+    ) -> BulkOperation<Value> {
+        let balance_value = holder.balance.0 as f64;
+        BulkOperation::update(
+            TokenHolderEsView::id(holder.address.as_str(), holder.token_code.as_str()),
+            serde_json::json!({
+                "script": {
+                    "source": script,
+                    "lang": "painless",
+                    "params": {
+                        "delta": delta.to_string(),
+                        "block_number": block_number,
+                    }
+                },
+                "upsert": {
+                    "address": holder.address,
+                    "token_code": holder.token_code,
+                    "balance": holder.balance,
+                    "balance_value": balance_value,
+                    "last_block_number": holder.last_block_number,
+                }
+            }),
+        )
+        .index(self.config.token_holder_index.as_str())
+        .into()
+    }
+
+    async fn search<T: DeserializeOwned>(&self, index: &str, body: Value) -> Result<Vec<T>> {
+        Ok(self.search_with_total(index, body).await?.1)
+    }
+
+    /// Search the index, return the total hits and the hits of the page.
+    async fn search_with_total<T: DeserializeOwned>(
+        &self,
+        index: &str,
+        body: Value,
+    ) -> Result<(u64, Vec<T>)> {
+        let resp: Value = self
+            .es
+            .search(SearchParts::Index(&[index]))
+            .body(body)
+            .send()
+            .await?
+            .error_for_status_code()?
+            .json()
+            .await?;
+        let total = resp["hits"]["total"]["value"].as_u64().unwrap_or_default();
+        let hits = match resp["hits"]["hits"].as_array() {
+            Some(hits) => hits
+                .iter()
+                .map(|hit| Ok(serde_json::from_value(hit["_source"].clone())?))
+                .collect::<Result<Vec<T>>>()?,
+            None => vec![],
+        };
+        Ok((total, hits))
+    }
+
+    /// Get the token transfers of the address, the latest first.
+    pub async fn get_address_transfers(
+        &self,
+        address: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<TokenTransferEsView>> {
+        self.search(
+            self.config.token_transfer_index.as_str(),
+            serde_json::json!({
+                "query": {
+                    "term": {
+                        "address": address,
+                    }
+                },
+                "sort": [
+                    {"block_number": "desc"},
+                    {"transaction_index": "desc"},
+                ],
+                "from": offset,
+                "size": limit,
+            }),
+        )
+        .await
+    }
+
+    /// Get the holders of the token with a positive balance, the largest balance first.
+    pub async fn get_token_holders(
+        &self,
+        token_code: &str,
+        limit: u64,
+    ) -> Result<Vec<TokenHolderEsView>> {
+        self.search(
+            self.config.token_holder_index.as_str(),
+            serde_json::json!({
+                "query": {
+                    "bool": {
+                        "filter": [
+                            {"term": {"token_code": token_code}},
+                            {"range": {"balance_value": {"gt": 0}}},
+                        ]
+                    }
+                },
+                "sort": [
+                    {"balance_value": "desc"},
+                ],
+                "size": limit,
+            }),
+        )
+        .await
+    }
+
     pub async fn repair_block(&self, block: BlockData) -> Result<()> {
         self.bulk(vec![block]).await?;
         Ok(())
@@ -259,8 +522,42 @@ impl EsSinker {
             return Ok(());
         }
         let mut bulk_operations = BulkOperations::new();
+        let block_transfers = self.push_document_operations(&mut bulk_operations, blocks)?;
+        self.push_holder_operations(&mut bulk_operations, &block_transfers)?;
+        self.send_bulk(bulk_operations).await
+    }
+
+    /// Bulk insert the blocks, txn infos and token transfers without updating the token holders,
+    /// so the blocks can be written concurrently.
+    /// The returned token transfers of the blocks should be applied by `bulk_holder_updates` in the block order.
+    pub async fn bulk_documents(&self, blocks: Vec<BlockData>) -> Result<Vec<BlockTransfers>> {
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut bulk_operations = BulkOperations::new();
+        let block_transfers = self.push_document_operations(&mut bulk_operations, blocks)?;
+        self.send_bulk(bulk_operations).await?;
+        Ok(block_transfers)
+    }
+
+    /// Apply the token transfers of the blocks to the token holders, the blocks should be in order.
+    pub async fn bulk_holder_updates(&self, block_transfers: &[BlockTransfers]) -> Result<()> {
+        let mut bulk_operations = BulkOperations::new();
+        if self.push_holder_operations(&mut bulk_operations, block_transfers)? == 0 {
+            return Ok(());
+        }
+        self.send_bulk(bulk_operations).await
+    }
+
+    fn push_document_operations(
+        &self,
+        bulk_operations: &mut BulkOperations,
+        blocks: Vec<BlockData>,
+    ) -> Result<Vec<BlockTransfers>> {
         let block_index = self.config.block_index.as_str();
         let txn_info_index = self.config.txn_info_index.as_str();
+        let token_transfer_index = self.config.token_transfer_index.as_str();
+        let mut block_transfers = vec![];
         for blockdata in blocks {
             let BlockData { block, txns_data } = blockdata;
             bulk_operations.push(
@@ -272,15 +569,56 @@ impl EsSinker {
                 .index(block_index),
             )?;
 
+            let mut transfers = vec![];
             for txn_data in txns_data {
+                transfers.extend(decode_token_transfers(&txn_data)?);
                 bulk_operations.push(
                     BulkOperation::index(txn_data.clone())
                         .id(txn_data.info.transaction_hash.to_string())
                         .index(txn_info_index),
                 )?;
             }
+            for transfer in &transfers {
+                bulk_operations.push(
+                    BulkOperation::index(transfer.clone())
+                        .id(transfer.id())
+                        .index(token_transfer_index),
+                )?;
+            }
+            block_transfers.push((block.header.number.0, transfers));
         }
+        Ok(block_transfers)
+    }
+
+    /// Push the holder updates of the blocks, return the count of the updates.
+    fn push_holder_operations(
+        &self,
+        bulk_operations: &mut BulkOperations,
+        block_transfers: &[BlockTransfers],
+    ) -> Result<usize> {
+        let mut count = 0;
+        // the holder update is skipped if the block is already applied, so it is safe to retry.
+        for (block_number, transfers) in block_transfers {
+            for ((address, token_code), delta) in holder_balance_deltas(transfers) {
+                let holder = TokenHolderEsView {
+                    address,
+                    token_code,
+                    balance: StrView(delta),
+                    last_block_number: *block_number,
+                };
+                bulk_operations.push(self.holder_update_operation(
+                    holder,
+                    APPLY_HOLDER_DELTA_SCRIPT,
+                    delta,
+                    *block_number,
+                ))?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
 
+    async fn send_bulk(&self, bulk_operations: BulkOperations) -> Result<()> {
         let resp = self
             .es
             .bulk(BulkParts::None)
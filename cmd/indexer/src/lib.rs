@@ -1,7 +1,14 @@
+pub mod api;
+mod archive;
 mod block_client;
 mod es_sinker;
+mod token;
+pub use archive::block_data_from_execution;
 pub use block_client::BlockClient;
-pub use es_sinker::{EsSinker, IndexConfig, LocalTipInfo};
+pub use es_sinker::{BlockTransfers, EsSinker, IndexConfig, LocalTipInfo};
+pub use token::{
+    decode_token_transfers, TokenHolderEsView, TokenTransferEsView, TokenTransferKind,
+};
 
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
@@ -205,6 +212,7 @@ mod tests {
             type_tag: TypeTag::Bool,
             event_key: EventKey::new_from_address(&AccountAddress::ZERO, 0),
             event_seq_number: StrView(0),
+            payment_reference: None,
         };
         let event_view = TransactionEventEsView::from(v);
         let expected = r#"
@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use clap::Clap;
 use elasticsearch::auth::Credentials;
 use elasticsearch::http::transport::SingleNodeConnectionPool;
@@ -7,10 +7,20 @@ use elasticsearch::Elasticsearch;
 use futures_retry::{FutureRetry, RetryPolicy};
 use futures_util::TryFutureExt;
 use jsonrpc_core_client::transports::http;
-use starcoin_indexer::{BlockClient, BlockData, EsSinker, IndexConfig};
+use starcoin_block_file::{BlockExecutionData, BlockFileReader};
+use starcoin_indexer::{
+    api, block_data_from_execution, BlockClient, BlockData, BlockTransfers, EsSinker, IndexConfig,
+    LocalTipInfo,
+};
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::chain::ChainClient;
-use std::cmp::min;
+use starcoin_types::block::BlockNumber;
+use std::cmp::{max, min};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
 use tokio::runtime;
@@ -34,6 +44,11 @@ pub struct Options {
     node_url: String,
     #[clap(long, about = "es bulk size", default_value = "50")]
     bulk_size: u64,
+    #[clap(
+        long,
+        about = "serve the token query api on the address, such as 127.0.0.1:9870"
+    )]
+    api_address: Option<SocketAddr>,
 
     #[clap(subcommand)]
     subcmd: Option<SubCommand>,
@@ -42,6 +57,7 @@ pub struct Options {
 #[derive(Clap, Debug, Clone)]
 enum SubCommand {
     Repair(Repair),
+    Ingest(Ingest),
 }
 
 /// repair sub command
@@ -56,7 +72,35 @@ struct Repair {
     to_block: Option<u64>,
 }
 
-async fn start_loop(block_client: BlockClient, sinker: EsSinker, bulk_size: u64) -> Result<()> {
+/// ingest sub command, index the history blocks from the archives without rpc,
+/// then keep indexing the new blocks by rpc.
+#[derive(Clap, Debug, Clone)]
+struct Ingest {
+    #[clap(
+        long = "archive",
+        parse(from_os_str),
+        required = true,
+        about = "block execution files exported by `starcoin_db_exporter export --with-execution-data`, in the block order"
+    )]
+    archives: Vec<PathBuf>,
+    #[clap(
+        long,
+        about = "the number of concurrent es bulk writes",
+        default_value = "4"
+    )]
+    workers: usize,
+    #[clap(
+        long,
+        about = "exit after the archives are ingested, do not tail the chain by rpc"
+    )]
+    no_tail: bool,
+}
+
+async fn start_loop(
+    block_client: BlockClient,
+    sinker: Arc<EsSinker>,
+    bulk_size: u64,
+) -> Result<()> {
     sinker.init_indices().await?;
 
     loop {
@@ -175,7 +219,7 @@ async fn start_loop(block_client: BlockClient, sinker: EsSinker, bulk_size: u64)
 
 async fn repair(
     block_client: BlockClient,
-    sinker: EsSinker,
+    sinker: Arc<EsSinker>,
     repair_config: Repair,
     bulk_size: u64,
 ) -> Result<()> {
@@ -235,6 +279,169 @@ async fn repair(
     Ok(())
 }
 
+/// Read the next batch of blocks from the archive, the blocks before `next_number` are skipped.
+fn read_archive_batch(
+    reader: &mut BlockFileReader<Box<dyn Read + Send>, BlockExecutionData>,
+    next_number: BlockNumber,
+    size: u64,
+) -> Result<Vec<BlockData>> {
+    let mut batch = vec![];
+    while (batch.len() as u64) < size {
+        let data = match reader.next() {
+            Some(data) => data?,
+            None => break,
+        };
+        if data.block.header().number() < next_number {
+            continue;
+        }
+        batch.push(block_data_from_execution(data)?);
+    }
+    Ok(batch)
+}
+
+/// Apply the token holder updates of an ingested batch, then move the tip to the batch end.
+async fn apply_ingested_batch(
+    sinker: &EsSinker,
+    tip: LocalTipInfo,
+    block_transfers: Vec<BlockTransfers>,
+) -> Result<()> {
+    FutureRetry::new(
+        || sinker.bulk_holder_updates(&block_transfers),
+        |e: anyhow::Error| {
+            warn!("[Retry]: update token holders, err: {}", e);
+            RetryPolicy::<anyhow::Error>::WaitRetry(Duration::from_secs(1))
+        },
+    )
+    .await
+    .map(|(d, _)| d)
+    .map_err(|(e, _)| e)?;
+    FutureRetry::new(
+        || sinker.update_remote_tip_header(tip.block_hash, tip.block_number),
+        |e: anyhow::Error| {
+            warn!("[Retry]: update remote tip, err: {}", e);
+            RetryPolicy::<anyhow::Error>::WaitRetry(Duration::from_secs(1))
+        },
+    )
+    .await
+    .map(|(d, _)| d)
+    .map_err(|(e, _)| e)?;
+    sinker
+        .update_local_tip_header(tip.block_hash, tip.block_number)
+        .await?;
+    info!("Ingested to block {}, {}", tip.block_number, tip.block_hash);
+    Ok(())
+}
+
+async fn ingest(
+    block_client: &BlockClient,
+    sinker: Arc<EsSinker>,
+    ingest_config: Ingest,
+    bulk_size: u64,
+) -> Result<()> {
+    sinker.init_indices().await?;
+    let chain_info = block_client
+        .get_chain_info()
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+    let workers = max(ingest_config.workers, 1);
+    // the last block sent to write, the batches are written concurrently,
+    // but the token holders and the tip are updated in the block order.
+    let mut last = sinker.get_local_tip_header().await?;
+    let mut pending = VecDeque::new();
+    for path in ingest_config.archives.iter() {
+        let mut reader = BlockFileReader::<_, BlockExecutionData>::open(path)?;
+        let header = reader.header().clone();
+        header
+            .check_chain(chain_info.chain_id.into(), chain_info.genesis_hash)
+            .map_err(|e| anyhow!("Invalid archive {}: {}", path.display(), e))?;
+        let next_number = last
+            .as_ref()
+            .map(|tip| tip.block_number + 1)
+            .unwrap_or_default();
+        if header.end < next_number {
+            info!(
+                "Skip archive {}, blocks [{}, {}] are already indexed",
+                path.display(),
+                header.start,
+                header.end
+            );
+            continue;
+        }
+        ensure!(
+            header.start <= next_number,
+            "The archive {} starts at block {}, but the next block to index is {}",
+            path.display(),
+            header.start,
+            next_number
+        );
+        info!(
+            "Ingesting archive {}, blocks [{}, {}]",
+            path.display(),
+            next_number,
+            header.end
+        );
+        loop {
+            let (returned_reader, batch) = tokio::task::spawn_blocking(move || {
+                let batch = read_archive_batch(&mut reader, next_number, bulk_size);
+                (reader, batch)
+            })
+            .await?;
+            reader = returned_reader;
+            let batch = batch?;
+            if batch.is_empty() {
+                break;
+            }
+            for block_data in batch.iter() {
+                let block_header = &block_data.block.header;
+                match last.as_ref() {
+                    Some(parent) => ensure!(
+                        block_header.parent_hash == parent.block_hash
+                            && block_header.number.0 == parent.block_number + 1,
+                        "Block {}({}) is not the child of the indexed block {}({})",
+                        block_header.number,
+                        block_header.block_hash,
+                        parent.block_number,
+                        parent.block_hash
+                    ),
+                    None => ensure!(
+                        block_header.number.0 == 0,
+                        "Nothing is indexed, the archive should start from the genesis, but got block {}",
+                        block_header.number
+                    ),
+                }
+                last = Some(LocalTipInfo {
+                    block_hash: block_header.block_hash,
+                    block_number: block_header.number.0,
+                });
+            }
+            let tip = last.clone().expect("the batch is not empty");
+            let write_sinker = sinker.clone();
+            let write_task = tokio::spawn(async move {
+                FutureRetry::new(
+                    || write_sinker.bulk_documents(batch.clone()),
+                    |e: anyhow::Error| {
+                        warn!("[Retry]: write archive blocks, err: {}", e);
+                        RetryPolicy::<anyhow::Error>::WaitRetry(Duration::from_secs(1))
+                    },
+                )
+                .await
+                .map(|(d, _)| d)
+                .map_err(|(e, _)| e)
+            });
+            pending.push_back((tip, write_task));
+            if pending.len() >= workers {
+                if let Some((tip, write_task)) = pending.pop_front() {
+                    apply_ingested_batch(sinker.as_ref(), tip, write_task.await??).await?;
+                }
+            }
+        }
+    }
+    while let Some((tip, write_task)) = pending.pop_front() {
+        apply_ingested_batch(sinker.as_ref(), tip, write_task.await??).await?;
+    }
+    Ok(())
+}
+
 fn main() -> anyhow::Result<()> {
     let _log_handle = starcoin_logger::init();
     let opts: Options = Options::parse();
@@ -260,7 +467,7 @@ fn main() -> anyhow::Result<()> {
     let transport = transport.build()?;
     let es = Elasticsearch::new(transport);
     let index_config = IndexConfig::new_with_prefix(opts.es_index_prefix.as_str());
-    let sinker = EsSinker::new(es, index_config);
+    let sinker = Arc::new(EsSinker::new(es, index_config));
     let bulk_size = opts.bulk_size;
 
     match &opts.subcmd {
@@ -272,7 +479,26 @@ fn main() -> anyhow::Result<()> {
                 bulk_size,
             ))?;
         }
+        Some(SubCommand::Ingest(ingest_config)) => {
+            rt.block_on(ingest(
+                &block_client,
+                sinker.clone(),
+                ingest_config.clone(),
+                bulk_size,
+            ))?;
+            if !ingest_config.no_tail {
+                rt.block_on(start_loop(block_client, sinker, bulk_size))?;
+            }
+        }
         None => {
+            if let Some(api_address) = opts.api_address {
+                let api_sinker = sinker.clone();
+                rt.spawn(async move {
+                    if let Err(e) = api::serve(api_address, api_sinker).await {
+                        error!("Indexer api exit with error: {:?}", e);
+                    }
+                });
+            }
             rt.block_on(start_loop(block_client, sinker, bulk_size))?;
         }
     }
@@ -0,0 +1,263 @@
+use crate::{TransactionData, TransactionEventEsView, TransactionVMStatusEsView};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::StrView;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{BurnEvent, DepositEvent, MintEvent, WithdrawEvent};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::event::EventKey;
+use starcoin_types::language_storage::TypeTag;
+use starcoin_vm_types::move_resource::MoveResource;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenTransferKind {
+    Deposit,
+    Withdraw,
+    Mint,
+    Burn,
+    /// The gas fee charged from the sender of a user transaction, which emits no event.
+    GasFee,
+}
+
+impl TokenTransferKind {
+    /// The balance delta of the transfer to the holder `address`.
+    /// Mint and burn events are emitted by the token issuer, the holder balance is changed by
+    /// the related deposit and withdraw.
+    pub fn balance_delta(self, amount: u128) -> i128 {
+        match self {
+            TokenTransferKind::Deposit => amount as i128,
+            TokenTransferKind::Withdraw | TokenTransferKind::GasFee => -(amount as i128),
+            TokenTransferKind::Mint | TokenTransferKind::Burn => 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TokenTransferEsView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub transaction_hash: HashValue,
+    // txn index in block
+    pub transaction_index: u32,
+    pub timestamp: u64,
+    /// The account which the token is deposited to or withdrawn from,
+    /// or the token issuer for mint and burn.
+    pub address: String,
+    pub token_code: String,
+    pub amount: StrView<u128>,
+    pub kind: TokenTransferKind,
+    /// The event key and sequence number of the token event, None for gas fee.
+    pub event_key: Option<EventKey>,
+    pub event_seq_number: Option<StrView<u64>>,
+}
+
+impl TokenTransferEsView {
+    /// The es document id, unique for every transfer of a transaction.
+    pub fn id(&self) -> String {
+        match (&self.event_key, &self.event_seq_number) {
+            (Some(event_key), Some(seq)) => {
+                format!("{}-{}-{}", self.transaction_hash, event_key, seq.0)
+            }
+            _ => format!("{}-gas", self.transaction_hash),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TokenHolderEsView {
+    pub address: String,
+    pub token_code: String,
+    pub balance: StrView<i128>,
+    /// The number of the last block which changed the balance.
+    pub last_block_number: BlockNumber,
+}
+
+impl TokenHolderEsView {
+    pub fn id(address: &str, token_code: &str) -> String {
+        format!("{}-{}", address, token_code)
+    }
+}
+
+fn is_event<T: MoveResource>(type_tag: &TypeTag) -> bool {
+    matches!(type_tag, TypeTag::Struct(struct_tag) if struct_tag == &T::struct_tag())
+}
+
+/// Decode the event into (kind, token_code, amount), return None if it is not a token event.
+fn decode_token_event(
+    event: &TransactionEventEsView,
+) -> Result<Option<(TokenTransferKind, String, u128)>> {
+    let type_tag = &event.type_tag.0;
+    let data = event.data.0.as_slice();
+    let decoded = if is_event::<DepositEvent>(type_tag) {
+        let e = DepositEvent::try_from_bytes(data)?;
+        Some((
+            TokenTransferKind::Deposit,
+            e.token_code().to_string(),
+            e.amount(),
+        ))
+    } else if is_event::<WithdrawEvent>(type_tag) {
+        let e = WithdrawEvent::try_from_bytes(data)?;
+        Some((
+            TokenTransferKind::Withdraw,
+            e.token_code().to_string(),
+            e.amount(),
+        ))
+    } else if is_event::<MintEvent>(type_tag) {
+        let e = MintEvent::try_from_bytes(data)?;
+        Some((
+            TokenTransferKind::Mint,
+            e.token_code().to_string(),
+            e.amount(),
+        ))
+    } else if is_event::<BurnEvent>(type_tag) {
+        let e = BurnEvent::try_from_bytes(data)?;
+        Some((
+            TokenTransferKind::Burn,
+            e.token_code().to_string(),
+            e.amount(),
+        ))
+    } else {
+        None
+    };
+    Ok(decoded)
+}
+
+/// Decode the token transfers of a transaction from its deposit/withdraw/mint/burn events and gas fee.
+pub fn decode_token_transfers(txn_data: &TransactionData) -> Result<Vec<TokenTransferEsView>> {
+    let info = &txn_data.info;
+    let new_transfer =
+        |address: AccountAddress,
+         token_code: String,
+         amount: u128,
+         kind: TokenTransferKind,
+         event: Option<&TransactionEventEsView>| TokenTransferEsView {
+            block_hash: info.block_hash,
+            block_number: info.block_number,
+            transaction_hash: info.transaction_hash,
+            transaction_index: info.transaction_index,
+            timestamp: txn_data.timestamp,
+            address: address.to_string(),
+            token_code,
+            amount: StrView(amount),
+            kind,
+            event_key: event.map(|event| event.event_key),
+            event_seq_number: event.map(|event| event.event_seq_number),
+        };
+
+    let mut transfers = vec![];
+    for event in &txn_data.events {
+        if let Some((kind, token_code, amount)) = decode_token_event(event)? {
+            transfers.push(new_transfer(
+                event.event_key.get_creator_address(),
+                token_code,
+                amount,
+                kind,
+                Some(event),
+            ));
+        }
+    }
+    if let Some(user_txn) = &txn_data.user_transaction {
+        let is_discarded = matches!(&info.status, TransactionVMStatusEsView::Discard { .. });
+        let raw_txn = &user_txn.raw_txn;
+        let gas_fee = u128::from(info.gas_used.0) * u128::from(raw_txn.gas_unit_price.0);
+        if !is_discarded && gas_fee > 0 {
+            transfers.push(new_transfer(
+                raw_txn.sender,
+                raw_txn.gas_token_code.clone(),
+                gas_fee,
+                TokenTransferKind::GasFee,
+                None,
+            ));
+        }
+    }
+    Ok(transfers)
+}
+
+/// Sum the balance deltas of the transfers per (address, token_code).
+pub fn holder_balance_deltas<'a>(
+    transfers: impl IntoIterator<Item = &'a TokenTransferEsView>,
+) -> BTreeMap<(String, String), i128> {
+    let mut deltas = BTreeMap::new();
+    for transfer in transfers {
+        let delta = transfer.kind.balance_delta(transfer.amount.0);
+        if delta == 0 {
+            continue;
+        }
+        *deltas
+            .entry((transfer.address.clone(), transfer.token_code.clone()))
+            .or_insert(0i128) += delta;
+    }
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransactionInfoEsView;
+    use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+
+    fn event_view<T: MoveResource + Serialize>(
+        event: &T,
+        address: AccountAddress,
+        seq: u64,
+    ) -> TransactionEventEsView {
+        TransactionEventEsView {
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            data: StrView(bcs_ext::to_bytes(event).unwrap()),
+            type_tag: StrView(TypeTag::Struct(T::struct_tag())),
+            event_key: EventKey::new_from_address(&address, 0),
+            event_seq_number: StrView(seq),
+        }
+    }
+
+    #[test]
+    fn test_decode_token_transfers() {
+        let sender = AccountAddress::random();
+        let receiver = AccountAddress::random();
+        let txn_data = TransactionData {
+            info: TransactionInfoEsView {
+                block_hash: HashValue::random(),
+                block_number: StrView(1),
+                transaction_hash: HashValue::random(),
+                transaction_index: 1,
+                state_root_hash: HashValue::zero(),
+                event_root_hash: HashValue::zero(),
+                gas_used: StrView(100),
+                status: TransactionVMStatusEsView::Executed,
+            },
+            block_metadata: None,
+            user_transaction: None,
+            events: vec![
+                event_view(
+                    &WithdrawEvent::new(1000, STC_TOKEN_CODE.clone(), vec![]),
+                    sender,
+                    0,
+                ),
+                event_view(
+                    &DepositEvent::new(1000, STC_TOKEN_CODE.clone(), vec![]),
+                    receiver,
+                    3,
+                ),
+            ],
+            timestamp: 0,
+        };
+        let transfers = decode_token_transfers(&txn_data).unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].kind, TokenTransferKind::Withdraw);
+        assert_eq!(transfers[0].address, sender.to_string());
+        assert_eq!(transfers[1].kind, TokenTransferKind::Deposit);
+        assert_eq!(transfers[1].token_code, STC_TOKEN_CODE.to_string());
+        assert_ne!(transfers[0].id(), transfers[1].id());
+
+        let deltas = holder_balance_deltas(&transfers);
+        let stc = STC_TOKEN_CODE.to_string();
+        assert_eq!(deltas[&(sender.to_string(), stc.clone())], -1000);
+        assert_eq!(deltas[&(receiver.to_string(), stc)], 1000);
+    }
+}
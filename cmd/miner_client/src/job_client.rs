@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::JobClient;
-use anyhow::Result;
+use anyhow::{bail, ensure, Result};
 use futures::stream::BoxStream;
 use futures::{stream::StreamExt, Future, TryStreamExt};
 use futures_channel::mpsc::{unbounded, UnboundedSender};
 use futures_timer::Delay;
 use logger::prelude::*;
+use parking_lot::Mutex;
 use starcoin_config::{RealTimeService, TimeService};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::block::BlockHeaderExtra;
@@ -15,33 +16,126 @@ use starcoin_types::system_events::MintBlockEvent;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Connect to one of the nodes, and fail over to the next node when the connection is lost.
+pub struct NodeConnector {
+    servers: Vec<String>,
+    state: Mutex<ConnectorState>,
+}
+
+struct ConnectorState {
+    index: usize,
+    client: Option<Arc<RpcClient>>,
+}
+
+impl NodeConnector {
+    pub fn new(servers: Vec<String>) -> Result<Self> {
+        ensure!(
+            !servers.is_empty(),
+            "At least one node server should be specified"
+        );
+        Ok(Self {
+            servers,
+            state: Mutex::new(ConnectorState {
+                index: 0,
+                client: None,
+            }),
+        })
+    }
+
+    /// Get the client of the current node, try to connect the nodes in turn if not connected.
+    pub fn client(&self) -> Result<Arc<RpcClient>> {
+        let mut state = self.state.lock();
+        if let Some(client) = state.client.as_ref() {
+            return Ok(client.clone());
+        }
+        for _ in 0..self.servers.len() {
+            let server = &self.servers[state.index];
+            match RpcClient::connect_websocket(&format!("ws://{}", server)) {
+                Ok(client) => {
+                    info!("Connected to starcoin node: {}", server);
+                    let client = Arc::new(client);
+                    state.client = Some(client.clone());
+                    return Ok(client);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to starcoin node: {}, error: {}",
+                        server, e
+                    );
+                    state.index = (state.index + 1) % self.servers.len();
+                }
+            }
+        }
+        bail!(
+            "Failed to connect to any starcoin node of {:?}",
+            self.servers
+        )
+    }
+
+    /// Submit the seal to the current node, and fail over to the next node if the submission failed,
+    /// until the seal is submitted or all the nodes are tried.
+    pub fn submit_seal(&self, minting_blob: String, nonce: u32, extra: String) -> Result<()> {
+        for _ in 0..self.servers.len() {
+            let client = self.client()?;
+            match client.miner_submit(minting_blob.clone(), nonce, extra.clone()) {
+                Ok(_) => return Ok(()),
+                Err(e) => {
+                    warn!("Submit seal error: {}, try the next node.", e);
+                    self.failover(&client);
+                }
+            }
+        }
+        bail!(
+            "Failed to submit seal to any starcoin node of {:?}",
+            self.servers
+        )
+    }
+
+    /// Drop the connection if it is still the current one, the next node is connected later.
+    pub fn failover(&self, client: &Arc<RpcClient>) {
+        let mut state = self.state.lock();
+        let is_current = state
+            .client
+            .as_ref()
+            .map(|current| Arc::ptr_eq(current, client))
+            .unwrap_or(false);
+        if is_current {
+            let lost = state.index;
+            state.client = None;
+            state.index = (state.index + 1) % self.servers.len();
+            info!(
+                "Connection to starcoin node {} lost, fail over to {}",
+                self.servers[lost], self.servers[state.index]
+            );
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct JobRpcClient {
-    rpc_client: Arc<RpcClient>,
+    connector: Arc<NodeConnector>,
     seal_sender: UnboundedSender<(Vec<u8>, u32, BlockHeaderExtra)>,
     time_service: Arc<dyn TimeService>,
 }
 
 impl JobRpcClient {
-    pub fn new(rpc_client: RpcClient) -> Self {
-        let rpc_client = Arc::new(rpc_client);
-        let seal_client = rpc_client.clone();
+    pub fn new(connector: NodeConnector) -> Self {
+        let connector = Arc::new(connector);
+        let seal_connector = connector.clone();
         let (seal_sender, mut seal_receiver) = unbounded::<(Vec<u8>, u32, BlockHeaderExtra)>();
         let fut = async move {
             while let Some((minting_blob, nonce, extra)) = seal_receiver.next().await {
-                if let Err(e) = seal_client.miner_submit(
-                    hex::encode(minting_blob),
-                    nonce,
-                    hex::encode(extra.to_vec()),
-                ) {
-                    warn!("Submit seal error: {}", e);
+                let minting_blob = hex::encode(minting_blob);
+                let extra = hex::encode(extra.to_vec());
+                if let Err(e) = seal_connector.submit_seal(minting_blob, nonce, extra) {
+                    error!("Submit seal error: {}", e);
                     Delay::new(Duration::from_secs(1)).await;
                 }
             }
         };
         Self::spawn(fut);
         Self {
-            rpc_client,
+            connector,
             seal_sender,
             time_service: Arc::new(RealTimeService::new()),
         }
@@ -49,10 +143,18 @@ impl JobRpcClient {
 
     fn forward_mint_block_stream(&self) -> BoxStream<'static, MintBlockEvent> {
         let (sender, receiver) = unbounded();
-        let client = self.rpc_client.clone();
+        let connector = self.connector.clone();
         let fut = async move {
-            // use a loop to retry subscribe event when connection error.
+            // use a loop to reconnect and resubscribe event when connection lost or node restarted.
             loop {
+                let client = match connector.client() {
+                    Ok(client) => client,
+                    Err(e) => {
+                        error!("{}, retry later.", e);
+                        Delay::new(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
                 match client.subscribe_new_mint_blocks() {
                     Ok(stream) => {
                         let mut stream = stream.into_stream();
@@ -71,12 +173,14 @@ impl JobRpcClient {
                                 }
                             }
                         }
+                        warn!("Mint block event subscription closed, resubscribe.");
                     }
                     Err(e) => {
                         error!("Subscribe new blocks event error: {}, retry later.", e);
                         Delay::new(Duration::from_secs(1)).await
                     }
                 }
+                connector.failover(&client);
             }
         };
         Self::spawn(fut);
@@ -1,10 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-mod cpu_solver;
 pub mod job_client;
 pub mod miner;
-mod solver;
+pub mod solver;
 
 use actix::prelude::*;
 use anyhow::Result;
@@ -24,7 +23,11 @@ pub trait JobClient: Send + Unpin + Sync + Clone {
     fn time_service(&self) -> Arc<dyn TimeService>;
 }
 
+/// The solver backend of the miner client, it can be the cpu solver, an external solver process,
+/// or a plugin loaded from a dynamic library, see `solver::create_solver`.
 pub trait Solver: Send + DynClone {
+    /// Solve the nonce of the minting blob and send it by `nonce_tx`,
+    /// should block until a stop event is received from `stop_rx`.
     fn solve(
         &mut self,
         strategy: ConsensusStrategy,
@@ -3,21 +3,33 @@
 use actix::System;
 use logger::prelude::*;
 use starcoin_config::MinerClientConfig;
-use starcoin_miner_client::job_client::JobRpcClient;
+use starcoin_miner_client::job_client::{JobRpcClient, NodeConnector};
 use starcoin_miner_client::miner::MinerClientService;
-use starcoin_rpc_client::RpcClient;
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 use structopt::StructOpt;
 
 #[derive(Debug, Clone, StructOpt, Default)]
 #[structopt(name = "starcoin-miner", about = "Starcoin Miner")]
 pub struct StarcoinOpt {
-    #[structopt(long, short = "a", default_value = "127.0.0.1:9870")]
-    pub server: String,
+    /// The node websocket address, can be specified multiple times,
+    /// the miner fails over to the next node if the connection is lost or a seal submission fails.
+    #[structopt(
+        long,
+        short = "a",
+        default_value = "127.0.0.1:9870",
+        number_of_values = 1
+    )]
+    pub server: Vec<String>,
     #[structopt(long, short = "n", default_value = "1")]
     pub thread_num: u16,
+    /// The dynamic library of the solver plugin, such as a gpu solver.
     #[structopt(long, short = "p")]
     pub plugin_path: Option<String>,
+    /// The command of the external solver process, ignored if the plugin is specified.
+    /// The job is written to its stdin as a json line,
+    /// and it prints the found nonce to stdout.
+    #[structopt(long)]
+    pub solver_command: Option<String>,
 }
 
 fn main() {
@@ -25,20 +37,18 @@ fn main() {
     let opts: StarcoinOpt = StarcoinOpt::from_args();
     let config = {
         MinerClientConfig {
-            server: Some(opts.server.clone()),
+            servers: opts.server.clone(),
             plugin_path: opts.plugin_path,
+            solver_command: opts.solver_command,
             miner_thread: opts.thread_num,
             enable_stderr: true,
         }
     };
 
-    let client = match RpcClient::connect_websocket(&format!("ws://{}", opts.server)) {
+    let connector = match NodeConnector::new(opts.server) {
         Ok(c) => c,
         Err(err) => {
-            error!(
-                "Failed to connect to starcoin node: {}, error: {}",
-                opts.server, err
-            );
+            error!("Invalid starcoin node servers: {}", err);
             std::process::exit(-1);
         }
     };
@@ -49,7 +59,7 @@ fn main() {
         .build();
     if let Err(err) = system.block_on(async move {
         let registry = RegistryService::launch();
-        let job_client = JobRpcClient::new(client);
+        let job_client = JobRpcClient::new(connector);
         registry.put_shared(config).await?;
         registry.put_shared(job_client).await?;
         registry
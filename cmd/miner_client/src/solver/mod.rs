@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod cpu;
+mod plugin;
+mod process;
+
+pub use cpu::CpuSolver;
+pub use plugin::SOLVER_CREATER;
+pub use process::{ProcessSolver, SolverJob};
+
+use crate::Solver;
+use anyhow::Result;
+use starcoin_config::{MinerClientConfig, TimeService};
+use std::sync::Arc;
+
+/// Create the solver backend selected by the config: the plugin solver if `plugin_path` is set,
+/// then the external process solver if `solver_command` is set, otherwise the cpu solver.
+pub fn create_solver(
+    config: MinerClientConfig,
+    time_service: Option<Arc<dyn TimeService>>,
+) -> Result<Box<dyn Solver>> {
+    if let Some(path) = config.plugin_path.as_ref() {
+        return plugin::load_solver(path);
+    }
+    if let Some(command) = config.solver_command.as_ref() {
+        return Ok(Box::new(ProcessSolver::new(
+            command,
+            config.miner_thread(),
+        )?));
+    }
+    let ts = time_service.expect("time service should exist");
+    Ok(Box::new(CpuSolver::new(config, ts)))
+}
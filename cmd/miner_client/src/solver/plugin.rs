@@ -0,0 +1,25 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Solver;
+use anyhow::Result;
+
+type CreateSolver = extern "C" fn() -> Box<dyn Solver>;
+
+/// The symbol a solver plugin exports, it is an `extern "C" fn() -> Box<dyn Solver>`.
+/// The plugin should be built with the same rustc and `starcoin-miner-client` version.
+pub const SOLVER_CREATER: &[u8] = b"create_solver";
+
+/// Load the solver from the dynamic library, such as a gpu solver.
+pub fn load_solver(path: &str) -> Result<Box<dyn Solver>> {
+    unsafe {
+        //Since this issue https://github.com/nagisa/rust_libloading/issues/41
+        #[cfg(target_os = "linux")]
+        let lib = libloading::os::unix::Library::open(Some(path), 0x2 | 0x1000)?;
+        #[cfg(not(target_os = "linux"))]
+        let lib = libloading::Library::new(path)?;
+        let call_ref = lib.get::<CreateSolver>(SOLVER_CREATER)?;
+
+        Ok(call_ref())
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Solver;
+use anyhow::{format_err, Result};
+use futures::executor::block_on;
+use futures::StreamExt;
+use futures_channel::mpsc;
+use logger::prelude::*;
+use serde::{Deserialize, Serialize};
+use starcoin_types::genesis_config::ConsensusStrategy;
+use starcoin_types::U256;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::thread;
+
+/// The job written to the stdin of the solver process as a json line.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SolverJob {
+    /// The value of the consensus strategy.
+    pub strategy: u8,
+    /// Hex encoded minting blob.
+    pub minting_blob: String,
+    /// Hex encoded difficulty with `0x` prefix.
+    pub difficulty: String,
+    pub threads: u16,
+}
+
+/// Solve by an external process, such as a gpu miner.
+/// For every job, the process is spawned with the `SolverJob` json line in its stdin,
+/// and it should print the found nonce in decimal as a line to its stdout.
+/// The process is killed when the job is stopped.
+#[derive(Clone)]
+pub struct ProcessSolver {
+    program: String,
+    args: Vec<String>,
+    threads: u16,
+}
+
+impl ProcessSolver {
+    pub fn new(command: &str, threads: u16) -> Result<Self> {
+        let mut parts = command.split_whitespace().map(|s| s.to_string());
+        let program = parts
+            .next()
+            .ok_or_else(|| format_err!("The solver command should not be empty"))?;
+        Ok(Self {
+            program,
+            args: parts.collect(),
+            threads,
+        })
+    }
+
+    fn spawn(&self, job: &SolverJob) -> Result<Child> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| format_err!("Failed to open stdin of solver process"))?;
+        let mut line = serde_json::to_vec(job)?;
+        line.push(b'\n');
+        stdin.write_all(&line)?;
+        Ok(child)
+    }
+
+    /// Read the stdout of the solver process until the nonce is found.
+    fn read_nonce(
+        stdout: ChildStdout,
+        minting_blob: Vec<u8>,
+        nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u32)>,
+    ) {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            match line.trim().parse::<u32>() {
+                Ok(nonce) => {
+                    if let Err(e) = nonce_tx.unbounded_send((minting_blob, nonce)) {
+                        error!("[miner-client-solver] Failed to send seal: {:?}", e);
+                    }
+                    break;
+                }
+                Err(_) => debug!("[miner-client-solver] solver output: {}", line),
+            }
+        }
+    }
+}
+
+impl Solver for ProcessSolver {
+    fn solve(
+        &mut self,
+        strategy: ConsensusStrategy,
+        minting_blob: &[u8],
+        diff: U256,
+        nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u32)>,
+        mut stop_rx: mpsc::UnboundedReceiver<bool>,
+    ) {
+        let job = SolverJob {
+            strategy: strategy.value(),
+            minting_blob: hex::encode(minting_blob),
+            difficulty: format!("0x{:x}", diff),
+            threads: self.threads,
+        };
+        let mut child = match self.spawn(&job) {
+            Ok(child) => child,
+            Err(e) => {
+                error!("Failed to start solver process {}: {:?}", self.program, e);
+                return;
+            }
+        };
+        if let Some(stdout) = child.stdout.take() {
+            let minting_blob = minting_blob.to_owned();
+            let _ = thread::Builder::new()
+                .name("starcoin-miner-process-solver".to_string())
+                .spawn(move || Self::read_nonce(stdout, minting_blob, nonce_tx));
+        }
+        block_on(stop_rx.next());
+        if let Err(e) = child.kill() {
+            debug!("Failed to kill solver process, may be exited: {:?}", e);
+        }
+        let _ = child.wait();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use futures_channel::mpsc::unbounded;
+
+    #[test]
+    fn test_process_solver() {
+        let solver = ProcessSolver::new("gpu-miner --device 0", 1).unwrap();
+        assert_eq!(solver.program, "gpu-miner");
+        assert_eq!(solver.args, vec!["--device", "0"]);
+        assert!(ProcessSolver::new(" ", 1).is_err());
+
+        // the solver prints some logs before the nonce.
+        let mut solver = ProcessSolver {
+            program: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                "read job; echo found; echo 42".to_string(),
+            ],
+            threads: 1,
+        };
+        let (nonce_tx, mut nonce_rx) = unbounded();
+        let (stop_tx, stop_rx) = unbounded();
+        let handle = thread::spawn(move || {
+            solver.solve(
+                ConsensusStrategy::Dummy,
+                &[1u8, 2, 3],
+                U256::from(1),
+                nonce_tx,
+                stop_rx,
+            )
+        });
+        let (minting_blob, nonce) = block_on(nonce_rx.next()).unwrap();
+        assert_eq!(minting_blob, vec![1u8, 2, 3]);
+        assert_eq!(nonce, 42);
+        stop_tx.unbounded_send(true).unwrap();
+        handle.join().unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use range_replay::RangeReplayer;
 use sp_utils::stop_watch::start_watch;
 use starcoin_chain::verifier::Verifier;
 use starcoin_chain::verifier::{BasicVerifier, ConsensusVerifier, FullVerifier, NoneVerifier};
@@ -11,12 +12,16 @@ use starcoin_genesis::Genesis;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::StorageInstance;
-use starcoin_storage::Storage;
+use starcoin_storage::{BlockStore, Storage, VEC_PREFIX_NAME};
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
 use structopt::StructOpt;
 
+mod range_replay;
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "replay")]
 pub struct ReplayOpt {
@@ -26,18 +31,90 @@ pub struct ReplayOpt {
     #[structopt(short = "f", long, parse(from_os_str))]
     /// Replay data dir.
     pub from: PathBuf,
-    #[structopt(short = "t", long, parse(from_os_str))]
+    #[structopt(
+        short = "t",
+        long,
+        parse(from_os_str),
+        required_unless = "verify-state"
+    )]
     /// Target dir.
-    pub to: PathBuf,
+    pub to: Option<PathBuf>,
     #[structopt(long, short = "c", default_value = "20000")]
     /// Number of block.
     pub block_num: u64,
-    #[structopt(possible_values = &Verifier::variants(), case_insensitive = true)]
+    #[structopt(
+        possible_values = &Verifier::variants(),
+        case_insensitive = true,
+        required_unless = "verify-state"
+    )]
     /// Verify type:  Basic, Consensus, Full, None, eg.
-    pub verifier: Verifier,
+    pub verifier: Option<Verifier>,
     #[structopt(long, short = "w")]
     /// Watch metrics logs.
     pub watch: bool,
+    #[structopt(long)]
+    /// Replay the block range [start-number, end-number] against a read-only copy of the `from` storage,
+    /// and verify the re-executed state roots with the stored ones, instead of replaying to `to`.
+    pub verify_state: bool,
+    #[structopt(long, default_value = "1")]
+    /// First block number of the range to verify.
+    pub start_number: u64,
+    #[structopt(long)]
+    /// Last block number of the range to verify, default is the head of the `from` chain.
+    pub end_number: Option<u64>,
+    #[structopt(long, parse(from_os_str))]
+    /// Write per-transaction execution timing of the range verification in the folded stack format,
+    /// which can be rendered by flamegraph tools, such as `inferno-flamegraph`.
+    pub timing_output: Option<PathBuf>,
+}
+
+fn verify_state(net: &ChainNetwork, opts: &ReplayOpt) {
+    let db_storage = DBStorage::open_with_cfs(
+        opts.from.join("starcoindb/db").join("starcoindb"),
+        VEC_PREFIX_NAME.to_vec(),
+        true,
+        RocksdbConfig::default(),
+    )
+    .expect("open storage read-only should success.");
+    let storage = Arc::new(
+        Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            db_storage,
+        ))
+        .unwrap(),
+    );
+    let startup_info = storage
+        .get_startup_info()
+        .unwrap()
+        .expect("startup info should exist in storage.");
+    let chain = BlockChain::new(net.time_service(), startup_info.main, storage.clone())
+        .expect("create block chain should success.");
+    let end_number = opts
+        .end_number
+        .unwrap_or_else(|| chain.current_header().number());
+    let timing_output = opts.timing_output.as_ref().map(|path| {
+        Box::new(BufWriter::new(
+            File::create(path).expect("create timing output file should success."),
+        )) as Box<dyn Write>
+    });
+    let mut replayer = RangeReplayer::new(storage, &chain, timing_output);
+    let summary = replayer
+        .replay(opts.start_number, end_number)
+        .expect("replay block range should success.");
+    println!(
+        "replayed blocks: {}, txns: {}, execute use time: {:?}",
+        summary.blocks, summary.txns, summary.execute_time
+    );
+    match summary.divergence {
+        Some(divergence) => {
+            println!("{}", divergence);
+            std::process::exit(1);
+        }
+        None => println!(
+            "state verified for block range [{}, {}]",
+            opts.start_number, end_number
+        ),
+    }
 }
 
 fn main() {
@@ -49,10 +126,14 @@ fn main() {
         None => BuiltinNetworkID::Proxima,
     };
     let net = ChainNetwork::new_builtin(network);
+    if opts.verify_state {
+        verify_state(&net, &opts);
+        return;
+    }
 
     let from_dir = opts.from;
     let block_num = opts.block_num;
-    let to_dir = opts.to;
+    let to_dir = opts.to.expect("to dir is required.");
     // start watching
     if opts.watch {
         start_watch();
@@ -102,9 +183,10 @@ fn main() {
         storage2,
     )
     .expect("create block chain should success.");
+    let verifier = opts.verifier.expect("verifier is required.");
     let begin = SystemTime::now();
     for block in block_vec {
-        match opts.verifier {
+        match verifier {
             Verifier::Basic => {
                 chain2.apply_with_verifier::<BasicVerifier>(block).unwrap();
             }
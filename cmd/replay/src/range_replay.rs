@@ -0,0 +1,339 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_state_api::{AccountStateReader, ChainStateReader, ChainStateWriter};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::Store;
+use starcoin_types::block::{Block, BlockNumber};
+use starcoin_types::transaction::{Transaction, TransactionStatus};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::write_set::{WriteOp, WriteSet};
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A state key whose replayed value differs from the stored state after the transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriteSetDiff {
+    pub access_path: AccessPath,
+    /// The value in the stored state, None if it is not exists.
+    pub expected: Option<Vec<u8>>,
+    /// The value written by the replay, None if it is deleted.
+    pub actual: Option<Vec<u8>>,
+}
+
+fn fmt_value(value: &Option<Vec<u8>>) -> String {
+    match value {
+        Some(value) => format!("0x{}", hex::encode(value)),
+        None => "None".to_string(),
+    }
+}
+
+/// The first transaction whose replayed result does not match the stored one.
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub block_number: BlockNumber,
+    pub block_id: HashValue,
+    pub txn_index: usize,
+    pub txn_hash: HashValue,
+    pub expected_state_root: Option<HashValue>,
+    pub actual_state_root: Option<HashValue>,
+    pub expected_gas_used: Option<u64>,
+    pub actual_gas_used: Option<u64>,
+    pub expected_status: String,
+    pub actual_status: String,
+    pub write_set_diffs: Vec<WriteSetDiff>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Divergence at block {}({}) txn {}({}):",
+            self.block_number, self.block_id, self.txn_index, self.txn_hash
+        )?;
+        writeln!(
+            f,
+            "  state root: expected {:?}, actual {:?}",
+            self.expected_state_root, self.actual_state_root
+        )?;
+        writeln!(
+            f,
+            "  gas used: expected {:?}, actual {:?}",
+            self.expected_gas_used, self.actual_gas_used
+        )?;
+        writeln!(
+            f,
+            "  status: expected {}, actual {}",
+            self.expected_status, self.actual_status
+        )?;
+        writeln!(f, "  write set diff ({}):", self.write_set_diffs.len())?;
+        for diff in &self.write_set_diffs {
+            writeln!(
+                f,
+                "    {}: expected {}, actual {}",
+                diff.access_path,
+                fmt_value(&diff.expected),
+                fmt_value(&diff.actual)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RangeReplaySummary {
+    pub blocks: u64,
+    pub txns: u64,
+    pub execute_time: Duration,
+    pub divergence: Option<Divergence>,
+}
+
+/// Replay the blocks in range [start, end] of the chain in storage, and verify every
+/// re-executed transaction against the stored transaction info. Re-executed states are
+/// only kept in memory, so the storage can be opened read-only.
+pub struct RangeReplayer<'a> {
+    storage: Arc<dyn Store>,
+    chain: &'a BlockChain,
+    /// Write per-transaction execution timing in the folded stack format, which can be
+    /// rendered by flamegraph tools such as `inferno-flamegraph`.
+    timing_output: Option<Box<dyn Write + 'a>>,
+}
+
+impl<'a> RangeReplayer<'a> {
+    pub fn new(
+        storage: Arc<dyn Store>,
+        chain: &'a BlockChain,
+        timing_output: Option<Box<dyn Write + 'a>>,
+    ) -> Self {
+        Self {
+            storage,
+            chain,
+            timing_output,
+        }
+    }
+
+    pub fn replay(&mut self, start: BlockNumber, end: BlockNumber) -> Result<RangeReplaySummary> {
+        if start == 0 {
+            bail!("Genesis block can not be replayed, start number should be greater than 0");
+        }
+        if start > end {
+            bail!("Invalid block range [{}, {}]", start, end);
+        }
+        let mut summary = RangeReplaySummary::default();
+        for number in start..=end {
+            let block = self
+                .chain
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            let divergence = self.replay_block(block, &mut summary)?;
+            summary.blocks += 1;
+            if divergence.is_some() {
+                summary.divergence = divergence;
+                break;
+            }
+            if number % 1000 == 0 {
+                info!(
+                    "Replayed to block {}, txns: {}, execute time: {:?}",
+                    number, summary.txns, summary.execute_time
+                );
+            }
+        }
+        if let Some(output) = self.timing_output.as_mut() {
+            output.flush()?;
+        }
+        Ok(summary)
+    }
+
+    fn replay_block(
+        &mut self,
+        block: Block,
+        summary: &mut RangeReplaySummary,
+    ) -> Result<Option<Divergence>> {
+        let header = block.header().clone();
+        let parent = self
+            .storage
+            .get_block_header_by_hash(header.parent_hash())?
+            .ok_or_else(|| format_err!("Can not find parent block header of {}", header.id()))?;
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(parent.state_root()),
+        );
+        let epoch = AccountStateReader::new(&statedb)
+            .get_resource::<Epoch>(genesis_address())?
+            .ok_or_else(|| format_err!("Epoch is none."))?;
+
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(parent.gas_used()),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        let expected_infos = self
+            .storage
+            .get_block_txn_info_ids(header.id())?
+            .into_iter()
+            .map(|id| {
+                self.storage
+                    .get_transaction_info(id)?
+                    .ok_or_else(|| format_err!("Can not find txn info by id {}", id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut divergence = Divergence {
+            block_number: header.number(),
+            block_id: header.id(),
+            txn_index: 0,
+            txn_hash: HashValue::zero(),
+            expected_state_root: None,
+            actual_state_root: None,
+            expected_gas_used: None,
+            actual_gas_used: None,
+            expected_status: "None".to_string(),
+            actual_status: "None".to_string(),
+            write_set_diffs: vec![],
+        };
+        let mut remaining_gas = epoch.block_gas_limit();
+        for (index, txn) in txns.into_iter().enumerate() {
+            let txn_hash = txn.id();
+            let txn_kind = match &txn {
+                Transaction::BlockMetadata(_) => "metadata",
+                Transaction::UserTransaction(_) => "user",
+            };
+            let expected = expected_infos.get(index);
+            divergence.txn_index = index;
+            divergence.txn_hash = txn_hash;
+            divergence.expected_state_root = expected.map(|info| info.state_root_hash());
+            divergence.expected_gas_used = expected.map(|info| info.gas_used());
+            divergence.expected_status = match expected {
+                Some(info) => format!("{:?}", info.status()),
+                None => "None".to_string(),
+            };
+
+            let begin = Instant::now();
+            let mut outputs =
+                starcoin_executor::execute_block_transactions(&statedb, vec![txn], remaining_gas)?;
+            let output = match outputs.pop() {
+                Some(output) => output,
+                // the block gas limit is used up, the txn is not executed.
+                None => return Ok(Some(divergence)),
+            };
+            let (write_set, _events, gas_used, status) = output.into_inner();
+            let kept_status = match status {
+                TransactionStatus::Keep(status) => status,
+                TransactionStatus::Discard(status) => {
+                    divergence.actual_status = format!("Discard({:?})", status);
+                    return Ok(Some(divergence));
+                }
+            };
+            statedb.apply_write_set(write_set.clone())?;
+            let state_root = statedb.commit()?;
+            let elapsed = begin.elapsed();
+            summary.txns += 1;
+            summary.execute_time += elapsed;
+            remaining_gas = remaining_gas.saturating_sub(gas_used);
+            if let Some(output) = self.timing_output.as_mut() {
+                writeln!(
+                    output,
+                    "replay;block_{};{}_{}_{} {}",
+                    header.number(),
+                    txn_kind,
+                    index,
+                    txn_hash,
+                    elapsed.as_micros()
+                )?;
+            }
+
+            divergence.actual_state_root = Some(state_root);
+            divergence.actual_gas_used = Some(gas_used);
+            divergence.actual_status = format!("{:?}", kept_status);
+            let matched = match expected {
+                Some(info) => {
+                    info.transaction_hash() == txn_hash
+                        && info.state_root_hash() == state_root
+                        && info.gas_used() == gas_used
+                        && info.status() == &kept_status
+                }
+                None => false,
+            };
+            if !matched {
+                if let Some(expected_state_root) = divergence.expected_state_root {
+                    divergence.write_set_diffs = self
+                        .diff_write_set(expected_state_root, &write_set)
+                        .unwrap_or_else(|e| {
+                            warn!("Diff write set of txn {} failed: {:?}", txn_hash, e);
+                            vec![]
+                        });
+                }
+                return Ok(Some(divergence));
+            }
+        }
+        if expected_infos.len() > divergence.txn_index + 1 {
+            // the stored block has more txns than replayed.
+            let index = divergence.txn_index + 1;
+            let expected = &expected_infos[index];
+            return Ok(Some(Divergence {
+                txn_index: index,
+                txn_hash: expected.transaction_hash(),
+                expected_state_root: Some(expected.state_root_hash()),
+                actual_state_root: None,
+                expected_gas_used: Some(expected.gas_used()),
+                actual_gas_used: None,
+                expected_status: format!("{:?}", expected.status()),
+                actual_status: "None".to_string(),
+                write_set_diffs: vec![],
+                ..divergence
+            }));
+        }
+        if statedb.state_root() != header.state_root() {
+            bail!(
+                "Block {} state root mismatch after all txns matched, expected {}, actual {}",
+                header.number(),
+                header.state_root(),
+                statedb.state_root()
+            );
+        }
+        Ok(None)
+    }
+
+    /// Diff the replayed write set with the stored state after the transaction.
+    /// Only the keys written by the replay are compared.
+    fn diff_write_set(
+        &self,
+        expected_state_root: HashValue,
+        write_set: &WriteSet,
+    ) -> Result<Vec<WriteSetDiff>> {
+        let expected_state = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(expected_state_root),
+        );
+        let mut diffs = vec![];
+        for (access_path, op) in write_set {
+            let actual = match op {
+                WriteOp::Value(value) => Some(value.clone()),
+                WriteOp::Deletion => None,
+            };
+            let expected = expected_state.get(access_path)?;
+            if expected != actual {
+                diffs.push(WriteSetDiff {
+                    access_path: access_path.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(diffs)
+    }
+}
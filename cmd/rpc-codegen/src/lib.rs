@@ -0,0 +1,10 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate the machine readable schema of the node rpc from the `#[rpc]` api traits,
+//! and the typed client stubs of other languages from the schema.
+
+pub mod parser;
+pub mod python;
+pub mod schema;
+pub mod typescript;
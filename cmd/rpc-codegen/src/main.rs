@@ -0,0 +1,86 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_rpc_codegen::schema::RpcSchema;
+use starcoin_rpc_codegen::{parser, python, typescript};
+use std::fs;
+use std::path::PathBuf;
+use structopt::{clap::arg_enum, StructOpt};
+
+arg_enum! {
+    #[derive(Debug)]
+    pub enum Language {
+        TypeScript,
+        Python,
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "rpc-codegen",
+    about = "Generate the rpc schema from the rpc api, and the typed clients from the schema"
+)]
+pub enum Cmd {
+    /// Generate the rpc schema from the `#[rpc]` api traits.
+    Schema(SchemaOpt),
+    /// Generate the typed client from the rpc schema.
+    Client(ClientOpt),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SchemaOpt {
+    #[structopt(long, parse(from_os_str), default_value = "rpc/api/src")]
+    /// Source dir of the rpc api crate.
+    pub api_dir: PathBuf,
+    #[structopt(long, short = "o", parse(from_os_str))]
+    /// Output schema json file.
+    pub output: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ClientOpt {
+    #[structopt(long, short = "s", parse(from_os_str))]
+    /// The rpc schema json file generated by the `schema` command.
+    pub schema: PathBuf,
+    #[structopt(
+        long,
+        short = "l",
+        possible_values = &Language::variants(),
+        case_insensitive = true
+    )]
+    /// Language of the client: TypeScript, Python.
+    pub language: Language,
+    #[structopt(long, short = "o", parse(from_os_str))]
+    /// Output source file of the client.
+    pub output: PathBuf,
+}
+
+fn main() -> Result<()> {
+    match Cmd::from_args() {
+        Cmd::Schema(opt) => {
+            let schema = parser::parse_api_dir(opt.api_dir.as_path())?;
+            schema.save(opt.output.as_path())?;
+            println!(
+                "generated schema of {} rpc methods to {}",
+                schema.methods.len(),
+                opt.output.display()
+            );
+        }
+        Cmd::Client(opt) => {
+            let schema = RpcSchema::load(opt.schema.as_path())?;
+            let code = match opt.language {
+                Language::TypeScript => typescript::generate(&schema),
+                Language::Python => python::generate(&schema),
+            };
+            fs::write(&opt.output, code)?;
+            println!(
+                "generated {} client of {} rpc methods to {}",
+                opt.language,
+                schema.methods.len(),
+                opt.output.display()
+            );
+        }
+    }
+    Ok(())
+}
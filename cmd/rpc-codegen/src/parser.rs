@@ -0,0 +1,320 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::{RpcMethod, RpcParam, RpcSchema, TypeRef};
+use anyhow::{bail, format_err, Result};
+use std::fs;
+use std::path::Path;
+use syn::{
+    Attribute, FnArg, GenericArgument, Item, ItemTrait, Lit, Meta, NestedMeta, Pat, PathArguments,
+    ReturnType, TraitItem, Type,
+};
+
+/// The types serialized as json string.
+const STRING_TYPES: &[&str] = &[
+    "String",
+    "str",
+    "HashValue",
+    "AccountAddress",
+    "PeerId",
+    "Multiaddr",
+    "StrView",
+];
+/// The integer types and the aliases of them.
+const INTEGER_TYPES: &[&str] = &[
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "BlockNumber",
+];
+/// The wrappers of the method result.
+const RESULT_TYPES: &[&str] = &["Result", "FutureResult", "BoxFuture"];
+
+/// Generate the schema from the `#[rpc]` api traits of the rust files under `api_dir`.
+pub fn parse_api_dir(api_dir: &Path) -> Result<RpcSchema> {
+    let mut methods = vec![];
+    for entry in walkdir::WalkDir::new(api_dir) {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map_or(false, |ext| ext == "rs") {
+            let source = fs::read_to_string(path)?;
+            methods.extend(
+                parse_source(&source)
+                    .map_err(|e| format_err!("Failed to parse {}: {}", path.display(), e))?,
+            );
+        }
+    }
+    Ok(RpcSchema::new(methods))
+}
+
+/// Parse the rpc methods of the `#[rpc]` api traits in the rust source,
+/// the pubsub methods are not included.
+pub fn parse_source(source: &str) -> Result<Vec<RpcMethod>> {
+    let file = syn::parse_file(source)?;
+    let mut methods = vec![];
+    for item in file.items {
+        if let Item::Trait(item_trait) = item {
+            if item_trait
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("rpc"))
+            {
+                methods.extend(parse_trait(&item_trait)?);
+            }
+        }
+    }
+    Ok(methods)
+}
+
+fn parse_trait(item_trait: &ItemTrait) -> Result<Vec<RpcMethod>> {
+    let mut methods = vec![];
+    for item in &item_trait.items {
+        let method = match item {
+            TraitItem::Method(method) => method,
+            _ => continue,
+        };
+        let name = match rpc_name(&method.attrs)? {
+            Some(name) => name,
+            None => continue,
+        };
+        let mut params = vec![];
+        for input in &method.sig.inputs {
+            let arg = match input {
+                FnArg::Typed(arg) => arg,
+                FnArg::Receiver(_) => continue,
+            };
+            let param_name = match arg.pat.as_ref() {
+                Pat::Ident(ident) => ident.ident.to_string(),
+                _ => bail!("Unsupported param pattern of method {}", name),
+            };
+            params.push(RpcParam {
+                name: param_name,
+                ty: parse_type(&arg.ty)?,
+            });
+        }
+        let result = match &method.sig.output {
+            ReturnType::Default => TypeRef::Unit,
+            ReturnType::Type(_, ty) => parse_type(unwrap_result(ty))?,
+        };
+        methods.push(RpcMethod {
+            name,
+            api: item_trait.ident.to_string(),
+            doc: doc(&method.attrs),
+            params,
+            result,
+        });
+    }
+    Ok(methods)
+}
+
+/// Get the method name of `#[rpc(name = "...")]`.
+fn rpc_name(attrs: &[Attribute]) -> Result<Option<String>> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("rpc")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+                    if let (true, Lit::Str(name)) =
+                        (name_value.path.is_ident("name"), &name_value.lit)
+                    {
+                        return Ok(Some(name.value()));
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(name_value)) => match name_value.lit {
+                Lit::Str(line) => Some(line.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn type_args(args: &PathArguments) -> Vec<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Unwrap the `Result<T>`, `FutureResult<T>` or `BoxFuture<Result<T>>` to `T`.
+fn unwrap_result(ty: &Type) -> &Type {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if RESULT_TYPES.contains(&segment.ident.to_string().as_str()) {
+                if let Some(inner) = type_args(&segment.arguments).first() {
+                    return unwrap_result(inner);
+                }
+            }
+        }
+    }
+    ty
+}
+
+pub fn parse_type(ty: &Type) -> Result<TypeRef> {
+    match ty {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(TypeRef::Unit),
+        Type::Tuple(tuple) => Ok(TypeRef::Tuple {
+            items: tuple
+                .elems
+                .iter()
+                .map(parse_type)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        Type::Reference(reference) => parse_type(&reference.elem),
+        Type::Paren(paren) => parse_type(&paren.elem),
+        Type::Group(group) => parse_type(&group.elem),
+        Type::Path(path) => {
+            let segment = path
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| format_err!("Empty type path"))?;
+            let name = segment.ident.to_string();
+            let args = type_args(&segment.arguments);
+            let arg = |idx: usize| -> Result<TypeRef> {
+                args.get(idx)
+                    .ok_or_else(|| format_err!("Missing type argument of {}", name))
+                    .and_then(|arg| parse_type(arg))
+            };
+            Ok(match name.as_str() {
+                "bool" => TypeRef::Boolean,
+                "Option" => TypeRef::Option {
+                    inner: Box::new(arg(0)?),
+                },
+                "Vec" | "HashSet" | "BTreeSet" => TypeRef::Array {
+                    item: Box::new(arg(0)?),
+                },
+                "HashMap" | "BTreeMap" => TypeRef::Map {
+                    key: Box::new(arg(0)?),
+                    value: Box::new(arg(1)?),
+                },
+                "Box" | "Arc" | "Cow" => arg(0)?,
+                name if STRING_TYPES.contains(&name) => TypeRef::String,
+                name if INTEGER_TYPES.contains(&name) => TypeRef::Integer,
+                other => TypeRef::Named {
+                    name: other.to_string(),
+                },
+            })
+        }
+        _ => bail!("Unsupported rpc type"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+        #[rpc]
+        pub trait ChainApi {
+            /// Get main chain info
+            #[rpc(name = "chain.info")]
+            fn info(&self) -> FutureResult<ChainInfoView>;
+            #[rpc(name = "chain.get_blocks_by_number")]
+            fn get_blocks_by_number(
+                &self,
+                number: Option<BlockNumber>,
+                count: u64,
+            ) -> FutureResult<Vec<BlockView>>;
+            #[rpc(name = "chain.get_events")]
+            fn get_events(&self, filter: EventFilter, reverse: Option<bool>) -> Result<HashMap<String, (HashValue, Cow<'static, str>)>>;
+            fn not_rpc(&self) -> Result<()>;
+        }
+
+        pub trait NotRpcApi {
+            #[rpc(name = "not.rpc")]
+            fn not_rpc(&self) -> Result<()>;
+        }
+    "#;
+
+    #[test]
+    fn test_parse_source() {
+        let schema = RpcSchema::new(parse_source(SOURCE).unwrap());
+        let names: Vec<_> = schema.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "chain.get_blocks_by_number",
+                "chain.get_events",
+                "chain.info"
+            ]
+        );
+
+        let info = &schema.methods[2];
+        assert_eq!(info.api, "ChainApi");
+        assert_eq!(info.doc.as_deref(), Some("Get main chain info"));
+        assert!(info.params.is_empty());
+        assert_eq!(
+            info.result,
+            TypeRef::Named {
+                name: "ChainInfoView".to_string()
+            }
+        );
+
+        let get_blocks = &schema.methods[0];
+        assert_eq!(get_blocks.params[0].name, "number");
+        assert_eq!(
+            get_blocks.params[0].ty,
+            TypeRef::Option {
+                inner: Box::new(TypeRef::Integer)
+            }
+        );
+        // the optional number is followed by a required param.
+        assert_eq!(get_blocks.optional_params_start(), 2);
+
+        let get_events = &schema.methods[1];
+        assert_eq!(get_events.optional_params_start(), 1);
+        assert_eq!(
+            get_events.result,
+            TypeRef::Map {
+                key: Box::new(TypeRef::String),
+                value: Box::new(TypeRef::Tuple {
+                    items: vec![TypeRef::String, TypeRef::String]
+                }),
+            }
+        );
+        assert_eq!(
+            schema.named_types().into_iter().collect::<Vec<_>>(),
+            vec!["BlockView", "ChainInfoView", "EventFilter"]
+        );
+    }
+
+    #[test]
+    fn test_parse_rpc_api() {
+        let api_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../rpc/api/src");
+        let schema = parse_api_dir(&api_dir).unwrap();
+        assert!(schema.methods.iter().any(|m| m.name == "chain.info"));
+        assert!(schema.methods.iter().all(|m| !m.name.is_empty()));
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::{RpcMethod, RpcSchema, TypeRef};
+use heck::SnakeCase;
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+const RUNTIME: &str = r#"class RpcError(Exception):
+    def __init__(self, error: Dict[str, Any]) -> None:
+        super().__init__(error.get("message"))
+        self.code = error.get("code")
+        self.data = error.get("data")
+
+
+class StarcoinClient:
+    """Call the json rpc of the node by http."""
+
+    def __init__(self, url: str) -> None:
+        self._url = url
+        self._ids = itertools.count(1)
+
+    def _call(self, method: str, params: List[Any]) -> Any:
+        request = {"jsonrpc": "2.0", "id": next(self._ids), "method": method, "params": params}
+        http_request = urllib.request.Request(
+            self._url,
+            data=json.dumps(request).encode("utf-8"),
+            headers={"Content-Type": "application/json"},
+        )
+        with urllib.request.urlopen(http_request) as response:
+            body = json.loads(response.read())
+        if body.get("error") is not None:
+            raise RpcError(body["error"])
+        return body.get("result")
+"#;
+
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn py_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::Unit => "None".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Integer => "int".to_string(),
+        TypeRef::String => "str".to_string(),
+        TypeRef::Option { inner } => format!("Optional[{}]", py_type(inner)),
+        TypeRef::Array { item } => format!("List[{}]", py_type(item)),
+        TypeRef::Map { value, .. } => format!("Dict[str, {}]", py_type(value)),
+        TypeRef::Tuple { items } => format!(
+            "Tuple[{}]",
+            items.iter().map(py_type).collect::<Vec<_>>().join(", ")
+        ),
+        TypeRef::Named { name } => name.clone(),
+    }
+}
+
+fn write_method(out: &mut String, method: &RpcMethod) {
+    let optional_start = method.optional_params_start();
+    let mut params = vec!["self".to_string()];
+    for (idx, param) in method.params.iter().enumerate() {
+        let default = if idx >= optional_start { " = None" } else { "" };
+        params.push(format!(
+            "{}: {}{}",
+            ident(&param.name),
+            py_type(&param.ty),
+            default
+        ));
+    }
+    let args: Vec<_> = method.params.iter().map(|p| ident(&p.name)).collect();
+    writeln!(
+        out,
+        "    def {}({}) -> {}:",
+        ident(&method.name.to_snake_case()),
+        params.join(", "),
+        py_type(&method.result)
+    )
+    .unwrap();
+    if let Some(doc) = &method.doc {
+        let doc = doc.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+        let mut lines = doc.lines();
+        write!(out, "        \"\"\"{}", lines.next().unwrap_or_default()).unwrap();
+        for line in lines {
+            write!(out, "\n        {}", line).unwrap();
+        }
+        writeln!(out, "\"\"\"").unwrap();
+    }
+    writeln!(
+        out,
+        "        return self._call(\"{}\", [{}])",
+        method.name,
+        args.join(", ")
+    )
+    .unwrap();
+}
+
+/// Generate the python client of the rpc methods in the `schema`.
+pub fn generate(schema: &RpcSchema) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# Generated by starcoin_rpc_codegen from the rpc schema, do not edit.\n"
+    )
+    .unwrap();
+    writeln!(out, "import itertools").unwrap();
+    writeln!(out, "import json").unwrap();
+    writeln!(out, "import urllib.request").unwrap();
+    writeln!(out, "from typing import Any, Dict, List, Optional, Tuple\n").unwrap();
+    writeln!(out, "# The json values of the types defined by the node.").unwrap();
+    for name in schema.named_types() {
+        writeln!(out, "{} = Any", name).unwrap();
+    }
+    writeln!(out, "\n").unwrap();
+    write!(out, "{}", RUNTIME).unwrap();
+    for method in &schema.methods {
+        writeln!(out).unwrap();
+        write_method(&mut out, method);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_source;
+
+    #[test]
+    fn test_generate() {
+        let source = r#"
+            #[rpc]
+            pub trait ChainApi {
+                /// Get latest `count` blocks before `number`.
+                #[rpc(name = "chain.get_blocks_by_number")]
+                fn get_blocks_by_number(&self, number: Option<BlockNumber>, count: u64) -> FutureResult<Vec<BlockView>>;
+                #[rpc(name = "chain.get_events")]
+                fn get_events(&self, from: u64, reverse: Option<bool>) -> FutureResult<()>;
+            }
+        "#;
+        let schema = RpcSchema::new(parse_source(source).unwrap());
+        let code = generate(&schema);
+        assert!(code.contains("BlockView = Any\n"));
+        assert!(code.contains(
+            "    def chain_get_blocks_by_number(self, number: Optional[int], count: int) -> List[BlockView]:\n        \"\"\"Get latest `count` blocks before `number`.\"\"\"\n"
+        ));
+        assert!(code.contains(
+            "    def chain_get_events(self, from_: int, reverse: Optional[bool] = None) -> None:\n        return self._call(\"chain.get_events\", [from_, reverse])\n"
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The machine readable schema of the rpc methods of the node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RpcSchema {
+    pub version: u32,
+    /// The methods sorted by name.
+    pub methods: Vec<RpcMethod>,
+}
+
+impl RpcSchema {
+    pub fn new(mut methods: Vec<RpcMethod>) -> Self {
+        methods.sort_by(|m1, m2| m1.name.cmp(&m2.name));
+        Self {
+            version: SCHEMA_VERSION,
+            methods,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let schema: Self = serde_json::from_slice(&fs::read(path)?)?;
+        ensure!(
+            schema.version == SCHEMA_VERSION,
+            "Unsupported rpc schema version {}",
+            schema.version
+        );
+        Ok(schema)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// The names of the types defined by the node, which are referenced by the methods.
+    pub fn named_types(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for method in &self.methods {
+            for param in &method.params {
+                param.ty.collect_named_types(&mut names);
+            }
+            method.result.collect_named_types(&mut names);
+        }
+        names
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RpcMethod {
+    /// The rpc method name, such as `chain.info`.
+    pub name: String,
+    /// The api trait which defines the method.
+    pub api: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// The positional params of the method.
+    pub params: Vec<RpcParam>,
+    pub result: TypeRef,
+}
+
+impl RpcMethod {
+    /// The index of the first param of the trailing optional params, they can be omitted.
+    pub fn optional_params_start(&self) -> usize {
+        self.params
+            .iter()
+            .rposition(|param| !param.ty.is_option())
+            .map_or(0, |idx| idx + 1)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RpcParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+}
+
+/// The json type of a param or result.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TypeRef {
+    Unit,
+    Boolean,
+    Integer,
+    String,
+    Option {
+        inner: Box<TypeRef>,
+    },
+    Array {
+        item: Box<TypeRef>,
+    },
+    Map {
+        key: Box<TypeRef>,
+        value: Box<TypeRef>,
+    },
+    Tuple {
+        items: Vec<TypeRef>,
+    },
+    /// A type defined by the node, such as `BlockView`.
+    Named {
+        name: String,
+    },
+}
+
+impl TypeRef {
+    pub fn is_option(&self) -> bool {
+        matches!(self, TypeRef::Option { .. })
+    }
+
+    fn collect_named_types(&self, names: &mut BTreeSet<String>) {
+        match self {
+            TypeRef::Option { inner } => inner.collect_named_types(names),
+            TypeRef::Array { item } => item.collect_named_types(names),
+            TypeRef::Map { key, value } => {
+                key.collect_named_types(names);
+                value.collect_named_types(names);
+            }
+            TypeRef::Tuple { items } => {
+                for item in items {
+                    item.collect_named_types(names);
+                }
+            }
+            TypeRef::Named { name } => {
+                names.insert(name.clone());
+            }
+            TypeRef::Unit | TypeRef::Boolean | TypeRef::Integer | TypeRef::String => {}
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::{RpcMethod, RpcSchema, TypeRef};
+use heck::MixedCase;
+use std::fmt::Write;
+
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+const RUNTIME: &str = r#"export type Transport = (method: string, params: unknown[]) => Promise<unknown>;
+
+export class RpcError extends Error {
+  constructor(readonly code: number, message: string, readonly data?: unknown) {
+    super(message);
+  }
+}
+
+/** Call the json rpc of the node by http. */
+export function httpTransport(url: string): Transport {
+  let id = 0;
+  return async (method: string, params: unknown[]) => {
+    id += 1;
+    const response = await fetch(url, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ jsonrpc: "2.0", id, method, params }),
+    });
+    const body = await response.json();
+    if (body.error) {
+      throw new RpcError(body.error.code, body.error.message, body.error.data);
+    }
+    return body.result;
+  };
+}
+"#;
+
+fn ident(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn ts_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::Unit => "null".to_string(),
+        TypeRef::Boolean => "boolean".to_string(),
+        TypeRef::Integer => "number".to_string(),
+        TypeRef::String => "string".to_string(),
+        TypeRef::Option { inner } => format!("{} | null", ts_type(inner)),
+        TypeRef::Array { item } => format!("Array<{}>", ts_type(item)),
+        TypeRef::Map { value, .. } => format!("Record<string, {}>", ts_type(value)),
+        TypeRef::Tuple { items } => format!(
+            "[{}]",
+            items.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+        TypeRef::Named { name } => name.clone(),
+    }
+}
+
+fn write_method(out: &mut String, method: &RpcMethod) {
+    if let Some(doc) = &method.doc {
+        writeln!(out, "  /**").unwrap();
+        for line in doc.lines() {
+            writeln!(out, "   * {}", line.replace("*/", "*\\/")).unwrap();
+        }
+        writeln!(out, "   */").unwrap();
+    }
+    let optional_start = method.optional_params_start();
+    let params: Vec<_> = method
+        .params
+        .iter()
+        .enumerate()
+        .map(|(idx, param)| {
+            let optional = if idx >= optional_start { "?" } else { "" };
+            format!("{}{}: {}", ident(&param.name), optional, ts_type(&param.ty))
+        })
+        .collect();
+    let args: Vec<_> = method.params.iter().map(|p| ident(&p.name)).collect();
+    let result = ts_type(&method.result);
+    writeln!(
+        out,
+        "  {}({}): Promise<{}> {{",
+        ident(&method.name.to_mixed_case()),
+        params.join(", "),
+        result
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    return this.transport(\"{}\", [{}]) as Promise<{}>;",
+        method.name,
+        args.join(", "),
+        result
+    )
+    .unwrap();
+    writeln!(out, "  }}").unwrap();
+}
+
+/// Generate the typescript client of the rpc methods in the `schema`.
+pub fn generate(schema: &RpcSchema) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// Generated by starcoin_rpc_codegen from the rpc schema, do not edit.\n"
+    )
+    .unwrap();
+    for name in schema.named_types() {
+        writeln!(
+            out,
+            "/** The json value of `{}` defined by the node. */",
+            name
+        )
+        .unwrap();
+        writeln!(out, "export type {} = any;", name).unwrap();
+    }
+    writeln!(out).unwrap();
+    writeln!(out, "{}", RUNTIME).unwrap();
+    writeln!(out, "export class StarcoinClient {{").unwrap();
+    writeln!(
+        out,
+        "  constructor(private readonly transport: Transport) {{}}"
+    )
+    .unwrap();
+    for method in &schema.methods {
+        writeln!(out).unwrap();
+        write_method(&mut out, method);
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_source;
+
+    #[test]
+    fn test_generate() {
+        let source = r#"
+            #[rpc]
+            pub trait ChainApi {
+                /// Get latest `count` blocks before `number`.
+                #[rpc(name = "chain.get_blocks_by_number")]
+                fn get_blocks_by_number(&self, number: Option<BlockNumber>, count: u64) -> FutureResult<Vec<BlockView>>;
+                #[rpc(name = "chain.get_block_by_hash")]
+                fn get_block_by_hash(&self, block_hash: HashValue, option: Option<bool>) -> FutureResult<Option<BlockView>>;
+            }
+        "#;
+        let schema = RpcSchema::new(parse_source(source).unwrap());
+        let code = generate(&schema);
+        assert!(code.contains("export type BlockView = any;"));
+        assert!(code.contains(
+            "  chainGetBlocksByNumber(number: number | null, count: number): Promise<Array<BlockView>> {"
+        ));
+        assert!(code.contains(
+            "    return this.transport(\"chain.get_blocks_by_number\", [number, count]) as Promise<Array<BlockView>>;"
+        ));
+        assert!(code.contains("   * Get latest `count` blocks before `number`."));
+        assert!(code.contains(
+            "  chainGetBlockByHash(block_hash: string, option?: boolean | null): Promise<BlockView | null> {"
+        ));
+    }
+}
@@ -9,14 +9,14 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::token::token_code::TokenCode;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "accept_token")]
 pub struct AcceptTokenOpt {
-    #[structopt(short = "s")]
+    #[structopt(short = "s", parse(try_from_str = parse_address))]
     /// if `sender` is absent, use default account.
     sender: Option<AccountAddress>,
 
@@ -81,9 +81,10 @@ impl CommandAction for AcceptTokenCommand {
             opt.max_gas_amount,
             opt.token_code.clone(),
             node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
-            ctx.state().net().chain_id(),
+            ctx.state().expected_chain_id(),
         );
 
+        ctx.state().ensure_chain_id(accept_token_txn.chain_id())?;
         let signed_txn = client.account_sign_txn(accept_token_txn)?;
         let txn_hash = signed_txn.id();
         client.submit_transaction(signed_txn)?;
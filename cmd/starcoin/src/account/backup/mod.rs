@@ -0,0 +1,280 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod sync_cmd;
+pub mod target;
+mod verify_cmd;
+
+pub use sync_cmd::*;
+pub use verify_cmd::*;
+
+use crate::account::backup::target::BackupTarget;
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_account_api::KeystoreEntry;
+use starcoin_crypto::HashValue;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The version 2 backup is encrypted with the key derived by scrypt.
+pub const KEYSTORE_BACKUP_VERSION: u8 = 2;
+pub const BACKUP_MANIFEST_NAME: &str = "manifest.json";
+/// The env of the backup password, if it is not given by the option.
+pub const BACKUP_PASSWORD_ENV: &str = "STARCOIN_BACKUP_PASSWORD";
+
+/// The keystore of the local wallet, it is encrypted by the backup password before it leaves the machine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreBackup {
+    pub version: u8,
+    /// The backup time in seconds.
+    pub created_at: u64,
+    pub entries: Vec<KeystoreEntry>,
+}
+
+/// A backup in the target, only the hashes of the backup are kept in the clear.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupRecord {
+    pub name: String,
+    pub created_at: u64,
+    pub accounts: usize,
+    pub size: u64,
+    /// The sha3 hash of the encrypted backup, it is checked before decrypting.
+    pub sha3_256: HashValue,
+    /// The sha3 hash of the keystore entries, the sync is skipped if the keystore is not changed.
+    pub keystore_hash: HashValue,
+}
+
+/// The list of the backups in the target, from the oldest to the newest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u8,
+    pub backups: Vec<BackupRecord>,
+}
+
+impl BackupManifest {
+    pub fn load(target: &dyn BackupTarget) -> Result<Self> {
+        match target.get(BACKUP_MANIFEST_NAME)? {
+            Some(data) => {
+                let manifest: Self = serde_json::from_slice(&data)?;
+                ensure!(
+                    manifest.version == KEYSTORE_BACKUP_VERSION,
+                    "Unsupported keystore backup version {}",
+                    manifest.version
+                );
+                Ok(manifest)
+            }
+            None => Ok(Self {
+                version: KEYSTORE_BACKUP_VERSION,
+                backups: vec![],
+            }),
+        }
+    }
+
+    pub fn save(&self, target: &dyn BackupTarget) -> Result<()> {
+        target.put(BACKUP_MANIFEST_NAME, &serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Find the backup by name, or the newest backup if the name is none.
+    pub fn find(&self, name: Option<&str>) -> Result<&BackupRecord> {
+        match name {
+            Some(name) => self
+                .backups
+                .iter()
+                .find(|record| record.name == name)
+                .ok_or_else(|| format_err!("Can not find backup {} in the target", name)),
+            None => self
+                .backups
+                .last()
+                .ok_or_else(|| format_err!("There is no backup in the target")),
+        }
+    }
+}
+
+/// The backup password of the option, or the env `STARCOIN_BACKUP_PASSWORD`.
+pub fn backup_password(password: Option<&String>) -> Result<String> {
+    let password = match password {
+        Some(password) => password.clone(),
+        None => std::env::var(BACKUP_PASSWORD_ENV).map_err(|_| {
+            format_err!(
+                "The backup password is required, by the option or the env {}",
+                BACKUP_PASSWORD_ENV
+            )
+        })?,
+    };
+    ensure!(
+        !password.is_empty(),
+        "The backup password should not be empty"
+    );
+    Ok(password)
+}
+
+fn keystore_hash(entries: &[KeystoreEntry]) -> Result<HashValue> {
+    Ok(HashValue::sha3_256_of(&serde_json::to_vec(entries)?))
+}
+
+/// Encrypt the keystore and put it to the target, then read it back to check the integrity.
+/// The sync is skipped and the newest backup is returned if the keystore is not changed, unless `force`.
+/// Only the newest `keep` backups are kept in the target.
+pub fn sync_backup(
+    target: &dyn BackupTarget,
+    entries: Vec<KeystoreEntry>,
+    password: &str,
+    keep: usize,
+    force: bool,
+) -> Result<(BackupRecord, bool)> {
+    ensure!(keep > 0, "keep should be greater than 0");
+    let mut manifest = BackupManifest::load(target)?;
+    let keystore_hash = keystore_hash(&entries)?;
+    if let Some(latest) = manifest.backups.last() {
+        if latest.keystore_hash == keystore_hash && !force {
+            return Ok((latest.clone(), false));
+        }
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let backup = KeystoreBackup {
+        version: KEYSTORE_BACKUP_VERSION,
+        created_at: now.as_secs(),
+        entries,
+    };
+    let encrypted =
+        starcoin_decrypt::encrypt_with_scrypt(password.as_bytes(), &serde_json::to_vec(&backup)?);
+    let sha3_256 = HashValue::sha3_256_of(&encrypted);
+    let record = BackupRecord {
+        // the hash makes the name unique even if two backups are created in the same millisecond.
+        name: format!(
+            "keystore-{}-{}.bak",
+            now.as_millis(),
+            hex::encode(&sha3_256.to_vec()[..4])
+        ),
+        created_at: backup.created_at,
+        accounts: backup.entries.len(),
+        size: encrypted.len() as u64,
+        sha3_256,
+        keystore_hash,
+    };
+    target.put(record.name.as_str(), &encrypted)?;
+    // the manifest is updated after the backup is verified, so it never refers to a broken backup.
+    ensure!(
+        download_backup(target, &record, password)? == backup,
+        "The uploaded backup {} is not same as the local keystore",
+        record.name
+    );
+    manifest.backups.push(record.clone());
+    let expired = manifest.backups.len().saturating_sub(keep);
+    let expired_backups: Vec<BackupRecord> = manifest.backups.drain(..expired).collect();
+    manifest.save(target)?;
+    for expired_backup in expired_backups {
+        target.delete(expired_backup.name.as_str())?;
+    }
+    Ok((record, true))
+}
+
+/// Get the backup from the target, check the integrity and decrypt it.
+pub fn download_backup(
+    target: &dyn BackupTarget,
+    record: &BackupRecord,
+    password: &str,
+) -> Result<KeystoreBackup> {
+    let encrypted = target
+        .get(record.name.as_str())?
+        .ok_or_else(|| format_err!("The backup {} is missing in the target", record.name))?;
+    ensure!(
+        encrypted.len() as u64 == record.size
+            && HashValue::sha3_256_of(&encrypted) == record.sha3_256,
+        "The backup {} is corrupted, the hash does not match the manifest",
+        record.name
+    );
+    let plain =
+        starcoin_decrypt::decrypt_with_scrypt(password.as_bytes(), &encrypted).map_err(|_| {
+            format_err!(
+                "Decrypt backup {} failed, invalid backup password",
+                record.name
+            )
+        })?;
+    let backup: KeystoreBackup = serde_json::from_slice(&plain)?;
+    ensure!(
+        backup.version == KEYSTORE_BACKUP_VERSION,
+        "Unsupported keystore backup version {}",
+        backup.version
+    );
+    if backup.entries.len() != record.accounts
+        || keystore_hash(&backup.entries)? != record.keystore_hash
+    {
+        bail!(
+            "The backup {} does not match the manifest, the manifest may be tampered",
+            record.name
+        );
+    }
+    for entry in &backup.entries {
+        ensure!(
+            entry.setting.is_readonly == entry.encrypted_private_key.is_none(),
+            "The private key of account {} in the backup does not match its readonly setting",
+            entry.address
+        );
+        ensure!(
+            entry.public_key.derived_address() == entry.address,
+            "The public key of account {} in the backup does not derive its address",
+            entry.address
+        );
+    }
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::backup::target::FileTarget;
+    use starcoin_account_api::{AccountPublicKey, Setting};
+    use starcoin_crypto::keygen::KeyGen;
+
+    fn mock_entry() -> KeystoreEntry {
+        let (_, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let public_key = AccountPublicKey::single(public_key);
+        KeystoreEntry {
+            address: public_key.derived_address(),
+            public_key,
+            encrypted_private_key: Some(vec![1, 2, 3]),
+            setting: Setting::default(),
+            accepted_tokens: vec![],
+        }
+    }
+
+    #[test]
+    fn test_sync_backup() {
+        let dir = starcoin_config::temp_path();
+        let target = FileTarget::new(dir.path().to_path_buf());
+        let entries = vec![mock_entry()];
+        let (first, uploaded) = sync_backup(&target, entries.clone(), "pass", 2, false).unwrap();
+        assert!(uploaded);
+        // the unchanged keystore is skipped.
+        let (latest, uploaded) = sync_backup(&target, entries.clone(), "pass", 2, false).unwrap();
+        assert!(!uploaded);
+        assert_eq!(latest, first);
+
+        let mut entries = entries;
+        entries.push(mock_entry());
+        let (second, _) = sync_backup(&target, entries.clone(), "pass", 2, false).unwrap();
+        let (third, _) = sync_backup(&target, entries.clone(), "pass", 2, true).unwrap();
+        let manifest = BackupManifest::load(&target).unwrap();
+        assert_eq!(manifest.backups, vec![second, third.clone()]);
+        assert_eq!(target.get(first.name.as_str()).unwrap(), None);
+
+        let backup = download_backup(&target, manifest.find(None).unwrap(), "pass").unwrap();
+        assert_eq!(backup.entries, entries);
+        assert!(download_backup(&target, &third, "wrong").is_err());
+
+        let mut corrupted = target.get(third.name.as_str()).unwrap().unwrap();
+        corrupted[0] ^= 1;
+        target.put(third.name.as_str(), &corrupted).unwrap();
+        assert!(download_backup(&target, &third, "pass").is_err());
+    }
+
+    #[test]
+    fn test_backup_address_mismatch() {
+        let dir = starcoin_config::temp_path();
+        let target = FileTarget::new(dir.path().to_path_buf());
+        let mut entry = mock_entry();
+        entry.public_key = mock_entry().public_key;
+        let (record, _) = sync_backup(&target, vec![entry], "pass", 1, false).unwrap();
+        assert!(download_backup(&target, &record, "pass").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::backup::target::open_target;
+use crate::account::backup::{backup_password, sync_backup, BackupRecord};
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+
+/// Encrypt the keystore of local wallet and upload it to the backup target.
+/// The private keys are still encrypted by the account passwords in the backup.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sync")]
+pub struct SyncOpt {
+    #[structopt(name = "target")]
+    /// the backup target, `file:///path`, `s3://bucket/prefix`, `gs://bucket/prefix` or `webdav://host/path`.
+    /// The credentials are read from the env, AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY for s3,
+    /// GCS_HMAC_ACCESS_ID and GCS_HMAC_SECRET for gs, WEBDAV_USER and WEBDAV_PASSWORD for webdav.
+    target: String,
+
+    #[structopt(short = "b", long = "backup-password")]
+    /// the password to encrypt the backup, default to the env STARCOIN_BACKUP_PASSWORD.
+    backup_password: Option<String>,
+
+    #[structopt(long = "keep", default_value = "5")]
+    /// how many newest backups to keep in the target.
+    keep: usize,
+
+    #[structopt(long = "force")]
+    /// upload the backup even if the keystore is not changed since the newest backup.
+    force: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupSyncView {
+    /// Whether a new backup is uploaded, false if the keystore is not changed.
+    pub uploaded: bool,
+    pub backup: BackupRecord,
+}
+
+pub struct SyncCommand;
+
+impl CommandAction for SyncCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SyncOpt;
+    type ReturnItem = BackupSyncView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let password = backup_password(opt.backup_password.as_ref())?;
+        let target = open_target(opt.target.as_str())?;
+        let entries = ctx.state().client().account_export_keystore()?;
+        let (backup, uploaded) = sync_backup(
+            target.as_ref(),
+            entries,
+            password.as_str(),
+            opt.keep,
+            opt.force,
+        )?;
+        Ok(BackupSyncView { uploaded, backup })
+    }
+}
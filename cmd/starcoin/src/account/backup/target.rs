@@ -0,0 +1,388 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac, NewMac};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+pub const AWS_ACCESS_KEY_ID_ENV: &str = "AWS_ACCESS_KEY_ID";
+pub const AWS_SECRET_ACCESS_KEY_ENV: &str = "AWS_SECRET_ACCESS_KEY";
+pub const AWS_REGION_ENV: &str = "AWS_REGION";
+/// The endpoint of the s3 compatible storage, such as minio, default to the aws endpoint of the region.
+pub const AWS_ENDPOINT_URL_ENV: &str = "AWS_ENDPOINT_URL";
+pub const GCS_HMAC_ACCESS_ID_ENV: &str = "GCS_HMAC_ACCESS_ID";
+pub const GCS_HMAC_SECRET_ENV: &str = "GCS_HMAC_SECRET";
+pub const WEBDAV_USER_ENV: &str = "WEBDAV_USER";
+pub const WEBDAV_PASSWORD_ENV: &str = "WEBDAV_PASSWORD";
+
+const DEFAULT_AWS_REGION: &str = "us-east-1";
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+const GCS_REGION: &str = "auto";
+
+/// Where the keystore backups are stored, the backups are encrypted before they are put to the target.
+pub trait BackupTarget {
+    /// Read the object, none if it does not exist.
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>>;
+    fn put(&self, name: &str, data: &[u8]) -> Result<()>;
+    fn delete(&self, name: &str) -> Result<()>;
+}
+
+/// Open the backup target by the url:
+/// `file:///path`, `s3://bucket/prefix`, `gs://bucket/prefix`, `webdav://host/path` or `webdav+http://host/path`.
+/// The credentials of the cloud targets are read from the env.
+pub fn open_target(url: &str) -> Result<Box<dyn BackupTarget>> {
+    let index = url
+        .find("://")
+        .ok_or_else(|| format_err!("Invalid backup target {}, expect <scheme>://<path>", url))?;
+    let (scheme, path) = (&url[..index], &url[index + 3..]);
+    let target: Box<dyn BackupTarget> = match scheme {
+        "file" => Box::new(FileTarget::new(PathBuf::from(path))),
+        "s3" => {
+            let region =
+                std::env::var(AWS_REGION_ENV).unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string());
+            let endpoint = std::env::var(AWS_ENDPOINT_URL_ENV)
+                .unwrap_or_else(|_| format!("https://s3.{}.amazonaws.com", region));
+            let signer = S3Signer::new(
+                env_var(AWS_ACCESS_KEY_ID_ENV)?,
+                env_var(AWS_SECRET_ACCESS_KEY_ENV)?,
+                region,
+            );
+            Box::new(S3Target::new(endpoint.as_str(), path, signer)?)
+        }
+        // use the s3 compatible xml api of the gcs with the hmac key.
+        "gs" => {
+            let signer = S3Signer::new(
+                env_var(GCS_HMAC_ACCESS_ID_ENV)?,
+                env_var(GCS_HMAC_SECRET_ENV)?,
+                GCS_REGION.to_string(),
+            );
+            Box::new(S3Target::new(GCS_ENDPOINT, path, signer)?)
+        }
+        "webdav" | "webdav+https" => Box::new(WebDavTarget::new(format!("https://{}", path))),
+        "webdav+http" => Box::new(WebDavTarget::new(format!("http://{}", path))),
+        _ => bail!(
+            "Unsupported backup target {}, expect file, s3, gs or webdav",
+            scheme
+        ),
+    };
+    Ok(target)
+}
+
+fn env_var(name: &str) -> Result<String> {
+    std::env::var(name)
+        .map_err(|_| format_err!("The env {} is required by the backup target", name))
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", prefix, name)
+    }
+}
+
+fn check_response(response: Response, name: &str) -> Result<Response> {
+    let status = response.status();
+    if !status.is_success() {
+        bail!(
+            "Request backup object {} failed, {}: {}",
+            name,
+            status,
+            response.text().unwrap_or_default()
+        );
+    }
+    Ok(response)
+}
+
+fn read_response(response: Response, name: &str) -> Result<Option<Vec<u8>>> {
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(check_response(response, name)?.bytes()?.to_vec()))
+}
+
+/// A dir of local file system, such as a mounted network drive.
+pub struct FileTarget {
+    dir: PathBuf,
+}
+
+impl FileTarget {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl BackupTarget for FileTarget {
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read(path)?))
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(self.dir.as_path())?;
+        // write to a temp file and rename it, so a broken write does not replace the object.
+        let tmp_path = self.dir.join(format!("{}.tmp", name));
+        std::fs::write(tmp_path.as_path(), data)?;
+        std::fs::rename(tmp_path, self.dir.join(name))?;
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        let path = self.dir.join(name);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The aws signature version 4 of the s3 requests.
+pub struct S3Signer {
+    access_key: String,
+    secret_key: String,
+    region: String,
+}
+
+impl S3Signer {
+    pub fn new(access_key: String, secret_key: String, region: String) -> Self {
+        Self {
+            access_key,
+            secret_key,
+            region,
+        }
+    }
+
+    /// The headers to sign the request, include the `Authorization`.
+    pub fn sign(
+        &self,
+        method: &Method,
+        host: &str,
+        path: &str,
+        payload: &[u8],
+        now: DateTime<Utc>,
+    ) -> Vec<(&'static str, String)> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(payload));
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method.as_str(),
+            uri_encode(path),
+            host,
+            payload_hash,
+            amz_date,
+            signed_headers,
+            payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = signing_key(self.secret_key.as_str(), date.as_str(), &self.region, "s3");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+        vec![
+            ("x-amz-date", amz_date),
+            ("x-amz-content-sha256", payload_hash),
+            (
+                "authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                    self.access_key, scope, signed_headers, signature
+                ),
+            ),
+        ]
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("Hmac accepts key of any size.");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date.as_bytes());
+    let region_key = hmac_sha256(&date_key, region.as_bytes());
+    let service_key = hmac_sha256(&region_key, service.as_bytes());
+    hmac_sha256(&service_key, b"aws4_request")
+}
+
+/// Encode the path of the uri, except the unreserved characters and the `/`.
+fn uri_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(format!("%{:02X}", byte).as_str()),
+        }
+    }
+    encoded
+}
+
+/// A bucket of the s3 compatible storage, the objects are addressed in the path style.
+pub struct S3Target {
+    endpoint: String,
+    host: String,
+    bucket: String,
+    prefix: String,
+    signer: S3Signer,
+    client: Client,
+}
+
+impl S3Target {
+    /// The `path` is `bucket/prefix`.
+    pub fn new(endpoint: &str, path: &str, signer: S3Signer) -> Result<Self> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let host = endpoint
+            .splitn(2, "://")
+            .nth(1)
+            .ok_or_else(|| format_err!("Invalid endpoint {}", endpoint))?;
+        let mut parts = path.splitn(2, '/');
+        let bucket = parts.next().unwrap_or_default();
+        if bucket.is_empty() {
+            bail!("The bucket of the backup target is missing");
+        }
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            host: host.to_string(),
+            bucket: bucket.to_string(),
+            prefix: parts.next().unwrap_or_default().to_string(),
+            signer,
+            client: Client::new(),
+        })
+    }
+
+    fn request(&self, method: Method, name: &str, payload: Vec<u8>) -> Result<Response> {
+        let path = format!("/{}/{}", self.bucket, join_path(self.prefix.as_str(), name));
+        let headers = self.signer.sign(
+            &method,
+            self.host.as_str(),
+            path.as_str(),
+            payload.as_slice(),
+            Utc::now(),
+        );
+        let mut request: RequestBuilder = self.client.request(
+            method,
+            format!("{}{}", self.endpoint, uri_encode(&path)).as_str(),
+        );
+        for (header, value) in headers {
+            request = request.header(header, value);
+        }
+        Ok(request.body(payload).send()?)
+    }
+}
+
+impl BackupTarget for S3Target {
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        read_response(self.request(Method::GET, name, vec![])?, name)
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        check_response(self.request(Method::PUT, name, data.to_vec())?, name)?;
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        check_response(self.request(Method::DELETE, name, vec![])?, name)?;
+        Ok(())
+    }
+}
+
+/// A collection of the WebDAV server, the collection should exist.
+pub struct WebDavTarget {
+    url: String,
+    auth: Option<(String, String)>,
+    client: Client,
+}
+
+impl WebDavTarget {
+    pub fn new(url: String) -> Self {
+        let auth = std::env::var(WEBDAV_USER_ENV)
+            .ok()
+            .map(|user| (user, std::env::var(WEBDAV_PASSWORD_ENV).unwrap_or_default()));
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            auth,
+            client: Client::new(),
+        }
+    }
+
+    fn request(&self, method: Method, name: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/{}", self.url, name).as_str());
+        match &self.auth {
+            Some((user, password)) => request.basic_auth(user, Some(password)),
+            None => request,
+        }
+    }
+}
+
+impl BackupTarget for WebDavTarget {
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        read_response(self.request(Method::GET, name).send()?, name)
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        check_response(
+            self.request(Method::PUT, name).body(data.to_vec()).send()?,
+            name,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        let response = self.request(Method::DELETE, name).send()?;
+        if response.status() != StatusCode::NOT_FOUND {
+            check_response(response, name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key() {
+        // the example of deriving the signing key in the aws signature version 4 document.
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_file_target() {
+        let dir = starcoin_config::temp_path();
+        let target = open_target(format!("file://{}", dir.path().display()).as_str()).unwrap();
+        assert_eq!(target.get("object").unwrap(), None);
+        target.put("object", b"data").unwrap();
+        assert_eq!(target.get("object").unwrap(), Some(b"data".to_vec()));
+        target.delete("object").unwrap();
+        assert_eq!(target.get("object").unwrap(), None);
+        assert!(open_target("ftp://host/path").is_err());
+        assert_eq!(uri_encode("/bucket/a b+c"), "/bucket/a%20b%2Bc");
+    }
+}
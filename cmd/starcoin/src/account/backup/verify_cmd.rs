@@ -0,0 +1,67 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::backup::target::open_target;
+use crate::account::backup::{backup_password, download_backup, BackupManifest, BackupRecord};
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+/// Download a backup from the backup target, check its integrity and decrypt it, without importing the accounts.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "verify")]
+pub struct VerifyOpt {
+    #[structopt(name = "target")]
+    /// the backup target, see `account backup sync`.
+    target: String,
+
+    #[structopt(short = "b", long = "backup-password")]
+    /// the password to decrypt the backup, default to the env STARCOIN_BACKUP_PASSWORD.
+    backup_password: Option<String>,
+
+    #[structopt(long = "backup")]
+    /// the name of the backup to verify, default to the newest backup.
+    backup: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupVerifyView {
+    pub backup: BackupRecord,
+    pub accounts: Vec<AccountAddress>,
+    /// All backups in the target.
+    pub backups: Vec<String>,
+}
+
+pub struct VerifyCommand;
+
+impl CommandAction for VerifyCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = VerifyOpt;
+    type ReturnItem = BackupVerifyView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let password = backup_password(opt.backup_password.as_ref())?;
+        let target = open_target(opt.target.as_str())?;
+        let manifest = BackupManifest::load(target.as_ref())?;
+        let record = manifest.find(opt.backup.as_deref())?;
+        let backup = download_backup(target.as_ref(), record, password.as_str())?;
+        Ok(BackupVerifyView {
+            backup: record.clone(),
+            accounts: backup.entries.iter().map(|entry| entry.address).collect(),
+            backups: manifest
+                .backups
+                .iter()
+                .map(|record| record.name.clone())
+                .collect(),
+        })
+    }
+}
@@ -6,7 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_account_api::AccountInfo;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -14,7 +14,8 @@ use structopt::StructOpt;
 pub struct ChangePasswordOpt {
     #[structopt(
         name = "account_address",
-        help = "The wallet account address which to change password."
+        help = "The wallet account address which to change password.",
+        parse(try_from_str = parse_address)
     )]
     account_address: AccountAddress,
 
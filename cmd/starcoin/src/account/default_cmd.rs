@@ -6,7 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_account_api::AccountInfo;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
@@ -14,7 +14,8 @@ use structopt::StructOpt;
 pub struct DefaultOpt {
     #[structopt(
         name = "account_address",
-        help = "set default address to this, if not provided, display current default address"
+        help = "set default address to this, if not provided, display current default address",
+        parse(try_from_str = parse_address)
     )]
     account_address: Option<AccountAddress>,
 }
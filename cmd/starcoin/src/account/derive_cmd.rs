@@ -0,0 +1,48 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::hd::DerivationPath;
+use starcoin_account_api::AccountInfo;
+use structopt::StructOpt;
+
+/// Derive an account from mnemonic by the hd path, and save it to the node wallet.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "derive")]
+pub struct DeriveOpt {
+    #[structopt(short = "p", default_value = "")]
+    password: String,
+
+    #[structopt(short = "m", long = "mnemonic")]
+    /// the mnemonic words, split by space.
+    mnemonic: String,
+
+    #[structopt(long = "path", default_value = "m/44'/101010'/0'/0'/0'")]
+    /// the hd derivation path, every level is hardened.
+    path: DerivationPath,
+}
+
+pub struct DeriveCommand;
+
+impl CommandAction for DeriveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = DeriveOpt;
+    type ReturnItem = AccountInfo;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let account = ctx.state().client().account_derive(
+            opt.mnemonic.clone(),
+            opt.path.to_string(),
+            opt.password.clone(),
+        )?;
+        Ok(account)
+    }
+}
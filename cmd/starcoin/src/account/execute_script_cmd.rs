@@ -15,7 +15,7 @@ use starcoin_types::transaction::{
     parse_transaction_argument, DryRunTransaction, RawUserTransaction, Script, TransactionArgument,
     TransactionPayload,
 };
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
 use std::path::PathBuf;
@@ -24,7 +24,7 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "execute-script")]
 pub struct ExecuteScriptOpt {
-    #[structopt(short = "s", long)]
+    #[structopt(short = "s", long, parse(try_from_str = parse_address))]
     /// hex encoded string, like 0x1, 0x12
     sender: Option<AccountAddress>,
 
@@ -141,10 +141,11 @@ impl CommandAction for ExecuteScriptCommand {
                 opt.max_gas_amount,
                 opt.gas_price,
                 expiration_time,
-                ctx.state().net().chain_id(),
+                ctx.state().expected_chain_id(),
             )
         };
 
+        ctx.state().ensure_chain_id(raw_txn.chain_id())?;
         let signed_txn = client.account_sign_txn(raw_txn)?;
         let txn_hash = signed_txn.id();
         let output: TransactionOutputView = {
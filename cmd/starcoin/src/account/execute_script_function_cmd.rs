@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account::transfer_cmd::{check_transfer_confirm, withdrawn_amounts};
 use crate::cli_state::CliState;
 use crate::view::{ExecuteResultView, ExecutionOutputView};
 use crate::StarcoinOpt;
@@ -13,7 +14,7 @@ use starcoin_state_api::AccountStateReader;
 use starcoin_types::transaction::{
     parse_transaction_argument, DryRunTransaction, RawUserTransaction, TransactionArgument,
 };
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::transaction::ScriptFunction;
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
@@ -22,7 +23,7 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "execute-function")]
 pub struct ExecuteScriptFunctionOpt {
-    #[structopt(short = "s")]
+    #[structopt(short = "s", parse(try_from_str = parse_address))]
     /// if `sender` is absent, use default account.
     sender: Option<AccountAddress>,
 
@@ -120,9 +121,10 @@ impl CommandAction for ExecuteScriptFunctionCmd {
             opt.max_gas_amount,
             opt.gas_price,
             expiration_time,
-            ctx.state().net().chain_id(),
+            ctx.state().expected_chain_id(),
         );
 
+        ctx.state().ensure_chain_id(script_txn.chain_id())?;
         let signed_txn = client.account_sign_txn(script_txn)?;
         let txn_hash = signed_txn.id();
         let output: TransactionOutputView = {
@@ -146,6 +148,17 @@ impl CommandAction for ExecuteScriptFunctionCmd {
             }
         }
         if !opt.dry_run {
+            // the transfers of the function are confirmed by the withdrawals of the sender in the dry run output.
+            for (token_code, (amount, receivers)) in
+                withdrawn_amounts(sender.address, &output.events)?
+            {
+                let receiver = if receivers.len() == 1 {
+                    receivers.into_iter().next()
+                } else {
+                    None
+                };
+                check_transfer_confirm(client, &sender.metadata, amount, &token_code, receiver)?;
+            }
             client.submit_transaction(signed_txn)?;
 
             println!("txn {:#x} submitted.", txn_hash);
@@ -1,26 +1,43 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account::mobile_export;
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountMetadata;
 use starcoin_crypto::ValidCryptoMaterialStringExt;
 use starcoin_types::transaction::authenticator::AccountPrivateKey;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "export")]
 pub struct ExportOpt {
-    #[structopt(name = "account_address")]
+    #[structopt(name = "account_address", parse(try_from_str = parse_address))]
     account_address: AccountAddress,
     #[structopt(short = "p", default_value = "")]
     password: String,
-    #[structopt(short = "o", parse(from_os_str))]
+    #[structopt(short = "o", name = "output_file", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// Export to a mobile wallet by the hex X25519 public key it shows, the private key is
+    /// encrypted to it and rendered as a QR, with a pairing code to type into the wallet.
+    #[structopt(
+        long = "to-mobile",
+        name = "mobile_public_key",
+        conflicts_with = "output_file"
+    )]
+    to_mobile: Option<String>,
+}
+
+/// The notes and tags of the account are exported to `<output_file>.metadata.json`.
+pub fn metadata_file_path(output_file: &Path) -> PathBuf {
+    let mut file_name = output_file.as_os_str().to_os_string();
+    file_name.push(".metadata.json");
+    PathBuf::from(file_name)
 }
 
 pub struct ExportCommand;
@@ -37,17 +54,52 @@ impl CommandAction for ExportCommand {
         let data = client.account_export(opt.account_address, opt.password.clone())?;
         let private_key = AccountPrivateKey::try_from(data.as_slice())?;
         let encoded = private_key.to_encoded_string()?;
+        if let Some(mobile_public_key) = &opt.to_mobile {
+            let mobile_public_key = mobile_export::parse_public_key(mobile_public_key)?;
+            let pairing_code = mobile_export::generate_pairing_code();
+            let payload = mobile_export::encrypt(
+                opt.account_address,
+                &encoded,
+                &mobile_public_key,
+                &pairing_code,
+            )?;
+            println!("{}", mobile_export::render_qr(&payload)?);
+            println!(
+                "scan the QR by the mobile wallet, and check the wallet shows the verification code: {}",
+                payload.verification_code(&mobile_public_key)?
+            );
+            println!(
+                "only if the codes match, enter the pairing code into the wallet: {}",
+                pairing_code
+            );
+            return Ok(());
+        }
+        let metadata = client
+            .account_get(opt.account_address)?
+            .map(|account| account.metadata)
+            .unwrap_or_default();
         if let Some(output_file) = &opt.output_file {
             if output_file.exists() {
                 bail!("the output_file {} is already exists, please change a name");
             }
             std::fs::write(output_file, encoded.clone())?;
             println!("private key saved to {}", output_file.as_path().display());
+            if metadata != AccountMetadata::default() {
+                let metadata_file = metadata_file_path(output_file);
+                std::fs::write(&metadata_file, serde_json::to_vec_pretty(&metadata)?)?;
+                println!("account metadata saved to {}", metadata_file.display());
+            }
         }
         println!(
             "account {}, private key: {}",
             &opt.account_address, &encoded
         );
+        if let Some(notes) = &metadata.notes {
+            println!("notes: {}", notes);
+        }
+        if !metadata.tags.is_empty() {
+            println!("tags: {}", metadata.tags.join(","));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,263 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::dev::transfer_from_association;
+use crate::StarcoinOpt;
+use anyhow::{bail, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_account_api::AccountPublicKey;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
+use starcoin_state_api::AccountStateReader;
+use starcoin_types::account_address::{parse_address, AccountAddress};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+/// Default fund amount when request coin from faucet, 1 STC.
+const DEFAULT_FAUCET_FUND_AMOUNT: u128 = 1_000_000_000;
+/// The env to custom the faucet fund api url, `--faucet-url` takes precedence over it.
+const FAUCET_URL_ENV: &str = "STARCOIN_FAUCET_URL";
+const BALANCE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Fund the account by the faucet of test network.
+/// In dev or test network, transfer from the association account of local node,
+/// In halley, proxima or barnard network, request the configured faucet http api.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "fund")]
+pub struct FundOpt {
+    #[structopt(name = "address", parse(try_from_str = parse_address))]
+    /// the account to fund, if absent, use default account.
+    address: Option<AccountAddress>,
+
+    #[structopt(short = "v")]
+    /// fund amount, in dev network default to 20% of association_address's balance.
+    amount: Option<u128>,
+
+    #[structopt(long = "faucet-url")]
+    /// custom the faucet fund api url, or by env STARCOIN_FAUCET_URL, default use the faucet of current network.
+    faucet_url: Option<String>,
+
+    #[structopt(
+        name = "no-blocking-mode",
+        long = "no-blocking",
+        help = "not blocking wait the fund arrived"
+    )]
+    no_blocking: bool,
+}
+
+pub struct FundCommand;
+
+impl CommandAction for FundCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = FundOpt;
+    type ReturnItem = FundView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let net = ctx.state().net();
+        let client = ctx.state().client();
+        let (address, public_key) = match opt.address {
+            Some(address) => {
+                let public_key = client
+                    .account_get(address)?
+                    .map(|account| account.public_key);
+                (address, public_key)
+            }
+            None => {
+                let account = ctx.state().default_account()?;
+                (account.address, Some(account.public_key))
+            }
+        };
+        let balance_before = get_balance(client, address)?;
+
+        let txn_hash = if net.is_test_or_dev() {
+            let txn = transfer_from_association(
+                ctx.state(),
+                address,
+                public_key.map(|k| k.authentication_key()),
+                opt.amount,
+            )?;
+            Some(txn.id())
+        } else {
+            let faucet_url = resolve_faucet_url(
+                opt.faucet_url.clone(),
+                std::env::var(FAUCET_URL_ENV).ok(),
+                net.faucet_url(),
+            )
+            .ok_or_else(|| {
+                format_err!(
+                    "Network {} has no faucet, please provide --faucet-url or env {}.",
+                    net,
+                    FAUCET_URL_ENV
+                )
+            })?;
+            fund_by_faucet(
+                faucet_url.as_str(),
+                address,
+                public_key,
+                opt.amount.unwrap_or(DEFAULT_FAUCET_FUND_AMOUNT),
+            )?;
+            None
+        };
+
+        if opt.no_blocking {
+            return Ok(FundView {
+                address,
+                txn_hash,
+                balance: balance_before,
+            });
+        }
+        let balance = match txn_hash {
+            Some(txn_hash) => {
+                ctx.state().watch_txn(txn_hash)?;
+                get_balance(client, address)?
+            }
+            None => {
+                wait_balance_changed(client, address, balance_before, ctx.state().watch_timeout())?
+            }
+        };
+        Ok(FundView {
+            address,
+            txn_hash,
+            balance,
+        })
+    }
+}
+
+fn get_balance(client: &RpcClient, address: AccountAddress) -> Result<u128> {
+    let chain_state_reader = RemoteStateReader::new(client)?;
+    let account_state_reader = AccountStateReader::new(&chain_state_reader);
+    Ok(account_state_reader.get_balance(&address)?.unwrap_or(0))
+}
+
+fn wait_balance_changed(
+    client: &RpcClient,
+    address: AccountAddress,
+    balance_before: u128,
+    timeout: Duration,
+) -> Result<u128> {
+    let start = Instant::now();
+    loop {
+        let balance = get_balance(client, address)?;
+        if balance != balance_before {
+            return Ok(balance);
+        }
+        if start.elapsed() > timeout {
+            bail!(
+                "Wait fund of {} timeout, the balance is still {}",
+                address,
+                balance
+            );
+        }
+        std::thread::sleep(BALANCE_POLL_INTERVAL);
+    }
+}
+
+/// The faucet url by the precedence: `--faucet-url`, env, the faucet of current network.
+fn resolve_faucet_url(
+    opt_url: Option<String>,
+    env_url: Option<String>,
+    network_url: Option<String>,
+) -> Option<String> {
+    opt_url
+        .or_else(|| env_url.filter(|url| !url.trim().is_empty()))
+        .or(network_url)
+}
+
+fn fund_by_faucet(
+    faucet_url: &str,
+    to: AccountAddress,
+    to_public_key: Option<AccountPublicKey>,
+    amount: u128,
+) -> Result<()> {
+    let mut query = vec![("address", to.to_string()), ("amount", amount.to_string())];
+    if let Some(public_key) = to_public_key {
+        query.push(("public_key", hex::encode(public_key.public_key_bytes())));
+    }
+    let response = reqwest::blocking::Client::new()
+        .get(faucet_url)
+        .query(&query)
+        .send()?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        bail!("Request faucet {} failed, {}: {}", faucet_url, status, body);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundView {
+    pub address: AccountAddress,
+    /// The fund txn hash, only available when fund by local association account.
+    pub txn_hash: Option<HashValue>,
+    pub balance: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_resolve_faucet_url() {
+        let url = |s: &str| Some(s.to_string());
+        assert_eq!(
+            resolve_faucet_url(url("http://opt"), url("http://env"), url("http://net")),
+            url("http://opt")
+        );
+        assert_eq!(
+            resolve_faucet_url(None, url("http://env"), url("http://net")),
+            url("http://env")
+        );
+        assert_eq!(
+            resolve_faucet_url(None, url(" "), url("http://net")),
+            url("http://net")
+        );
+        assert_eq!(resolve_faucet_url(None, None, None), None);
+    }
+
+    fn serve_once(status: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/api/fund", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let len = stream.read(&mut buf).unwrap();
+            let body = "ok";
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8_lossy(&buf[..len]).to_string()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_fund_by_faucet() {
+        let address = AccountAddress::random();
+        let (url, handle) = serve_once("200 OK");
+        fund_by_faucet(url.as_str(), address, None, 100).unwrap();
+        let request = handle.join().unwrap();
+        let request_line = request.lines().next().unwrap();
+        assert!(request_line.starts_with("GET /api/fund?"));
+        assert!(request_line.contains(&format!("address={}", address)));
+        assert!(request_line.contains("amount=100"));
+
+        let (url, handle) = serve_once("429 Too Many Requests");
+        assert!(fund_by_faucet(url.as_str(), address, None, 100).is_err());
+        handle.join().unwrap();
+    }
+}
@@ -1,19 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account::{EntropySource, KeyCeremonyAttestation, KeyCeremonyBundle};
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
-use anyhow::{bail, format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use serde::Deserialize;
 use serde::Serialize;
 use starcoin_crypto::keygen::KeyGen;
-use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::receipt_identifier::ReceiptIdentifier;
 use starcoin_types::transaction::authenticator::AuthenticationKey;
 use starcoin_vm_types::transaction::authenticator::{AccountPrivateKey, AccountPublicKey};
 use std::convert::TryInto;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 /// Generate keypair
@@ -26,6 +28,11 @@ pub struct GenerateKeypairOpt {
     /// How many keypair to generate
     #[structopt(short = "c", name = "count")]
     count: Option<u32>,
+    /// Generate a single keypair in the key ceremony mode, write the attestation bundle signed by the key to the file,
+    /// it can be verified by `account verify-ceremony`.
+    /// The bundle is self-signed by the generated key, so it records the ceremony but proves nothing to a third party.
+    #[structopt(long = "ceremony", name = "ceremony-file", parse(from_os_str))]
+    ceremony: Option<PathBuf>,
 }
 
 pub struct GenerateKeypairCommand;
@@ -42,6 +49,11 @@ impl CommandAction for GenerateKeypairCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let opt = ctx.opt();
+        ensure!(
+            opt.ceremony.is_none() || opt.count.unwrap_or(1) == 1,
+            "The key ceremony generates a single keypair"
+        );
+        let mut entropy_sources = vec![];
         let mut key_gen = if let Some(literal) = opt.seed.as_ref() {
             let literal = literal.strip_prefix("0x").unwrap_or(literal);
             if literal.len() != SEED_HEX_LENGTH {
@@ -55,25 +67,34 @@ impl CommandAction for GenerateKeypairCommand {
             let seed: [u8; 32] = seed
                 .try_into()
                 .map_err(|_| format_err!("invalid seed argument"))?;
+            entropy_sources.push(EntropySource::UserSeed {
+                seed_hash: HashValue::sha3_256_of(&seed),
+            });
             KeyGen::from_seed(seed)
         } else {
+            entropy_sources.push(EntropySource::OsRng);
             KeyGen::from_os_rng()
         };
+        if let Some(ceremony) = opt.ceremony.as_ref() {
+            let (private_key, public_key) = key_gen.generate_keypair();
+            let account_public_key = AccountPublicKey::single(public_key);
+            let account_private_key = AccountPrivateKey::Single(private_key);
+            let attestation =
+                KeyCeremonyAttestation::new(entropy_sources, account_public_key.clone())?;
+            KeyCeremonyBundle::sign(attestation, &account_private_key)?.save(ceremony)?;
+            return Ok(vec![GenerateKeypairData::new(
+                account_public_key,
+                account_private_key,
+            )]);
+        }
         let keypairs = (0..opt.count.unwrap_or(1))
             .into_iter()
             .map(|_| {
                 let (private_key, public_key) = key_gen.generate_keypair();
-                let account_public_key = AccountPublicKey::single(public_key);
-                let account_private_key = AccountPrivateKey::Single(private_key);
-                GenerateKeypairData {
-                    address: account_public_key.derived_address(),
-                    auth_key: account_public_key.authentication_key(),
-                    receipt_identifier: account_public_key.receipt_identifier(),
-                    public_key: account_public_key,
-                    private_key: account_private_key
-                        .to_encoded_string()
-                        .expect("private key to string should success."),
-                }
+                GenerateKeypairData::new(
+                    AccountPublicKey::single(public_key),
+                    AccountPrivateKey::Single(private_key),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -89,3 +110,17 @@ pub struct GenerateKeypairData {
     pub public_key: AccountPublicKey,
     pub private_key: String,
 }
+
+impl GenerateKeypairData {
+    fn new(public_key: AccountPublicKey, private_key: AccountPrivateKey) -> Self {
+        Self {
+            address: public_key.derived_address(),
+            auth_key: public_key.authentication_key(),
+            receipt_identifier: public_key.receipt_identifier(),
+            public_key,
+            private_key: private_key
+                .to_encoded_string()
+                .expect("private key to string should success."),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_account_api::hd::{self, DerivationPath};
+use starcoin_account_api::{AccountPrivateKey, AccountPublicKey};
+use starcoin_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+/// Generate a new mnemonic, and show the first BIP44 account derived from it.
+/// The mnemonic is generated locally, and not saved to the node.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "generate-mnemonic")]
+pub struct GenerateMnemonicOpt {
+    /// Word count of the mnemonic, should be one of 12, 15, 18, 21, 24.
+    #[structopt(short = "w", long = "words", default_value = "12")]
+    word_count: usize,
+}
+
+pub struct GenerateMnemonicCommand;
+
+impl CommandAction for GenerateMnemonicCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenerateMnemonicOpt;
+    type ReturnItem = GenerateMnemonicData;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let mnemonic = hd::generate_mnemonic(opt.word_count)?;
+        let path = DerivationPath::bip44(0);
+        let private_key = hd::derive_private_key(mnemonic.as_str(), &path)?;
+        let public_key = AccountPrivateKey::Single(private_key).public_key();
+        Ok(GenerateMnemonicData {
+            mnemonic,
+            path: path.to_string(),
+            address: public_key.derived_address(),
+            public_key,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateMnemonicData {
+    pub mnemonic: String,
+    pub path: String,
+    pub address: AccountAddress,
+    pub public_key: AccountPublicKey,
+}
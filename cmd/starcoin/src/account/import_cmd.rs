@@ -1,13 +1,14 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account::metadata_file_path;
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
-use starcoin_account_api::{AccountInfo, AccountPrivateKey};
+use starcoin_account_api::{AccountInfo, AccountMetadata, AccountPrivateKey};
 use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -22,14 +23,14 @@ pub struct ImportOpt {
 
     #[structopt(
         short = "f",
-        help = "file path of private key",
+        help = "file path of private key, also import the metadata in `<file>.metadata.json`",
         parse(from_os_str),
         conflicts_with("input")
     )]
     from_file: Option<PathBuf>,
 
     /// if account_address is absent, generate address by public_key.
-    #[structopt(name = "account_address")]
+    #[structopt(name = "account_address", parse(try_from_str = parse_address))]
     account_address: Option<AccountAddress>,
 }
 
@@ -67,6 +68,15 @@ impl CommandAction for ImportCommand {
             private_key.to_bytes().to_vec(),
             opt.password.clone(),
         )?;
+        if let Some(metadata_file) = opt
+            .from_file
+            .as_ref()
+            .map(|p| metadata_file_path(p.as_path()))
+            .filter(|p| p.exists())
+        {
+            let metadata: AccountMetadata = serde_json::from_slice(&std::fs::read(metadata_file)?)?;
+            return client.account_update_metadata(account.address, metadata.into());
+        }
         Ok(account)
     }
 }
@@ -10,7 +10,7 @@ use starcoin_account_api::{AccountInfo, AccountPrivateKey};
 use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
 use starcoin_crypto::{PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -21,7 +21,7 @@ pub struct ImportMultisigOpt {
     /// a password to protect imported account.
     password: String,
 
-    #[structopt(name = "addr", long)]
+    #[structopt(name = "addr", long, parse(try_from_str = parse_address))]
     /// if account_address is absent, generate address by public_key.
     account_address: Option<AccountAddress>,
 
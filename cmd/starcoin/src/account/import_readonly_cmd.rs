@@ -7,7 +7,7 @@ use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_account_api::{AccountInfo, AccountPublicKey};
 use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -17,7 +17,7 @@ pub struct ImportReadonlyOpt {
     from_input: String,
 
     /// if account_address is absent, generate address by public_key.
-    #[structopt(name = "account_address")]
+    #[structopt(name = "account_address", parse(try_from_str = parse_address))]
     account_address: Option<AccountAddress>,
 }
 
@@ -3,14 +3,50 @@
 
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
 use starcoin_account_api::AccountInfo;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::{BalanceResource, STC_TOKEN_CODE};
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
 #[structopt(name = "list")]
-pub struct ListOpt {}
+pub struct ListOpt {
+    #[structopt(long = "with-balance")]
+    /// Fetch the balances of all accounts, and show the portfolio summary of every token.
+    with_balance: bool,
+
+    #[structopt(long = "sort-by", requires("with-balance"))]
+    /// Sort the accounts by `address` or `balance`, sort by `balance` means sort by STC balance in descending order.
+    sort_by: Option<AccountSortBy>,
+
+    #[structopt(long = "tag", number_of_values = 1)]
+    /// Only list the accounts with the tag, if specified multiple times, the accounts should have all of the tags.
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AccountSortBy {
+    Address,
+    Balance,
+}
+
+impl FromStr for AccountSortBy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "address" => AccountSortBy::Address,
+            "balance" => AccountSortBy::Balance,
+            _ => bail!("Unknown sort by {}, expect address or balance.", s),
+        })
+    }
+}
 
 pub struct ListCommand;
 
@@ -18,14 +54,102 @@ impl CommandAction for ListCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = ListOpt;
-    type ReturnItem = Vec<AccountInfo>;
+    type ReturnItem = AccountListView;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
         let client = ctx.state().client();
-        let accounts = client.account_list()?;
-        Ok(accounts)
+        let accounts: Vec<AccountInfo> = client
+            .account_list()?
+            .into_iter()
+            .filter(|account| opt.tags.iter().all(|tag| account.has_tag(tag)))
+            .collect();
+        if !opt.with_balance {
+            return Ok(AccountListView::Accounts(accounts));
+        }
+
+        let accepted_tokens_response = accounts
+            .iter()
+            .fold(client.batch(), |batch, account| {
+                batch.account_accepted_tokens(account.address)
+            })
+            .send()?;
+        let mut balance_keys = vec![];
+        for index in 0..accounts.len() {
+            for token in accepted_tokens_response.get::<Vec<TokenCode>>(index)? {
+                balance_keys.push((index, token));
+            }
+        }
+        let balances_response = balance_keys
+            .iter()
+            .fold(client.batch(), |batch, (index, token)| {
+                batch.state_get(AccessPath::new(
+                    accounts[*index].address,
+                    BalanceResource::access_path_for(token.clone().into()),
+                ))
+            })
+            .send()?;
+
+        let mut account_balances: Vec<AccountWithBalanceView> = accounts
+            .into_iter()
+            .map(|account| AccountWithBalanceView {
+                account,
+                balances: BTreeMap::new(),
+            })
+            .collect();
+        let mut total_balances = BTreeMap::new();
+        for (response_index, (index, token)) in balance_keys.into_iter().enumerate() {
+            let balance = match balances_response.get::<Option<Vec<u8>>>(response_index)? {
+                Some(bytes) => bcs_ext::from_bytes::<BalanceResource>(bytes.as_slice())?.token(),
+                None => continue,
+            };
+            let token_name = token.to_string();
+            *total_balances.entry(token_name.clone()).or_insert(0u128) += balance;
+            account_balances[index].balances.insert(token_name, balance);
+        }
+
+        match opt.sort_by {
+            Some(AccountSortBy::Address) => {
+                account_balances.sort_by_key(|view| view.account.address);
+            }
+            Some(AccountSortBy::Balance) => {
+                let stc = STC_TOKEN_CODE.to_string();
+                account_balances.sort_by(|a, b| {
+                    let a_balance = a.balances.get(&stc).copied().unwrap_or_default();
+                    let b_balance = b.balances.get(&stc).copied().unwrap_or_default();
+                    b_balance.cmp(&a_balance)
+                });
+            }
+            None => {}
+        }
+
+        Ok(AccountListView::Portfolio(PortfolioView {
+            accounts: account_balances,
+            total_balances,
+        }))
     }
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountWithBalanceView {
+    pub account: AccountInfo,
+    /// token code -> balance
+    pub balances: BTreeMap<String, u128>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioView {
+    pub accounts: Vec<AccountWithBalanceView>,
+    /// the total balance of every token across all accounts.
+    pub total_balances: BTreeMap<String, u128>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AccountListView {
+    Accounts(Vec<AccountInfo>),
+    Portfolio(PortfolioView),
+}
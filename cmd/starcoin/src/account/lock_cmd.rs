@@ -6,7 +6,7 @@ use crate::view::StringView;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
@@ -14,7 +14,8 @@ use structopt::StructOpt;
 pub struct LockOpt {
     #[structopt(
         name = "account_address",
-        help = "The wallet account address witch to lock, if absent, lock the default wallet."
+        help = "The wallet account address witch to lock, if absent, lock the default wallet.",
+        parse(try_from_str = parse_address)
     )]
     account_address: Option<AccountAddress>,
 }
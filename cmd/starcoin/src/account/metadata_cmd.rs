@@ -0,0 +1,153 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::tax_export_cmd::get_scaling_factor;
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::{AccountInfo, AccountMetadataUpdate};
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::BTreeMap;
+use structopt::StructOpt;
+
+/// Show or update the notes, tags and transfer confirm threshold of the account in local wallet.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "metadata")]
+pub struct MetadataOpt {
+    #[structopt(
+        name = "account_address",
+        help = "The wallet account address, if absent, use the default wallet.",
+        parse(try_from_str = parse_address)
+    )]
+    account_address: Option<AccountAddress>,
+
+    #[structopt(long = "notes")]
+    /// Set the notes of the account, an empty string clears the notes.
+    notes: Option<String>,
+
+    #[structopt(long = "tag", number_of_values = 1)]
+    /// Add a tag to the account, such as `exchange` or `payroll`, can be specified multiple times.
+    add_tags: Vec<String>,
+
+    #[structopt(long = "remove-tag", number_of_values = 1)]
+    /// Remove a tag from the account, can be specified multiple times.
+    remove_tags: Vec<String>,
+
+    #[structopt(long = "transfer-confirm-threshold")]
+    /// Require typing the amount and the receiver again to confirm a transfer of the token above the amount in cli,
+    /// even if the account is unlocked, 0 clears the threshold. The amount is in the token unit with decimals, such as `1000.5`.
+    transfer_confirm_threshold: Option<String>,
+
+    #[structopt(
+        short = "t",
+        long = "token-code",
+        name = "token-code",
+        requires = "transfer_confirm_threshold"
+    )]
+    /// The token of the `transfer-confirm-threshold`, for example: 0x1::STC::STC, default is STC.
+    token_code: Option<TokenCode>,
+}
+
+/// Parse the decimal token amount to the smallest unit of the token by its scaling factor,
+/// such as 1500000000 for `1.5` STC.
+fn parse_amount(input: &str, scaling_factor: u128) -> Result<u128> {
+    let input = input.trim();
+    let (integer, fraction) = match input.find('.') {
+        Some(pos) => (&input[..pos], &input[pos + 1..]),
+        None => (input, ""),
+    };
+    let decimals = if scaling_factor <= 1 {
+        0
+    } else {
+        (scaling_factor - 1).to_string().len()
+    };
+    let fraction = fraction.trim_end_matches('0');
+    ensure!(
+        fraction.len() <= decimals,
+        "The amount {} has more than {} decimals",
+        input,
+        decimals
+    );
+    let integer = integer.parse::<u128>()?;
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = decimals).parse::<u128>()?
+    };
+    integer
+        .checked_mul(scaling_factor.max(1))
+        .and_then(|amount| amount.checked_add(fraction))
+        .ok_or_else(|| format_err!("The amount {} is too large", input))
+}
+
+pub struct MetadataCommand;
+
+impl CommandAction for MetadataCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = MetadataOpt;
+    type ReturnItem = AccountInfo;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let account = ctx.state().get_account_or_default(opt.account_address)?;
+        if opt.notes.is_none()
+            && opt.add_tags.is_empty()
+            && opt.remove_tags.is_empty()
+            && opt.transfer_confirm_threshold.is_none()
+        {
+            return Ok(account);
+        }
+        let client = ctx.state().client();
+        let mut transfer_confirm_thresholds = BTreeMap::new();
+        if let Some(threshold) = opt.transfer_confirm_threshold.as_ref() {
+            let token_code = opt
+                .token_code
+                .clone()
+                .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+            let scaling_factor = get_scaling_factor(client, &token_code)?;
+            transfer_confirm_thresholds.insert(
+                token_code.to_string(),
+                parse_amount(threshold, scaling_factor)?,
+            );
+        }
+        client.account_update_metadata(
+            account.address,
+            AccountMetadataUpdate {
+                notes: opt.notes.clone(),
+                add_tags: opt.add_tags.clone(),
+                remove_tags: opt.remove_tags.clone(),
+                transfer_confirm_thresholds,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1.5", 1_000_000_000).unwrap(), 1_500_000_000);
+        assert_eq!(
+            parse_amount("1000", 1_000_000_000).unwrap(),
+            1_000_000_000_000
+        );
+        assert_eq!(parse_amount("0.000000001", 1_000_000_000).unwrap(), 1);
+        assert_eq!(parse_amount("2.10", 1000).unwrap(), 2100);
+        assert_eq!(parse_amount("0", 1000).unwrap(), 0);
+        assert_eq!(parse_amount("7", 1).unwrap(), 7);
+        assert!(parse_amount("0.0000000001", 1_000_000_000).is_err());
+        assert!(parse_amount("1.5", 1).is_err());
+        assert!(parse_amount("-1", 1000).is_err());
+        assert!(parse_amount("1,000", 1000).is_err());
+        assert!(parse_amount(&u128::max_value().to_string(), 1000).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The encrypted account handoff to a mobile wallet.
+//!
+//! The mobile wallet starts an import by showing its ephemeral X25519 public key. The cli
+//! generates another ephemeral key and a short pairing code, derives the key of the payload from
+//! the ECDH shared secret and the pairing code, and renders the encrypted payload as a QR. The
+//! pairing code is typed into the mobile wallet, so a scanned QR alone can not be decrypted even
+//! with the mobile key, and the private key never appears in plaintext on the clipboard or disk.
+//!
+//! Both sides derive a verification code from the two public keys. The cli prints it and the
+//! wallet displays it after scanning the QR, the user compares them before typing the pairing
+//! code, so a mobile public key replaced on the way to the cli is detected. The wallet side of the
+//! protocol is described in `developer.starcoin.org/content/cli/mobile_export.md`.
+
+use anyhow::{ensure, format_err, Result};
+use chacha20poly1305::aead::generic_array::GenericArray;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use qrcode::render::unicode;
+use qrcode::QrCode;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starcoin_vm_types::account_address::AccountAddress;
+use std::convert::TryFrom;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+pub const MOBILE_EXPORT_VERSION: u8 = 1;
+const KEY_DERIVATION_DOMAIN: &[u8] = b"STARCOIN::MobileExport::Key";
+const VERIFICATION_DOMAIN: &[u8] = b"STARCOIN::MobileExport::Verification";
+/// The digits of the pairing code.
+const PAIRING_CODE_LEN: usize = 6;
+/// The digits of the verification code.
+const VERIFICATION_CODE_LEN: usize = 6;
+/// The key of a payload is used only once, so the nonce is always zero.
+const NONCE: [u8; 12] = [0u8; 12];
+
+/// The content of the QR scanned by the mobile wallet, the keys and ciphertext are hex encoded.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MobileExportPayload {
+    pub version: u8,
+    pub address: AccountAddress,
+    pub ephemeral_public_key: String,
+    pub ciphertext: String,
+}
+
+pub fn parse_public_key(public_key: &str) -> Result<PublicKey> {
+    let bytes = hex::decode(public_key.trim_start_matches("0x"))?;
+    let bytes = <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format_err!("The X25519 public key should be 32 bytes"))?;
+    Ok(PublicKey::from(bytes))
+}
+
+pub fn generate_pairing_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..PAIRING_CODE_LEN)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10u8)))
+        .collect()
+}
+
+/// The code compared between the cli and the mobile wallet, derived from the mobile public key
+/// and the ephemeral public key of the payload.
+pub fn verification_code(
+    mobile_public_key: &PublicKey,
+    ephemeral_public_key: &PublicKey,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(VERIFICATION_DOMAIN);
+    hasher.update(mobile_public_key.as_bytes());
+    hasher.update(ephemeral_public_key.as_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    format!(
+        "{:0width$}",
+        u64::from_be_bytes(bytes) % 10u64.pow(VERIFICATION_CODE_LEN as u32),
+        width = VERIFICATION_CODE_LEN
+    )
+}
+
+impl MobileExportPayload {
+    /// The verification code of the payload, computed by the wallet with its own public key.
+    pub fn verification_code(&self, mobile_public_key: &PublicKey) -> Result<String> {
+        Ok(verification_code(
+            mobile_public_key,
+            &parse_public_key(&self.ephemeral_public_key)?,
+        ))
+    }
+}
+
+fn cipher(shared_secret: &[u8], pairing_code: &str) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_DOMAIN);
+    hasher.update(shared_secret);
+    hasher.update(pairing_code.as_bytes());
+    ChaCha20Poly1305::new(&hasher.finalize())
+}
+
+/// The address and the ephemeral key are authenticated with the ciphertext.
+fn aad(address: &AccountAddress, ephemeral_public_key: &PublicKey) -> Vec<u8> {
+    let mut aad = address.to_vec();
+    aad.extend_from_slice(ephemeral_public_key.as_bytes());
+    aad
+}
+
+/// Encrypt the encoded private key of the account to the mobile public key.
+pub fn encrypt(
+    address: AccountAddress,
+    private_key: &str,
+    mobile_public_key: &PublicKey,
+    pairing_code: &str,
+) -> Result<MobileExportPayload> {
+    let mut secret = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret);
+    let ephemeral_secret = StaticSecret::from(secret);
+    let ephemeral_public_key = PublicKey::from(&ephemeral_secret);
+    let shared_secret = ephemeral_secret.diffie_hellman(mobile_public_key);
+    let ciphertext = cipher(shared_secret.as_bytes(), pairing_code)
+        .encrypt(
+            GenericArray::from_slice(&NONCE),
+            Payload {
+                msg: private_key.as_bytes(),
+                aad: &aad(&address, &ephemeral_public_key),
+            },
+        )
+        .map_err(|_| format_err!("Encrypt the private key of {} failed", address))?;
+    Ok(MobileExportPayload {
+        version: MOBILE_EXPORT_VERSION,
+        address,
+        ephemeral_public_key: hex::encode(ephemeral_public_key.as_bytes()),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+/// Decrypt the payload by the mobile secret key, as the mobile wallet does.
+pub fn decrypt(
+    payload: &MobileExportPayload,
+    mobile_secret: &StaticSecret,
+    pairing_code: &str,
+) -> Result<String> {
+    ensure!(
+        payload.version == MOBILE_EXPORT_VERSION,
+        "Unsupported mobile export version {}",
+        payload.version
+    );
+    let ephemeral_public_key = parse_public_key(&payload.ephemeral_public_key)?;
+    let shared_secret = mobile_secret.diffie_hellman(&ephemeral_public_key);
+    let plaintext = cipher(shared_secret.as_bytes(), pairing_code)
+        .decrypt(
+            GenericArray::from_slice(&NONCE),
+            Payload {
+                msg: &hex::decode(&payload.ciphertext)?,
+                aad: &aad(&payload.address, &ephemeral_public_key),
+            },
+        )
+        .map_err(|_| format_err!("Decrypt the payload failed, check the pairing code"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+/// Render the payload as a QR of unicode blocks for the terminal.
+pub fn render_qr(payload: &MobileExportPayload) -> Result<String> {
+    let code = QrCode::new(serde_json::to_vec(payload)?)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mobile_export() {
+        let mobile_secret = StaticSecret::from([7u8; 32]);
+        let mobile_public_key =
+            parse_public_key(&hex::encode(PublicKey::from(&mobile_secret).as_bytes())).unwrap();
+        let pairing_code = generate_pairing_code();
+        assert_eq!(pairing_code.len(), PAIRING_CODE_LEN);
+        assert!(pairing_code.chars().all(|c| c.is_ascii_digit()));
+
+        let address = AccountAddress::random();
+        let payload = encrypt(address, "0xprivatekey", &mobile_public_key, &pairing_code).unwrap();
+        assert_eq!(
+            decrypt(&payload, &mobile_secret, &pairing_code).unwrap(),
+            "0xprivatekey"
+        );
+        let wrong_code = if pairing_code == "000000" {
+            "000001"
+        } else {
+            "000000"
+        };
+        assert!(decrypt(&payload, &mobile_secret, wrong_code).is_err());
+        assert!(decrypt(&payload, &StaticSecret::from([8u8; 32]), &pairing_code).is_err());
+        let tampered = MobileExportPayload {
+            address: AccountAddress::random(),
+            ..payload.clone()
+        };
+        assert!(decrypt(&tampered, &mobile_secret, &pairing_code).is_err());
+        assert!(!render_qr(&payload).unwrap().is_empty());
+
+        let code = payload.verification_code(&mobile_public_key).unwrap();
+        assert_eq!(code.len(), VERIFICATION_CODE_LEN);
+        assert!(code.chars().all(|c| c.is_ascii_digit()));
+        // the code of a replaced mobile key does not match the one shown by the wallet.
+        let other_public_key = PublicKey::from(&StaticSecret::from([9u8; 32]));
+        assert_ne!(payload.verification_code(&other_public_key).unwrap(), code);
+    }
+}
@@ -6,38 +6,57 @@ pub use change_password_cmd::*;
 pub use create_cmd::*;
 pub use default_cmd::*;
 pub use derive_account_address_cmd::*;
+pub use derive_cmd::*;
 pub use execute_script_cmd::*;
 pub use execute_script_function_cmd::*;
 pub use export_cmd::*;
+pub use fund_cmd::*;
+pub use generate_mnemonic_cmd::*;
 pub use import_cmd::*;
 pub use list_cmd::*;
 pub use lock_cmd::*;
+pub use metadata_cmd::*;
+pub use restore_cmd::*;
 pub use show_cmd::*;
 pub use sign_cmd::*;
 pub use transfer_cmd::*;
 pub use unlock_cmd::*;
+pub use verify_ceremony_cmd::*;
 pub use verify_sign_cmd::*;
 
 mod accept_token_cmd;
+pub mod backup;
 mod change_password_cmd;
 mod create_cmd;
 mod default_cmd;
 mod derive_account_address_cmd;
+mod derive_cmd;
 mod execute_script_cmd;
 mod execute_script_function_cmd;
 mod export_cmd;
+mod fund_cmd;
 pub mod generate_keypair;
+mod generate_mnemonic_cmd;
 mod import_cmd;
 pub mod import_multisig_cmd;
 pub mod import_readonly_cmd;
 mod list_cmd;
 mod lock_cmd;
+mod metadata_cmd;
+pub mod mobile_export;
+pub mod prove_reserves_cmd;
 pub mod receipt_identifier_cmd;
 pub mod remove_cmd;
+pub mod reserve_attestation;
+mod restore_cmd;
 mod show_cmd;
 mod sign_cmd;
 pub mod sign_multisig_txn_cmd;
 pub mod submit_multisig_txn_cmd;
+pub mod sweep_cmd;
+pub mod tax_export_cmd;
 mod transfer_cmd;
 mod unlock_cmd;
+mod verify_ceremony_cmd;
+pub mod verify_reserves_cmd;
 mod verify_sign_cmd;
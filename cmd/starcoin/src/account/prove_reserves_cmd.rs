@@ -0,0 +1,140 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::reserve_attestation::{
+    attestation_message, balance_access_path, decode_balance, verify_signature, ReserveAccount,
+    ReserveAttestation, ATTESTATION_VERSION,
+};
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// Prove the reserves of the local accounts, each account signs the nonce with the block, and
+/// the balances at the block are proved by the state root. The attestation can be published and
+/// verified by `account verify-reserves`. Only the single key accounts can sign the attestation.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "prove-reserves")]
+pub struct ProveReservesOpt {
+    #[structopt(long = "addresses", parse(from_os_str))]
+    /// the file of the account addresses, one address per line, the lines starting with `#` are ignored.
+    addresses: PathBuf,
+
+    #[structopt(long = "message", name = "nonce")]
+    /// the nonce given by the auditor.
+    nonce: String,
+
+    #[structopt(long = "block-number")]
+    /// the block of the balances, default to the current head.
+    block_number: Option<u64>,
+
+    #[structopt(long = "token-code")]
+    /// the token of the balances, default to STC.
+    token_code: Option<TokenCode>,
+
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    /// write the attestation to the json file.
+    output: Option<PathBuf>,
+}
+
+fn read_addresses(path: &Path) -> Result<Vec<AccountAddress>> {
+    let mut addresses = vec![];
+    for line in fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let address = parse_address(line)?;
+        ensure!(
+            !addresses.contains(&address),
+            "Duplicate address {} in {:?}",
+            address,
+            path
+        );
+        addresses.push(address);
+    }
+    Ok(addresses)
+}
+
+pub struct ProveReservesCommand;
+
+impl CommandAction for ProveReservesCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ProveReservesOpt;
+    type ReturnItem = ReserveAttestation;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let addresses = read_addresses(opt.addresses.as_path())?;
+        ensure!(!addresses.is_empty(), "No address in {:?}", opt.addresses);
+        let client = ctx.state().client();
+        let chain_info = client.chain_info()?;
+        let block_number = opt.block_number.unwrap_or(chain_info.head.number.0);
+        let block = client
+            .chain_get_block_by_number(block_number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", block_number))?;
+        let block_hash = block.header.block_hash;
+        let state_root = block.header.state_root;
+        let message = attestation_message(
+            chain_info.chain_id,
+            opt.nonce.as_str(),
+            block_number,
+            block_hash,
+        )?;
+        let token_code = opt
+            .token_code
+            .clone()
+            .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+
+        let mut total_balance = 0u128;
+        let mut accounts = vec![];
+        for address in addresses {
+            let signature = client
+                .account_sign_message(address, message.clone())?
+                .to_string();
+            // fail early on the accounts can not sign the attestation, such as the rotated keys.
+            verify_signature(address, signature.as_str(), &message)?;
+            let balance_proof = client.state_get_with_proof_by_root(
+                balance_access_path(address, token_code.clone()),
+                state_root,
+            )?;
+            let balance =
+                decode_balance(balance_proof.state.as_ref().map(|state| state.0.as_slice()))?;
+            total_balance = total_balance
+                .checked_add(balance)
+                .ok_or_else(|| format_err!("The total balance overflows"))?;
+            accounts.push(ReserveAccount {
+                address,
+                balance,
+                signature,
+                balance_proof,
+            });
+        }
+        let attestation = ReserveAttestation {
+            version: ATTESTATION_VERSION,
+            chain_id: chain_info.chain_id,
+            nonce: opt.nonce.clone(),
+            block_number,
+            block_hash,
+            state_root,
+            token_code: token_code.to_string(),
+            total_balance,
+            accounts,
+        };
+        attestation.verify()?;
+        if let Some(output) = &opt.output {
+            fs::write(output, serde_json::to_vec_pretty(&attestation)?)?;
+        }
+        Ok(attestation)
+    }
+}
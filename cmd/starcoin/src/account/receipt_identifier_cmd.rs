@@ -25,6 +25,11 @@ pub struct ReceiptIdentifierOpt {
     #[structopt(short = "k")]
     /// When encode address to receipt_identifier, use public_key to generate auth_key
     public_key: Option<String>,
+
+    #[structopt(long = "payment-reference")]
+    /// When encode address to receipt_identifier, encode the payment reference, such as an order id or memo, into a v2 receipt_identifier.
+    /// The reference is put into the deposit event when transfer to the receipt_identifier.
+    payment_reference: Option<String>,
 }
 
 pub struct ReceiptIdentifierCommand;
@@ -40,7 +45,7 @@ impl CommandAction for ReceiptIdentifierCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let opt = ctx.opt();
-        match opt.address_or_receipt {
+        match &opt.address_or_receipt {
             AddressOrReceipt::Address(address) => {
                 let auth_key = opt
                     .public_key
@@ -48,17 +53,28 @@ impl CommandAction for ReceiptIdentifierCommand {
                     .map(|pubkey| AccountPublicKey::from_encoded_string(pubkey.as_str()))
                     .transpose()?
                     .map(|pubkey| pubkey.authentication_key());
-                let receipt_identifier = ReceiptIdentifier::v1(address, auth_key);
+                let receipt_identifier = match opt.payment_reference.as_ref() {
+                    Some(payment_reference) => ReceiptIdentifier::v2(
+                        *address,
+                        auth_key,
+                        Some(payment_reference.as_bytes().to_vec()),
+                    )?,
+                    None => ReceiptIdentifier::v1(*address, auth_key),
+                };
                 Ok(ReceiptIdentifierData {
-                    address,
+                    address: *address,
                     auth_key,
+                    payment_reference: opt.payment_reference.clone(),
                     receipt_identifier,
                 })
             }
             AddressOrReceipt::Receipt(receipt_identifier) => Ok(ReceiptIdentifierData {
                 address: receipt_identifier.address(),
                 auth_key: receipt_identifier.auth_key().cloned(),
-                receipt_identifier,
+                payment_reference: receipt_identifier
+                    .payment_reference()
+                    .map(|reference| String::from_utf8_lossy(reference).to_string()),
+                receipt_identifier: receipt_identifier.clone(),
             }),
         }
     }
@@ -68,5 +84,7 @@ impl CommandAction for ReceiptIdentifierCommand {
 pub struct ReceiptIdentifierData {
     pub address: AccountAddress,
     pub auth_key: Option<AuthenticationKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_reference: Option<String>,
     pub receipt_identifier: ReceiptIdentifier,
 }
@@ -6,7 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_account_api::AccountInfo;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
 /// Remove account from local wallet. This operate do not affect the on chain account.
@@ -18,7 +18,8 @@ pub struct RemoveOpt {
     password: Option<String>,
     #[structopt(
         name = "account_address",
-        help = "The wallet account address which to remove, the default account can not bean removed."
+        help = "The wallet account address which to remove, the default account can not bean removed.",
+        parse(try_from_str = parse_address)
     )]
     account_address: AccountAddress,
 }
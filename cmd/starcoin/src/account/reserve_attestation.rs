@@ -0,0 +1,305 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::{ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::StateWithProofView;
+use starcoin_state_api::{StateProof, StateWithProof};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::transaction::authenticator::{AccountSignature, AuthenticationKey};
+use starcoin_vm_types::account_config::BalanceResource;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub const ATTESTATION_VERSION: u8 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReserveAccount {
+    pub address: AccountAddress,
+    pub balance: u128,
+    /// The signature of the attestation message by the account, in the format of `account sign-message`.
+    pub signature: String,
+    /// The proof of the balance resource against the state root of the block.
+    pub balance_proof: StateWithProofView,
+}
+
+/// The proof of reserves published by an exchange, the listed accounts sign the message with
+/// the nonce and the block, and the balances are proved by the state root of the block.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReserveAttestation {
+    pub version: u8,
+    pub chain_id: u8,
+    /// The nonce given by the auditor, so the attestation can not be prepared in advance.
+    pub nonce: String,
+    pub block_number: u64,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+    pub token_code: String,
+    pub total_balance: u128,
+    pub accounts: Vec<ReserveAccount>,
+}
+
+/// The message signed by the accounts, bind the nonce to the block of the balances.
+pub fn attestation_message(
+    chain_id: u8,
+    nonce: &str,
+    block_number: u64,
+    block_hash: HashValue,
+) -> Result<SigningMessage> {
+    SigningMessage::from_str(
+        format!(
+            "Starcoin proof of reserves, chain: {}, nonce: {}, block: {} {}",
+            chain_id, nonce, block_number, block_hash
+        )
+        .as_str(),
+    )
+}
+
+pub fn balance_access_path(address: AccountAddress, token_code: TokenCode) -> AccessPath {
+    AccessPath::new(address, BalanceResource::access_path_for(token_code.into()))
+}
+
+/// Decode the balance of the balance resource blob, no balance resource means zero.
+pub fn decode_balance(state: Option<&[u8]>) -> Result<u128> {
+    match state {
+        Some(state) => Ok(bcs_ext::from_bytes::<BalanceResource>(state)?.token()),
+        None => Ok(0),
+    }
+}
+
+/// Verify the signature of the message is signed by the key of the address.
+/// Only the single key accounts are supported, a multisig account signs a shard of the signature.
+pub fn verify_signature(
+    address: AccountAddress,
+    signature: &str,
+    message: &SigningMessage,
+) -> Result<()> {
+    let bytes = hex::decode(signature.trim_start_matches("0x"))?;
+    ensure!(
+        bytes.len() == ED25519_PUBLIC_KEY_LENGTH + ED25519_SIGNATURE_LENGTH,
+        "The signature of {} is not signed by a single key account",
+        address
+    );
+    let signature = AccountSignature::try_from(bytes.as_slice())?;
+    let signer = match &signature {
+        AccountSignature::Single(public_key, _) => {
+            AuthenticationKey::ed25519(public_key).derived_address()
+        }
+        AccountSignature::Multi(..) => bail!("Multisig signature is not supported"),
+    };
+    ensure!(
+        signer == address,
+        "The signature of {} is signed by the key of {}, the key may be rotated",
+        address,
+        signer
+    );
+    signature
+        .verify(message)
+        .map_err(|e| format_err!("Invalid signature of {}: {}", address, e))
+}
+
+impl ReserveAttestation {
+    pub fn message(&self) -> Result<SigningMessage> {
+        attestation_message(
+            self.chain_id,
+            self.nonce.as_str(),
+            self.block_number,
+            self.block_hash,
+        )
+    }
+
+    /// Verify the signatures, the balance proofs against the state root and the total balance.
+    /// An account can only be listed once, otherwise its balance is counted more than once.
+    /// The caller should check the block hash and the state root are on the chain.
+    pub fn verify(&self) -> Result<()> {
+        ensure!(
+            self.version == ATTESTATION_VERSION,
+            "Unsupported attestation version {}",
+            self.version
+        );
+        let message = self.message()?;
+        let token_code = TokenCode::from_str(self.token_code.as_str())?;
+        let mut total_balance = 0u128;
+        let mut addresses = HashSet::new();
+        for account in &self.accounts {
+            ensure!(
+                addresses.insert(account.address),
+                "The account {} is listed more than once",
+                account.address
+            );
+            verify_signature(account.address, account.signature.as_str(), &message)?;
+            let proof = &account.balance_proof;
+            let state = proof.state.as_ref().map(|state| state.0.clone());
+            StateWithProof::new(
+                state.clone(),
+                StateProof::new(
+                    proof.account_state.as_ref().map(|state| state.0.clone()),
+                    proof.account_proof.clone(),
+                    proof.account_state_proof.clone(),
+                ),
+            )
+            .verify(
+                self.state_root,
+                balance_access_path(account.address, token_code.clone()),
+            )
+            .map_err(|e| format_err!("Invalid balance proof of {}: {}", account.address, e))?;
+            let balance = decode_balance(state.as_deref())?;
+            ensure!(
+                balance == account.balance,
+                "The balance of {} is {}, but the proved balance is {}",
+                account.address,
+                account.balance,
+                balance
+            );
+            total_balance = total_balance
+                .checked_add(balance)
+                .ok_or_else(|| format_err!("The total balance overflows"))?;
+        }
+        ensure!(
+            total_balance == self.total_balance,
+            "The total balance is {}, but the sum of the balances is {}",
+            self.total_balance,
+            total_balance
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::ed25519::Ed25519PrivateKey;
+    use starcoin_crypto::keygen::KeyGen;
+    use starcoin_crypto::{PrivateKey, SigningKey, ValidCryptoMaterial};
+    use starcoin_rpc_api::types::StrView;
+    use starcoin_state_api::{ChainStateReader, ChainStateWriter};
+    use starcoin_statedb::ChainStateDB;
+    use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+
+    fn sign(private_key: &Ed25519PrivateKey, message: &SigningMessage) -> String {
+        let signature =
+            AccountSignature::Single(private_key.public_key(), private_key.sign(message))
+                .to_bytes();
+        format!("0x{}", hex::encode(signature))
+    }
+
+    /// Build an attestation of the accounts with the balances, the balances are proved by a mock state.
+    fn build_attestation(balances: &[u128]) -> ReserveAttestation {
+        let chain_state = ChainStateDB::mock();
+        let keys: Vec<_> = balances
+            .iter()
+            .map(|balance| {
+                let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+                let address = AuthenticationKey::ed25519(&public_key).derived_address();
+                chain_state
+                    .set(
+                        &balance_access_path(address, STC_TOKEN_CODE.clone()),
+                        bcs_ext::to_bytes(&BalanceResource::new(*balance)).unwrap(),
+                    )
+                    .unwrap();
+                (address, private_key, *balance)
+            })
+            .collect();
+        let state_root = chain_state.commit().unwrap();
+        let block_hash = HashValue::random();
+        let message = attestation_message(1, "nonce", 100, block_hash).unwrap();
+        let accounts = keys
+            .into_iter()
+            .map(|(address, private_key, balance)| ReserveAccount {
+                address,
+                balance,
+                signature: sign(&private_key, &message),
+                balance_proof: chain_state
+                    .get_with_proof(&balance_access_path(address, STC_TOKEN_CODE.clone()))
+                    .unwrap()
+                    .into(),
+            })
+            .collect();
+        ReserveAttestation {
+            version: ATTESTATION_VERSION,
+            chain_id: 1,
+            nonce: "nonce".to_string(),
+            block_number: 100,
+            block_hash,
+            state_root,
+            token_code: STC_TOKEN_CODE.to_string(),
+            total_balance: balances.iter().sum(),
+            accounts,
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let address = AuthenticationKey::ed25519(&public_key).derived_address();
+        let message = attestation_message(1, "nonce", 100, HashValue::random()).unwrap();
+        let signature = AccountSignature::Single(public_key, private_key.sign(&message)).to_bytes();
+        let signature = format!("0x{}", hex::encode(signature));
+        verify_signature(address, signature.as_str(), &message).unwrap();
+
+        let other_message = attestation_message(1, "other", 100, HashValue::random()).unwrap();
+        assert!(verify_signature(address, signature.as_str(), &other_message).is_err());
+        assert!(verify_signature(AccountAddress::random(), signature.as_str(), &message).is_err());
+        assert!(verify_signature(address, "0x00", &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_attestation() {
+        let attestation = build_attestation(&[100, 200]);
+        attestation.verify().unwrap();
+    }
+
+    #[test]
+    fn test_verify_tampered_balance() {
+        // the claimed balance is not the proved balance.
+        let mut attestation = build_attestation(&[100, 200]);
+        attestation.accounts[0].balance = 1000;
+        attestation.total_balance = 1200;
+        assert!(attestation.verify().is_err());
+
+        // the proved state is tampered.
+        let mut attestation = build_attestation(&[100, 200]);
+        attestation.accounts[0].balance = 1000;
+        attestation.accounts[0].balance_proof.state = Some(StrView(
+            bcs_ext::to_bytes(&BalanceResource::new(1000)).unwrap(),
+        ));
+        attestation.total_balance = 1200;
+        assert!(attestation.verify().is_err());
+
+        // the total balance is not the sum of the balances.
+        let mut attestation = build_attestation(&[100, 200]);
+        attestation.total_balance = 1000;
+        assert!(attestation.verify().is_err());
+    }
+
+    #[test]
+    fn test_verify_wrong_state_root() {
+        let mut attestation = build_attestation(&[100, 200]);
+        attestation.state_root = HashValue::random();
+        assert!(attestation.verify().is_err());
+    }
+
+    #[test]
+    fn test_verify_duplicate_accounts() {
+        let mut attestation = build_attestation(&[100]);
+        let account = &attestation.accounts[0];
+        let duplicate = ReserveAccount {
+            address: account.address,
+            balance: account.balance,
+            signature: account.signature.clone(),
+            balance_proof: serde_json::from_value(
+                serde_json::to_value(&account.balance_proof).unwrap(),
+            )
+            .unwrap(),
+        };
+        attestation.accounts.push(duplicate);
+        attestation.total_balance = 200;
+        assert!(attestation.verify().is_err());
+    }
+}
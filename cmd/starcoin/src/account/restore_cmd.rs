@@ -0,0 +1,76 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::backup::target::open_target;
+use crate::account::backup::{backup_password, download_backup, BackupManifest};
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountInfo;
+use structopt::StructOpt;
+
+/// Restore the accounts from mnemonic, derive by path `m/44'/101010'/0'/0'/{index}'`,
+/// index from 0 to count - 1. Or restore the accounts from the keystore backup by `--from-cloud`,
+/// the accounts keep the passwords when they are backed up. The accounts which already exist are kept.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "restore")]
+pub struct RestoreOpt {
+    #[structopt(short = "p", default_value = "")]
+    password: String,
+
+    #[structopt(short = "m", long = "mnemonic", required_unless = "from-cloud")]
+    /// the mnemonic words, split by space.
+    mnemonic: Option<String>,
+
+    #[structopt(short = "c", long = "count", default_value = "1")]
+    /// how many accounts to restore, at most 1000.
+    count: u32,
+
+    #[structopt(long = "from-cloud", name = "from-cloud", conflicts_with = "mnemonic")]
+    /// the backup target written by `account backup sync`, such as `s3://bucket/prefix`.
+    from_cloud: Option<String>,
+
+    #[structopt(short = "b", long = "backup-password")]
+    /// the password to decrypt the backup, default to the env STARCOIN_BACKUP_PASSWORD.
+    backup_password: Option<String>,
+
+    #[structopt(long = "backup")]
+    /// the name of the backup to restore, default to the newest backup.
+    backup: Option<String>,
+}
+
+pub struct RestoreCommand;
+
+impl CommandAction for RestoreCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RestoreOpt;
+    type ReturnItem = Vec<AccountInfo>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        if let Some(from_cloud) = opt.from_cloud.as_ref() {
+            let password = backup_password(opt.backup_password.as_ref())?;
+            let target = open_target(from_cloud.as_str())?;
+            let manifest = BackupManifest::load(target.as_ref())?;
+            let record = manifest.find(opt.backup.as_deref())?;
+            let backup = download_backup(target.as_ref(), record, password.as_str())?;
+            return ctx.state().client().account_import_keystore(backup.entries);
+        }
+        let mnemonic = match opt.mnemonic.as_ref() {
+            Some(mnemonic) => mnemonic,
+            None => bail!("mnemonic or from-cloud is required"),
+        };
+        ensure!(opt.count > 0, "count should be greater than 0");
+        let accounts = ctx.state().client().account_restore(
+            mnemonic.clone(),
+            opt.password.clone(),
+            opt.count,
+        )?;
+        Ok(accounts)
+    }
+}
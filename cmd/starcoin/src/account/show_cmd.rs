@@ -6,9 +6,11 @@ use crate::view::{AccountWithStateView, AddressOrReceipt};
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountInfo;
 use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
+use starcoin_vm_types::token::token_code::TokenCode;
 use std::collections::HashMap;
 use structopt::StructOpt;
 
@@ -38,7 +40,7 @@ impl CommandAction for ShowCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        let account_address = if let Some(address_or_receipt) = opt.address_or_receipt {
+        let account_address = if let Some(address_or_receipt) = opt.address_or_receipt.as_ref() {
             address_or_receipt.address()
         } else {
             let default_account = client
@@ -46,15 +48,20 @@ impl CommandAction for ShowCommand {
                 .ok_or_else(|| format_err!("Default account should exist."))?;
             default_account.address
         };
-        let account = client
-            .account_get(account_address)?
+        let batch_response = client
+            .batch()
+            .account_get(account_address)
+            .account_accepted_tokens(account_address)
+            .send()?;
+        let account = batch_response
+            .get::<Option<AccountInfo>>(0)?
             .ok_or_else(|| format_err!("Account with address {} not exist.", account_address))?;
 
         let chain_state_reader = if let Some(block_id) = opt.block_id {
             let block = client
                 .chain_get_block_by_hash(block_id)?
                 .ok_or_else(|| format_err!("block {} not found", block_id))?;
-            RemoteStateReader::new_with_root(client, block.header.state_root)
+            RemoteStateReader::new_with_root(client, block.header.state_root)?
         } else {
             RemoteStateReader::new(client)?
         };
@@ -63,7 +70,7 @@ impl CommandAction for ShowCommand {
             .get_account_resource(account.address())?
             .map(|res| res.sequence_number());
 
-        let accepted_tokens = client.account_accepted_tokens(account_address)?;
+        let accepted_tokens = batch_response.get::<Vec<TokenCode>>(1)?;
         let mut balances = HashMap::with_capacity(accepted_tokens.len());
         for token in accepted_tokens {
             let token_name = token.name.clone();
@@ -18,7 +18,7 @@ use starcoin_types::transaction::{
     parse_transaction_argument, DryRunTransaction, RawUserTransaction, SignedUserTransaction,
     TransactionArgument,
 };
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
 use starcoin_vm_types::transaction::{ScriptFunction, TransactionPayload};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
@@ -37,7 +37,11 @@ pub struct GenerateMultisigTxnOpt {
     /// mutlisig txn data generated by other participants.
     multisig_txn_file: Option<PathBuf>,
 
-    #[structopt(short = "s", required_unless = "multisig-file")]
+    #[structopt(
+        short = "s",
+        required_unless = "multisig-file",
+        parse(try_from_str = parse_address)
+    )]
     /// sender address of this multisig txn.
     sender: Option<AccountAddress>,
     #[structopt(
@@ -137,7 +141,7 @@ impl CommandAction for GenerateMultisigTxnCommand {
                     opt.max_gas_amount,
                     opt.gas_price,
                     expiration_time,
-                    ctx.state().net().chain_id(),
+                    ctx.state().expected_chain_id(),
                     STC_TOKEN_CODE_STR.to_string(),
                 );
                 (raw_txn, None)
@@ -199,6 +203,7 @@ impl CommandAction for GenerateMultisigTxnCommand {
             }
         }
 
+        ctx.state().ensure_chain_id(raw_txn.chain_id())?;
         let partial_signed_txn = client.account_sign_txn(raw_txn)?;
         let my_signatures = if let TransactionAuthenticator::MultiEd25519 { signature, .. } =
             partial_signed_txn.authenticator()
@@ -43,6 +43,7 @@ impl CommandAction for SubmitMultiSignedTxnCommand {
         let signed_txn: SignedUserTransaction =
             bcs_ext::from_bytes(&std::fs::read(opt.signed_txn_file.as_path())?)?;
 
+        ctx.state().ensure_chain_id(signed_txn.chain_id())?;
         let txn_hash = signed_txn.id();
         client.submit_transaction(signed_txn)?;
 
@@ -0,0 +1,233 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::transfer_cmd::{
+    check_dust, check_transfer_confirm, default_dust_threshold, estimated_transfer_fee,
+};
+use crate::cli_state::CliState;
+use crate::pipe::read_piped_addresses;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_executor::DEFAULT_EXPIRATION_TIME;
+use starcoin_rpc_client::RemoteStateReader;
+use starcoin_state_api::AccountStateReader;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
+use structopt::StructOpt;
+
+/// Transfer all the balance of the token of the accounts to the receiver,
+/// the max gas fee is kept in the account when sweeping STC. The accounts should be unlocked.
+/// The sweeps are checked like `account transfer`, the dust sweep is skipped unless `--allow-dust`,
+/// and the sweep above the confirm threshold of the account requires interactive confirmation.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sweep")]
+pub struct SweepOpt {
+    #[structopt(
+        long = "from",
+        number_of_values = 1,
+        parse(try_from_str = parse_address),
+        required_unless = "from-stdin"
+    )]
+    /// the account to sweep, can be specified multiple times.
+    from: Vec<AccountAddress>,
+
+    #[structopt(long = "from-stdin", name = "from-stdin", conflicts_with = "from")]
+    /// read the accounts to sweep from stdin, such as the output of `account list`.
+    from_stdin: bool,
+
+    #[structopt(long = "to", parse(try_from_str = parse_address))]
+    /// the receiver, it should exist on chain.
+    to: AccountAddress,
+
+    #[structopt(
+        short = "g",
+        long = "max-gas",
+        name = "max-gas-amount",
+        default_value = "200000",
+        help = "max gas to use"
+    )]
+    max_gas_amount: u64,
+
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used"
+    )]
+    gas_price: u64,
+
+    #[structopt(
+        short = "t",
+        long = "token-code",
+        name = "token-code",
+        help = "token's code, for example: 0x1::STC::STC, default is STC"
+    )]
+    token_code: Option<TokenCode>,
+
+    #[structopt(long = "dust-threshold")]
+    /// the min useful amount, the account is skipped if the swept amount is below it.
+    /// default is the estimated transfer fee, 200000 gas at the `gas-price`, for STC, no threshold for other tokens.
+    dust_threshold: Option<u128>,
+
+    #[structopt(long = "allow-dust")]
+    /// only warn instead of skip the account when the swept amount is dust.
+    allow_dust: bool,
+}
+
+/// The sweep result of an account, one item per account in the output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepView {
+    pub address: AccountAddress,
+    pub amount: u128,
+    /// The hash of the submitted txn, `None` if the account is skipped or failed.
+    pub txn_hash: Option<HashValue>,
+    /// The reason of skipping the account, or the error of signing or submitting the txn.
+    pub error: Option<String>,
+}
+
+/// The amount to sweep, keep the max gas fee if the gas is paid by the swept token.
+fn sweep_amount(balance: u128, gas_fee: u128, is_gas_token: bool) -> u128 {
+    if is_gas_token {
+        balance.saturating_sub(gas_fee)
+    } else {
+        balance
+    }
+}
+
+pub struct SweepCommand;
+
+impl CommandAction for SweepCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SweepOpt;
+    type ReturnItem = Vec<SweepView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let addresses = if opt.from_stdin {
+            read_piped_addresses()?
+        } else {
+            opt.from.clone()
+        };
+        ensure!(!addresses.is_empty(), "No account to sweep.");
+        ensure!(
+            !addresses.contains(&opt.to),
+            "The receiver {} is one of the accounts to sweep.",
+            opt.to
+        );
+
+        let client = ctx.state().client();
+        let node_info = client.node_info()?;
+        let chain_state_reader = RemoteStateReader::new(client)?;
+        let account_state_reader = AccountStateReader::new(&chain_state_reader);
+        ensure!(
+            account_state_reader
+                .get_account_resource(&opt.to)?
+                .is_some(),
+            "The receiver {} does not exist on chain.",
+            opt.to
+        );
+        let token_code = opt
+            .token_code
+            .clone()
+            .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+        let gas_fee = (opt.max_gas_amount as u128).saturating_mul(opt.gas_price as u128);
+        let is_gas_token = token_code == *STC_TOKEN_CODE;
+        let fee = if is_gas_token {
+            estimated_transfer_fee(opt.gas_price, opt.max_gas_amount)
+        } else {
+            0
+        };
+        let dust_threshold = opt
+            .dust_threshold
+            .unwrap_or_else(|| default_dust_threshold(opt.gas_price, &token_code));
+
+        let mut views = vec![];
+        for address in addresses {
+            let balance = account_state_reader
+                .get_balance_by_token_code(&address, token_code.clone())?
+                .unwrap_or_default();
+            let amount = sweep_amount(balance, gas_fee, is_gas_token);
+            let mut view = SweepView {
+                address,
+                amount,
+                txn_hash: None,
+                error: None,
+            };
+            if amount == 0 {
+                view.error = Some(format!(
+                    "The balance {} is not enough to sweep, the max gas fee is {}",
+                    balance, gas_fee
+                ));
+                views.push(view);
+                continue;
+            }
+            let result = account_state_reader
+                .get_account_resource(&address)?
+                .ok_or_else(|| format_err!("Can not find account on chain by address: {}", address))
+                .and_then(|account_resource| {
+                    if let Some(warning) = check_dust(amount, balance, fee, dust_threshold) {
+                        if !opt.allow_dust {
+                            bail!(
+                                "Sweep skipped, {}, use --allow-dust to sweep anyway.",
+                                warning
+                            );
+                        }
+                        eprintln!("Warning: {}: {}", address, warning);
+                    }
+                    if let Some(account) = client.account_get(address)? {
+                        check_transfer_confirm(
+                            client,
+                            &account.metadata,
+                            amount,
+                            &token_code,
+                            Some(opt.to),
+                        )?;
+                    }
+                    let raw_txn = starcoin_executor::build_transfer_txn_by_token_type(
+                        address,
+                        opt.to,
+                        None,
+                        account_resource.sequence_number(),
+                        amount,
+                        opt.gas_price,
+                        opt.max_gas_amount,
+                        token_code.clone(),
+                        node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
+                        ctx.state().expected_chain_id(),
+                    );
+                    ctx.state().ensure_chain_id(raw_txn.chain_id())?;
+                    let txn = client.account_sign_txn(raw_txn)?;
+                    let txn_hash = txn.id();
+                    client.submit_transaction(txn)?;
+                    Ok(txn_hash)
+                });
+            match result {
+                Ok(txn_hash) => view.txn_hash = Some(txn_hash),
+                Err(e) => view.error = Some(e.to_string()),
+            }
+            views.push(view);
+        }
+        Ok(views)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_amount() {
+        assert_eq!(sweep_amount(1000, 200, true), 800);
+        assert_eq!(sweep_amount(100, 200, true), 0);
+        assert_eq!(sweep_amount(100, 200, false), 100);
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{
+    AnnotatedMoveValueView, BlockTransactionsView, StrView, TransactionEventView,
+};
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::block::BlockNumber;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::account_config::{
+    core_code_address, AccountResource, DepositEvent, TokenInfo, WithdrawEvent,
+};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::transaction::TransactionPayload;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// The max block range of the `chain.get_events` rpc by default.
+const EVENT_QUERY_RANGE: u64 = 32;
+const TRANSFER_SCRIPTS_MODULE_NAME: &str = "TransferScripts";
+const CSV_HEADER: &str = "Date,Category,Sent Amount,Sent Currency,Received Amount,Received Currency,Fee Amount,Fee Currency,Block Number,TxHash";
+
+/// Export the transactions of an account in a year as a CSV for the tax software.
+/// The transactions are classified as transfer in/out, mining reward, gas expense and contract interaction,
+/// the amounts are in tokens without fiat values, and the dates are in UTC.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "tax-export")]
+pub struct TaxExportOpt {
+    #[structopt(name = "address", parse(try_from_str = parse_address))]
+    /// the account to export.
+    address: AccountAddress,
+
+    #[structopt(long = "year")]
+    /// the calendar year to export, in UTC.
+    year: i32,
+
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    /// the CSV file to write, default to `tax-<address>-<year>.csv` in the current dir.
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxCategory {
+    TransferIn,
+    TransferOut,
+    MiningReward,
+    GasExpense,
+    ContractInteraction,
+}
+
+impl fmt::Display for TaxCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::TransferIn => "transfer_in",
+            Self::TransferOut => "transfer_out",
+            Self::MiningReward => "mining_reward",
+            Self::GasExpense => "gas_expense",
+            Self::ContractInteraction => "contract_interaction",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A classified transaction record, a row of the CSV.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxRecord {
+    /// The block timestamp in milliseconds.
+    pub timestamp: u64,
+    pub block_number: BlockNumber,
+    pub transaction_index: u32,
+    pub transaction_hash: HashValue,
+    pub category: TaxCategory,
+    pub sent: Option<(u128, TokenCode)>,
+    pub received: Option<(u128, TokenCode)>,
+    pub fee: Option<(u128, TokenCode)>,
+}
+
+/// The summary of the export.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TaxExportView {
+    pub address: AccountAddress,
+    pub year: i32,
+    /// The block range of the year, empty if no block is in the year.
+    pub from_block: BlockNumber,
+    pub to_block: Option<BlockNumber>,
+    pub output: PathBuf,
+    pub records: usize,
+    pub categories: BTreeMap<TaxCategory, usize>,
+}
+
+/// A transaction sent by the exported account.
+struct SentTxn {
+    timestamp: u64,
+    block_number: BlockNumber,
+    payload: TransactionPayload,
+    gas_unit_price: u64,
+    gas_token_code: TokenCode,
+}
+
+pub struct TaxExportCommand;
+
+impl CommandAction for TaxExportCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TaxExportOpt;
+    type ReturnItem = TaxExportView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        let address = opt.address;
+        let year_start = Utc
+            .ymd_opt(opt.year, 1, 1)
+            .single()
+            .ok_or_else(|| format_err!("Invalid year {}", opt.year))?
+            .and_hms(0, 0, 0)
+            .timestamp_millis();
+        ensure!(
+            year_start >= 0,
+            "The year {} is before the genesis",
+            opt.year
+        );
+        let year_end = Utc
+            .ymd_opt(opt.year.saturating_add(1), 1, 1)
+            .single()
+            .ok_or_else(|| format_err!("Invalid year {}", opt.year))?
+            .and_hms(0, 0, 0)
+            .timestamp_millis();
+
+        let head = client.chain_info()?.head.number.0;
+        let from_block = first_block_since(client, head, year_start as u64)?;
+        let end_block = first_block_since(client, head, year_end as u64)?;
+        let to_block = end_block.checked_sub(1).filter(|to| *to >= from_block);
+
+        let mut records = vec![];
+        if let Some(to_block) = to_block {
+            let mut sequence_number = match from_block.checked_sub(1) {
+                Some(number) => sequence_number_at(client, address, number)?,
+                None => 0,
+            };
+            let mut from = from_block;
+            while from <= to_block {
+                let to = std::cmp::min(from + EVENT_QUERY_RANGE - 1, to_block);
+                // the sequence number only changes in the blocks which have the txns of the account.
+                let next_sequence_number = sequence_number_at(client, address, to)?;
+                let sent_txns = if next_sequence_number != sequence_number {
+                    get_sent_txns(client, address, from, to)?
+                } else {
+                    HashMap::new()
+                };
+                sequence_number = next_sequence_number;
+                records.extend(classify_range(client, address, from, to, sent_txns)?);
+                from = to + 1;
+            }
+        }
+
+        let output = opt
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("tax-{}-{}.csv", address, opt.year)));
+        let mut scaling_factors = HashMap::new();
+        let mut content = String::from(CSV_HEADER);
+        content.push('\n');
+        for record in &records {
+            content.push_str(to_csv_line(client, &mut scaling_factors, record)?.as_str());
+            content.push('\n');
+        }
+        std::fs::write(output.as_path(), content)?;
+
+        let mut categories = BTreeMap::new();
+        for record in &records {
+            *categories.entry(record.category).or_default() += 1;
+        }
+        Ok(TaxExportView {
+            address,
+            year: opt.year,
+            from_block,
+            to_block,
+            output,
+            records: records.len(),
+            categories,
+        })
+    }
+}
+
+/// The first main chain block at or after the timestamp, `head + 1` if no such block.
+fn first_block_since(client: &RpcClient, head: BlockNumber, timestamp: u64) -> Result<BlockNumber> {
+    let (mut low, mut high) = (0, head.saturating_add(1));
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let block = client
+            .chain_get_block_by_number(middle)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", middle))?;
+        if block.header.timestamp.0 < timestamp {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(low)
+}
+
+fn sequence_number_at(
+    client: &RpcClient,
+    address: AccountAddress,
+    block_number: BlockNumber,
+) -> Result<u64> {
+    let resource =
+        client.state_get_resource_at(address, AccountResource::struct_tag(), block_number)?;
+    Ok(resource
+        .and_then(|resource| {
+            resource
+                .value
+                .into_iter()
+                .find(|(name, _)| name.as_str() == "sequence_number")
+        })
+        .and_then(|(_, value)| match value {
+            AnnotatedMoveValueView::U64(sequence_number) => Some(sequence_number.0),
+            _ => None,
+        })
+        .unwrap_or_default())
+}
+
+/// The txns sent by the account in the blocks, by the txn hash.
+fn get_sent_txns(
+    client: &RpcClient,
+    address: AccountAddress,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> Result<HashMap<HashValue, SentTxn>> {
+    let mut sent_txns = HashMap::new();
+    for number in from..=to {
+        let block = client
+            .chain_get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        let txns = match block.body {
+            BlockTransactionsView::Full(txns) => txns,
+            BlockTransactionsView::Hashes(_) => {
+                return Err(format_err!(
+                    "The transactions of block {} are absent",
+                    number
+                ))
+            }
+        };
+        for txn in txns {
+            if txn.raw_txn.sender != address {
+                continue;
+            }
+            sent_txns.insert(
+                txn.transaction_hash,
+                SentTxn {
+                    timestamp: block.header.timestamp.0,
+                    block_number: number,
+                    payload: bcs_ext::from_bytes(txn.raw_txn.payload.0.as_slice())?,
+                    gas_unit_price: txn.raw_txn.gas_unit_price.0,
+                    gas_token_code: TokenCode::from_str(txn.raw_txn.gas_token_code.as_str())?,
+                },
+            );
+        }
+    }
+    Ok(sent_txns)
+}
+
+fn classify_range(
+    client: &RpcClient,
+    address: AccountAddress,
+    from: BlockNumber,
+    to: BlockNumber,
+    sent_txns: HashMap<HashValue, SentTxn>,
+) -> Result<Vec<TaxRecord>> {
+    let events = client.chain_get_events(EventFilter {
+        from_block: Some(from),
+        to_block: Some(to),
+        event_keys: vec![],
+        addrs: vec![address],
+        type_tags: vec![
+            StrView(DepositEvent::type_tag()),
+            StrView(WithdrawEvent::type_tag()),
+        ],
+        limit: None,
+        replay: None,
+    })?;
+    let mut block_hashes = events
+        .iter()
+        .filter_map(|event| event.block_hash)
+        .collect::<Vec<_>>();
+    block_hashes.dedup();
+    let timestamps = if block_hashes.is_empty() {
+        HashMap::new()
+    } else {
+        client
+            .get_headers(block_hashes)?
+            .into_iter()
+            .map(|header| (header.block_hash, header.timestamp.0))
+            .collect()
+    };
+
+    let mut records = vec![];
+    for event in events {
+        let block_hash = event
+            .block_hash
+            .ok_or_else(|| format_err!("The block hash of event is absent"))?;
+        let timestamp = timestamps
+            .get(&block_hash)
+            .copied()
+            .ok_or_else(|| format_err!("Can not find block header {}", block_hash))?;
+        let sent_txn = event
+            .transaction_hash
+            .and_then(|txn_hash| sent_txns.get(&txn_hash));
+        if let Some(record) = classify_event(&event, timestamp, sent_txn)? {
+            records.push(record);
+        }
+    }
+
+    for (txn_hash, sent_txn) in sent_txns {
+        let txn_info = client
+            .chain_get_transaction_info(txn_hash)?
+            .ok_or_else(|| format_err!("Can not find transaction info {}", txn_hash))?;
+        let fee = u128::from(txn_info.gas_used.0) * u128::from(sent_txn.gas_unit_price);
+        let record = TaxRecord {
+            timestamp: sent_txn.timestamp,
+            block_number: sent_txn.block_number,
+            transaction_index: txn_info.transaction_index,
+            transaction_hash: txn_hash,
+            category: TaxCategory::GasExpense,
+            sent: None,
+            received: None,
+            fee: Some((fee, sent_txn.gas_token_code.clone())),
+        };
+        // the contract call which moves no token of the account is still recorded.
+        if !is_transfer(&sent_txn.payload)
+            && !records
+                .iter()
+                .any(|existing: &TaxRecord| existing.transaction_hash == txn_hash)
+        {
+            records.push(TaxRecord {
+                category: TaxCategory::ContractInteraction,
+                fee: None,
+                ..record.clone()
+            });
+        }
+        if fee > 0 {
+            records.push(record);
+        }
+    }
+    records.sort_by_key(|record| {
+        (
+            record.block_number,
+            record.transaction_index,
+            record.category == TaxCategory::GasExpense,
+        )
+    });
+    Ok(records)
+}
+
+/// Classify a deposit or withdraw event of the account, `sent_txn` is the txn of the event if it is sent by the account.
+fn classify_event(
+    event: &TransactionEventView,
+    timestamp: u64,
+    sent_txn: Option<&SentTxn>,
+) -> Result<Option<TaxRecord>> {
+    let contract_interaction = sent_txn
+        .map(|txn| !is_transfer(&txn.payload))
+        .unwrap_or(false);
+    let (category, sent, received) = if event.type_tag == DepositEvent::type_tag() {
+        let deposit = DepositEvent::try_from_bytes(event.data.0.as_slice())?;
+        let received = Some((deposit.amount(), deposit.token_code().clone()));
+        // the block reward is deposited by the block metadata txn, the first txn of a block.
+        if event.transaction_index == Some(0) {
+            (TaxCategory::MiningReward, None, received)
+        } else if contract_interaction {
+            (TaxCategory::ContractInteraction, None, received)
+        } else {
+            (TaxCategory::TransferIn, None, received)
+        }
+    } else if event.type_tag == WithdrawEvent::type_tag() {
+        let withdraw = WithdrawEvent::try_from_bytes(event.data.0.as_slice())?;
+        let sent = Some((withdraw.amount(), withdraw.token_code().clone()));
+        if contract_interaction {
+            (TaxCategory::ContractInteraction, sent, None)
+        } else {
+            (TaxCategory::TransferOut, sent, None)
+        }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(TaxRecord {
+        timestamp,
+        block_number: event
+            .block_number
+            .map(|number| number.0)
+            .ok_or_else(|| format_err!("The block number of event is absent"))?,
+        transaction_index: event.transaction_index.unwrap_or_default(),
+        transaction_hash: event
+            .transaction_hash
+            .ok_or_else(|| format_err!("The transaction hash of event is absent"))?,
+        category,
+        sent,
+        received,
+        fee: None,
+    }))
+}
+
+fn is_transfer(payload: &TransactionPayload) -> bool {
+    match payload {
+        TransactionPayload::ScriptFunction(script_function) => {
+            let module = script_function.module();
+            module.address() == &core_code_address()
+                && module.name().as_str() == TRANSFER_SCRIPTS_MODULE_NAME
+        }
+        _ => false,
+    }
+}
+
+/// Format the token amount as a decimal by the token's scaling factor, such as `1.5` for 1500000000 nanoSTC.
+pub(crate) fn format_amount(amount: u128, scaling_factor: u128) -> String {
+    if scaling_factor <= 1 {
+        return amount.to_string();
+    }
+    let decimals = (scaling_factor - 1).to_string().len();
+    let fraction = format!("{:0width$}", amount % scaling_factor, width = decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (amount / scaling_factor).to_string()
+    } else {
+        format!("{}.{}", amount / scaling_factor, fraction)
+    }
+}
+
+/// Get the scaling factor of the token from its token info on chain.
+pub(crate) fn get_scaling_factor(client: &RpcClient, token_code: &TokenCode) -> Result<u128> {
+    client
+        .state_get(TokenInfo::resource_path_for(token_code.clone()))?
+        .map(|bytes| TokenInfo::try_from_bytes(bytes.as_slice()))
+        .transpose()?
+        .map(|token_info| token_info.scaling_factor())
+        .ok_or_else(|| format_err!("Can not find token info {}", token_code))
+}
+
+fn to_csv_line(
+    client: &RpcClient,
+    scaling_factors: &mut HashMap<TokenCode, u128>,
+    record: &TaxRecord,
+) -> Result<String> {
+    let mut amount_fields = |amount: &Option<(u128, TokenCode)>| -> Result<[String; 2]> {
+        Ok(match amount {
+            Some((amount, token_code)) => {
+                let scaling_factor = match scaling_factors.get(token_code) {
+                    Some(scaling_factor) => *scaling_factor,
+                    None => {
+                        let scaling_factor = get_scaling_factor(client, token_code)?;
+                        scaling_factors.insert(token_code.clone(), scaling_factor);
+                        scaling_factor
+                    }
+                };
+                // the tokens of the same name in different modules are told apart by the full type.
+                [
+                    format_amount(*amount, scaling_factor),
+                    token_code.to_string(),
+                ]
+            }
+            None => [String::new(), String::new()],
+        })
+    };
+    let [sent_amount, sent_currency] = amount_fields(&record.sent)?;
+    let [received_amount, received_currency] = amount_fields(&record.received)?;
+    let [fee_amount, fee_currency] = amount_fields(&record.fee)?;
+    Ok(format!(
+        "{},{},{},{},{},{},{},{},{},{}",
+        Utc.timestamp_millis(record.timestamp as i64)
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        record.category,
+        sent_amount,
+        sent_currency,
+        received_amount,
+        received_currency,
+        fee_amount,
+        fee_currency,
+        record.block_number,
+        record.transaction_hash,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_types::event::EventKey;
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::ModuleId;
+    use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+    use starcoin_vm_types::transaction::ScriptFunction;
+
+    fn deposit_event(transaction_index: u32) -> TransactionEventView {
+        TransactionEventView {
+            block_hash: Some(HashValue::random()),
+            block_number: Some(StrView(10)),
+            transaction_hash: Some(HashValue::random()),
+            transaction_index: Some(transaction_index),
+            data: StrView(
+                bcs_ext::to_bytes(&DepositEvent::new(100, STC_TOKEN_CODE.clone(), vec![])).unwrap(),
+            ),
+            type_tag: DepositEvent::type_tag(),
+            event_key: EventKey::random(),
+            event_seq_number: StrView(0),
+            payment_reference: None,
+        }
+    }
+
+    fn sent_txn(module_name: &str) -> SentTxn {
+        SentTxn {
+            timestamp: 0,
+            block_number: 10,
+            payload: TransactionPayload::ScriptFunction(ScriptFunction::new(
+                ModuleId::new(core_code_address(), Identifier::new(module_name).unwrap()),
+                Identifier::new("f").unwrap(),
+                vec![],
+                vec![],
+            )),
+            gas_unit_price: 1,
+            gas_token_code: STC_TOKEN_CODE.clone(),
+        }
+    }
+
+    #[test]
+    fn test_classify_event() {
+        let category = |event: &TransactionEventView, sent_txn: Option<&SentTxn>| {
+            classify_event(event, 0, sent_txn)
+                .unwrap()
+                .unwrap()
+                .category
+        };
+        assert_eq!(category(&deposit_event(0), None), TaxCategory::MiningReward);
+        assert_eq!(category(&deposit_event(1), None), TaxCategory::TransferIn);
+        assert_eq!(
+            category(
+                &deposit_event(1),
+                Some(&sent_txn(TRANSFER_SCRIPTS_MODULE_NAME))
+            ),
+            TaxCategory::TransferIn
+        );
+        assert_eq!(
+            category(&deposit_event(1), Some(&sent_txn("SwapScripts"))),
+            TaxCategory::ContractInteraction
+        );
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(1_500_000_000, 1_000_000_000), "1.5");
+        assert_eq!(format_amount(2_000_000_000, 1_000_000_000), "2");
+        assert_eq!(format_amount(1, 1_000_000_000), "0.000000001");
+        assert_eq!(format_amount(7, 1), "7");
+    }
+}
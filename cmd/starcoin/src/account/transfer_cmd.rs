@@ -1,26 +1,36 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account::tax_export_cmd::{format_amount, get_scaling_factor};
 use crate::cli_state::CliState;
 use crate::view::{AddressOrReceipt, ExecuteResultView, ExecutionOutputView};
 use crate::StarcoinOpt;
-use anyhow::{bail, format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use scmd::{CommandAction, ExecContext};
-use starcoin_account_api::AccountPublicKey;
+use starcoin_account_api::{AccountMetadata, AccountPublicKey};
 use starcoin_crypto::ValidCryptoMaterialStringExt;
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
-use starcoin_rpc_client::RemoteStateReader;
+use starcoin_rpc_api::types::TransactionEventView;
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
 use starcoin_state_api::AccountStateReader;
 use starcoin_types::receipt_identifier::ReceiptIdentifier;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::account_config::{DepositEvent, WithdrawEvent};
+use starcoin_vm_types::move_resource::MoveResource;
 use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
 use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
 use structopt::StructOpt;
 
+/// The estimated gas used by a transfer, the default dust threshold is its fee,
+/// a balance below it can not even pay the gas of transferring it out.
+const ESTIMATED_TRANSFER_GAS: u64 = 200000;
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "transfer")]
 pub struct TransferOpt {
-    #[structopt(short = "s")]
+    #[structopt(short = "s", parse(try_from_str = parse_address))]
     /// if `sender` is absent, use default account.
     sender: Option<AccountAddress>,
 
@@ -29,7 +39,7 @@ pub struct TransferOpt {
     receipt: Option<AddressOrReceipt>,
 
     #[structopt(short = "r", required_unless = "receipt")]
-    /// transfer to, accept address (start with 0x) or receipt_identifier (start with stc1)
+    /// transfer to, accept address (start with 0x) or receipt_identifier (start with stc1, or stcr1 with payment reference)
     receiver: Option<AddressOrReceipt>,
 
     #[structopt(short = "k")]
@@ -70,6 +80,189 @@ pub struct TransferOpt {
         help = "blocking wait txn mined"
     )]
     blocking: bool,
+
+    #[structopt(long = "replace")]
+    /// replace the latest pending txn of `sender` in txpool, reuse its sequence number.
+    /// the `gas-price` must be high enough to replace the pending one.
+    replace: bool,
+
+    #[structopt(long = "dust-threshold")]
+    /// the min useful amount, the transfer is blocked if the sent amount or the remaining balance of sender is below it.
+    /// default is the estimated transfer fee, 200000 gas at the `gas-price`, for STC, no threshold for other tokens.
+    dust_threshold: Option<u128>,
+
+    #[structopt(long = "allow-dust")]
+    /// only warn instead of block the transfer when the sent amount or the remaining balance is dust.
+    allow_dust: bool,
+}
+
+impl TransferOpt {
+    /// The estimated gas fee of the transfer.
+    fn estimated_fee(&self) -> u128 {
+        estimated_transfer_fee(self.gas_price, self.max_gas_amount)
+    }
+
+    fn dust_threshold(&self, token_code: &TokenCode) -> u128 {
+        self.dust_threshold
+            .unwrap_or_else(|| default_dust_threshold(self.gas_price, token_code))
+    }
+}
+
+/// The estimated gas fee of a transfer at the gas price.
+pub(crate) fn estimated_transfer_fee(gas_price: u64, max_gas_amount: u64) -> u128 {
+    (gas_price as u128).saturating_mul(max_gas_amount.min(ESTIMATED_TRANSFER_GAS) as u128)
+}
+
+/// The default dust threshold is the estimated transfer fee for STC, no threshold for other tokens.
+pub(crate) fn default_dust_threshold(gas_price: u64, token_code: &TokenCode) -> u128 {
+    if token_code == &*STC_TOKEN_CODE {
+        (gas_price as u128).saturating_mul(ESTIMATED_TRANSFER_GAS as u128)
+    } else {
+        0
+    }
+}
+
+/// Check the sent amount and the remaining balance are not dust.
+/// `fee` is the gas fee paid by the same token, it is 0 if the token is not the gas token.
+/// The sent amount is dust if it is below the threshold or not worth the fee,
+/// zero remaining balance is not dust, the account is cleared.
+pub(crate) fn check_dust(
+    amount: u128,
+    balance: u128,
+    fee: u128,
+    dust_threshold: u128,
+) -> Option<String> {
+    if amount < dust_threshold {
+        return Some(format!(
+            "the sent amount {} is below the dust threshold {}",
+            amount, dust_threshold
+        ));
+    }
+    if amount < fee {
+        return Some(format!(
+            "the sent amount {} is not worth the estimated gas fee {}",
+            amount, fee
+        ));
+    }
+    let remaining = balance.saturating_sub(amount).saturating_sub(fee);
+    if remaining > 0 && remaining < dust_threshold {
+        return Some(format!(
+            "the remaining balance {} is below the dust threshold {}",
+            remaining, dust_threshold
+        ));
+    }
+    None
+}
+
+/// Check the typed amount and receiver are the same as the transfer, the receiver is not typed
+/// if the transfer has no single receiver.
+fn check_confirmation(
+    typed_amount: &str,
+    typed_receiver: Option<&str>,
+    amount: u128,
+    receiver: Option<AccountAddress>,
+) -> bool {
+    typed_amount.parse::<u128>().ok() == Some(amount)
+        && typed_receiver.map(|typed_receiver| parse_address(typed_receiver).ok())
+            == receiver.map(Some)
+}
+
+fn prompt(message: &str) -> Result<String> {
+    eprint!("{}", message);
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// Require typing the amount and the receiver again to confirm a transfer above the threshold of the sender,
+/// the transfer is blocked if the stdin is not a terminal, such as in scripts.
+/// The amounts are shown with the decimals of the token by its `scaling_factor`.
+fn confirm_transfer(
+    amount: u128,
+    token_code: &TokenCode,
+    scaling_factor: u128,
+    receiver: Option<AccountAddress>,
+    threshold: u128,
+) -> Result<()> {
+    let token_amount = |amount: u128| {
+        format!(
+            "{} {}",
+            format_amount(amount, scaling_factor),
+            token_code.name
+        )
+    };
+    ensure!(
+        atty::is(atty::Stream::Stdin),
+        "Transfer blocked, the amount {} is above the confirm threshold {} of the sender, it requires interactive confirmation.",
+        token_amount(amount),
+        token_amount(threshold)
+    );
+    eprintln!(
+        "The transfer of {} ({} in the smallest unit of {}) to {} is above the confirm threshold {} of the sender.",
+        token_amount(amount),
+        amount,
+        token_code,
+        receiver
+            .map(|receiver| receiver.to_string())
+            .unwrap_or_else(|| "the receivers".to_string()),
+        token_amount(threshold)
+    );
+    let typed_amount = prompt("Type the amount in the smallest unit to confirm: ")?;
+    let typed_receiver = match receiver {
+        Some(_) => Some(prompt("Type the receiver address to confirm: ")?),
+        None => None,
+    };
+    ensure!(
+        check_confirmation(&typed_amount, typed_receiver.as_deref(), amount, receiver),
+        "Transfer canceled, the typed amount or receiver mismatch."
+    );
+    Ok(())
+}
+
+/// Require the interactive confirmation if the amount is above the confirm threshold of the token
+/// of the sender, the tokens without threshold are not checked.
+pub(crate) fn check_transfer_confirm(
+    client: &RpcClient,
+    metadata: &AccountMetadata,
+    amount: u128,
+    token_code: &TokenCode,
+    receiver: Option<AccountAddress>,
+) -> Result<()> {
+    if let Some(threshold) = metadata.transfer_confirm_threshold(token_code) {
+        if amount > threshold {
+            let scaling_factor = get_scaling_factor(client, token_code)?;
+            confirm_transfer(amount, token_code, scaling_factor, receiver, threshold)?;
+        }
+    }
+    Ok(())
+}
+
+/// The transfers of the txn output, the amounts withdrawn from the `sender` grouped by the token,
+/// with the receivers of the deposits of the token.
+pub(crate) fn withdrawn_amounts(
+    sender: AccountAddress,
+    events: &[TransactionEventView],
+) -> Result<BTreeMap<TokenCode, (u128, BTreeSet<AccountAddress>)>> {
+    let mut amounts: BTreeMap<TokenCode, (u128, BTreeSet<AccountAddress>)> = BTreeMap::new();
+    for event in events {
+        let address = event.event_key.get_creator_address();
+        if event.type_tag == WithdrawEvent::type_tag() && address == sender {
+            let withdraw = WithdrawEvent::try_from_bytes(&event.data.0)?;
+            let entry = amounts.entry(withdraw.token_code().clone()).or_default();
+            entry.0 = entry.0.saturating_add(withdraw.amount());
+        }
+    }
+    for event in events {
+        let address = event.event_key.get_creator_address();
+        if event.type_tag == DepositEvent::type_tag() && address != sender {
+            let deposit = DepositEvent::try_from_bytes(&event.data.0)?;
+            if let Some(entry) = amounts.get_mut(deposit.token_code()) {
+                entry.1.insert(address);
+            }
+        }
+    }
+    Ok(amounts)
 }
 
 pub struct TransferCommand;
@@ -98,14 +291,14 @@ impl CommandAction for TransferCommand {
 
         let chain_state_reader = RemoteStateReader::new(client)?;
         let account_state_reader = AccountStateReader::new(&chain_state_reader);
-        let receiver = match (opt.receiver, opt.receipt) {
+        let receiver = match (opt.receiver.clone(), opt.receipt.clone()) {
             (Some(address_or_receipt), _) => address_or_receipt,
             (None, Some(address_or_receipt)) => address_or_receipt,
             (None, None) => {
                 bail!("Please set the receiver argument.")
             }
         };
-        let (receiver_address, receiver_auth_key) = match receiver {
+        let (receiver_address, receiver_auth_key, payment_reference) = match receiver {
             AddressOrReceipt::Address(receiver) => {
                 let receiver_exist_on_chain = account_state_reader
                     .get_account_resource(&receiver)?
@@ -129,10 +322,13 @@ impl CommandAction for TransferCommand {
                 };
                 let receiver_auth_key =
                     receiver_public_key.as_ref().map(|k| k.authentication_key());
-                (receiver, receiver_auth_key)
+                (receiver, receiver_auth_key, None)
             }
             AddressOrReceipt::Receipt(receipt_id) => match receipt_id {
-                ReceiptIdentifier::V1(addr, auth_key) => (addr, auth_key),
+                ReceiptIdentifier::V1(addr, auth_key) => (addr, auth_key, None),
+                ReceiptIdentifier::V2(addr, auth_key, payment_reference) => {
+                    (addr, auth_key, payment_reference)
+                }
             },
         };
 
@@ -144,22 +340,81 @@ impl CommandAction for TransferCommand {
                     sender.address()
                 )
             })?;
+        let sequence_number = if opt.replace {
+            let next_sequence_number = client
+                .next_sequence_number_in_txpool(*sender.address())?
+                .ok_or_else(|| {
+                    format_err!(
+                        "Can not find pending txn of {} in txpool to replace",
+                        sender.address()
+                    )
+                })?;
+            next_sequence_number.saturating_sub(1)
+        } else {
+            account_resource.sequence_number()
+        };
         let token_code = opt
             .token_code
             .clone()
             .unwrap_or_else(|| STC_TOKEN_CODE.clone());
-        let raw_txn = starcoin_executor::build_transfer_txn_by_token_type(
-            sender.address,
-            receiver_address,
-            receiver_auth_key,
-            account_resource.sequence_number(),
+        let dust_threshold = opt.dust_threshold(&token_code);
+        let fee = if token_code == *STC_TOKEN_CODE {
+            opt.estimated_fee()
+        } else {
+            0
+        };
+        if dust_threshold > 0 || fee > 0 {
+            let balance = account_state_reader
+                .get_balance_by_token_code(sender.address(), token_code.clone())?
+                .unwrap_or_default();
+            if let Some(warning) = check_dust(opt.amount, balance, fee, dust_threshold) {
+                if !opt.allow_dust {
+                    bail!(
+                        "Transfer blocked, {}, use --allow-dust to transfer anyway.",
+                        warning
+                    );
+                }
+                eprintln!("Warning: {}", warning);
+            }
+        }
+        check_transfer_confirm(
+            client,
+            &sender.metadata,
             opt.amount,
-            opt.gas_price,
-            opt.max_gas_amount,
-            token_code,
-            node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
-            ctx.state().net().chain_id(),
-        );
+            &token_code,
+            Some(receiver_address),
+        )?;
+        let raw_txn = match payment_reference {
+            // put the payment reference into the deposit event, so the receiver can reconcile it.
+            Some(payment_reference) => {
+                starcoin_executor::build_transfer_txn_with_metadata_by_token_type(
+                    sender.address,
+                    receiver_address,
+                    receiver_auth_key,
+                    sequence_number,
+                    opt.amount,
+                    opt.gas_price,
+                    opt.max_gas_amount,
+                    token_code,
+                    payment_reference,
+                    node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
+                    ctx.state().expected_chain_id(),
+                )
+            }
+            None => starcoin_executor::build_transfer_txn_by_token_type(
+                sender.address,
+                receiver_address,
+                receiver_auth_key,
+                sequence_number,
+                opt.amount,
+                opt.gas_price,
+                opt.max_gas_amount,
+                token_code,
+                node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
+                ctx.state().expected_chain_id(),
+            ),
+        };
+        ctx.state().ensure_chain_id(raw_txn.chain_id())?;
         let txn = client.account_sign_txn(raw_txn)?;
         let txn_hash = txn.id();
         client.submit_transaction(txn)?;
@@ -174,3 +429,124 @@ impl CommandAction for TransferCommand {
         Ok(ExecuteResultView::Run(output_view))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_rpc_api::types::StrView;
+    use starcoin_vm_types::event::EventKey;
+
+    #[test]
+    fn test_check_dust() {
+        assert!(check_dust(100, 1000, 0, 200).is_some());
+        assert!(check_dust(900, 1000, 0, 200).is_some());
+        assert!(check_dust(1000, 1000, 0, 200).is_none());
+        assert!(check_dust(500, 1000, 0, 200).is_none());
+        assert!(check_dust(1, 1000, 0, 0).is_none());
+
+        // the sent amount is not worth the fee.
+        assert!(check_dust(50, 1000, 100, 0).is_some());
+        // the fee is paid from the remaining balance.
+        assert!(check_dust(500, 1000, 400, 200).is_some());
+        assert!(check_dust(500, 1000, 200, 200).is_none());
+        assert!(check_dust(900, 1000, 100, 200).is_none());
+    }
+
+    #[test]
+    fn test_check_confirmation() {
+        let receiver = AccountAddress::random();
+        let typed_receiver = receiver.to_string();
+        let typed_receiver = Some(typed_receiver.as_str());
+        assert!(check_confirmation(
+            "1000",
+            typed_receiver,
+            1000,
+            Some(receiver)
+        ));
+        assert!(!check_confirmation(
+            "100",
+            typed_receiver,
+            1000,
+            Some(receiver)
+        ));
+        assert!(!check_confirmation(
+            "1,000",
+            typed_receiver,
+            1000,
+            Some(receiver)
+        ));
+        assert!(!check_confirmation(
+            "1000",
+            Some(AccountAddress::random().to_string().as_str()),
+            1000,
+            Some(receiver)
+        ));
+        assert!(!check_confirmation(
+            "1000",
+            Some("0x1"),
+            1000,
+            Some(receiver)
+        ));
+        // no single receiver, only the amount is typed.
+        assert!(check_confirmation("1000", None, 1000, None));
+        assert!(!check_confirmation("100", None, 1000, None));
+    }
+
+    fn event<E: MoveResource + serde::Serialize>(
+        address: AccountAddress,
+        event: &E,
+    ) -> TransactionEventView {
+        TransactionEventView {
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            data: StrView(bcs_ext::to_bytes(event).unwrap()),
+            type_tag: E::type_tag(),
+            event_key: EventKey::new_from_address(&address, 0),
+            event_seq_number: StrView(0),
+            payment_reference: None,
+        }
+    }
+
+    #[test]
+    fn test_withdrawn_amounts() {
+        let sender = AccountAddress::random();
+        let receiver1 = AccountAddress::random();
+        let receiver2 = AccountAddress::random();
+        let other_token: TokenCode = "0x1::DummyToken::DummyToken".parse().unwrap();
+        let events = vec![
+            event(
+                sender,
+                &WithdrawEvent::new(100, STC_TOKEN_CODE.clone(), vec![]),
+            ),
+            event(
+                receiver1,
+                &DepositEvent::new(100, STC_TOKEN_CODE.clone(), vec![]),
+            ),
+            event(
+                sender,
+                &WithdrawEvent::new(200, STC_TOKEN_CODE.clone(), vec![]),
+            ),
+            event(
+                receiver2,
+                &DepositEvent::new(200, STC_TOKEN_CODE.clone(), vec![]),
+            ),
+            event(sender, &WithdrawEvent::new(5, other_token.clone(), vec![])),
+            event(sender, &DepositEvent::new(5, other_token.clone(), vec![])),
+            // the withdrawal of another account is not counted.
+            event(
+                receiver1,
+                &WithdrawEvent::new(1000, other_token.clone(), vec![]),
+            ),
+        ];
+        let amounts = withdrawn_amounts(sender, &events).unwrap();
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(
+            amounts.get(&*STC_TOKEN_CODE).unwrap(),
+            &(300, vec![receiver1, receiver2].into_iter().collect())
+        );
+        // the deposit back to the sender is not a receiver.
+        assert_eq!(amounts.get(&other_token).unwrap(), &(5, BTreeSet::new()));
+    }
+}
@@ -6,7 +6,9 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_account_api::AccountInfo;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::token::token_value::TokenValue;
 use std::time::Duration;
 use structopt::StructOpt;
 
@@ -17,17 +19,34 @@ pub struct UnlockOpt {
     password: String,
     #[structopt(
         short = "d",
+        long = "duration",
         help = "keep account unlock for how long(in seconds) from now",
         default_value = "300"
     )]
     duration: u32,
+    #[structopt(
+        long = "max-amount",
+        help = "the max amount the txns signed before the account locked can spend, include the gas fee, such as 1000STC, 1.5STC or 100000nanoSTC. The txns whose spend amount can not be known before executing, such as scripts and the script functions other than STC transfers, are rejected unless the node enables `--vault-allow-unknown-spend`",
+        parse(try_from_str = parse_stc_amount)
+    )]
+    max_amount: Option<TokenValue<STCUnit>>,
     #[structopt(
         name = "account_address",
-        help = "The wallet account address witch to unlock, if absent, unlock the default wallet."
+        help = "The wallet account address witch to unlock, if absent, unlock the default wallet.",
+        parse(try_from_str = parse_address)
     )]
     account_address: Option<AccountAddress>,
 }
 
+/// Parse the STC amount with unit, such as `1000STC` or `1000 STC`, the default unit is STC.
+fn parse_stc_amount(s: &str) -> Result<TokenValue<STCUnit>> {
+    let s = s.trim();
+    match s.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(index) => STCUnit::parse(format!("{} {}", s[..index].trim(), &s[index..]).as_str()),
+        None => STCUnit::parse(s),
+    }
+}
+
 pub struct UnlockCommand;
 
 impl CommandAction for UnlockCommand {
@@ -49,7 +68,15 @@ impl CommandAction for UnlockCommand {
         };
 
         let duration = Duration::from_secs(opt.duration as u64);
-        let account = client.account_unlock(account_address, opt.password.clone(), duration)?;
+        let account = match opt.max_amount.as_ref() {
+            Some(max_amount) => client.account_unlock_with_limit(
+                account_address,
+                opt.password.clone(),
+                duration,
+                max_amount.scaling(),
+            )?,
+            None => client.account_unlock(account_address, opt.password.clone(), duration)?,
+        };
         Ok(account)
     }
 }
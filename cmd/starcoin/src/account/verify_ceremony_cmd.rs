@@ -0,0 +1,247 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::sign_message::SigningMessage;
+use starcoin_vm_types::transaction::authenticator::{
+    AccountPrivateKey, AccountPublicKey, AccountSignature,
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use structopt::StructOpt;
+
+pub const KEY_CEREMONY_VERSION: u8 = 1;
+/// The prefix of the signed message of the attestation, so it can not be replayed as other messages.
+const KEY_CEREMONY_MESSAGE_PREFIX: &str = "STARCOIN::KeyCeremonyAttestation::";
+/// The files read for the machine fingerprint, missing files are skipped.
+const MACHINE_ID_FILES: &[&str] = &[
+    "/etc/machine-id",
+    "/var/lib/dbus/machine-id",
+    "/etc/hostname",
+];
+
+/// Where the randomness of the generated key comes from.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum EntropySource {
+    /// The random number generator of the operating system.
+    OsRng,
+    /// The seed given by the user, only the sha3 hash of it is recorded.
+    UserSeed { seed_hash: HashValue },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MachineFingerprint {
+    pub os: String,
+    pub arch: String,
+    /// The sha3 hash of the machine id and the host name, the raw values are not recorded.
+    pub fingerprint: HashValue,
+}
+
+impl MachineFingerprint {
+    pub fn current() -> Self {
+        let mut data = vec![];
+        for file in MACHINE_ID_FILES {
+            if let Ok(content) = std::fs::read(file) {
+                data.extend(content);
+            }
+        }
+        for var in &["HOSTNAME", "COMPUTERNAME"] {
+            if let Ok(value) = std::env::var(var) {
+                data.extend(value.into_bytes());
+            }
+        }
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            fingerprint: HashValue::sha3_256_of(data.as_slice()),
+        }
+    }
+}
+
+/// The facts of a key generation, signed by the generated key.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyCeremonyAttestation {
+    pub version: u8,
+    pub entropy_sources: Vec<EntropySource>,
+    pub machine: MachineFingerprint,
+    pub address: AccountAddress,
+    pub public_key: AccountPublicKey,
+    /// The generation time in seconds.
+    pub timestamp: u64,
+    /// The version of the cli which generates the key.
+    pub tool_version: String,
+}
+
+impl KeyCeremonyAttestation {
+    pub fn new(entropy_sources: Vec<EntropySource>, public_key: AccountPublicKey) -> Result<Self> {
+        Ok(Self {
+            version: KEY_CEREMONY_VERSION,
+            entropy_sources,
+            machine: MachineFingerprint::current(),
+            address: public_key.derived_address(),
+            public_key,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    fn signing_message(&self) -> Result<SigningMessage> {
+        SigningMessage::from_str(
+            format!(
+                "{}{}",
+                KEY_CEREMONY_MESSAGE_PREFIX,
+                hex::encode(bcs_ext::to_bytes(self)?)
+            )
+            .as_str(),
+        )
+    }
+}
+
+/// The attestation bundle written alongside the key by `account generate-keypair --ceremony`.
+///
+/// The bundle is self-signed by the generated key, it only proves the holder of the key made the
+/// statement and it is not tampered since. The facts in it are reported by the generating machine,
+/// so it proves nothing to a third party about how the key was generated, the audit should rely on
+/// the procedure witnessed during the ceremony, and keep the bundle as the record of it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyCeremonyBundle {
+    pub attestation: KeyCeremonyAttestation,
+    pub signature: AccountSignature,
+}
+
+impl KeyCeremonyBundle {
+    pub fn sign(
+        attestation: KeyCeremonyAttestation,
+        private_key: &AccountPrivateKey,
+    ) -> Result<Self> {
+        let signature = private_key.sign_message(attestation.signing_message()?);
+        Ok(Self {
+            attestation,
+            signature,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        ensure!(
+            !path.exists(),
+            "The ceremony file {} already exists",
+            path.display()
+        );
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check the attestation is signed by the key it attests, and it is not tampered.
+    /// This does not check the facts in the attestation, see `KeyCeremonyBundle`.
+    pub fn verify(&self) -> Result<()> {
+        let attestation = &self.attestation;
+        ensure!(
+            attestation.version == KEY_CEREMONY_VERSION,
+            "Unsupported key ceremony version {}",
+            attestation.version
+        );
+        ensure!(
+            attestation.address == attestation.public_key.derived_address(),
+            "The address {} is not derived from the public key",
+            attestation.address
+        );
+        let signer = match &self.signature {
+            AccountSignature::Single(public_key, _) => AccountPublicKey::single(public_key.clone()),
+            AccountSignature::Multi(_, _) => bail!("The ceremony should be signed by a single key"),
+        };
+        ensure!(
+            signer == attestation.public_key,
+            "The attestation is not signed by the attested key"
+        );
+        self.signature.verify(&attestation.signing_message()?)
+    }
+}
+
+/// Verify the attestation bundle of a key ceremony is signed by the attested key and not tampered.
+/// The bundle is self-signed, so the facts in it are only as trustworthy as the machine which
+/// generated the key, it is a record of the ceremony, not a proof to a third party.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "verify-ceremony")]
+pub struct VerifyCeremonyOpt {
+    #[structopt(name = "ceremony-file", parse(from_os_str))]
+    /// The attestation bundle file written by `account generate-keypair --ceremony`.
+    ceremony_file: PathBuf,
+
+    #[structopt(long = "address", short = "a")]
+    /// The expected account address of the attested key.
+    address: Option<AccountAddress>,
+}
+
+pub struct VerifyCeremonyCommand;
+
+impl CommandAction for VerifyCeremonyCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = VerifyCeremonyOpt;
+    type ReturnItem = KeyCeremonyAttestation;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let bundle = KeyCeremonyBundle::load(opt.ceremony_file.as_path())?;
+        bundle.verify()?;
+        if let Some(address) = opt.address {
+            ensure!(
+                bundle.attestation.address == address,
+                "The ceremony attests the key of {}, but expect {}",
+                bundle.attestation.address,
+                address
+            );
+        }
+        Ok(bundle.attestation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::keygen::KeyGen;
+
+    #[test]
+    fn test_key_ceremony_bundle() {
+        let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let attestation = KeyCeremonyAttestation::new(
+            vec![EntropySource::OsRng],
+            AccountPublicKey::single(public_key),
+        )
+        .unwrap();
+        let private_key = AccountPrivateKey::Single(private_key);
+        let bundle = KeyCeremonyBundle::sign(attestation, &private_key).unwrap();
+        bundle.verify().unwrap();
+
+        let temp_path = starcoin_config::temp_path();
+        let path = temp_path.path().join("ceremony.json");
+        bundle.save(path.as_path()).unwrap();
+        assert_eq!(KeyCeremonyBundle::load(path.as_path()).unwrap(), bundle);
+        assert!(bundle.save(path.as_path()).is_err());
+
+        let mut tampered = bundle.clone();
+        tampered.attestation.timestamp += 1;
+        assert!(tampered.verify().is_err());
+
+        // the attestation should be signed by the attested key.
+        let (other_private_key, _) = KeyGen::from_os_rng().generate_keypair();
+        let mut forged = bundle;
+        forged.signature = AccountPrivateKey::Single(other_private_key)
+            .sign_message(forged.attestation.signing_message().unwrap());
+        assert!(forged.verify().is_err());
+    }
+}
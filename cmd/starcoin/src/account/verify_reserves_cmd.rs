@@ -0,0 +1,79 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::reserve_attestation::ReserveAttestation;
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
+use starcoin_crypto::HashValue;
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Verify an attestation of `account prove-reserves`, the signatures and the balance proofs are
+/// verified, and the block of the attestation is checked against the chain of the connected node.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "verify-reserves")]
+pub struct VerifyReservesOpt {
+    #[structopt(name = "attestation", parse(from_os_str))]
+    /// the json file of the attestation.
+    attestation: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReservesView {
+    pub nonce: String,
+    pub block_number: u64,
+    pub block_hash: HashValue,
+    pub token_code: String,
+    pub total_balance: u128,
+    pub accounts: usize,
+}
+
+pub struct VerifyReservesCommand;
+
+impl CommandAction for VerifyReservesCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = VerifyReservesOpt;
+    type ReturnItem = ReservesView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let attestation: ReserveAttestation =
+            serde_json::from_slice(&fs::read(opt.attestation.as_path())?)?;
+        attestation.verify()?;
+        let client = ctx.state().client();
+        let chain_id = client.chain_info()?.chain_id;
+        ensure!(
+            chain_id == attestation.chain_id,
+            "The attestation is of chain {}, but the node is of chain {}",
+            attestation.chain_id,
+            chain_id
+        );
+        let block = client
+            .chain_get_block_by_number(attestation.block_number)?
+            .ok_or_else(|| {
+                format_err!("Can not find block by number {}", attestation.block_number)
+            })?;
+        ensure!(
+            block.header.block_hash == attestation.block_hash
+                && block.header.state_root == attestation.state_root,
+            "The block {} of the attestation is not on the chain",
+            attestation.block_number
+        );
+        Ok(ReservesView {
+            nonce: attestation.nonce,
+            block_number: attestation.block_number,
+            block_hash: attestation.block_hash,
+            token_code: attestation.token_code,
+            total_balance: attestation.total_balance,
+            accounts: attestation.accounts.len(),
+        })
+    }
+}
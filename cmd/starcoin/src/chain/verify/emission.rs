@@ -0,0 +1,285 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{StrView, TotalSupplyView, TransactionEventView};
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::block::BlockNumber;
+use starcoin_vm_types::account_config::{BlockRewardEvent, STC_TOKEN_CODE};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_resource::{Treasury, TreasuryDepositEvent, TreasuryWithdrawEvent};
+use structopt::StructOpt;
+
+/// The max block range of the `chain.get_events` rpc by default.
+const EVENT_QUERY_RANGE: u64 = 32;
+
+/// Audit the STC emission from the genesis to a block, the circulating supply is the total supply
+/// minus the treasury balance, it should equal the genesis allocation plus the block rewards
+/// and the other treasury withdrawals, minus the treasury deposits.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "emission")]
+pub struct EmissionOpt {
+    /// The block number to audit to, default to the head block.
+    #[structopt(name = "number", long, short = "n")]
+    block_number: Option<BlockNumber>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EmissionAuditView {
+    pub block_number: BlockNumber,
+    /// The circulating supply at the genesis.
+    pub genesis_allocation: u128,
+    pub cumulative_block_rewards: u128,
+    /// The treasury withdrawals by the block metadata txns, which pay the block rewards.
+    pub treasury_reward_withdrawals: u128,
+    pub treasury_other_withdrawals: u128,
+    pub treasury_deposits: u128,
+    pub expected_circulating_supply: u128,
+    pub circulating_supply: u128,
+    pub total_supply: u128,
+    pub treasury_balance: Option<u128>,
+    /// Empty if the audit passed.
+    pub discrepancies: Vec<String>,
+}
+
+/// The STC amounts summed from the events of the blocks.
+#[derive(Clone, Debug, Default)]
+struct EmissionEvents {
+    cumulative_block_rewards: u128,
+    treasury_reward_withdrawals: u128,
+    treasury_other_withdrawals: u128,
+    treasury_deposits: u128,
+}
+
+pub struct VerifyEmissionCommand;
+
+impl CommandAction for VerifyEmissionCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = EmissionOpt;
+    type ReturnItem = EmissionAuditView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let block_number = match ctx.opt().block_number {
+            Some(number) => number,
+            None => client.chain_info()?.head.number.0,
+        };
+
+        let genesis = client.chain_total_supply_at(0)?;
+        let current = client.chain_total_supply_at(block_number)?;
+
+        let treasury =
+            match client.state_get(Treasury::resource_path_for(STC_TOKEN_CODE.clone()))? {
+                Some(bytes) => Some(bcs_ext::from_bytes::<Treasury>(bytes.as_slice())?),
+                None => None,
+            };
+
+        let mut events = EmissionEvents::default();
+        let mut from = 1;
+        while from <= block_number {
+            let to = std::cmp::min(from + EVENT_QUERY_RANGE - 1, block_number);
+            for event in get_events(client, from, to, |filter| {
+                filter.type_tags = vec![StrView(BlockRewardEvent::type_tag())];
+            })? {
+                events.cumulative_block_rewards = events
+                    .cumulative_block_rewards
+                    .saturating_add(BlockRewardEvent::try_from_bytes(&event.data.0)?.block_reward);
+            }
+            if let Some(treasury) = treasury.as_ref() {
+                let withdraw_key = *treasury.withdraw_events.key();
+                let deposit_key = *treasury.deposit_events.key();
+                for event in get_events(client, from, to, |filter| {
+                    filter.event_keys = vec![withdraw_key, deposit_key];
+                })? {
+                    if event.event_key == withdraw_key {
+                        let amount =
+                            bcs_ext::from_bytes::<TreasuryWithdrawEvent>(&event.data.0)?.amount;
+                        // the block reward is withdrawn by the block metadata txn, the first txn of a block.
+                        if event.transaction_index == Some(0) {
+                            events.treasury_reward_withdrawals =
+                                events.treasury_reward_withdrawals.saturating_add(amount);
+                        } else {
+                            events.treasury_other_withdrawals =
+                                events.treasury_other_withdrawals.saturating_add(amount);
+                        }
+                    } else {
+                        let amount =
+                            bcs_ext::from_bytes::<TreasuryDepositEvent>(&event.data.0)?.amount;
+                        events.treasury_deposits = events.treasury_deposits.saturating_add(amount);
+                    }
+                }
+            }
+            from = to + 1;
+        }
+
+        Ok(audit_emission(block_number, &genesis, &current, events))
+    }
+}
+
+/// Compare the supply at the block with the emission from the events, the mismatches are
+/// reported as discrepancies instead of errors, so the audit never underflows on a broken chain.
+fn audit_emission(
+    block_number: BlockNumber,
+    genesis: &TotalSupplyView,
+    current: &TotalSupplyView,
+    events: EmissionEvents,
+) -> EmissionAuditView {
+    let mut discrepancies = vec![];
+    let genesis_allocation = circulating_supply_of(genesis, &mut discrepancies);
+    let circulating_supply = circulating_supply_of(current, &mut discrepancies);
+    let treasury_balance = current.treasury_balance.as_ref().map(|b| b.0);
+
+    if treasury_balance.is_some()
+        && events.treasury_reward_withdrawals != events.cumulative_block_rewards
+    {
+        discrepancies.push(format!(
+            "The treasury reward withdrawals {} mismatch the block rewards {}",
+            events.treasury_reward_withdrawals, events.cumulative_block_rewards
+        ));
+    }
+    let emitted = genesis_allocation
+        .saturating_add(events.cumulative_block_rewards)
+        .saturating_add(events.treasury_other_withdrawals);
+    let expected_circulating_supply = match emitted.checked_sub(events.treasury_deposits) {
+        Some(expected) => expected,
+        None => {
+            discrepancies.push(format!(
+                "The treasury deposits {} exceed the emission {}",
+                events.treasury_deposits, emitted
+            ));
+            0
+        }
+    };
+    if circulating_supply != expected_circulating_supply {
+        discrepancies.push(format!(
+            "The circulating supply {} mismatch the expected {}",
+            circulating_supply, expected_circulating_supply
+        ));
+    }
+
+    EmissionAuditView {
+        block_number,
+        genesis_allocation,
+        cumulative_block_rewards: events.cumulative_block_rewards,
+        treasury_reward_withdrawals: events.treasury_reward_withdrawals,
+        treasury_other_withdrawals: events.treasury_other_withdrawals,
+        treasury_deposits: events.treasury_deposits,
+        expected_circulating_supply,
+        circulating_supply,
+        total_supply: current.total_supply.0,
+        treasury_balance,
+        discrepancies,
+    }
+}
+
+/// The total supply minus the treasury balance, the treasury balance exceeding the total supply
+/// is a discrepancy, and the circulating supply is 0 then.
+fn circulating_supply_of(supply: &TotalSupplyView, discrepancies: &mut Vec<String>) -> u128 {
+    let treasury_balance = supply
+        .treasury_balance
+        .as_ref()
+        .map(|b| b.0)
+        .unwrap_or_default();
+    match supply.total_supply.0.checked_sub(treasury_balance) {
+        Some(circulating_supply) => circulating_supply,
+        None => {
+            discrepancies.push(format!(
+                "The treasury balance {} exceeds the total supply {} at block {}",
+                treasury_balance, supply.total_supply.0, supply.block_number.0
+            ));
+            0
+        }
+    }
+}
+
+fn get_events<F>(
+    client: &RpcClient,
+    from: BlockNumber,
+    to: BlockNumber,
+    with: F,
+) -> Result<Vec<TransactionEventView>>
+where
+    F: FnOnce(&mut EventFilter),
+{
+    let mut filter = EventFilter {
+        from_block: Some(from),
+        to_block: Some(to),
+        event_keys: vec![],
+        addrs: vec![],
+        type_tags: vec![],
+        limit: None,
+        replay: None,
+    };
+    with(&mut filter);
+    client.chain_get_events(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supply(block_number: BlockNumber, total: u128, treasury: Option<u128>) -> TotalSupplyView {
+        TotalSupplyView {
+            block_number: StrView(block_number),
+            total_supply: StrView(total),
+            treasury_balance: treasury.map(StrView),
+        }
+    }
+
+    #[test]
+    fn test_audit_emission() {
+        let genesis = supply(0, 1000, Some(900));
+        let current = supply(10, 1000, Some(830));
+        let events = EmissionEvents {
+            cumulative_block_rewards: 50,
+            treasury_reward_withdrawals: 50,
+            treasury_other_withdrawals: 30,
+            treasury_deposits: 10,
+        };
+        let audit = audit_emission(10, &genesis, &current, events.clone());
+        assert_eq!(audit.genesis_allocation, 100);
+        assert_eq!(audit.circulating_supply, 170);
+        assert_eq!(audit.expected_circulating_supply, 170);
+        assert!(audit.discrepancies.is_empty(), "{:?}", audit.discrepancies);
+
+        let audit = audit_emission(10, &genesis, &supply(10, 1000, Some(800)), events);
+        assert_eq!(audit.discrepancies.len(), 1);
+    }
+
+    #[test]
+    fn test_audit_emission_underflow() {
+        // the treasury balance exceeds the total supply.
+        let audit = audit_emission(
+            10,
+            &supply(0, 1000, Some(1001)),
+            &supply(10, 1000, Some(2000)),
+            EmissionEvents::default(),
+        );
+        assert_eq!(audit.genesis_allocation, 0);
+        assert_eq!(audit.circulating_supply, 0);
+        assert_eq!(audit.discrepancies.len(), 2);
+
+        // the treasury deposits exceed the emission.
+        let audit = audit_emission(
+            10,
+            &supply(0, 1000, Some(900)),
+            &supply(10, 1000, Some(900)),
+            EmissionEvents {
+                treasury_deposits: 101,
+                ..Default::default()
+            },
+        );
+        assert_eq!(audit.expected_circulating_supply, 0);
+        assert_eq!(audit.discrepancies.len(), 2);
+        assert!(audit.discrepancies[0].contains("exceed the emission"));
+    }
+}
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod block;
+mod emission;
 mod epoch_info;
 mod node;
 
 pub use block::*;
+pub use emission::*;
 pub use epoch_info::*;
 pub use node::*;
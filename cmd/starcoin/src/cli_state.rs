@@ -1,23 +1,30 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use starcoin_account_api::AccountInfo;
 use starcoin_config::{ChainNetworkID, DataDirPath};
 use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
 use starcoin_node::NodeHandle;
 use starcoin_rpc_api::types::TransactionInfoView;
 use starcoin_rpc_client::chain_watcher::ThinHeadBlock;
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::genesis_config::ChainId;
 use starcoin_vm_types::account_config::association_address;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 static HISTORY_FILE_NAME: &str = "history";
+static TEMP_DIR_NAME: &str = "tmp";
+static GENESIS_HASH_FILE_NAME: &str = "genesis_hash";
+static ARCHIVE_DIR_NAME: &str = "archive";
 
 pub struct CliState {
     net: ChainNetworkID,
+    /// The network expected by the user with `--net` option, may be different with the connected node's network.
+    expected_net: Option<ChainNetworkID>,
     client: Arc<RpcClient>,
     watch_timeout: Duration,
     node_handle: Option<NodeHandle>,
@@ -42,7 +49,7 @@ impl CliState {
             std::fs::create_dir_all(data_dir.as_path())
                 .unwrap_or_else(|e| panic!("Create cli data dir {:?} fail, err:{:?}", data_dir, e))
         }
-        let temp_dir = data_dir.join("tmp");
+        let temp_dir = data_dir.join(TEMP_DIR_NAME);
         if !temp_dir.exists() {
             std::fs::create_dir_all(temp_dir.as_path())
                 .unwrap_or_else(|e| panic!("Create cli temp dir {:?} fail, err:{:?}", temp_dir, e))
@@ -51,6 +58,7 @@ impl CliState {
 
         Self {
             net,
+            expected_net: None,
             client,
             watch_timeout: watch_timeout.unwrap_or(Self::DEFAULT_WATCH_TIMEOUT),
             node_handle,
@@ -59,14 +67,63 @@ impl CliState {
         }
     }
 
+    pub fn with_expected_net(mut self, expected_net: Option<ChainNetworkID>) -> Self {
+        self.expected_net = expected_net;
+        self
+    }
+
     pub fn net(&self) -> &ChainNetworkID {
         &self.net
     }
 
+    pub fn expected_net(&self) -> Option<&ChainNetworkID> {
+        self.expected_net.as_ref()
+    }
+
+    /// The chain id of the network expected by the user with `--net` option,
+    /// or the connected node's network if the option is absent.
+    pub fn expected_chain_id(&self) -> ChainId {
+        self.expected_net.as_ref().unwrap_or(&self.net).chain_id()
+    }
+
+    /// Check the txn is built for the expected network, and the connected node is of the network,
+    /// call it before unlocking the account, signing or submitting the txn.
+    pub fn ensure_chain_id(&self, txn_chain_id: ChainId) -> Result<()> {
+        let expected_chain_id = self.expected_chain_id();
+        ensure!(
+            txn_chain_id == expected_chain_id,
+            "The txn's chain id {} mismatch with the expected chain id {}, refuse to sign or submit transaction. Please rebuild the txn for the expected network.",
+            txn_chain_id,
+            expected_chain_id,
+        );
+        ensure!(
+            expected_chain_id == self.net.chain_id(),
+            "The connected node's network is {}(chain id: {}), but the expected chain id is {}, refuse to sign or submit transaction. Please connect to a node of the expected network, or fix the --net option.",
+            self.net,
+            self.net.chain_id(),
+            expected_chain_id,
+        );
+        Ok(())
+    }
+
     pub fn client(&self) -> &RpcClient {
         &self.client
     }
 
+    /// Start the trace of a user action, the node logs the rpc calls of the action with the
+    /// printed trace id, so a bug report with it can be correlated with the node logs.
+    pub fn start_trace(&self) {
+        let trace_id = RpcClient::new_trace_id();
+        match self.client.set_trace_id(Some(trace_id.clone())) {
+            Ok(()) => eprintln!("trace id: {}", trace_id),
+            Err(e) => debug!("Set the trace id failed: {}", e),
+        }
+    }
+
+    pub fn watch_timeout(&self) -> Duration {
+        self.watch_timeout
+    }
+
     pub fn temp_dir(&self) -> &Path {
         self.temp_dir.path()
     }
@@ -80,6 +137,23 @@ impl CliState {
         self.data_dir().join(HISTORY_FILE_NAME)
     }
 
+    /// The genesis hash of the chain which the cli data is cached for, None if not recorded.
+    pub fn cached_genesis_hash(&self) -> Result<Option<HashValue>> {
+        read_genesis_hash(self.data_dir())
+    }
+
+    /// Check the cli data is cached for the connected node's chain, record the genesis hash if not recorded.
+    /// Return the cached genesis hash if it mismatches, the network is reset and the cached data is stale.
+    pub fn check_genesis(&self) -> Result<Option<HashValue>> {
+        check_genesis_hash(self.data_dir(), self.client.chain_info()?.genesis_hash)
+    }
+
+    /// Move the cli cached data of the reset chain to `archive/$cached_genesis_hash` in the cli data dir,
+    /// except the command history, then record the genesis hash of the connected node.
+    pub fn archive_cached_data(&self) -> Result<PathBuf> {
+        archive_data(self.data_dir(), self.client.chain_info()?.genesis_hash)
+    }
+
     pub fn node_handle(&self) -> Option<&NodeHandle> {
         self.node_handle.as_ref()
     }
@@ -130,3 +204,115 @@ impl CliState {
         (self.net, self.client, self.node_handle)
     }
 }
+
+fn read_genesis_hash(data_dir: &Path) -> Result<Option<HashValue>> {
+    let path = data_dir.join(GENESIS_HASH_FILE_NAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(HashValue::from_hex_literal(
+        std::fs::read_to_string(path)?.trim(),
+    )?))
+}
+
+fn write_genesis_hash(data_dir: &Path, genesis_hash: HashValue) -> Result<()> {
+    std::fs::write(
+        data_dir.join(GENESIS_HASH_FILE_NAME),
+        format!("{:#x}", genesis_hash),
+    )?;
+    Ok(())
+}
+
+fn check_genesis_hash(data_dir: &Path, genesis_hash: HashValue) -> Result<Option<HashValue>> {
+    match read_genesis_hash(data_dir)? {
+        Some(cached_genesis_hash) if cached_genesis_hash != genesis_hash => {
+            Ok(Some(cached_genesis_hash))
+        }
+        Some(_) => Ok(None),
+        None => {
+            write_genesis_hash(data_dir, genesis_hash)?;
+            Ok(None)
+        }
+    }
+}
+
+fn archive_data(data_dir: &Path, genesis_hash: HashValue) -> Result<PathBuf> {
+    let cached_genesis_hash = read_genesis_hash(data_dir)?
+        .ok_or_else(|| format_err!("The genesis hash of the cli data is not recorded."))?;
+    let archive_dir = data_dir
+        .join(ARCHIVE_DIR_NAME)
+        .join(format!("{:x}", cached_genesis_hash));
+    std::fs::create_dir_all(archive_dir.as_path())?;
+    for entry in std::fs::read_dir(data_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if [ARCHIVE_DIR_NAME, TEMP_DIR_NAME, HISTORY_FILE_NAME]
+            .iter()
+            .any(|name| file_name == *name)
+        {
+            continue;
+        }
+        std::fs::rename(entry.path(), archive_dir.join(file_name))?;
+    }
+    write_genesis_hash(data_dir, genesis_hash)?;
+    Ok(archive_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_genesis_hash() {
+        let dir = starcoin_config::temp_path();
+        let genesis_hash = HashValue::random();
+        assert_eq!(read_genesis_hash(dir.path()).unwrap(), None);
+        // the genesis hash is recorded at the first check.
+        assert_eq!(check_genesis_hash(dir.path(), genesis_hash).unwrap(), None);
+        assert_eq!(read_genesis_hash(dir.path()).unwrap(), Some(genesis_hash));
+        assert_eq!(check_genesis_hash(dir.path(), genesis_hash).unwrap(), None);
+        // the network is reset, the cached genesis hash is returned and kept.
+        let new_genesis_hash = HashValue::random();
+        assert_eq!(
+            check_genesis_hash(dir.path(), new_genesis_hash).unwrap(),
+            Some(genesis_hash)
+        );
+        assert_eq!(read_genesis_hash(dir.path()).unwrap(), Some(genesis_hash));
+    }
+
+    #[test]
+    fn test_archive_data() {
+        let dir = starcoin_config::temp_path();
+        let data_dir = dir.path();
+        let new_genesis_hash = HashValue::random();
+        assert!(archive_data(data_dir, new_genesis_hash).is_err());
+
+        let genesis_hash = HashValue::random();
+        check_genesis_hash(data_dir, genesis_hash).unwrap();
+        std::fs::write(data_dir.join(HISTORY_FILE_NAME), "history").unwrap();
+        std::fs::create_dir_all(data_dir.join(TEMP_DIR_NAME)).unwrap();
+        std::fs::write(data_dir.join("cache"), "cache").unwrap();
+        std::fs::create_dir_all(data_dir.join("cache_dir")).unwrap();
+
+        let archive_dir = archive_data(data_dir, new_genesis_hash).unwrap();
+        assert_eq!(
+            archive_dir,
+            data_dir
+                .join(ARCHIVE_DIR_NAME)
+                .join(format!("{:x}", genesis_hash))
+        );
+        assert!(archive_dir.join("cache").exists());
+        assert!(archive_dir.join("cache_dir").is_dir());
+        assert!(archive_dir.join(GENESIS_HASH_FILE_NAME).exists());
+        assert!(!data_dir.join("cache").exists());
+        assert!(!data_dir.join("cache_dir").exists());
+        // the history and temp dir are kept.
+        assert!(data_dir.join(HISTORY_FILE_NAME).exists());
+        assert!(data_dir.join(TEMP_DIR_NAME).is_dir());
+        assert_eq!(read_genesis_hash(data_dir).unwrap(), Some(new_genesis_hash));
+        assert_eq!(
+            check_genesis_hash(data_dir, new_genesis_hash).unwrap(),
+            None
+        );
+    }
+}
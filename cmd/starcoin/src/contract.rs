@@ -2,12 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_state::CliState;
+use crate::dev::blocking_display_notification;
 use crate::StarcoinOpt;
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use serde::{Serialize, Serializer};
-use starcoin_rpc_api::types::{AnnotatedMoveStructView, StrView};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{
+    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, FunctionIdView, StrView,
+    TransactionArgumentView, TypeTagView,
+};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::event::EventKey;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use structopt::StructOpt;
 
@@ -81,3 +89,123 @@ impl CommandAction for GetContractDataCommand {
         Ok(result)
     }
 }
+
+/// Watch the contract events filtered by event key, emitter address and move type.
+///  Some examples:
+///  ``` shell
+///  contract watch-events --type-tag 0x1::Account::WithdrawEvent --address 0x1
+///  contract watch-events --type-tag 0x1::Account::DepositEvent --from-block 100
+///  ```
+#[derive(Debug, StructOpt)]
+#[structopt(name = "watch-events")]
+pub struct WatchEventsOpt {
+    #[structopt(long = "from-block")]
+    /// Backfill the history events from the block number, then watch the new events.
+    from_block: Option<u64>,
+    #[structopt(short = "k", long = "event-key", multiple = true)]
+    /// Only watch the events of the event keys.
+    event_keys: Vec<EventKey>,
+    #[structopt(
+        short = "a",
+        long = "address",
+        multiple = true,
+        parse(try_from_str = parse_address)
+    )]
+    /// Only watch the events emitted by the addresses.
+    addrs: Vec<AccountAddress>,
+    #[structopt(short = "t", long = "type-tag", multiple = true)]
+    /// Only watch the events of the move types, such as 0x1::Account::WithdrawEvent
+    type_tags: Vec<TypeTagView>,
+}
+
+pub struct WatchEventsCommand;
+
+impl CommandAction for WatchEventsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = WatchEventsOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let filter = EventFilter {
+            from_block: opt.from_block,
+            to_block: None,
+            event_keys: opt.event_keys.clone(),
+            addrs: opt.addrs.clone(),
+            type_tags: opt.type_tags.clone(),
+            limit: None,
+            replay: None,
+        };
+        let event_stream = ctx.state().client().subscribe_events(filter)?;
+        println!("Watch successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
+/// Call a read-only move function without creating a transaction, return the decoded return values.
+///  Some examples:
+///  ``` shell
+///  contract call-view --function 0x1::Block::current_block_number
+///  contract call-view --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x1 --block-number 100
+///  ```
+#[derive(Debug, StructOpt)]
+#[structopt(name = "call-view")]
+pub struct CallViewOpt {
+    #[structopt(long)]
+    /// function to call, example: 0x1::Block::current_block_number
+    function: FunctionIdView,
+    #[structopt(short = "t", long = "type-tag", multiple = true)]
+    /// type args of the function, can specify multi type_tag.
+    type_tags: Vec<TypeTagView>,
+    #[structopt(long = "arg", multiple = true)]
+    /// args of the function, can specify multi arg.
+    args: Vec<TransactionArgumentView>,
+    #[structopt(long = "state-root", conflicts_with("block-number"))]
+    /// call on the state of the state root, if absent, use the latest state.
+    state_root: Option<HashValue>,
+    #[structopt(long = "block-number", name = "block-number")]
+    /// call on the state after the block executed.
+    block_number: Option<BlockNumber>,
+}
+
+pub struct CallViewCommand;
+
+impl CommandAction for CallViewCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = CallViewOpt;
+    type ReturnItem = Vec<AnnotatedMoveValueView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let state_root = match (opt.state_root, opt.block_number) {
+            (Some(state_root), _) => Some(state_root),
+            (None, Some(number)) => Some(
+                client
+                    .chain_get_block_by_number(number)?
+                    .ok_or_else(|| format_err!("Can not find block by number {}", number))?
+                    .header
+                    .state_root,
+            ),
+            (None, None) => None,
+        };
+        let call = ContractCall {
+            function_id: opt.function.clone(),
+            type_args: opt.type_tags.clone(),
+            args: opt.args.clone(),
+            state_root,
+        };
+        client.contract_call(call)
+    }
+}
@@ -58,6 +58,7 @@ impl CommandAction for CallContractCommand {
             function_id: opt.function.clone(),
             type_args: opt.type_tags.clone().unwrap_or_default(),
             args: opt.args.clone().unwrap_or_default(),
+            state_root: None,
         };
 
         let result = ctx.state().client().contract_call(call)?;
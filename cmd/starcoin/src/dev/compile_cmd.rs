@@ -8,7 +8,7 @@ use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_config::temp_path;
 use starcoin_move_compiler::{compile_source_string_no_report, errors};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -22,7 +22,8 @@ pub struct CompileOpt {
         short = "s",
         long = "sender",
         name = "sender address",
-        help = "hex encoded string, like 0x0, 0x1"
+        help = "hex encoded string, like 0x0, 0x1",
+        parse(try_from_str = parse_address)
     )]
     sender: Option<AccountAddress>,
 
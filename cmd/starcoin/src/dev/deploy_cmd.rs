@@ -108,9 +108,10 @@ impl CommandAction for DeployCommand {
             opt.max_gas_amount,
             opt.gas_price,
             expiration_time,
-            ctx.state().net().chain_id(),
+            ctx.state().expected_chain_id(),
         );
 
+        ctx.state().ensure_chain_id(deploy_txn.chain_id())?;
         let signed_txn = client.account_sign_txn(deploy_txn)?;
         let txn_hash = signed_txn.id();
 
@@ -0,0 +1,166 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
+use starcoin_config::BuiltinNetworkID;
+use starcoin_dev::playground;
+use starcoin_rpc_api::types::{TransactionOutputView, TransactionVMStatus};
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
+use starcoin_state_api::AccountStateReader;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::gas_schedule::CostTable;
+use starcoin_vm_types::on_chain_config::VMConfig;
+use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
+use starcoin_vm_types::transaction::{DryRunTransaction, RawUserTransaction};
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Show the active on chain gas schedule,
+/// or estimate the gas of a raw txn under the current or a hypothetical gas schedule.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "gas-schedule")]
+pub struct GasScheduleOpt {
+    #[structopt(long = "price-txn", name = "raw_txn")]
+    /// hex encoded bcs bytes of the RawUserTransaction to estimate gas.
+    price_txn: Option<String>,
+
+    #[structopt(long = "public-key", requires("raw_txn"))]
+    /// hex encoded public key of the txn sender, if absent, get it from the local wallet.
+    public_key: Option<String>,
+
+    #[structopt(
+        short = "n",
+        long = "net",
+        requires("raw_txn"),
+        conflicts_with("gas-schedule-file")
+    )]
+    /// use the genesis gas schedule of the builtin network as the hypothetical gas schedule, for example, proxima
+    net: Option<BuiltinNetworkID>,
+
+    #[structopt(
+        name = "gas-schedule-file",
+        long = "gas-schedule-file",
+        parse(from_os_str),
+        requires("raw_txn")
+    )]
+    /// json file of the hypothetical gas schedule, same format as the output of this command.
+    gas_schedule_file: Option<PathBuf>,
+}
+
+pub struct GasScheduleCommand;
+
+impl CommandAction for GasScheduleCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasScheduleOpt;
+    type ReturnItem = GasScheduleView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let chain_state_reader = RemoteStateReader::new(client)?;
+        let account_state_reader = AccountStateReader::new(&chain_state_reader);
+
+        let raw_txn = match opt.price_txn.as_ref() {
+            Some(raw_txn) => raw_txn,
+            None => {
+                let vm_config = account_state_reader
+                    .get_on_chain_config::<VMConfig>()?
+                    .ok_or_else(|| format_err!("VMConfig not exist on chain."))?;
+                return Ok(GasScheduleView::GasSchedule(Box::new(
+                    vm_config.gas_schedule,
+                )));
+            }
+        };
+        let raw_txn: RawUserTransaction =
+            bcs_ext::from_bytes(&hex::decode(raw_txn.trim_start_matches("0x"))?)?;
+        let public_key = match opt.public_key.as_ref() {
+            Some(public_key) => AccountPublicKey::try_from(
+                hex::decode(public_key.trim_start_matches("0x"))?.as_slice(),
+            )?,
+            None => get_public_key(client, raw_txn.sender())?,
+        };
+        let txn = DryRunTransaction {
+            raw_txn: raw_txn.clone(),
+            public_key,
+        };
+
+        let (_, output) = playground::dry_run(&chain_state_reader, txn.clone())?;
+        let current = TxnGasView::new(&raw_txn, output.into());
+
+        let hypothetical_gas_schedule = match (opt.net, opt.gas_schedule_file.as_ref()) {
+            (Some(net), _) => Some(net.genesis_config().vm_config.gas_schedule.clone()),
+            (None, Some(file)) => {
+                let content = std::fs::read_to_string(file)?;
+                Some(serde_json::from_str::<CostTable>(content.as_str())?)
+            }
+            (None, None) => None,
+        };
+        let hypothetical = match hypothetical_gas_schedule {
+            Some(gas_schedule) => {
+                let (_, output) =
+                    playground::dry_run_with_gas_schedule(&chain_state_reader, txn, gas_schedule)?;
+                Some(TxnGasView::new(&raw_txn, output.into()))
+            }
+            None => None,
+        };
+        Ok(GasScheduleView::TxnGas {
+            current,
+            hypothetical,
+        })
+    }
+}
+
+fn get_public_key(client: &RpcClient, sender: AccountAddress) -> Result<AccountPublicKey> {
+    client
+        .account_get(sender)?
+        .map(|account| account.public_key)
+        .ok_or_else(|| {
+            format_err!(
+                "Can not find account {} in local wallet, please provide --public-key.",
+                sender
+            )
+        })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TxnGasView {
+    pub status: TransactionVMStatus,
+    pub gas_used: u64,
+    pub gas_unit_price: u64,
+    pub max_gas_amount: u64,
+    /// gas_used * gas_unit_price
+    pub fee: u128,
+}
+
+impl TxnGasView {
+    fn new(raw_txn: &RawUserTransaction, output: TransactionOutputView) -> Self {
+        let gas_used = output.gas_used.0;
+        Self {
+            status: output.status,
+            gas_used,
+            gas_unit_price: raw_txn.gas_unit_price(),
+            max_gas_amount: raw_txn.max_gas_amount(),
+            fee: gas_used as u128 * raw_txn.gas_unit_price() as u128,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum GasScheduleView {
+    GasSchedule(Box<CostTable>),
+    TxnGas {
+        /// gas of the txn under the current on chain gas schedule.
+        current: TxnGasView,
+        /// gas of the txn under the hypothetical gas schedule.
+        hypothetical: Option<TxnGasView>,
+    },
+}
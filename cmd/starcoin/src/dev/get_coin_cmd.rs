@@ -9,7 +9,10 @@ use starcoin_executor::{DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT};
 use starcoin_rpc_api::types::SignedUserTransactionView;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+use starcoin_types::transaction::SignedUserTransaction;
 use std::convert::TryInto;
 use structopt::StructOpt;
 use tokio::time::Duration;
@@ -50,54 +53,76 @@ impl CommandAction for GetCoinCommand {
                 net
             );
         }
-        let client = ctx.state().client();
-        let node_info = client.node_info()?;
-        let to = client.account_default()?.ok_or_else(|| {
+        let to = ctx.state().client().account_default()?.ok_or_else(|| {
             format_err!("Can not find default account, Please create account first.")
         })?;
-
-        let association_address = account_config::association_address();
-        let chain_state_reader = RemoteStateReader::new(client)?;
-        let account_state_reader = AccountStateReader::new(&chain_state_reader);
-        let account_resource = account_state_reader
-            .get_account_resource(&association_address)?
-            .ok_or_else(|| {
-                format_err!(
-                    "association_address address {} must exist",
-                    association_address
-                )
-            })?;
-        let balance = account_state_reader
-            .get_balance(&association_address)?
-            .ok_or_else(|| {
-                format_err!(
-                    "association_address address {} balance must exist",
-                    association_address
-                )
-            })?;
-        let amount = opt.amount.unwrap_or(balance * 20 / 100);
-        let raw_txn = starcoin_executor::build_transfer_txn(
-            association_address,
+        let txn = transfer_from_association(
+            ctx.state(),
             to.address,
             Some(to.public_key.authentication_key()),
-            account_resource.sequence_number(),
-            amount,
-            1,
-            DEFAULT_MAX_GAS_AMOUNT,
-            node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
-            ctx.state().net().chain_id(),
-        );
-        client.account_unlock(
-            association_address,
-            "".to_string(),
-            Duration::from_secs(300),
+            opt.amount,
         )?;
-        let txn = client.account_sign_txn(raw_txn)?;
         let id = txn.id();
-        client.submit_transaction(txn.clone())?;
         if !opt.no_blocking {
             ctx.state().watch_txn(id)?;
         }
         txn.try_into()
     }
 }
+
+/// Transfer `amount` STC from the association account to `to`, and submit the txn.
+/// If `amount` is absent, transfer 20% of association_address's balance.
+pub fn transfer_from_association(
+    state: &CliState,
+    to: AccountAddress,
+    to_auth_key: Option<AuthenticationKey>,
+    amount: Option<u128>,
+) -> Result<SignedUserTransaction> {
+    let client = state.client();
+    let node_info = client.node_info()?;
+    let association_address = account_config::association_address();
+    let chain_state_reader = RemoteStateReader::new(client)?;
+    let account_state_reader = AccountStateReader::new(&chain_state_reader);
+    let account_resource = account_state_reader
+        .get_account_resource(&association_address)?
+        .ok_or_else(|| {
+            format_err!(
+                "association_address address {} must exist",
+                association_address
+            )
+        })?;
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            let balance = account_state_reader
+                .get_balance(&association_address)?
+                .ok_or_else(|| {
+                    format_err!(
+                        "association_address address {} balance must exist",
+                        association_address
+                    )
+                })?;
+            balance * 20 / 100
+        }
+    };
+    let raw_txn = starcoin_executor::build_transfer_txn(
+        association_address,
+        to,
+        to_auth_key,
+        account_resource.sequence_number(),
+        amount,
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
+        state.expected_chain_id(),
+    );
+    state.ensure_chain_id(raw_txn.chain_id())?;
+    client.account_unlock(
+        association_address,
+        "".to_string(),
+        Duration::from_secs(300),
+    )?;
+    let txn = client.account_sign_txn(raw_txn)?;
+    client.submit_transaction(txn.clone())?;
+    Ok(txn)
+}
@@ -4,8 +4,10 @@
 pub use call_contract_cmd::*;
 pub use compile_cmd::*;
 pub use deploy_cmd::*;
+pub use gas_schedule_cmd::*;
 pub use get_coin_cmd::*;
 pub use package_cmd::*;
+pub use reset_cli_data_cmd::*;
 pub use sign_txn_helper::sign_txn_with_account_by_rpc_client;
 pub use subscribe_cmd::*;
 pub use upgrade_module_exe_cmd::*;
@@ -17,8 +19,10 @@ pub use upgrade_vm_config_proposal_cmd::*;
 mod call_contract_cmd;
 mod compile_cmd;
 mod deploy_cmd;
+mod gas_schedule_cmd;
 mod get_coin_cmd;
 mod package_cmd;
+mod reset_cli_data_cmd;
 pub(crate) mod sign_txn_helper;
 mod subscribe_cmd;
 mod upgrade_module_exe_cmd;
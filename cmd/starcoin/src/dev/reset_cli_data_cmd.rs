@@ -0,0 +1,58 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Archive the cli cached data of the network after the network is reset (the genesis changed),
+/// the archived data is moved to the `archive` dir in the cli data dir.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "reset-cli-data")]
+pub struct ResetCliDataOpt {
+    #[structopt(long = "force")]
+    /// Archive the cached data even if the genesis is not changed.
+    force: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetCliDataView {
+    pub previous_genesis_hash: HashValue,
+    pub genesis_hash: HashValue,
+    pub archive_dir: PathBuf,
+}
+
+pub struct ResetCliDataCommand;
+
+impl CommandAction for ResetCliDataCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ResetCliDataOpt;
+    type ReturnItem = ResetCliDataView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let state = ctx.state();
+        let genesis_hash = state.client().chain_info()?.genesis_hash;
+        let previous_genesis_hash = state.cached_genesis_hash()?.unwrap_or(genesis_hash);
+        ensure!(
+            ctx.opt().force || previous_genesis_hash != genesis_hash,
+            "The genesis {:#x} of network {} is not changed, use --force to archive the cli data anyway.",
+            genesis_hash,
+            state.net()
+        );
+        let archive_dir = state.archive_cached_data()?;
+        Ok(ResetCliDataView {
+            previous_genesis_hash,
+            genesis_hash,
+            archive_dir,
+        })
+    }
+}
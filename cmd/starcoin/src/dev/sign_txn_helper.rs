@@ -3,7 +3,6 @@
 
 use crate::cli_state::CliState;
 use anyhow::{format_err, Result};
-use starcoin_config::ChainNetwork;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
 use starcoin_types::transaction::RawUserTransaction;
@@ -45,12 +44,10 @@ pub fn sign_txn_by_rpc_client(
     let account_resource = account_state_reader
         .get_account_resource(account.address())?
         .ok_or_else(|| format_err!("account {:?} must exist on chain.", account.address()))?;
-    let net = ChainNetwork::new_builtin(
-        *cli_state
-            .net()
-            .as_builtin()
-            .ok_or_else(|| format_err!("Only support builtin network"))?,
-    );
+    cli_state
+        .net()
+        .as_builtin()
+        .ok_or_else(|| format_err!("Only support builtin network"))?;
     let expiration_time = expiration_time + node_info.now_seconds;
     let raw_txn = RawUserTransaction::new_with_default_gas_token(
         account.address,
@@ -59,9 +56,10 @@ pub fn sign_txn_by_rpc_client(
         max_gas_amount,
         gas_price,
         expiration_time,
-        net.chain_id(),
+        cli_state.expected_chain_id(),
     );
 
+    cli_state.ensure_chain_id(raw_txn.chain_id())?;
     client.account_sign_txn(raw_txn)
 }
 
@@ -51,7 +51,10 @@ impl CommandAction for SubscribeEventCommand {
             from_block: ctx.opt().from_block,
             to_block: ctx.opt().to_block,
             event_keys: ctx.opt().event_key.clone().unwrap_or_default(),
+            addrs: vec![],
+            type_tags: vec![],
             limit: ctx.opt().limit,
+            replay: None,
         };
 
         let event_stream = ctx.state().client().subscribe_events(filter)?;
@@ -107,7 +110,7 @@ impl CommandAction for SubscribeNewTxnCommand {
     }
 }
 
-fn blocking_display_notification<T, F>(
+pub fn blocking_display_notification<T, F>(
     mut event_stream: impl TryStream<Ok = T, Error = anyhow::Error> + Unpin,
     display: F,
 ) where
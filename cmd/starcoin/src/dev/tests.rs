@@ -368,9 +368,22 @@ fn test_upgrade_module() {
         function_id: FunctionIdView::from_str("0x1::TestModule::is_test").unwrap(),
         type_args: Vec::new(),
         args: Vec::new(),
+        state_root: None,
     };
-    let result = cli_state.client().contract_call(call).unwrap();
+    let result = cli_state.client().contract_call(call.clone()).unwrap();
     assert!(!result.is_empty());
+    let state_root = cli_state.client().state_get_state_root().unwrap();
+    let result_at_state_root = cli_state
+        .client()
+        .contract_call(ContractCall {
+            state_root: Some(state_root),
+            ..call
+        })
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&result).unwrap(),
+        serde_json::to_value(&result_at_state_root).unwrap()
+    );
     info!("result: {:?}", result);
     if let AnnotatedMoveValueView::Bool(flag) = result.get(0).unwrap() {
         assert!(flag);
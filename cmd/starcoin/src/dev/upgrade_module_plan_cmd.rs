@@ -8,14 +8,14 @@ use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::hash::HashValue;
 use starcoin_transaction_builder::build_module_upgrade_plan;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::transaction::TransactionPayload;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "module_plan")]
 pub struct UpgradeModulePlanOpt {
-    #[structopt(short = "s", long)]
+    #[structopt(short = "s", long, parse(try_from_str = parse_address))]
     /// hex encoded string, like 0x1, 0x12
     sender: Option<AccountAddress>,
 
@@ -50,7 +50,12 @@ pub struct UpgradeModulePlanOpt {
     )]
     blocking: bool,
 
-    #[structopt(short = "a", name = "proposer-address", long = "proposer_address")]
+    #[structopt(
+        short = "a",
+        name = "proposer-address",
+        long = "proposer_address",
+        parse(try_from_str = parse_address)
+    )]
     /// hex encoded string, like 0x1, 0x12
     proposer_address: Option<AccountAddress>,
 
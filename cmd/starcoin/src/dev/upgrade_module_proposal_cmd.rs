@@ -12,7 +12,7 @@ use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::StateReaderExt;
 use starcoin_transaction_builder::build_module_upgrade_proposal;
 use starcoin_types::transaction::Package;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::on_chain_config::Version;
 use starcoin_vm_types::transaction::TransactionPayload;
@@ -24,7 +24,7 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "module_proposal")]
 pub struct UpgradeModuleProposalOpt {
-    #[structopt(short = "s", long)]
+    #[structopt(short = "s", long, parse(try_from_str = parse_address))]
     /// hex encoded string, like 0x1, 0x12
     sender: Option<AccountAddress>,
 
@@ -10,7 +10,7 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::StateReaderExt;
 use starcoin_transaction_builder::build_module_upgrade_queue;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::on_chain_config::Version;
 use starcoin_vm_types::transaction::TransactionPayload;
@@ -19,7 +19,7 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "module_queue")]
 pub struct UpgradeModuleQueueOpt {
-    #[structopt(short = "s", long)]
+    #[structopt(short = "s", long, parse(try_from_str = parse_address))]
     /// hex encoded string, like 0x1, 0x12
     sender: Option<AccountAddress>,
 
@@ -54,7 +54,12 @@ pub struct UpgradeModuleQueueOpt {
     )]
     blocking: bool,
 
-    #[structopt(short = "a", name = "proposer-address", long = "proposer_address")]
+    #[structopt(
+        short = "a",
+        name = "proposer-address",
+        long = "proposer_address",
+        parse(try_from_str = parse_address)
+    )]
     /// hex encoded string, like 0x1, 0x12
     proposer_address: Option<AccountAddress>,
 
@@ -9,7 +9,7 @@ use scmd::{CommandAction, ExecContext};
 use starcoin_config::BuiltinNetworkID;
 use starcoin_crypto::hash::HashValue;
 use starcoin_transaction_builder::build_vm_config_upgrade_proposal;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::transaction::TransactionPayload;
 use std::str::FromStr;
 use structopt::StructOpt;
@@ -18,7 +18,7 @@ use structopt::StructOpt;
 #[structopt(name = "vm_config_proposal")]
 #[allow(clippy::upper_case_acronyms)]
 pub struct UpgradeVMConfigProposalOpt {
-    #[structopt(short = "s", long)]
+    #[structopt(short = "s", long, parse(try_from_str = parse_address))]
     /// hex encoded string, like 0x1, 0x12
     sender: Option<AccountAddress>,
 
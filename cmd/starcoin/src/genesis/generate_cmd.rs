@@ -0,0 +1,73 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_config::{ChainNetworkID, GENESIS_CONFIG_FILE_NAME};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::GenesisSpec;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Generate the genesis of a custom network by a toml spec,
+/// the genesis config is saved to the `genesis_config.json` in the dir of the output genesis file.
+/// Start the node of the network with the `--net <chain_name>:<chain_id> --genesis-config <genesis_config.json> --genesis-file <output>` options.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "generate")]
+pub struct GenerateOpt {
+    #[structopt(long = "spec", parse(from_os_str))]
+    /// The toml spec of the genesis.
+    spec: PathBuf,
+
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    /// The output genesis file.
+    output: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateGenesisView {
+    pub net: ChainNetworkID,
+    pub genesis_hash: HashValue,
+    pub genesis_file: PathBuf,
+    pub genesis_config_file: PathBuf,
+}
+
+pub struct GenerateCommand;
+
+impl CommandAction for GenerateCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenerateOpt;
+    type ReturnItem = GenerateGenesisView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ensure!(
+            !opt.output.exists(),
+            "The output file {:?} already exists.",
+            opt.output
+        );
+        let (net, genesis) = GenesisSpec::load(opt.spec.as_path())?
+            .into_builder()?
+            .build()?;
+        let genesis_config_file = opt
+            .output
+            .parent()
+            .map(|dir| dir.join(GENESIS_CONFIG_FILE_NAME))
+            .unwrap_or_else(|| PathBuf::from(GENESIS_CONFIG_FILE_NAME));
+        genesis.save_to_file(opt.output.as_path())?;
+        net.genesis_config().save(genesis_config_file.as_path())?;
+        Ok(GenerateGenesisView {
+            net: net.id().clone(),
+            genesis_hash: genesis.block().id(),
+            genesis_file: opt.output.clone(),
+            genesis_config_file,
+        })
+    }
+}
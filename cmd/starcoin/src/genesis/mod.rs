@@ -0,0 +1,6 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod generate_cmd;
+
+pub use generate_cmd::*;
@@ -7,10 +7,15 @@ pub mod cli_state;
 pub mod contract;
 pub mod debug;
 pub mod dev;
+pub mod genesis;
 pub mod helper;
+pub mod miner;
 pub mod mutlisig_transaction;
+pub mod networks;
 pub mod node;
+pub mod pipe;
 pub mod state;
+pub mod tools;
 mod txpool;
 pub mod view;
 
@@ -23,12 +28,25 @@ pub use starcoin_node::crash_handler;
 pub fn add_command(
     context: CmdContext<CliState, StarcoinOpt>,
 ) -> CmdContext<CliState, StarcoinOpt> {
+    let txpool_command = Command::with_name("txpool")
+        .subcommand(txpool::PendingTxnCommand)
+        .subcommand(txpool::PendingTxnsCommand)
+        .subcommand(txpool::TxPoolStatusCommand)
+        .subcommand(txpool::TxPoolAnalyticsCommand)
+        .subcommand(txpool::WatchDirCommand);
+    // the experimental encrypted pool commands are only available with the threshold-encryption feature.
+    #[cfg(feature = "threshold-encryption")]
+    let txpool_command = txpool_command
+        .subcommand(txpool::GenerateCommitteeCommand)
+        .subcommand(txpool::SubmitEncryptedTxnCommand)
+        .subcommand(txpool::ReleaseDecryptionSharesCommand);
     context
         .command(
             Command::with_name("account")
                 .subcommand(account::CreateCommand)
                 .subcommand(account::ShowCommand)
                 .subcommand(account::TransferCommand)
+                .subcommand(account::FundCommand)
                 .subcommand(account::AcceptTokenCommand)
                 .subcommand(account::ListCommand)
                 .subcommand(account::import_multisig_cmd::ImportMultisigCommand)
@@ -37,9 +55,13 @@ pub fn add_command(
                 .subcommand(account::remove_cmd::RemoveCommand)
                 .subcommand(account::LockCommand)
                 .subcommand(account::UnlockCommand)
+                .subcommand(account::MetadataCommand)
                 .subcommand(account::ExportCommand)
                 .subcommand(account::ImportCommand)
                 .subcommand(account::import_readonly_cmd::ImportReadonlyCommand)
+                .subcommand(account::GenerateMnemonicCommand)
+                .subcommand(account::DeriveCommand)
+                .subcommand(account::RestoreCommand)
                 .subcommand(account::ExecuteScriptFunctionCmd)
                 .subcommand(account::ExecuteScriptCommand)
                 .subcommand(account::sign_multisig_txn_cmd::GenerateMultisigTxnCommand)
@@ -48,7 +70,17 @@ pub fn add_command(
                 .subcommand(account::VerifySignMessageCmd)
                 .subcommand(account::DeriveAddressCommand)
                 .subcommand(account::receipt_identifier_cmd::ReceiptIdentifierCommand)
-                .subcommand(account::generate_keypair::GenerateKeypairCommand),
+                .subcommand(account::generate_keypair::GenerateKeypairCommand)
+                .subcommand(account::VerifyCeremonyCommand)
+                .subcommand(account::tax_export_cmd::TaxExportCommand)
+                .subcommand(account::prove_reserves_cmd::ProveReservesCommand)
+                .subcommand(account::verify_reserves_cmd::VerifyReservesCommand)
+                .subcommand(account::sweep_cmd::SweepCommand)
+                .subcommand(
+                    Command::with_name("backup")
+                        .subcommand(account::backup::SyncCommand)
+                        .subcommand(account::backup::VerifyCommand),
+                ),
         )
         .command(
             Command::with_name("state")
@@ -62,6 +94,7 @@ pub fn add_command(
                 .subcommand(node::InfoCommand)
                 .subcommand(node::PeersCommand)
                 .subcommand(node::MetricsCommand)
+                .subcommand(node::ReportCommand)
                 .subcommand(
                     Command::with_name("service")
                         .subcommand(node::service::ListCommand)
@@ -71,6 +104,18 @@ pub fn add_command(
                     //TODO support shutdown by command    
                     //.subcommand(node::service::ShutdownSystemCommand),
                 )
+                .subcommand(Command::with_name("log").subcommand(node::log::TailCommand))
+                .subcommand(
+                    Command::with_name("maintenance")
+                        .subcommand(node::maintenance::StatusCommand)
+                        .subcommand(node::maintenance::NextCommand)
+                )
+                .subcommand(
+                    Command::with_name("admin")
+                        .subcommand(node::admin::AcceptReorgCommand)
+                        .subcommand(node::admin::ApproveTxnCommand)
+                        .subcommand(node::admin::PendingTxnApprovalsCommand)
+                )
                 .subcommand(
                     Command::with_name("sync")
                         .subcommand(node::sync::StartCommand)
@@ -86,6 +131,14 @@ pub fn add_command(
                     .subcommand(node::network::GetAddressCommand)
                     .subcommand(node::network::AddPeerCommand)
                     .subcommand(node::network::CallPeerCommand)
+                    .subcommand(node::network::ListPeersCommand)
+                    .subcommand(node::network::BanPeerCommand)
+                    .subcommand(node::network::UnbanPeerCommand)
+                    .subcommand(node::network::BannedPeersCommand)
+                    .subcommand(node::network::TrustPeerCommand)
+                    .subcommand(node::network::UntrustPeerCommand)
+                    .subcommand(node::network::TrustedPeersCommand)
+                    .subcommand(node::network::RotateIdentityCommand)
             ),
         )
         .command(
@@ -117,16 +170,12 @@ pub fn add_command(
                 .subcommand(
                     Command::with_name("verify")
                         .subcommand(chain::VerifyBlockCommand)
+                        .subcommand(chain::VerifyEmissionCommand)
                         .subcommand(chain::VerifyEpochCommand)
                         .subcommand(chain::VerifyNodeCommand),
                 ),
         )
-        .command(
-            Command::with_name("txpool")
-                .subcommand(txpool::PendingTxnCommand)
-                .subcommand(txpool::PendingTxnsCommand)
-                .subcommand(txpool::TxPoolStatusCommand),
-        )
+        .command(txpool_command)
         .command(
             Command::with_name("dev")
                 .subcommand(dev::GetCoinCommand)
@@ -139,6 +188,8 @@ pub fn add_command(
                 .subcommand(dev::UpgradeVMConfigProposalCommand)
                 .subcommand(dev::PackageCmd)
                 .subcommand(dev::CallContractCommand)
+                .subcommand(dev::GasScheduleCommand)
+                .subcommand(dev::ResetCliDataCommand)
                 .subcommand(
                     Command::with_name("subscribe")
                         .subcommand(dev::SubscribeBlockCommand)
@@ -146,7 +197,26 @@ pub fn add_command(
                         .subcommand(dev::SubscribeNewTxnCommand),
                 ),
         )
-        .command(Command::with_name("contract").subcommand(contract::GetContractDataCommand))
+        .command(
+            Command::with_name("contract")
+                .subcommand(contract::GetContractDataCommand)
+                .subcommand(contract::WatchEventsCommand)
+                .subcommand(contract::CallViewCommand),
+        )
+        .command(Command::with_name("miner").subcommand(miner::EstimateRewardsCommand))
+        .command(Command::with_name("genesis").subcommand(genesis::GenerateCommand))
+        .stateless_command(
+            Command::with_name("networks")
+                .subcommand(networks::ListCommand)
+                .subcommand(networks::InstallCommand),
+        )
+        .stateless_command(
+            Command::with_name("tools").subcommand(
+                Command::with_name("receipt")
+                    .subcommand(tools::ReceiptEncodeCommand)
+                    .subcommand(tools::ReceiptDecodeCommand),
+            ),
+        )
         .command(
             Command::with_name("debug")
                 .subcommand(
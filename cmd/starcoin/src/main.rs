@@ -9,7 +9,7 @@ use starcoin_config::{Connect, APP_VERSION, CRATE_VERSION};
 use starcoin_logger::prelude::*;
 use starcoin_node::crash_handler;
 use starcoin_node_api::errors::NodeStartError;
-use starcoin_rpc_client::RpcClient;
+use starcoin_rpc_client::{RpcClient, TxnRelay};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -68,15 +68,48 @@ fn run() -> Result<()> {
                     let client = RpcClient::connect_websocket(address)?;
                     (client, None)
                 }
+                Connect::Http(address) => {
+                    info!("Try to connect node by http: {:?}", address);
+                    let client = RpcClient::connect_http(address)?;
+                    (client, None)
+                }
+            };
+
+            let client = match opt.txn_relays.as_ref() {
+                Some(relays) => {
+                    info!("Submit transactions through relays: {:?}", relays);
+                    client
+                        .with_txn_relay(TxnRelay::new(relays.clone(), opt.txn_relay_proxy.clone())?)
+                }
+                None => client,
             };
 
             let node_info = client.node_info()?;
+            if let Some(expected_net) = opt.net.as_ref() {
+                if expected_net.chain_id() != node_info.net.chain_id() {
+                    warn!(
+                        "The connected node's network is {}, but the expected network is {}, sign or submit transaction will be refused.",
+                        node_info.net, expected_net
+                    );
+                }
+            }
             let state = CliState::new(
                 node_info.net,
                 Arc::new(client),
                 opt.watch_timeout.map(Duration::from_secs),
                 node_handle,
-            );
+            )
+            .with_expected_net(opt.net.clone());
+            match state.check_genesis() {
+                Ok(Some(cached_genesis_hash)) => warn!(
+                    "The network {} has been reset, the cli data in {:?} is cached for the previous genesis {:#x}, run `dev reset-cli-data` to archive it.",
+                    state.net(),
+                    state.data_dir(),
+                    cached_genesis_hash
+                ),
+                Ok(None) => {}
+                Err(e) => warn!("Check the genesis of the cli data failed: {:?}", e),
+            }
             Ok(state)
         },
         |_, _, state| {
@@ -111,6 +144,7 @@ fn run() -> Result<()> {
             }
         },
     );
+    let context = context.with_command_hook(|state: &CliState| state.start_trace());
     add_command(context).exec()
 }
 
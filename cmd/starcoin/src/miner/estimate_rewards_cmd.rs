@@ -0,0 +1,271 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
+use starcoin_rpc_api::types::{BlockTransactionsView, BlockView};
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::U256;
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use std::collections::HashMap;
+use structopt::StructOpt;
+
+const MILLIS_PER_DAY: f64 = 86_400_000f64;
+/// The max blocks to sample, the fees of every sampled block are fetched.
+const MAX_SAMPLE_BLOCKS: u64 = 1000;
+
+/// Estimate the expected blocks and income per day of the hashrate at the current difficulty,
+/// block reward and fee level. The blocks mined are a probability, the estimate is the average.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "estimate-rewards")]
+pub struct EstimateRewardsOpt {
+    /// The hashrate of the miner, in hashes per second.
+    #[structopt(long = "hashrate")]
+    hashrate: f64,
+    /// The algo of the hashrate, default to the algo of the chain.
+    #[structopt(long = "algo")]
+    algo: Option<ConsensusStrategy>,
+    /// The number of the latest blocks to sample the difficulty, block time and fees, at most 1000.
+    #[structopt(long = "blocks", default_value = "100")]
+    blocks: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RewardEstimateView {
+    pub algo: ConsensusStrategy,
+    /// The hashrate of the miner, in hashes per second.
+    pub hashrate: f64,
+    /// The estimated hashrate of the network, in hashes per second.
+    pub network_hashrate: f64,
+    /// The share of the miner of the network hashrate after joining the network.
+    pub share: f64,
+    /// The sampled blocks of the estimate.
+    pub sampled_blocks: u64,
+    pub avg_difficulty: U256,
+    /// The average block time of the sampled blocks, in milliseconds.
+    pub avg_block_time: u64,
+    /// The block time target, the difficulty is adjusted to it, in milliseconds.
+    pub block_time_target: u64,
+    /// The block reward, in nanoSTC.
+    pub reward_per_block: u128,
+    /// The average gas fees of the sampled blocks, in nanoSTC.
+    pub avg_fees_per_block: u128,
+    pub blocks_per_day: f64,
+    /// The expected days to mine a block.
+    pub days_per_block: f64,
+    /// The expected income per day, in nanoSTC.
+    pub income_per_day: u128,
+}
+
+pub struct EstimateRewardsCommand;
+
+impl CommandAction for EstimateRewardsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = EstimateRewardsOpt;
+    type ReturnItem = RewardEstimateView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ensure!(
+            opt.hashrate.is_finite() && opt.hashrate > 0f64,
+            "The hashrate should be greater than 0"
+        );
+        ensure!(
+            opt.blocks >= 2 && opt.blocks <= MAX_SAMPLE_BLOCKS,
+            "The sampled blocks should be in [2, {}]",
+            MAX_SAMPLE_BLOCKS
+        );
+        let client = ctx.state().client();
+        let epoch_info = client.epoch_info()?;
+        let epoch = epoch_info.epoch();
+        let strategy = epoch.strategy();
+        if let Some(algo) = opt.algo {
+            if algo != strategy {
+                bail!(
+                    "The chain is mined with {:?}, the hashrate of {:?} can not mine it",
+                    strategy,
+                    algo
+                );
+            }
+        }
+        let mut blocks = client.chain_get_blocks_by_number(None, opt.blocks)?;
+        ensure!(
+            blocks.len() >= 2,
+            "The chain has too few blocks to estimate the rewards"
+        );
+        blocks.sort_by_key(|block| block.header.number.0);
+        let avg_fees_per_block = avg_fees_per_block(client, &blocks)?;
+        let sample = BlockSample::new(&blocks);
+        Ok(estimate(
+            opt.hashrate,
+            strategy,
+            &sample,
+            epoch.block_time_target(),
+            epoch.reward_per_block(),
+            avg_fees_per_block,
+        ))
+    }
+}
+
+struct BlockSample {
+    blocks: u64,
+    avg_difficulty: U256,
+    /// In milliseconds.
+    avg_block_time: u64,
+}
+
+impl BlockSample {
+    /// The blocks should be sorted by number.
+    fn new(blocks: &[BlockView]) -> Self {
+        let total_difficulty = blocks
+            .iter()
+            .fold(U256::zero(), |total, block| total + block.header.difficulty);
+        let first = blocks.first().map(|block| block.header.timestamp.0);
+        let last = blocks.last().map(|block| block.header.timestamp.0);
+        let elapsed = last
+            .unwrap_or_default()
+            .saturating_sub(first.unwrap_or_default());
+        let intervals = (blocks.len() as u64).saturating_sub(1).max(1);
+        Self {
+            blocks: blocks.len() as u64,
+            avg_difficulty: total_difficulty / U256::from(blocks.len().max(1)),
+            avg_block_time: elapsed / intervals,
+        }
+    }
+}
+
+/// The gas fees of the blocks are paid to the block author, average them over the blocks.
+fn avg_fees_per_block(client: &RpcClient, blocks: &[BlockView]) -> Result<u128> {
+    let mut total_fees = 0u128;
+    for block in blocks {
+        if block.body.txn_hashes().is_empty() {
+            continue;
+        }
+        let number = block.header.number.0;
+        let full_block = client
+            .chain_get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        let gas_unit_prices = match full_block.body {
+            BlockTransactionsView::Full(txns) => txns
+                .into_iter()
+                .map(|txn| (txn.transaction_hash, txn.raw_txn.gas_unit_price.0))
+                .collect::<HashMap<_, _>>(),
+            BlockTransactionsView::Hashes(_) => {
+                bail!("The transactions of block {} are absent", number)
+            }
+        };
+        for txn_info in client.chain_get_block_txn_infos(block.header.block_hash)? {
+            if let Some(gas_unit_price) = gas_unit_prices.get(&txn_info.transaction_hash) {
+                total_fees = total_fees.saturating_add(
+                    u128::from(txn_info.gas_used.0).saturating_mul(u128::from(*gas_unit_price)),
+                );
+            }
+        }
+    }
+    Ok(total_fees / (blocks.len().max(1) as u128))
+}
+
+fn difficulty_as_f64(difficulty: U256) -> f64 {
+    if difficulty.bits() > 128 {
+        u128::MAX as f64
+    } else {
+        difficulty.low_u128() as f64
+    }
+}
+
+/// The expected hashes to mine a block equal the difficulty, so the network hashrate is the
+/// difficulty over the block time. Joining the network raises the difficulty by the hashrate,
+/// and the difficulty adjusts the block time back to the target.
+fn estimate(
+    hashrate: f64,
+    algo: ConsensusStrategy,
+    sample: &BlockSample,
+    block_time_target: u64,
+    reward_per_block: u128,
+    avg_fees_per_block: u128,
+) -> RewardEstimateView {
+    let avg_block_time = if sample.avg_block_time == 0 {
+        block_time_target
+    } else {
+        sample.avg_block_time
+    };
+    let network_hashrate =
+        difficulty_as_f64(sample.avg_difficulty) * 1000f64 / (avg_block_time.max(1) as f64);
+    let share = hashrate / (network_hashrate + hashrate);
+    let blocks_per_day = share * MILLIS_PER_DAY / (block_time_target.max(1) as f64);
+    let days_per_block = if blocks_per_day > 0f64 {
+        1f64 / blocks_per_day
+    } else {
+        f64::INFINITY
+    };
+    let income_per_block = reward_per_block.saturating_add(avg_fees_per_block);
+    RewardEstimateView {
+        algo,
+        hashrate,
+        network_hashrate,
+        share,
+        sampled_blocks: sample.blocks,
+        avg_difficulty: sample.avg_difficulty,
+        avg_block_time,
+        block_time_target,
+        reward_per_block,
+        avg_fees_per_block,
+        blocks_per_day,
+        days_per_block,
+        income_per_day: (income_per_block as f64 * blocks_per_day) as u128,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(left: f64, right: f64) {
+        assert!((left - right).abs() < 1e-9, "{} != {}", left, right);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let sample = BlockSample {
+            blocks: 100,
+            avg_difficulty: U256::from(10_000u64),
+            avg_block_time: 5000,
+        };
+        let view = estimate(
+            2000f64,
+            ConsensusStrategy::CryptoNight,
+            &sample,
+            10_000,
+            900,
+            100,
+        );
+        // 10000 hashes per 5 seconds.
+        assert_float_eq(view.network_hashrate, 2000f64);
+        assert_float_eq(view.share, 0.5);
+        assert_float_eq(view.blocks_per_day, 4320f64);
+        assert_eq!(view.income_per_day, 4_320_000);
+
+        let sample = BlockSample {
+            avg_block_time: 0,
+            ..sample
+        };
+        let view = estimate(
+            1000f64,
+            ConsensusStrategy::CryptoNight,
+            &sample,
+            10_000,
+            900,
+            0,
+        );
+        assert_eq!(view.avg_block_time, 10_000);
+        assert_float_eq(view.network_hashrate, 1000f64);
+        assert_float_eq(view.days_per_block, 1f64 / 4320f64);
+    }
+}
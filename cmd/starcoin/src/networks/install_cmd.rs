@@ -0,0 +1,39 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::networks::{base_data_dir, NetworkDescriptorView};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::NetworkDescriptor;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Install a third-party network descriptor from a file,
+/// then join the network by `starcoin -n <name>:<chain_id>`, the boot nodes and the genesis config of the descriptor are used.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "install")]
+pub struct InstallOpt {
+    #[structopt(name = "file", parse(from_os_str))]
+    /// the network descriptor file in json.
+    file: PathBuf,
+}
+
+pub struct InstallCommand;
+
+impl CommandAction for InstallCommand {
+    type State = ();
+    type GlobalOpt = StarcoinOpt;
+    type Opt = InstallOpt;
+    type ReturnItem = NetworkDescriptorView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let base_data_dir = base_data_dir(ctx.global_opt());
+        let descriptor =
+            NetworkDescriptor::install(base_data_dir.as_path(), ctx.opt().file.as_path())?;
+        Ok(descriptor.into())
+    }
+}
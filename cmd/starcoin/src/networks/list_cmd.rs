@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::networks::{base_data_dir, NetworkDescriptorView};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::NetworkDescriptor;
+use structopt::StructOpt;
+
+/// List the supported networks, the builtin networks and the installed third-party networks.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "list")]
+pub struct ListOpt {}
+
+pub struct ListCommand;
+
+impl CommandAction for ListCommand {
+    type State = ();
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ListOpt;
+    type ReturnItem = Vec<NetworkDescriptorView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let base_data_dir = base_data_dir(ctx.global_opt());
+        Ok(NetworkDescriptor::list(base_data_dir.as_path())?
+            .into_iter()
+            .map(NetworkDescriptorView::from)
+            .collect())
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The network descriptor commands only read or write the base data dir, they are stateless commands,
+//! so the cli does not start or connect the node to run them.
+
+mod install_cmd;
+mod list_cmd;
+
+pub use install_cmd::*;
+pub use list_cmd::*;
+
+use crate::StarcoinOpt;
+use serde::{Deserialize, Serialize};
+use starcoin_config::{NetworkDescriptor, DEFAULT_BASE_DATA_DIR};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkDescriptorView {
+    pub name: String,
+    /// The value of the `net` option to join the network.
+    pub net: String,
+    pub chain_id: u8,
+    pub builtin: bool,
+    pub boot_nodes: Vec<String>,
+    pub faucet_url: Option<String>,
+    pub explorer_url: Option<String>,
+}
+
+impl From<NetworkDescriptor> for NetworkDescriptorView {
+    fn from(descriptor: NetworkDescriptor) -> Self {
+        let net = descriptor
+            .network_id()
+            .map(|id| id.to_string())
+            .unwrap_or_else(|_| format!("{}:{}", descriptor.name, descriptor.chain_id));
+        Self {
+            builtin: descriptor.is_builtin(),
+            net,
+            chain_id: descriptor.chain_id.id(),
+            boot_nodes: descriptor
+                .boot_nodes
+                .iter()
+                .map(|node| node.to_string())
+                .collect(),
+            faucet_url: descriptor.faucet_url,
+            explorer_url: descriptor.explorer_url,
+            name: descriptor.name,
+        }
+    }
+}
+
+/// The base data dir where the network descriptors are installed.
+fn base_data_dir(opt: &StarcoinOpt) -> PathBuf {
+    opt.base_data_dir
+        .clone()
+        .unwrap_or_else(|| DEFAULT_BASE_DATA_DIR.to_path_buf())
+}
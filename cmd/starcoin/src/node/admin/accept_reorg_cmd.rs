@@ -0,0 +1,37 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_sync_api::PendingReorg;
+use structopt::StructOpt;
+
+/// Accept the reorg which is deeper than the max reorg depth, and switch the main chain to its branch.
+/// The pending reorg is alerted in the node log.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "accept-reorg")]
+pub struct AcceptReorgOpt {
+    /// the branch head of the pending reorg, the reorg is refused if the pending one has a different head.
+    #[structopt(long = "branch-head")]
+    branch_head: Option<HashValue>,
+}
+
+pub struct AcceptReorgCommand;
+
+impl CommandAction for AcceptReorgCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = AcceptReorgOpt;
+    type ReturnItem = PendingReorg;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_accept_reorg(ctx.opt().branch_head)
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::StringView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use structopt::StructOpt;
+
+/// Approve the txn the sign policy of the node asks for, then the requester signs it again.
+/// The approval does not cover the sequence number and the expiration of the txn, so the txn can be rebuilt before signing again.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "approve-txn")]
+pub struct ApproveTxnOpt {
+    #[structopt(name = "approval-hash")]
+    /// The approval hash of the txn in `node admin pending-txn-approvals`.
+    approval_hash: HashValue,
+}
+
+pub struct ApproveTxnCommand;
+
+impl CommandAction for ApproveTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ApproveTxnOpt;
+    type ReturnItem = StringView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ctx.state().client().node_approve_txn(opt.approval_hash)?;
+        Ok(StringView {
+            result: opt.approval_hash.to_string(),
+        })
+    }
+}
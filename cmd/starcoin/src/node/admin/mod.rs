@@ -0,0 +1,10 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod accept_reorg_cmd;
+mod approve_txn_cmd;
+mod pending_txn_approvals_cmd;
+
+pub use accept_reorg_cmd::*;
+pub use approve_txn_cmd::*;
+pub use pending_txn_approvals_cmd::*;
@@ -0,0 +1,30 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::PendingTxnApproval;
+use structopt::StructOpt;
+
+/// List the txns the sign policy of the node asks the operator to approve.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "pending-txn-approvals")]
+pub struct PendingTxnApprovalsOpt {}
+
+pub struct PendingTxnApprovalsCommand;
+
+impl CommandAction for PendingTxnApprovalsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = PendingTxnApprovalsOpt;
+    type ReturnItem = Vec<PendingTxnApproval>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        ctx.state().client().node_pending_txn_approvals()
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod tail_cmd;
+
+pub use tail_cmd::*;
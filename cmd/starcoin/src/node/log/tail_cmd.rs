@@ -0,0 +1,41 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_logger::LogSubsystem;
+use structopt::StructOpt;
+
+/// Show the last lines of the node log file.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "tail")]
+pub struct TailOpt {
+    #[structopt(short = "s", long = "subsystem")]
+    /// the subsystem log to show, one of network, txpool, miner, rpc. if absent, show the main log.
+    subsystem: Option<LogSubsystem>,
+
+    #[structopt(short = "n", long = "lines", default_value = "100")]
+    /// how many lines to show, should not be greater than the max lines of the node's rpc config.
+    lines: usize,
+}
+
+pub struct TailCommand;
+
+impl CommandAction for TailCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TailOpt;
+    type ReturnItem = Vec<String>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ctx.state()
+            .client()
+            .debug_log_tail(opt.subsystem, opt.lines)
+    }
+}
@@ -0,0 +1,8 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod next_cmd;
+mod status_cmd;
+
+pub use next_cmd::*;
+pub use status_cmd::*;
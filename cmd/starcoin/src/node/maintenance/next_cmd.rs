@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_node_api::maintenance::{MaintenanceTask, MaintenanceTaskState};
+use structopt::StructOpt;
+
+/// Show the next maintenance task to run.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "next")]
+pub struct NextOpt {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NextMaintenanceView {
+    pub task: MaintenanceTask,
+    pub schedule: String,
+    pub state: MaintenanceTaskState,
+    pub next_run_at: u64,
+    /// Seconds to the next run, 0 if it is due.
+    pub next_run_in: u64,
+}
+
+pub struct NextCommand;
+
+impl CommandAction for NextCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = NextOpt;
+    type ReturnItem = NextMaintenanceView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let status = client.node_maintenance_status()?;
+        let next = status
+            .next()
+            .ok_or_else(|| format_err!("No maintenance task is scheduled."))?;
+        Ok(NextMaintenanceView {
+            task: next.task,
+            schedule: next.schedule.clone(),
+            state: next.state.clone(),
+            next_run_at: next.next_run_at,
+            next_run_in: next.next_run_at.saturating_sub(status.now),
+        })
+    }
+}
@@ -4,7 +4,11 @@
 mod info_cmd;
 mod metrics_cmd;
 mod peers_cmd;
+mod report_cmd;
 
+pub mod admin;
+pub mod log;
+pub mod maintenance;
 pub mod network;
 pub mod service;
 pub mod sync;
@@ -12,3 +16,4 @@ pub mod sync;
 pub use info_cmd::*;
 pub use metrics_cmd::*;
 pub use peers_cmd::*;
+pub use report_cmd::*;
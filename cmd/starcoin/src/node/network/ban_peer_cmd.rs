@@ -0,0 +1,43 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::BannedPeerView;
+use starcoin_types::peer_info::PeerId;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "ban_peer")]
+///Disconnect and ban a peer, the ban list is kept across node restarts
+pub struct BanPeerOpt {
+    #[structopt(name = "peer-id")]
+    peer_id: PeerId,
+    #[structopt(
+        short = "d",
+        long = "duration",
+        help = "ban the peer for how long(in seconds) from now",
+        default_value = "86400"
+    )]
+    duration: u64,
+}
+
+pub struct BanPeerCommand;
+
+impl CommandAction for BanPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BanPeerOpt;
+    type ReturnItem = BannedPeerView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.network_ban_peer(opt.peer_id.to_string(), opt.duration)
+    }
+}
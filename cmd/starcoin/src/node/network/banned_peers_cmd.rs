@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::BannedPeerView;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "banned_peers")]
+///List banned peers
+pub struct BannedPeersOpt {}
+
+pub struct BannedPeersCommand;
+
+impl CommandAction for BannedPeersCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BannedPeersOpt;
+    type ReturnItem = Vec<BannedPeerView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_banned_peers()
+    }
+}
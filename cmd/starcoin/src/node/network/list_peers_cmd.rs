@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::PeerStatusView;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "list_peers")]
+///List connected peers with latency and reputation
+pub struct ListPeersOpt {}
+
+pub struct ListPeersCommand;
+
+impl CommandAction for ListPeersCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ListPeersOpt;
+    type ReturnItem = Vec<PeerStatusView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_peers()
+    }
+}
@@ -2,13 +2,29 @@
 // Copyright (c) The Starcoin Core Contributors
 
 mod add_peer_cmd;
+mod ban_peer_cmd;
+mod banned_peers_cmd;
 mod call_peer_cmd;
 mod get_address_cmd;
 mod known_peers_cmd;
+mod list_peers_cmd;
+mod rotate_identity_cmd;
 mod state_cmd;
+mod trust_peer_cmd;
+mod trusted_peers_cmd;
+mod unban_peer_cmd;
+mod untrust_peer_cmd;
 
 pub use add_peer_cmd::*;
+pub use ban_peer_cmd::*;
+pub use banned_peers_cmd::*;
 pub use call_peer_cmd::*;
 pub use get_address_cmd::*;
 pub use known_peers_cmd::*;
+pub use list_peers_cmd::*;
+pub use rotate_identity_cmd::*;
 pub use state_cmd::*;
+pub use trust_peer_cmd::*;
+pub use trusted_peers_cmd::*;
+pub use unban_peer_cmd::*;
+pub use untrust_peer_cmd::*;
@@ -0,0 +1,32 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::IdentityRotationView;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "rotate_identity")]
+///Rotate the network identity key, restart the node to use the new identity.
+///The peers carry over the reputation, ban and trust of the old identity.
+pub struct RotateIdentityOpt {}
+
+pub struct RotateIdentityCommand;
+
+impl CommandAction for RotateIdentityCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RotateIdentityOpt;
+    type ReturnItem = IdentityRotationView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_rotate_identity()
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "trust_peer")]
+///Add a trusted peer, the node always keeps connection with it, and remembers it across restarts
+pub struct TrustPeerOpt {
+    #[structopt(name = "peer")]
+    /// format: multiaddr/p2p/peer_id
+    peer: String,
+}
+
+pub struct TrustPeerCommand;
+
+impl CommandAction for TrustPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TrustPeerOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_trust_peer(ctx.opt().peer.clone())
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "trusted_peers")]
+///List trusted peers
+pub struct TrustedPeersOpt {}
+
+pub struct TrustedPeersCommand;
+
+impl CommandAction for TrustedPeersCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TrustedPeersOpt;
+    type ReturnItem = Vec<String>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_trusted_peers()
+    }
+}
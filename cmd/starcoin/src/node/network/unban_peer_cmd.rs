@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_types::peer_info::PeerId;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "unban_peer")]
+///Remove a peer from the ban list
+pub struct UnbanPeerOpt {
+    #[structopt(name = "peer-id")]
+    peer_id: PeerId,
+}
+
+pub struct UnbanPeerCommand;
+
+impl CommandAction for UnbanPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UnbanPeerOpt;
+    type ReturnItem = bool;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_unban_peer(ctx.opt().peer_id.to_string())
+    }
+}
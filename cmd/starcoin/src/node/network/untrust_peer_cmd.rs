@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "untrust_peer")]
+///Remove a trusted peer
+pub struct UntrustPeerOpt {
+    #[structopt(name = "peer")]
+    /// format: multiaddr/p2p/peer_id
+    peer: String,
+}
+
+pub struct UntrustPeerCommand;
+
+impl CommandAction for UntrustPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UntrustPeerOpt;
+    type ReturnItem = bool;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_untrust_peer(ctx.opt().peer.clone())
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_node_api::usage::UsageReport;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// A time span as `<number><unit>`, the unit is one of `s`, `m`, `h` and `d`, such as `24h`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TimeSpan(u64);
+
+impl TimeSpan {
+    pub fn seconds(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for TimeSpan {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let unit_position = s
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| s.len());
+        let number = u64::from_str(&s[..unit_position])
+            .map_err(|_| format_err!("Invalid time span {}, expect such as 24h", s))?;
+        let unit_seconds = match &s[unit_position..] {
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            unit => return Err(format_err!("Unknown time unit {}", unit)),
+        };
+        let seconds = number
+            .checked_mul(unit_seconds)
+            .ok_or_else(|| format_err!("Time span {} is too large", s))?;
+        ensure!(seconds > 0, "Time span should be greater than zero");
+        Ok(Self(seconds))
+    }
+}
+
+/// Summarize the local usage stats of the node, the report can be attached to the bug report.
+/// The stats are recorded locally, and never sent out of the node.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "report")]
+pub struct ReportOpt {
+    /// the time span of the report, such as 30m, 24h or 7d.
+    #[structopt(long = "last", default_value = "24h")]
+    last: TimeSpan,
+}
+
+pub struct ReportCommand;
+
+impl CommandAction for ReportCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReportOpt;
+    type ReturnItem = UsageReport;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_usage_report(ctx.opt().last.seconds())
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compose the commands by piping the json output of a command to the stdin of another, such as
+//! `starcoin -o jsonl account list | starcoin account sweep --from-stdin --to <address>`.
+//! The input of a command with `--from-stdin` is parsed by `scmd::parse_piped_values`,
+//! the schema of the items is documented at the reader of the command input.
+
+use anyhow::{bail, ensure, Result};
+use serde_json::Value;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+
+/// Read the account addresses piped from the stdin, an item of the input is one of:
+/// - an address string, such as `"0x1"`;
+/// - an object with the `address` field, such as the items of `account list`;
+/// - an object with the `account` object field, such as the output of `account show`;
+/// - an object with the `accounts` array field, such as the output of `account list --with-balance`.
+///
+/// The duplicate addresses are removed, and the order of the input is kept.
+pub fn read_piped_addresses() -> Result<Vec<AccountAddress>> {
+    ensure!(
+        !atty::is(atty::Stream::Stdin),
+        "--from-stdin requires the input piped from another command."
+    );
+    collect_addresses(scmd::read_piped_values()?)
+}
+
+fn collect_addresses(values: Vec<Value>) -> Result<Vec<AccountAddress>> {
+    let mut addresses = vec![];
+    for value in values {
+        collect_address(value, &mut addresses)?;
+    }
+    Ok(addresses)
+}
+
+fn collect_address(value: Value, addresses: &mut Vec<AccountAddress>) -> Result<()> {
+    match value {
+        Value::String(address) => {
+            let address = parse_address(address.as_str())?;
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        Value::Object(mut obj) => {
+            if let Some(address @ Value::String(_)) = obj.remove("address") {
+                collect_address(address, addresses)?;
+            } else if let Some(account @ Value::Object(_)) = obj.remove("account") {
+                collect_address(account, addresses)?;
+            } else if let Some(Value::Array(accounts)) = obj.remove("accounts") {
+                for account in accounts {
+                    collect_address(account, addresses)?;
+                }
+            } else {
+                bail!("Invalid account input item, the address field is missing.");
+            }
+        }
+        value => bail!("Invalid account input item: {}", value),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collect_addresses() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let values = vec![
+            json!(alice.to_string()),
+            json!({"address": bob.to_string(), "is_default": false}),
+            json!({"account": {"address": alice.to_string()}, "sequence_number": 1}),
+            json!({"accounts": [{"account": {"address": bob.to_string()}, "balances": {}}]}),
+        ];
+        assert_eq!(collect_addresses(values).unwrap(), vec![alice, bob]);
+        assert!(collect_addresses(vec![json!({"addr": alice.to_string()})]).is_err());
+        assert!(collect_addresses(vec![json!(1)]).is_err());
+        assert!(collect_addresses(vec![json!("not an address")]).is_err());
+    }
+}
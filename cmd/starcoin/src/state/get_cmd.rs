@@ -9,7 +9,8 @@ use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::types::AnnotatedMoveStructView;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_types::access_path::AccessPath;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use starcoin_vm_types::account_config::account_struct_tag;
 use starcoin_vm_types::language_storage::StructTag;
 use starcoin_vm_types::parser::parse_struct_tag;
@@ -19,12 +20,15 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(name = "get")]
 pub struct GetOpt {
-    #[structopt(short = "a", long = "addr")]
+    #[structopt(short = "a", long = "addr", parse(try_from_str = parse_address))]
     /// address which the resource is under of. Default to default account address.
     account_address: Option<AccountAddress>,
     #[structopt(name = "struct-tag", parse(try_from_str = parse_struct_tag))]
     /// resource type to get. Default to 0x1::Account::Account
     struct_tag: Option<StructTag>,
+    #[structopt(name = "block-number", long, short = "b")]
+    /// get the resource at the state of the block number, default to the latest state.
+    block_number: Option<BlockNumber>,
 }
 
 pub struct GetCommand;
@@ -49,6 +53,17 @@ impl CommandAction for GetCommand {
             Some(s) => s.clone(),
             None => account_struct_tag(),
         };
+        if let Some(block_number) = opt.block_number {
+            return client
+                .state_get_resource_at(account_addr, struct_tag, block_number)?
+                .ok_or_else(|| {
+                    format_err!(
+                        "Account with address {} state not exist at block {}.",
+                        account_addr,
+                        block_number
+                    )
+                });
+        }
         let state = client
             .state_get(AccessPath::resource_access_path(
                 account_addr,
@@ -7,14 +7,14 @@ use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::{AnnotatedMoveStructView, StructTagView};
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use std::collections::BTreeMap;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "list-resource")]
 pub struct ListResourceOpt {
-    #[structopt(name = "address")]
+    #[structopt(name = "address", parse(try_from_str = parse_address))]
     /// address which the resources is under of.
     account_address: AccountAddress,
     #[structopt(name = "state_root")]
@@ -0,0 +1,49 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The standalone tools, which work without the node, they are stateless commands,
+//! so the cli does not start or connect the node to run them.
+
+mod receipt_decode_cmd;
+mod receipt_encode_cmd;
+
+pub use receipt_decode_cmd::*;
+pub use receipt_encode_cmd::*;
+
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::receipt_identifier::ReceiptIdentifier;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReceiptIdentifierView {
+    pub version: u8,
+    pub address: AccountAddress,
+    pub auth_key: Option<AuthenticationKey>,
+    /// The payment reference as utf8, the invalid bytes are replaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_reference: Option<String>,
+    /// The raw payment reference bytes in hex.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_reference_hex: Option<String>,
+    pub receipt_identifier: ReceiptIdentifier,
+}
+
+impl From<ReceiptIdentifier> for ReceiptIdentifierView {
+    fn from(receipt_identifier: ReceiptIdentifier) -> Self {
+        let version = match &receipt_identifier {
+            ReceiptIdentifier::V1(..) => 1,
+            ReceiptIdentifier::V2(..) => 2,
+        };
+        let payment_reference = receipt_identifier.payment_reference();
+        Self {
+            version,
+            address: receipt_identifier.address(),
+            auth_key: receipt_identifier.auth_key().cloned(),
+            payment_reference: payment_reference
+                .map(|reference| String::from_utf8_lossy(reference).to_string()),
+            payment_reference_hex: payment_reference.map(hex::encode),
+            receipt_identifier,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tools::ReceiptIdentifierView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_types::receipt_identifier::ReceiptIdentifier;
+use structopt::StructOpt;
+
+/// Decode a receipt identifier, such as stc1..., the error explains where the input is invalid.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "decode")]
+pub struct ReceiptDecodeOpt {
+    #[structopt(name = "receipt_identifier")]
+    receipt_identifier: String,
+}
+
+pub struct ReceiptDecodeCommand;
+
+impl CommandAction for ReceiptDecodeCommand {
+    type State = ();
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReceiptDecodeOpt;
+    type ReturnItem = ReceiptIdentifierView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let receipt_identifier = ReceiptIdentifier::parse(ctx.opt().receipt_identifier.as_str())?;
+        Ok(receipt_identifier.into())
+    }
+}
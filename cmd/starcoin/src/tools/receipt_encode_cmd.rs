@@ -0,0 +1,66 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tools::ReceiptIdentifierView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountPublicKey;
+use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_types::account_address::{parse_address, AccountAddress};
+use starcoin_types::receipt_identifier::ReceiptIdentifier;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+use structopt::StructOpt;
+
+/// Encode an address, and optionally an auth key, into a receipt identifier.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "encode")]
+pub struct ReceiptEncodeOpt {
+    /// the hex encoded address, such as 0x1.
+    #[structopt(name = "address", parse(try_from_str = parse_address))]
+    address: AccountAddress,
+
+    /// the hex encoded auth key of the address.
+    #[structopt(name = "auth_key")]
+    auth_key: Option<AuthenticationKey>,
+
+    /// derive the auth key from the public key, instead of the auth key argument.
+    #[structopt(long = "public-key", conflicts_with = "auth_key")]
+    public_key: Option<String>,
+
+    /// encode the payment reference, such as an order id or memo, into a v2 receipt identifier.
+    #[structopt(long = "payment-reference")]
+    payment_reference: Option<String>,
+}
+
+pub struct ReceiptEncodeCommand;
+
+impl CommandAction for ReceiptEncodeCommand {
+    type State = ();
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReceiptEncodeOpt;
+    type ReturnItem = ReceiptIdentifierView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let auth_key = match (opt.auth_key, opt.public_key.as_ref()) {
+            (Some(auth_key), _) => Some(auth_key),
+            (None, Some(public_key)) => Some(
+                AccountPublicKey::from_encoded_string(public_key.as_str())?.authentication_key(),
+            ),
+            (None, None) => None,
+        };
+        let receipt_identifier = match opt.payment_reference.as_ref() {
+            Some(payment_reference) => ReceiptIdentifier::v2(
+                opt.address,
+                auth_key,
+                Some(payment_reference.as_bytes().to_vec()),
+            )?,
+            None => ReceiptIdentifier::v1(opt.address, auth_key),
+        };
+        Ok(receipt_identifier.into())
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::txpool::watch_dir_cmd::decode_signed_txn;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_txpool::{generate_committee, KeyShare, ThresholdCommittee};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+const COMMITTEE_FILE_NAME: &str = "committee.json";
+
+/// Generate a threshold committee and the key shares of the members by a trusted dealer,
+/// write the `committee.json` and the `key_share_<index>.json` files to the output dir.
+/// It is only for the research networks, every member should keep its key share secret.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "generate-committee")]
+pub struct GenerateCommitteeOpt {
+    #[structopt(short = "t", long = "threshold")]
+    /// the number of the decryption shares required to decrypt a txn.
+    threshold: u32,
+    #[structopt(short = "n", long = "members")]
+    /// the number of the committee members.
+    members: u32,
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    /// the output dir.
+    output: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenerateCommitteeView {
+    pub committee_file: PathBuf,
+    pub key_share_files: Vec<PathBuf>,
+}
+
+pub struct GenerateCommitteeCommand;
+
+impl CommandAction for GenerateCommitteeCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenerateCommitteeOpt;
+    type ReturnItem = GenerateCommitteeView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let (committee, key_shares) = generate_committee(opt.threshold, opt.members)?;
+        std::fs::create_dir_all(opt.output.as_path())?;
+        let committee_file = opt.output.join(COMMITTEE_FILE_NAME);
+        std::fs::write(
+            committee_file.as_path(),
+            serde_json::to_vec_pretty(&committee.to_config())?,
+        )?;
+        let mut key_share_files = vec![];
+        for key_share in key_shares {
+            let key_share_file = opt
+                .output
+                .join(format!("key_share_{}.json", key_share.index()));
+            std::fs::write(
+                key_share_file.as_path(),
+                serde_json::to_vec_pretty(&key_share.to_config())?,
+            )?;
+            key_share_files.push(key_share_file);
+        }
+        Ok(GenerateCommitteeView {
+            committee_file,
+            key_share_files,
+        })
+    }
+}
+
+/// Encrypt a signed txn to the threshold committee and submit it to the encrypted pool of the node.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "submit-encrypted")]
+pub struct SubmitEncryptedOpt {
+    #[structopt(long = "committee-file", parse(from_os_str))]
+    /// the threshold committee json file of the encrypted pool.
+    committee_file: PathBuf,
+    #[structopt(name = "txn-file", parse(from_os_str))]
+    /// the file contains the bcs bytes or the hex string of the signed txn.
+    txn_file: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubmitEncryptedView {
+    pub encrypted_txn_id: HashValue,
+    pub txn_hash: HashValue,
+}
+
+pub struct SubmitEncryptedTxnCommand;
+
+impl CommandAction for SubmitEncryptedTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubmitEncryptedOpt;
+    type ReturnItem = SubmitEncryptedView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let committee = ThresholdCommittee::load(opt.committee_file.as_path())?;
+        let txn = decode_signed_txn(&std::fs::read(opt.txn_file.as_path())?)?;
+        let encrypted_txn = committee.encrypt(&txn)?;
+        let encrypted_txn_id = ctx
+            .state()
+            .client()
+            .submit_encrypted_transaction(encrypted_txn)?;
+        Ok(SubmitEncryptedView {
+            encrypted_txn_id,
+            txn_hash: txn.id(),
+        })
+    }
+}
+
+/// Release the decryption shares of the sealed encrypted txns in the node with the key share of a committee member.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "release-decryption-shares")]
+pub struct ReleaseDecryptionSharesOpt {
+    #[structopt(long = "key-share-file", parse(from_os_str))]
+    /// the key share json file of the committee member.
+    key_share_file: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReleaseDecryptionSharesView {
+    pub released: usize,
+    /// The hashes of the txns decrypted by the released shares.
+    pub decrypted_txns: Vec<HashValue>,
+}
+
+pub struct ReleaseDecryptionSharesCommand;
+
+impl CommandAction for ReleaseDecryptionSharesCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReleaseDecryptionSharesOpt;
+    type ReturnItem = ReleaseDecryptionSharesView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let key_share = KeyShare::load(ctx.opt().key_share_file.as_path())?;
+        let shares = client
+            .sealed_encrypted_transactions()?
+            .iter()
+            .map(|sealed| key_share.decryption_share(&sealed.txn))
+            .collect::<Result<Vec<_>>>()?;
+        let released = shares.len();
+        let decrypted_txns = if shares.is_empty() {
+            vec![]
+        } else {
+            client.submit_decryption_shares(shares)?
+        };
+        Ok(ReleaseDecryptionSharesView {
+            released,
+            decrypted_txns,
+        })
+    }
+}
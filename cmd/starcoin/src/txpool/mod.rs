@@ -7,10 +7,18 @@ use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::SignedUserTransactionView;
-use starcoin_txpool_api::TxPoolStatus;
-use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_txpool_api::{TxPoolAnalytics, TxPoolStatus};
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
 use structopt::StructOpt;
 
+#[cfg(feature = "threshold-encryption")]
+pub use encrypted_pool_cmd::*;
+pub use watch_dir_cmd::*;
+
+#[cfg(feature = "threshold-encryption")]
+mod encrypted_pool_cmd;
+mod watch_dir_cmd;
+
 /// Get txn data by its hash
 #[derive(Debug, StructOpt)]
 #[structopt(name = "pending-txn")]
@@ -42,7 +50,11 @@ impl CommandAction for PendingTxnCommand {
 #[derive(Debug, StructOpt)]
 #[structopt(name = "pending-txns")]
 pub struct PendingTxnsOpt {
-    #[structopt(name = "sender", help = "sender of pending txns")]
+    #[structopt(
+        name = "sender",
+        help = "sender of pending txns",
+        parse(try_from_str = parse_address)
+    )]
     sender: AccountAddress,
     #[structopt(name = "max-len", long = "max", help = "max num to return")]
     max_len: Option<u32>,
@@ -88,3 +100,32 @@ impl CommandAction for TxPoolStatusCommand {
         client.txpool_status()
     }
 }
+
+/// Get the analytics of the txns in tx pool, include the readiness, the gas price distribution and the top senders.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "analytics")]
+pub struct TxPoolAnalyticsOpt {
+    #[structopt(
+        name = "top-senders",
+        long,
+        help = "num of the top senders to return, default to 10"
+    )]
+    top_senders: Option<u32>,
+}
+
+pub struct TxPoolAnalyticsCommand;
+
+impl CommandAction for TxPoolAnalyticsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TxPoolAnalyticsOpt;
+    type ReturnItem = TxPoolAnalytics;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_analytics(ctx.opt().top_senders)
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_vm_types::transaction::SignedUserTransaction;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use structopt::StructOpt;
+
+pub const DONE_DIR: &str = "done";
+pub const FAILED_DIR: &str = "failed";
+/// Files with these extensions are still being written, skip them.
+const PARTIAL_EXTENSIONS: [&str; 2] = ["tmp", "partial"];
+const RESULT_SUFFIX: &str = ".result.json";
+
+/// Watch a directory for signed transaction files, validate and submit each of them,
+/// then move it to the `done` or `failed` sub directory with a `<file>.result.json` result file.
+/// A file contains the bcs bytes or the hex string of a signed transaction,
+/// write it as `*.tmp` or `*.partial` and rename it after it is written completely.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "watch-dir")]
+pub struct WatchDirOpt {
+    #[structopt(name = "dir", parse(from_os_str))]
+    /// the directory to watch.
+    dir: PathBuf,
+    #[structopt(short = "i", long = "interval", default_value = "1000")]
+    /// interval(in ms) to scan the directory.
+    interval: u64,
+    #[structopt(long = "once")]
+    /// process the current files in the directory, then exit.
+    once: bool,
+    #[structopt(short = "b", long = "blocking")]
+    /// wait every txn mined before processing the next file.
+    blocking: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxnFileResult {
+    pub file: String,
+    pub txn_hash: Option<HashValue>,
+    pub submitted: bool,
+    pub error: Option<String>,
+    /// The error of waiting the submitted txn mined, the txn may still be mined later.
+    pub watch_error: Option<String>,
+    /// Unix timestamp in seconds.
+    pub processed_at: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WatchDirSummary {
+    pub submitted: u64,
+    pub failed: u64,
+    /// The submitted txns which are not confirmed mined.
+    pub watch_failed: u64,
+    /// The processed files which can not be archived, they are skipped by the following scans.
+    pub archive_failed: u64,
+}
+
+/// Decode a signed txn from the bcs bytes or the hex string with optional `0x` prefix.
+pub(crate) fn decode_signed_txn(bytes: &[u8]) -> Result<SignedUserTransaction> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        let text = text.trim();
+        let text = text.strip_prefix("0x").unwrap_or(text);
+        if let Ok(decoded) = hex::decode(text) {
+            return bcs_ext::from_bytes(&decoded);
+        }
+    }
+    bcs_ext::from_bytes(bytes)
+}
+
+/// List the txn files to process, sorted by file name.
+fn list_txn_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with('.'))
+            .unwrap_or(true);
+        let is_partial = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| PARTIAL_EXTENSIONS.contains(&ext))
+            .unwrap_or(false);
+        if !is_hidden && !is_partial {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The file name in `target_dir` which does not exist, a number is appended if the name is taken,
+/// so a file with the same name as an archived one does not overwrite it.
+fn unique_file_name(target_dir: &Path, file_name: &str) -> String {
+    let is_taken = |name: &str| {
+        target_dir.join(name).exists()
+            || target_dir
+                .join(format!("{}{}", name, RESULT_SUFFIX))
+                .exists()
+    };
+    if !is_taken(file_name) {
+        return file_name.to_string();
+    }
+    (1u64..)
+        .map(|n| format!("{}.{}", file_name, n))
+        .find(|name| !is_taken(name))
+        .expect("the unique file name should exist")
+}
+
+/// Move the file to `target_dir` with its result file, return the archived path.
+fn archive(file: &Path, target_dir: &Path, result: &TxnFileResult) -> Result<PathBuf> {
+    fs::create_dir_all(target_dir)?;
+    let target_file = target_dir.join(unique_file_name(target_dir, result.file.as_str()));
+    fs::rename(file, target_file.as_path())?;
+    let result_file = PathBuf::from(format!("{}{}", target_file.display(), RESULT_SUFFIX));
+    fs::write(result_file, serde_json::to_vec_pretty(result)?)?;
+    Ok(target_file)
+}
+
+pub struct WatchDirCommand;
+
+impl WatchDirCommand {
+    fn submit(
+        ctx: &ExecContext<CliState, StarcoinOpt, WatchDirOpt>,
+        file: &Path,
+    ) -> Result<HashValue> {
+        let signed_txn = decode_signed_txn(&fs::read(file)?)?;
+        ctx.state().ensure_chain_id(signed_txn.chain_id())?;
+        let txn_hash = signed_txn.id();
+        signed_txn.clone().check_signature()?;
+        ctx.state().client().submit_transaction(signed_txn)?;
+        Ok(txn_hash)
+    }
+
+    /// Process the file, return false if the file can not be archived.
+    fn process(
+        ctx: &ExecContext<CliState, StarcoinOpt, WatchDirOpt>,
+        file: &Path,
+        summary: &mut WatchDirSummary,
+    ) -> Result<bool> {
+        let result = Self::submit(ctx, file);
+        // the submitted txn is done even if the watch fails, it should not be submitted again.
+        let watch_result = match &result {
+            Ok(txn_hash) if ctx.opt().blocking => ctx.state().watch_txn(*txn_hash).map(|_| ()),
+            _ => Ok(()),
+        };
+        let file_result = TxnFileResult {
+            file: file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            txn_hash: result.as_ref().ok().cloned(),
+            submitted: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+            watch_error: watch_result.as_ref().err().map(|e| e.to_string()),
+            processed_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        let target_dir = match &result {
+            Ok(txn_hash) => {
+                match &watch_result {
+                    Ok(()) => println!("{} submitted, txn {:#x}", file.display(), txn_hash),
+                    Err(e) => {
+                        println!(
+                            "{} submitted, txn {:#x}, but watch failed: {}",
+                            file.display(),
+                            txn_hash,
+                            e
+                        );
+                        summary.watch_failed += 1;
+                    }
+                }
+                summary.submitted += 1;
+                DONE_DIR
+            }
+            Err(e) => {
+                println!("{} failed: {}", file.display(), e);
+                summary.failed += 1;
+                FAILED_DIR
+            }
+        };
+        match archive(file, &ctx.opt().dir.join(target_dir), &file_result) {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                error!(
+                    "Archive {} to {} failed: {:?}",
+                    file.display(),
+                    target_dir,
+                    e
+                );
+                summary.archive_failed += 1;
+                Ok(false)
+            }
+        }
+    }
+}
+
+impl CommandAction for WatchDirCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = WatchDirOpt;
+    type ReturnItem = WatchDirSummary;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        if !opt.dir.is_dir() {
+            return Err(format_err!("{} is not a directory", opt.dir.display()));
+        }
+        let mut summary = WatchDirSummary::default();
+        // the processed files which can not be archived, skip them, so they are not submitted again.
+        let mut unarchived = HashSet::new();
+        if !opt.once {
+            println!(
+                "Watching {} for signed txn files, Press Ctrl-C to quit",
+                opt.dir.display()
+            );
+        }
+        loop {
+            for file in list_txn_files(opt.dir.as_path())? {
+                if unarchived.contains(&file) {
+                    continue;
+                }
+                if !Self::process(ctx, file.as_path(), &mut summary)? {
+                    unarchived.insert(file);
+                }
+            }
+            if opt.once {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(opt.interval));
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcs_ext::Sample;
+
+    #[test]
+    fn test_decode_signed_txn() {
+        let txn = SignedUserTransaction::sample();
+        let bytes = bcs_ext::to_bytes(&txn).unwrap();
+        assert_eq!(decode_signed_txn(&bytes).unwrap(), txn);
+        let hex_str = format!("0x{}\n", hex::encode(&bytes));
+        assert_eq!(decode_signed_txn(hex_str.as_bytes()).unwrap(), txn);
+        assert!(decode_signed_txn(b"0xinvalid").is_err());
+    }
+
+    #[test]
+    fn test_archive_unique_name() {
+        let dir = starcoin_config::temp_path();
+        let target_dir = dir.path().join(DONE_DIR);
+        let result = |file: &str| TxnFileResult {
+            file: file.to_string(),
+            txn_hash: None,
+            submitted: true,
+            error: None,
+            watch_error: None,
+            processed_at: 0,
+        };
+        let mut archived = vec![];
+        for content in &["first", "second", "third"] {
+            let file = dir.path().join("txn");
+            fs::write(file.as_path(), content).unwrap();
+            archived.push(archive(file.as_path(), target_dir.as_path(), &result("txn")).unwrap());
+        }
+        assert_eq!(
+            archived,
+            vec![
+                target_dir.join("txn"),
+                target_dir.join("txn.1"),
+                target_dir.join("txn.2")
+            ]
+        );
+        for (path, content) in archived.iter().zip(&["first", "second", "third"]) {
+            assert_eq!(fs::read_to_string(path).unwrap(), *content);
+            assert!(PathBuf::from(format!("{}{}", path.display(), RESULT_SUFFIX)).exists());
+        }
+        // the archive error is returned, and the file is kept.
+        let file = dir.path().join("txn");
+        fs::write(file.as_path(), "fourth").unwrap();
+        assert!(archive(file.as_path(), file.as_path(), &result("txn")).is_err());
+        assert!(file.exists());
+    }
+}
@@ -9,7 +9,9 @@ use starcoin_rpc_api::types::{
     StrView, TransactionEventView, TransactionOutputAction, TransactionOutputView,
     TransactionVMStatus,
 };
-use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_address::{
+    looks_like_receipt_identifier, parse_address, AccountAddress,
+};
 use starcoin_types::account_config::{DepositEvent, MintEvent, WithdrawEvent};
 use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::language_storage::TypeTag;
@@ -21,7 +23,7 @@ use starcoin_vm_types::move_resource::MoveResource;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum AddressOrReceipt {
     Address(AccountAddress),
     Receipt(ReceiptIdentifier),
@@ -45,7 +47,7 @@ impl AddressOrReceipt {
 
     pub fn as_receipt(&self) -> Option<ReceiptIdentifier> {
         match self {
-            AddressOrReceipt::Receipt(receipt) => Some(*receipt),
+            AddressOrReceipt::Receipt(receipt) => Some(receipt.clone()),
             _ => None,
         }
     }
@@ -55,10 +57,10 @@ impl FromStr for AddressOrReceipt {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(if s.starts_with("stc") {
-            AddressOrReceipt::Receipt(ReceiptIdentifier::decode(s)?)
+        Ok(if looks_like_receipt_identifier(s) {
+            AddressOrReceipt::Receipt(ReceiptIdentifier::parse(s)?)
         } else {
-            AddressOrReceipt::Address(AccountAddress::from_hex_literal(s)?)
+            AddressOrReceipt::Address(parse_address(s)?)
         })
     }
 }
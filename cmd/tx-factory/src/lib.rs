@@ -1 +1,5 @@
+pub mod metrics;
+pub mod report;
+pub mod runner;
 pub mod txn_generator;
+pub mod workload;
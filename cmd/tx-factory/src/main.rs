@@ -11,10 +11,13 @@ use starcoin_rpc_api::types::FactoryAction;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_rpc_client::RpcClient;
 use starcoin_state_api::{ChainStateReader, StateReaderExt};
+use starcoin_tx_factory::runner::{run_workload, WorkloadOptions};
 use starcoin_tx_factory::txn_generator::MockTxnGenerator;
+use starcoin_tx_factory::workload::{WorkloadConfig, WorkloadGenerator, WorkloadProfile};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::association_address;
 use starcoin_types::transaction::RawUserTransaction;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -83,6 +86,48 @@ pub struct TxFactoryOpt {
         help = "create account batch size"
     )]
     pub batch_size: u32,
+
+    #[structopt(
+        long,
+        help = "workload profile: transfer, mint, compute, write-set or mixed, run the workload benchmark instead of the mock txns if specified"
+    )]
+    pub profile: Option<WorkloadProfile>,
+    #[structopt(long, help = "target tps of the workload, unlimited if not specified")]
+    pub tps: Option<u64>,
+    #[structopt(
+        long,
+        default_value = "1",
+        help = "count of concurrent senders of the workload"
+    )]
+    pub senders: u32,
+    #[structopt(
+        long,
+        help = "duration(in seconds) of the workload, run until ctrl-c if not specified"
+    )]
+    pub duration: Option<u64>,
+    #[structopt(
+        long,
+        default_value = "10000",
+        help = "loop count of the compute workload script"
+    )]
+    pub compute_loop_count: u64,
+    #[structopt(
+        long,
+        default_value = "32",
+        help = "count of accounts created by a write-set workload txn"
+    )]
+    pub write_set_size: usize,
+    #[structopt(
+        long,
+        help = "address to export the prometheus metrics of the workload, such as 127.0.0.1:9102"
+    )]
+    pub metrics_address: Option<SocketAddr>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "path to write the json report of the workload"
+    )]
+    pub report_path: Option<PathBuf>,
 }
 
 const INITIAL_BALANCE: u128 = 1_000_000_000;
@@ -182,6 +227,57 @@ fn main() {
         stopping_signal_clone.store(true, Ordering::SeqCst);
     })
     .unwrap();
+
+    if let Some(profile) = opts.profile {
+        if let Some(metrics_address) = opts.metrics_address {
+            starcoin_metrics::metric_server::start_server(metrics_address);
+        }
+        let generator = WorkloadGenerator::new(
+            net.chain_id(),
+            WorkloadConfig {
+                compute_loop_count: opts.compute_loop_count,
+                write_set_size: opts.write_set_size,
+                ..Default::default()
+            },
+        )
+        .expect("workload generator init should success");
+        let accounts = tx_mocker
+            .get_accounts(opts.senders, batch_size)
+            .expect("create accounts should success");
+        info!(
+            "run workload {} with {} senders, target tps: {:?}",
+            profile,
+            accounts.len(),
+            opts.tps
+        );
+        let report = run_workload(
+            opts.ipc_path.clone(),
+            Arc::new(generator),
+            accounts,
+            receiver_address,
+            WorkloadOptions {
+                profile,
+                tps: opts.tps,
+                duration: opts.duration.map(Duration::from_secs),
+                watch_timeout: Duration::from_secs(watch_timeout as u64),
+                account_password: opts.account_password.clone(),
+                unlock_duration: Duration::from_secs(60 * 10),
+            },
+            stopping_signal,
+        )
+        .expect("run workload should success");
+        let report_json =
+            serde_json::to_string_pretty(&report).expect("serialize report should success");
+        println!("{}", report_json);
+        if let Some(report_path) = opts.report_path.as_ref() {
+            report
+                .save(report_path)
+                .expect("write workload report should success");
+        }
+        info!("txfactory: workload finished");
+        return;
+    }
+
     let handle = std::thread::spawn(move || {
         let accounts = tx_mocker
             .get_accounts(account_num, batch_size)
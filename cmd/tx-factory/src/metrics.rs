@@ -0,0 +1,66 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{
+    default_registry, register_histogram_vec, HistogramOpts, HistogramVec, Opts, PrometheusError,
+    UIntCounterVec,
+};
+
+pub static TX_FACTORY_METRICS: Lazy<TxFactoryMetrics> =
+    Lazy::new(|| TxFactoryMetrics::register().expect("TxFactoryMetrics register should ok."));
+
+#[derive(Clone)]
+pub struct TxFactoryMetrics {
+    pub txn_submitted: UIntCounterVec,
+    pub txn_submit_failed: UIntCounterVec,
+    pub txn_confirmed: UIntCounterVec,
+    pub txn_timeout: UIntCounterVec,
+    pub txn_confirm_latency: HistogramVec,
+}
+
+impl TxFactoryMetrics {
+    fn register_counter_vec(name: &str, help: &str) -> Result<UIntCounterVec, PrometheusError> {
+        let counter =
+            UIntCounterVec::new(Opts::new(name, help).namespace("starcoin"), &["profile"])?;
+        default_registry().register(Box::new(counter.clone()))?;
+        Ok(counter)
+    }
+
+    pub fn register() -> Result<Self, PrometheusError> {
+        let txn_submitted = Self::register_counter_vec(
+            "txfactory_txn_submitted",
+            "Count of txns submitted by txfactory",
+        )?;
+        let txn_submit_failed = Self::register_counter_vec(
+            "txfactory_txn_submit_failed",
+            "Count of txns failed to sign or submit by txfactory",
+        )?;
+        let txn_confirmed = Self::register_counter_vec(
+            "txfactory_txn_confirmed",
+            "Count of txns submitted by txfactory and included in the chain",
+        )?;
+        let txn_timeout = Self::register_counter_vec(
+            "txfactory_txn_timeout",
+            "Count of txns submitted by txfactory and not included in the chain before timeout",
+        )?;
+        let txn_confirm_latency = register_histogram_vec!(
+            HistogramOpts::new(
+                "txfactory_txn_confirm_latency",
+                "Histogram of txn confirmation latency in seconds, from submission to inclusion"
+            )
+            .namespace("starcoin")
+            .buckets(vec![
+                0.1, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0
+            ]),
+            &["profile"]
+        )?;
+        Ok(Self {
+            txn_submitted,
+            txn_submit_failed,
+            txn_confirmed,
+            txn_timeout,
+            txn_confirm_latency,
+        })
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workload::WorkloadProfile;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub mean_ms: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Get the nearest-rank percentile of the sorted values.
+fn percentile(sorted: &[u64], percent: u64) -> u64 {
+    debug_assert!(!sorted.is_empty());
+    let rank = (sorted.len() as u64 * percent + 99) / 100;
+    sorted[(rank.max(1) - 1) as usize]
+}
+
+impl LatencySummary {
+    /// Summarize the latencies, return None if there is no latency.
+    pub fn from_latencies(latencies: &[Duration]) -> Option<Self> {
+        if latencies.is_empty() {
+            return None;
+        }
+        let mut millis: Vec<u64> = latencies
+            .iter()
+            .map(|latency| latency.as_millis() as u64)
+            .collect();
+        millis.sort_unstable();
+        let sum: u128 = millis.iter().map(|ms| u128::from(*ms)).sum();
+        Some(Self {
+            min_ms: millis[0],
+            max_ms: millis[millis.len() - 1],
+            mean_ms: (sum / millis.len() as u128) as u64,
+            p50_ms: percentile(&millis, 50),
+            p90_ms: percentile(&millis, 90),
+            p99_ms: percentile(&millis, 99),
+        })
+    }
+}
+
+/// The final report of a workload run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadReport {
+    pub profile: WorkloadProfile,
+    /// None if the tps is unlimited.
+    pub target_tps: Option<u64>,
+    pub senders: usize,
+    pub duration_secs: f64,
+    pub submitted: u64,
+    pub submit_failed: u64,
+    /// Txns included in the chain, whatever the vm status is.
+    pub confirmed: u64,
+    /// Confirmed txns which are not executed successfully.
+    pub execute_failed: u64,
+    pub timed_out: u64,
+    pub submit_tps: f64,
+    pub confirm_tps: f64,
+    pub latency: Option<LatencySummary>,
+}
+
+impl WorkloadReport {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_summary() {
+        assert_eq!(LatencySummary::from_latencies(&[]), None);
+        let latencies: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_latencies(&latencies).unwrap();
+        assert_eq!(
+            summary,
+            LatencySummary {
+                min_ms: 1,
+                max_ms: 100,
+                mean_ms: 50,
+                p50_ms: 50,
+                p90_ms: 90,
+                p99_ms: 99,
+            }
+        );
+        let summary = LatencySummary::from_latencies(&[Duration::from_secs(1)]).unwrap();
+        assert_eq!(summary.p50_ms, 1000);
+        assert_eq!(summary.p99_ms, 1000);
+    }
+}
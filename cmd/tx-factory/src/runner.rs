@@ -0,0 +1,356 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::TX_FACTORY_METRICS;
+use crate::report::{LatencySummary, WorkloadReport};
+use crate::workload::{WorkloadGenerator, WorkloadProfile};
+use anyhow::{bail, format_err, Result};
+use starcoin_account_api::AccountInfo;
+use starcoin_crypto::HashValue;
+use starcoin_executor::DEFAULT_EXPIRATION_TIME;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::TransactionVMStatus;
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
+use starcoin_state_api::StateReaderExt;
+use starcoin_types::account_address::AccountAddress;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const SUBMIT_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug)]
+pub struct WorkloadOptions {
+    pub profile: WorkloadProfile,
+    /// Target tps of all senders, unlimited if None.
+    pub tps: Option<u64>,
+    /// Run until stopped if None.
+    pub duration: Option<Duration>,
+    pub watch_timeout: Duration,
+    pub account_password: String,
+    pub unlock_duration: Duration,
+}
+
+/// Shape the send rate by scheduling every send at a fixed interval.
+struct RateShaper {
+    interval: Duration,
+    next_send: Instant,
+}
+
+impl RateShaper {
+    fn new(tps: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / tps),
+            next_send: Instant::now(),
+        }
+    }
+
+    fn wait(&mut self) {
+        let now = Instant::now();
+        if self.next_send > now {
+            thread::sleep(self.next_send - now);
+        } else if now - self.next_send > Duration::from_secs(1) {
+            // the sender fell far behind the schedule, do not burst to catch up.
+            self.next_send = now;
+        }
+        self.next_send += self.interval;
+    }
+}
+
+struct PendingTxn {
+    hash: HashValue,
+    profile: WorkloadProfile,
+    submitted_at: Instant,
+}
+
+#[derive(Default)]
+struct SenderStats {
+    submitted: u64,
+    submit_failed: u64,
+}
+
+#[derive(Default)]
+struct ConfirmStats {
+    latencies: Vec<Duration>,
+    execute_failed: u64,
+    timed_out: u64,
+}
+
+/// Get the next sequence number of the account, prefer the one in txpool.
+fn next_sequence_number(client: &RpcClient, address: AccountAddress) -> Result<u64> {
+    if let Some(n) = client.next_sequence_number_in_txpool(address)? {
+        return Ok(n);
+    }
+    let state_reader = RemoteStateReader::new(client)?;
+    match state_reader.get_account_resource(address)? {
+        Some(account_resource) => Ok(account_resource.sequence_number()),
+        None => bail!("account {} not exists, please faucet it", address),
+    }
+}
+
+struct WorkloadSender {
+    client: RpcClient,
+    generator: Arc<WorkloadGenerator>,
+    account: AccountAddress,
+    receiver: AccountAddress,
+    options: WorkloadOptions,
+    next_sequence_number: u64,
+}
+
+impl WorkloadSender {
+    fn unlock(&self) -> Result<()> {
+        self.client.account_unlock(
+            self.account,
+            self.options.account_password.clone(),
+            self.options.unlock_duration,
+        )
+    }
+
+    fn submit(&self, expiration_timestamp: u64) -> Result<(WorkloadProfile, HashValue)> {
+        let (profile, raw_txn) = self.generator.generate(
+            self.options.profile,
+            self.account,
+            self.receiver,
+            self.next_sequence_number,
+            expiration_timestamp,
+        )?;
+        let user_txn = self.client.account_sign_txn(raw_txn)?;
+        let txn_hash = user_txn.id();
+        self.client.submit_transaction(user_txn)?;
+        Ok((profile, txn_hash))
+    }
+
+    fn run(
+        mut self,
+        tps: Option<f64>,
+        deadline: Option<Instant>,
+        stopping_signal: Arc<AtomicBool>,
+        pending_sender: Sender<PendingTxn>,
+    ) -> Result<SenderStats> {
+        self.unlock()?;
+        self.next_sequence_number = next_sequence_number(&self.client, self.account)?;
+        let start = Instant::now();
+        let start_seconds = self.client.node_info()?.now_seconds;
+        let mut shaper = tps.map(RateShaper::new);
+        let mut stats = SenderStats::default();
+        while !stopping_signal.load(Ordering::SeqCst)
+            && deadline.map(|d| Instant::now() < d).unwrap_or(true)
+        {
+            if let Some(shaper) = shaper.as_mut() {
+                shaper.wait();
+            }
+            let expiration_timestamp =
+                start_seconds + start.elapsed().as_secs() + DEFAULT_EXPIRATION_TIME;
+            match self.submit(expiration_timestamp) {
+                Ok((profile, hash)) => {
+                    self.next_sequence_number += 1;
+                    stats.submitted += 1;
+                    TX_FACTORY_METRICS
+                        .txn_submitted
+                        .with_label_values(&[profile.name()])
+                        .inc();
+                    // the watcher only exits after all senders exit.
+                    let _ = pending_sender.send(PendingTxn {
+                        hash,
+                        profile,
+                        submitted_at: Instant::now(),
+                    });
+                }
+                Err(e) => {
+                    stats.submit_failed += 1;
+                    TX_FACTORY_METRICS
+                        .txn_submit_failed
+                        .with_label_values(&[WorkloadGenerator::resolve_profile(
+                            self.options.profile,
+                            self.next_sequence_number,
+                        )
+                        .name()])
+                        .inc();
+                    warn!(
+                        "Sender {} submit txn {} failed: {:?}, try again after {:?}.",
+                        self.account, self.next_sequence_number, e, SUBMIT_RETRY_INTERVAL
+                    );
+                    thread::sleep(SUBMIT_RETRY_INTERVAL);
+                    // the account may be locked or the txn may be rejected, unlock and recheck.
+                    if let Err(e) = self.unlock() {
+                        error!("Sender {} unlock failed: {:?}", self.account, e);
+                    }
+                    match next_sequence_number(&self.client, self.account) {
+                        Ok(n) => self.next_sequence_number = n,
+                        Err(e) => error!(
+                            "Sender {} recheck sequence number failed: {:?}",
+                            self.account, e
+                        ),
+                    }
+                }
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Poll the pending txns until all of them are confirmed or timeout,
+/// and until the pending channel is closed.
+fn watch_confirmations(
+    client: RpcClient,
+    pending_receiver: Receiver<PendingTxn>,
+    watch_timeout: Duration,
+) -> ConfirmStats {
+    let mut stats = ConfirmStats::default();
+    let mut pending: Vec<PendingTxn> = vec![];
+    let mut closed = false;
+    loop {
+        loop {
+            match pending_receiver.try_recv() {
+                Ok(txn) => pending.push(txn),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    closed = true;
+                    break;
+                }
+            }
+        }
+        if closed && pending.is_empty() {
+            break;
+        }
+        let mut still_pending = Vec::with_capacity(pending.len());
+        for txn in pending {
+            match client.chain_get_transaction_info(txn.hash) {
+                Ok(Some(txn_info)) => {
+                    let latency = txn.submitted_at.elapsed();
+                    let profile = txn.profile.name();
+                    TX_FACTORY_METRICS
+                        .txn_confirmed
+                        .with_label_values(&[profile])
+                        .inc();
+                    TX_FACTORY_METRICS
+                        .txn_confirm_latency
+                        .with_label_values(&[profile])
+                        .observe(latency.as_secs_f64());
+                    if !matches!(txn_info.status, TransactionVMStatus::Executed) {
+                        stats.execute_failed += 1;
+                    }
+                    stats.latencies.push(latency);
+                }
+                Ok(None) if txn.submitted_at.elapsed() > watch_timeout => {
+                    TX_FACTORY_METRICS
+                        .txn_timeout
+                        .with_label_values(&[txn.profile.name()])
+                        .inc();
+                    stats.timed_out += 1;
+                }
+                Ok(None) => still_pending.push(txn),
+                Err(e) => {
+                    warn!("Get txn info of {} failed: {:?}", txn.hash, e);
+                    still_pending.push(txn);
+                }
+            }
+        }
+        pending = still_pending;
+        thread::sleep(CONFIRM_POLL_INTERVAL);
+    }
+    stats
+}
+
+/// Run the workload with every account as an independent sender, until stopped or the
+/// duration elapsed, then wait for the confirmations of submitted txns and report.
+/// `receiver` receives the transfers if there is only one sender, otherwise the senders
+/// transfer to each other.
+pub fn run_workload(
+    ipc_path: PathBuf,
+    generator: Arc<WorkloadGenerator>,
+    accounts: Vec<AccountInfo>,
+    receiver: AccountAddress,
+    options: WorkloadOptions,
+    stopping_signal: Arc<AtomicBool>,
+) -> Result<WorkloadReport> {
+    if accounts.is_empty() {
+        bail!("No account to send workload txns");
+    }
+    let senders = accounts.len();
+    let per_sender_tps = match options.tps {
+        Some(0) => bail!("Target tps should be greater than 0"),
+        Some(tps) => Some(tps as f64 / senders as f64),
+        None => None,
+    };
+    let start = Instant::now();
+    let deadline = options.duration.map(|duration| start + duration);
+    let (pending_sender, pending_receiver) = mpsc::channel();
+
+    let watch_client = RpcClient::connect_ipc(ipc_path.clone())?;
+    let watch_timeout = options.watch_timeout;
+    let watcher = thread::Builder::new()
+        .name("txfactory-watcher".to_string())
+        .spawn(move || watch_confirmations(watch_client, pending_receiver, watch_timeout))?;
+
+    let mut handles = Vec::with_capacity(senders);
+    for (index, account) in accounts.iter().enumerate() {
+        let receiver = if senders == 1 {
+            receiver
+        } else {
+            accounts[(index + 1) % senders].address
+        };
+        let sender = WorkloadSender {
+            client: RpcClient::connect_ipc(ipc_path.clone())?,
+            generator: generator.clone(),
+            account: account.address,
+            receiver,
+            options: options.clone(),
+            next_sequence_number: 0,
+        };
+        let stopping_signal = stopping_signal.clone();
+        let pending_sender = pending_sender.clone();
+        handles.push(
+            thread::Builder::new()
+                .name(format!("txfactory-sender-{}", index))
+                .spawn(move || {
+                    sender.run(per_sender_tps, deadline, stopping_signal, pending_sender)
+                })?,
+        );
+    }
+    // close the channel once all senders exit.
+    drop(pending_sender);
+
+    let mut submitted = 0;
+    let mut submit_failed = 0;
+    for handle in handles {
+        match handle
+            .join()
+            .map_err(|_| format_err!("Sender thread panicked"))?
+        {
+            Ok(stats) => {
+                submitted += stats.submitted;
+                submit_failed += stats.submit_failed;
+            }
+            Err(e) => error!("Sender exit with error: {:?}", e),
+        }
+    }
+    let send_duration = start.elapsed();
+    info!(
+        "All senders stopped after {:?}, wait for confirmations.",
+        send_duration
+    );
+    let confirm_stats = watcher
+        .join()
+        .map_err(|_| format_err!("Watcher thread panicked"))?;
+    let send_secs = send_duration.as_secs_f64();
+    let confirmed = confirm_stats.latencies.len() as u64;
+    Ok(WorkloadReport {
+        profile: options.profile,
+        target_tps: options.tps,
+        senders,
+        duration_secs: send_secs,
+        submitted,
+        submit_failed,
+        confirmed,
+        execute_failed: confirm_stats.execute_failed,
+        timed_out: confirm_stats.timed_out,
+        submit_tps: submitted as f64 / send_secs,
+        confirm_tps: confirmed as f64 / start.elapsed().as_secs_f64(),
+        latency: LatencySummary::from_latencies(&confirm_stats.latencies),
+    })
+}
@@ -0,0 +1,296 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_move_compiler::compile_source_string_no_report;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::genesis_address;
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+use starcoin_types::transaction::{RawUserTransaction, Script, TransactionPayload};
+use std::fmt;
+use std::str::FromStr;
+
+/// Mint DummyToken to the sender, DummyToken is only available on test, dev and halley network.
+const MINT_SCRIPT: &str = r#"
+script {
+    use 0x1::Account;
+    use 0x1::DummyToken::{Self, DummyToken};
+    use 0x1::Signer;
+
+    fun mint(account: signer, amount: u128) {
+        if (!Account::is_accepts_token<DummyToken>(Signer::address_of(&account))) {
+            Account::do_accept_token<DummyToken>(&account);
+        };
+        let token = DummyToken::mint(&account, amount);
+        Account::deposit_to_self(&account, token);
+    }
+}
+"#;
+
+/// Burn cpu by a loop without touching the state.
+const COMPUTE_SCRIPT: &str = r#"
+script {
+    fun compute(_account: signer, loop_count: u64) {
+        let i = 0;
+        let sum = 0u64;
+        while (i < loop_count) {
+            sum = (sum + i) % 1000000007;
+            i = i + 1;
+        };
+        assert(sum < 1000000007, 1);
+    }
+}
+"#;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkloadProfile {
+    /// Simple STC transfers.
+    Transfer,
+    /// Mint DummyToken.
+    Mint,
+    /// Heavy compute scripts.
+    Compute,
+    /// Batch transfers to fresh accounts, which produce large write sets.
+    WriteSet,
+    /// Round robin of all the other profiles.
+    Mixed,
+}
+
+impl WorkloadProfile {
+    const MIXED: [WorkloadProfile; 4] = [
+        WorkloadProfile::Transfer,
+        WorkloadProfile::Mint,
+        WorkloadProfile::Compute,
+        WorkloadProfile::WriteSet,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WorkloadProfile::Transfer => "transfer",
+            WorkloadProfile::Mint => "mint",
+            WorkloadProfile::Compute => "compute",
+            WorkloadProfile::WriteSet => "write-set",
+            WorkloadProfile::Mixed => "mixed",
+        }
+    }
+}
+
+impl fmt::Display for WorkloadProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for WorkloadProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "transfer" => WorkloadProfile::Transfer,
+            "mint" => WorkloadProfile::Mint,
+            "compute" => WorkloadProfile::Compute,
+            "write-set" => WorkloadProfile::WriteSet,
+            "mixed" => WorkloadProfile::Mixed,
+            _ => bail!(
+                "Unknown workload profile: {}, expect transfer, mint, compute, write-set or mixed",
+                s
+            ),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkloadConfig {
+    pub transfer_amount: u128,
+    /// DummyToken limits the mint amount to 10000.
+    pub mint_amount: u128,
+    pub compute_loop_count: u64,
+    /// Count of fresh accounts created by a write-set txn.
+    pub write_set_size: usize,
+    pub gas_price: u64,
+    pub max_gas_amount: u64,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        Self {
+            transfer_amount: 1000,
+            mint_amount: 100,
+            compute_loop_count: 10000,
+            write_set_size: 32,
+            gas_price: 1,
+            max_gas_amount: 40000000,
+        }
+    }
+}
+
+pub struct WorkloadGenerator {
+    chain_id: ChainId,
+    config: WorkloadConfig,
+    mint_script: Vec<u8>,
+    compute_script: Vec<u8>,
+}
+
+fn compile_script(source: &str, stdlib_files: &[String]) -> Result<Vec<u8>> {
+    let (_, compiled_result) =
+        compile_source_string_no_report(source, stdlib_files, genesis_address())?;
+    let mut units = compiled_result.map_err(|e| format_err!("Compile script error: {:?}", e))?;
+    let unit = units
+        .pop()
+        .ok_or_else(|| format_err!("Compile script result is empty"))?;
+    Ok(unit.serialize())
+}
+
+impl WorkloadGenerator {
+    pub fn new(chain_id: ChainId, config: WorkloadConfig) -> Result<Self> {
+        let temp_dir = tempfile::tempdir()?;
+        let stdlib_files = stdlib::restore_stdlib_in_dir(temp_dir.path())?;
+        Ok(Self {
+            chain_id,
+            config,
+            mint_script: compile_script(MINT_SCRIPT, &stdlib_files)?,
+            compute_script: compile_script(COMPUTE_SCRIPT, &stdlib_files)?,
+        })
+    }
+
+    /// Resolve the profile of the txn with `sequence_number`, the mixed profile is resolved
+    /// by round robin, so a workload is reproducible.
+    pub fn resolve_profile(profile: WorkloadProfile, sequence_number: u64) -> WorkloadProfile {
+        match profile {
+            WorkloadProfile::Mixed => {
+                let mixed = &WorkloadProfile::MIXED;
+                mixed[(sequence_number % mixed.len() as u64) as usize]
+            }
+            profile => profile,
+        }
+    }
+
+    fn build_txn(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+        payload: TransactionPayload,
+        expiration_timestamp: u64,
+    ) -> RawUserTransaction {
+        RawUserTransaction::new_with_default_gas_token(
+            sender,
+            sequence_number,
+            payload,
+            self.config.max_gas_amount,
+            self.config.gas_price,
+            expiration_timestamp,
+            self.chain_id,
+        )
+    }
+
+    /// Generate a txn of the profile, return the resolved profile and the txn.
+    pub fn generate(
+        &self,
+        profile: WorkloadProfile,
+        sender: AccountAddress,
+        receiver: AccountAddress,
+        sequence_number: u64,
+        expiration_timestamp: u64,
+    ) -> Result<(WorkloadProfile, RawUserTransaction)> {
+        let profile = Self::resolve_profile(profile, sequence_number);
+        let txn = match profile {
+            WorkloadProfile::Transfer => starcoin_executor::build_transfer_txn(
+                sender,
+                receiver,
+                None,
+                sequence_number,
+                self.config.transfer_amount,
+                self.config.gas_price,
+                self.config.max_gas_amount,
+                expiration_timestamp,
+                self.chain_id,
+            ),
+            WorkloadProfile::Mint => self.build_txn(
+                sender,
+                sequence_number,
+                TransactionPayload::Script(Script::new(
+                    self.mint_script.clone(),
+                    vec![],
+                    vec![bcs_ext::to_bytes(&self.config.mint_amount)?],
+                )),
+                expiration_timestamp,
+            ),
+            WorkloadProfile::Compute => self.build_txn(
+                sender,
+                sequence_number,
+                TransactionPayload::Script(Script::new(
+                    self.compute_script.clone(),
+                    vec![],
+                    vec![bcs_ext::to_bytes(&self.config.compute_loop_count)?],
+                )),
+                expiration_timestamp,
+            ),
+            WorkloadProfile::WriteSet => {
+                let auth_keys: Vec<AuthenticationKey> = (0..self.config.write_set_size)
+                    .map(|_| AuthenticationKey::random())
+                    .collect();
+                let receivers = auth_keys
+                    .iter()
+                    .map(|auth_key| auth_key.derived_address())
+                    .collect();
+                starcoin_executor::build_batch_transfer_txn(
+                    sender,
+                    receivers,
+                    auth_keys,
+                    sequence_number,
+                    1,
+                    self.config.gas_price,
+                    self.config.max_gas_amount,
+                    expiration_timestamp,
+                    self.chain_id,
+                )
+            }
+            WorkloadProfile::Mixed => unreachable!("mixed profile has been resolved"),
+        };
+        Ok((profile, txn))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workload_profile() {
+        for profile in WorkloadProfile::MIXED.iter() {
+            assert_eq!(WorkloadProfile::from_str(profile.name()).unwrap(), *profile);
+        }
+        assert!(WorkloadProfile::from_str("unknown").is_err());
+        let resolved: Vec<_> = (0..5)
+            .map(|seq| WorkloadGenerator::resolve_profile(WorkloadProfile::Mixed, seq))
+            .collect();
+        assert_eq!(&resolved[..4], &WorkloadProfile::MIXED[..]);
+        assert_eq!(resolved[4], WorkloadProfile::Transfer);
+        assert_eq!(
+            WorkloadGenerator::resolve_profile(WorkloadProfile::Compute, 1),
+            WorkloadProfile::Compute
+        );
+    }
+
+    #[test]
+    fn test_generate_workload() {
+        let generator = WorkloadGenerator::new(ChainId::test(), WorkloadConfig::default()).unwrap();
+        let sender = AccountAddress::random();
+        for seq in 0..4 {
+            let (profile, txn) = generator
+                .generate(
+                    WorkloadProfile::Mixed,
+                    sender,
+                    AccountAddress::random(),
+                    seq,
+                    u64::max_value(),
+                )
+                .unwrap();
+            assert_ne!(profile, WorkloadProfile::Mixed);
+            assert_eq!(txn.sequence_number(), seq);
+        }
+    }
+}
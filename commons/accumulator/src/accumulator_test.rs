@@ -318,6 +318,49 @@ fn test_get_leaves_overflow() {
     assert_eq!(leaves1.len(), 100);
 }
 
+#[test]
+fn test_append_batch() {
+    let batches = [1usize, 3, 2, 7, 300, 1, 64, 1025, 5];
+    let all_leaves = create_leaves(0..batches.iter().sum());
+    for parallel in [false, true].iter() {
+        let accumulator = MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+        let batch_accumulator = MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+        assert_eq!(
+            batch_accumulator.append_batch(&[], *parallel).unwrap(),
+            *ACCUMULATOR_PLACEHOLDER_HASH
+        );
+        let mut first_leaf_idx = 0usize;
+        for batch in batches.iter() {
+            let leaves = &all_leaves[first_leaf_idx..first_leaf_idx + batch];
+            let root_hash = accumulator.append(leaves).unwrap();
+            let batch_root_hash = batch_accumulator.append_batch(leaves, *parallel).unwrap();
+            assert_eq!(root_hash, batch_root_hash);
+            assert_eq!(accumulator.get_info(), batch_accumulator.get_info());
+            // flush every other batch, so the siblings are read from both temp nodes and store.
+            if first_leaf_idx % 2 == 0 {
+                batch_accumulator.flush().unwrap();
+            }
+            proof_verify(
+                &batch_accumulator,
+                batch_root_hash,
+                leaves,
+                first_leaf_idx as u64,
+            );
+            first_leaf_idx += batch;
+        }
+        assert_eq!(
+            batch_accumulator.root_hash(),
+            compute_root_hash_naive(&all_leaves)
+        );
+        for position in 0..batch_accumulator.num_nodes() {
+            assert_eq!(
+                accumulator.get_node_by_position(position).unwrap(),
+                batch_accumulator.get_node_by_position(position).unwrap()
+            );
+        }
+    }
+}
+
 fn proof_verify(
     accumulator: &MerkleAccumulator,
     root_hash: HashValue,
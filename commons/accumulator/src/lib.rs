@@ -18,7 +18,7 @@ mod accumulator_test;
 pub mod inmemory;
 pub mod node;
 pub mod node_index;
-mod proof;
+pub mod proof;
 mod tree;
 pub mod tree_store;
 
@@ -33,6 +33,9 @@ pub const MAC_CACHE_SIZE: usize = 65535;
 pub trait Accumulator {
     /// Append leaves and return new root
     fn append(&self, leaves: &[HashValue]) -> Result<HashValue>;
+    /// Append leaves with a single pass over the affected internal nodes and return new root,
+    /// hash the nodes of a level in parallel if `parallel` is true.
+    fn append_batch(&self, leaves: &[HashValue], parallel: bool) -> Result<HashValue>;
     /// Get leaf node by index.
     fn get_leaf(&self, leaf_index: u64) -> Result<Option<HashValue>>;
     /// Batch get leaves by index.
@@ -115,6 +118,10 @@ impl Accumulator for MerkleAccumulator {
         Ok(root_hash)
     }
 
+    fn append_batch(&self, new_leaves: &[HashValue], parallel: bool) -> Result<HashValue> {
+        self.tree.lock().append_batch(new_leaves, parallel)
+    }
+
     fn get_leaf(&self, leaf_index: u64) -> Result<Option<HashValue>> {
         self.tree
             .lock()
@@ -9,11 +9,15 @@ use anyhow::{bail, format_err, Result};
 use logger::prelude::*;
 use lru::LruCache;
 use mirai_annotations::*;
+use rayon::prelude::*;
 use starcoin_crypto::hash::ACCUMULATOR_PLACEHOLDER_HASH;
 use starcoin_crypto::HashValue;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Min count of node hashes on a level to hash in parallel, avoid the overhead for small batches.
+const PARALLEL_HASH_THRESHOLD: usize = 256;
+
 pub struct AccumulatorTree {
     /// forzen subtree roots hashes.
     frozen_subtree_roots: Vec<HashValue>,
@@ -112,6 +116,135 @@ impl AccumulatorTree {
             left_siblings.push((pos, hash));
         }
 
+        let (hash, mut not_frozen_nodes) = self.compute_root(left_siblings, root_level)?;
+        //update frozen tag
+        to_freeze = to_freeze
+            .iter()
+            .map(|node| {
+                node.clone().frozen().expect("frozen must have value");
+                node.clone()
+            })
+            .collect();
+        //aggregator all nodes
+        not_frozen_nodes.extend_from_slice(&to_freeze);
+        self.update_temp_nodes(not_frozen_nodes.clone());
+        // udpate to cache
+        self.update_cache(not_frozen_nodes);
+        // update self properties
+        self.root_hash = hash;
+        self.num_leaves = last_new_leaf_count;
+        self.frozen_subtree_roots = self.scan_frozen_subtree_roots()?;
+        self.num_nodes = new_num_nodes;
+        trace!("acc append_leaves ok: {:?}", new_leaves);
+        Ok(hash)
+    }
+
+    /// Append multiple leaves level by level, every affected internal node is computed only once,
+    /// and the node hashes of a level are computed in parallel if `parallel` is true.
+    /// The result is same as `append`.
+    pub fn append_batch(&mut self, new_leaves: &[HashValue], parallel: bool) -> Result<HashValue> {
+        if new_leaves.is_empty() {
+            return if self.num_leaves == 0 {
+                Ok(*ACCUMULATOR_PLACEHOLDER_HASH)
+            } else {
+                Ok(self.root_hash)
+            };
+        }
+        let last_new_leaf_count = self.num_leaves + new_leaves.len() as LeafCount;
+        let root_level = NodeIndex::root_level_from_leaf_count(last_new_leaf_count);
+        let mut to_freeze: Vec<(AccumulatorNode, HashValue)> =
+            Vec::with_capacity(Self::max_to_freeze(new_leaves.len(), root_level));
+        to_freeze.extend(new_leaves.iter().enumerate().map(|(leaf_offset, leaf)| {
+            let leaf_pos = NodeIndex::from_leaf_index(self.num_leaves + leaf_offset as LeafCount);
+            (AccumulatorNode::new_leaf(leaf_pos, *leaf), *leaf)
+        }));
+
+        // The new frozen subtree roots, which are left children without right sibling.
+        let mut left_siblings = vec![];
+        let mut level = 0u32;
+        // The position on the level of the first node in `hashes`.
+        let mut first_pos = self.num_leaves;
+        let mut hashes = new_leaves.to_vec();
+        while !hashes.is_empty() {
+            if first_pos % 2 == 1 {
+                // The first node is a right child, its left sibling must already exist in storage.
+                let sibling = NodeIndex::from_level_and_pos(level, first_pos - 1);
+                let mut level_hashes = Vec::with_capacity(hashes.len() + 1);
+                level_hashes.push(
+                    self.get_node_hash(sibling)?
+                        .unwrap_or(*ACCUMULATOR_PLACEHOLDER_HASH),
+                );
+                level_hashes.extend(hashes);
+                hashes = level_hashes;
+                first_pos -= 1;
+            }
+            if hashes.len() % 2 == 1 {
+                let last_pos = first_pos + hashes.len() as u64 - 1;
+                let last_hash = hashes.pop().expect("hashes must not be empty");
+                left_siblings.push((NodeIndex::from_level_and_pos(level, last_pos), last_hash));
+            }
+            level += 1;
+            first_pos /= 2;
+            let parents = Self::hash_pairs(level, first_pos, &hashes, parallel);
+            hashes = parents.iter().map(|(_, hash)| *hash).collect();
+            to_freeze.extend(parents);
+        }
+        // `compute_root` requires the left siblings ordered by level from high to low.
+        left_siblings.reverse();
+
+        let new_num_nodes = self.num_nodes + to_freeze.len() as NodeCount;
+        let (hash, not_frozen_nodes) = self.compute_root(left_siblings, root_level)?;
+        for node in not_frozen_nodes {
+            let node_hash = node.hash();
+            self.save_node(node, node_hash);
+        }
+        for (node, node_hash) in to_freeze {
+            self.save_node(node, node_hash);
+        }
+        self.root_hash = hash;
+        self.num_leaves = last_new_leaf_count;
+        self.frozen_subtree_roots = self.scan_frozen_subtree_roots()?;
+        self.num_nodes = new_num_nodes;
+        trace!("acc append_batch ok, leaves count: {}", new_leaves.len());
+        Ok(hash)
+    }
+
+    /// Build the parent nodes on `level` of the adjacent node `hashes`, starting from `first_pos`.
+    fn hash_pairs(
+        level: u32,
+        first_pos: u64,
+        hashes: &[HashValue],
+        parallel: bool,
+    ) -> Vec<(AccumulatorNode, HashValue)> {
+        let new_parent = |(offset, pair): (usize, &[HashValue])| {
+            let node = AccumulatorNode::new_internal(
+                NodeIndex::from_level_and_pos(level, first_pos + offset as u64),
+                pair[0],
+                pair[1],
+            );
+            let hash = node.hash();
+            (node, hash)
+        };
+        if parallel && hashes.len() >= PARALLEL_HASH_THRESHOLD {
+            hashes.par_chunks(2).enumerate().map(new_parent).collect()
+        } else {
+            hashes.chunks(2).enumerate().map(new_parent).collect()
+        }
+    }
+
+    /// Save the new node to temp nodes and index cache.
+    fn save_node(&mut self, node: AccumulatorNode, node_hash: HashValue) {
+        self.index_cache.put(node.index(), node_hash);
+        self.update_nodes.insert(node_hash, node);
+    }
+
+    /// Reconstruct the root hash from the new frozen subtree roots `left_siblings`,
+    /// ordered by level from high to low, return the root hash and the not frozen nodes.
+    fn compute_root(
+        &mut self,
+        mut left_siblings: Vec<(NodeIndex, HashValue)>,
+        root_level: u32,
+    ) -> Result<(HashValue, Vec<AccumulatorNode>)> {
         let mut not_frozen_nodes = vec![];
         // Now reconstruct the final root hash by walking up to root level and adding
         // placeholder hash nodes as needed on the right, and left siblings that have either
@@ -152,26 +285,7 @@ impl AccumulatorTree {
         }
 
         debug_assert!(left_siblings.is_empty());
-        //update frozen tag
-        to_freeze = to_freeze
-            .iter()
-            .map(|node| {
-                node.clone().frozen().expect("frozen must have value");
-                node.clone()
-            })
-            .collect();
-        //aggregator all nodes
-        not_frozen_nodes.extend_from_slice(&to_freeze);
-        self.update_temp_nodes(not_frozen_nodes.clone());
-        // udpate to cache
-        self.update_cache(not_frozen_nodes);
-        // update self properties
-        self.root_hash = hash;
-        self.num_leaves = last_new_leaf_count;
-        self.frozen_subtree_roots = self.scan_frozen_subtree_roots()?;
-        self.num_nodes = new_num_nodes;
-        trace!("acc append_leaves ok: {:?}", new_leaves);
-        Ok(hash)
+        Ok((hash, not_frozen_nodes))
     }
 
     /// Get node for self package.
@@ -7,6 +7,14 @@ use std::io::{Cursor, Read, Write};
 pub const PBKDF2_DEFAULT_ITERATIONS: usize = 1000;
 pub const PBKDF2_SALT_SIZE: usize = 32;
 pub const AES_NONCE_SIZE: usize = 12;
+/// The scrypt params recommended for interactive logins, N = 2^15, r = 8, p = 1, use 32M memory.
+pub const SCRYPT_DEFAULT_LOG_N: u8 = 15;
+pub const SCRYPT_DEFAULT_R: u32 = 8;
+pub const SCRYPT_DEFAULT_P: u32 = 1;
+/// Limit the params read from the encrypted data, so a tampered data can not exhaust the memory.
+const SCRYPT_MAX_LOG_N: u8 = 20;
+const SCRYPT_MAX_R: u32 = 16;
+const SCRYPT_MAX_P: u32 = 16;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct KeyDerivationParams {
@@ -138,5 +146,74 @@ pub fn decrypt(secret: &[u8], encrypted: &[u8]) -> Result<Vec<u8>> {
     aes_decrypt(&meta.encryption_params, dk, crypted)
 }
 
+const SCRYPT_META_LEN: usize = 1usize + 4 + 4 + PBKDF2_SALT_SIZE + AES_NONCE_SIZE;
+
+fn scrypt_derive_key(log_n: u8, r: u32, p: u32, salt: &[u8], secret: &[u8]) -> Result<[u8; 32]> {
+    if log_n > SCRYPT_MAX_LOG_N || r > SCRYPT_MAX_R || p > SCRYPT_MAX_P {
+        bail!(
+            "invalid scrypt params, log_n: {}, r: {}, p: {}",
+            log_n,
+            r,
+            p
+        );
+    }
+    let params = scrypt::Params::new(log_n, r, p)
+        .map_err(|e| format_err!("invalid scrypt params: {:?}", e))?;
+    let mut dk = [0u8; 32];
+    scrypt::scrypt(secret, salt, &params, &mut dk)
+        .map_err(|e| format_err!("scrypt error: {:?}", e))?;
+    Ok(dk)
+}
+
+/// Encrypt with the key derived by scrypt, it is memory hard and much slower to brute force than
+/// `encrypt`, use it for the secrets stored out of the local machine.
+pub fn encrypt_with_scrypt(secret: &[u8], plain: &[u8]) -> Vec<u8> {
+    let mut salt = [0u8; PBKDF2_SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let encryption_params = EncryptionParams::generate();
+    let dk = scrypt_derive_key(
+        SCRYPT_DEFAULT_LOG_N,
+        SCRYPT_DEFAULT_R,
+        SCRYPT_DEFAULT_P,
+        &salt,
+        secret,
+    )
+    .expect("default scrypt params should be valid");
+    let mut buf = Cursor::new(Vec::with_capacity(SCRYPT_META_LEN + plain.len()));
+    buf.write_u8(SCRYPT_DEFAULT_LOG_N)
+        .expect("should never fail");
+    buf.write_u32::<byteorder::BigEndian>(SCRYPT_DEFAULT_R)
+        .expect("should never fail");
+    buf.write_u32::<byteorder::BigEndian>(SCRYPT_DEFAULT_P)
+        .expect("should never fail");
+    buf.write_all(&salt).expect("should never fail");
+    buf.write_all(&encryption_params.nonce)
+        .expect("should never fail");
+    let mut result = buf.into_inner();
+    result.append(&mut aes_encrypt(&encryption_params, dk, plain));
+    result
+}
+
+/// Decrypt the data encrypted by `encrypt_with_scrypt`.
+pub fn decrypt_with_scrypt(secret: &[u8], encrypted: &[u8]) -> Result<Vec<u8>> {
+    if encrypted.len() <= SCRYPT_META_LEN {
+        bail!("invalid encrypted data");
+    }
+    let mut buf = Cursor::new(&encrypted[0..SCRYPT_META_LEN]);
+    let log_n = buf.read_u8()?;
+    let r = buf.read_u32::<byteorder::BigEndian>()?;
+    let p = buf.read_u32::<byteorder::BigEndian>()?;
+    let mut salt = [0u8; PBKDF2_SALT_SIZE];
+    buf.read_exact(&mut salt)?;
+    let mut nonce = [0u8; AES_NONCE_SIZE];
+    buf.read_exact(&mut nonce)?;
+    let dk = scrypt_derive_key(log_n, r, p, &salt, secret)?;
+    aes_decrypt(
+        &EncryptionParams { nonce },
+        dk,
+        &encrypted[SCRYPT_META_LEN..],
+    )
+}
+
 #[cfg(test)]
 mod tests;
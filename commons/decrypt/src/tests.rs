@@ -1,4 +1,4 @@
-use crate::{decrypt, encrypt};
+use crate::{decrypt, decrypt_with_scrypt, encrypt, encrypt_with_scrypt};
 
 #[test]
 fn test_encryption() {
@@ -10,3 +10,18 @@ fn test_encryption() {
     let decrypted = decrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
     assert_eq!(decrypted.as_slice(), plain.as_bytes());
 }
+
+#[test]
+fn test_scrypt_encryption() {
+    let secret = "hello";
+    let plain = "world";
+    let encrypted = encrypt_with_scrypt(secret.as_bytes(), plain.as_bytes());
+    let decrypted = decrypt_with_scrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
+    assert_eq!(decrypted.as_slice(), plain.as_bytes());
+    assert!(decrypt_with_scrypt("hell0".as_bytes(), encrypted.as_slice()).is_err());
+
+    // the tampered params are rejected before deriving the key.
+    let mut tampered = encrypted;
+    tampered[0] = 63;
+    assert!(decrypt_with_scrypt(secret.as_bytes(), tampered.as_slice()).is_err());
+}
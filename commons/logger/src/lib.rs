@@ -18,8 +18,10 @@ use log4rs::{
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, Once};
 
@@ -89,6 +91,78 @@ impl std::fmt::Display for LogPattern {
     }
 }
 
+/// The subsystem which can write log to a separate file, with independent rotation policy.
+#[derive(Clone, Copy, Debug, Hash, PartialOrd, PartialEq, Ord, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSubsystem {
+    Network,
+    TxPool,
+    Miner,
+    Rpc,
+}
+
+impl LogSubsystem {
+    pub fn all() -> &'static [LogSubsystem] {
+        &[
+            LogSubsystem::Network,
+            LogSubsystem::TxPool,
+            LogSubsystem::Miner,
+            LogSubsystem::Rpc,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogSubsystem::Network => "network",
+            LogSubsystem::TxPool => "txpool",
+            LogSubsystem::Miner => "miner",
+            LogSubsystem::Rpc => "rpc",
+        }
+    }
+
+    /// The log targets which belong to this subsystem.
+    pub fn targets(&self) -> &'static [&'static str] {
+        match self {
+            LogSubsystem::Network => &[
+                "starcoin_network",
+                "starcoin_network_rpc",
+                "network_p2p",
+                "network-p2p",
+                "sub-libp2p",
+                "peerset",
+            ],
+            LogSubsystem::TxPool => &["starcoin_txpool"],
+            LogSubsystem::Miner => &["starcoin_miner"],
+            LogSubsystem::Rpc => &["starcoin_rpc_server"],
+        }
+    }
+}
+
+impl FromStr for LogSubsystem {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LogSubsystem::all()
+            .iter()
+            .find(|subsystem| subsystem.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| format_err!("Unknown log subsystem: {}", s))
+    }
+}
+
+impl std::fmt::Display for LogSubsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LogFileArg {
+    log_path: PathBuf,
+    max_file_size: u64,
+    max_backup: u32,
+}
+
 /// set some third party module's default log level for reduce debug log.
 static THIRD_PARTY_MODULES: Lazy<Vec<(&str, LevelFilter)>> = Lazy::new(|| {
     vec![
@@ -131,6 +205,7 @@ struct LoggerConfigArg {
     max_file_size: u64,
     max_backup: u32,
     pattern: LogPattern,
+    subsystem_files: BTreeMap<LogSubsystem, LogFileArg>,
 }
 
 impl LoggerConfigArg {
@@ -161,6 +236,7 @@ impl LoggerConfigArg {
             max_file_size: 0,
             max_backup: 0,
             pattern: pattern.unwrap_or_else(|| LogPattern::by_level(level)),
+            subsystem_files: BTreeMap::new(),
         }
     }
 }
@@ -205,6 +281,26 @@ impl LoggerHandle {
         self.update_logger(arg);
     }
 
+    /// Write the log of `subsystem` to a separate file, instead of the main log file.
+    pub fn enable_subsystem_file(
+        &self,
+        subsystem: LogSubsystem,
+        log_path: PathBuf,
+        max_file_size: u64,
+        max_backup: u32,
+    ) {
+        let mut arg = self.arg.lock().clone();
+        arg.subsystem_files.insert(
+            subsystem,
+            LogFileArg {
+                log_path,
+                max_file_size,
+                max_backup,
+            },
+        );
+        self.update_logger(arg);
+    }
+
     pub fn update_level(&self, level: LevelFilter) {
         let mut arg = self.arg.lock().clone();
         arg.level = level;
@@ -238,6 +334,25 @@ impl LoggerHandle {
         self.arg.lock().log_path.as_ref().cloned()
     }
 
+    /// Get the log path of `subsystem`, return the main log path if the subsystem has no separate file.
+    pub fn subsystem_log_path(&self, subsystem: LogSubsystem) -> Option<PathBuf> {
+        let arg = self.arg.lock();
+        arg.subsystem_files
+            .get(&subsystem)
+            .map(|file| file.log_path.clone())
+            .or_else(|| arg.log_path.clone())
+    }
+
+    /// Read the last `lines` lines of the `subsystem` log file, or the main log file if `subsystem` is none.
+    pub fn tail(&self, subsystem: Option<LogSubsystem>, lines: usize) -> Result<Vec<String>> {
+        let log_path = match subsystem {
+            Some(subsystem) => self.subsystem_log_path(subsystem),
+            None => self.log_path(),
+        }
+        .ok_or_else(|| format_err!("Log file is disabled."))?;
+        tail_file(log_path.as_path(), lines)
+    }
+
     /// Check is stderr enabled
     pub fn stderr(&self) -> bool {
         self.arg.lock().enable_stderr
@@ -252,7 +367,7 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
     let LoggerConfigArg {
         enable_stderr,
         level,
-        module_levels,
+        mut module_levels,
         log_path,
         slog_path,
         // slog_is_sync,
@@ -260,6 +375,7 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
         max_file_size,
         max_backup,
         pattern,
+        subsystem_files,
         ..
     } = arg;
     if !enable_stderr && log_path.is_none() {
@@ -291,7 +407,7 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
     if slog_path != log_path {
         if let Some(log_path) = slog_path {
             let appender =
-                rolling_file_append("slog", max_file_size, max_backup, pattern, log_path)?;
+                rolling_file_append("slog", max_file_size, max_backup, pattern.clone(), log_path)?;
             builder = builder.appender(appender);
             builder = builder.logger(
                 Logger::builder()
@@ -302,6 +418,28 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
         }
     }
 
+    for (subsystem, file) in subsystem_files {
+        let appender_name = format!("{}_file", subsystem);
+        let appender = rolling_file_append(
+            appender_name.as_str(),
+            file.max_file_size,
+            file.max_backup,
+            pattern.clone(),
+            file.log_path,
+        )?;
+        builder = builder.appender(appender);
+        for target in subsystem.targets() {
+            let target_level = module_levels.remove(*target).unwrap_or(level);
+            let mut logger_builder = Logger::builder()
+                .additive(false)
+                .appender(appender_name.as_str());
+            if enable_stderr {
+                logger_builder = logger_builder.appender("stderr");
+            }
+            builder = builder.logger(logger_builder.build(*target, target_level));
+        }
+    }
+
     builder = builder.loggers(
         module_levels
             .into_iter()
@@ -341,6 +479,35 @@ fn rolling_file_append(
     Ok(Appender::builder().build(append_name, Box::new(file_appender)))
 }
 
+const TAIL_READ_BLOCK_SIZE: u64 = 64 * 1024;
+
+/// Read the last `lines` lines of the file, read from the end of file block by block,
+/// so the big log file is not loaded to memory entirely.
+fn tail_file(path: &Path, lines: usize) -> Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let mut pos = file_len;
+    let mut buf: Vec<u8> = vec![];
+    // a line is complete when there is a '\n' before it, so count lines + 1 '\n'.
+    while pos > 0 && buf.iter().filter(|b| **b == b'\n').count() <= lines {
+        let read_size = std::cmp::min(pos, TAIL_READ_BLOCK_SIZE);
+        pos -= read_size;
+        file.seek(SeekFrom::Start(pos))?;
+        let mut block = vec![0u8; read_size as usize];
+        file.read_exact(&mut block)?;
+        block.extend(buf);
+        buf = block;
+    }
+    let content = String::from_utf8_lossy(&buf);
+    let all_lines = content.lines().collect::<Vec<_>>();
+    let skip = all_lines.len().saturating_sub(lines);
+    Ok(all_lines
+        .into_iter()
+        .skip(skip)
+        .map(|line| line.to_string())
+        .collect())
+}
+
 /// read log level filters from `RUST_LOG` env.
 /// return global level filter and specified level filters.
 fn env_log_level(default_level: &str) -> (LevelFilter, Vec<(String, LevelFilter)>) {
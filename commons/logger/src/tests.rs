@@ -1,5 +1,6 @@
 use super::prelude::*;
-use crate::LogLevelSpec;
+use crate::{tail_file, LogLevelSpec, LogSubsystem};
+use std::str::FromStr;
 
 #[test]
 fn test_log() {
@@ -50,3 +51,34 @@ fn test_log_level_spec() {
         assert_eq!(actual, expected);
     }
 }
+
+#[test]
+fn test_log_subsystem() {
+    for subsystem in LogSubsystem::all() {
+        assert_eq!(
+            LogSubsystem::from_str(subsystem.to_string().as_str()).unwrap(),
+            *subsystem
+        );
+    }
+    assert_eq!(
+        LogSubsystem::from_str("TxPool").unwrap(),
+        LogSubsystem::TxPool
+    );
+    assert!(LogSubsystem::from_str("unknown").is_err());
+}
+
+#[test]
+fn test_tail_file() {
+    let path = std::env::temp_dir().join(format!("test_tail_{}.log", std::process::id()));
+    let content = (0..10000)
+        .map(|i| format!("line {}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&path, content).unwrap();
+    let lines = tail_file(path.as_path(), 3).unwrap();
+    assert_eq!(lines, vec!["line 9997", "line 9998", "line 9999"]);
+    let lines = tail_file(path.as_path(), 20000).unwrap();
+    assert_eq!(lines.len(), 10000);
+    assert_eq!(lines[0], "line 0");
+    std::fs::remove_file(path).unwrap();
+}
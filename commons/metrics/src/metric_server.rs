@@ -7,14 +7,51 @@ use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
+use once_cell::sync::OnceCell;
+use prometheus::proto::{LabelPair, MetricFamily};
 use prometheus::{hostname_grouping_key, BasicAuthentication};
 use prometheus::{Encoder, TextEncoder};
 use starcoin_logger::prelude::*;
 use std::{net::SocketAddr, thread};
 use tokio::runtime;
 
+const NETWORK_LABEL: &str = "network";
+
+static NETWORK: OnceCell<String> = OnceCell::new();
+
+/// Set the network of the node, it is added as the `network` label to all exported metrics,
+/// so the metrics of the nodes of different networks can be distinguished.
+pub fn set_network(network: String) {
+    if NETWORK.set(network).is_err() {
+        warn!("The network label of metrics has been set.");
+    }
+}
+
+fn gather() -> Vec<MetricFamily> {
+    let mut metric_families = prometheus::gather();
+    if let Some(network) = NETWORK.get() {
+        for metric in metric_families
+            .iter_mut()
+            .flat_map(|family| family.mut_metric().iter_mut())
+        {
+            if metric
+                .get_label()
+                .iter()
+                .any(|label| label.get_name() == NETWORK_LABEL)
+            {
+                continue;
+            }
+            let mut label = LabelPair::default();
+            label.set_name(NETWORK_LABEL.to_string());
+            label.set_value(network.clone());
+            metric.mut_label().push(label);
+        }
+    }
+    metric_families
+}
+
 fn encode_metrics(encoder: impl Encoder) -> Vec<u8> {
-    let metric_families = prometheus::gather();
+    let metric_families = gather();
     let mut buffer = vec![];
     //if encode error, just return empty body.
     if let Err(e) = encoder.encode(&metric_families, &mut buffer) {
@@ -83,7 +120,7 @@ pub fn start_server(addr: SocketAddr) {
     });
 }
 pub fn push_metrics(push_server_url: String, auth_username: Option<String>, auth_password: String) {
-    let metric_families = prometheus::gather();
+    let metric_families = gather();
     let basic_auth = match auth_username {
         Some(username) => Some(BasicAuthentication {
             username,
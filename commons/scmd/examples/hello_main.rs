@@ -200,6 +200,30 @@ struct TestOpts {
     debug: bool,
 }
 
+#[derive(Debug, StructOpt)]
+#[structopt(name = "echo")]
+struct EchoOpts {
+    #[structopt(name = "message")]
+    message: String,
+}
+
+/// The echo command does not need the state.
+struct EchoCommand;
+
+impl CommandAction for EchoCommand {
+    type State = ();
+    type GlobalOpt = GlobalOpts;
+    type Opt = EchoOpts;
+    type ReturnItem = String;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        Ok(ctx.opt().message.clone())
+    }
+}
+
 pub(crate) fn init_context() -> CmdContext<Counter, GlobalOpts> {
     let context = CmdContext::<Counter, GlobalOpts>::with_default_action(
         "0.1",
@@ -221,6 +245,7 @@ pub(crate) fn init_context() -> CmdContext<Counter, GlobalOpts> {
     context
         .command(ListCommand)
         .command(ShowCommand)
+        .stateless_command(EchoCommand)
         .command(
             Command::with_name("alpha").subcommand(Command::with_action_fn(
                 |ctx: &ExecContext<Counter, GlobalOpts, AlphaSub1Opts>| -> Result<()> {
@@ -272,4 +297,53 @@ mod tests {
         assert_eq!(result.len(), count);
         Ok(())
     }
+
+    #[test]
+    fn test_command_hook() -> Result<()> {
+        let hook_count = Arc::new(AtomicUsize::new(0));
+        let hook_count2 = hook_count.clone();
+        let context = init_context().with_command_hook(move |_state| {
+            hook_count2.fetch_add(1, Ordering::SeqCst);
+        });
+        let result = context.exec_with_args::<Vec<User>>(vec![
+            "hello",
+            "-r",
+            "test_required",
+            "list",
+            "-c",
+            "1",
+        ])?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(hook_count.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stateless_command() -> Result<()> {
+        let init_count = Arc::new(AtomicUsize::new(0));
+        let new_context = || {
+            let init_count = init_count.clone();
+            CmdContext::<Counter, GlobalOpts>::with_initializer("0.1", None, move |_| {
+                init_count.fetch_add(1, Ordering::SeqCst);
+                anyhow::bail!("the state is unavailable")
+            })
+            .command(ListCommand)
+            .stateless_command(EchoCommand)
+        };
+        let result = new_context().exec_with_args::<String>(vec![
+            "hello",
+            "-r",
+            "test_required",
+            "echo",
+            "hi",
+        ])?;
+        assert_eq!(result, "hi");
+        assert_eq!(init_count.load(Ordering::SeqCst), 0);
+
+        let result =
+            new_context().exec_with_args::<Vec<User>>(vec!["hello", "-r", "test_required", "list"]);
+        assert!(result.is_err());
+        assert_eq!(init_count.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
 }
@@ -40,12 +40,14 @@ where
 {
     app: App<'static, 'static>,
     commands: HashMap<String, Box<dyn CommandExec<State, GlobalOpt>>>,
+    stateless_commands: HashMap<String, Box<dyn CommandExec<(), GlobalOpt>>>,
     default_action: Box<dyn FnOnce(App, GlobalOpt, State)>,
     state_initializer: Box<dyn FnOnce(&GlobalOpt) -> Result<State>>,
     console_support: Option<(
         Box<dyn FnOnce(&App, Arc<GlobalOpt>, Arc<State>) -> (ConsoleConfig, Option<PathBuf>)>,
         Box<dyn FnOnce(App, GlobalOpt, State)>,
     )>,
+    command_hook: Option<Box<dyn Fn(&State)>>,
 }
 
 impl<State, GlobalOpt> CmdContext<State, GlobalOpt>
@@ -101,7 +103,8 @@ where
             .arg(
                 Arg::with_name(OUTPUT_FORMAT_ARG)
                     .short("o")
-                    .help("set output-format, support [json|table]")
+                    .long(OUTPUT_FORMAT_ARG)
+                    .help("set output-format, support [json|jsonl|table]")
                     .takes_value(true)
                     .default_value("table"),
             );
@@ -109,12 +112,23 @@ where
         Self {
             app,
             commands: HashMap::new(),
+            stateless_commands: HashMap::new(),
             default_action: Box::new(default_action),
             state_initializer: Box::new(state_initializer),
             console_support: None,
+            command_hook: None,
         }
     }
 
+    /// Run the hook before executing every command, in the console too.
+    pub fn with_command_hook<H>(mut self, hook: H) -> Self
+    where
+        H: Fn(&State) + 'static,
+    {
+        self.command_hook = Some(Box::new(hook));
+        self
+    }
+
     pub fn with_console_support_default(self) -> Self {
         self.with_console_support(
             |_, _, _| -> (ConsoleConfig, Option<PathBuf>) { (*DEFAULT_CONSOLE_CONFIG, None) },
@@ -151,10 +165,8 @@ where
     {
         let command = command.into();
         let name = command.name();
-        if self.commands.contains_key(name) {
-            panic!("Command with name {} exist.", name);
-        }
-        let order = self.commands.len();
+        self.check_command_name(name);
+        let order = self.commands_len();
         self.app = self
             .app
             .subcommand(command.app().clone().display_order(order));
@@ -162,6 +174,38 @@ where
         self
     }
 
+    /// Add the command which does not need the state, the state is not initialized when executing it,
+    /// so it works even if the state initializer fails, such as the offline tools.
+    pub fn stateless_command<Opt, ReturnItem, Action, CMD>(mut self, command: CMD) -> Self
+    where
+        Opt: StructOpt + 'static,
+        ReturnItem: serde::Serialize + 'static,
+        Action: CommandAction<State = (), GlobalOpt = GlobalOpt, Opt = Opt, ReturnItem = ReturnItem>
+            + 'static,
+        CMD: Into<Command<(), GlobalOpt, Opt, ReturnItem, Action>> + 'static,
+    {
+        let command = command.into();
+        let name = command.name();
+        self.check_command_name(name);
+        let order = self.commands_len();
+        self.app = self
+            .app
+            .subcommand(command.app().clone().display_order(order));
+        self.stateless_commands
+            .insert(name.to_string(), Box::new(command));
+        self
+    }
+
+    fn check_command_name(&self, name: &str) {
+        if self.commands.contains_key(name) || self.stateless_commands.contains_key(name) {
+            panic!("Command with name {} exist.", name);
+        }
+    }
+
+    fn commands_len(&self) -> usize {
+        self.commands.len() + self.stateless_commands.len()
+    }
+
     pub fn print_help(&mut self) {
         self.app
             .print_long_help()
@@ -212,22 +256,32 @@ where
             .expect("parse output-format must success.");
 
         let global_opt = GlobalOpt::from_clap(&matches);
+        let (cmd_name, arg_matches) = matches.subcommand();
+        if let (Some(cmd), Some(arg_matches)) =
+            (self.stateless_commands.get_mut(cmd_name), arg_matches)
+        {
+            let result = cmd.exec(Arc::new(()), Arc::new(global_opt), arg_matches);
+            return Ok((output_format, result));
+        }
         let state = (self.state_initializer)(&global_opt)?;
 
-        let (cmd_name, arg_matches) = matches.subcommand();
         let default_action = self.default_action;
+        let command_hook = self.command_hook;
         let result = match cmd_name {
             "console" => {
                 if let Some((init_action, quit_action)) = self.console_support {
                     let commands = self.commands;
+                    let stateless_commands = self.stateless_commands;
 
                     Self::console_inner(
                         app,
                         global_opt,
                         state,
                         commands,
+                        stateless_commands,
                         init_action,
                         quit_action,
+                        command_hook,
                         output_format,
                     );
                     Ok(Value::Null)
@@ -243,6 +297,9 @@ where
                 let cmd = self.commands.get_mut(cmd_name);
                 match (cmd, arg_matches) {
                     (Some(cmd), Some(arg_matches)) => {
+                        if let Some(hook) = command_hook.as_ref() {
+                            hook(&state);
+                        }
                         cmd.exec(Arc::new(state), Arc::new(global_opt), arg_matches)
                         //print_action_result(value, output_format)?;
                     }
@@ -253,15 +310,18 @@ where
         Ok((output_format, result))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn console_inner(
         app: App,
         global_opt: GlobalOpt,
         state: State,
         mut commands: HashMap<String, Box<dyn CommandExec<State, GlobalOpt>>>,
+        mut stateless_commands: HashMap<String, Box<dyn CommandExec<(), GlobalOpt>>>,
         init_action: Box<
             dyn FnOnce(&App, Arc<GlobalOpt>, Arc<State>) -> (ConsoleConfig, Option<PathBuf>),
         >,
         quit_action: Box<dyn FnOnce(App, GlobalOpt, State)>,
+        command_hook: Option<Box<dyn Fn(&State)>>,
         mut output_format: OutputFormat,
     ) {
         //insert version, quit, history command
@@ -388,6 +448,9 @@ where
                                     let app = cmd.get_app();
                                     match app.get_matches_from_safe_borrow(params) {
                                         Ok(arg_matches) => {
+                                            if let Some(hook) = command_hook.as_ref() {
+                                                hook(state.as_ref());
+                                            }
                                             let result = cmd.exec(
                                                 state.clone(),
                                                 global_opt.clone(),
@@ -404,10 +467,32 @@ where
                                         }
                                     }
                                 }
-                                _ => {
-                                    println!("Unknown command: {:?}", cmd_name);
-                                    app.print_long_help().expect("print help should success.");
-                                }
+                                None => match stateless_commands.get_mut(cmd_name) {
+                                    Some(cmd) => {
+                                        let app = cmd.get_app();
+                                        match app.get_matches_from_safe_borrow(params) {
+                                            Ok(arg_matches) => {
+                                                let result = cmd.exec(
+                                                    Arc::new(()),
+                                                    global_opt.clone(),
+                                                    &arg_matches,
+                                                );
+                                                if let Err(err) =
+                                                    print_action_result(output_format, result, true)
+                                                {
+                                                    println!("Print result error: {:?}", err);
+                                                }
+                                            }
+                                            Err(e) => {
+                                                println!("{}", e);
+                                            }
+                                        }
+                                    }
+                                    None => {
+                                        println!("Unknown command: {:?}", cmd_name);
+                                        app.print_long_help().expect("print help should success.");
+                                    }
+                                },
                             }
                         }
                     }
@@ -0,0 +1,82 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::io::Read;
+
+/// Parse the items piped from the output of another command.
+///
+/// The input is a stream of json values, such as the output of `-o json` or `-o jsonl`.
+/// The `{"ok": ...}` envelope of the json output is unwrapped and an array is flattened to its items,
+/// an `{"err": ...}` envelope fails the parsing with the error of the upstream command.
+pub fn parse_piped_values(input: &str) -> Result<Vec<Value>> {
+    let mut items = vec![];
+    for value in serde_json::Deserializer::from_str(input).into_iter::<Value>() {
+        let value = match value? {
+            Value::Object(mut obj) if obj.len() == 1 && obj.contains_key("ok") => {
+                obj.remove("ok").expect("ok field must exist")
+            }
+            Value::Object(obj) if obj.len() == 1 && obj.contains_key("err") => {
+                bail!(
+                    "The upstream command failed: {}",
+                    obj["err"].as_str().unwrap_or("unknown error")
+                )
+            }
+            value => value,
+        };
+        match value {
+            Value::Null => {}
+            Value::Array(values) => items.extend(values),
+            value => items.push(value),
+        }
+    }
+    Ok(items)
+}
+
+/// Read the piped items from the stdin, see `parse_piped_values` for the input format.
+pub fn read_piped_values() -> Result<Vec<Value>> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    parse_piped_values(input.as_str())
+}
+
+/// Read the piped items from the stdin and deserialize every item to `T`.
+pub fn read_piped_items<T: DeserializeOwned>() -> Result<Vec<T>> {
+    read_piped_values()?
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            serde_json::from_value(value)
+                .map_err(|e| format_err!("Invalid input item at {}: {}", index, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_piped_values() {
+        let json_output = "{\n  \"ok\": [\n    {\"a\": 1},\n    {\"a\": 2}\n  ]\n}\n";
+        assert_eq!(
+            parse_piped_values(json_output).unwrap(),
+            vec![json!({"a": 1}), json!({"a": 2})]
+        );
+
+        let jsonl_output = "{\"a\":1}\n{\"a\":2}\n\"0x1\"\n";
+        assert_eq!(
+            parse_piped_values(jsonl_output).unwrap(),
+            vec![json!({"a": 1}), json!({"a": 2}), json!("0x1")]
+        );
+
+        assert!(parse_piped_values("").unwrap().is_empty());
+        assert!(parse_piped_values("{\"ok\": null}").unwrap().is_empty());
+        let err = parse_piped_values("{\"err\": \"account not found\"}").unwrap_err();
+        assert!(err.to_string().contains("account not found"));
+        assert!(parse_piped_values("{\"a\": ").is_err());
+    }
+}
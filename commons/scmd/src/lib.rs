@@ -5,9 +5,11 @@ mod action;
 mod command;
 mod context;
 pub mod error;
+mod input;
 mod result;
 
 pub use action::*;
 pub use command::*;
 pub use context::*;
+pub use input::*;
 pub use result::*;
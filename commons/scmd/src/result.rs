@@ -12,6 +12,9 @@ use std::str::FromStr;
 #[allow(clippy::upper_case_acronyms)]
 pub enum OutputFormat {
     JSON,
+    /// One compact json value per line, the items of an array result are printed line by line,
+    /// so the output can be streamed to another command.
+    JSONL,
     TABLE,
 }
 
@@ -21,6 +24,7 @@ impl FromStr for OutputFormat {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "json" | "JSON" => OutputFormat::JSON,
+            "jsonl" | "JSONL" => OutputFormat::JSONL,
             _ => OutputFormat::TABLE,
         })
     }
@@ -31,6 +35,7 @@ impl std::fmt::Display for OutputFormat {
         let s = match self {
             OutputFormat::TABLE => "TABLE",
             OutputFormat::JSON => "JSON",
+            OutputFormat::JSONL => "JSONL",
         };
         write!(f, "{}", s)
     }
@@ -55,6 +60,16 @@ pub fn print_action_result(
             };
             print_json(value)
         }
+        OutputFormat::JSONL => {
+            if console_mode && result.is_err() {
+                println!("{}", result.unwrap_err().to_string());
+                return Ok(());
+            }
+            match result {
+                Ok(value) => print_json_lines(value),
+                Err(err) => print_json_line(&json!({"err": err.to_string()})),
+            }
+        }
         OutputFormat::TABLE => {
             match result {
                 Ok(value) => print_table(value)?,
@@ -72,6 +87,19 @@ pub fn print_json(value: Value) -> Result<()> {
     Ok(())
 }
 
+pub fn print_json_lines(value: Value) -> Result<()> {
+    match value {
+        Value::Null => Ok(()),
+        Value::Array(values) => values.iter().try_for_each(print_json_line),
+        value => print_json_line(&value),
+    }
+}
+
+fn print_json_line(value: &Value) -> Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
 fn build_rows(values: &[Value]) -> Result<(Vec<Row>, Box<dyn RowBuilder>)> {
     let bold = CellFormat::builder().bold(true).build();
     let mut rows = vec![];
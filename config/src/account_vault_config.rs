@@ -7,6 +7,7 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 static DEFAULT_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("account_vaults"));
@@ -20,6 +21,27 @@ pub struct AccountVaultConfig {
     /// Default: account_vaults in data_dir
     dir: Option<PathBuf>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-unlock-idle-timeout")]
+    /// Auto lock the unlocked account after it is idle for the seconds.
+    /// Default: no idle timeout, the account keeps unlocked until the unlock duration expired.
+    unlock_idle_timeout: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-sign-policy", parse(from_os_str))]
+    /// The rhai script file of the sign policy, its `check(txn)` function inspects the txn
+    /// before it is signed and returns "allow", "deny" or "ask".
+    /// The "ask" txns are approved by `node admin approve-txn` through the ipc of the node.
+    /// Default: no sign policy, all txns of the unlocked accounts are signed.
+    sign_policy: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-allow-unknown-spend")]
+    /// Allow the unlock sessions with a max amount to sign the txns whose spend amount can not be
+    /// known before executing, such as scripts and the script functions other than STC transfers.
+    /// Default: false, such txns are rejected by the sessions with a max amount.
+    allow_unknown_spend: Option<bool>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -38,6 +60,25 @@ impl AccountVaultConfig {
             self.base().data_dir().join(path)
         }
     }
+
+    pub fn unlock_idle_timeout(&self) -> Option<Duration> {
+        self.unlock_idle_timeout.map(Duration::from_secs)
+    }
+
+    pub fn allow_unknown_spend(&self) -> bool {
+        self.allow_unknown_spend.unwrap_or(false)
+    }
+
+    /// The sign policy script file, a relative path is in the data_dir.
+    pub fn sign_policy(&self) -> Option<PathBuf> {
+        self.sign_policy.as_ref().map(|path| {
+            if path.is_absolute() {
+                path.clone()
+            } else {
+                self.base().data_dir().join(path)
+            }
+        })
+    }
 }
 
 impl ConfigModule for AccountVaultConfig {
@@ -46,6 +87,15 @@ impl ConfigModule for AccountVaultConfig {
         if opt.vault.dir.is_some() {
             self.dir = opt.vault.dir.clone();
         }
+        if opt.vault.unlock_idle_timeout.is_some() {
+            self.unlock_idle_timeout = opt.vault.unlock_idle_timeout;
+        }
+        if opt.vault.sign_policy.is_some() {
+            self.sign_policy = opt.vault.sign_policy.clone();
+        }
+        if opt.vault.allow_unknown_spend.is_some() {
+            self.allow_unknown_spend = opt.vault.allow_unknown_spend;
+        }
         Ok(())
     }
 }
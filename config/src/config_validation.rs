@@ -0,0 +1,385 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Api, NodeConfig};
+use network_api::messages::GossipTopic;
+use starcoin_logger::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+
+/// The apis can manage the node or the accounts, should not be exposed on a public address.
+const ADMIN_APIS: [Api; 5] = [
+    Api::Account,
+    Api::Debug,
+    Api::NetworkManager,
+    Api::NodeManager,
+    Api::SyncManager,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ViolationLevel {
+    /// The node can start, but some settings do not take effect as expected.
+    Warning,
+    /// The node would fail or misbehave at runtime, refuse to start.
+    Error,
+}
+
+impl Display for ViolationLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A conflict of the interdependent settings of the node config.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigViolation {
+    pub level: ViolationLevel,
+    /// The conflicting settings, such as `tx_pool.mirror, miner.disable_miner_client`.
+    pub settings: String,
+    pub message: String,
+    /// How to fix the conflict.
+    pub suggestion: String,
+}
+
+impl ConfigViolation {
+    fn new(
+        level: ViolationLevel,
+        settings: impl Into<String>,
+        message: impl Into<String>,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            level,
+            settings: settings.into(),
+            message: message.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level == ViolationLevel::Error
+    }
+}
+
+impl Display for ConfigViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}\n     fix: {}",
+            self.level, self.settings, self.message, self.suggestion
+        )
+    }
+}
+
+/// The node config has error violations, all the violations are reported at once.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigValidationError {
+    pub violations: Vec<ConfigViolation>,
+}
+
+impl Display for ConfigValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let error_count = self.violations.iter().filter(|v| v.is_error()).count();
+        write!(
+            f,
+            "Invalid node config, found {} error(s) in {} violation(s):",
+            error_count,
+            self.violations.len()
+        )?;
+        for (index, violation) in self.violations.iter().enumerate() {
+            write!(f, "\n  {}. {}", index + 1, violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigValidationError {}
+
+impl NodeConfig {
+    /// Cross check the interdependent settings of the config modules,
+    /// the errors are sorted before the warnings.
+    pub fn validate(&self) -> Vec<ConfigViolation> {
+        let mut violations = vec![];
+        check_mirror(self, &mut violations);
+        check_threshold_files(self, &mut violations);
+        check_miner_template(self, &mut violations);
+        check_miner_reward(self, &mut violations);
+        check_block_compression(self, &mut violations);
+        check_rpc_exposure(self, &mut violations);
+        check_rpc_endpoints(self, &mut violations);
+        check_port_conflicts(self, &mut violations);
+        check_propagation(self, &mut violations);
+        check_backup(self, &mut violations);
+        violations.sort_by_key(|v| !v.is_error());
+        violations
+    }
+
+    /// Validate the config, log the warnings, and return all the violations as an error if
+    /// there is any error violation.
+    pub fn ensure_valid(&self) -> Result<(), ConfigValidationError> {
+        let violations = self.validate();
+        if violations.iter().any(|v| v.is_error()) {
+            return Err(ConfigValidationError { violations });
+        }
+        for violation in violations {
+            warn!("Node config {}", violation);
+        }
+        Ok(())
+    }
+}
+
+fn check_mirror(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if !config.tx_pool.is_mirror() {
+        return;
+    }
+    if config.miner.disable_miner_client == Some(false) {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Error,
+            "tx_pool.mirror, miner.disable_miner_client",
+            "the mirror node never mints blocks, but the miner client is enabled",
+            "remove `miner.disable_miner_client`, or disable `tx_pool.mirror`",
+        ));
+    }
+    if !config
+        .network
+        .gossip_topics()
+        .contains(&GossipTopic::Transactions)
+    {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Error,
+            "tx_pool.mirror, network.gossip_topics",
+            "the mirror node only receives txns from the gossip, but the transactions topic is not subscribed",
+            format!(
+                "add `{}` to `network.gossip_topics`, or remove `network.gossip_topics` to subscribe all the topics",
+                GossipTopic::Transactions
+            ),
+        ));
+    }
+    if config.tx_pool.threshold_key_share_file().is_some() {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Warning,
+            "tx_pool.mirror, tx_pool.threshold_key_share_file",
+            "the encrypted pool is not started in the mirror mode, the key share is ignored",
+            "remove `tx_pool.threshold_key_share_file`",
+        ));
+    }
+}
+
+fn check_threshold_files(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if config.tx_pool.threshold_key_share_file().is_some()
+        && config.tx_pool.threshold_committee_file().is_none()
+    {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Error,
+            "tx_pool.threshold_key_share_file, tx_pool.threshold_committee_file",
+            "the key share can not be verified without the committee",
+            "set `tx_pool.threshold_committee_file` to the committee of the key share",
+        ));
+    }
+}
+
+fn check_miner_template(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if config.miner.template_refresh_interval().is_some() {
+        return;
+    }
+    if config.miner.template_min_fee_delta() > 0 {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Warning,
+            "miner.template_min_fee_delta, miner.template_refresh_interval",
+            "the min fee delta only applies to the periodic template refresh, which is disabled",
+            "set `miner.template_refresh_interval`, or remove `miner.template_min_fee_delta`",
+        ));
+    }
+    if !config.miner.refresh_template_on_new_head() {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Warning,
+            "miner.refresh_template_on_new_head, miner.template_refresh_interval",
+            "the block template is never refreshed, the miner keeps mining on a stale parent",
+            "set `miner.template_refresh_interval`, or enable `miner.refresh_template_on_new_head`",
+        ));
+    }
+}
+
+fn check_miner_reward(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if config.net().is_test_or_dev()
+        || config.tx_pool.is_mirror()
+        || config.miner.miner_client_config().is_none()
+        || config.miner.reward_address().is_some()
+    {
+        return;
+    }
+    violations.push(ConfigViolation::new(
+        ViolationLevel::Warning,
+        "miner.disable_miner_client, miner.reward_address",
+        "the miner is enabled without a reward address, the rewards go to the default account of the node wallet, which is auto created with an empty password if absent",
+        "set `miner.reward_address` to an account you control, or set `miner.disable_miner_client`",
+    ));
+}
+
+fn check_block_compression(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if let (Some(compression_depth), Some(max_reorg_depth)) = (
+        config.storage.block_compression_depth(),
+        config.sync.max_reorg_depth(),
+    ) {
+        if compression_depth <= max_reorg_depth {
+            violations.push(ConfigViolation::new(
+                ViolationLevel::Warning,
+                "storage.block_compression_depth, sync.max_reorg_depth",
+                format!(
+                    "the blocks deeper than {} are compressed, but a reorg can rollback {} blocks, the rollback has to decompress the blocks",
+                    compression_depth, max_reorg_depth
+                ),
+                format!(
+                    "set `storage.block_compression_depth` greater than {}",
+                    max_reorg_depth
+                ),
+            ));
+        }
+    }
+}
+
+fn check_rpc_exposure(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    let rpc_address = config.rpc.rpc_address();
+    if rpc_address.is_loopback() {
+        return;
+    }
+    // The rpc has no authentication, the admin apis on a public address can be called by anyone,
+    // unless the address is protected outside the node, which the operator has to acknowledge.
+    let level = if config.net().is_test_or_dev() || config.rpc.allow_public_admin_apis() {
+        ViolationLevel::Warning
+    } else {
+        ViolationLevel::Error
+    };
+    let endpoints = [
+        ("http", config.rpc.http.disable, config.rpc.http.apis()),
+        ("tcp", config.rpc.tcp.disable, config.rpc.tcp.apis()),
+        ("ws", config.rpc.ws.disable, config.rpc.ws.apis()),
+    ];
+    for (name, disable, apis) in endpoints.iter() {
+        if *disable {
+            continue;
+        }
+        let apis = apis.list_apis();
+        let mut exposed: Vec<String> = ADMIN_APIS
+            .iter()
+            .filter(|api| apis.contains(*api))
+            .map(ToString::to_string)
+            .collect();
+        if exposed.is_empty() {
+            continue;
+        }
+        exposed.sort();
+        violations.push(ConfigViolation::new(
+            level,
+            format!("rpc.rpc_address, rpc.{}.apis", name),
+            format!(
+                "the admin apis [{}] are exposed on {} without authentication",
+                exposed.join(","),
+                rpc_address
+            ),
+            format!(
+                "set `--rpc-address 127.0.0.1`, or remove [{}] from `rpc.{}.apis` and use the ipc for the admin apis, or set `--rpc-allow-public-admin-apis` if the address is firewalled",
+                exposed.join(","),
+                name
+            ),
+        ));
+    }
+}
+
+fn check_rpc_endpoints(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    let rpc = &config.rpc;
+    if rpc.http.disable && rpc.tcp.disable && rpc.ws.disable && rpc.ipc.disable {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Warning,
+            "rpc.http.disable, rpc.tcp.disable, rpc.ws.disable, rpc.ipc.disable",
+            "all the rpc endpoints are disabled, the node can not be managed by the cli",
+            "enable the ipc endpoint at least",
+        ));
+    }
+}
+
+fn check_port_conflicts(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    let mut listeners: Vec<(&str, SocketAddr)> = vec![];
+    if let Some(address) = config.rpc.get_http_address() {
+        listeners.push(("rpc.http.port", address.into()));
+    }
+    if let Some(address) = config.rpc.get_tcp_address() {
+        listeners.push(("rpc.tcp.port", address.into()));
+    }
+    if let Some(address) = config.rpc.get_ws_address() {
+        listeners.push(("rpc.ws.port", address.into()));
+    }
+    // The default stratum port is resolved at startup, only check the configured one.
+    if config.stratum.port.is_some() && !config.tx_pool.is_mirror() {
+        if let Some(address) = config.stratum.get_address() {
+            listeners.push(("stratum.port", address));
+        }
+    }
+    if let Some(address) = config.metrics.metrics_address() {
+        listeners.push(("metrics.port", address));
+    }
+    for (i, (setting, address)) in listeners.iter().enumerate() {
+        for (other_setting, other_address) in listeners.iter().skip(i + 1) {
+            if address.port() == other_address.port()
+                && (address.ip() == other_address.ip()
+                    || address.ip().is_unspecified()
+                    || other_address.ip().is_unspecified())
+            {
+                violations.push(ConfigViolation::new(
+                    ViolationLevel::Error,
+                    format!("{}, {}", setting, other_setting),
+                    format!(
+                        "{} and {} both listen on the port {}",
+                        setting,
+                        other_setting,
+                        address.port()
+                    ),
+                    format!("change `{}` or `{}`", setting, other_setting),
+                ));
+            }
+        }
+    }
+}
+
+fn check_propagation(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    let min_peers = config.network.min_peers_to_propagate();
+    let max_peers = config.network.max_peers_to_propagate();
+    if min_peers > max_peers {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Error,
+            "network.min_peers_to_propagate, network.max_peers_to_propagate",
+            format!(
+                "the min peers to propagate {} is greater than the max {}",
+                min_peers, max_peers
+            ),
+            "set `network.min_peers_to_propagate` not greater than `network.max_peers_to_propagate`",
+        ));
+    }
+    let max_per_sender = config.tx_pool.max_per_sender();
+    let max_count = config.tx_pool.max_count();
+    if max_per_sender > max_count {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Warning,
+            "tx_pool.max_per_sender, tx_pool.max_count",
+            format!(
+                "the txns limit per sender {} is greater than the pool limit {}",
+                max_per_sender, max_count
+            ),
+            "set `tx_pool.max_per_sender` not greater than `tx_pool.max_count`",
+        ));
+    }
+}
+
+fn check_backup(config: &NodeConfig, violations: &mut Vec<ConfigViolation>) {
+    if config.maintenance.backup.is_some() && config.maintenance.backup_keep() == 0 {
+        violations.push(ConfigViolation::new(
+            ViolationLevel::Error,
+            "maintenance.backup, maintenance.backup_keep",
+            "the backup is scheduled, but no backup is kept",
+            "set `maintenance.backup_keep` to 1 at least, or remove `maintenance.backup`",
+        ));
+    }
+}
@@ -179,6 +179,24 @@ impl BuiltinNetworkID {
             _ => format!("{}.seed.starcoin.org", self),
         }
     }
+
+    /// The faucet fund api of the network, only available for public test networks.
+    pub fn faucet_url(self) -> Option<String> {
+        match self {
+            BuiltinNetworkID::Halley | BuiltinNetworkID::Proxima | BuiltinNetworkID::Barnard => {
+                Some(format!("https://faucet.starcoin.org/{}/api/fund", self))
+            }
+            _ => None,
+        }
+    }
+
+    /// The block explorer of the network.
+    pub fn explorer_url(self) -> Option<String> {
+        match self {
+            BuiltinNetworkID::Test | BuiltinNetworkID::Dev => None,
+            _ => Some(format!("https://stcscan.io/{}", self)),
+        }
+    }
 }
 
 impl Default for BuiltinNetworkID {
@@ -354,6 +372,13 @@ impl ChainNetworkID {
         matches!(self, Self::Custom(_))
     }
 
+    pub fn faucet_url(&self) -> Option<String> {
+        match self {
+            Self::Builtin(net) => net.faucet_url(),
+            Self::Custom(_) => None,
+        }
+    }
+
     /// Default data dir name of this network
     pub fn dir_name(&self) -> String {
         match self {
@@ -3,8 +3,7 @@
 
 use crate::account_vault_config::AccountVaultConfig;
 use crate::helper::{load_config, save_config};
-use crate::sync_config::SyncConfig;
-use anyhow::{ensure, format_err, Result};
+use anyhow::{bail, ensure, Result};
 use git_version::git_version;
 use once_cell::sync::Lazy;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -28,13 +27,17 @@ mod account_vault_config;
 mod api_config;
 mod api_quota;
 mod available_port;
+mod config_validation;
 pub mod genesis_config;
 mod helper;
 mod logger_config;
+mod maintenance_config;
 mod metrics_config;
 mod miner_config;
 mod network_config;
+mod network_descriptor;
 mod rpc_config;
+mod soak_check_config;
 mod storage_config;
 mod stratum_config;
 mod sync_config;
@@ -48,6 +51,7 @@ pub use api_quota::{ApiQuotaConfig, QuotaDuration};
 pub use available_port::{
     get_available_port_from, get_random_available_port, get_random_available_ports,
 };
+pub use config_validation::{ConfigValidationError, ConfigViolation, ViolationLevel};
 pub use diem_temppath::TempPath;
 pub use genesis_config::{
     BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
@@ -56,17 +60,21 @@ pub use genesis_config::{
     MAIN_CONFIG, PROXIMA_CONFIG, TEST_CONFIG, TEST_GAS_SCHEDULE,
 };
 pub use logger_config::LoggerConfig;
+pub use maintenance_config::{MaintenanceConfig, MaintenanceSchedule};
 pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
+pub use network_descriptor::{NetworkDescriptor, NETWORK_DESCRIPTOR_DIR};
 pub use rpc_config::{
     ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
     WsConfiguration,
 };
+pub use soak_check_config::SoakCheckConfig;
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
 pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
-pub use txpool_config::TxPoolConfig;
+pub use sync_config::{SyncCheckpoint, SyncConfig};
+pub use txpool_config::{TxPoolConfig, DEFAULT_IDEMPOTENCY_KEY_RETENTION};
 
 pub static CRATE_VERSION: &str = crate_version!();
 pub static GIT_VERSION: &str = git_version!(
@@ -94,6 +102,7 @@ pub static DEFAULT_BASE_DATA_DIR: Lazy<PathBuf> = Lazy::new(|| {
 });
 pub static CONFIG_FILE_PATH: &str = "config.toml";
 pub static GENESIS_CONFIG_FILE_NAME: &str = "genesis_config.json";
+pub static GENESIS_FILE_NAME: &str = "genesis";
 
 pub fn load_config_with_opt(opt: &StarcoinOpt) -> Result<NodeConfig> {
     NodeConfig::load_with_opt(opt)
@@ -135,6 +144,8 @@ pub enum Connect {
     IPC(Option<PathBuf>),
     /// Connect by json rpc address.
     WebSocket(String),
+    /// Connect by http json rpc address.
+    Http(String),
 }
 
 impl Default for Connect {
@@ -152,6 +163,8 @@ impl FromStr for Connect {
         }
         if s.starts_with("ws://") || s.starts_with("wss://") {
             Ok(Connect::WebSocket(s.to_string()))
+        } else if s.starts_with("http://") || s.starts_with("https://") {
+            Ok(Connect::Http(s.to_string()))
         } else {
             Ok(Connect::IPC(Some(PathBuf::from_str(s)?)))
         }
@@ -194,6 +207,23 @@ pub struct StarcoinOpt {
     /// This option only work for node init start.
     pub genesis_config: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "genesis-file", parse(from_os_str))]
+    /// Init chain by a custom genesis file generated by the `genesis generate` command,
+    /// should be used with the `genesis-config` option. This option only work for node init start.
+    pub genesis_file: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "txn-relay", number_of_values = 1)]
+    /// Submit transactions through the http rpc of the remote relay nodes instead of the connected node,
+    /// can be repeated, the relays are used in round-robin. This option only work for cli.
+    pub txn_relays: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "txn-relay-proxy")]
+    /// Proxy to connect the txn relays, such as the tor socks proxy socks5h://127.0.0.1:9050.
+    pub txn_relay_proxy: Option<String>,
+
     #[structopt(flatten)]
     pub rpc: RpcConfig,
     #[structopt(flatten)]
@@ -215,6 +245,12 @@ pub struct StarcoinOpt {
     #[serde(default)]
     #[structopt(flatten)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub soak_check: SoakCheckConfig,
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub maintenance: MaintenanceConfig,
 }
 
 impl std::fmt::Display for StarcoinOpt {
@@ -262,6 +298,8 @@ pub struct BaseConfig {
     pub net: ChainNetwork,
     pub base_data_dir: DataDirPath,
     pub data_dir: PathBuf,
+    /// The installed descriptor of the custom network.
+    pub network_descriptor: Option<NetworkDescriptor>,
 }
 
 impl BaseConfig {
@@ -282,23 +320,48 @@ impl BaseConfig {
         if !data_dir.exists() {
             create_dir_all(data_dir.as_path())?;
         }
+        let network_descriptor = NetworkDescriptor::find_installed(base_data_dir.as_ref(), &id)?;
         let genesis_config = Self::load_genesis_config_by_opt(
             id.clone(),
             data_dir.as_path(),
             opt.genesis_config.clone(),
+            network_descriptor
+                .as_ref()
+                .and_then(|descriptor| descriptor.genesis_config.clone()),
         )?;
+        if let Some(genesis_file) = opt.genesis_file.as_ref() {
+            Self::init_genesis_file(data_dir.as_path(), genesis_file.as_path())?;
+        }
         let net = ChainNetwork::new(id, genesis_config);
         Ok(Self {
             net,
             base_data_dir,
             data_dir,
+            network_descriptor,
         })
     }
 
+    /// Copy the custom genesis file to the data dir, the genesis is checked when the node init storage.
+    fn init_genesis_file(data_dir: &Path, genesis_file: &Path) -> Result<()> {
+        let genesis_path = data_dir.join(GENESIS_FILE_NAME);
+        if genesis_path.exists() {
+            ensure!(
+                fs::read(genesis_path.as_path())? == fs::read(genesis_file)?,
+                "The genesis in {:?} is different from the genesis file {:?}",
+                genesis_path,
+                genesis_file
+            );
+        } else {
+            fs::copy(genesis_file, genesis_path)?;
+        }
+        Ok(())
+    }
+
     fn load_genesis_config_by_opt(
         id: ChainNetworkID,
         data_dir: &Path,
         genesis_config_name: Option<String>,
+        installed_genesis_config: Option<GenesisConfig>,
     ) -> Result<GenesisConfig> {
         let config_path = data_dir.join(GENESIS_CONFIG_FILE_NAME);
         let config_in_file = if config_path.exists() {
@@ -327,14 +390,18 @@ impl BaseConfig {
                 genesis_config
             }
             (None, ChainNetworkID::Custom(_net)) => {
-                let config_name_or_path = genesis_config_name.ok_or_else(|| format_err!("Can not load genesis config from {:?}, please set `genesis-config` cli option.", config_path))?;
-                let genesis_config = match BuiltinNetworkID::from_str(config_name_or_path.as_str())
-                {
-                    Ok(net) => net.genesis_config().clone(),
-                    Err(_) => {
-                        let path = Path::new(config_name_or_path.as_str());
-                        GenesisConfig::load(path)?
+                let genesis_config = match (genesis_config_name, installed_genesis_config) {
+                    (Some(config_name_or_path), _) => {
+                        match BuiltinNetworkID::from_str(config_name_or_path.as_str()) {
+                            Ok(net) => net.genesis_config().clone(),
+                            Err(_) => {
+                                let path = Path::new(config_name_or_path.as_str());
+                                GenesisConfig::load(path)?
+                            }
+                        }
                     }
+                    (None, Some(installed_genesis_config)) => installed_genesis_config,
+                    (None, None) => bail!("Can not load genesis config from {:?}, please set `genesis-config` cli option, or install the network descriptor.", config_path),
                 };
                 genesis_config.save(config_path.as_path())?;
                 genesis_config
@@ -394,6 +461,7 @@ impl BaseConfig {
             config
         };
         info!("Final config: {}", config);
+        config.ensure_valid()?;
         Ok(config)
     }
 }
@@ -430,6 +498,10 @@ pub struct NodeConfig {
     pub logger: LoggerConfig,
     #[serde(default)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    pub soak_check: SoakCheckConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
 }
 
 impl std::fmt::Display for NodeConfig {
@@ -497,7 +569,9 @@ impl NodeConfig {
         self.vault.merge_with_opt(opt, base.clone())?;
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
-        self.stratum.merge_with_opt(opt, base)?;
+        self.stratum.merge_with_opt(opt, base.clone())?;
+        self.soak_check.merge_with_opt(opt, base.clone())?;
+        self.maintenance.merge_with_opt(opt, base)?;
         Ok(())
     }
 }
@@ -4,6 +4,8 @@
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use starcoin_logger::LogSubsystem;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use structopt::StructOpt;
@@ -50,11 +52,31 @@ pub struct LoggerConfig {
     #[structopt(name = "slog-chan-size", long)]
     pub slog_chan_size: Option<usize>,
 
+    /// Write the log of the subsystem to a separate file `{subsystem}.log`, with independent rotation policy.
+    /// Only can be set by config file, for example:
+    /// [logger.subsystems.miner]
+    /// max_backup = 2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(skip)]
+    pub subsystems: Option<BTreeMap<LogSubsystem, SubsystemLoggerConfig>>,
+
     #[structopt(skip)]
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
 }
 
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubsystemLoggerConfig {
+    /// Max file size of the subsystem log file, default is same as the main log file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<u64>,
+
+    /// Max backup of the subsystem log file, default is same as the main log file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_backup: Option<u32>,
+}
+
 impl LoggerConfig {
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
@@ -119,6 +141,30 @@ impl LoggerConfig {
             }
         })
     }
+    /// Return the subsystems which write log to separate files, with the log path, max file size and max backup.
+    pub fn get_subsystem_log_files(&self) -> Vec<(LogSubsystem, PathBuf, u64, u32)> {
+        if self.disable_file() {
+            return vec![];
+        }
+        self.subsystems
+            .as_ref()
+            .map(|subsystems| {
+                subsystems
+                    .iter()
+                    .map(|(subsystem, config)| {
+                        (
+                            *subsystem,
+                            self.base()
+                                .data_dir
+                                .join(format!("{}.log", subsystem.name())),
+                            config.max_file_size.unwrap_or_else(|| self.max_file_size()),
+                            config.max_backup.unwrap_or_else(|| self.max_backup()),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl ConfigModule for LoggerConfig {
@@ -0,0 +1,233 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{bail, format_err, Result};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+pub const DEFAULT_MAINTENANCE_BACKUP_DIR: &str = "backup";
+pub const DEFAULT_MAINTENANCE_BACKUP_KEEP: u32 = 3;
+pub const DEFAULT_MAINTENANCE_RETENTION_DAYS: u64 = 7;
+pub const DEFAULT_MAINTENANCE_MAX_DEFER: u64 = 3600;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+
+/// The UTC time of a maintenance task, `HH:MM` for daily, or `<weekday> HH:MM` for weekly.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaintenanceSchedule {
+    /// The day of the week, 0 is Sunday, none for every day.
+    pub weekday: Option<u8>,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl MaintenanceSchedule {
+    /// The first scheduled time after `now`, both in unix seconds.
+    pub fn next_after(&self, now: u64) -> u64 {
+        let today = now / SECONDS_PER_DAY;
+        let offset = u64::from(self.hour) * 3600 + u64::from(self.minute) * 60;
+        (0..=7u64)
+            .map(|days| (today + days) * SECONDS_PER_DAY + offset)
+            .find(|time| {
+                // 1970-01-01 is a Thursday.
+                let weekday = ((time / SECONDS_PER_DAY + 4) % 7) as u8;
+                *time > now && self.weekday.map(|day| day == weekday).unwrap_or(true)
+            })
+            .expect("A scheduled time must exist in a week.")
+    }
+}
+
+impl fmt::Display for MaintenanceSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(weekday) = self.weekday {
+            write!(f, "{} ", WEEKDAYS[weekday as usize])?;
+        }
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl FromStr for MaintenanceSchedule {
+    type Err = anyhow::Error;
+
+    /// Parse the schedule from `HH:MM` or `<weekday> HH:MM`, the weekday is like `sun` or `sunday`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let (weekday, time) = match parts.as_slice() {
+            [time] => (None, *time),
+            [weekday, time] => {
+                let weekday = weekday.to_lowercase();
+                let index = WEEKDAYS
+                    .iter()
+                    .zip(WEEKDAY_NAMES.iter())
+                    .position(|(day, name)| weekday == *day || weekday == *name)
+                    .ok_or_else(|| format_err!("Invalid weekday {} of schedule {}", weekday, s))?;
+                (Some(index as u8), *time)
+            }
+            _ => bail!(
+                "Invalid maintenance schedule {}, expect HH:MM or <weekday> HH:MM",
+                s
+            ),
+        };
+        let mut hm = time.splitn(2, ':');
+        let (hour, minute) = match (hm.next(), hm.next()) {
+            (Some(hour), Some(minute)) => (hour.parse::<u8>()?, minute.parse::<u8>()?),
+            _ => bail!("Invalid time {} of schedule {}, expect HH:MM", time, s),
+        };
+        if hour >= 24 || minute >= 60 {
+            bail!("Invalid time {} of schedule {}", time, s);
+        }
+        Ok(Self {
+            weekday,
+            hour,
+            minute,
+        })
+    }
+}
+
+impl Serialize for MaintenanceSchedule {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MaintenanceSchedule {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse::<MaintenanceSchedule>().map_err(D::Error::custom)
+    }
+}
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct MaintenanceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-compaction", long)]
+    /// the UTC schedule of the storage compaction, as `HH:MM` or `<weekday> HH:MM`. default to disable.
+    pub compaction: Option<MaintenanceSchedule>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-backup", long)]
+    /// the UTC schedule of the storage backup, as `HH:MM` or `<weekday> HH:MM`. default to disable.
+    pub backup: Option<MaintenanceSchedule>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-backup-dir", long, parse(from_os_str))]
+    /// the dir of the storage backups, relative to the data dir. default to backup.
+    pub backup_dir: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-backup-keep", long)]
+    /// the count of the newest storage backups to keep. default to 3.
+    pub backup_keep: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-cleanup", long)]
+    /// the UTC schedule of the retention cleanup of the node diagnostics files, as `HH:MM` or `<weekday> HH:MM`. default to disable.
+    pub cleanup: Option<MaintenanceSchedule>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-retention-days", long)]
+    /// the diagnostics files older than the days are removed by the cleanup. default to 7.
+    pub retention_days: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-scrub", long)]
+    /// the UTC schedule of the storage scrub, which verifies the stored records and quarantines the corrupted ones, as `HH:MM` or `<weekday> HH:MM`. default to disable.
+    pub scrub: Option<MaintenanceSchedule>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "maintenance-max-defer", long)]
+    /// max time(s) a due task is deferred by the sync or mining, the run is skipped after it. default to 3600.
+    pub max_defer: Option<u64>,
+
+    #[serde(skip)]
+    #[structopt(skip)]
+    base: Option<Arc<BaseConfig>>,
+}
+
+impl MaintenanceConfig {
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
+
+    pub fn is_enable(&self) -> bool {
+        self.compaction.is_some()
+            || self.backup.is_some()
+            || self.cleanup.is_some()
+            || self.scrub.is_some()
+    }
+
+    pub fn backup_dir(&self) -> PathBuf {
+        let dir = self
+            .backup_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_MAINTENANCE_BACKUP_DIR));
+        self.base().data_dir().join(dir)
+    }
+
+    pub fn backup_keep(&self) -> u32 {
+        self.backup_keep.unwrap_or(DEFAULT_MAINTENANCE_BACKUP_KEEP)
+    }
+
+    pub fn retention_days(&self) -> u64 {
+        self.retention_days
+            .unwrap_or(DEFAULT_MAINTENANCE_RETENTION_DAYS)
+    }
+
+    pub fn max_defer(&self) -> u64 {
+        self.max_defer.unwrap_or(DEFAULT_MAINTENANCE_MAX_DEFER)
+    }
+}
+
+impl ConfigModule for MaintenanceConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
+        if opt.maintenance.compaction.is_some() {
+            self.compaction = opt.maintenance.compaction;
+        }
+        if opt.maintenance.backup.is_some() {
+            self.backup = opt.maintenance.backup;
+        }
+        if opt.maintenance.backup_dir.is_some() {
+            self.backup_dir = opt.maintenance.backup_dir.clone();
+        }
+        if opt.maintenance.backup_keep.is_some() {
+            self.backup_keep = opt.maintenance.backup_keep;
+        }
+        if opt.maintenance.cleanup.is_some() {
+            self.cleanup = opt.maintenance.cleanup;
+        }
+        if opt.maintenance.retention_days.is_some() {
+            self.retention_days = opt.maintenance.retention_days;
+        }
+        if opt.maintenance.scrub.is_some() {
+            self.scrub = opt.maintenance.scrub;
+        }
+        if opt.maintenance.max_defer.is_some() {
+            self.max_defer = opt.maintenance.max_defer;
+        }
+        Ok(())
+    }
+}
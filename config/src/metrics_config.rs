@@ -7,12 +7,15 @@ use crate::{
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 use structopt::StructOpt;
 
 pub static DEFAULT_METRIC_SERVER_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 pub static DEFAULT_METRIC_SERVER_PORT: u16 = 9101;
 pub static DEFAULT_METRIC_PUSH_AUTH_PASSWORD: &str = "";
+/// The file of the local usage stats snapshots, under the data dir.
+pub const USAGE_STATS_FILE_NAME: &str = "usage_stats.jsonl";
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -75,6 +78,16 @@ pub struct MetricsConfig {
     /// Metrics server port, default is 9101
     pub port: Option<u16>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "usage-stats-interval", long)]
+    /// the interval in seconds to record the local usage stats for the `node report`, 0 to disable, default to 300.
+    pub usage_stats_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "usage-stats-retention-days", long)]
+    /// how many days the local usage stats are kept, default to 7.
+    pub usage_stats_retention_days: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -96,6 +109,18 @@ impl MetricsConfig {
         self.metrics_address
     }
 
+    pub fn usage_stats_interval(&self) -> u64 {
+        self.usage_stats_interval.unwrap_or(300)
+    }
+
+    pub fn usage_stats_retention_days(&self) -> u64 {
+        self.usage_stats_retention_days.unwrap_or(7)
+    }
+
+    pub fn usage_stats_file(&self) -> PathBuf {
+        self.base().data_dir().join(USAGE_STATS_FILE_NAME)
+    }
+
     fn generate_address(&mut self) {
         if !self.disable_metrics() {
             self.metrics_address = Some(SocketAddr::new(
@@ -128,6 +153,12 @@ impl ConfigModule for MetricsConfig {
         if opt.metrics.port.is_some() {
             self.port = opt.metrics.port;
         }
+        if opt.metrics.usage_stats_interval.is_some() {
+            self.usage_stats_interval = opt.metrics.usage_stats_interval;
+        }
+        if opt.metrics.usage_stats_retention_days.is_some() {
+            self.usage_stats_retention_days = opt.metrics.usage_stats_retention_days;
+        }
         if opt.metrics.push_config.is_config() {
             self.push_config = opt.metrics.push_config.clone();
         }
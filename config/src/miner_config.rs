@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, StructOpt)]
@@ -31,6 +33,34 @@ pub struct MinerConfig {
     /// Miner client thread number, not work for dev network, default is 1
     pub miner_thread: Option<u16>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "miner-extranonce-size")]
+    /// The byte size of extranonce region at the end of block header extra, rolled by pool workers for `mining.get_work`, max is 4, default is 0.
+    pub extranonce_size: Option<u8>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "miner-template-refresh-interval")]
+    /// Rebuild the block template every the seconds to include the new txns in pool, the new mining job supersedes the current one only if it adds enough fee, see `template_min_fee_delta`.
+    /// Default is 0, no periodic refresh.
+    pub template_refresh_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "miner-template-min-fee-delta")]
+    /// The min fee(gas_used * gas_unit_price) a refreshed block template should add to supersede the current mining job, default is 0.
+    pub template_min_fee_delta: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "miner-refresh-template-on-new-head")]
+    /// Supersede the current mining job immediately when the chain head changes, default is true.
+    /// If false, the current job is kept until it is mined or superseded by the periodic refresh, the mined block may become an uncle.
+    pub refresh_template_on_new_head: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "miner-reward-address")]
+    /// The author of the mined blocks, which receives the block rewards, the account should exist on chain.
+    /// Default is the default account of the node wallet, it is auto created with an empty password if absent.
+    pub reward_address: Option<AccountAddress>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -49,13 +79,32 @@ impl MinerConfig {
         self.disable_mint_empty_block
             .unwrap_or_else(|| self.base().net().is_dev())
     }
+    pub fn extranonce_size(&self) -> u8 {
+        self.extranonce_size.unwrap_or(0)
+    }
+    /// The periodic block template refresh interval, None if the refresh is disabled.
+    pub fn template_refresh_interval(&self) -> Option<Duration> {
+        self.template_refresh_interval
+            .filter(|interval| *interval > 0)
+            .map(Duration::from_secs)
+    }
+    pub fn template_min_fee_delta(&self) -> u64 {
+        self.template_min_fee_delta.unwrap_or(0)
+    }
+    pub fn refresh_template_on_new_head(&self) -> bool {
+        self.refresh_template_on_new_head.unwrap_or(true)
+    }
+    pub fn reward_address(&self) -> Option<AccountAddress> {
+        self.reward_address
+    }
     pub fn miner_client_config(&self) -> Option<MinerClientConfig> {
         if self.disable_miner_client() {
             return None;
         }
         Some(MinerClientConfig {
-            server: None,
+            servers: vec![],
             plugin_path: None,
+            solver_command: None,
             miner_thread: self.miner_thread.unwrap_or(1),
             enable_stderr: true,
         })
@@ -64,8 +113,12 @@ impl MinerConfig {
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MinerClientConfig {
-    pub server: Option<String>,
+    /// The nodes to connect, the miner client fails over to the next node if the connection is lost or a seal submission fails.
+    pub servers: Vec<String>,
+    /// The dynamic library of the solver plugin, such as a gpu solver.
     pub plugin_path: Option<String>,
+    /// The command of the external solver process.
+    pub solver_command: Option<String>,
     pub miner_thread: u16,
     pub enable_stderr: bool,
 }
@@ -77,8 +130,9 @@ impl MinerClientConfig {
 impl Default for MinerClientConfig {
     fn default() -> Self {
         Self {
-            server: None,
+            servers: vec![],
             plugin_path: None,
+            solver_command: None,
             miner_thread: 1,
             enable_stderr: false,
         }
@@ -99,6 +153,26 @@ impl ConfigModule for MinerConfig {
         if opt.miner.block_gas_limit.is_some() {
             self.block_gas_limit = opt.miner.block_gas_limit;
         }
+        if opt.miner.extranonce_size.is_some() {
+            self.extranonce_size = opt.miner.extranonce_size;
+        }
+        if opt.miner.template_refresh_interval.is_some() {
+            self.template_refresh_interval = opt.miner.template_refresh_interval;
+        }
+        if opt.miner.template_min_fee_delta.is_some() {
+            self.template_min_fee_delta = opt.miner.template_min_fee_delta;
+        }
+        if opt.miner.refresh_template_on_new_head.is_some() {
+            self.refresh_template_on_new_head = opt.miner.refresh_template_on_new_head;
+        }
+        if opt.miner.reward_address.is_some() {
+            self.reward_address = opt.miner.reward_address;
+        }
+        ensure!(
+            self.extranonce_size() <= 4,
+            "miner extranonce size should not be greater than 4, got: {}",
+            self.extranonce_size()
+        );
 
         Ok(())
     }
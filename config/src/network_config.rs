@@ -6,8 +6,10 @@ use crate::{
     get_available_port_from, get_random_available_port, parse_key_val, ApiQuotaConfig, BaseConfig,
     ConfigModule, QuotaDuration, StarcoinOpt,
 };
-use anyhow::Result;
-use network_api::messages::{NotificationMessage, BLOCK_PROTOCOL_NAME};
+use anyhow::{ensure, Result};
+use network_api::messages::{
+    GossipTopic, IdentityLinkageMessage, NotificationMessage, BLOCK_PROTOCOL_NAME,
+};
 use network_p2p_types::{
     is_memory_addr, memory_addr,
     multiaddr::{Multiaddr, Protocol},
@@ -22,6 +24,7 @@ use starcoin_logger::prelude::*;
 use starcoin_types::peer_info::PeerId;
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::fs;
 use std::net::Ipv4Addr;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
@@ -31,6 +34,8 @@ use structopt::StructOpt;
 
 pub static DEFAULT_NETWORK_PORT: u16 = 9840;
 static NETWORK_KEY_FILE: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("network_key"));
+static PEER_CONTROL_FILE: &str = "network_peers.json";
+static IDENTITY_LINKAGE_FILE: &str = "network_identity_linkage.json";
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct NetworkRpcQuotaConfiguration {
@@ -242,6 +247,12 @@ pub struct NetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(name = "unsupported-protocols", long, use_delimiter = true)]
     pub unsupported_protocols: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "gossip-topics", long, use_delimiter = true)]
+    /// the gossip topics subscribed by this node: blocks, transactions, consensus_hints, default to all topics.
+    /// The blocks topic is always subscribed, a header-only node can subscribe blocks only to opt out of the transaction gossip.
+    pub gossip_topics: Option<Vec<GossipTopic>>,
 }
 
 impl NetworkConfig {
@@ -260,6 +271,9 @@ impl NetworkConfig {
         let mut seeds: HashSet<MultiaddrWithPeerId> =
             self.seeds.clone().into_vec().into_iter().collect();
         seeds.extend(self.base().net().boot_nodes().iter().cloned());
+        if let Some(descriptor) = self.base().network_descriptor.as_ref() {
+            seeds.extend(descriptor.boot_nodes.iter().cloned());
+        }
 
         let self_peer_id = self.self_peer_id();
         seeds.retain(|node| {
@@ -335,6 +349,60 @@ impl NetworkConfig {
         }
     }
 
+    /// The file to persist banned and trusted peers, under the data dir.
+    pub fn peer_control_file(&self) -> PathBuf {
+        self.base().data_dir().join(PEER_CONTROL_FILE)
+    }
+
+    /// The file to persist the identity linkage of the last network key rotation, under the data dir.
+    pub fn identity_linkage_file(&self) -> PathBuf {
+        self.base().data_dir().join(IDENTITY_LINKAGE_FILE)
+    }
+
+    /// The identity linkage of the last network key rotation.
+    pub fn load_identity_linkage(&self) -> Result<Option<IdentityLinkageMessage>> {
+        let path = self.identity_linkage_file();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Rotate the network key, the new key is saved to the node key file and takes effect after the node restarts,
+    /// the old key file is kept with the `old` extension.
+    /// Return the identity linkage signed by the old key, it is persisted to the identity linkage file.
+    pub fn rotate_network_key(&self, timestamp: u64) -> Result<IdentityLinkageMessage> {
+        ensure!(
+            self.node_key.is_none(),
+            "The node key is set by the --node-key option, can not rotate it"
+        );
+        let (old_private_key, old_public_key) = self.network_keypair();
+        if let Some(linkage) = self.load_identity_linkage()? {
+            ensure!(
+                &linkage.linkage.old_public_key != old_public_key,
+                "The network key is already rotated to {}, restart the node to use it",
+                linkage.new_peer_id()
+            );
+        }
+        let path = self.node_key_file();
+        if path.exists() {
+            fs::rename(&path, path.with_extension("old"))?;
+        }
+        let (private_key, public_key) = gen_keypair();
+        save_key(&private_key.to_bytes(), &path)?;
+        let linkage = IdentityLinkageMessage::new(old_private_key, public_key, timestamp);
+        fs::write(
+            self.identity_linkage_file(),
+            serde_json::to_vec_pretty(&linkage)?,
+        )?;
+        info!(
+            "Rotate network identity from {} to {}",
+            linkage.old_peer_id(),
+            linkage.new_peer_id()
+        );
+        Ok(linkage)
+    }
+
     /// node key loader step:
     /// 1. if node_key is Some, directly decode the key.
     /// 2. try load node key from node_key_file
@@ -382,8 +450,25 @@ impl NetworkConfig {
         }
     }
 
+    pub fn gossip_topics(&self) -> Vec<GossipTopic> {
+        match &self.gossip_topics {
+            Some(topics) => GossipTopic::all()
+                .into_iter()
+                .filter(|topic| *topic == GossipTopic::Blocks || topics.contains(topic))
+                .collect(),
+            None => GossipTopic::all(),
+        }
+    }
+
     pub fn supported_network_protocols(&self) -> Vec<Cow<'static, str>> {
-        let protocols = NotificationMessage::protocols();
+        let topics = self.gossip_topics();
+        let protocols: Vec<Cow<'static, str>> = NotificationMessage::protocols()
+            .into_iter()
+            .filter(|protocol| match GossipTopic::of_protocol(protocol) {
+                Some(topic) => topics.contains(&topic),
+                None => true,
+            })
+            .collect();
         if let Some(unsupported_protocols) = &self.unsupported_protocols {
             return protocols
                 .into_iter()
@@ -464,6 +549,10 @@ impl ConfigModule for NetworkConfig {
             );
         }
 
+        if opt.network.gossip_topics.is_some() {
+            self.gossip_topics = opt.network.gossip_topics.clone();
+        }
+
         self.load_or_generate_keypair()?;
         self.generate_listen_address();
         Ok(())
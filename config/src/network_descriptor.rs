@@ -0,0 +1,153 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BuiltinNetworkID, ChainNetworkID, GenesisConfig};
+use anyhow::{ensure, Result};
+use network_p2p_types::MultiaddrWithPeerId;
+use serde::{Deserialize, Serialize};
+use starcoin_vm_types::genesis_config::ChainId;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The dir of the installed network descriptors, under the base data dir.
+pub const NETWORK_DESCRIPTOR_DIR: &str = "networks";
+
+/// The description of a network, with everything required to join it.
+/// The builtin networks have embedded descriptors, and the descriptors of the third-party
+/// networks, such as the community testnets, can be installed from a file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NetworkDescriptor {
+    pub name: String,
+    pub chain_id: ChainId,
+    #[serde(default)]
+    pub boot_nodes: Vec<MultiaddrWithPeerId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// The genesis config of a third-party network, it is used if the `genesis-config` option is absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_config: Option<GenesisConfig>,
+}
+
+impl NetworkDescriptor {
+    pub fn builtin(net: BuiltinNetworkID) -> Self {
+        Self {
+            name: net.chain_name(),
+            chain_id: net.chain_id(),
+            boot_nodes: net.boot_nodes().to_vec(),
+            faucet_url: net.faucet_url(),
+            explorer_url: net.explorer_url(),
+            genesis_config: None,
+        }
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        BuiltinNetworkID::networks()
+            .into_iter()
+            .any(|net| net.chain_name() == self.name && net.chain_id() == self.chain_id)
+    }
+
+    /// The network id of the descriptor, as the `net` option.
+    pub fn network_id(&self) -> Result<ChainNetworkID> {
+        match BuiltinNetworkID::networks()
+            .into_iter()
+            .find(|net| net.chain_name() == self.name)
+        {
+            Some(net) => Ok(ChainNetworkID::Builtin(net)),
+            None => ChainNetworkID::new_custom(self.name.clone(), self.chain_id),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let descriptor: Self = serde_json::from_slice(&fs::read(path)?)?;
+        descriptor.validate()?;
+        Ok(descriptor)
+    }
+
+    /// Check a third-party descriptor does not conflict with the builtin networks.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.name.is_empty()
+                && self
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "Invalid network name {:?}, only alphanumeric, '-' and '_' are allowed",
+            self.name
+        );
+        ChainNetworkID::new_custom(self.name.clone(), self.chain_id)?;
+        Ok(())
+    }
+
+    fn installed_path(base_data_dir: &Path, name: &str) -> PathBuf {
+        base_data_dir
+            .join(NETWORK_DESCRIPTOR_DIR)
+            .join(format!("{}.json", name))
+    }
+
+    /// Install the third-party descriptor file to the base data dir, the installed descriptor of the same name is replaced.
+    pub fn install(base_data_dir: &Path, path: &Path) -> Result<Self> {
+        let descriptor = Self::load(path)?;
+        for installed in Self::installed(base_data_dir)? {
+            ensure!(
+                installed.name == descriptor.name || installed.chain_id != descriptor.chain_id,
+                "Chain id {} has used for installed network {}",
+                descriptor.chain_id,
+                installed.name
+            );
+        }
+        let installed_path = Self::installed_path(base_data_dir, descriptor.name.as_str());
+        fs::create_dir_all(base_data_dir.join(NETWORK_DESCRIPTOR_DIR))?;
+        fs::write(installed_path, serde_json::to_vec_pretty(&descriptor)?)?;
+        Ok(descriptor)
+    }
+
+    /// The third-party descriptors installed in the base data dir.
+    pub fn installed(base_data_dir: &Path) -> Result<Vec<Self>> {
+        let dir = base_data_dir.join(NETWORK_DESCRIPTOR_DIR);
+        if !dir.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut descriptors = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+                descriptors.push(Self::load(path.as_path())?);
+            }
+        }
+        descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(descriptors)
+    }
+
+    /// The installed descriptor of the custom network.
+    pub fn find_installed(base_data_dir: &Path, net: &ChainNetworkID) -> Result<Option<Self>> {
+        let net = match net.as_custom() {
+            Some(net) => net,
+            None => return Ok(None),
+        };
+        let path = Self::installed_path(base_data_dir, net.chain_name());
+        if !path.exists() {
+            return Ok(None);
+        }
+        let descriptor = Self::load(path.as_path())?;
+        ensure!(
+            descriptor.chain_id == net.chain_id(),
+            "The chain id of network {} is {}, but the installed network is {}",
+            net.chain_name(),
+            net.chain_id(),
+            descriptor.chain_id
+        );
+        Ok(Some(descriptor))
+    }
+
+    /// The builtin descriptors and the installed descriptors.
+    pub fn list(base_data_dir: &Path) -> Result<Vec<Self>> {
+        let mut descriptors: Vec<Self> = BuiltinNetworkID::networks()
+            .into_iter()
+            .map(Self::builtin)
+            .collect();
+        descriptors.extend(Self::installed(base_data_dir)?);
+        Ok(descriptors)
+    }
+}
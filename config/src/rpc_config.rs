@@ -25,6 +25,7 @@ const DEFAULT_WEB_SOCKET_PORT: u16 = 9870;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_RPC_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 const DEFAULT_BLOCK_QUERY_MAX_RANGE: u64 = 32;
+const DEFAULT_LOG_TAIL_MAX_LINES: usize = 1000;
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct HttpConfiguration {
@@ -367,10 +368,32 @@ pub struct RpcConfig {
     /// Rpc address, default is 0.0.0.0
     pub rpc_address: Option<IpAddr>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "rpc-allow-public-admin-apis")]
+    /// Allow the admin apis on a non-loopback rpc address, such as a node behind a firewall.
+    /// The exposure is reported as a warning instead of refusing to start. Default is false.
+    pub allow_public_admin_apis: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "event-query-max-block-range")]
     pub block_query_max_range: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "dev-call-time-limit")]
+    /// Max wall-clock time in milliseconds of contract dry run and call, independent of gas.
+    /// It is enforced at the state accesses of the execution.
+    pub dev_call_time_limit: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "dev-call-state-read-limit")]
+    /// Max bytes of state read by contract dry run and call, independent of gas.
+    pub dev_call_state_read_limit: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "log-tail-max-lines")]
+    /// Max lines of the log file read by `debug.log_tail`, default is 1000.
+    pub log_tail_max_lines: Option<usize>,
+
     #[serde(skip)]
     #[structopt(skip)]
     http_address: Option<ListenAddress>,
@@ -429,6 +452,10 @@ impl RpcConfig {
         self.rpc_address.clone().unwrap_or(DEFAULT_RPC_ADDRESS)
     }
 
+    pub fn allow_public_admin_apis(&self) -> bool {
+        self.allow_public_admin_apis.unwrap_or(false)
+    }
+
     pub fn get_ipc_file(&self) -> PathBuf {
         let base = self.base();
         Self::get_ipc_file_by_base(base)
@@ -451,6 +478,11 @@ impl RpcConfig {
             .unwrap_or(DEFAULT_BLOCK_QUERY_MAX_RANGE)
     }
 
+    pub fn log_tail_max_lines(&self) -> usize {
+        self.log_tail_max_lines
+            .unwrap_or(DEFAULT_LOG_TAIL_MAX_LINES)
+    }
+
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
     }
@@ -520,9 +552,21 @@ impl ConfigModule for RpcConfig {
         if opt.rpc.rpc_address.is_some() {
             self.rpc_address = opt.rpc.rpc_address;
         }
+        if opt.rpc.allow_public_admin_apis.is_some() {
+            self.allow_public_admin_apis = opt.rpc.allow_public_admin_apis;
+        }
         if opt.rpc.block_query_max_range.is_some() {
             self.block_query_max_range = opt.rpc.block_query_max_range;
         }
+        if opt.rpc.dev_call_time_limit.is_some() {
+            self.dev_call_time_limit = opt.rpc.dev_call_time_limit;
+        }
+        if opt.rpc.dev_call_state_read_limit.is_some() {
+            self.dev_call_state_read_limit = opt.rpc.dev_call_state_read_limit;
+        }
+        if opt.rpc.log_tail_max_lines.is_some() {
+            self.log_tail_max_lines = opt.rpc.log_tail_max_lines;
+        }
         self.http.merge(&opt.rpc.http)?;
         self.tcp.merge(&opt.rpc.tcp)?;
         self.ws.merge(&opt.rpc.ws)?;
@@ -0,0 +1,63 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use structopt::StructOpt;
+
+pub const DEFAULT_SOAK_CHECK_INTERVAL: u64 = 10;
+pub const DEFAULT_SOAK_CHECK_STUCK_TIMEOUT: u64 = 600;
+
+#[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct SoakCheckConfig {
+    #[serde(skip)]
+    #[structopt(name = "soak-check", long)]
+    /// Continuously run the invariant checks of the node, and dump the diagnostics on the first violation.
+    /// Only for test networks, this option is skip for config file, only support cli option.
+    pub enable: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "soak-check-interval", long)]
+    /// interval(s) of the soak check. default to 10.
+    pub interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "soak-check-stuck-timeout", long)]
+    /// the block is stuck if the head is not changed in the timeout(s) while there are pending txns in txpool. default to 600.
+    pub stuck_timeout: Option<u64>,
+}
+
+impl SoakCheckConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable
+    }
+    pub fn interval(&self) -> u64 {
+        self.interval.unwrap_or(DEFAULT_SOAK_CHECK_INTERVAL)
+    }
+    pub fn stuck_timeout(&self) -> u64 {
+        self.stuck_timeout
+            .unwrap_or(DEFAULT_SOAK_CHECK_STUCK_TIMEOUT)
+    }
+}
+
+impl ConfigModule for SoakCheckConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        if opt.soak_check.enable {
+            self.enable = true;
+        }
+        if opt.soak_check.interval.is_some() {
+            self.interval = opt.soak_check.interval;
+        }
+        if opt.soak_check.stuck_timeout.is_some() {
+            self.stuck_timeout = opt.soak_check.stuck_timeout;
+        }
+        ensure!(
+            !self.enable || !base.net().is_main(),
+            "The soak check is only for test networks."
+        );
+        Ok(())
+    }
+}
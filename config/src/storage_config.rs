@@ -77,6 +77,11 @@ pub struct StorageConfig {
     #[structopt(name = "cache-sizes", long, help = "cache sizes")]
     pub cache_size: Option<usize>,
 
+    /// compress the bodies and the transaction infos of the main chain blocks deeper than this number of blocks, default to 0, no compression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "block-compression-depth", long)]
+    pub block_compression_depth: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -103,6 +108,10 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+    /// The depth of the blocks to compress, None means the compression is disabled.
+    pub fn block_compression_depth(&self) -> Option<u64> {
+        self.block_compression_depth.filter(|depth| *depth > 0)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -117,6 +126,9 @@ impl ConfigModule for StorageConfig {
         if opt.storage.cache_size.is_some() {
             self.cache_size = opt.storage.cache_size;
         }
+        if opt.storage.block_compression_depth.is_some() {
+            self.block_compression_depth = opt.storage.block_compression_depth;
+        }
         Ok(())
     }
 }
@@ -2,12 +2,54 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{BlockIdAndNumber, BlockNumber};
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
+/// A trusted block of the main chain, the chain synced from peers must contain it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SyncCheckpoint {
+    pub number: BlockNumber,
+    pub block_id: HashValue,
+}
+
+impl From<SyncCheckpoint> for BlockIdAndNumber {
+    fn from(checkpoint: SyncCheckpoint) -> Self {
+        BlockIdAndNumber::new(checkpoint.block_id, checkpoint.number)
+    }
+}
+
+impl fmt::Display for SyncCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.number, self.block_id)
+    }
+}
+
+impl FromStr for SyncCheckpoint {
+    type Err = anyhow::Error;
+
+    /// Parse the checkpoint from `<number>:<block_id>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(number), Some(block_id)) => Ok(Self {
+                number: number.trim().parse()?,
+                block_id: HashValue::from_hex(block_id.trim().trim_start_matches("0x"))?,
+            }),
+            _ => Err(format_err!(
+                "Invalid sync checkpoint {}, expect <number>:<block_id>",
+                s
+            )),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct SyncConfig {
@@ -28,6 +70,48 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// how many threads to execute the block transactions in parallel when sync, 1 means execute serially.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "sync-execution-concurrency",
+        long,
+        help = "threads to execute block transactions in parallel when sync, default 1, execute serially."
+    )]
+    execution_concurrency: Option<usize>,
+
+    /// prefetch the state read by the block in the background when executing it, it speeds up the sync on the disk with high read latency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "sync-state-prefetch",
+        long,
+        help = "prefetch the state of the block senders and receivers when executing the block, default false."
+    )]
+    state_prefetch: Option<bool>,
+
+    /// trusted blocks of the main chain, the block ids synced from peers are anchored to them.
+    /// The headers are fetched backward from the last checkpoint before the sync target, and
+    /// only the blocks linked to it by `parent_hash` skip the pow verify.
+    /// The blocks are still fully downloaded and executed: headers-first sync, state snapshot
+    /// fetch and lazy body backfill are not supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "sync-checkpoint",
+        long,
+        help = "trusted block of the main chain as <number>:<block_id>, can be repeated.",
+        number_of_values = 1
+    )]
+    checkpoints: Option<Vec<SyncCheckpoint>>,
+
+    /// the max count of the main chain blocks a reorg can retract automatically, a deeper reorg is halted
+    /// until the operator accepts it by `node admin accept-reorg`. 0 means no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "sync-max-reorg-depth",
+        long,
+        help = "refuse to reorg deeper than this number of blocks automatically, default 0, no limit."
+    )]
+    max_reorg_depth: Option<u64>,
 }
 
 impl SyncConfig {
@@ -38,6 +122,30 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn execution_concurrency(&self) -> usize {
+        self.execution_concurrency.unwrap_or(1)
+    }
+
+    pub fn state_prefetch(&self) -> bool {
+        self.state_prefetch.unwrap_or(false)
+    }
+
+    /// The max depth of the automatic reorg, None means no limit.
+    pub fn max_reorg_depth(&self) -> Option<u64> {
+        self.max_reorg_depth.filter(|depth| *depth > 0)
+    }
+
+    pub fn set_max_reorg_depth(&mut self, max_reorg_depth: u64) {
+        self.max_reorg_depth = Some(max_reorg_depth);
+    }
+
+    /// The checkpoints sorted by block number.
+    pub fn checkpoints(&self) -> Vec<SyncCheckpoint> {
+        let mut checkpoints = self.checkpoints.clone().unwrap_or_default();
+        checkpoints.sort_by_key(|checkpoint| checkpoint.number);
+        checkpoints
+    }
 }
 
 impl ConfigModule for SyncConfig {
@@ -50,6 +158,22 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.execution_concurrency.is_some() {
+            self.execution_concurrency = opt.sync.execution_concurrency;
+        }
+
+        if opt.sync.state_prefetch.is_some() {
+            self.state_prefetch = opt.sync.state_prefetch;
+        }
+
+        if opt.sync.checkpoints.is_some() {
+            self.checkpoints = opt.sync.checkpoints.clone();
+        }
+
+        if opt.sync.max_reorg_depth.is_some() {
+            self.max_reorg_depth = opt.sync.max_reorg_depth;
+        }
+
         Ok(())
     }
 }
@@ -3,7 +3,14 @@
 
 use super::*;
 use crate::helper::to_toml;
+use network_api::messages::{
+    GossipTopic, BLOCK_PROTOCOL_NAME, CONSENSUS_HINT_PROTOCOL_NAME, IDENTITY_LINKAGE_PROTOCOL_NAME,
+};
+use network_p2p_types::MultiaddrWithPeerId;
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_vm_types::gas_schedule::GasAlgebra;
+use starcoin_vm_types::genesis_config::ChainId;
 
 #[test]
 fn test_generate_and_load() -> Result<()> {
@@ -64,6 +71,103 @@ fn test_api_quota_config() {
     assert_eq!("1000/s", config.to_string().as_str());
 }
 
+#[test]
+fn test_sync_checkpoint() {
+    let block_id = HashValue::random();
+    let checkpoint = format!("100:{}", block_id)
+        .parse::<SyncCheckpoint>()
+        .unwrap();
+    assert_eq!(checkpoint.number, 100);
+    assert_eq!(checkpoint.block_id, block_id);
+    assert_eq!(
+        checkpoint,
+        checkpoint.to_string().parse::<SyncCheckpoint>().unwrap()
+    );
+    assert!("100".parse::<SyncCheckpoint>().is_err());
+}
+
+#[test]
+fn test_maintenance_schedule() {
+    let daily = "03:00".parse::<MaintenanceSchedule>().unwrap();
+    assert_eq!(daily.weekday, None);
+    assert_eq!(daily, daily.to_string().parse().unwrap());
+    let weekly = "Sunday 04:30".parse::<MaintenanceSchedule>().unwrap();
+    assert_eq!(weekly.weekday, Some(0));
+    assert_eq!("sun 04:30", weekly.to_string().as_str());
+    assert!("24:00".parse::<MaintenanceSchedule>().is_err());
+    assert!("someday 03:00".parse::<MaintenanceSchedule>().is_err());
+
+    // 1970-01-01 00:00 is a Thursday.
+    assert_eq!(daily.next_after(0), 3 * 3600);
+    assert_eq!(daily.next_after(3 * 3600), 86400 + 3 * 3600);
+    assert_eq!(weekly.next_after(0), 3 * 86400 + 4 * 3600 + 30 * 60);
+    assert_eq!(
+        weekly.next_after(3 * 86400 + 4 * 3600 + 30 * 60),
+        10 * 86400 + 4 * 3600 + 30 * 60
+    );
+}
+
+#[test]
+fn test_gossip_topics() -> Result<()> {
+    let temp_path = temp_path();
+    let args = vec![
+        "starcoin",
+        "-n",
+        "test",
+        "-d",
+        temp_path.path().to_str().unwrap(),
+        "--gossip-topics",
+        "consensus-hints",
+    ];
+    let opt = StarcoinOpt::from_iter_safe(args)?;
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert_eq!(
+        config.network.gossip_topics(),
+        vec![GossipTopic::Blocks, GossipTopic::ConsensusHints]
+    );
+    let protocols = config.network.supported_network_protocols();
+    assert_eq!(
+        protocols,
+        vec![
+            BLOCK_PROTOCOL_NAME,
+            CONSENSUS_HINT_PROTOCOL_NAME,
+            IDENTITY_LINKAGE_PROTOCOL_NAME
+        ]
+    );
+    assert!(StarcoinOpt::from_iter_safe(vec!["starcoin", "--gossip-topics", "votes"]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rotate_network_key() -> Result<()> {
+    let temp_path = temp_path();
+    let args = vec![
+        "starcoin",
+        "-n",
+        "test",
+        "-d",
+        temp_path.path().to_str().unwrap(),
+    ];
+    let opt = StarcoinOpt::from_iter_safe(args)?;
+    let config = NodeConfig::load_with_opt(&opt)?;
+    let old_peer_id = config.network.self_peer_id();
+    assert!(config.network.load_identity_linkage()?.is_none());
+
+    let linkage = config.network.rotate_network_key(1000)?;
+    linkage.verify()?;
+    assert_eq!(linkage.old_peer_id(), old_peer_id);
+    assert_eq!(
+        config.network.load_identity_linkage()?,
+        Some(linkage.clone())
+    );
+    // rotate again before restart is refused.
+    assert!(config.network.rotate_network_key(2000).is_err());
+
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert_eq!(config.network.self_peer_id(), linkage.new_peer_id());
+    Ok(())
+}
+
 #[test]
 fn test_example_config_compact() -> Result<()> {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -183,3 +287,117 @@ fn test_genesis_config_security() {
         );
     }
 }
+
+#[test]
+fn test_network_descriptor() -> Result<()> {
+    let temp_path = temp_path();
+    let base_data_dir = temp_path.path();
+    let descriptor = NetworkDescriptor {
+        name: "community".to_string(),
+        chain_id: ChainId::new(123),
+        boot_nodes: vec![MultiaddrWithPeerId::from_str(
+            "/ip4/1.2.3.4/tcp/9840/p2p/12D3KooWCfUex27aoqaKScponiLB4N4FWbgmbHYjVoRebGrQaRYk",
+        )?],
+        faucet_url: None,
+        explorer_url: Some("https://explorer.example.com".to_string()),
+        genesis_config: Some(BuiltinNetworkID::Test.genesis_config().clone()),
+    };
+    let file = base_data_dir.join("community.json");
+    std::fs::write(file.as_path(), serde_json::to_vec(&descriptor)?)?;
+    assert_eq!(
+        NetworkDescriptor::install(base_data_dir, file.as_path())?,
+        descriptor
+    );
+    let networks = NetworkDescriptor::list(base_data_dir)?;
+    assert_eq!(networks.len(), BuiltinNetworkID::networks().len() + 1);
+    assert!(networks
+        .iter()
+        .any(|net| net.name == "main" && net.is_builtin()));
+
+    // the builtin network can not be replaced by a third-party descriptor.
+    let mut conflict = descriptor.clone();
+    conflict.chain_id = BuiltinNetworkID::Main.chain_id();
+    std::fs::write(file.as_path(), serde_json::to_vec(&conflict)?)?;
+    assert!(NetworkDescriptor::install(base_data_dir, file.as_path()).is_err());
+
+    // the genesis config and the boot nodes of the installed network are used.
+    let opt = StarcoinOpt {
+        net: Some(ChainNetworkID::from_str("community:123")?),
+        base_data_dir: Some(base_data_dir.to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert_eq!(
+        config.net().genesis_config(),
+        BuiltinNetworkID::Test.genesis_config()
+    );
+    assert!(config.network.seeds().contains(&descriptor.boot_nodes[0]));
+    Ok(())
+}
+
+#[test]
+fn test_config_validation() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    assert!(config.validate().iter().all(|v| !v.is_error()));
+
+    config.stratum.port = config.rpc.get_http_address().map(|address| address.port);
+    let violations = config.validate();
+    assert_eq!(violations.iter().filter(|v| v.is_error()).count(), 1);
+    assert!(violations[0].settings.contains("stratum.port"));
+
+    config.tx_pool.set_mirror(true);
+    config.miner.disable_miner_client = Some(false);
+    config.network.gossip_topics = Some(vec![GossipTopic::Blocks]);
+    let err = config.ensure_valid().unwrap_err();
+    // the stratum is not started in the mirror mode, the mirror conflicts are all reported.
+    assert_eq!(err.violations.iter().filter(|v| v.is_error()).count(), 2);
+    assert!(err.to_string().contains("fix:"));
+    Ok(())
+}
+
+#[test]
+fn test_rpc_exposure_validation() -> Result<()> {
+    let temp_path = temp_path();
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Main.into()),
+        base_data_dir: Some(temp_path.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    opt.rpc.http.apis = Some(ApiSet::All);
+    let err = NodeConfig::load_with_opt(&opt).unwrap_err();
+    assert!(err.to_string().contains("rpc.http.apis"));
+
+    // the exposure is acknowledged, the node starts with a warning.
+    opt.rpc.allow_public_admin_apis = Some(true);
+    let config = NodeConfig::load_with_opt(&opt)?;
+    let violations = config.validate();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].level, ViolationLevel::Warning);
+
+    opt.rpc.allow_public_admin_apis = None;
+    opt.rpc.rpc_address = Some("127.0.0.1".parse()?);
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert!(config.validate().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_miner_reward_validation() -> Result<()> {
+    let temp_path = temp_path();
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Main.into()),
+        base_data_dir: Some(temp_path.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    opt.rpc.rpc_address = Some("127.0.0.1".parse()?);
+    opt.miner.disable_miner_client = Some(false);
+    let config = NodeConfig::load_with_opt(&opt)?;
+    let violations = config.validate();
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].settings.contains("miner.reward_address"));
+
+    opt.miner.reward_address = Some(AccountAddress::random());
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert!(config.validate().is_empty());
+    Ok(())
+}
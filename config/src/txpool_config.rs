@@ -5,10 +5,13 @@ use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use std::path::PathBuf;
 use std::sync::Arc;
 use structopt::StructOpt;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
+/// The default retention window(in seconds) of the txn submission idempotency keys.
+pub const DEFAULT_IDEMPOTENCY_KEY_RETENTION: u64 = 600;
 
 #[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -37,6 +40,46 @@ pub struct TxPoolConfig {
     #[structopt(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-gas-price-bump-percent", long)]
+    /// a transaction with the same sender and sequence number can replace the pooled one only if its gas_price is at least this percent higher. default to 10.
+    gas_price_bump_percent: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-threshold-committee-file", long, parse(from_os_str))]
+    /// The threshold committee json file of the experimental encrypted pool, the encrypted pool is enabled only if it is set and the node is built with the `threshold-encryption` feature.
+    threshold_committee_file: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-threshold-key-share-file", long, parse(from_os_str))]
+    /// The key share json file of the threshold committee member, the node releases the decryption shares of the sealed encrypted txns automatically if it is set.
+    threshold_key_share_file: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-idempotency-key-retention", long)]
+    /// the retention window(in seconds) of the txn submission idempotency keys, a retried submission with the same key returns the original result in the window. default to 600.
+    idempotency_key_retention: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-propagation-delay", long)]
+    /// delay(s) of the first-hop propagation of the locally submitted txns, makes the origin inference by network observers harder. default to 0, no delay.
+    propagation_delay: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-propagation-jitter", long)]
+    /// max random extra delay(s) added to the propagation delay of every locally submitted txn. default to 0.
+    propagation_jitter: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-relay-peers", long)]
+    /// relay the locally submitted txns to this number of random peers first, and broadcast them only if they are still pending after a while. default to 0, broadcast directly.
+    relay_peers: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-mirror", long)]
+    /// run the txpool in the observation-only mirror mode, the node receives and propagates the txns from peers, but never mints blocks or accepts the txn submission. default to false.
+    mirror: Option<bool>,
 }
 
 impl TxPoolConfig {
@@ -69,6 +112,37 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn gas_price_bump_percent(&self) -> u64 {
+        self.gas_price_bump_percent.unwrap_or(10)
+    }
+    pub fn idempotency_key_retention(&self) -> u64 {
+        self.idempotency_key_retention
+            .unwrap_or(DEFAULT_IDEMPOTENCY_KEY_RETENTION)
+    }
+    pub fn propagation_delay(&self) -> u64 {
+        self.propagation_delay.unwrap_or(0)
+    }
+    pub fn propagation_jitter(&self) -> u64 {
+        self.propagation_jitter.unwrap_or(0)
+    }
+    pub fn relay_peers(&self) -> u32 {
+        self.relay_peers.unwrap_or(0)
+    }
+    pub fn set_propagation_delay(&mut self, propagation_delay: u64) {
+        self.propagation_delay = Some(propagation_delay);
+    }
+    pub fn set_mirror(&mut self, mirror: bool) {
+        self.mirror = Some(mirror);
+    }
+    pub fn is_mirror(&self) -> bool {
+        self.mirror.unwrap_or(false)
+    }
+    pub fn threshold_committee_file(&self) -> Option<&PathBuf> {
+        self.threshold_committee_file.as_ref()
+    }
+    pub fn threshold_key_share_file(&self) -> Option<&PathBuf> {
+        self.threshold_key_share_file.as_ref()
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
@@ -89,6 +163,30 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.gas_price_bump_percent.as_ref() {
+            self.gas_price_bump_percent = Some(*m);
+        }
+        if let Some(m) = txpool_opt.idempotency_key_retention.as_ref() {
+            self.idempotency_key_retention = Some(*m);
+        }
+        if let Some(m) = txpool_opt.propagation_delay.as_ref() {
+            self.propagation_delay = Some(*m);
+        }
+        if let Some(m) = txpool_opt.propagation_jitter.as_ref() {
+            self.propagation_jitter = Some(*m);
+        }
+        if let Some(m) = txpool_opt.relay_peers.as_ref() {
+            self.relay_peers = Some(*m);
+        }
+        if let Some(m) = txpool_opt.mirror.as_ref() {
+            self.mirror = Some(*m);
+        }
+        if let Some(m) = txpool_opt.threshold_committee_file.as_ref() {
+            self.threshold_committee_file = Some(m.clone());
+        }
+        if let Some(m) = txpool_opt.threshold_key_share_file.as_ref() {
+            self.threshold_key_share_file = Some(m.clone());
+        }
         Ok(())
     }
 }
@@ -5,9 +5,14 @@
 
 use crate::consensus::Consensus;
 use crate::difficulty::{get_next_target_helper, BlockDiffInfo};
-use crate::{difficult_to_target, target_to_difficulty, CRYPTONIGHT};
+use crate::header_codec::{HeaderBlob, HEADER_BLOB_SIZE};
+use crate::{
+    difficult_to_target, extra_with_extranonce, set_header_extranonce, set_header_nonce,
+    target_to_difficulty, CRYPTONIGHT,
+};
 use starcoin_crypto::hash::PlainCryptoHash;
-use starcoin_types::block::{BlockHeader, BlockHeaderBuilder, RawBlockHeader};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::{BlockHeader, BlockHeaderBuilder, BlockHeaderExtra, RawBlockHeader};
 use starcoin_types::U256;
 use starcoin_vm_types::time::{
     duration_since_epoch, MockTimeService, TimeService, TimeServiceType,
@@ -250,3 +255,104 @@ fn test_next_target_increment_difficulty_compare() {
     assert!(next_target_1 < target0);
     assert!(next_target_2 > target0);
 }
+
+#[stest::test]
+fn test_extra_with_extranonce() {
+    let worker_id = 0x0403_0201u32;
+    let extra = extra_with_extranonce(worker_id, 0x0d0c_0b0a, 0).unwrap();
+    assert_eq!(extra.as_slice(), &[0x01, 0x02, 0x03, 0x04]);
+    let extra = extra_with_extranonce(0x0201, 0x0d0c_0b0a, 2).unwrap();
+    assert_eq!(extra.as_slice(), &[0x01, 0x02, 0x0a, 0x0b]);
+    let extra = extra_with_extranonce(0, 0x0d0c_0b0a, 4).unwrap();
+    assert_eq!(extra.as_slice(), &[0x0a, 0x0b, 0x0c, 0x0d]);
+
+    let header = BlockHeader::random().as_pow_header_blob();
+    assert_eq!(
+        set_header_extranonce(&header, 1, 0x0201, 0x0d0c_0b0a, 2).unwrap(),
+        set_header_nonce(
+            &header,
+            1,
+            &extra_with_extranonce(0x0201, 0x0d0c_0b0a, 2).unwrap()
+        )
+    );
+}
+
+#[stest::test]
+fn test_extra_with_extranonce_worker_id_overflow() {
+    // the max worker id of a 2 bytes prefix is 0xffff.
+    let extra = extra_with_extranonce(0xffff, 0x0d0c_0b0a, 2).unwrap();
+    assert_eq!(extra.as_slice(), &[0xff, 0xff, 0x0a, 0x0b]);
+    assert!(extra_with_extranonce(0x1_0000, 0x0d0c_0b0a, 2).is_err());
+    assert!(set_header_extranonce(
+        &BlockHeader::random().as_pow_header_blob(),
+        1,
+        0x1_0000,
+        0x0d0c_0b0a,
+        2
+    )
+    .is_err());
+
+    // no worker prefix when the whole extra is extranonce.
+    assert!(extra_with_extranonce(0, 0x0d0c_0b0a, 4).is_ok());
+    assert!(extra_with_extranonce(1, 0x0d0c_0b0a, 4).is_err());
+    // u32::MAX always fits in the 4 bytes prefix.
+    assert!(extra_with_extranonce(u32::MAX, 0, 0).is_ok());
+}
+
+#[stest::test]
+fn test_header_codec_golden_vector() {
+    let mut mining_hash = [0u8; 32];
+    for (i, b) in mining_hash.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let header_blob = HeaderBlob {
+        mining_hash: HashValue::new(mining_hash),
+        extra: BlockHeaderExtra::new([0x0a, 0x0b, 0x0c, 0x0d]),
+        nonce: 0x1234_5678,
+        difficulty: U256::from(0x1000),
+    };
+    let blob = header_blob.encode();
+    assert_eq!(blob.len(), HEADER_BLOB_SIZE);
+    assert_eq!(
+        hex::encode(&blob),
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+         0000000a0b0c0d7856341200\
+         0000000000000000000000000000000000000000000000000000000000001000"
+    );
+    assert_eq!(HeaderBlob::decode(&blob).unwrap(), header_blob);
+
+    let mut invalid_blob = blob.clone();
+    invalid_blob[32] = 1;
+    assert!(HeaderBlob::decode(&invalid_blob).is_err());
+    assert!(HeaderBlob::decode(&blob[1..]).is_err());
+}
+
+#[stest::test]
+fn test_header_codec_compatible_with_header() {
+    let header = BlockHeader::random();
+    let blob = header.as_pow_header_blob();
+    let raw_header: RawBlockHeader = header.clone().into();
+    let header_blob = HeaderBlob::decode(&blob).unwrap();
+    assert_eq!(header_blob.mining_hash, raw_header.crypto_hash());
+    assert_eq!(header_blob.difficulty, header.difficulty());
+    assert_eq!(header_blob.nonce, 0);
+    assert_eq!(
+        header_blob,
+        HeaderBlob::new(raw_header.crypto_hash(), header.difficulty())
+    );
+
+    let extra = BlockHeaderExtra::new([1, 2, 3, 4]);
+    let mined_blob = set_header_nonce(&blob, 42, &extra);
+    let mined_header_blob = HeaderBlob::decode(&mined_blob).unwrap();
+    assert_eq!(mined_header_blob.nonce, 42);
+    assert_eq!(mined_header_blob.extra, extra);
+    assert_eq!(
+        mined_blob,
+        HeaderBlob {
+            nonce: 42,
+            extra,
+            ..header_blob
+        }
+        .encode()
+    );
+}
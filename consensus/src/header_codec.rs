@@ -0,0 +1,107 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The binary layout of the mining header blob, which is `MintBlockEvent.minting_blob`,
+//! `MineCtx.header` in the miner, and the input of the pow hash.
+//!
+//! The pow hash is computed over the blob, so the layout is part of the consensus and carries
+//! no version field, a layout change is a hard fork. The layout, 76 bytes in total:
+//!
+//! | offset | size | field                                              |
+//! |--------|------|----------------------------------------------------|
+//! | 0      | 32   | mining hash, the hash of `RawBlockHeader`          |
+//! | 32     | 3    | reserved, all zero                                 |
+//! | 35     | 4    | `BlockHeaderExtra`, the extranonce region for pool |
+//! | 39     | 4    | nonce, little endian u32                           |
+//! | 43     | 1    | reserved, all zero                                 |
+//! | 44     | 32   | difficulty, big endian U256                        |
+
+use crate::HEADER_EXTRA_SIZE;
+use anyhow::{ensure, Result};
+use byteorder::{ByteOrder, LittleEndian};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockHeaderExtra;
+use starcoin_types::U256;
+use std::convert::TryInto;
+use std::ops::Range;
+
+/// The byte size of the header blob.
+pub const HEADER_BLOB_SIZE: usize = 76;
+
+/// The byte range of the mining hash in the header blob.
+pub const MINING_HASH_REGION: Range<usize> = 0..32;
+/// The byte range of the `BlockHeaderExtra` in the header blob.
+pub const EXTRA_REGION: Range<usize> = 35..39;
+/// The byte range of the nonce in the header blob.
+pub const NONCE_REGION: Range<usize> = 39..43;
+/// The byte range of the difficulty in the header blob.
+pub const DIFFICULTY_REGION: Range<usize> = 44..76;
+
+/// The reserved byte ranges, must be all zero.
+const RESERVED_REGIONS: [Range<usize>; 2] = [32..35, 43..44];
+
+/// The decoded header blob.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeaderBlob {
+    pub mining_hash: HashValue,
+    pub extra: BlockHeaderExtra,
+    pub nonce: u32,
+    pub difficulty: U256,
+}
+
+impl HeaderBlob {
+    pub fn new(mining_hash: HashValue, difficulty: U256) -> Self {
+        Self {
+            mining_hash,
+            extra: BlockHeaderExtra::new([0u8; HEADER_EXTRA_SIZE]),
+            nonce: 0,
+            difficulty,
+        }
+    }
+
+    /// Assemble the header blob.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut blob = vec![0u8; HEADER_BLOB_SIZE];
+        blob[MINING_HASH_REGION].copy_from_slice(self.mining_hash.to_vec().as_slice());
+        blob[EXTRA_REGION].copy_from_slice(self.extra.as_slice());
+        LittleEndian::write_u32(&mut blob[NONCE_REGION], self.nonce);
+        self.difficulty.to_big_endian(&mut blob[DIFFICULTY_REGION]);
+        blob
+    }
+
+    /// Disassemble the header blob.
+    pub fn decode(blob: &[u8]) -> Result<Self> {
+        ensure!(
+            blob.len() == HEADER_BLOB_SIZE,
+            "Invalid header blob size {}, expect {}.",
+            blob.len(),
+            HEADER_BLOB_SIZE
+        );
+        for region in RESERVED_REGIONS.iter() {
+            ensure!(
+                blob[region.clone()].iter().all(|b| *b == 0),
+                "Reserved bytes {:?} of header blob must be zero.",
+                region
+            );
+        }
+        Ok(Self {
+            mining_hash: HashValue::from_slice(&blob[MINING_HASH_REGION])?,
+            extra: BlockHeaderExtra::new(blob[EXTRA_REGION].try_into()?),
+            nonce: LittleEndian::read_u32(&blob[NONCE_REGION]),
+            difficulty: U256::from_big_endian(&blob[DIFFICULTY_REGION]),
+        })
+    }
+}
+
+/// Write the nonce and extra to the header blob in place.
+pub fn write_nonce(blob: &mut [u8], nonce: u32, extra: &BlockHeaderExtra) -> Result<()> {
+    ensure!(
+        blob.len() == HEADER_BLOB_SIZE,
+        "Invalid header blob size {}, expect {}.",
+        blob.len(),
+        HEADER_BLOB_SIZE
+    );
+    blob[EXTRA_REGION].copy_from_slice(extra.as_slice());
+    LittleEndian::write_u32(&mut blob[NONCE_REGION], nonce);
+    Ok(())
+}
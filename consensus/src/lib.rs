@@ -6,8 +6,7 @@ use crate::argon::ArgonConsensus;
 use crate::cn::CryptoNightConsensus;
 use crate::dummy::DummyConsensus;
 use crate::keccak::KeccakConsensus;
-use anyhow::Result;
-use byteorder::{LittleEndian, WriteBytesExt};
+use anyhow::{ensure, Result};
 use once_cell::sync::Lazy;
 use rand::Rng;
 use starcoin_chain_api::ChainReader;
@@ -16,7 +15,6 @@ use starcoin_types::block::{BlockHeader, BlockHeaderExtra};
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use starcoin_vm_types::time::TimeService;
-use std::io::Write;
 
 pub mod argon;
 pub mod cn;
@@ -25,6 +23,7 @@ mod consensus;
 mod consensus_test;
 pub mod difficulty;
 pub mod dummy;
+pub mod header_codec;
 pub mod keccak;
 
 pub use consensus::{Consensus, ConsensusVerifyError};
@@ -38,14 +37,53 @@ pub fn difficult_to_target(difficulty: U256) -> U256 {
     U256::max_value() / difficulty
 }
 
+/// The byte size of `BlockHeaderExtra` in the header blob.
+pub const HEADER_EXTRA_SIZE: usize = 4;
+
+/// Build the `BlockHeaderExtra` for pool mining, the leading bytes are the worker prefix assigned by pool,
+/// and the last `extranonce_size` bytes are the extranonce region rolled by the worker.
+/// Return error if the `worker_id` does not fit in the worker prefix.
+pub fn extra_with_extranonce(
+    worker_id: u32,
+    extranonce: u32,
+    extranonce_size: usize,
+) -> Result<BlockHeaderExtra> {
+    let extranonce_size = extranonce_size.min(HEADER_EXTRA_SIZE);
+    let prefix_size = HEADER_EXTRA_SIZE.saturating_sub(extranonce_size);
+    let worker_id_bytes = worker_id.to_le_bytes();
+    ensure!(
+        worker_id_bytes[prefix_size..].iter().all(|b| *b == 0),
+        "worker id {} does not fit in the {} bytes worker prefix",
+        worker_id,
+        prefix_size
+    );
+    let mut extra = [0u8; HEADER_EXTRA_SIZE];
+    extra[..prefix_size].copy_from_slice(&worker_id_bytes[..prefix_size]);
+    extra[prefix_size..].copy_from_slice(&extranonce.to_le_bytes()[..extranonce_size]);
+    Ok(BlockHeaderExtra::new(extra))
+}
+
+pub fn set_header_extranonce(
+    header: &[u8],
+    nonce: u32,
+    worker_id: u32,
+    extranonce: u32,
+    extranonce_size: usize,
+) -> Result<Vec<u8>> {
+    Ok(set_header_nonce(
+        header,
+        nonce,
+        &extra_with_extranonce(worker_id, extranonce, extranonce_size)?,
+    ))
+}
+
+/// Set the nonce and extra of the header blob, return empty if the header blob is invalid.
+/// See `header_codec` for the layout of the header blob.
 pub fn set_header_nonce(header: &[u8], nonce: u32, extra: &BlockHeaderExtra) -> Vec<u8> {
-    let len = header.len();
-    if len != 76 {
+    let mut header = header.to_owned();
+    if header_codec::write_nonce(&mut header, nonce, extra).is_err() {
         return vec![];
     }
-    let mut header = header.to_owned();
-    let _ = header[39..].as_mut().write_u32::<LittleEndian>(nonce);
-    let _ = header[35..39].as_mut().write_all(extra.as_slice());
     header
 }
 
@@ -0,0 +1,16 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Track the deposits to the registered addresses, such as the deposit addresses of an exchange.
+//! A deposit is confirmed after the confirmations required by the policy, and the deposits of the
+//! blocks rolled back by a reorg are reported, so the callers only credit confirmed deposits.
+
+mod policy;
+mod service;
+mod source;
+mod tracker;
+
+pub use policy::{ConfirmationPolicy, ConfirmationTier};
+pub use service::{confirmed_deposits, DepositTrackerService};
+pub use source::{BlockRef, DepositSource};
+pub use tracker::{Deposit, DepositNotification, DepositTracker};
@@ -0,0 +1,69 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tracker::Deposit;
+use serde::{Deserialize, Serialize};
+
+/// Require more confirmations for the deposits of at least `min_amount`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConfirmationTier {
+    /// The tier only applies to the token if set, such as `0x1::STC::STC`.
+    #[serde(default)]
+    pub token_code: Option<String>,
+    pub min_amount: u128,
+    pub confirmations: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConfirmationPolicy {
+    /// The confirmations of a deposit, the block of the deposit counts as one.
+    pub confirmations: u64,
+    #[serde(default)]
+    pub tiers: Vec<ConfirmationTier>,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self {
+            confirmations: 6,
+            tiers: vec![],
+        }
+    }
+}
+
+impl ConfirmationPolicy {
+    pub fn new(confirmations: u64) -> Self {
+        Self {
+            confirmations,
+            tiers: vec![],
+        }
+    }
+
+    pub fn with_tier(mut self, tier: ConfirmationTier) -> Self {
+        self.tiers.push(tier);
+        self
+    }
+
+    /// The confirmations of the deposit, the max of the matched tiers and the default.
+    pub fn required_confirmations(&self, deposit: &Deposit) -> u64 {
+        self.tiers
+            .iter()
+            .filter(|tier| {
+                deposit.amount >= tier.min_amount
+                    && tier
+                        .token_code
+                        .as_ref()
+                        .map_or(true, |token_code| token_code == &deposit.token_code)
+            })
+            .map(|tier| tier.confirmations)
+            .fold(self.confirmations, u64::max)
+    }
+
+    /// The max confirmations of the policy, the tracker keeps the blocks for it.
+    pub fn max_confirmations(&self) -> u64 {
+        self.tiers
+            .iter()
+            .map(|tier| tier.confirmations)
+            .fold(self.confirmations, u64::max)
+    }
+}
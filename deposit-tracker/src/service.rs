@@ -0,0 +1,82 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::source::{BlockRef, DepositSource};
+use crate::tracker::{Deposit, DepositNotification, DepositTracker};
+use anyhow::Result;
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt, TryStreamExt};
+use starcoin_logger::prelude::*;
+use starcoin_rpc_client::RpcClient;
+use std::sync::Arc;
+use std::thread;
+
+/// Run the tracker by the new heads subscription of the node.
+pub struct DepositTrackerService;
+
+impl DepositTrackerService {
+    /// Spawn a thread to track the deposits, the notifications are sent to the returned stream.
+    /// The tracker catches up to the current head first, then follows the new heads. The stream
+    /// ends with the error if the tracking fails, such as a reorg deeper than the tracked blocks,
+    /// the caller should restart it from its last processed block by `DepositTracker::resume_from`.
+    /// The client should be connected by ipc or websocket, which supports subscription.
+    pub fn spawn(
+        client: Arc<RpcClient>,
+        mut tracker: DepositTracker,
+    ) -> Result<mpsc::UnboundedReceiver<Result<DepositNotification>>> {
+        let (sender, receiver) = mpsc::unbounded();
+        thread::Builder::new()
+            .name("deposit-tracker".to_string())
+            .spawn(move || {
+                let result = futures::executor::block_on(async {
+                    let mut blocks = Box::pin(client.subscribe_new_blocks()?);
+                    let head = DepositSource::head(client.as_ref())?;
+                    Self::process(client.as_ref(), &mut tracker, head, &sender)?;
+                    while let Some(block) = blocks.try_next().await? {
+                        if sender.is_closed() {
+                            break;
+                        }
+                        Self::process(
+                            client.as_ref(),
+                            &mut tracker,
+                            BlockRef::from(&block),
+                            &sender,
+                        )?;
+                    }
+                    Ok::<(), anyhow::Error>(())
+                });
+                if let Err(e) = result {
+                    error!("Deposit tracker stopped: {:?}", e);
+                    let _ = sender.unbounded_send(Err(e));
+                }
+            })?;
+        Ok(receiver)
+    }
+
+    fn process(
+        source: &dyn DepositSource,
+        tracker: &mut DepositTracker,
+        head: BlockRef,
+        sender: &mpsc::UnboundedSender<Result<DepositNotification>>,
+    ) -> Result<()> {
+        for notification in tracker.on_head(source, head)? {
+            debug!("Deposit notification: {:?}", notification);
+            // the receiver is dropped, the loop breaks at the next head.
+            let _ = sender.unbounded_send(Ok(notification));
+        }
+        Ok(())
+    }
+}
+
+/// Only keep the confirmed deposits and the error of the notifications.
+pub fn confirmed_deposits(
+    notifications: impl Stream<Item = Result<DepositNotification>>,
+) -> impl Stream<Item = Result<Deposit>> {
+    notifications.filter_map(|notification| {
+        futures::future::ready(match notification {
+            Ok(DepositNotification::Confirmed(deposit)) => Some(Ok(deposit)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    })
+}
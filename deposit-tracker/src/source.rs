@@ -0,0 +1,93 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tracker::Deposit;
+use anyhow::Result;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{BlockHeaderView, BlockView};
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::DepositEvent;
+use starcoin_types::block::BlockNumber;
+use starcoin_vm_types::move_resource::MoveResource;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BlockRef {
+    pub number: BlockNumber,
+    pub hash: HashValue,
+    pub parent_hash: HashValue,
+}
+
+impl From<&BlockHeaderView> for BlockRef {
+    fn from(header: &BlockHeaderView) -> Self {
+        Self {
+            number: header.number.0,
+            hash: header.block_hash,
+            parent_hash: header.parent_hash,
+        }
+    }
+}
+
+impl From<&BlockView> for BlockRef {
+    fn from(block: &BlockView) -> Self {
+        Self::from(&block.header)
+    }
+}
+
+/// The chain the deposits are tracked on.
+pub trait DepositSource {
+    fn head(&self) -> Result<BlockRef>;
+
+    fn block_by_hash(&self, hash: HashValue) -> Result<Option<BlockRef>>;
+
+    /// The deposits to the addresses in the block.
+    fn deposits(&self, block: &BlockRef, addresses: &[AccountAddress]) -> Result<Vec<Deposit>>;
+}
+
+impl DepositSource for RpcClient {
+    fn head(&self) -> Result<BlockRef> {
+        Ok(BlockRef::from(&self.chain_info()?.head))
+    }
+
+    fn block_by_hash(&self, hash: HashValue) -> Result<Option<BlockRef>> {
+        Ok(self
+            .chain_get_block_by_hash(hash)?
+            .map(|block| BlockRef::from(&block)))
+    }
+
+    fn deposits(&self, block: &BlockRef, addresses: &[AccountAddress]) -> Result<Vec<Deposit>> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+        let filter = EventFilter {
+            from_block: Some(block.number),
+            to_block: Some(block.number),
+            event_keys: vec![],
+            addrs: addresses.to_vec(),
+            type_tags: vec![DepositEvent::type_tag().into()],
+            limit: None,
+            replay: None,
+        };
+        let mut deposits = vec![];
+        for event in self.chain_get_events(filter)? {
+            // the block of the number is switched by a reorg, the following head rolls it back.
+            if event.block_hash != Some(block.hash) {
+                continue;
+            }
+            let deposit_event = DepositEvent::try_from_bytes(event.data.0.as_slice())?;
+            deposits.push(Deposit {
+                address: event.event_key.get_creator_address(),
+                amount: deposit_event.amount(),
+                token_code: deposit_event.token_code().to_string(),
+                block_number: block.number,
+                block_hash: block.hash,
+                txn_hash: event.transaction_hash.unwrap_or_else(HashValue::zero),
+                event_seq_number: event.event_seq_number.0,
+            });
+        }
+        // the events are returned from the latest.
+        deposits.reverse();
+        Ok(deposits)
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::policy::ConfirmationPolicy;
+use crate::source::{BlockRef, DepositSource};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub address: AccountAddress,
+    pub amount: u128,
+    pub token_code: String,
+    pub block_number: BlockNumber,
+    pub block_hash: HashValue,
+    pub txn_hash: HashValue,
+    /// The sequence number of the deposit event of the address, a deposit is identified by
+    /// the address and it.
+    pub event_seq_number: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepositNotification {
+    /// The deposit is included in a block, waiting for the confirmations.
+    Seen(Deposit),
+    /// The deposit has the confirmations required by the policy.
+    Confirmed(Deposit),
+    /// The block of the deposit is rolled back by a reorg, the deposit may be included again
+    /// by the new branch. A confirmed deposit is only rolled back by a reorg deeper than the
+    /// confirmations.
+    RolledBack { deposit: Deposit, confirmed: bool },
+}
+
+struct TrackedDeposit {
+    deposit: Deposit,
+    confirmed: bool,
+}
+
+/// Track the deposits to the registered addresses by the heads of the chain.
+///
+/// The tracker keeps the recent blocks, a new head is walked back by the parent hash to the
+/// tracked blocks, so the missed blocks are scanned and the blocks of the old branch are rolled
+/// back. The tracker does no io itself, the chain is read by the `DepositSource`.
+pub struct DepositTracker {
+    addresses: Vec<AccountAddress>,
+    policy: ConfirmationPolicy,
+    max_reorg_depth: u64,
+    /// The recent blocks of the tracked chain, from the oldest.
+    blocks: VecDeque<BlockRef>,
+    deposits: Vec<TrackedDeposit>,
+}
+
+impl DepositTracker {
+    pub fn new(
+        addresses: Vec<AccountAddress>,
+        policy: ConfirmationPolicy,
+        max_reorg_depth: u64,
+    ) -> Self {
+        let mut tracker = Self {
+            addresses: vec![],
+            policy,
+            max_reorg_depth,
+            blocks: VecDeque::new(),
+            deposits: vec![],
+        };
+        for address in addresses {
+            tracker.register(address);
+        }
+        tracker
+    }
+
+    /// Resume tracking after the block, the blocks after it are scanned at the next head.
+    /// The block is usually the tip persisted by the caller before it stopped.
+    pub fn resume_from(mut self, block: BlockRef) -> Self {
+        self.blocks.clear();
+        self.blocks.push_back(block);
+        self
+    }
+
+    /// Track the deposits to the address from the next head.
+    pub fn register(&mut self, address: AccountAddress) {
+        if !self.addresses.contains(&address) {
+            self.addresses.push(address);
+        }
+    }
+
+    pub fn unregister(&mut self, address: AccountAddress) -> bool {
+        let len = self.addresses.len();
+        self.addresses.retain(|registered| registered != &address);
+        self.addresses.len() != len
+    }
+
+    pub fn addresses(&self) -> &[AccountAddress] {
+        self.addresses.as_slice()
+    }
+
+    pub fn policy(&self) -> &ConfirmationPolicy {
+        &self.policy
+    }
+
+    /// The latest block scanned by the tracker.
+    pub fn tip(&self) -> Option<&BlockRef> {
+        self.blocks.back()
+    }
+
+    /// The deposits waiting for the confirmations.
+    pub fn pending(&self) -> Vec<&Deposit> {
+        self.deposits
+            .iter()
+            .filter(|tracked| !tracked.confirmed)
+            .map(|tracked| &tracked.deposit)
+            .collect()
+    }
+
+    fn is_tracked(&self, hash: HashValue) -> bool {
+        self.blocks.iter().any(|block| block.hash == hash)
+    }
+
+    /// Process the new head of the chain, return the notifications of the deposits.
+    /// The state is unchanged if it fails, so the head can be processed again.
+    pub fn on_head(
+        &mut self,
+        source: &dyn DepositSource,
+        head: BlockRef,
+    ) -> Result<Vec<DepositNotification>> {
+        if self.is_tracked(head.hash) {
+            return Ok(vec![]);
+        }
+        // walk back to the tracked blocks, the branch is from the head.
+        let mut branch = vec![head];
+        if let Some(oldest) = self.blocks.front().copied() {
+            loop {
+                let last = branch[branch.len() - 1];
+                if self.is_tracked(last.parent_hash) {
+                    break;
+                }
+                if last.number <= oldest.number {
+                    bail!(
+                        "Can not find the ancestor of head {:?} in the tracked blocks, the reorg is deeper than {} blocks",
+                        head,
+                        self.blocks.len()
+                    );
+                }
+                match source.block_by_hash(last.parent_hash)? {
+                    Some(parent) => branch.push(parent),
+                    None => bail!("Can not find block by hash {}", last.parent_hash),
+                }
+            }
+        }
+        let mut scanned = Vec::with_capacity(branch.len());
+        for block in branch.into_iter().rev() {
+            let deposits = source.deposits(&block, self.addresses.as_slice())?;
+            scanned.push((block, deposits));
+        }
+
+        let mut notifications = vec![];
+        if let Some((first, _)) = scanned.first() {
+            self.rollback_to(first.parent_hash, &mut notifications);
+        }
+        for (block, deposits) in scanned {
+            for deposit in deposits {
+                notifications.push(DepositNotification::Seen(deposit.clone()));
+                self.deposits.push(TrackedDeposit {
+                    deposit,
+                    confirmed: false,
+                });
+            }
+            self.blocks.push_back(block);
+        }
+        self.confirm(&mut notifications);
+        self.prune();
+        Ok(notifications)
+    }
+
+    fn rollback_to(&mut self, ancestor: HashValue, notifications: &mut Vec<DepositNotification>) {
+        while let Some(tip) = self.blocks.back().copied() {
+            if tip.hash == ancestor {
+                break;
+            }
+            self.blocks.pop_back();
+            info!("Roll back block {} {} by reorg", tip.number, tip.hash);
+            let (rolled_back, kept): (Vec<_>, Vec<_>) = self
+                .deposits
+                .drain(..)
+                .partition(|tracked| tracked.deposit.block_hash == tip.hash);
+            self.deposits = kept;
+            for tracked in rolled_back {
+                if tracked.confirmed {
+                    warn!(
+                        "Confirmed deposit {:?} is rolled back by a reorg deeper than the confirmations",
+                        tracked.deposit
+                    );
+                }
+                notifications.push(DepositNotification::RolledBack {
+                    deposit: tracked.deposit,
+                    confirmed: tracked.confirmed,
+                });
+            }
+        }
+    }
+
+    fn confirm(&mut self, notifications: &mut Vec<DepositNotification>) {
+        let tip_number = match self.blocks.back() {
+            Some(tip) => tip.number,
+            None => return,
+        };
+        let policy = &self.policy;
+        for tracked in self
+            .deposits
+            .iter_mut()
+            .filter(|tracked| !tracked.confirmed)
+        {
+            let confirmations = tip_number
+                .saturating_sub(tracked.deposit.block_number)
+                .saturating_add(1);
+            if confirmations >= policy.required_confirmations(&tracked.deposit) {
+                tracked.confirmed = true;
+                notifications.push(DepositNotification::Confirmed(tracked.deposit.clone()));
+            }
+        }
+    }
+
+    /// Keep the blocks for the reorg depth and the confirmations.
+    fn prune(&mut self) {
+        let window = self
+            .max_reorg_depth
+            .max(self.policy.max_confirmations())
+            .saturating_add(1) as usize;
+        while self.blocks.len() > window {
+            self.blocks.pop_front();
+        }
+        if let Some(oldest) = self.blocks.front() {
+            let oldest_number = oldest.number;
+            self.deposits.retain(|tracked| {
+                !tracked.confirmed || tracked.deposit.block_number >= oldest_number
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::ConfirmationTier;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockSource {
+        blocks: HashMap<HashValue, BlockRef>,
+        deposits: HashMap<HashValue, Vec<Deposit>>,
+    }
+
+    impl MockSource {
+        fn block(&mut self, parent: Option<&BlockRef>) -> BlockRef {
+            let block = BlockRef {
+                number: parent.map_or(0, |parent| parent.number + 1),
+                hash: HashValue::random(),
+                parent_hash: parent.map_or_else(HashValue::zero, |parent| parent.hash),
+            };
+            self.blocks.insert(block.hash, block);
+            block
+        }
+
+        fn deposit(&mut self, block: &BlockRef, address: AccountAddress, amount: u128) -> Deposit {
+            let deposit = Deposit {
+                address,
+                amount,
+                token_code: "0x1::STC::STC".to_string(),
+                block_number: block.number,
+                block_hash: block.hash,
+                txn_hash: HashValue::random(),
+                event_seq_number: 0,
+            };
+            self.deposits
+                .entry(block.hash)
+                .or_default()
+                .push(deposit.clone());
+            deposit
+        }
+    }
+
+    impl DepositSource for MockSource {
+        fn head(&self) -> Result<BlockRef> {
+            unreachable!()
+        }
+
+        fn block_by_hash(&self, hash: HashValue) -> Result<Option<BlockRef>> {
+            Ok(self.blocks.get(&hash).copied())
+        }
+
+        fn deposits(&self, block: &BlockRef, addresses: &[AccountAddress]) -> Result<Vec<Deposit>> {
+            Ok(self
+                .deposits
+                .get(&block.hash)
+                .map(|deposits| {
+                    deposits
+                        .iter()
+                        .filter(|deposit| addresses.contains(&deposit.address))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn test_deposit_tracker() {
+        let address = AccountAddress::random();
+        let mut source = MockSource::default();
+        let policy = ConfirmationPolicy::new(3).with_tier(ConfirmationTier {
+            token_code: None,
+            min_amount: 1000,
+            confirmations: 5,
+        });
+        let mut tracker = DepositTracker::new(vec![address], policy, 10);
+
+        let b0 = source.block(None);
+        assert!(tracker.on_head(&source, b0).unwrap().is_empty());
+        let b1 = source.block(Some(&b0));
+        let d1 = source.deposit(&b1, address, 100);
+        source.deposit(&b1, AccountAddress::random(), 100);
+        assert_eq!(
+            tracker.on_head(&source, b1).unwrap(),
+            vec![DepositNotification::Seen(d1.clone())]
+        );
+        let b2 = source.block(Some(&b1));
+        assert!(tracker.on_head(&source, b2).unwrap().is_empty());
+        // the head is processed once.
+        assert!(tracker.on_head(&source, b2).unwrap().is_empty());
+        let b3 = source.block(Some(&b2));
+        assert_eq!(
+            tracker.on_head(&source, b3).unwrap(),
+            vec![DepositNotification::Confirmed(d1.clone())]
+        );
+
+        // reorg from b1, the new head is notified without the blocks before it.
+        let c2 = source.block(Some(&b1));
+        let d2 = source.deposit(&c2, address, 2000);
+        let c3 = source.block(Some(&c2));
+        let c4 = source.block(Some(&c3));
+        assert_eq!(
+            tracker.on_head(&source, c4).unwrap(),
+            vec![DepositNotification::Seen(d2.clone())]
+        );
+        assert_eq!(tracker.pending(), vec![&d2]);
+        assert_eq!(tracker.tip(), Some(&c4));
+
+        // the large deposit requires 5 confirmations.
+        let c5 = source.block(Some(&c4));
+        let d3 = source.deposit(&c5, address, 10);
+        let c6 = source.block(Some(&c5));
+        assert_eq!(
+            tracker.on_head(&source, c6).unwrap(),
+            vec![
+                DepositNotification::Seen(d3.clone()),
+                DepositNotification::Confirmed(d2),
+            ]
+        );
+
+        // reorg rolls back the pending deposit.
+        let e5 = source.block(Some(&c4));
+        let e6 = source.block(Some(&e5));
+        let e7 = source.block(Some(&e6));
+        assert_eq!(
+            tracker.on_head(&source, e7).unwrap(),
+            vec![DepositNotification::RolledBack {
+                deposit: d3,
+                confirmed: false,
+            }]
+        );
+        assert!(tracker.pending().is_empty());
+    }
+
+    #[test]
+    fn test_deep_reorg() {
+        let address = AccountAddress::random();
+        let mut source = MockSource::default();
+        let mut tracker = DepositTracker::new(vec![address], ConfirmationPolicy::new(1), 2);
+        let b0 = source.block(None);
+        let mut head = b0;
+        for _ in 0..5 {
+            head = source.block(Some(&head));
+            tracker.on_head(&source, head).unwrap();
+        }
+        let fork = source.block(Some(&b0));
+        source.deposit(&fork, address, 100);
+        let fork_head = source.block(Some(&fork));
+        assert!(tracker.on_head(&source, fork_head).is_err());
+        // the failed head changes nothing.
+        assert_eq!(tracker.tip(), Some(&head));
+        assert!(tracker.pending().is_empty());
+    }
+}
@@ -6,7 +6,7 @@ use starcoin_state_api::ChainState;
 use starcoin_types::error::BlockExecutorError;
 use starcoin_types::error::ExecutorResult;
 use starcoin_types::transaction::TransactionStatus;
-use starcoin_types::transaction::{Transaction, TransactionInfo};
+use starcoin_types::transaction::{Transaction, TransactionInfo, TransactionOutput};
 use starcoin_vm_types::contract_event::ContractEvent;
 use vm_runtime::metrics::TXN_STATUS_COUNTERS;
 
@@ -35,7 +35,34 @@ pub fn block_execute(
     let txn_outputs =
         crate::execute_block_transactions(chain_state.as_super(), txns.clone(), block_gas_limit)
             .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
+
+/// Same as `block_execute`, but execute the independent transactions in parallel by `concurrency` threads.
+pub fn block_execute_parallel<S>(
+    chain_state: &S,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+    concurrency: usize,
+) -> ExecutorResult<BlockExecutedData>
+where
+    S: ChainState + Sync,
+{
+    let txn_outputs = crate::parallel_executor::execute_block_transactions_parallel(
+        chain_state,
+        txns.clone(),
+        block_gas_limit,
+        concurrency,
+    )
+    .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
 
+fn apply_block_outputs(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    txn_outputs: Vec<TransactionOutput>,
+) -> ExecutorResult<BlockExecutedData> {
     let mut executed_data = BlockExecutedData::default();
     for (txn, output) in txns
         .iter()
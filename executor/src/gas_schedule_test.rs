@@ -0,0 +1,51 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::Account;
+use crate::peer_to_peer_txn_sent_as_association;
+use anyhow::Result;
+use starcoin_dev::playground::{dry_run, dry_run_with_gas_schedule};
+use starcoin_vm_types::gas_schedule::{GasAlgebra, GasCost};
+use starcoin_vm_types::transaction::DryRunTransaction;
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use test_helper::executor::prepare_genesis;
+
+#[stest::test]
+fn test_dry_run_with_gas_schedule() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account = Account::new();
+    let txn = peer_to_peer_txn_sent_as_association(
+        *account.address(),
+        Some(account.auth_key()),
+        0,
+        1000,
+        net.time_service().now_secs() + crate::DEFAULT_EXPIRATION_TIME,
+        &net,
+    );
+    let dry_run_txn = DryRunTransaction {
+        public_key: txn.authenticator().public_key(),
+        raw_txn: txn.into_raw_transaction(),
+    };
+
+    let (_, output) = dry_run(&chain_state, dry_run_txn.clone())?;
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let mut gas_schedule = net.genesis_config().vm_config.gas_schedule.clone();
+    gas_schedule.instruction_table = gas_schedule
+        .instruction_table
+        .iter()
+        .map(|cost| GasCost::new(cost.instruction_gas.get() * 2, cost.memory_gas.get()))
+        .collect();
+    let (_, hypothetical_output) =
+        dry_run_with_gas_schedule(&chain_state, dry_run_txn.clone(), gas_schedule)?;
+    assert_eq!(
+        KeptVMStatus::Executed,
+        hypothetical_output.status().status().unwrap()
+    );
+    assert!(hypothetical_output.gas_used() > output.gas_used());
+
+    // the on chain gas schedule is used again without the override.
+    let (_, output2) = dry_run(&chain_state, dry_run_txn)?;
+    assert_eq!(output.gas_used(), output2.gas_used());
+    Ok(())
+}
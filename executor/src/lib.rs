@@ -4,14 +4,15 @@
 extern crate log;
 
 pub use account::Account;
-pub use block_executor::{block_execute, BlockExecutedData};
+pub use block_executor::{block_execute, block_execute_parallel, BlockExecutedData};
 pub use executor::*;
+pub use parallel_executor::execute_block_transactions_parallel;
 pub use starcoin_transaction_builder::{
     build_accept_token_txn, build_batch_transfer_txn, build_transfer_from_association,
     build_transfer_txn, build_transfer_txn_by_token_type,
-    create_signed_txn_with_association_account, encode_create_account_script_function,
-    encode_transfer_script_function, peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME,
-    DEFAULT_MAX_GAS_AMOUNT,
+    build_transfer_txn_with_metadata_by_token_type, create_signed_txn_with_association_account,
+    encode_create_account_script_function, encode_transfer_script_function,
+    peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
 
 pub mod account;
@@ -23,7 +24,12 @@ mod executor;
 #[cfg(test)]
 pub mod executor_test;
 #[cfg(test)]
+pub mod gas_schedule_test;
+#[cfg(test)]
 pub mod module_compatibility_test;
+mod parallel_executor;
+#[cfg(test)]
+pub mod parallel_executor_test;
 #[cfg(test)]
 pub mod readonly_function_call_test;
 #[cfg(test)]
@@ -0,0 +1,247 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Speculative parallel execution of block transactions.
+//!
+//! The user transactions of a block are executed in parallel on the state after the block prologue,
+//! and the access paths read by every transaction are recorded. Then the outputs are validated in
+//! the block order: if a transaction read any access path written by a previous transaction of the
+//! block, it is re-executed serially on the latest state, so the result is always the same as the
+//! serial execution. The re-executed transactions still benefit from the parallel phase, because the
+//! state they read has been loaded into the chain state cache.
+//!
+//! Every transaction of the parallel phase is executed by a fresh vm, so the modules it loads are
+//! read through the recording view instead of the module cache of a reused vm. If a transaction
+//! publishes or upgrades a module, the block falls back to serial execution.
+
+use crate::execute_block_transactions;
+use anyhow::{format_err, Result};
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use starcoin_types::transaction::{Transaction, TransactionOutput, TransactionStatus};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::on_chain_config::{OnChainConfig, VMConfig, Version};
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::write_set::{WriteOp, WriteSet};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use vm_runtime::metrics::{PARALLEL_EXECUTION_CONFLICTS, TXN_EXECUTION_HISTOGRAM};
+use vm_runtime::starcoin_vm::StarcoinVM;
+
+/// The thread pools by the concurrency, a pool is built on the first use and reused by the later blocks.
+static THREAD_POOLS: Lazy<Mutex<HashMap<usize, Arc<ThreadPool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn thread_pool(concurrency: usize) -> Result<Arc<ThreadPool>> {
+    let mut pools = THREAD_POOLS
+        .lock()
+        .map_err(|_| format_err!("The thread pools lock is poisoned"))?;
+    if let Some(pool) = pools.get(&concurrency) {
+        return Ok(pool.clone());
+    }
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(concurrency)
+            .thread_name(|index| format!("parallel-executor-{}", index))
+            .build()?,
+    );
+    pools.insert(concurrency, pool.clone());
+    Ok(pool)
+}
+
+/// Execute a block transactions with gas_limit in parallel by `concurrency` threads,
+/// the outputs are the same as `execute_block_transactions`.
+pub fn execute_block_transactions_parallel(
+    chain_state: &(dyn StateView + Sync),
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+    concurrency: usize,
+) -> Result<Vec<TransactionOutput>> {
+    let prologue_len = txns
+        .iter()
+        .take_while(|txn| matches!(txn, Transaction::BlockMetadata(_)))
+        .count();
+    let user_txns = &txns[prologue_len..];
+    if concurrency <= 1
+        || user_txns.len() < 2
+        || user_txns
+            .iter()
+            .any(|txn| matches!(txn, Transaction::BlockMetadata(_)))
+    {
+        return execute_block_transactions(chain_state, txns, block_gas_limit);
+    }
+    let timer = TXN_EXECUTION_HISTOGRAM
+        .with_label_values(&["execute_block_transactions_parallel"])
+        .start_timer();
+    // The vm loads the configs only once for a block, if a txn changes the configs,
+    // the later txns must be executed under the old configs, so fallback to serial execution.
+    // The loaded modules are cached by the vm, a module published in the block is not seen as
+    // a read conflict, so fallback to serial execution too.
+    let config_paths = [
+        VMConfig::config_id().access_path(),
+        Version::config_id().access_path(),
+    ];
+    let requires_serial = |write_set: &WriteSet| {
+        write_set.iter().any(|(access_path, _)| {
+            access_path.path.is_code() || config_paths.contains(access_path)
+        })
+    };
+
+    let mut state = WriteSetOverlay::new(chain_state);
+    let mut outputs = Vec::with_capacity(txns.len());
+    // the block prologue do not use gas, and all the user txns depend on it.
+    if prologue_len > 0 {
+        for output in crate::execute_transactions(chain_state, txns[..prologue_len].to_vec())? {
+            if let TransactionStatus::Keep(_) = output.status() {
+                if requires_serial(output.write_set()) {
+                    return execute_block_transactions(chain_state, txns, block_gas_limit);
+                }
+                state.apply(output.write_set());
+            }
+            outputs.push(output);
+        }
+    }
+
+    let pool = thread_pool(concurrency)?;
+    let speculative_outputs = pool.install(|| {
+        user_txns
+            .par_iter()
+            .map(|txn| {
+                let mut vm = StarcoinVM::new();
+                let view = ReadRecordingView::new(&state);
+                let output = execute_single_transaction(&mut vm, &view, txn.clone())?;
+                Ok((view.into_read_set(), output))
+            })
+            .collect::<Vec<Result<_>>>()
+    });
+
+    let mut vm = StarcoinVM::new();
+    let mut written = HashSet::new();
+    let mut gas_left = block_gas_limit;
+    for (txn, speculative_output) in user_txns.iter().zip(speculative_outputs) {
+        let (read_set, output) = speculative_output?;
+        let output = if read_set
+            .iter()
+            .any(|access_path| written.contains(access_path))
+        {
+            PARALLEL_EXECUTION_CONFLICTS.inc();
+            execute_single_transaction(&mut vm, &state, txn.clone())?
+        } else {
+            output
+        };
+        match gas_left.checked_sub(output.gas_used()) {
+            Some(left) => gas_left = left,
+            None => break,
+        }
+        if let TransactionStatus::Keep(_) = output.status() {
+            if requires_serial(output.write_set()) {
+                return execute_block_transactions(chain_state, txns, block_gas_limit);
+            }
+            written.extend(
+                output
+                    .write_set()
+                    .iter()
+                    .map(|(access_path, _)| access_path.clone()),
+            );
+            state.apply(output.write_set());
+        }
+        outputs.push(output);
+    }
+    timer.observe_duration();
+    Ok(outputs)
+}
+
+fn execute_single_transaction(
+    vm: &mut StarcoinVM,
+    state_view: &dyn StateView,
+    txn: Transaction,
+) -> Result<TransactionOutput> {
+    vm.execute_block_transactions(state_view, vec![txn], None)?
+        .pop()
+        .map(|(_, output)| output)
+        .ok_or_else(|| format_err!("Execute transaction should return an output."))
+}
+
+/// A layer of the write sets in front of the chain state.
+struct WriteSetOverlay<'a> {
+    base: &'a (dyn StateView + Sync),
+    writes: HashMap<AccessPath, Option<Vec<u8>>>,
+}
+
+impl<'a> WriteSetOverlay<'a> {
+    fn new(base: &'a (dyn StateView + Sync)) -> Self {
+        Self {
+            base,
+            writes: HashMap::new(),
+        }
+    }
+
+    fn apply(&mut self, write_set: &WriteSet) {
+        for (access_path, write_op) in write_set {
+            let value = match write_op {
+                WriteOp::Value(blob) => Some(blob.clone()),
+                WriteOp::Deletion => None,
+            };
+            self.writes.insert(access_path.clone(), value);
+        }
+    }
+}
+
+impl<'a> StateView for WriteSetOverlay<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.writes.get(access_path) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get(access_path),
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+}
+
+/// Record the access paths read by a transaction.
+struct ReadRecordingView<'a> {
+    inner: &'a dyn StateView,
+    read_set: RefCell<HashSet<AccessPath>>,
+}
+
+impl<'a> ReadRecordingView<'a> {
+    fn new(inner: &'a dyn StateView) -> Self {
+        Self {
+            inner,
+            read_set: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn into_read_set(self) -> HashSet<AccessPath> {
+        self.read_set.into_inner()
+    }
+}
+
+impl<'a> StateView for ReadRecordingView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        self.read_set.borrow_mut().insert(access_path.clone());
+        self.inner.get(access_path)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        self.read_set
+            .borrow_mut()
+            .extend(access_paths.iter().cloned());
+        self.inner.multi_get(access_paths)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
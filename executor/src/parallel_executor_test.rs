@@ -0,0 +1,166 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use crate::parallel_executor::thread_pool;
+use crate::Account;
+use anyhow::Result;
+use starcoin_transaction_builder::DEFAULT_EXPIRATION_TIME;
+use starcoin_types::identifier::Identifier;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::{Package, ScriptFunction, TransactionPayload};
+use starcoin_types::{account_config, block_metadata::BlockMetadata, transaction::Transaction};
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use std::sync::Arc;
+use test_helper::executor::{
+    compile_modules_with_address, current_block_number, execute_and_apply, get_sequence_number,
+    prepare_genesis,
+};
+
+#[stest::test]
+fn test_parallel_execute_same_as_serial() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let association_sequence_number =
+        get_sequence_number(account_config::association_address(), &chain_state);
+    let accounts = (0..4).map(|_| Account::new()).collect::<Vec<_>>();
+    for (i, account) in accounts.iter().enumerate() {
+        let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            association_sequence_number + i as u64,
+            50_000_000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            &net,
+        ));
+        let output = execute_and_apply(&chain_state, txn);
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+
+    let block_meta = BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *accounts[0].address(),
+        Some(accounts[0].auth_key()),
+        0,
+        current_block_number(&chain_state) + 1,
+        net.chain_id(),
+        0,
+    );
+    let mut txns = vec![Transaction::BlockMetadata(block_meta)];
+    // every account send two txns, the second txn conflicts with the first one.
+    for seq_number in 0..2 {
+        for (i, account) in accounts.iter().enumerate() {
+            txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+                account,
+                &accounts[(i + 1) % accounts.len()],
+                seq_number,
+                10_000,
+                net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                net.chain_id(),
+            )));
+        }
+    }
+
+    let block_gas_limit = u64::MAX;
+    let serial_outputs =
+        crate::execute_block_transactions(&chain_state, txns.clone(), block_gas_limit)?;
+    let parallel_outputs =
+        crate::execute_block_transactions_parallel(&chain_state, txns.clone(), block_gas_limit, 4)?;
+    assert_eq!(serial_outputs.len(), txns.len());
+    assert_eq!(serial_outputs, parallel_outputs);
+
+    // the block gas limit should take effect as serial execution.
+    let block_gas_limit = serial_outputs[1].gas_used() * 3;
+    let serial_outputs =
+        crate::execute_block_transactions(&chain_state, txns.clone(), block_gas_limit)?;
+    let parallel_outputs =
+        crate::execute_block_transactions_parallel(&chain_state, txns, block_gas_limit, 4)?;
+    assert!(serial_outputs.len() < 9);
+    assert_eq!(serial_outputs, parallel_outputs);
+    Ok(())
+}
+
+#[stest::test]
+fn test_parallel_execute_module_published_in_block() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let association_sequence_number =
+        get_sequence_number(account_config::association_address(), &chain_state);
+    let accounts = (0..2).map(|_| Account::new()).collect::<Vec<_>>();
+    for (i, account) in accounts.iter().enumerate() {
+        let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            association_sequence_number + i as u64,
+            50_000_000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            &net,
+        ));
+        let output = execute_and_apply(&chain_state, txn);
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+
+    let module_source = r#"
+        module M {
+            public(script) fun hello() {
+            }
+        }
+        "#;
+    let module = compile_modules_with_address(*accounts[0].address(), module_source)
+        .pop()
+        .unwrap();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let block_meta = BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *accounts[0].address(),
+        Some(accounts[0].auth_key()),
+        0,
+        current_block_number(&chain_state) + 1,
+        net.chain_id(),
+        0,
+    );
+    // the second txn calls the module published by the first txn of the block.
+    let txns = vec![
+        Transaction::BlockMetadata(block_meta),
+        Transaction::UserTransaction(accounts[0].create_signed_txn_impl(
+            *accounts[0].address(),
+            TransactionPayload::Package(Package::new_with_module(module)?),
+            0,
+            100_000,
+            1,
+            expiration,
+            net.chain_id(),
+        )),
+        Transaction::UserTransaction(accounts[1].create_signed_txn_impl(
+            *accounts[1].address(),
+            TransactionPayload::ScriptFunction(ScriptFunction::new(
+                ModuleId::new(*accounts[0].address(), Identifier::new("M").unwrap()),
+                Identifier::new("hello").unwrap(),
+                vec![],
+                vec![],
+            )),
+            0,
+            100_000,
+            1,
+            expiration,
+            net.chain_id(),
+        )),
+    ];
+
+    let serial_outputs = crate::execute_block_transactions(&chain_state, txns.clone(), u64::MAX)?;
+    assert_eq!(
+        KeptVMStatus::Executed,
+        serial_outputs[2].status().status().unwrap()
+    );
+    let parallel_outputs =
+        crate::execute_block_transactions_parallel(&chain_state, txns, u64::MAX, 4)?;
+    assert_eq!(serial_outputs, parallel_outputs);
+    Ok(())
+}
+
+#[test]
+fn test_thread_pool_reused() -> Result<()> {
+    let pool = thread_pool(3)?;
+    assert_eq!(pool.current_num_threads(), 3);
+    assert!(Arc::ptr_eq(&pool, &thread_pool(3)?));
+    assert!(!Arc::ptr_eq(&pool, &thread_pool(2)?));
+    Ok(())
+}
@@ -0,0 +1,490 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Genesis;
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_config::{
+    BuiltinNetworkID, ChainNetwork, GenesisBlockParameter, GenesisBlockParameterConfig,
+    GenesisConfig,
+};
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
+use starcoin_crypto::{HashValue, PrivateKey, ValidCryptoMaterialStringExt};
+use starcoin_transaction_builder::peer_to_peer_txn_sent_as_association;
+use starcoin_vm_types::account_address::{parse_address, AccountAddress};
+use starcoin_vm_types::genesis_config::{ChainId, ConsensusStrategy};
+use starcoin_vm_types::on_chain_config::{
+    ConsensusConfig, DaoConfig, TransactionPublishOption, VMConfig,
+};
+use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An account funded with STC in the genesis.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAccount {
+    /// The account address in hex.
+    pub address: String,
+    /// The auth key of the account, None to create the account by the address only.
+    #[serde(default)]
+    pub auth_key: Option<String>,
+    /// The STC balance in nanoSTC, transferred from the association account.
+    pub balance: u64,
+}
+
+/// Build the genesis of a custom network, start from the genesis config of a builtin network.
+pub struct GenesisBuilder {
+    chain_name: String,
+    chain_id: ChainId,
+    config: GenesisConfig,
+    accounts: Vec<(AccountAddress, Option<AuthenticationKey>, u128)>,
+}
+
+impl GenesisBuilder {
+    /// The genesis block parameter of the `base` network is replaced by a new one
+    /// with the current timestamp and the parent hash derived from the chain name.
+    pub fn new(chain_name: String, chain_id: ChainId, base: BuiltinNetworkID) -> Self {
+        let mut config = base.genesis_config().clone();
+        let difficulty = config
+            .genesis_block_parameter()
+            .map(|parameter| parameter.difficulty)
+            .unwrap_or_else(|| 1.into());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Get system time should success.")
+            .as_millis() as u64;
+        config.genesis_block_parameter =
+            GenesisBlockParameterConfig::Static(GenesisBlockParameter {
+                parent_hash: HashValue::sha3_256_of(chain_name.as_bytes()),
+                timestamp,
+                difficulty,
+            });
+        Self {
+            chain_name,
+            chain_id,
+            config,
+            accounts: vec![],
+        }
+    }
+
+    pub fn config(&self) -> &GenesisConfig {
+        &self.config
+    }
+
+    pub fn with_genesis_block_parameter(mut self, parameter: GenesisBlockParameter) -> Self {
+        self.config.genesis_block_parameter = GenesisBlockParameterConfig::Static(parameter);
+        self
+    }
+
+    pub fn with_consensus_config(mut self, consensus_config: ConsensusConfig) -> Self {
+        self.config.consensus_config = consensus_config;
+        self
+    }
+
+    /// Set the PoW algorithm.
+    pub fn with_consensus_strategy(mut self, strategy: ConsensusStrategy) -> Self {
+        self.config.consensus_config.strategy = strategy.value();
+        self
+    }
+
+    /// Set the initial block time target in milliseconds.
+    pub fn with_block_time_target(mut self, block_time_target: u64) -> Self {
+        self.config.consensus_config.base_block_time_target = block_time_target;
+        self
+    }
+
+    pub fn with_reward_delay(mut self, reward_delay: u64) -> Self {
+        self.config.reward_delay = reward_delay;
+        self
+    }
+
+    /// Set the STC amount pre-mined to the association account, the initial accounts are funded from it.
+    pub fn with_pre_mine_amount(mut self, pre_mine_amount: u128) -> Self {
+        self.config.pre_mine_amount = pre_mine_amount;
+        self
+    }
+
+    /// Set the STC amount linearly minted to the association account in the `period` seconds.
+    pub fn with_time_mint(mut self, amount: u128, period: u64) -> Self {
+        self.config.time_mint_amount = amount;
+        self.config.time_mint_period = period;
+        self
+    }
+
+    pub fn with_vm_config(mut self, vm_config: VMConfig) -> Self {
+        self.config.vm_config = vm_config;
+        self
+    }
+
+    pub fn with_publishing_option(mut self, publishing_option: TransactionPublishOption) -> Self {
+        self.config.publishing_option = publishing_option;
+        self
+    }
+
+    pub fn with_dao_config(mut self, dao_config: DaoConfig) -> Self {
+        self.config.dao_config = dao_config;
+        self
+    }
+
+    pub fn with_transaction_timeout(mut self, transaction_timeout: u64) -> Self {
+        self.config.transaction_timeout = transaction_timeout;
+        self
+    }
+
+    /// Set the association account key, the private key is required to fund the initial accounts,
+    /// and it is not kept in the genesis config of the built network.
+    pub fn with_association_key(mut self, private_key: Ed25519PrivateKey) -> Result<Self> {
+        let shard = MultiEd25519KeyShard::new(vec![private_key.public_key()], 1, private_key)?;
+        let public_key = shard.public_key();
+        self.config.association_key_pair = (Some(Arc::new(shard)), public_key);
+        Ok(self)
+    }
+
+    /// Fund the account with the STC `balance` from the association account in the genesis.
+    pub fn add_account(
+        mut self,
+        address: AccountAddress,
+        auth_key: Option<AuthenticationKey>,
+        balance: u128,
+    ) -> Self {
+        self.accounts.push((address, auth_key, balance));
+        self
+    }
+
+    /// Build the network and the genesis of it.
+    pub fn build(self) -> Result<(ChainNetwork, Genesis)> {
+        let consensus_config = &self.config.consensus_config;
+        ConsensusStrategy::try_from(consensus_config.strategy)
+            .map_err(|_| format_err!("Invalid consensus strategy {}", consensus_config.strategy))?;
+        ensure!(
+            consensus_config.min_block_time_target <= consensus_config.base_block_time_target
+                && consensus_config.base_block_time_target
+                    <= consensus_config.max_block_time_target,
+            "The block time target {} should be in [{}, {}]",
+            consensus_config.base_block_time_target,
+            consensus_config.min_block_time_target,
+            consensus_config.max_block_time_target
+        );
+        let total_balance = self
+            .accounts
+            .iter()
+            .try_fold(0u128, |total, (_, _, balance)| total.checked_add(*balance))
+            .ok_or_else(|| format_err!("The total balance of the accounts overflows"))?;
+        ensure!(
+            total_balance <= self.config.pre_mine_amount,
+            "The total balance {} of the accounts exceeds the pre mine amount {}",
+            total_balance,
+            self.config.pre_mine_amount
+        );
+
+        let mut config = self.config;
+        let net = ChainNetwork::new_custom(self.chain_name, self.chain_id, config.clone())?;
+        let txns = if self.accounts.is_empty() {
+            vec![]
+        } else {
+            ensure!(
+                config.association_key_pair.0.is_some(),
+                "The association private key is required to fund the genesis accounts."
+            );
+            let expiration_timestamp_secs = (net.genesis_block_parameter().timestamp / 1000)
+                .saturating_add(config.transaction_timeout);
+            self.accounts
+                .into_iter()
+                .enumerate()
+                .map(|(seq_num, (address, auth_key, balance))| {
+                    peer_to_peer_txn_sent_as_association(
+                        address,
+                        auth_key,
+                        seq_num as u64,
+                        balance,
+                        expiration_timestamp_secs,
+                        &net,
+                    )
+                })
+                .collect()
+        };
+        let block = Genesis::build_genesis_block_with_txns(&net, txns)?;
+
+        config.association_key_pair.0 = None;
+        let net = ChainNetwork::new(net.id().clone(), config);
+        Ok((net, Genesis { block }))
+    }
+}
+
+/// The consensus parameters of `GenesisSpec`, the omitted ones inherit from the base network.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsensusSpec {
+    /// The PoW algorithm: dummy, argon, keccak or cryptonight.
+    pub strategy: Option<String>,
+    /// The block time target in milliseconds.
+    pub block_time_target: Option<u64>,
+    pub min_block_time_target: Option<u64>,
+    pub max_block_time_target: Option<u64>,
+    pub epoch_block_count: Option<u64>,
+    pub block_difficulty_window: Option<u64>,
+    /// The block reward in nanoSTC.
+    pub reward_per_block: Option<u64>,
+    pub reward_per_uncle_percent: Option<u64>,
+    pub uncle_rate_target: Option<u64>,
+    pub max_uncles_per_block: Option<u64>,
+    pub block_gas_limit: Option<u64>,
+}
+
+/// The on chain config values of `GenesisSpec`, the omitted ones inherit from the base network.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OnChainConfigSpec {
+    pub reward_delay: Option<u64>,
+    /// The STC amount in nanoSTC pre-mined to the association account.
+    pub pre_mine_amount: Option<u64>,
+    pub time_mint_amount: Option<u64>,
+    pub time_mint_period: Option<u64>,
+    /// The publishing option: locked, custom_scripts or open.
+    pub publishing_option: Option<String>,
+    pub transaction_timeout: Option<u64>,
+    pub voting_delay: Option<u64>,
+    pub voting_period: Option<u64>,
+    pub voting_quorum_rate: Option<u8>,
+    pub min_action_delay: Option<u64>,
+}
+
+/// The toml spec to generate a custom network genesis, such as:
+/// ```toml
+/// chain_name = "my_chain"
+/// chain_id = 123
+/// base = "halley"
+/// association_private_key = "0x..."
+///
+/// [consensus]
+/// strategy = "keccak"
+/// block_time_target = 5000
+///
+/// [on_chain_config]
+/// pre_mine_amount = 1000000000000000
+///
+/// [[accounts]]
+/// address = "0x..."
+/// balance = 1000000000000
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisSpec {
+    pub chain_name: String,
+    pub chain_id: u8,
+    /// The builtin network to inherit the genesis config, default is halley.
+    pub base: Option<String>,
+    /// The genesis timestamp in milliseconds, default is now.
+    pub timestamp: Option<u64>,
+    pub difficulty: Option<u64>,
+    /// The association private key in hex, required to fund the genesis accounts.
+    pub association_private_key: Option<String>,
+    #[serde(default)]
+    pub consensus: ConsensusSpec,
+    #[serde(default)]
+    pub on_chain_config: OnChainConfigSpec,
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+}
+
+impl GenesisSpec {
+    pub fn load<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn into_builder(self) -> Result<GenesisBuilder> {
+        let base = match self.base.as_ref() {
+            Some(base) => BuiltinNetworkID::from_str(base)?,
+            None => BuiltinNetworkID::Halley,
+        };
+        let mut builder = GenesisBuilder::new(self.chain_name, ChainId::new(self.chain_id), base);
+        if self.timestamp.is_some() || self.difficulty.is_some() {
+            let mut parameter = builder
+                .config()
+                .genesis_block_parameter()
+                .cloned()
+                .expect("Genesis block parameter is set by the builder.");
+            if let Some(timestamp) = self.timestamp {
+                parameter.timestamp = timestamp;
+            }
+            if let Some(difficulty) = self.difficulty {
+                parameter.difficulty = difficulty.into();
+            }
+            builder = builder.with_genesis_block_parameter(parameter);
+        }
+        if let Some(private_key) = self.association_private_key.as_ref() {
+            builder = builder.with_association_key(Ed25519PrivateKey::from_encoded_string(
+                private_key.strip_prefix("0x").unwrap_or(private_key),
+            )?)?;
+        }
+
+        let spec = self.consensus;
+        let mut consensus_config = builder.config().consensus_config.clone();
+        if let Some(strategy) = spec.strategy.as_ref() {
+            consensus_config.strategy = ConsensusStrategy::from_str(strategy)?.value();
+        }
+        let fields = vec![
+            (
+                spec.block_time_target,
+                &mut consensus_config.base_block_time_target,
+            ),
+            (
+                spec.min_block_time_target,
+                &mut consensus_config.min_block_time_target,
+            ),
+            (
+                spec.max_block_time_target,
+                &mut consensus_config.max_block_time_target,
+            ),
+            (
+                spec.epoch_block_count,
+                &mut consensus_config.epoch_block_count,
+            ),
+            (
+                spec.block_difficulty_window,
+                &mut consensus_config.base_block_difficulty_window,
+            ),
+            (
+                spec.reward_per_uncle_percent,
+                &mut consensus_config.base_reward_per_uncle_percent,
+            ),
+            (
+                spec.uncle_rate_target,
+                &mut consensus_config.uncle_rate_target,
+            ),
+            (
+                spec.max_uncles_per_block,
+                &mut consensus_config.base_max_uncles_per_block,
+            ),
+            (
+                spec.block_gas_limit,
+                &mut consensus_config.base_block_gas_limit,
+            ),
+        ];
+        for (value, field) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        if let Some(reward_per_block) = spec.reward_per_block {
+            consensus_config.base_reward_per_block = reward_per_block as u128;
+        }
+        builder = builder.with_consensus_config(consensus_config);
+
+        let spec = self.on_chain_config;
+        if let Some(reward_delay) = spec.reward_delay {
+            builder = builder.with_reward_delay(reward_delay);
+        }
+        if let Some(pre_mine_amount) = spec.pre_mine_amount {
+            builder = builder.with_pre_mine_amount(pre_mine_amount as u128);
+        }
+        if spec.time_mint_amount.is_some() || spec.time_mint_period.is_some() {
+            let amount = spec
+                .time_mint_amount
+                .map(|amount| amount as u128)
+                .unwrap_or(builder.config().time_mint_amount);
+            let period = spec
+                .time_mint_period
+                .unwrap_or(builder.config().time_mint_period);
+            builder = builder.with_time_mint(amount, period);
+        }
+        if let Some(publishing_option) = spec.publishing_option.as_ref() {
+            builder = builder.with_publishing_option(match publishing_option.as_str() {
+                "locked" => TransactionPublishOption::locked(),
+                "custom_scripts" => TransactionPublishOption::custom_scripts(),
+                "open" => TransactionPublishOption::open(),
+                other => return Err(format_err!("Unknown publishing option: {}", other)),
+            });
+        }
+        if let Some(transaction_timeout) = spec.transaction_timeout {
+            builder = builder.with_transaction_timeout(transaction_timeout);
+        }
+        let mut dao_config = builder.config().dao_config.clone();
+        if let Some(voting_delay) = spec.voting_delay {
+            dao_config.voting_delay = voting_delay;
+        }
+        if let Some(voting_period) = spec.voting_period {
+            dao_config.voting_period = voting_period;
+        }
+        if let Some(voting_quorum_rate) = spec.voting_quorum_rate {
+            dao_config.voting_quorum_rate = voting_quorum_rate;
+        }
+        if let Some(min_action_delay) = spec.min_action_delay {
+            dao_config.min_action_delay = min_action_delay;
+        }
+        builder = builder.with_dao_config(dao_config);
+
+        for account in self.accounts {
+            let auth_key = match account.auth_key.as_ref() {
+                Some(auth_key) => Some(AuthenticationKey::from_str(auth_key)?),
+                None => None,
+            };
+            builder = builder.add_account(
+                parse_address(account.address.as_str())?,
+                auth_key,
+                account.balance as u128,
+            );
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_state_api::AccountStateReader;
+    use starcoin_statedb::ChainStateDB;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_storage::{BlockStore, IntoSuper, Storage};
+
+    #[stest::test]
+    fn test_genesis_builder_with_accounts() -> Result<()> {
+        let spec: GenesisSpec = toml::from_str(
+            r#"
+            chain_name = "builder_test"
+            chain_id = 123
+            base = "dev"
+            association_private_key = "0x1b2d9b6fbd6d9e4d3b1cf4bc43ef1f8e0b3c9fd97bf3b1b4e5c5d0b6a6a1e2f3"
+
+            [consensus]
+            strategy = "keccak"
+            block_time_target = 5000
+
+            [[accounts]]
+            address = "0xd0c5a06ae6100ce115cad1600fe59e96"
+            balance = 1000000000
+            "#,
+        )?;
+        let (net, genesis) = spec.into_builder()?.build()?;
+        assert_eq!(net.chain_id(), ChainId::new(123));
+        assert!(net.genesis_config().association_key_pair.0.is_none());
+        assert_eq!(
+            net.genesis_config().consensus_config.strategy,
+            ConsensusStrategy::Keccak.value()
+        );
+        assert_eq!(genesis.block().transactions().len(), 2);
+
+        let temp_dir = starcoin_config::temp_path();
+        genesis.save(temp_dir.path())?;
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
+        let (chain_info, _) =
+            Genesis::init_and_check_storage(&net, storage.clone(), temp_dir.path())?;
+        let block = storage
+            .get_block(chain_info.status().head().id())?
+            .expect("Genesis block must exist.");
+        let state_db =
+            ChainStateDB::new(storage.into_super_arc(), Some(block.header().state_root()));
+        let balance = AccountStateReader::new(&state_db)
+            .get_balance(&parse_address("0xd0c5a06ae6100ce115cad1600fe59e96")?)?;
+        assert_eq!(balance, Some(1000000000));
+        Ok(())
+    }
+}
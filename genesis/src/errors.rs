@@ -13,4 +13,6 @@ pub enum GenesisError {
     GenesisLoadFailure(Error),
     #[error("Genesis block not exist in {0}.")]
     GenesisNotExist(String),
+    #[error("Custom genesis {0:?} mismatch the network {1}.")]
+    CustomGenesisMismatch(HashValue, String),
 }
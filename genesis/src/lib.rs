@@ -21,7 +21,7 @@ use starcoin_transaction_builder::{build_stdlib_package, StdLibOptions};
 use starcoin_types::startup_info::{ChainInfo, StartupInfo};
 use starcoin_types::transaction::TransactionInfo;
 use starcoin_types::{block::Block, transaction::Transaction};
-use starcoin_vm_types::account_config::CORE_CODE_ADDRESS;
+use starcoin_vm_types::account_config::{association_address, CORE_CODE_ADDRESS};
 use starcoin_vm_types::transaction::{
     RawUserTransaction, SignedUserTransaction, TransactionPayload,
 };
@@ -32,7 +32,9 @@ use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+mod builder;
 mod errors;
+pub use builder::{GenesisAccount, GenesisBuilder, GenesisSpec};
 pub use errors::GenesisError;
 
 pub static GENESIS_GENERATED_DIR: &str = "generated";
@@ -98,6 +100,14 @@ impl Genesis {
     }
 
     fn build_genesis_block(net: &ChainNetwork) -> Result<Block> {
+        Self::build_genesis_block_with_txns(net, vec![])
+    }
+
+    /// Build the genesis block with the `txns` executed after the genesis txn.
+    fn build_genesis_block_with_txns(
+        net: &ChainNetwork,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<Block> {
         let genesis_config = net.genesis_config();
         if let Some(GenesisBlockParameter {
             parent_hash,
@@ -111,22 +121,50 @@ impl Genesis {
             let chain_state_db = ChainStateDB::new(storage.clone(), None);
 
             let transaction_info = Self::execute_genesis_txn(&chain_state_db, txn.clone())?;
+            let mut txn_info_hashes = vec![transaction_info.id()];
+            let mut state_root = transaction_info.state_root_hash();
+            let mut gas_used = 0u64;
+            if !txns.is_empty() {
+                let executed_data = starcoin_executor::block_execute(
+                    &chain_state_db,
+                    txns.iter()
+                        .cloned()
+                        .map(Transaction::UserTransaction)
+                        .collect(),
+                    net.genesis_epoch().block_gas_limit(),
+                )?;
+                ensure!(
+                    executed_data.txn_infos.len() == txns.len(),
+                    "Some of the genesis txns are discarded, the block gas limit may be exceeded."
+                );
+                for txn_info in executed_data.txn_infos {
+                    ensure!(
+                        txn_info.status() == &KeptVMStatus::Executed,
+                        "Genesis txn {} execute fail for: {:?}",
+                        txn_info.transaction_hash(),
+                        txn_info.status()
+                    );
+                    gas_used += txn_info.gas_used();
+                    txn_info_hashes.push(txn_info.id());
+                }
+                state_root = executed_data.state_root;
+            }
 
             let accumulator = MerkleAccumulator::new_with_info(
                 AccumulatorInfo::default(),
                 storage.get_accumulator_store(AccumulatorStoreType::Transaction),
             );
-            let txn_info_hash = transaction_info.id();
-
-            let accumulator_root = accumulator.append(vec![txn_info_hash].as_slice())?;
+            let accumulator_root = accumulator.append(txn_info_hashes.as_slice())?;
             accumulator.flush()?;
-            Ok(Block::genesis_block(
+            Ok(Block::genesis_block_with_txns(
                 *parent_hash,
                 *timestamp,
                 accumulator_root,
-                transaction_info.state_root_hash(),
+                state_root,
+                gas_used,
                 *difficulty,
                 txn,
+                txns,
             ))
         } else {
             bail!("{}'s genesis config not ready to build genesis block", net);
@@ -248,15 +286,47 @@ impl Genesis {
         if !data_dir.exists() {
             create_dir_all(data_dir)?;
         }
-        let genesis_file = data_dir.join(Self::GENESIS_FILE_NAME);
-        let mut file = File::create(genesis_file)?;
+        self.save_to_file(data_dir.join(Self::GENESIS_FILE_NAME))
+    }
+
+    pub fn save_to_file<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut file = File::create(path)?;
         let contents = bcs_ext::to_bytes(self)?;
         file.write_all(&contents)?;
         Ok(())
     }
 
+    /// The genesis generated by `GenesisBuilder` with initial accounts can not be rebuilt from the genesis config,
+    /// so check the genesis txn and block parameters match the network, the state is checked by executing the block.
+    fn check_custom_genesis(&self, net: &ChainNetwork) -> Result<()> {
+        let header = self.block.header();
+        let parameter = net.genesis_block_parameter();
+        let txns = self.block.transactions();
+        let genesis_txn = Self::build_genesis_transaction(net)?;
+        let matched = header.chain_id() == net.chain_id()
+            && header.parent_hash() == parameter.parent_hash
+            && header.timestamp() == parameter.timestamp
+            && header.difficulty() == parameter.difficulty
+            && txns.first() == Some(&genesis_txn)
+            && txns
+                .iter()
+                .skip(1)
+                .all(|txn| txn.sender() == association_address());
+        if !matched {
+            return Err(GenesisError::CustomGenesisMismatch(header.id(), net.to_string()).into());
+        }
+        Ok(())
+    }
+
     fn load_and_check_genesis(net: &ChainNetwork, data_dir: &Path, init: bool) -> Result<Genesis> {
         let genesis = match Genesis::load_from_dir(data_dir) {
+            Ok(Some(genesis)) if net.is_custom() && genesis.block().transactions().len() > 1 => {
+                genesis.check_custom_genesis(net)?;
+                genesis
+            }
             Ok(Some(genesis)) => {
                 let expect_genesis = Genesis::load_or_build(net)?;
                 if genesis.block().header().id() != expect_genesis.block().header().id() {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::metrics::MINER_METRICS;
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use consensus::Consensus;
 use crypto::hash::HashValue;
 use futures::executor::block_on;
@@ -20,6 +20,7 @@ use starcoin_service_registry::{
 use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::transaction::SignedUserTransaction;
 use std::cmp::min;
 use std::{collections::HashMap, sync::Arc};
@@ -74,7 +75,8 @@ impl ServiceFactory<Self> for CreateBlockTemplateService {
             txpool,
             config.miner.block_gas_limit,
             miner_account,
-        )?;
+        )?
+        .with_reward_address(config.miner.reward_address());
         Ok(Self { inner })
     }
 }
@@ -134,7 +136,9 @@ impl ServiceHandler<Self, CreateBlockTemplateRequest> for CreateBlockTemplateSer
         _msg: CreateBlockTemplateRequest,
         _ctx: &mut ServiceContext<CreateBlockTemplateService>,
     ) -> Result<BlockTemplate> {
+        let timer = MINER_METRICS.block_template_build_time.start_timer();
         let template = self.inner.create_block_template();
+        timer.observe_duration();
         self.inner.uncles_prune();
         template
     }
@@ -183,6 +187,8 @@ pub struct Inner<P> {
     uncles: HashMap<HashValue, BlockHeader>,
     local_block_gas_limit: Option<u64>,
     miner_account: AccountInfo,
+    /// The author of the blocks instead of the miner account.
+    reward_address: Option<AccountAddress>,
 }
 
 impl<P> Inner<P>
@@ -207,9 +213,15 @@ where
             uncles: HashMap::new(),
             local_block_gas_limit,
             miner_account,
+            reward_address: None,
         })
     }
 
+    pub fn with_reward_address(mut self, reward_address: Option<AccountAddress>) -> Self {
+        self.reward_address = reward_address;
+        self
+    }
+
     pub fn insert_uncle(&mut self, uncle: BlockHeader) {
         self.parent_uncle
             .entry(uncle.parent_hash())
@@ -280,11 +292,25 @@ where
         let txns = self.tx_provider.get_txns(max_txns);
 
         let chain_state = self.chain.chain_state_reader();
-        let author = *self.miner_account.address();
-        let author_auth_key = if chain_state.exist_account(self.miner_account.address())? {
-            None
-        } else {
-            Some(self.miner_account.public_key.authentication_key())
+        let (author, author_auth_key) = match self.reward_address {
+            // the public key of the reward address is unknown, so it can not be created by the block.
+            Some(reward_address) => {
+                ensure!(
+                    chain_state.exist_account(&reward_address)?,
+                    "The miner reward address {} does not exist on chain",
+                    reward_address
+                );
+                (reward_address, None)
+            }
+            None => {
+                let author = *self.miner_account.address();
+                let author_auth_key = if chain_state.exist_account(&author)? {
+                    None
+                } else {
+                    Some(self.miner_account.public_key.authentication_key())
+                };
+                (author, author_auth_key)
+            }
         };
 
         let previous_header = self.chain.current_header();
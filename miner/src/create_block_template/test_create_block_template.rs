@@ -17,6 +17,8 @@ use starcoin_genesis::Genesis as StarcoinGenesis;
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 use starcoin_storage::BlockStore;
 use starcoin_txpool::TxPoolService;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::association_address;
 use starcoin_vm_types::time::MockTimeService;
 use std::sync::Arc;
 
@@ -56,6 +58,36 @@ fn test_create_block_template_by_net(net: ChainNetworkID) {
     assert_eq!(block_template.number, 1);
 }
 
+#[stest::test]
+fn test_create_block_template_with_reward_address() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, _, genesis) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let genesis_id = genesis.block().id();
+    let new_inner = |reward_address| {
+        Inner::new(
+            node_config.net(),
+            storage.clone(),
+            genesis_id,
+            EmptyProvider,
+            None,
+            AccountInfo::random(),
+        )
+        .unwrap()
+        .with_reward_address(reward_address)
+    };
+
+    let block_template = new_inner(Some(association_address()))
+        .create_block_template()
+        .unwrap();
+    assert_eq!(block_template.author, association_address());
+    assert!(block_template.author_auth_key.is_none());
+    // the reward address can not be created by the block without its public key.
+    assert!(new_inner(Some(AccountAddress::random()))
+        .create_block_template()
+        .is_err());
+}
+
 #[stest::test(timeout = 120)]
 fn test_switch_main() {
     let node_config = Arc::new(NodeConfig::random_for_test());
@@ -68,7 +68,8 @@ impl EventHandler<Self, NewHeadBlock> for GenerateBlockEventPacemaker {
         ctx: &mut ServiceContext<GenerateBlockEventPacemaker>,
     ) {
         if self.is_synced() {
-            self.send_event(true, ctx)
+            let force = self.config.miner.refresh_template_on_new_head();
+            self.send_event(force, ctx)
         } else {
             debug!("[pacemaker] Ignore NewHeadBlock event because the node has not been synchronized yet.")
         }
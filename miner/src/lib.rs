@@ -27,7 +27,8 @@ pub use starcoin_miner_client::miner::{MinerClient, MinerClientService};
 use std::fmt;
 use thiserror::Error;
 pub use types::block::BlockHeaderExtra;
-pub use types::system_events::{GenerateBlockEvent, MinedBlock, MintBlockEvent};
+use types::block::BlockTemplate;
+pub use types::system_events::{GenerateBlockEvent, MinedBlock, MiningWork, MintBlockEvent};
 
 #[derive(Debug, Error)]
 pub enum MinerError {
@@ -46,6 +47,33 @@ impl ServiceRequest for UpdateSubscriberNumRequest {
     type Response = Option<MintBlockEvent>;
 }
 
+/// Get the current mining work for a pool worker.
+#[derive(Debug)]
+pub struct GetWorkRequest {
+    pub worker_id: u32,
+}
+
+impl ServiceRequest for GetWorkRequest {
+    type Response = Result<Option<MiningWork>>;
+}
+
+/// Fire this event to rebuild the block template,
+/// the new template supersedes the current mining job if it is better, see `should_supersede`.
+#[derive(Clone, Debug)]
+pub struct RefreshTemplateEvent;
+
+/// A refreshed template supersedes the current one if it is built on another parent,
+/// or it adds at least `min_fee_delta` fee.
+fn should_supersede(current: &BlockTemplate, new: &BlockTemplate, min_fee_delta: u64) -> bool {
+    if new.parent_hash != current.parent_hash {
+        return true;
+    }
+    match new.total_fee.checked_sub(current.total_fee) {
+        Some(added_fee) => added_fee > 0 && added_fee >= u128::from(min_fee_delta),
+        None => false,
+    }
+}
+
 pub struct MinerService {
     config: Arc<NodeConfig>,
     current_task: Option<MintTask>,
@@ -105,6 +133,33 @@ impl ServiceHandler<Self, UpdateSubscriberNumRequest> for MinerService {
     }
 }
 
+impl ServiceHandler<Self, GetWorkRequest> for MinerService {
+    fn handle(
+        &mut self,
+        req: GetWorkRequest,
+        _ctx: &mut ServiceContext<MinerService>,
+    ) -> Result<Option<MiningWork>> {
+        let extranonce_size = self.config.miner.extranonce_size();
+        let task = match self.current_task.as_ref() {
+            Some(task) => task,
+            None => return Ok(None),
+        };
+        let extra = consensus::extra_with_extranonce(req.worker_id, 0, extranonce_size as usize)?;
+        Ok(Some(MiningWork {
+            job: MintBlockEvent {
+                parent_hash: task.block_template.parent_hash,
+                strategy: task.block_template.strategy,
+                minting_blob: task.minting_blob.clone(),
+                difficulty: task.block_template.difficulty,
+                block_number: task.block_template.number,
+            },
+            worker_id: req.worker_id,
+            extra,
+            extranonce_size,
+        }))
+    }
+}
+
 impl ServiceFactory<MinerService> for MinerService {
     fn create(ctx: &mut ServiceContext<MinerService>) -> Result<MinerService> {
         let config = ctx.get_shared::<Arc<NodeConfig>>()?;
@@ -122,6 +177,9 @@ impl ServiceFactory<MinerService> for MinerService {
 impl ActorService for MinerService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<GenerateBlockEvent>();
+        if let Some(interval) = self.config.miner.template_refresh_interval() {
+            ctx.run_interval(interval, |ctx| ctx.notify(RefreshTemplateEvent));
+        }
         Ok(())
     }
 
@@ -137,51 +195,103 @@ impl ServiceHandler<Self, SubmitSealRequest> for MinerService {
         req: SubmitSealRequest,
         ctx: &mut ServiceContext<MinerService>,
     ) -> Result<HashValue> {
-        self.finish_task(req.nonce, req.extra, req.minting_blob.clone(), ctx)
+        let strategy = self
+            .current_task
+            .as_ref()
+            .map(|task| task.block_template.strategy.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let result = self
+            .finish_task(req.nonce, req.extra, req.minting_blob.clone(), ctx)
             .map_err(|e| {
                 warn!(target: "miner", "process seal: {} failed: {}", req, e);
                 e
-            })
+            });
+        MINER_METRICS
+            .mine_accept_count
+            .with_label_values(&[
+                strategy.as_str(),
+                if result.is_ok() { "success" } else { "failure" },
+            ])
+            .inc();
+        result
     }
 }
 
 impl MinerService {
-    pub fn dispatch_task(&mut self, ctx: &mut ServiceContext<MinerService>) -> Result<()> {
+    fn create_block_template(&self) -> Result<BlockTemplate> {
         //create block template should block_on for avoid mint same block template.
-        let block_template = block_on(async {
+        block_on(async {
             self.create_block_template_service
                 .send(CreateBlockTemplateRequest)
                 .await?
-        })?;
+        })
+    }
+
+    pub fn dispatch_task(&mut self, ctx: &mut ServiceContext<MinerService>) -> Result<()> {
+        let block_template = self.create_block_template()?;
         if block_template.body.transactions.is_empty()
             && self.config.miner.is_disable_mint_empty_block()
         {
             debug!("The flag disable_mint_empty_block is true and no txn in pool, so skip mint empty block.");
-            Ok(())
         } else {
-            debug!("Mint block template: {:?}", block_template);
-            let difficulty = block_template.difficulty;
-            let strategy = block_template.strategy;
-            let number = block_template.number;
-            let parent_hash = block_template.parent_hash;
-            let task = MintTask::new(block_template);
-            let mining_blob = task.minting_blob.clone();
-            if let Some(current_task) = self.current_task.as_ref() {
-                debug!(
-                    "force set mint task, current_task: {:?}, new_task: {:?}",
-                    current_task, task
-                );
-            }
-            self.current_task = Some(task);
-            ctx.broadcast(MintBlockEvent::new(
-                parent_hash,
-                strategy,
-                mining_blob,
-                difficulty,
-                number,
-            ));
-            Ok(())
+            self.start_task(block_template, ctx);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the block template, and supersede the current mining job if the new template is better.
+    pub fn refresh_task(&mut self, ctx: &mut ServiceContext<MinerService>) -> Result<()> {
+        let block_template = self.create_block_template()?;
+        let supersede = match self.current_task.as_ref() {
+            Some(current_task) => should_supersede(
+                &current_task.block_template,
+                &block_template,
+                self.config.miner.template_min_fee_delta(),
+            ),
+            None => true,
+        };
+        if supersede {
+            MINER_METRICS
+                .template_refresh_count
+                .with_label_values(&["superseded"])
+                .inc();
+            self.start_task(block_template, ctx);
+        } else {
+            MINER_METRICS
+                .template_refresh_count
+                .with_label_values(&["skipped"])
+                .inc();
+            debug!("Refreshed block template does not add enough fee, keep the current mint task.");
+        }
+        Ok(())
+    }
+
+    fn start_task(
+        &mut self,
+        block_template: BlockTemplate,
+        ctx: &mut ServiceContext<MinerService>,
+    ) {
+        debug!("Mint block template: {:?}", block_template);
+        let difficulty = block_template.difficulty;
+        let strategy = block_template.strategy;
+        let number = block_template.number;
+        let parent_hash = block_template.parent_hash;
+        let task = MintTask::new(block_template);
+        let mining_blob = task.minting_blob.clone();
+        if let Some(current_task) = self.current_task.as_ref() {
+            debug!(
+                "force set mint task, current_task: {:?}, new_task: {:?}",
+                current_task, task
+            );
         }
+        self.current_task = Some(task);
+        ctx.broadcast(MintBlockEvent::new(
+            parent_hash,
+            strategy,
+            mining_blob,
+            difficulty,
+            number,
+        ));
     }
 
     pub fn finish_task(
@@ -229,6 +339,18 @@ impl MinerService {
     }
 }
 
+impl EventHandler<Self, RefreshTemplateEvent> for MinerService {
+    fn handle_event(&mut self, _event: RefreshTemplateEvent, ctx: &mut ServiceContext<Self>) {
+        // only refresh the current mint task, the new task is dispatched by GenerateBlockEvent.
+        if !self.is_minting() {
+            return;
+        }
+        if let Err(err) = self.refresh_task(ctx) {
+            warn!("Failed to refresh block template: {:?}", err);
+        }
+    }
+}
+
 impl EventHandler<Self, GenerateBlockEvent> for MinerService {
     fn handle_event(&mut self, event: GenerateBlockEvent, ctx: &mut ServiceContext<MinerService>) {
         debug!("Handle GenerateBlockEvent:{:?}", event);
@@ -251,3 +373,47 @@ impl EventHandler<Self, GenerateBlockEvent> for MinerService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::should_supersede;
+    use crypto::HashValue;
+    use starcoin_vm_types::genesis_config::ConsensusStrategy;
+    use types::block::{Block, BlockBody, BlockHeader, BlockTemplate};
+
+    /// The template with 1000 gas used by the txns of the gas price.
+    fn template_with_gas_price(template: &BlockTemplate, gas_price: u64) -> BlockTemplate {
+        let mut template = template.clone();
+        template.total_fee = 1000 * u128::from(gas_price);
+        template
+    }
+
+    #[stest::test]
+    fn test_should_supersede() {
+        let current = BlockTemplate::from_block(
+            Block::new(BlockHeader::random(), BlockBody::new_empty()),
+            ConsensusStrategy::Dummy,
+        );
+        let current = template_with_gas_price(&current, 2);
+
+        // equal gas price
+        let new = template_with_gas_price(&current, 2);
+        assert!(!should_supersede(&current, &new, 0));
+        assert!(!should_supersede(&current, &new, 1000));
+
+        // higher gas price
+        let new = template_with_gas_price(&current, 3);
+        assert!(should_supersede(&current, &new, 0));
+        assert!(should_supersede(&current, &new, 1000));
+        assert!(!should_supersede(&current, &new, 1001));
+
+        // lower gas price
+        let new = template_with_gas_price(&current, 1);
+        assert!(!should_supersede(&current, &new, 0));
+
+        // another parent supersedes regardless of the fee
+        let mut new = template_with_gas_price(&current, 1);
+        new.parent_hash = HashValue::random();
+        assert!(should_supersede(&current, &new, 1000));
+    }
+}
@@ -3,8 +3,8 @@
 
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    default_registry, register_histogram_vec, register_int_gauge, HistogramOpts, HistogramVec,
-    IntGauge, Opts, PrometheusError, UIntCounter,
+    default_registry, register_histogram, register_histogram_vec, register_int_gauge, Histogram,
+    HistogramOpts, HistogramVec, IntGauge, Opts, PrometheusError, UIntCounter, UIntCounterVec,
 };
 
 pub static MINER_METRICS: Lazy<MinerMetrics> = Lazy::new(|| MinerMetrics::register().unwrap());
@@ -14,6 +14,9 @@ pub struct MinerMetrics {
     pub block_mint_count: IntGauge,
     pub block_mint_time: HistogramVec,
     pub maybe_uncle_count: UIntCounter,
+    pub template_refresh_count: UIntCounterVec,
+    pub block_template_build_time: Histogram,
+    pub mine_accept_count: UIntCounterVec,
 }
 
 impl MinerMetrics {
@@ -31,11 +34,37 @@ impl MinerMetrics {
             "maybe uncle count".to_string(),
         )?;
         default_registry().register(Box::new(maybe_uncle_count.clone()))?;
+        let template_refresh_count = UIntCounterVec::new(
+            Opts::new(
+                "template_refresh_count",
+                "Count of periodic block template refresh, by superseded or skipped",
+            )
+            .namespace("starcoin"),
+            &["result"],
+        )?;
+        default_registry().register(Box::new(template_refresh_count.clone()))?;
+        let block_template_build_time = register_histogram!(HistogramOpts::new(
+            "block_template_build_time",
+            "Histogram of block template build time"
+        )
+        .namespace("starcoin"))?;
+        let mine_accept_count = UIntCounterVec::new(
+            Opts::new(
+                "mine_accept_count",
+                "Count of submitted seals, by consensus strategy and success or failure",
+            )
+            .namespace("starcoin"),
+            &["strategy", "result"],
+        )?;
+        default_registry().register(Box::new(mine_accept_count.clone()))?;
 
         Ok(Self {
             block_mint_count,
             block_mint_time,
             maybe_uncle_count,
+            template_refresh_count,
+            block_template_build_time,
+            mine_accept_count,
         })
     }
 }
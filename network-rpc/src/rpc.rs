@@ -54,6 +54,7 @@ impl NetworkRpcImpl {
 }
 
 impl gen_server::NetworkRpc for NetworkRpcImpl {
+    /// The local txns held for privacy are not served to the peers until they are propagated.
     fn get_txns_from_pool(
         &self,
         _peer_id: PeerId,
@@ -65,7 +66,13 @@ impl gen_server::NetworkRpc for NetworkRpcImpl {
         } else {
             MAX_TXN_REQUEST_SIZE
         };
-        let fut = async move { Ok(txpool.get_pending_txns(Some(max_size), None)) };
+        let fut = async move {
+            Ok(txpool
+                .get_pending_txns(Some(max_size), None)
+                .into_iter()
+                .filter(|txn| !txpool.is_propagation_held(&txn.id()))
+                .collect())
+        };
         Box::pin(fut)
     }
 
@@ -78,7 +85,11 @@ impl gen_server::NetworkRpc for NetworkRpcImpl {
         let fut = async move {
             let mut data = vec![];
             for id in req.ids {
-                data.push(txpool.find_txn(&id));
+                if txpool.is_propagation_held(&id) {
+                    data.push(None);
+                } else {
+                    data.push(txpool.find_txn(&id));
+                }
             }
             Ok(data)
         };
@@ -7,12 +7,13 @@ use futures::executor::block_on;
 use starcoin_logger::prelude::*;
 use starcoin_network_rpc_api::{
     gen_client as starcoin_gen_client, GetBlockHeadersByNumber, GetBlockIds, GetStateWithProof,
-    Ping,
+    GetTxnsWithHash, GetTxnsWithSize, Ping,
 };
 use starcoin_node::NodeHandle;
 use starcoin_state_api::StateWithProof;
 use starcoin_types::{access_path, account_config::genesis_address, block::BlockHeader};
 use std::sync::Arc;
+use txpool_api::TxPoolSyncService;
 use vm_types::move_resource::MoveResource;
 use vm_types::on_chain_resource::Epoch;
 
@@ -113,6 +114,66 @@ fn test_network_rpc() {
     handle1.stop().unwrap();
 }
 
+#[stest::test]
+fn test_network_rpc_held_txns() {
+    let (handle1, net_addr_1) = {
+        let config_1 = NodeConfig::random_for_test();
+        let net_addr = config_1.network.self_address();
+        (gen_chain_env(config_1).unwrap(), net_addr)
+    };
+    let (handle2, peer_id_2) = {
+        let mut config_2 = NodeConfig::random_for_test();
+        config_2.network.seeds = vec![net_addr_1].into();
+        config_2.tx_pool.set_propagation_delay(600);
+        let peer_id_2 = config_2.network.self_peer_id();
+        (gen_chain_env(config_2).unwrap(), peer_id_2)
+    };
+    let txpool_2 = handle2.txpool();
+    let mut txns: Vec<_> =
+        test_helper::txn::create_account_with_txpool(handle2.config().net(), &txpool_2, 2)
+            .into_iter()
+            .map(|(_, txn)| txn)
+            .collect();
+    let remote_txn = txns.pop().unwrap();
+    let local_txn = txns.pop().unwrap();
+    // the local txn is held by the propagation delay, the txn from the peers is not held.
+    assert!(txpool_2
+        .add_local_txns(vec![local_txn.clone()])
+        .pop()
+        .unwrap()
+        .is_ok());
+    assert!(txpool_2
+        .add_txns(vec![remote_txn.clone()])
+        .pop()
+        .unwrap()
+        .is_ok());
+    assert_eq!(txpool_2.get_pending_txns(None, None).len(), 2);
+
+    let client = starcoin_gen_client::NetworkRpcClient::new(handle1.network());
+    let txns = block_on(async {
+        client
+            .get_txns_from_pool(peer_id_2.clone(), GetTxnsWithSize { max_size: 10 })
+            .await
+            .unwrap()
+    });
+    assert_eq!(txns, vec![remote_txn.clone()]);
+    let txns = block_on(async {
+        client
+            .get_txns_with_hash_from_pool(
+                peer_id_2.clone(),
+                GetTxnsWithHash {
+                    ids: vec![local_txn.id(), remote_txn.id()],
+                },
+            )
+            .await
+            .unwrap()
+    });
+    assert_eq!(txns, vec![None, Some(remote_txn)]);
+
+    handle2.stop().unwrap();
+    handle1.stop().unwrap();
+}
+
 fn gen_chain_env(config: NodeConfig) -> Result<NodeHandle> {
     test_helper::run_node_by_config(Arc::new(config))
 }
@@ -6,19 +6,98 @@ use anyhow::*;
 use bcs_ext::{BCSCodec, Sample};
 use futures::channel::oneshot::Receiver;
 use serde::{Deserialize, Serialize};
-use starcoin_crypto::HashValue;
+use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
+use starcoin_crypto::hash::{CryptoHash, CryptoHasher};
+use starcoin_crypto::{HashValue, Signature, SigningKey};
 use starcoin_service_registry::ServiceRequest;
-use starcoin_types::block::BlockInfo;
+use starcoin_types::block::{BlockHeader, BlockInfo};
 use starcoin_types::cmpact_block::CompactBlock;
 use starcoin_types::peer_info::{PeerId, PeerInfo};
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::SignedUserTransaction;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 
 pub const TXN_PROTOCOL_NAME: &str = "/starcoin/txn/1";
 pub const BLOCK_PROTOCOL_NAME: &str = "/starcoin/block/1";
 pub const ANNOUNCEMENT_PROTOCOL_NAME: &str = "/starcoin/announcement/1";
+pub const CONSENSUS_HINT_PROTOCOL_NAME: &str = "/starcoin/consensus_hint/1";
+pub const IDENTITY_LINKAGE_PROTOCOL_NAME: &str = "/starcoin/identity_linkage/1";
+
+/// The identity linkage is announced and honored within this duration after the rotation.
+pub const IDENTITY_LINKAGE_TTL_MILLIS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// The gossip topics, a node only receives the gossip of the topics it subscribes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GossipTopic {
+    /// New blocks, every node must subscribe it.
+    Blocks,
+    /// New transactions and the transaction announcements.
+    Transactions,
+    /// The new head hints of the peers, without the block body.
+    ConsensusHints,
+}
+
+impl GossipTopic {
+    pub fn all() -> Vec<GossipTopic> {
+        vec![
+            GossipTopic::Blocks,
+            GossipTopic::Transactions,
+            GossipTopic::ConsensusHints,
+        ]
+    }
+
+    /// The notification protocols of the topic.
+    pub fn protocols(&self) -> Vec<&'static str> {
+        match self {
+            GossipTopic::Blocks => vec![BLOCK_PROTOCOL_NAME],
+            GossipTopic::Transactions => vec![TXN_PROTOCOL_NAME, ANNOUNCEMENT_PROTOCOL_NAME],
+            GossipTopic::ConsensusHints => vec![CONSENSUS_HINT_PROTOCOL_NAME],
+        }
+    }
+
+    pub fn of_protocol(protocol_name: &str) -> Option<GossipTopic> {
+        Self::all()
+            .into_iter()
+            .find(|topic| topic.protocols().contains(&protocol_name))
+    }
+
+    /// The topics subscribed by a peer, by the notification protocols it supports.
+    pub fn of_protocols(notif_protocols: &[Cow<'static, str>]) -> HashSet<GossipTopic> {
+        notif_protocols
+            .iter()
+            .filter_map(|protocol| Self::of_protocol(protocol.as_ref()))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for GossipTopic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GossipTopic::Blocks => write!(f, "blocks"),
+            GossipTopic::Transactions => write!(f, "transactions"),
+            GossipTopic::ConsensusHints => write!(f, "consensus_hints"),
+        }
+    }
+}
+
+impl std::str::FromStr for GossipTopic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "blocks" => Ok(GossipTopic::Blocks),
+            "transactions" => Ok(GossipTopic::Transactions),
+            "consensus_hints" => Ok(GossipTopic::ConsensusHints),
+            _ => bail!(
+                "Unknown gossip topic {}, expect blocks, transactions or consensus_hints",
+                s
+            ),
+        }
+    }
+}
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TransactionsMessage {
@@ -115,12 +194,92 @@ impl Announcement {
     }
 }
 
+/// Message of the new head of a peer, it is much smaller than the compact block,
+/// and is sent to the peers which are not selected to receive the compact block.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ConsensusHintMessage {
+    pub header: BlockHeader,
+    pub block_info: BlockInfo,
+}
+
+impl ConsensusHintMessage {
+    pub fn new(header: BlockHeader, block_info: BlockInfo) -> Self {
+        Self { header, block_info }
+    }
+}
+
+impl Sample for ConsensusHintMessage {
+    fn sample() -> Self {
+        Self::new(BlockHeader::sample(), BlockInfo::sample())
+    }
+}
+
+/// The statement that the node identity `old_public_key` is rotated to `new_public_key`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, CryptoHasher, CryptoHash)]
+pub struct IdentityLinkage {
+    pub old_public_key: Ed25519PublicKey,
+    pub new_public_key: Ed25519PublicKey,
+    /// Unix timestamp in milliseconds of the rotation.
+    pub timestamp: u64,
+}
+
+/// Message of the identity linkage signed by the old identity key, it is sent by the node with the new identity,
+/// so the peers carry over the reputation, the ban and the trust of the old identity to the new one.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct IdentityLinkageMessage {
+    pub linkage: IdentityLinkage,
+    pub signature: Ed25519Signature,
+}
+
+impl IdentityLinkageMessage {
+    pub fn new(
+        old_private_key: &Ed25519PrivateKey,
+        new_public_key: Ed25519PublicKey,
+        timestamp: u64,
+    ) -> Self {
+        let linkage = IdentityLinkage {
+            old_public_key: Ed25519PublicKey::from(old_private_key),
+            new_public_key,
+            timestamp,
+        };
+        let signature = old_private_key.sign(&linkage);
+        Self { linkage, signature }
+    }
+
+    pub fn old_peer_id(&self) -> PeerId {
+        PeerId::from_ed25519_public_key(self.linkage.old_public_key.clone())
+    }
+
+    pub fn new_peer_id(&self) -> PeerId {
+        PeerId::from_ed25519_public_key(self.linkage.new_public_key.clone())
+    }
+
+    /// Verify the linkage is signed by the old identity key.
+    pub fn verify(&self) -> Result<()> {
+        ensure!(
+            self.linkage.old_public_key != self.linkage.new_public_key,
+            "The identity linkage should link to a different identity"
+        );
+        self.signature
+            .verify(&self.linkage, &self.linkage.old_public_key)
+    }
+
+    pub fn is_expired(&self, now_millis: u64) -> bool {
+        self.linkage
+            .timestamp
+            .saturating_add(IDENTITY_LINKAGE_TTL_MILLIS)
+            < now_millis
+    }
+}
+
 /// Network notification protocol message, change this type, maybe break the network protocol compatibility.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum NotificationMessage {
     Transactions(TransactionsMessage),
     CompactBlock(Box<CompactBlockMessage>),
     Announcement(Announcement),
+    ConsensusHint(ConsensusHintMessage),
+    IdentityLinkage(IdentityLinkageMessage),
 }
 
 impl NotificationMessage {
@@ -135,6 +294,12 @@ impl NotificationMessage {
             ANNOUNCEMENT_PROTOCOL_NAME => {
                 NotificationMessage::Announcement(Announcement::decode(bytes)?)
             }
+            CONSENSUS_HINT_PROTOCOL_NAME => {
+                NotificationMessage::ConsensusHint(ConsensusHintMessage::decode(bytes)?)
+            }
+            IDENTITY_LINKAGE_PROTOCOL_NAME => {
+                NotificationMessage::IdentityLinkage(IdentityLinkageMessage::decode(bytes)?)
+            }
             unknown_protocol => bail!(
                 "Unknown protocol {}'s message: {}",
                 unknown_protocol,
@@ -150,6 +315,12 @@ impl NotificationMessage {
             NotificationMessage::Announcement(msg) => {
                 (ANNOUNCEMENT_PROTOCOL_NAME.into(), msg.encode()?)
             }
+            NotificationMessage::ConsensusHint(msg) => {
+                (CONSENSUS_HINT_PROTOCOL_NAME.into(), msg.encode()?)
+            }
+            NotificationMessage::IdentityLinkage(msg) => {
+                (IDENTITY_LINKAGE_PROTOCOL_NAME.into(), msg.encode()?)
+            }
         })
     }
 
@@ -158,6 +329,8 @@ impl NotificationMessage {
             Self::Transactions(_) => TXN_PROTOCOL_NAME.into(),
             Self::CompactBlock(_) => BLOCK_PROTOCOL_NAME.into(),
             Self::Announcement(_) => ANNOUNCEMENT_PROTOCOL_NAME.into(),
+            Self::ConsensusHint(_) => CONSENSUS_HINT_PROTOCOL_NAME.into(),
+            Self::IdentityLinkage(_) => IDENTITY_LINKAGE_PROTOCOL_NAME.into(),
         }
     }
 
@@ -167,6 +340,8 @@ impl NotificationMessage {
             BLOCK_PROTOCOL_NAME.into(),
             TXN_PROTOCOL_NAME.into(),
             ANNOUNCEMENT_PROTOCOL_NAME.into(),
+            CONSENSUS_HINT_PROTOCOL_NAME.into(),
+            IDENTITY_LINKAGE_PROTOCOL_NAME.into(),
         ]
     }
 
@@ -190,6 +365,13 @@ impl NotificationMessage {
             _ => None,
         }
     }
+
+    pub fn into_consensus_hint(self) -> Option<ConsensusHintMessage> {
+        match self {
+            NotificationMessage::ConsensusHint(message) => Some(message),
+            _ => None,
+        }
+    }
 }
 
 /// Message for send or receive from peer
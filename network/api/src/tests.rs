@@ -1,5 +1,10 @@
+use crate::messages::{
+    GossipTopic, IdentityLinkageMessage, NotificationMessage, BLOCK_PROTOCOL_NAME,
+    CONSENSUS_HINT_PROTOCOL_NAME, IDENTITY_LINKAGE_TTL_MILLIS, TXN_PROTOCOL_NAME,
+};
 use crate::peer_provider::{PeerSelector, PeerStrategy};
 use crate::peer_score::{InverseScore, Score};
+use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_types::peer_info::{PeerId, PeerInfo};
@@ -88,3 +93,56 @@ fn test_better_peer() {
             })
     }
 }
+
+#[test]
+fn test_identity_linkage() {
+    let (old_private_key, old_public_key) = KeyGen::from_os_rng().generate_keypair();
+    let (_, new_public_key) = KeyGen::from_os_rng().generate_keypair();
+    let linkage = IdentityLinkageMessage::new(&old_private_key, new_public_key.clone(), 1000);
+    assert!(linkage.verify().is_ok());
+    assert_eq!(
+        linkage.old_peer_id(),
+        PeerId::from_ed25519_public_key(old_public_key)
+    );
+    assert_eq!(
+        linkage.new_peer_id(),
+        PeerId::from_ed25519_public_key(new_public_key)
+    );
+    assert!(!linkage.is_expired(1000 + IDENTITY_LINKAGE_TTL_MILLIS));
+    assert!(linkage.is_expired(1001 + IDENTITY_LINKAGE_TTL_MILLIS));
+
+    let notification = NotificationMessage::IdentityLinkage(linkage.clone());
+    let (protocol, bytes) = notification.encode_notification().unwrap();
+    assert_eq!(
+        NotificationMessage::decode_notification(protocol.as_ref(), bytes.as_slice()).unwrap(),
+        notification
+    );
+
+    // the linkage signed by another key is rejected.
+    let (_, forged_public_key) = KeyGen::from_os_rng().generate_keypair();
+    let mut forged = linkage.clone();
+    forged.linkage.old_public_key = forged_public_key;
+    assert!(forged.verify().is_err());
+
+    let mut tampered = linkage;
+    tampered.linkage.timestamp = 2000;
+    assert!(tampered.verify().is_err());
+}
+
+#[test]
+fn test_gossip_topics_of_protocols() {
+    let topics = GossipTopic::of_protocols(&NotificationMessage::protocols());
+    assert_eq!(topics, GossipTopic::all().into_iter().collect());
+
+    let topics = GossipTopic::of_protocols(&[BLOCK_PROTOCOL_NAME.into(), TXN_PROTOCOL_NAME.into()]);
+    assert!(topics.contains(&GossipTopic::Blocks));
+    assert!(topics.contains(&GossipTopic::Transactions));
+    assert!(!topics.contains(&GossipTopic::ConsensusHints));
+
+    let topics = GossipTopic::of_protocols(&[CONSENSUS_HINT_PROTOCOL_NAME.into()]);
+    assert_eq!(
+        topics,
+        vec![GossipTopic::ConsensusHints].into_iter().collect()
+    );
+    assert!(GossipTopic::of_protocols(&[]).is_empty());
+}
@@ -5,6 +5,7 @@
 mod broadcast_score_metrics;
 pub mod helper;
 mod network_metrics;
+pub mod peer_control;
 mod service;
 pub mod service_ref;
 pub mod worker;
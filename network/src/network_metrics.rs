@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::Error as PrometheusError;
-use starcoin_metrics::register_histogram_vec;
 use starcoin_metrics::HistogramVec;
+use starcoin_metrics::{register_histogram_vec, register_int_counter_vec, IntCounterVec, Opts};
 
 #[derive(Clone)]
 pub struct NetworkMetrics {
     pub broadcast_duration: HistogramVec,
+    pub peer_events: IntCounterVec,
 }
 
 impl NetworkMetrics {
@@ -17,6 +18,17 @@ impl NetworkMetrics {
             "network broadcast message duration by protocol",
             &["notification_protocol"]
         )?;
-        Ok(Self { broadcast_duration })
+        let peer_events = register_int_counter_vec!(
+            Opts::new(
+                "network_peer_events",
+                "Counter of the peers connected and disconnected"
+            )
+            .namespace("starcoin"),
+            &["event"]
+        )?;
+        Ok(Self {
+            broadcast_duration,
+            peer_events,
+        })
     }
 }
@@ -0,0 +1,417 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::helper::get_unix_ts_as_millis;
+use anyhow::{ensure, Result};
+use network_api::messages::IdentityLinkageMessage;
+use serde::{Deserialize, Serialize};
+use starcoin_service_registry::ServiceRequest;
+use starcoin_types::peer_info::PeerId;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The max count of the honored identity rotations kept, the oldest one is dropped first.
+const MAX_IDENTITY_LINKS: usize = 1024;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BannedPeer {
+    pub peer_id: PeerId,
+    /// Unix timestamp in milliseconds when the ban expires.
+    pub banned_until: u64,
+}
+
+/// The old identity of a peer is rotated to the new identity.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IdentityLink {
+    pub old_peer_id: PeerId,
+    pub new_peer_id: PeerId,
+}
+
+/// The ban and the trusted entries carried over from the old identity of a peer to its new identity.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CarriedOver {
+    pub banned: Option<BannedPeer>,
+    /// The replaced trusted entries, in (old entry, new entry).
+    pub trusted: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+struct PeerControlData {
+    banned: Vec<BannedPeer>,
+    /// Trusted peers in format multiaddr/p2p/peer_id, always connected.
+    trusted: Vec<String>,
+    /// The honored identity rotations of the peers.
+    #[serde(default)]
+    linked: Vec<IdentityLink>,
+}
+
+/// Operator controlled ban list and trusted peers, persisted to a file so it survives restarts.
+pub struct PeerControlList {
+    path: PathBuf,
+    data: PeerControlData,
+    /// The carried over changes are not saved yet, see `flush`.
+    dirty: bool,
+}
+
+impl PeerControlList {
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        let data = if path.exists() {
+            serde_json::from_slice(&fs::read(path)?)?
+        } else {
+            PeerControlData::default()
+        };
+        let mut list = Self {
+            path: path.to_path_buf(),
+            data,
+            dirty: false,
+        };
+        list.remove_expired();
+        Ok(list)
+    }
+
+    fn save(&mut self) -> Result<()> {
+        fs::write(&self.path, serde_json::to_vec_pretty(&self.data)?)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Save the changes carried over by the identity linkages, the linkages are sent by the peers,
+    /// so the changes are saved in batch instead of on every linkage.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.dirty {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn remove_expired(&mut self) {
+        let now = get_unix_ts_as_millis() as u64;
+        self.data.banned.retain(|peer| peer.banned_until > now);
+    }
+
+    pub fn ban(&mut self, peer_id: PeerId, duration: Duration) -> Result<BannedPeer> {
+        self.remove_expired();
+        let banned_until =
+            (get_unix_ts_as_millis() as u64).saturating_add(duration.as_millis() as u64);
+        let banned_peer = BannedPeer {
+            peer_id,
+            banned_until,
+        };
+        self.data
+            .banned
+            .retain(|peer| peer.peer_id != banned_peer.peer_id);
+        self.data.banned.push(banned_peer.clone());
+        self.save()?;
+        Ok(banned_peer)
+    }
+
+    /// Return true if the peer was banned.
+    pub fn unban(&mut self, peer_id: &PeerId) -> Result<bool> {
+        let len = self.data.banned.len();
+        self.data.banned.retain(|peer| &peer.peer_id != peer_id);
+        let removed = self.data.banned.len() != len;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        let now = get_unix_ts_as_millis() as u64;
+        self.data
+            .banned
+            .iter()
+            .any(|peer| &peer.peer_id == peer_id && peer.banned_until > now)
+    }
+
+    pub fn banned_peers(&mut self) -> Vec<BannedPeer> {
+        self.remove_expired();
+        self.data.banned.clone()
+    }
+
+    pub fn trust(&mut self, peer: String) -> Result<()> {
+        if !self.data.trusted.contains(&peer) {
+            self.data.trusted.push(peer);
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Return true if the peer was trusted.
+    pub fn untrust(&mut self, peer: &str) -> Result<bool> {
+        let len = self.data.trusted.len();
+        self.data.trusted.retain(|trusted| trusted != peer);
+        let removed = self.data.trusted.len() != len;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    pub fn trusted_peers(&self) -> Vec<String> {
+        self.data.trusted.clone()
+    }
+
+    /// Carry over the ban and the trusted entries of the old identity to the new identity.
+    /// An old identity is only linked once, return None if the link is already honored.
+    /// The link is only recorded if something is carried over, and the change is saved by `flush`.
+    pub fn carry_over(
+        &mut self,
+        old_peer_id: &PeerId,
+        new_peer_id: &PeerId,
+    ) -> Result<Option<CarriedOver>> {
+        if let Some(link) = self
+            .data
+            .linked
+            .iter()
+            .find(|link| &link.old_peer_id == old_peer_id)
+        {
+            ensure!(
+                &link.new_peer_id == new_peer_id,
+                "The identity {} is already linked to {}",
+                old_peer_id,
+                link.new_peer_id
+            );
+            return Ok(None);
+        }
+        self.remove_expired();
+        let mut carried_over = CarriedOver::default();
+        let old_ban = self
+            .data
+            .banned
+            .iter()
+            .find(|peer| &peer.peer_id == old_peer_id)
+            .cloned();
+        if let Some(old_ban) = old_ban {
+            let banned_peer = BannedPeer {
+                peer_id: new_peer_id.clone(),
+                banned_until: old_ban.banned_until,
+            };
+            self.data.banned.retain(|peer| &peer.peer_id != new_peer_id);
+            self.data.banned.push(banned_peer.clone());
+            carried_over.banned = Some(banned_peer);
+        }
+        let old_suffix = format!("/p2p/{}", old_peer_id);
+        for trusted in self.data.trusted.iter_mut() {
+            if let Some(address) = trusted.strip_suffix(old_suffix.as_str()) {
+                let new_trusted = format!("{}/p2p/{}", address, new_peer_id);
+                carried_over
+                    .trusted
+                    .push((trusted.clone(), new_trusted.clone()));
+                *trusted = new_trusted;
+            }
+        }
+        if carried_over.banned.is_some() || !carried_over.trusted.is_empty() {
+            while self.data.linked.len() >= MAX_IDENTITY_LINKS {
+                self.data.linked.remove(0);
+            }
+            self.data.linked.push(IdentityLink {
+                old_peer_id: old_peer_id.clone(),
+                new_peer_id: new_peer_id.clone(),
+            });
+            self.dirty = true;
+        }
+        Ok(Some(carried_over))
+    }
+}
+
+/// The reputation change carried over from the old identity to the new identity,
+/// `old_reputation` is None if the old identity is unknown.
+/// Only the penalty is carried over, so linking a throwaway identity never raises the reputation.
+pub fn carried_reputation_change(old_reputation: Option<i32>, new_reputation: i32) -> i32 {
+    match old_reputation {
+        Some(old_reputation) if old_reputation < new_reputation => {
+            old_reputation.saturating_sub(new_reputation)
+        }
+        _ => 0,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BanPeer {
+    pub peer_id: PeerId,
+    pub duration: Duration,
+}
+
+impl ServiceRequest for BanPeer {
+    type Response = Result<BannedPeer>;
+}
+
+#[derive(Clone, Debug)]
+pub struct UnbanPeer {
+    pub peer_id: PeerId,
+}
+
+impl ServiceRequest for UnbanPeer {
+    type Response = Result<bool>;
+}
+
+#[derive(Clone, Debug)]
+pub struct GetBannedPeers;
+
+impl ServiceRequest for GetBannedPeers {
+    type Response = Vec<BannedPeer>;
+}
+
+#[derive(Clone, Debug)]
+pub struct TrustPeer {
+    /// format: multiaddr/p2p/peer_id
+    pub peer: String,
+}
+
+impl ServiceRequest for TrustPeer {
+    type Response = Result<()>;
+}
+
+#[derive(Clone, Debug)]
+pub struct UntrustPeer {
+    pub peer: String,
+}
+
+impl ServiceRequest for UntrustPeer {
+    type Response = Result<bool>;
+}
+
+#[derive(Clone, Debug)]
+pub struct GetTrustedPeers;
+
+impl ServiceRequest for GetTrustedPeers {
+    type Response = Vec<String>;
+}
+
+/// Rotate the network identity key of the node, the new identity takes effect after the node restarts.
+#[derive(Clone, Debug)]
+pub struct RotateIdentity;
+
+impl ServiceRequest for RotateIdentity {
+    type Response = Result<IdentityLinkageMessage>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_control_list_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+        let peer_id = PeerId::random();
+        let expired_peer_id = PeerId::random();
+        {
+            let mut list = PeerControlList::load_or_create(path.as_path()).unwrap();
+            list.ban(peer_id.clone(), Duration::from_secs(3600))
+                .unwrap();
+            list.ban(expired_peer_id.clone(), Duration::from_secs(0))
+                .unwrap();
+            list.trust(format!("/ip4/127.0.0.1/tcp/9840/p2p/{}", PeerId::random()))
+                .unwrap();
+        }
+        let mut list = PeerControlList::load_or_create(path.as_path()).unwrap();
+        assert!(list.is_banned(&peer_id));
+        assert!(!list.is_banned(&expired_peer_id));
+        assert_eq!(list.banned_peers().len(), 1);
+        assert_eq!(list.trusted_peers().len(), 1);
+        assert!(list.unban(&peer_id).unwrap());
+        assert!(!list.is_banned(&peer_id));
+    }
+
+    #[test]
+    fn test_peer_control_carry_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+        let banned_peer_id = PeerId::random();
+        let trusted_peer_id = PeerId::random();
+        let new_peer_id = PeerId::random();
+        let mut list = PeerControlList::load_or_create(path.as_path()).unwrap();
+        let banned = list
+            .ban(banned_peer_id.clone(), Duration::from_secs(3600))
+            .unwrap();
+        list.trust(format!("/ip4/127.0.0.1/tcp/9840/p2p/{}", trusted_peer_id))
+            .unwrap();
+
+        let carried_over = list
+            .carry_over(&banned_peer_id, &new_peer_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            carried_over.banned.map(|peer| peer.banned_until),
+            Some(banned.banned_until)
+        );
+        assert!(list.is_banned(&new_peer_id));
+        // the link is honored once.
+        assert!(list
+            .carry_over(&banned_peer_id, &new_peer_id)
+            .unwrap()
+            .is_none());
+        assert!(list.carry_over(&banned_peer_id, &PeerId::random()).is_err());
+
+        let new_trusted_peer_id = PeerId::random();
+        let carried_over = list
+            .carry_over(&trusted_peer_id, &new_trusted_peer_id)
+            .unwrap()
+            .unwrap();
+        assert!(carried_over.banned.is_none());
+        let new_trusted = format!("/ip4/127.0.0.1/tcp/9840/p2p/{}", new_trusted_peer_id);
+        assert_eq!(carried_over.trusted.len(), 1);
+        assert_eq!(carried_over.trusted[0].1, new_trusted);
+
+        // the carried over changes are saved by flush.
+        let saved = PeerControlList::load_or_create(path.as_path()).unwrap();
+        assert!(!saved.is_banned(&new_peer_id));
+        list.flush().unwrap();
+        let list = PeerControlList::load_or_create(path.as_path()).unwrap();
+        assert_eq!(list.trusted_peers(), vec![new_trusted]);
+        assert!(list.is_banned(&new_peer_id));
+    }
+
+    #[test]
+    fn test_peer_control_link_nothing_carried() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+        let mut list = PeerControlList::load_or_create(path.as_path()).unwrap();
+        for _ in 0..10 {
+            let carried_over = list
+                .carry_over(&PeerId::random(), &PeerId::random())
+                .unwrap()
+                .unwrap();
+            assert_eq!(carried_over, CarriedOver::default());
+        }
+        // the links without anything carried over are not recorded or saved.
+        assert!(list.data.linked.is_empty());
+        assert!(!list.dirty);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_peer_control_links_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+        let mut list = PeerControlList::load_or_create(path.as_path()).unwrap();
+        for _ in 0..MAX_IDENTITY_LINKS.saturating_add(10) {
+            let old_peer_id = PeerId::random();
+            list.ban(old_peer_id.clone(), Duration::from_secs(3600))
+                .unwrap();
+            list.carry_over(&old_peer_id, &PeerId::random())
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(list.data.linked.len(), MAX_IDENTITY_LINKS);
+    }
+
+    #[test]
+    fn test_carried_reputation_change() {
+        // the throwaway old identity is unknown.
+        assert_eq!(carried_reputation_change(None, -100), 0);
+        // the penalty is carried over.
+        assert_eq!(carried_reputation_change(Some(-100), 0), -100);
+        assert_eq!(carried_reputation_change(Some(-100), -40), -60);
+        // the reputation is never raised.
+        assert_eq!(carried_reputation_change(Some(0), -100), 0);
+        assert_eq!(carried_reputation_change(Some(100), 0), 0);
+        assert_eq!(
+            carried_reputation_change(Some(i32::MIN), i32::MAX),
+            i32::MIN
+        );
+    }
+}
@@ -2,21 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::broadcast_score_metrics::BROADCAST_SCORE_METRICS;
+use crate::helper::get_unix_ts_as_millis;
 use crate::network_metrics::NetworkMetrics;
+use crate::peer_control::{
+    carried_reputation_change, BanPeer, GetBannedPeers, GetTrustedPeers, PeerControlList,
+    RotateIdentity, TrustPeer, UnbanPeer, UntrustPeer,
+};
 use crate::{build_network_worker, Announcement};
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use bytes::Bytes;
 use futures::future::{abortable, AbortHandle};
 use futures::FutureExt;
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
 use lru::LruCache;
 use network_api::messages::{
-    AnnouncementType, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerEvent,
-    PeerMessage, PeerReputations, ReportReputation, TransactionsMessage,
+    AnnouncementType, ConsensusHintMessage, GetPeerById, GetPeerSet, GetSelfPeer, GossipTopic,
+    IdentityLinkageMessage, NotificationMessage, PeerEvent, PeerMessage, PeerReputations,
+    ReportReputation, TransactionsMessage, IDENTITY_LINKAGE_PROTOCOL_NAME,
 };
 use network_api::peer_score::{BlockBroadcastEntry, HandleState, LinearScore, Score};
 use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler};
 use network_p2p::{Event, NetworkWorker};
+use network_p2p_types::ReputationChange;
 use rand::prelude::SliceRandom;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
@@ -29,10 +36,18 @@ use starcoin_types::peer_info::{PeerId, PeerInfo, RpcInfo};
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::SyncStatusChangeEvent;
+use starcoin_types::transaction::SignedUserTransaction;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+const PEER_CONTROL_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct FlushPeerControlEvent;
 
 pub struct NetworkActorService {
     worker: Option<NetworkWorker>,
@@ -78,6 +93,14 @@ impl ActorService for NetworkActorService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<SyncStatusChangeEvent>();
         ctx.subscribe::<PropagateTransactions>();
+        ctx.run_interval(PEER_CONTROL_FLUSH_INTERVAL, |ctx| {
+            ctx.notify(FlushPeerControlEvent)
+        });
+        for peer in self.inner.peer_control.trusted_peers() {
+            if let Err(e) = self.inner.network_service.add_reserved_peer(peer.clone()) {
+                warn!("Add trusted peer {} error: {:?}", peer, e);
+            }
+        }
         let worker = self
             .worker
             .take()
@@ -102,7 +125,15 @@ impl ActorService for NetworkActorService {
         if let Some(abort_handle) = self.network_worker_handle.take() {
             abort_handle.abort();
         }
-        Ok(())
+        self.inner.peer_control.flush()
+    }
+}
+
+impl EventHandler<Self, FlushPeerControlEvent> for NetworkActorService {
+    fn handle_event(&mut self, _msg: FlushPeerControlEvent, _ctx: &mut ServiceContext<Self>) {
+        if let Err(e) = self.inner.peer_control.flush() {
+            warn!("Flush peer control list error: {:?}", e);
+        }
     }
 }
 
@@ -131,9 +162,18 @@ impl EventHandler<Self, Event> for NetworkActorService {
                     "Connected peer {:?}, protocol: {}, notif_protocols: {:?}, rpc_protocols: {:?}",
                     remote, protocol, notif_protocols, rpc_protocols
                 );
+                let peer_id: PeerId = remote.clone().into();
+                if self.inner.peer_control.is_banned(&peer_id) {
+                    info!("Reject banned peer {:?}", peer_id);
+                    self.inner.disconnect_peer(peer_id);
+                    return;
+                }
                 let peer_event = PeerEvent::Open(remote.clone().into(), info.clone());
                 self.inner
                     .on_peer_connected(remote.into(), *info, notif_protocols, rpc_protocols);
+                if protocol.as_ref() == IDENTITY_LINKAGE_PROTOCOL_NAME {
+                    self.inner.send_identity_linkage(peer_id);
+                }
                 ctx.broadcast(peer_event);
             }
             Event::NotificationStreamClosed { remote, .. } => {
@@ -144,6 +184,17 @@ impl EventHandler<Self, Event> for NetworkActorService {
             }
             Event::NotificationsReceived { remote, messages } => {
                 for (protocol, message) in messages {
+                    if protocol.as_ref() == IDENTITY_LINKAGE_PROTOCOL_NAME {
+                        if let Err(e) =
+                            self.on_identity_linkage(remote.clone().into(), message, ctx)
+                        {
+                            warn!(
+                                "Handle identity linkage fail, remote:{}, error: {:?}",
+                                remote, e
+                            )
+                        }
+                        continue;
+                    }
                     if let Err(e) = self.inner.handle_network_message(
                         remote.clone().into(),
                         protocol.clone(),
@@ -160,6 +211,96 @@ impl EventHandler<Self, Event> for NetworkActorService {
     }
 }
 
+impl NetworkActorService {
+    /// Carry over the reputation, ban and trust of the old identity to the new identity of the peer.
+    fn on_identity_linkage(
+        &mut self,
+        peer_id: PeerId,
+        message: Bytes,
+        ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> Result<()> {
+        let linkage = IdentityLinkageMessage::decode(message.as_ref())?;
+        linkage.verify()?;
+        let old_peer_id = linkage.old_peer_id();
+        let new_peer_id = linkage.new_peer_id();
+        ensure!(
+            new_peer_id == peer_id,
+            "The identity linkage to {} is sent by peer {}",
+            new_peer_id,
+            peer_id
+        );
+        ensure!(
+            !linkage.is_expired(get_unix_ts_as_millis() as u64),
+            "The identity linkage from {} to {} is expired",
+            old_peer_id,
+            new_peer_id
+        );
+        let carried_over = match self
+            .inner
+            .peer_control
+            .carry_over(&old_peer_id, &new_peer_id)?
+        {
+            Some(carried_over) => carried_over,
+            None => return Ok(()),
+        };
+        info!(
+            "Peer {} rotated its identity to {}, carry over the penalty",
+            old_peer_id, new_peer_id
+        );
+        for (old_trusted, new_trusted) in carried_over.trusted {
+            self.inner
+                .network_service
+                .remove_reserved_peer(old_peer_id.clone().into());
+            if let Err(e) = self
+                .inner
+                .network_service
+                .add_reserved_peer(new_trusted.clone())
+            {
+                warn!(
+                    "Replace trusted peer {} with {} error: {:?}",
+                    old_trusted, new_trusted, e
+                );
+            }
+        }
+        if carried_over.banned.is_some() {
+            info!("Reject banned peer {:?}", new_peer_id);
+            self.inner.network_service.report_peer(
+                new_peer_id.clone().into(),
+                ReputationChange::new_fatal("Banned by operator"),
+            );
+            self.inner.disconnect_peer(new_peer_id);
+            return Ok(());
+        }
+        let network_service = self.inner.network_service.clone();
+        let rx = network_service.reputations(i32::MIN);
+        ctx.spawn(async move {
+            let reputations = match rx.await {
+                Ok(reputations) => reputations,
+                Err(e) => {
+                    debug!("Get peer reputations error: {}", e);
+                    return;
+                }
+            };
+            let reputation_of = |peer_id: &PeerId| {
+                reputations
+                    .iter()
+                    .find(|(id, _)| &PeerId::new(id.clone()) == peer_id)
+                    .map(|(_, reputation)| *reputation)
+            };
+            let old_reputation = reputation_of(&old_peer_id);
+            let new_reputation = reputation_of(&new_peer_id).unwrap_or_default();
+            let change = carried_reputation_change(old_reputation, new_reputation);
+            if change != 0 {
+                network_service.report_peer(
+                    new_peer_id.into(),
+                    ReputationChange::new(change, "Identity rotation"),
+                );
+            }
+        });
+        Ok(())
+    }
+}
+
 impl EventHandler<Self, ReportReputation> for NetworkActorService {
     fn handle_event(
         &mut self,
@@ -199,11 +340,23 @@ impl EventHandler<Self, PropagateTransactions> for NetworkActorService {
         if txns.is_empty() {
             return;
         }
-        debug!("prepare to propagate txns, len: {}", txns.len());
-        self.inner
-            .broadcast(NotificationMessage::Transactions(TransactionsMessage::new(
-                txns,
-            )));
+        match msg.relay_peers() {
+            Some(relay_peers) => {
+                debug!(
+                    "prepare to relay txns to {} peers, len: {}",
+                    relay_peers,
+                    txns.len()
+                );
+                self.inner.relay_transactions(txns, relay_peers);
+            }
+            None => {
+                debug!("prepare to propagate txns, len: {}", txns.len());
+                self.inner
+                    .broadcast(NotificationMessage::Transactions(TransactionsMessage::new(
+                        txns,
+                    )));
+            }
+        }
     }
 }
 
@@ -267,6 +420,101 @@ impl ServiceHandler<Self, GetSelfPeer> for NetworkActorService {
     }
 }
 
+impl ServiceHandler<Self, BanPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: BanPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <BanPeer as ServiceRequest>::Response {
+        let banned_peer = self
+            .inner
+            .peer_control
+            .ban(msg.peer_id.clone(), msg.duration)?;
+        self.inner.network_service.report_peer(
+            msg.peer_id.clone().into(),
+            ReputationChange::new_fatal("Banned by operator"),
+        );
+        self.inner.disconnect_peer(msg.peer_id);
+        Ok(banned_peer)
+    }
+}
+
+impl ServiceHandler<Self, UnbanPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: UnbanPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <UnbanPeer as ServiceRequest>::Response {
+        self.inner.peer_control.unban(&msg.peer_id)
+    }
+}
+
+impl ServiceHandler<Self, GetBannedPeers> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: GetBannedPeers,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <GetBannedPeers as ServiceRequest>::Response {
+        self.inner.peer_control.banned_peers()
+    }
+}
+
+impl ServiceHandler<Self, TrustPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: TrustPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <TrustPeer as ServiceRequest>::Response {
+        self.inner
+            .network_service
+            .add_reserved_peer(msg.peer.clone())
+            .map_err(|e| format_err!("{:?}", e))?;
+        self.inner.peer_control.trust(msg.peer)
+    }
+}
+
+impl ServiceHandler<Self, UntrustPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: UntrustPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <UntrustPeer as ServiceRequest>::Response {
+        let removed = self.inner.peer_control.untrust(msg.peer.as_str())?;
+        if removed {
+            if let Some(peer_id) = msg.peer.rsplit('/').next() {
+                let peer_id = PeerId::from_str(peer_id)?;
+                self.inner
+                    .network_service
+                    .remove_reserved_peer(peer_id.into());
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl ServiceHandler<Self, RotateIdentity> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: RotateIdentity,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <RotateIdentity as ServiceRequest>::Response {
+        self.inner
+            .config
+            .network
+            .rotate_network_key(get_unix_ts_as_millis() as u64)
+    }
+}
+
+impl ServiceHandler<Self, GetTrustedPeers> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: GetTrustedPeers,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <GetTrustedPeers as ServiceRequest>::Response {
+        self.inner.peer_control.trusted_peers()
+    }
+}
+
 // max peers is 100(in: 25 + out:75), so blocks lru + txn lru max memory usage about is:
 // (100 +1 ) * ( LRU_CACHE_SIZE * 32) *2 = 64M
 const LRU_CACHE_SIZE: usize = 10240;
@@ -277,11 +525,14 @@ pub struct Peer {
     known_transactions: LruCache<HashValue, ()>,
     /// Holds a set of blocks known to this peer.
     known_blocks: LruCache<HashValue, ()>,
+    /// The gossip topics subscribed by this peer.
+    gossip_topics: HashSet<GossipTopic>,
 }
 
 impl Peer {
     fn new(peer_info: PeerInfo) -> Self {
         Self {
+            gossip_topics: GossipTopic::of_protocols(&peer_info.notif_protocols),
             peer_info,
             known_blocks: LruCache::new(LRU_CACHE_SIZE),
             known_transactions: LruCache::new(LRU_CACHE_SIZE),
@@ -301,6 +552,11 @@ pub(crate) struct Inner {
     peer_message_handler: Arc<dyn PeerMessageHandler>,
     metrics: Option<NetworkMetrics>,
     score_handler: Arc<dyn Score<BlockBroadcastEntry> + 'static>,
+    peer_control: PeerControlList,
+    /// The linkage from the previous identity of this node, sent to the peers after rotation.
+    identity_linkage: Option<IdentityLinkageMessage>,
+    /// The gossip topics subscribed by this node, the messages of the other topics are ignored.
+    gossip_topics: HashSet<GossipTopic>,
 }
 
 impl BroadcastProtocolFilter for Inner {
@@ -329,6 +585,17 @@ impl Inner {
         H: PeerMessageHandler + 'static,
     {
         let metrics = NetworkMetrics::register().ok();
+        let peer_control =
+            PeerControlList::load_or_create(config.network.peer_control_file().as_path())?;
+        let self_peer_id = self_info.peer_id();
+        let gossip_topics = config.network.gossip_topics().into_iter().collect();
+        let identity_linkage = match config.network.load_identity_linkage() {
+            Ok(linkage) => linkage.filter(|linkage| linkage.new_peer_id() == self_peer_id),
+            Err(e) => {
+                warn!("Load identity linkage error: {:?}", e);
+                None
+            }
+        };
 
         Ok(Inner {
             config,
@@ -338,6 +605,9 @@ impl Inner {
             peer_message_handler: Arc::new(peer_message_handler),
             metrics,
             score_handler: Arc::new(LinearScore::new(10)),
+            peer_control,
+            identity_linkage,
+            gossip_topics,
         })
     }
 
@@ -355,6 +625,15 @@ impl Inner {
         protocol: Cow<'static, str>,
         message: Bytes,
     ) -> Result<()> {
+        if let Some(topic) = GossipTopic::of_protocol(protocol.as_ref()) {
+            if !self.gossip_topics.contains(&topic) {
+                debug!(
+                    "Receive message of unsubscribed topic {} from peer: {}, ignore.",
+                    topic, peer_id
+                );
+                return Ok(());
+            }
+        }
         if let Some(peer_info) = self.peers.get_mut(&peer_id) {
             let notification =
                 NotificationMessage::decode_notification(protocol.as_ref(), message.as_ref())?;
@@ -438,6 +717,32 @@ impl Inner {
                         None
                     }
                 }
+                NotificationMessage::ConsensusHint(hint) => {
+                    let block_id = hint.header.id();
+                    debug!(
+                        "Receive consensus hint from {:?} with hash {:?}",
+                        peer_id, block_id
+                    );
+                    peer_info.known_blocks.put(block_id, ());
+                    if hint.block_info.total_difficulty
+                        > peer_info
+                            .peer_info
+                            .chain_info
+                            .status()
+                            .info
+                            .total_difficulty
+                    {
+                        peer_info.peer_info.update_chain_status(ChainStatus::new(
+                            hint.header.clone(),
+                            hint.block_info.clone(),
+                        ));
+                    }
+                    if self.self_peer.known_blocks.contains(&block_id) {
+                        None
+                    } else {
+                        Some(notification)
+                    }
+                }
             };
 
             if let Some(notification) = notification {
@@ -476,6 +781,7 @@ impl Inner {
         notif_protocols: Vec<Cow<'static, str>>,
         rpc_protocols: Vec<Cow<'static, str>>,
     ) {
+        let is_new_peer = !self.peers.contains_key(&peer_id);
         self.peers
             .entry(peer_id.clone())
             .and_modify(|peer| {
@@ -497,12 +803,41 @@ impl Inner {
                     rpc_protocols,
                 ))
             });
+        if is_new_peer {
+            self.inc_peer_event("connected");
+        }
     }
 
     pub(crate) fn on_peer_disconnected(&mut self, peer_id: PeerId) {
+        if self.peers.remove(&peer_id).is_some() {
+            self.inc_peer_event("disconnected");
+        }
+    }
+
+    fn inc_peer_event(&self, event: &str) {
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.peer_events.with_label_values(&[event]).inc();
+        }
+    }
+
+    pub(crate) fn disconnect_peer(&mut self, peer_id: PeerId) {
+        for protocol in self.config.network.supported_network_protocols() {
+            self.network_service
+                .disconnect_peer(peer_id.clone().into(), protocol);
+        }
         self.peers.remove(&peer_id);
     }
 
+    pub(crate) fn send_identity_linkage(&mut self, peer_id: PeerId) {
+        if let Some(linkage) = self.identity_linkage.as_ref() {
+            if linkage.is_expired(get_unix_ts_as_millis() as u64) {
+                return;
+            }
+            let notification = NotificationMessage::IdentityLinkage(linkage.clone());
+            self.send_peer_message(peer_id, notification);
+        }
+    }
+
     pub(crate) fn send_peer_message(&mut self, peer_id: PeerId, notification: NotificationMessage) {
         let (protocol_name, data) = notification
             .encode_notification()
@@ -532,6 +867,8 @@ impl Inner {
                     })
                 }
             }
+            NotificationMessage::ConsensusHint(_) => {}
+            NotificationMessage::IdentityLinkage(_) => {}
         };
         self.network_service
             .write_notification(peer_id.into(), protocol_name, data);
@@ -598,19 +935,48 @@ impl Inner {
                     filtered_peer_ids.iter(),
                 );
                 let peers_send_message = selected_peers.len();
-                for peer_id in selected_peers {
-                    let peer = self.peers.get_mut(&peer_id).expect("peer should exists");
+                for peer_id in selected_peers.iter() {
+                    let peer = self.peers.get_mut(peer_id).expect("peer should exists");
                     peer.known_blocks.put(id, ());
 
                     self.network_service.write_notification(
-                        peer_id.into(),
+                        peer_id.clone().into(),
                         protocol_name.clone(),
                         message.clone(),
                     )
                 }
+                // the peers which are not selected get the consensus hint only, they can fetch the block when they need it.
+                let (hint_protocol_name, hint_message) =
+                    NotificationMessage::ConsensusHint(ConsensusHintMessage::new(
+                        msg.compact_block.header.clone(),
+                        msg.block_info.clone(),
+                    ))
+                    .encode_notification()
+                    .expect("Encode notification ConsensusHint message should ok");
+                // only the peers subscribed the consensus hints topic get the hint.
+                let hint_peer_ids = filtered_peer_ids
+                    .into_iter()
+                    .filter(|peer_id| {
+                        !selected_peers.contains(peer_id)
+                            && self.peers.get(peer_id).map_or(false, |peer| {
+                                peer.gossip_topics.contains(&GossipTopic::ConsensusHints)
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                let peers_send_hint = hint_peer_ids.len();
+                for peer_id in hint_peer_ids {
+                    let peer = self.peers.get_mut(&peer_id).expect("peer should exists");
+                    peer.known_blocks.put(id, ());
+
+                    self.network_service.write_notification(
+                        peer_id.into(),
+                        hint_protocol_name.clone(),
+                        hint_message.clone(),
+                    )
+                }
                 debug!(
-                    "[network] broadcast new compact block message {:?} to {} peers, total_peers: {}, peers_after_known_hash_filter: {}, peers_after_protocol_filter: {}",
-                    id, peers_send_message, peers_len, peers_after_known_hash_filter, peers_after_protocol_filter
+                    "[network] broadcast new compact block message {:?} to {} peers, consensus hint to {} peers, total_peers: {}, peers_after_known_hash_filter: {}, peers_after_protocol_filter: {}",
+                    id, peers_send_message, peers_send_hint, peers_len, peers_after_known_hash_filter, peers_after_protocol_filter
                 );
             }
             NotificationMessage::Transactions(msg) => {
@@ -702,8 +1068,56 @@ impl Inner {
             NotificationMessage::Announcement(_msg) => {
                 error!("[network] can not broadcast announcement message directly.");
             }
+            NotificationMessage::ConsensusHint(_msg) => {
+                error!("[network] can not broadcast consensus hint message directly.");
+            }
+            NotificationMessage::IdentityLinkage(_msg) => {
+                error!("[network] can not broadcast identity linkage message directly.");
+            }
         }
     }
+
+    /// Send the txns to the number of random peers only, the peers propagate them as usual.
+    pub(crate) fn relay_transactions(
+        &mut self,
+        txns: Vec<SignedUserTransaction>,
+        relay_peers: u32,
+    ) {
+        let txn_ids = txns.iter().map(|txn| txn.id()).collect::<Vec<_>>();
+        let (protocol_name, message) =
+            NotificationMessage::Transactions(TransactionsMessage::new(txns))
+                .encode_notification()
+                .expect("Encode notification Transactions message should ok");
+        let selected_peers = select_random_peers(
+            relay_peers..=relay_peers,
+            self.peers
+                .keys()
+                .filter(|id| self.is_supported(id, protocol_name.clone()))
+                .cloned()
+                .collect::<Vec<_>>()
+                .iter(),
+        );
+        for id in txn_ids.iter() {
+            self.self_peer.known_transactions.put(*id, ());
+        }
+        let relayed_peers = selected_peers.len();
+        for peer_id in selected_peers {
+            let peer = self.peers.get_mut(&peer_id).expect("peer should exists");
+            for id in txn_ids.iter() {
+                peer.known_transactions.put(*id, ());
+            }
+            self.network_service.write_notification(
+                peer_id.into(),
+                protocol_name.clone(),
+                message.clone(),
+            );
+        }
+        debug!(
+            "[network] relay {} transactions to {} peers",
+            txn_ids.len(),
+            relayed_peers
+        );
+    }
 }
 
 fn select_random_peers<'a, P>(peer_num_range: RangeInclusive<u32>, peers: P) -> Vec<PeerId>
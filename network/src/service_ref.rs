@@ -1,6 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::peer_control::{
+    BanPeer, BannedPeer, GetBannedPeers, GetTrustedPeers, RotateIdentity, TrustPeer, UnbanPeer,
+    UntrustPeer,
+};
 use crate::service::NetworkActorService;
 use crate::worker::RPC_PROTOCOL_PREFIX;
 use crate::PeerMessage;
@@ -9,7 +13,7 @@ use futures::channel::oneshot::Receiver;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use log::warn;
-use network_api::messages::NotificationMessage;
+use network_api::messages::{IdentityLinkageMessage, NotificationMessage};
 use network_api::{NetworkService, PeerProvider, ReputationChange, SupportedRpcProtocol};
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::{IfDisconnected, Multiaddr, RequestFailure};
@@ -19,6 +23,7 @@ use starcoin_types::peer_info::PeerId;
 use starcoin_types::peer_info::PeerInfo;
 use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::Duration;
 
 //TODO Service registry should support custom service ref.
 #[derive(Clone)]
@@ -151,4 +156,33 @@ impl NetworkServiceRef {
     pub async fn is_connected(&self, peer_id: PeerId) -> bool {
         self.network_service.is_connected(peer_id.into()).await
     }
+
+    pub async fn ban_peer(&self, peer_id: PeerId, duration: Duration) -> Result<BannedPeer> {
+        self.service_ref.send(BanPeer { peer_id, duration }).await?
+    }
+
+    pub async fn unban_peer(&self, peer_id: PeerId) -> Result<bool> {
+        self.service_ref.send(UnbanPeer { peer_id }).await?
+    }
+
+    pub async fn banned_peers(&self) -> Result<Vec<BannedPeer>> {
+        self.service_ref.send(GetBannedPeers).await
+    }
+
+    pub async fn trust_peer(&self, peer: String) -> Result<()> {
+        self.service_ref.send(TrustPeer { peer }).await?
+    }
+
+    pub async fn untrust_peer(&self, peer: String) -> Result<bool> {
+        self.service_ref.send(UntrustPeer { peer }).await?
+    }
+
+    pub async fn trusted_peers(&self) -> Result<Vec<String>> {
+        self.service_ref.send(GetTrustedPeers).await
+    }
+
+    /// Rotate the network identity key, the new identity takes effect after the node restarts.
+    pub async fn rotate_identity(&self) -> Result<IdentityLinkageMessage> {
+        self.service_ref.send(RotateIdentity).await?
+    }
 }
@@ -5,8 +5,8 @@ use async_std::task;
 use futures::stream::StreamExt;
 use futures_timer::Delay;
 use network_api::messages::{
-    Announcement, AnnouncementType, CompactBlockMessage, NotificationMessage, PeerMessage,
-    TransactionsMessage, ANNOUNCEMENT_PROTOCOL_NAME, TXN_PROTOCOL_NAME,
+    Announcement, AnnouncementType, CompactBlockMessage, GossipTopic, NotificationMessage,
+    PeerMessage, TransactionsMessage, ANNOUNCEMENT_PROTOCOL_NAME, TXN_PROTOCOL_NAME,
 };
 use network_api::{Multiaddr, NetworkService};
 use network_p2p_types::MultiaddrWithPeerId;
@@ -370,3 +370,45 @@ async fn test_filter_protocol() {
         msg_3.notification.protocol_name()
     );
 }
+
+#[stest::test]
+async fn test_gossip_topics() {
+    let node_config_1 = Arc::new(NodeConfig::random_for_test());
+    let service1 = build_network_with_config(node_config_1.clone(), None)
+        .await
+        .unwrap();
+
+    let nodes = vec![MultiaddrWithPeerId::new(
+        node_config_1.network.listen(),
+        service1.peer_id().into(),
+    )];
+    let mut node_config_2 = NodeConfig::random_for_test();
+    node_config_2.network.seeds = nodes.into();
+    // a header-only node opts out of the transaction gossip.
+    node_config_2.network.gossip_topics = Some(vec![GossipTopic::Blocks]);
+    let service2 = build_network_with_config(Arc::new(node_config_2), None)
+        .await
+        .unwrap();
+    Delay::new(Duration::from_secs(2)).await;
+    assert!(service1.service_ref.is_connected(service2.peer_id()).await);
+
+    let mut receiver2 = service2.message_handler.channel();
+
+    let txns = vec![SignedUserTransaction::mock()];
+    service1
+        .service_ref
+        .broadcast(NotificationMessage::Transactions(TransactionsMessage::new(
+            txns,
+        )));
+    let msg_2 = async_std::future::timeout(Duration::from_secs(1), receiver2.next()).await;
+    assert!(msg_2.is_err());
+
+    let block = Block::new(BlockHeader::random(), BlockBody::new_empty());
+    let notification = NotificationMessage::CompactBlock(Box::new(CompactBlockMessage::new(
+        CompactBlock::new(block, vec![]),
+        mock_block_info(10.into()),
+    )));
+    service1.service_ref.broadcast(notification.clone());
+    let msg_2 = receiver2.next().await.unwrap();
+    assert_eq!(notification, msg_2.notification);
+}
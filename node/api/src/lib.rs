@@ -2,5 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 pub mod errors;
+pub mod maintenance;
 pub mod message;
 pub mod node_service;
+pub mod usage;
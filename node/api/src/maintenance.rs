@@ -0,0 +1,84 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MaintenanceTask {
+    /// Compact the storage.
+    Compaction,
+    /// Create a checkpoint of the storage in the backup dir, and remove the old backups.
+    Backup,
+    /// Remove the node diagnostics files out of the retention.
+    Cleanup,
+    /// Verify the stored records, and quarantine the corrupted ones.
+    Scrub,
+}
+
+impl fmt::Display for MaintenanceTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compaction => write!(f, "compaction"),
+            Self::Backup => write!(f, "backup"),
+            Self::Cleanup => write!(f, "cleanup"),
+            Self::Scrub => write!(f, "scrub"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MaintenanceTaskState {
+    /// Wait for the scheduled time.
+    Scheduled,
+    /// The scheduled time is due, but the run is deferred by the sync or mining.
+    Deferred {
+        reason: String,
+    },
+    Running,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MaintenanceRunResult {
+    Succeeded,
+    Failed(String),
+    /// The run is deferred longer than the max defer time.
+    Skipped(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceRun {
+    /// The scheduled time of the run in seconds.
+    pub scheduled_at: u64,
+    pub finished_at: u64,
+    pub result: MaintenanceRunResult,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceTaskStatus {
+    pub task: MaintenanceTask,
+    /// The UTC schedule of the task, as `HH:MM` or `<weekday> HH:MM`.
+    pub schedule: String,
+    pub state: MaintenanceTaskState,
+    /// The scheduled time of the next run in seconds, it is in the past if the run is deferred or running.
+    pub next_run_at: u64,
+    pub last_run: Option<MaintenanceRun>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    /// The current time of the node in seconds.
+    pub now: u64,
+    pub syncing: bool,
+    pub minting: bool,
+    /// Whether the block generation is paused by the maintenance.
+    pub mining_paused: bool,
+    pub tasks: Vec<MaintenanceTaskStatus>,
+}
+
+impl MaintenanceStatus {
+    /// The task status with the earliest next run.
+    pub fn next(&self) -> Option<&MaintenanceTaskStatus> {
+        self.tasks.iter().min_by_key(|task| task.next_run_at)
+    }
+}
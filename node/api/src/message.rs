@@ -1,8 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::maintenance::MaintenanceStatus;
+use crate::usage::UsageReport;
 use anyhow::Result;
+use starcoin_account_api::PendingTxnApproval;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ServiceInfo, ServiceRequest, ServiceStatus};
+use starcoin_sync_api::PendingReorg;
 
 #[derive(Debug, Clone)]
 pub enum NodeRequest {
@@ -13,6 +18,14 @@ pub enum NodeRequest {
     StopPacemaker,
     StartPacemaker,
     ShutdownSystem,
+    MaintenanceStatus,
+    /// Summarize the usage stats of the last seconds.
+    UsageReport(u64),
+    /// Accept the reorg halted by the max reorg depth, optionally check its branch head.
+    AcceptReorg(Option<HashValue>),
+    /// Approve the txn the sign policy asks for by its approval hash.
+    ApproveTxn(HashValue),
+    PendingTxnApprovals,
 }
 
 #[derive(Debug)]
@@ -20,6 +33,10 @@ pub enum NodeResponse {
     Services(Vec<ServiceInfo>),
     Result(Result<()>),
     ServiceStatus(ServiceStatus),
+    MaintenanceStatus(MaintenanceStatus),
+    UsageReport(UsageReport),
+    AcceptedReorg(PendingReorg),
+    PendingTxnApprovals(Vec<PendingTxnApproval>),
 }
 
 impl ServiceRequest for NodeRequest {
@@ -1,11 +1,16 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::maintenance::MaintenanceStatus;
 use crate::message::{NodeRequest, NodeResponse};
+use crate::usage::UsageReport;
 use anyhow::Result;
+use starcoin_account_api::PendingTxnApproval;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::{
     ActorService, ServiceHandler, ServiceInfo, ServiceRef, ServiceStatus,
 };
+use starcoin_sync_api::PendingReorg;
 
 #[async_trait::async_trait]
 pub trait NodeAsyncService:
@@ -24,6 +29,16 @@ pub trait NodeAsyncService:
     async fn stop_pacemaker(&self) -> Result<()>;
 
     async fn shutdown_system(&self) -> Result<()>;
+
+    async fn maintenance_status(&self) -> Result<MaintenanceStatus>;
+
+    async fn usage_report(&self, last_seconds: u64) -> Result<UsageReport>;
+
+    async fn accept_reorg(&self, branch_head: Option<HashValue>) -> Result<PendingReorg>;
+
+    async fn approve_txn(&self, approval_hash: HashValue) -> Result<()>;
+
+    async fn pending_txn_approvals(&self) -> Result<Vec<PendingTxnApproval>>;
 }
 
 #[async_trait::async_trait]
@@ -91,4 +106,49 @@ where
         self.try_send(NodeRequest::ShutdownSystem)?;
         Ok(())
     }
+
+    async fn maintenance_status(&self) -> Result<MaintenanceStatus> {
+        let response = self.send(NodeRequest::MaintenanceStatus).await??;
+        if let NodeResponse::MaintenanceStatus(status) = response {
+            Ok(status)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn usage_report(&self, last_seconds: u64) -> Result<UsageReport> {
+        let response = self.send(NodeRequest::UsageReport(last_seconds)).await??;
+        if let NodeResponse::UsageReport(report) = response {
+            Ok(report)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn accept_reorg(&self, branch_head: Option<HashValue>) -> Result<PendingReorg> {
+        let response = self.send(NodeRequest::AcceptReorg(branch_head)).await??;
+        if let NodeResponse::AcceptedReorg(pending_reorg) = response {
+            Ok(pending_reorg)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn approve_txn(&self, approval_hash: HashValue) -> Result<()> {
+        let response = self.send(NodeRequest::ApproveTxn(approval_hash)).await??;
+        if let NodeResponse::Result(result) = response {
+            result
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn pending_txn_approvals(&self) -> Result<Vec<PendingTxnApproval>> {
+        let response = self.send(NodeRequest::PendingTxnApprovals).await??;
+        if let NodeResponse::PendingTxnApprovals(approvals) = response {
+            Ok(approvals)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
 }
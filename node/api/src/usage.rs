@@ -0,0 +1,332 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The local usage stats of the node, they are never sent out of the node.
+//!
+//! The usage counters are taken from the node metrics periodically, and appended to a local file
+//! as snapshots. The usage report of a time range sums the counter increments of the snapshots
+//! in the range, a decreased counter means the node is restarted, so it is counted from zero.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The count of the executed blocks.
+pub const BLOCKS_PROCESSED_METRIC: &str = "starcoin_chain_executed_blocks";
+/// The count of the reorgs, as the sample count of the reorg depth histogram.
+pub const REORGS_METRIC: &str = "starcoin_starcoin_write_block_chain_reorg_depth_count";
+/// The peer events, labeled by `connected` and `disconnected`.
+pub const PEER_EVENTS_METRIC: &str = "starcoin_network_peer_events";
+/// The rpc calls, labeled by the call type, the method and the result code.
+pub const RPC_CALLS_METRIC: &str = "starcoin_rpc";
+/// The txpool imports, labeled by `admitted` or the rejected reason.
+pub const TXPOOL_IMPORTS_METRIC: &str = "starcoin_txpool_txn_import";
+
+const USAGE_METRICS: [&str; 5] = [
+    BLOCKS_PROCESSED_METRIC,
+    REORGS_METRIC,
+    PEER_EVENTS_METRIC,
+    RPC_CALLS_METRIC,
+    TXPOOL_IMPORTS_METRIC,
+];
+
+/// The label value separator of the flattened metric names.
+const LABEL_SEPARATOR: char = '.';
+const TXPOOL_ADMITTED: &str = "admitted";
+
+/// The usage counters at a time.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UsageSnapshot {
+    /// The unix timestamp in seconds.
+    pub timestamp: u64,
+    /// The counters by the flattened metric name, such as `starcoin_network_peer_events.connected`.
+    pub counters: BTreeMap<String, u64>,
+}
+
+impl UsageSnapshot {
+    /// Take the usage counters from the flattened node metrics.
+    pub fn from_metrics(timestamp: u64, metrics: &HashMap<String, String>) -> Self {
+        let counters = metrics
+            .iter()
+            .filter(|(name, _)| {
+                USAGE_METRICS.iter().any(|metric| {
+                    name.as_str() == *metric
+                        || (name.starts_with(metric)
+                            && name[metric.len()..].starts_with(LABEL_SEPARATOR))
+                })
+            })
+            .filter_map(|(name, value)| {
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .map(|value| (name.clone(), value as u64))
+            })
+            .collect();
+        Self {
+            timestamp,
+            counters,
+        }
+    }
+}
+
+/// The usage summary of a time range.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// The start of the range in unix seconds.
+    pub from: u64,
+    /// The end of the range in unix seconds.
+    pub to: u64,
+    /// The count of the snapshots in the range, the report is empty if no snapshot is recorded.
+    pub snapshots: u64,
+    /// How many times the node is restarted in the range.
+    pub restarts: u64,
+    pub blocks_processed: u64,
+    pub reorgs: u64,
+    pub peers_connected: u64,
+    pub peers_disconnected: u64,
+    /// The rpc calls by method.
+    pub rpc_calls: BTreeMap<String, u64>,
+    pub txpool_admitted: u64,
+    /// The txns rejected by txpool, by reason.
+    pub txpool_rejected: BTreeMap<String, u64>,
+}
+
+impl UsageReport {
+    /// Summarize the snapshots since `from`, the snapshots should be sorted by time.
+    pub fn from_snapshots(snapshots: &[UsageSnapshot], from: u64) -> Self {
+        // the last snapshot before the range is the baseline of the increments.
+        let baseline = snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.timestamp <= from);
+        let in_range = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.timestamp > from)
+            .collect::<Vec<_>>();
+        let mut report = UsageReport {
+            from,
+            to: in_range
+                .last()
+                .map(|snapshot| snapshot.timestamp)
+                .unwrap_or(from),
+            snapshots: in_range.len() as u64,
+            ..Default::default()
+        };
+
+        let mut increments: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut previous = baseline;
+        for snapshot in in_range {
+            if let Some(previous) = previous {
+                if Self::is_restarted(previous, snapshot) {
+                    report.restarts = report.restarts.saturating_add(1);
+                }
+            }
+            for (name, value) in &snapshot.counters {
+                let previous_value = previous
+                    .and_then(|previous| previous.counters.get(name))
+                    .copied()
+                    .unwrap_or_default();
+                // the counter is reset by the restart.
+                let increment = if *value >= previous_value {
+                    value.saturating_sub(previous_value)
+                } else {
+                    *value
+                };
+                let total = increments.entry(name.as_str()).or_default();
+                *total = total.saturating_add(increment);
+            }
+            previous = Some(snapshot);
+        }
+
+        for (name, increment) in increments {
+            report.add(name, increment);
+        }
+        report
+    }
+
+    fn is_restarted(previous: &UsageSnapshot, snapshot: &UsageSnapshot) -> bool {
+        previous
+            .counters
+            .iter()
+            .any(|(name, value)| snapshot.counters.get(name).copied().unwrap_or_default() < *value)
+    }
+
+    fn add(&mut self, name: &str, increment: u64) {
+        let (metric, labels) = match name.find(LABEL_SEPARATOR) {
+            Some(position) => (&name[..position], &name[position + 1..]),
+            None => (name, ""),
+        };
+        match metric {
+            BLOCKS_PROCESSED_METRIC => {
+                self.blocks_processed = self.blocks_processed.saturating_add(increment)
+            }
+            REORGS_METRIC => self.reorgs = self.reorgs.saturating_add(increment),
+            PEER_EVENTS_METRIC if labels == "connected" => {
+                self.peers_connected = self.peers_connected.saturating_add(increment)
+            }
+            PEER_EVENTS_METRIC if labels == "disconnected" => {
+                self.peers_disconnected = self.peers_disconnected.saturating_add(increment)
+            }
+            RPC_CALLS_METRIC => {
+                // the labels are `<type>.<method>.<code>`, and the method may contain dots.
+                let method = match (labels.find(LABEL_SEPARATOR), labels.rfind(LABEL_SEPARATOR)) {
+                    (Some(start), Some(end)) if start < end => &labels[start + 1..end],
+                    _ => return,
+                };
+                let total = self.rpc_calls.entry(method.to_string()).or_default();
+                *total = total.saturating_add(increment);
+            }
+            TXPOOL_IMPORTS_METRIC if labels == TXPOOL_ADMITTED => {
+                self.txpool_admitted = self.txpool_admitted.saturating_add(increment)
+            }
+            TXPOOL_IMPORTS_METRIC if !labels.is_empty() => {
+                let total = self.txpool_rejected.entry(labels.to_string()).or_default();
+                *total = total.saturating_add(increment);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Remove the sorted snapshots before the time, but keep the last one as the baseline of the later
+/// snapshots, return the count of the removed snapshots.
+pub fn prune_snapshots(snapshots: &mut Vec<UsageSnapshot>, before: u64) -> usize {
+    let baseline = snapshots
+        .iter()
+        .rposition(|snapshot| snapshot.timestamp < before);
+    match baseline {
+        Some(position) if position > 0 => {
+            snapshots.drain(..position);
+            position
+        }
+        _ => 0,
+    }
+}
+
+/// The local file of the usage snapshots, a snapshot per line.
+pub struct UsageStatsStore {
+    path: PathBuf,
+}
+
+impl UsageStatsStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    pub fn append(&self, snapshot: &UsageSnapshot) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+        Ok(())
+    }
+
+    /// The recorded snapshots sorted by time, the broken lines are skipped.
+    pub fn load(&self) -> Result<Vec<UsageSnapshot>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let mut snapshots = fs::read_to_string(&self.path)?
+            .lines()
+            .filter_map(|line| serde_json::from_str::<UsageSnapshot>(line).ok())
+            .collect::<Vec<_>>();
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+        Ok(snapshots)
+    }
+
+    /// Remove the snapshots before the time, but keep the last one as the baseline of the later snapshots.
+    pub fn prune(&self, before: u64) -> Result<usize> {
+        let mut snapshots = self.load()?;
+        let removed = prune_snapshots(&mut snapshots, before);
+        if removed == 0 {
+            return Ok(0);
+        }
+        let mut content = String::new();
+        for snapshot in &snapshots {
+            content.push_str(serde_json::to_string(snapshot)?.as_str());
+            content.push('\n');
+        }
+        fs::write(&self.path, content)?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp: u64, counters: &[(&str, u64)]) -> UsageSnapshot {
+        UsageSnapshot {
+            timestamp,
+            counters: counters
+                .iter()
+                .map(|(name, value)| (name.to_string(), *value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_usage_report() {
+        let blocks = BLOCKS_PROCESSED_METRIC;
+        let rpc = "starcoin_rpc.method.chain.info.0";
+        let rejected = "starcoin_txpool_txn_import.insufficient_gas_price";
+        let snapshots = vec![
+            snapshot(100, &[(blocks, 10), (rpc, 1)]),
+            snapshot(200, &[(blocks, 15), (rpc, 3), (rejected, 1)]),
+            // restarted
+            snapshot(300, &[(blocks, 4), (rpc, 2)]),
+            snapshot(400, &[(blocks, 6), (rpc, 2), (rejected, 2)]),
+        ];
+        let report = UsageReport::from_snapshots(&snapshots, 150);
+        assert_eq!(report.snapshots, 3);
+        assert_eq!(report.restarts, 1);
+        assert_eq!(report.to, 400);
+        // 10 -> 15, restart -> 4, 4 -> 6
+        assert_eq!(report.blocks_processed, 11);
+        assert_eq!(report.rpc_calls.get("chain.info"), Some(&4));
+        assert_eq!(
+            report.txpool_rejected.get("insufficient_gas_price"),
+            Some(&3)
+        );
+
+        let report = UsageReport::from_snapshots(&snapshots, 0);
+        assert_eq!(report.blocks_processed, 21);
+    }
+
+    #[test]
+    fn test_prune_snapshots() {
+        let mut snapshots = vec![snapshot(100, &[]), snapshot(200, &[]), snapshot(300, &[])];
+        assert_eq!(prune_snapshots(&mut snapshots, 100), 0);
+        // the snapshot at 200 is kept as the baseline of the snapshot at 300.
+        assert_eq!(prune_snapshots(&mut snapshots, 250), 1);
+        assert_eq!(snapshots[0].timestamp, 200);
+        assert_eq!(prune_snapshots(&mut snapshots, 1000), 1);
+        assert_eq!(snapshots.len(), 1);
+    }
+
+    #[test]
+    fn test_usage_snapshot_from_metrics() {
+        let metrics = vec![
+            ("starcoin_chain_executed_blocks", "3"),
+            ("starcoin_network_peer_events.connected", "2"),
+            ("starcoin_rpc_time_count.chain.info", "5"),
+            ("starcoin_txpool_txn_nums", "7"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+        let snapshot = UsageSnapshot::from_metrics(1, &metrics);
+        assert_eq!(snapshot.counters.len(), 2);
+        let report = UsageReport::from_snapshots(&[snapshot], 0);
+        assert_eq!(report.blocks_processed, 3);
+        assert_eq!(report.peers_connected, 2);
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::{BlockStore, Storage};
+use std::sync::Arc;
+use std::time::Duration;
+
+const COMPRESSION_INTERVAL: Duration = Duration::from_secs(10);
+/// Compress a batch of blocks per run, so the compression does not block the storage for long.
+const COMPRESSION_BATCH_BLOCKS: u64 = 1000;
+
+/// Compress the historical block bodies and transaction infos deeper than the configured depth.
+pub struct BlockCompressionService {
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+}
+
+impl ServiceFactory<Self> for BlockCompressionService {
+    fn create(
+        ctx: &mut ServiceContext<BlockCompressionService>,
+    ) -> Result<BlockCompressionService> {
+        Ok(Self {
+            config: ctx.get_shared::<Arc<NodeConfig>>()?,
+            storage: ctx.get_shared::<Arc<Storage>>()?,
+        })
+    }
+}
+
+impl ActorService for BlockCompressionService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        info!(
+            "Start compressing the blocks deeper than {:?}",
+            self.config.storage.block_compression_depth()
+        );
+        ctx.run_interval(COMPRESSION_INTERVAL, |ctx| ctx.notify(CompressBlocksEvent));
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CompressBlocksEvent;
+
+impl EventHandler<Self, CompressBlocksEvent> for BlockCompressionService {
+    fn handle_event(&mut self, _msg: CompressBlocksEvent, _ctx: &mut ServiceContext<Self>) {
+        if let Err(e) = self.compress() {
+            warn!("Compress blocks failed: {:?}", e);
+        }
+    }
+}
+
+impl BlockCompressionService {
+    fn compress(&self) -> Result<()> {
+        let depth = match self.config.storage.block_compression_depth() {
+            Some(depth) => depth,
+            None => return Ok(()),
+        };
+        let head_number = match self.storage.get_chain_info()? {
+            Some(chain_info) => chain_info.head().number(),
+            None => return Ok(()),
+        };
+        if head_number < depth {
+            return Ok(());
+        }
+        let report = self
+            .storage
+            .compress_blocks(head_number.saturating_sub(depth), COMPRESSION_BATCH_BLOCKS)?;
+        if report.blocks > 0 {
+            info!(
+                "Compressed {} blocks to {:?}, {} records, {} -> {} bytes",
+                report.blocks,
+                report.compressed_to,
+                report.records,
+                report.original_bytes,
+                report.compressed_bytes
+            );
+        } else {
+            debug!(
+                "No block to compress, compressed to {:?}",
+                report.compressed_to
+            );
+        }
+        Ok(())
+    }
+}
@@ -26,13 +26,17 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::Runtime;
 
+mod block_compression;
 pub mod crash_handler;
 mod genesis_parameter_resolve;
+mod maintenance;
 mod metrics;
 pub mod network_service_factory;
 pub mod node;
 pub mod peer_message_handler;
 pub mod rpc_service_factory;
+mod soak_check;
+mod usage_stats;
 
 pub struct NodeHandle {
     runtime: Runtime,
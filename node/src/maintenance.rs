@@ -0,0 +1,577 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use starcoin_config::{MaintenanceSchedule, NodeConfig};
+use starcoin_logger::prelude::*;
+use starcoin_miner::generate_block_event_pacemaker::GenerateBlockEventPacemaker;
+use starcoin_node_api::maintenance::{
+    MaintenanceRun, MaintenanceRunResult, MaintenanceStatus, MaintenanceTask, MaintenanceTaskState,
+    MaintenanceTaskStatus,
+};
+use starcoin_service_registry::{
+    ActorService, EventHandler, RegistryAsyncService, RegistryService, ServiceContext,
+    ServiceFactory, ServiceHandler, ServiceRef, ServiceRequest, ServiceStatus,
+};
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::scrub::scrub;
+use starcoin_storage::VEC_PREFIX_NAME;
+use starcoin_types::sync_status::SyncStatus;
+use starcoin_types::system_events::{MintBlockEvent, NewHeadBlock, SyncStatusChangeEvent};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The interval to check the due tasks.
+const MAINTENANCE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const BACKUP_NAME_PREFIX: &str = "starcoindb_";
+/// The prefixes of the diagnostics files written to the data dir, removed by the cleanup.
+const DIAGNOSTICS_FILE_PREFIXES: &[&str] = &["soak_check_"];
+
+struct ScheduledTask {
+    task: MaintenanceTask,
+    schedule: MaintenanceSchedule,
+    next_run_at: u64,
+    deferred: Option<String>,
+    last_run: Option<MaintenanceRun>,
+}
+
+/// Run the node upkeep tasks at the scheduled time of `NodeConfig::maintenance`.
+///
+/// The tasks are checked by a timer at their due time. A due task is deferred while the node is syncing.
+/// Otherwise the block generation is paused, and the task runs at once, or after the current block
+/// is mined if the node is minting a block. The block generation is resumed when no task is due.
+/// The run is skipped if it is deferred longer than the max defer time. The tasks run one by one
+/// on a separate thread.
+pub struct MaintenanceService {
+    config: Arc<NodeConfig>,
+    db: Arc<DBStorage>,
+    /// The live cache over the db, the records removed from the db are invalidated in it.
+    cache: Option<Arc<CacheStorage>>,
+    registry: ServiceRef<RegistryService>,
+    tasks: Vec<ScheduledTask>,
+    sync_status: Option<SyncStatus>,
+    minting: bool,
+    mining_paused: bool,
+    running: Option<MaintenanceTask>,
+    /// The time of the scheduled check for the next due task.
+    next_check_at: Option<u64>,
+}
+
+/// The action of a due task at the check.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DueAction {
+    /// The task is deferred over the max defer time, skip the run.
+    Skip,
+    /// Defer the task, and pause the block generation if `pause_mining`.
+    Defer {
+        reason: &'static str,
+        pause_mining: bool,
+    },
+    /// Pause the block generation and run the task.
+    Run,
+}
+
+/// Decide the action of the task due at `next_run_at`, the block generation is paused once
+/// the task is due, no matter whether a block is being minted.
+fn due_action(
+    next_run_at: u64,
+    now: u64,
+    max_defer: u64,
+    synced: bool,
+    minting: bool,
+) -> DueAction {
+    if next_run_at.saturating_add(max_defer) < now {
+        DueAction::Skip
+    } else if !synced {
+        // the node does not mine while syncing.
+        DueAction::Defer {
+            reason: "The node is syncing",
+            pause_mining: false,
+        }
+    } else if minting {
+        DueAction::Defer {
+            reason: "Wait for the current block to be mined",
+            pause_mining: true,
+        }
+    } else {
+        DueAction::Run
+    }
+}
+
+impl ServiceFactory<Self> for MaintenanceService {
+    fn create(ctx: &mut ServiceContext<MaintenanceService>) -> Result<MaintenanceService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let now = now_secs();
+        let tasks = vec![
+            (MaintenanceTask::Compaction, config.maintenance.compaction),
+            (MaintenanceTask::Backup, config.maintenance.backup),
+            (MaintenanceTask::Cleanup, config.maintenance.cleanup),
+            (MaintenanceTask::Scrub, config.maintenance.scrub),
+        ]
+        .into_iter()
+        .filter_map(|(task, schedule)| {
+            schedule.map(|schedule| ScheduledTask {
+                task,
+                schedule,
+                next_run_at: schedule.next_after(now),
+                deferred: None,
+                last_run: None,
+            })
+        })
+        .collect();
+        Ok(Self {
+            db: ctx.get_shared::<Arc<DBStorage>>()?,
+            cache: ctx.get_shared::<Arc<CacheStorage>>().ok(),
+            registry: ctx.registry_ref().clone(),
+            config,
+            tasks,
+            sync_status: None,
+            minting: false,
+            mining_paused: false,
+            running: None,
+            next_check_at: None,
+        })
+    }
+}
+
+impl ActorService for MaintenanceService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        for task in &self.tasks {
+            info!(
+                "[maintenance] Schedule {} at {} UTC, next run at {}",
+                task.task, task.schedule, task.next_run_at
+            );
+        }
+        ctx.subscribe::<SyncStatusChangeEvent>();
+        ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<NewHeadBlock>();
+        ctx.run_interval(MAINTENANCE_CHECK_INTERVAL, |ctx| {
+            ctx.notify(MaintenanceCheckEvent)
+        });
+        self.schedule_next_check(ctx);
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<SyncStatusChangeEvent>();
+        ctx.unsubscribe::<MintBlockEvent>();
+        ctx.unsubscribe::<NewHeadBlock>();
+        self.resume_mining();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MaintenanceCheckEvent;
+
+#[derive(Clone, Debug)]
+pub struct MaintenanceDoneEvent {
+    task: MaintenanceTask,
+    result: Result<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MaintenanceStatusRequest;
+
+impl ServiceRequest for MaintenanceStatusRequest {
+    type Response = MaintenanceStatus;
+}
+
+impl EventHandler<Self, SyncStatusChangeEvent> for MaintenanceService {
+    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
+        self.sync_status = Some(msg.0);
+    }
+}
+
+impl EventHandler<Self, MintBlockEvent> for MaintenanceService {
+    fn handle_event(&mut self, _msg: MintBlockEvent, _ctx: &mut ServiceContext<Self>) {
+        self.minting = true;
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for MaintenanceService {
+    fn handle_event(&mut self, _msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
+        self.minting = false;
+        if self.mining_paused {
+            self.check(ctx);
+        }
+    }
+}
+
+impl EventHandler<Self, MaintenanceCheckEvent> for MaintenanceService {
+    fn handle_event(&mut self, _msg: MaintenanceCheckEvent, ctx: &mut ServiceContext<Self>) {
+        self.check(ctx);
+    }
+}
+
+impl EventHandler<Self, MaintenanceDoneEvent> for MaintenanceService {
+    fn handle_event(&mut self, msg: MaintenanceDoneEvent, ctx: &mut ServiceContext<Self>) {
+        self.running = None;
+        let now = now_secs();
+        let result = match msg.result {
+            Ok(detail) => {
+                info!("[maintenance] Task {} is done: {}", msg.task, detail);
+                MaintenanceRunResult::Succeeded
+            }
+            Err(e) => {
+                error!("[maintenance] Task {} failed: {}", msg.task, e);
+                MaintenanceRunResult::Failed(e)
+            }
+        };
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.task == msg.task) {
+            task.finish(now, result);
+        }
+        self.check(ctx);
+    }
+}
+
+impl ServiceHandler<Self, MaintenanceStatusRequest> for MaintenanceService {
+    fn handle(
+        &mut self,
+        _msg: MaintenanceStatusRequest,
+        _ctx: &mut ServiceContext<MaintenanceService>,
+    ) -> MaintenanceStatus {
+        let tasks = self
+            .tasks
+            .iter()
+            .map(|task| {
+                let state = if self.running == Some(task.task) {
+                    MaintenanceTaskState::Running
+                } else if let Some(reason) = task.deferred.as_ref() {
+                    MaintenanceTaskState::Deferred {
+                        reason: reason.clone(),
+                    }
+                } else {
+                    MaintenanceTaskState::Scheduled
+                };
+                MaintenanceTaskStatus {
+                    task: task.task,
+                    schedule: task.schedule.to_string(),
+                    state,
+                    next_run_at: task.next_run_at,
+                    last_run: task.last_run.clone(),
+                }
+            })
+            .collect();
+        MaintenanceStatus {
+            now: now_secs(),
+            syncing: !self.is_synced(),
+            minting: self.minting,
+            mining_paused: self.mining_paused,
+            tasks,
+        }
+    }
+}
+
+impl ScheduledTask {
+    /// Record the run of the scheduled time, and schedule the next run.
+    fn finish(&mut self, now: u64, result: MaintenanceRunResult) {
+        self.last_run = Some(MaintenanceRun {
+            scheduled_at: self.next_run_at,
+            finished_at: now,
+            result,
+        });
+        self.next_run_at = self.schedule.next_after(now);
+        self.deferred = None;
+    }
+}
+
+impl MaintenanceService {
+    fn is_synced(&self) -> bool {
+        match self.sync_status.as_ref() {
+            Some(sync_status) => sync_status.is_synced(),
+            None => false,
+        }
+    }
+
+    fn check(&mut self, ctx: &mut ServiceContext<Self>) {
+        if self.running.is_some() {
+            return;
+        }
+        let now = now_secs();
+        let max_defer = self.config.maintenance.max_defer();
+        let mut due_tasks: Vec<usize> = (0..self.tasks.len())
+            .filter(|index| self.tasks[*index].next_run_at <= now)
+            .collect();
+        due_tasks.sort_by_key(|index| self.tasks[*index].next_run_at);
+        let synced = self.is_synced();
+        for index in due_tasks {
+            let task = &mut self.tasks[index];
+            match due_action(task.next_run_at, now, max_defer, synced, self.minting) {
+                DueAction::Skip => {
+                    let reason = task
+                        .deferred
+                        .clone()
+                        .unwrap_or_else(|| "The node is busy".to_string());
+                    warn!(
+                        "[maintenance] Skip task {} scheduled at {}, deferred over {}s: {}",
+                        task.task, task.next_run_at, max_defer, reason
+                    );
+                    task.finish(now, MaintenanceRunResult::Skipped(reason));
+                }
+                DueAction::Defer {
+                    reason,
+                    pause_mining,
+                } => {
+                    if pause_mining {
+                        self.pause_mining();
+                    }
+                    self.defer(index, reason);
+                    return;
+                }
+                DueAction::Run => {
+                    self.pause_mining();
+                    self.run(index, ctx);
+                    return;
+                }
+            }
+        }
+        // no task is due.
+        self.resume_mining();
+        self.schedule_next_check(ctx);
+    }
+
+    /// Check the tasks by a timer at the earliest next run time, so the due task pauses the block
+    /// generation at its scheduled time, instead of the next check interval.
+    fn schedule_next_check(&mut self, ctx: &mut ServiceContext<Self>) {
+        let next_run_at = match self.tasks.iter().map(|task| task.next_run_at).min() {
+            Some(next_run_at) => next_run_at,
+            None => return,
+        };
+        if self.next_check_at == Some(next_run_at) {
+            return;
+        }
+        self.next_check_at = Some(next_run_at);
+        let delay = Duration::from_secs(next_run_at.saturating_sub(now_secs()));
+        ctx.run_later(delay, |ctx| ctx.notify(MaintenanceCheckEvent));
+    }
+
+    fn defer(&mut self, index: usize, reason: &str) {
+        let task = &mut self.tasks[index];
+        if task.deferred.as_deref() != Some(reason) {
+            info!("[maintenance] Defer task {}: {}", task.task, reason);
+        }
+        task.deferred = Some(reason.to_string());
+    }
+
+    fn run(&mut self, index: usize, ctx: &mut ServiceContext<Self>) {
+        let task = self.tasks[index].task;
+        self.tasks[index].deferred = None;
+        self.running = Some(task);
+        info!("[maintenance] Start task {}", task);
+        let db = self.db.clone();
+        let cache = self.cache.clone();
+        let config = self.config.clone();
+        let self_ref = ctx.self_ref();
+        std::thread::spawn(move || {
+            let result = match task {
+                MaintenanceTask::Compaction => {
+                    db.compact_all().map(|_| "storage compacted".to_string())
+                }
+                MaintenanceTask::Backup => backup_storage(
+                    db.as_ref(),
+                    config.maintenance.backup_dir().as_path(),
+                    config.maintenance.backup_keep() as usize,
+                    now_secs(),
+                )
+                .map(|path| format!("backup to {}", path.display())),
+                MaintenanceTask::Cleanup => cleanup_diagnostics(
+                    config.data_dir(),
+                    Duration::from_secs(config.maintenance.retention_days().saturating_mul(86400)),
+                )
+                .map(|count| format!("{} diagnostics files removed", count)),
+                MaintenanceTask::Scrub => scrub_storage(db.as_ref(), cache.as_deref()),
+            };
+            let result = result.map_err(|e| e.to_string());
+            if let Err(e) = self_ref.notify(MaintenanceDoneEvent { task, result }) {
+                error!(
+                    "[maintenance] Notify the result of task {} failed: {}",
+                    task, e
+                );
+            }
+        });
+    }
+
+    /// Stop the block generation pacemaker, so no new block is minted during the maintenance.
+    fn pause_mining(&mut self) {
+        if self.mining_paused {
+            return;
+        }
+        let name = GenerateBlockEventPacemaker::service_name();
+        match self.registry.check_service_status_sync(name) {
+            Ok(ServiceStatus::Started) => match self.registry.stop_service_sync(name) {
+                Ok(()) => {
+                    info!("[maintenance] Pause the block generation for the maintenance.");
+                    self.mining_paused = true;
+                }
+                Err(e) => warn!("[maintenance] Pause the block generation failed: {}", e),
+            },
+            Ok(_) => {}
+            Err(e) => warn!("[maintenance] Check the block generation failed: {}", e),
+        }
+    }
+
+    fn resume_mining(&mut self) {
+        if !self.mining_paused {
+            return;
+        }
+        match self
+            .registry
+            .start_service_sync(GenerateBlockEventPacemaker::service_name())
+        {
+            Ok(()) => {
+                info!("[maintenance] Resume the block generation.");
+                self.mining_paused = false;
+            }
+            Err(e) => warn!("[maintenance] Resume the block generation failed: {}", e),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Create a checkpoint of the storage in the backup dir, and remove the old backups beyond `keep`.
+fn backup_storage(db: &DBStorage, backup_dir: &Path, keep: usize, now: u64) -> Result<PathBuf> {
+    std::fs::create_dir_all(backup_dir)?;
+    let path = backup_dir.join(format!("{}{}", BACKUP_NAME_PREFIX, now));
+    db.create_checkpoint(path.as_path())?;
+    let mut backups = vec![];
+    for entry in std::fs::read_dir(backup_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some(Ok(timestamp)) = name
+            .strip_prefix(BACKUP_NAME_PREFIX)
+            .map(|timestamp| timestamp.parse::<u64>())
+        {
+            if entry.file_type()?.is_dir() {
+                backups.push((timestamp, entry.path()));
+            }
+        }
+    }
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+    for (_, old_backup) in backups.into_iter().skip(keep.max(1)) {
+        info!("[maintenance] Remove old backup {}", old_backup.display());
+        std::fs::remove_dir_all(old_backup.as_path())
+            .map_err(|e| format_err!("Remove backup {} failed: {}", old_backup.display(), e))?;
+    }
+    Ok(path)
+}
+
+/// Scrub all the column families, the run fails if any corruption is found, so it is alerted.
+fn scrub_storage(db: &DBStorage, cache: Option<&CacheStorage>) -> Result<String> {
+    let report = scrub(db, cache, VEC_PREFIX_NAME.as_slice())?;
+    if !report.is_clean() {
+        bail!(
+            "{} records scanned, {} corrupted records quarantined, corrupted column families: {:?}",
+            report.scanned,
+            report.quarantined,
+            report.corrupted_cfs
+        );
+    }
+    Ok(format!(
+        "{} records scanned, no corruption, partially verified column families: {:?}",
+        report.scanned, report.partially_verified_cfs
+    ))
+}
+
+/// Remove the diagnostics files in the data dir which are not modified in the retention.
+fn cleanup_diagnostics(data_dir: &Path, retention: Duration) -> Result<usize> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(data_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !DIAGNOSTICS_FILE_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        let expired = metadata
+            .modified()?
+            .elapsed()
+            .map(|elapsed| elapsed > retention)
+            .unwrap_or(false);
+        if metadata.is_file() && expired {
+            std::fs::remove_file(entry.path())?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_config::RocksdbConfig;
+
+    #[test]
+    fn test_due_action() {
+        let max_defer = 600;
+        // the block generation is paused by the due task even if no block is being minted.
+        assert_eq!(due_action(100, 100, max_defer, true, false), DueAction::Run);
+        assert_eq!(
+            due_action(100, 130, max_defer, true, true),
+            DueAction::Defer {
+                reason: "Wait for the current block to be mined",
+                pause_mining: true,
+            }
+        );
+        assert_eq!(
+            due_action(100, 130, max_defer, false, false),
+            DueAction::Defer {
+                reason: "The node is syncing",
+                pause_mining: false,
+            }
+        );
+        // the max defer time is not over yet.
+        assert_eq!(due_action(100, 700, max_defer, true, false), DueAction::Run);
+        assert_eq!(
+            due_action(100, 701, max_defer, true, false),
+            DueAction::Skip
+        );
+        assert_eq!(
+            due_action(100, 701, max_defer, false, true),
+            DueAction::Skip
+        );
+    }
+
+    #[test]
+    fn test_backup_and_cleanup() {
+        let temp_path = starcoin_config::temp_path();
+        let db = DBStorage::new(temp_path.path(), RocksdbConfig::default()).unwrap();
+        let backup_dir = temp_path.path().join("backup");
+        for now in 1..=3 {
+            let path = backup_storage(&db, backup_dir.as_path(), 2, now).unwrap();
+            assert!(path.is_dir());
+        }
+        let mut backups: Vec<_> = std::fs::read_dir(backup_dir.as_path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        backups.sort();
+        assert_eq!(backups, vec!["starcoindb_2", "starcoindb_3"]);
+
+        let diagnostics = temp_path.path().join("soak_check_1.json");
+        std::fs::write(diagnostics.as_path(), "{}").unwrap();
+        assert_eq!(
+            cleanup_diagnostics(temp_path.path(), Duration::from_secs(3600)).unwrap(),
+            0
+        );
+        assert!(diagnostics.exists());
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(
+            cleanup_diagnostics(temp_path.path(), Duration::from_millis(1)).unwrap(),
+            1
+        );
+        assert!(!diagnostics.exists());
+        assert!(backup_dir.exists());
+    }
+}
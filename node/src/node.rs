@@ -1,16 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::block_compression::BlockCompressionService;
+use crate::maintenance::{MaintenanceService, MaintenanceStatusRequest};
 use crate::metrics::MetricsActorService;
 use crate::network_service_factory::NetworkServiceFactory;
 use crate::peer_message_handler::NodePeerMessageHandler;
 use crate::rpc_service_factory::RpcServiceFactory;
+use crate::soak_check::SoakCheckService;
+use crate::usage_stats::{UsageReportRequest, UsageStatsService};
 use crate::NodeHandle;
 use actix::prelude::*;
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use futures::channel::oneshot;
 use futures::executor::block_on;
 use futures_timer::Delay;
+use starcoin_account_api::AccountAsyncService;
 use starcoin_account_service::{AccountEventService, AccountService, AccountStorage};
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_chain_notify::ChainNotifyHandlerService;
@@ -46,7 +51,8 @@ use starcoin_sync::announcement::AnnouncementService;
 use starcoin_sync::block_connector::BlockConnectorService;
 use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
-use starcoin_txpool::TxPoolActorService;
+use starcoin_sync_api::AcceptReorgRequest;
+use starcoin_txpool::{TxPoolActorService, TxnKeeperService};
 use starcoin_types::system_events::SystemStarted;
 use std::sync::Arc;
 use std::time::Duration;
@@ -69,7 +75,7 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
     fn handle(
         &mut self,
         msg: NodeRequest,
-        _ctx: &mut ServiceContext<NodeService>,
+        ctx: &mut ServiceContext<NodeService>,
     ) -> Result<NodeResponse> {
         Ok(match msg {
             NodeRequest::ListService => NodeResponse::Services(self.registry.list_service_sync()?),
@@ -115,6 +121,49 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
                 self.registry
                     .start_service_sync(GenerateBlockEventPacemaker::service_name()),
             ),
+            NodeRequest::MaintenanceStatus => {
+                let service = ctx
+                    .service_ref_opt::<MaintenanceService>()?
+                    .cloned()
+                    .ok_or_else(|| {
+                        format_err!(
+                            "The maintenance is not enabled, no maintenance task is scheduled."
+                        )
+                    })?;
+                NodeResponse::MaintenanceStatus(block_on(service.send(MaintenanceStatusRequest))?)
+            }
+            NodeRequest::UsageReport(last_seconds) => {
+                let service = ctx
+                    .service_ref_opt::<UsageStatsService>()?
+                    .cloned()
+                    .ok_or_else(|| {
+                        format_err!(
+                            "The usage stats is not enabled, set `metrics.usage_stats_interval` to record it."
+                        )
+                    })?;
+                NodeResponse::UsageReport(block_on(
+                    service.send(UsageReportRequest { last_seconds }),
+                )?)
+            }
+            NodeRequest::AcceptReorg(branch_head) => {
+                info!(
+                    "Receive AcceptReorg request, try to accept the pending reorg {:?}",
+                    branch_head
+                );
+                let service = ctx.service_ref::<BlockConnectorService>()?.clone();
+                NodeResponse::AcceptedReorg(block_on(
+                    service.send(AcceptReorgRequest { branch_head }),
+                )??)
+            }
+            NodeRequest::ApproveTxn(approval_hash) => {
+                info!("Receive ApproveTxn request, approve txn {}", approval_hash);
+                let service = ctx.service_ref::<AccountService>()?.clone();
+                NodeResponse::Result(block_on(service.approve_txn(approval_hash)))
+            }
+            NodeRequest::PendingTxnApprovals => {
+                let service = ctx.service_ref::<AccountService>()?.clone();
+                NodeResponse::PendingTxnApprovals(block_on(service.pending_txn_approvals())?)
+            }
         })
     }
 }
@@ -146,6 +195,12 @@ impl NodeService {
             } else {
                 warn!("slog config error.");
             }
+            for (subsystem, log_path, max_file_size, max_backup) in
+                config.logger.get_subsystem_log_files()
+            {
+                info!("Write {} log to file: {:?}", subsystem, log_path);
+                logger_handle.enable_subsystem_file(subsystem, log_path, max_file_size, max_backup);
+            }
         }
 
         if config.logger.disable_stderr() {
@@ -155,6 +210,7 @@ impl NodeService {
         }
 
         // start metric server
+        starcoin_metrics::metric_server::set_network(config.net().id().to_string());
         if let Some(metrics_address) = config.metrics.metrics_address() {
             starcoin_metrics::metric_server::start_server(metrics_address);
         }
@@ -200,10 +256,17 @@ impl NodeService {
         registry.put_shared(logger_handle).await?;
 
         let bus = registry.service_ref::<BusService>().await?;
-        let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        let storage_instance = StorageInstance::new_cache_and_db_instance(
             CacheStorage::new_with_capacity(config.storage.cache_size()),
             DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
-        ))?);
+        );
+        if let Some(db) = storage_instance.db() {
+            registry.put_shared(db).await?;
+        }
+        if let Some(cache) = storage_instance.cache() {
+            registry.put_shared(cache).await?;
+        }
+        let storage = Arc::new(Storage::new(storage_instance)?);
         registry.put_shared(storage.clone()).await?;
         let (chain_info, genesis) =
             Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
@@ -253,37 +316,64 @@ impl NodeService {
         Delay::new(Duration::from_millis(200)).await;
 
         registry.register::<TxnSyncService>().await?;
+        // the mirror node only observes the txpool, it does not accept the txn submission and mint blocks.
+        let mirror = config.tx_pool.is_mirror();
+        if mirror {
+            info!("Txpool mirror mode is enabled, the txn submission and the miner are disabled.");
+        } else {
+            registry.register::<TxnKeeperService>().await?;
+            #[cfg(feature = "threshold-encryption")]
+            if config.tx_pool.threshold_committee_file().is_some() {
+                registry
+                    .register::<starcoin_txpool::EncryptedPoolService>()
+                    .await?;
+            }
+        }
 
         let peer_id = config.network.self_peer_id();
 
         info!("Self peer_id is: {}", peer_id.to_base58());
         info!("Self address is: {}", config.network.self_address());
 
-        registry.register::<CreateBlockTemplateService>().await?;
-        let miner_service = registry.register::<MinerService>().await?;
+        if !mirror {
+            registry.register::<CreateBlockTemplateService>().await?;
+            let miner_service = registry.register::<MinerService>().await?;
+
+            if let Some(miner_client_config) = config.miner.miner_client_config() {
+                registry.put_shared(miner_client_config).await?;
+                let job_client =
+                    JobBusClient::new(miner_service, bus.clone(), config.net().time_service());
+                registry.put_shared(job_client).await?;
+                registry
+                    .register::<MinerClientService<JobBusClient>>()
+                    .await?;
+            } else {
+                info!("Config.miner.enable_miner_client is false, No in process MinerClient.");
+            }
 
-        if let Some(miner_client_config) = config.miner.miner_client_config() {
-            registry.put_shared(miner_client_config).await?;
-            let job_client =
-                JobBusClient::new(miner_service, bus.clone(), config.net().time_service());
-            registry.put_shared(job_client).await?;
             registry
-                .register::<MinerClientService<JobBusClient>>()
+                .register_by_factory::<Stratum, StratumFactory>()
                 .await?;
-        } else {
-            info!("Config.miner.enable_miner_client is false, No in process MinerClient.");
-        }
-
-        registry
-            .register_by_factory::<Stratum, StratumFactory>()
-            .await?;
 
-        registry.register::<GenerateBlockEventPacemaker>().await?;
+            registry.register::<GenerateBlockEventPacemaker>().await?;
+        }
 
         // start metrics push service
         if config.metrics.push_config.is_config() {
             registry.register::<MetricsActorService>().await?;
         }
+        if config.soak_check.is_enable() {
+            registry.register::<SoakCheckService>().await?;
+        }
+        if config.maintenance.is_enable() {
+            registry.register::<MaintenanceService>().await?;
+        }
+        if config.metrics.usage_stats_interval() > 0 {
+            registry.register::<UsageStatsService>().await?;
+        }
+        if config.storage.block_compression_depth().is_some() {
+            registry.register::<BlockCompressionService>().await?;
+        }
         // wait for service init.
         Delay::new(Duration::from_millis(1000)).await;
 
@@ -295,9 +385,11 @@ impl NodeService {
         registry
             .register_by_factory::<RpcService, RpcServiceFactory>()
             .await?;
-        registry
-            .register_by_factory::<StratumService, StratumServiceFactory>()
-            .await?;
+        if !mirror {
+            registry
+                .register_by_factory::<StratumService, StratumServiceFactory>()
+                .await?;
+        }
 
         Ok((registry, node_service))
     }
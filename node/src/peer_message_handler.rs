@@ -81,6 +81,19 @@ impl PeerMessageHandler for NodePeerMessageHandler {
                     }
                 }
             }
+            NotificationMessage::ConsensusHint(message) => {
+                // the chain status of the peer is updated by the network, the sync picks it up from the peer info.
+                debug!(
+                    "Receive consensus hint {}({}) from peer {}",
+                    message.header.number(),
+                    message.header.id(),
+                    peer_message.peer_id
+                );
+            }
+            NotificationMessage::IdentityLinkage(_) => {
+                // the identity linkage is handled by the network service.
+                debug!("Ignore identity linkage from peer {}", peer_message.peer_id);
+            }
         }
     }
 }
@@ -7,6 +7,7 @@ use starcoin_account_service::AccountService;
 use starcoin_chain_service::ChainReaderService;
 use starcoin_config::NodeConfig;
 use starcoin_dev::playground::PlaygroudService;
+use starcoin_dev::sandbox::SandboxConfig;
 use starcoin_genesis::Genesis;
 use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerService;
@@ -21,8 +22,9 @@ use starcoin_service_registry::{ServiceContext, ServiceFactory};
 use starcoin_state_service::ChainStateService;
 use starcoin_storage::Storage;
 use starcoin_sync::sync::SyncService;
-use starcoin_txpool::TxPoolService;
+use starcoin_txpool::{TxPoolService, TxnKeeperService};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct RpcServiceFactory;
 
@@ -48,22 +50,39 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 ChainRpcImpl::new(config.clone(), genesis.block().id(), service_ref.clone())
             });
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let mut txpool_api = TxPoolRpcImpl::new(txpool_service.clone())
+            .with_idempotency_key_retention(Duration::from_secs(
+                config.tx_pool.idempotency_key_retention(),
+            ));
+        if config.tx_pool.is_mirror() {
+            txpool_api = txpool_api.with_mirror_mode();
+        }
+        if let Some(txn_keeper) = ctx.service_ref_opt::<TxnKeeperService>()? {
+            txpool_api = txpool_api.with_txn_keeper(txn_keeper.clone());
+        }
 
-        let state_api = ctx
-            .service_ref_opt::<ChainStateService>()?
-            .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
         let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
+        let state_api = Some(StateRpcImpl::new(
+            config.clone(),
+            chain_state_service.clone(),
+            storage.clone(),
+            chain_service.clone(),
+        ));
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
+        let txn_keeper = ctx.service_ref_opt::<TxnKeeperService>()?.cloned();
         let account_api = account_service.clone().map(|service_ref| {
-            AccountRpcImpl::new(
+            let account_api = AccountRpcImpl::new(
                 config.clone(),
                 service_ref,
                 txpool_service.clone(),
                 chain_state_service.clone(),
                 chain_service.clone(),
-            )
+            );
+            match txn_keeper.clone() {
+                Some(txn_keeper) => account_api.with_txn_keeper(txn_keeper),
+                None => account_api,
+            }
         });
         let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
         let pubsub_api = Some(PubSubImpl::new(pubsub_service));
@@ -73,7 +92,14 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
 
         let contract_api = {
-            let dev_playground = PlaygroudService::new(storage);
+            let mut sandbox = SandboxConfig::default();
+            if let Some(time_limit) = config.rpc.dev_call_time_limit {
+                sandbox.time_limit = Duration::from_millis(time_limit);
+            }
+            if let Some(state_read_limit) = config.rpc.dev_call_state_read_limit {
+                sandbox.state_read_limit = state_read_limit;
+            }
+            let dev_playground = PlaygroudService::new_with_sandbox(storage, sandbox);
 
             ContractRpcImpl::new(
                 config.clone(),
@@ -85,20 +111,43 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             )
         };
 
-        Ok(RpcService::new_with_api(
+        #[cfg(feature = "threshold-encryption")]
+        let encrypted_txpool_api = ctx
+            .service_ref_opt::<starcoin_txpool::EncryptedPoolService>()?
+            .map(|service_ref| {
+                let api =
+                    starcoin_rpc_server::module::EncryptedTxPoolRpcImpl::new(service_ref.clone());
+                if config.tx_pool.is_mirror() {
+                    api.with_mirror_mode()
+                } else {
+                    api
+                }
+            });
+
+        #[allow(unused_mut)]
+        let mut rpc_service = RpcService::new_with_api(
             config,
             node_api,
             node_manager_api,
             sync_manager_api,
             Some(network_manager_api),
             chain_api,
-            txpool_api,
+            Some(txpool_api),
             account_api,
             state_api,
             pubsub_api,
             debug_api,
             miner_api,
             Some(contract_api),
-        ))
+        );
+        #[cfg(feature = "threshold-encryption")]
+        if let Some(encrypted_txpool_api) = encrypted_txpool_api {
+            use starcoin_rpc_server::module::EncryptedTxPoolApi;
+            rpc_service.register_api(
+                starcoin_config::Api::TxPool,
+                EncryptedTxPoolApi::to_delegate(encrypted_txpool_api),
+            );
+        }
+        Ok(rpc_service)
     }
 }
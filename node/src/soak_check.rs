@@ -0,0 +1,305 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_state_api::{AccountStateReader, StateReaderExt};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{Storage, Store};
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::TxPoolSyncService;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Continuously run the invariant checks of the node on test networks,
+/// and dump the diagnostics on the first violation, then stop checking.
+pub struct SoakCheckService {
+    config: Arc<NodeConfig>,
+    storage: Arc<dyn Store>,
+    txpool: TxPoolService,
+    stuck_tracker: StuckTracker,
+    violated: bool,
+}
+
+impl ServiceFactory<Self> for SoakCheckService {
+    fn create(ctx: &mut ServiceContext<SoakCheckService>) -> Result<SoakCheckService> {
+        Ok(Self {
+            config: ctx.get_shared::<Arc<NodeConfig>>()?,
+            storage: ctx.get_shared::<Arc<Storage>>()?,
+            txpool: ctx.get_shared::<TxPoolService>()?,
+            stuck_tracker: StuckTracker::default(),
+            violated: false,
+        })
+    }
+}
+
+impl ActorService for SoakCheckService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        let interval = Duration::from_secs(self.config.soak_check.interval());
+        info!("Start soak check, interval: {:?}", interval);
+        ctx.run_interval(interval, |ctx| ctx.notify(SoakCheckEvent));
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SoakCheckEvent;
+
+/// Track how long the head is unchanged while there are pending txns.
+#[derive(Debug, Default)]
+struct StuckTracker {
+    /// The head and the time since it is unchanged with pending txns.
+    head: Option<(HashValue, Instant)>,
+}
+
+impl StuckTracker {
+    /// Return how long the head is stuck, the time is reset when the head changes or the pool is empty,
+    /// so a pending txn arrived later is not counted as stuck since the head became the head.
+    fn update(&mut self, head_id: HashValue, has_pending: bool, now: Instant) -> Duration {
+        if !has_pending {
+            self.head = None;
+            return Duration::from_secs(0);
+        }
+        match self.head {
+            Some((id, since)) if id == head_id => now.saturating_duration_since(since),
+            _ => {
+                self.head = Some((head_id, now));
+                Duration::from_secs(0)
+            }
+        }
+    }
+}
+
+impl EventHandler<Self, SoakCheckEvent> for SoakCheckService {
+    fn handle_event(&mut self, _msg: SoakCheckEvent, _ctx: &mut ServiceContext<Self>) {
+        if self.violated {
+            return;
+        }
+        if let Err(violation) = self.check() {
+            self.violated = true;
+            error!("[soak-check] Invariant violated: {:?}", violation);
+            match self.dump_diagnostics(&violation.to_string()) {
+                Ok(path) => error!("[soak-check] Dump diagnostics to {}", path.display()),
+                Err(e) => error!("[soak-check] Dump diagnostics failed: {:?}", e),
+            }
+        }
+    }
+}
+
+impl SoakCheckService {
+    fn check(&mut self) -> Result<()> {
+        let head_id = self
+            .storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?
+            .main;
+        self.check_accumulator(head_id)?;
+        self.check_balances(head_id)?;
+        self.check_txpool()?;
+        self.check_stuck(head_id)
+    }
+
+    /// The accumulator roots of the head match the roots recomputed from the stored nodes.
+    fn check_accumulator(&self, head_id: HashValue) -> Result<()> {
+        let header = self
+            .storage
+            .get_block_header_by_hash(head_id)?
+            .ok_or_else(|| format_err!("Can not find head block header {}", head_id))?;
+        let block_info = self
+            .storage
+            .get_block_info(head_id)?
+            .ok_or_else(|| format_err!("Can not find head block info {}", head_id))?;
+        ensure!(
+            header.txn_accumulator_root() == block_info.txn_accumulator_info.accumulator_root,
+            "Txn accumulator root of head {} mismatch, header: {}, block info: {}",
+            head_id,
+            header.txn_accumulator_root(),
+            block_info.txn_accumulator_info.accumulator_root
+        );
+
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            block_info.block_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        ensure!(
+            block_accumulator.num_leaves() == header.number() + 1,
+            "Block accumulator of head {} has {} leaves, expect {}",
+            head_id,
+            block_accumulator.num_leaves(),
+            header.number() + 1
+        );
+        let proof = block_accumulator
+            .get_proof(header.number())?
+            .ok_or_else(|| {
+                format_err!("Can not get block accumulator proof of head {}", head_id)
+            })?;
+        proof.verify(block_accumulator.root_hash(), head_id, header.number())?;
+
+        if header.number() > 0 {
+            let parent_info = self
+                .storage
+                .get_block_info(header.parent_hash())?
+                .ok_or_else(|| {
+                    format_err!("Can not find block info of parent {}", header.parent_hash())
+                })?;
+            ensure!(
+                header.block_accumulator_root()
+                    == parent_info.block_accumulator_info.accumulator_root,
+                "Block accumulator root of head {} mismatch with parent, header: {}, parent: {}",
+                head_id,
+                header.block_accumulator_root(),
+                parent_info.block_accumulator_info.accumulator_root
+            );
+        }
+        Ok(())
+    }
+
+    /// The balance can not be negative, a balance underflow wraps to a value bigger than the total supply,
+    /// so check the balances of the accounts touched by the head block do not exceed the total supply.
+    fn check_balances(&self, head_id: HashValue) -> Result<()> {
+        let block = self
+            .storage
+            .get_block_by_hash(head_id)?
+            .ok_or_else(|| format_err!("Can not find head block {}", head_id))?;
+        let state = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(block.header().state_root()),
+        );
+        let total_value = state
+            .get_stc_info()?
+            .ok_or_else(|| format_err!("Can not find STC token info"))?
+            .total_value();
+        let reader = AccountStateReader::new(&state);
+        let mut addresses: BTreeSet<_> = block
+            .transactions()
+            .iter()
+            .map(|txn| txn.sender())
+            .collect();
+        addresses.insert(block.header().author());
+        for address in addresses {
+            let balance = reader.get_balance(&address)?.unwrap_or_default();
+            ensure!(
+                balance <= total_value,
+                "STC balance {} of {} exceeds the total supply {}",
+                balance,
+                address,
+                total_value
+            );
+        }
+        Ok(())
+    }
+
+    fn check_txpool(&self) -> Result<()> {
+        let status = self.txpool.status();
+        ensure!(
+            status.txn_count <= status.txn_max_count,
+            "Txpool size {} exceeds the max count {}",
+            status.txn_count,
+            status.txn_max_count
+        );
+        Ok(())
+    }
+
+    /// The head should be changed in the stuck timeout while there are pending txns.
+    fn check_stuck(&mut self, head_id: HashValue) -> Result<()> {
+        let timeout = Duration::from_secs(self.config.soak_check.stuck_timeout());
+        let pending = self.txpool.status().txn_count;
+        let stuck = self
+            .stuck_tracker
+            .update(head_id, pending > 0, Instant::now());
+        ensure!(
+            stuck < timeout,
+            "Head block {} is stuck for {:?} with {} pending txns",
+            head_id,
+            stuck,
+            pending
+        );
+        Ok(())
+    }
+
+    fn dump_diagnostics(&self, violation: &str) -> Result<PathBuf> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let startup_info = self.storage.get_startup_info().ok().flatten();
+        let head_id = startup_info.as_ref().map(|info| info.main);
+        let head = head_id.and_then(|id| self.storage.get_block_header_by_hash(id).ok().flatten());
+        let head_info = head_id.and_then(|id| self.storage.get_block_info(id).ok().flatten());
+        let diagnostics = serde_json::json!({
+            "violation": violation,
+            "timestamp": now,
+            "net": self.config.net().id().to_string(),
+            "startup_info": startup_info,
+            "head": head,
+            "head_block_info": head_info,
+            "txpool": self.txpool.status(),
+        });
+        let path = self
+            .config
+            .data_dir()
+            .join(format!("soak_check_{}.json", now));
+        std::fs::write(&path, serde_json::to_string_pretty(&diagnostics)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stuck_tracker() {
+        let mut tracker = StuckTracker::default();
+        let head = HashValue::random();
+        let start = Instant::now();
+        let later = |secs| start + Duration::from_secs(secs);
+
+        // the head is not stuck without pending txns.
+        assert_eq!(tracker.update(head, false, start), Duration::from_secs(0));
+        assert_eq!(
+            tracker.update(head, false, later(100)),
+            Duration::from_secs(0)
+        );
+
+        // the stuck time starts when the pending txn arrives.
+        assert_eq!(
+            tracker.update(head, true, later(200)),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            tracker.update(head, true, later(230)),
+            Duration::from_secs(30)
+        );
+
+        // the pool is empty, then an unrelated txn arrives.
+        assert_eq!(
+            tracker.update(head, false, later(240)),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            tracker.update(head, true, later(300)),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            tracker.update(head, true, later(310)),
+            Duration::from_secs(10)
+        );
+
+        // the head changes.
+        let new_head = HashValue::random();
+        assert_eq!(
+            tracker.update(new_head, true, later(320)),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            tracker.update(new_head, true, later(325)),
+            Duration::from_secs(5)
+        );
+    }
+}
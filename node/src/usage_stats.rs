@@ -0,0 +1,125 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
+use starcoin_node_api::usage::{prune_snapshots, UsageReport, UsageSnapshot, UsageStatsStore};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRequest,
+};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Record the local usage stats periodically for the `node report`.
+///
+/// The recorded snapshots are loaded once at start and kept in memory, the file is only
+/// appended and pruned, so the reports do not read the file.
+pub struct UsageStatsService {
+    config: Arc<NodeConfig>,
+    store: UsageStatsStore,
+    snapshots: Vec<UsageSnapshot>,
+}
+
+impl ServiceFactory<Self> for UsageStatsService {
+    fn create(ctx: &mut ServiceContext<UsageStatsService>) -> Result<UsageStatsService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let store = UsageStatsStore::new(config.metrics.usage_stats_file());
+        let snapshots = store.load().unwrap_or_else(|e| {
+            warn!("Load usage stats failed, start from empty: {:?}", e);
+            vec![]
+        });
+        Ok(Self {
+            config,
+            store,
+            snapshots,
+        })
+    }
+}
+
+impl ActorService for UsageStatsService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        let interval = Duration::from_secs(self.config.metrics.usage_stats_interval());
+        info!(
+            "Start recording usage stats to {}, interval: {:?}",
+            self.store.path().display(),
+            interval
+        );
+        ctx.run_interval(interval, |ctx| ctx.notify(RecordUsageEvent));
+        Ok(())
+    }
+
+    fn stopped(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+        // keep the stats since the last record.
+        self.record();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RecordUsageEvent;
+
+impl EventHandler<Self, RecordUsageEvent> for UsageStatsService {
+    fn handle_event(&mut self, _msg: RecordUsageEvent, _ctx: &mut ServiceContext<Self>) {
+        self.record();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UsageReportRequest {
+    pub last_seconds: u64,
+}
+
+impl ServiceRequest for UsageReportRequest {
+    type Response = UsageReport;
+}
+
+impl ServiceHandler<Self, UsageReportRequest> for UsageStatsService {
+    fn handle(
+        &mut self,
+        msg: UsageReportRequest,
+        _ctx: &mut ServiceContext<UsageStatsService>,
+    ) -> UsageReport {
+        let now = now_secs();
+        let mut snapshots = self.snapshots.clone();
+        // count the usage since the last record.
+        snapshots.push(UsageSnapshot::from_metrics(
+            now,
+            &starcoin_metrics::get_all_metrics(),
+        ));
+        UsageReport::from_snapshots(&snapshots, now.saturating_sub(msg.last_seconds))
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+impl UsageStatsService {
+    fn record(&mut self) {
+        let now = now_secs();
+        let snapshot = UsageSnapshot::from_metrics(now, &starcoin_metrics::get_all_metrics());
+        if let Err(e) = self.store.append(&snapshot) {
+            warn!("Record usage stats failed: {:?}", e);
+            return;
+        }
+        self.snapshots.push(snapshot);
+        let retention = self
+            .config
+            .metrics
+            .usage_stats_retention_days()
+            .saturating_mul(SECONDS_PER_DAY);
+        let before = now.saturating_sub(retention);
+        if prune_snapshots(&mut self.snapshots, before) == 0 {
+            return;
+        }
+        if let Err(e) = self.store.prune(before) {
+            warn!("Prune usage stats failed: {:?}", e);
+        }
+    }
+}
@@ -6,6 +6,9 @@ use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_node::run_node;
 use starcoin_node_api::node_service::NodeAsyncService;
+use starcoin_rpc_client::RpcClient;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::transaction::SignedUserTransaction;
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -44,3 +47,37 @@ fn test_generate_block() {
     );
     handle.stop().unwrap()
 }
+
+#[stest::test]
+fn test_mirror_node() {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.network.disable_seed = true;
+    node_config.tx_pool.set_mirror(true);
+    let config = Arc::new(node_config);
+    let handle = run_node(config).unwrap();
+    let services = handle.list_service().unwrap();
+    for service in &[
+        "MinerService",
+        "GenerateBlockEventPacemaker",
+        "TxnKeeperService",
+    ] {
+        assert!(
+            services.iter().all(|info| !info.name.contains(service)),
+            "{} should not run on a mirror node",
+            service
+        );
+    }
+
+    let chain_service = handle.chain_service().unwrap();
+    let latest_block = block_on(async { chain_service.main_head_block().await }).unwrap();
+    assert!(handle.generate_block().is_err());
+    let latest_block2 = block_on(async { chain_service.main_head_block().await }).unwrap();
+    assert_eq!(latest_block.id(), latest_block2.id());
+
+    let client = RpcClient::connect_local(handle.rpc_service().unwrap()).unwrap();
+    assert!(client
+        .submit_transaction(SignedUserTransaction::mock())
+        .is_err());
+    assert!(handle.txpool().get_pending_txns(None, None).is_empty());
+    handle.stop().unwrap()
+}
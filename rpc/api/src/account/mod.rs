@@ -6,7 +6,9 @@ use jsonrpc_derive::rpc;
 pub use self::gen_client::Client as AccountClient;
 use crate::types::{StrView, TransactionRequest};
 use crate::FutureResult;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, AccountMetadataUpdate, KeystoreEntry};
+use starcoin_crypto::HashValue;
+use starcoin_txpool_api::TxnKeepPolicy;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
@@ -44,12 +46,15 @@ pub trait AccountApi {
     ) -> FutureResult<SignedUserTransaction>;
 
     /// unlock account for duration in seconds, default to u32::max.
+    /// If `max_amount` is present, the txns signed before the account locked can spend at most `max_amount` nanoSTC,
+    /// and the txns whose spend amount is unknown are rejected unless the node allows unknown spend.
     #[rpc(name = "account.unlock")]
     fn unlock(
         &self,
         address: AccountAddress,
         password: String,
         duration: Option<u32>,
+        max_amount: Option<StrView<u128>>,
     ) -> FutureResult<AccountInfo>;
     #[rpc(name = "account.lock")]
     fn lock(&self, address: AccountAddress) -> FutureResult<AccountInfo>;
@@ -75,6 +80,28 @@ pub trait AccountApi {
     #[rpc(name = "account.export")]
     fn export(&self, address: AccountAddress, password: String) -> FutureResult<Vec<u8>>;
 
+    /// Derive an account from mnemonic by the hd path, such as `m/44'/101010'/0'/0'/0'`.
+    #[rpc(name = "account.derive")]
+    fn derive(&self, mnemonic: String, path: String, password: String)
+        -> FutureResult<AccountInfo>;
+
+    /// Restore the first `count` BIP44 accounts derived from mnemonic, `count` is at most 1000.
+    #[rpc(name = "account.restore")]
+    fn restore(
+        &self,
+        mnemonic: String,
+        password: String,
+        count: u32,
+    ) -> FutureResult<Vec<AccountInfo>>;
+
+    /// Export all accounts of local wallet for backup, the private keys are kept encrypted by the account passwords.
+    #[rpc(name = "account.export_keystore")]
+    fn export_keystore(&self) -> FutureResult<Vec<KeystoreEntry>>;
+
+    /// Import the accounts of a keystore backup, the accounts already exist are kept.
+    #[rpc(name = "account.import_keystore")]
+    fn import_keystore(&self, entries: Vec<KeystoreEntry>) -> FutureResult<Vec<AccountInfo>>;
+
     #[rpc(name = "account.change_password")]
     /// change account password, user need to unlock account first.
     fn change_account_password(
@@ -93,4 +120,22 @@ pub trait AccountApi {
         address: AccountAddress,
         password: Option<String>,
     ) -> FutureResult<AccountInfo>;
+
+    /// update the notes and tags of the account in local wallet.
+    #[rpc(name = "account.update_metadata")]
+    fn update_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> FutureResult<AccountInfo>;
+
+    /// Submit the txn and keep it until it is included in a block, the node re-broadcasts it to peers,
+    /// and re-signs it with a higher gas price by the unlocked sender account when the expiration nears
+    /// if the policy allows. The txn with the re-sign policy is only accepted by this api.
+    #[rpc(name = "account.submit_txn_with_policy")]
+    fn submit_txn_with_policy(
+        &self,
+        txn: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> FutureResult<HashValue>;
 }
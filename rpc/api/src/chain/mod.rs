@@ -5,13 +5,14 @@ pub use self::gen_client::Client as ChainClient;
 use crate::types::pubsub::EventFilter;
 use crate::types::{
     BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    TotalSupplyView, TransactionEventView, TransactionInfoView, TransactionView,
 };
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
 use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
 
 #[rpc]
@@ -60,6 +61,15 @@ pub trait ChainApi {
         idx: u64,
     ) -> FutureResult<Option<TransactionInfoView>>;
 
+    /// Get txn info of txn `transaction_hash` in block `block_hash` with its accumulator proof,
+    /// the proof can be verified against the txn_accumulator_root of the block header.
+    #[rpc(name = "chain.get_transaction_proof")]
+    fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        transaction_hash: HashValue,
+    ) -> FutureResult<Option<TransactionInfoWithProof>>;
+
     #[rpc(name = "chain.get_events_by_txn_hash")]
     fn get_events_by_txn_hash(
         &self,
@@ -81,6 +91,10 @@ pub trait ChainApi {
     #[rpc(name = "chain.get_global_time_by_number")]
     fn get_global_time_by_number(&self, number: BlockNumber) -> FutureResult<GlobalTimeOnChain>;
 
+    /// Get the STC total supply and treasury balance at the state of the block `number`.
+    #[rpc(name = "chain.total_supply_at")]
+    fn total_supply_at(&self, number: BlockNumber) -> FutureResult<TotalSupplyView>;
+
     /// Get uncles by number.
     #[rpc(name = "chain.get_epoch_uncles_by_number")]
     fn get_epoch_uncles_by_number(
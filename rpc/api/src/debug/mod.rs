@@ -3,7 +3,7 @@
 
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
-use starcoin_logger::LogPattern;
+use starcoin_logger::{LogPattern, LogSubsystem};
 
 pub use self::gen_client::Client as DebugClient;
 use crate::types::FactoryAction;
@@ -18,6 +18,11 @@ pub trait DebugApi {
     #[rpc(name = "debug.set_log_pattern")]
     fn set_log_pattern(&self, pattern: LogPattern) -> Result<()>;
 
+    /// Get the last `lines` lines of the subsystem log file, if subsystem is none, get the main log file.
+    /// The `lines` should not be greater than the `log_tail_max_lines` of the rpc config.
+    #[rpc(name = "debug.log_tail")]
+    fn log_tail(&self, subsystem: Option<LogSubsystem>, lines: usize) -> Result<Vec<String>>;
+
     ///Trigger the node panic, only work for dev network.
     #[rpc(name = "debug.panic")]
     fn panic(&self) -> Result<()>;
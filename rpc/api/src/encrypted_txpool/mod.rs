@@ -0,0 +1,32 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use crate::FutureResult;
+use jsonrpc_derive::rpc;
+use starcoin_crypto::HashValue;
+use starcoin_txpool_api::{DecryptionShare, EncryptedTransaction, SealedEncryptedTransaction};
+
+pub use self::gen_client::Client as EncryptedTxPoolClient;
+
+/// The api of the experimental threshold-encrypted pool, only available in the node built with
+/// the `threshold-encryption` feature, it is registered in the `txpool` api set.
+#[rpc]
+pub trait EncryptedTxPoolApi {
+    /// Submit a txn encrypted to the threshold committee to the experimental encrypted pool,
+    /// return the id of the encrypted txn.
+    #[rpc(name = "txpool.submit_encrypted_transaction")]
+    fn submit_encrypted_transaction(&self, tx: EncryptedTransaction) -> FutureResult<HashValue>;
+
+    /// Submit the decryption shares of the sealed encrypted txns,
+    /// return the hashes of the txns decrypted and added to the txpool.
+    /// The request is rejected as a whole if any share is invalid.
+    #[rpc(name = "txpool.submit_decryption_shares")]
+    fn submit_decryption_shares(
+        &self,
+        shares: Vec<DecryptionShare>,
+    ) -> FutureResult<Vec<HashValue>>;
+
+    /// Get the sealed encrypted txns waiting for the decryption shares.
+    #[rpc(name = "txpool.sealed_encrypted_transactions")]
+    fn sealed_encrypted_transactions(&self) -> FutureResult<Vec<SealedEncryptedTransaction>>;
+}
@@ -9,6 +9,8 @@ pub mod account;
 pub mod chain;
 pub mod contract_api;
 pub mod debug;
+#[cfg(feature = "threshold-encryption")]
+pub mod encrypted_txpool;
 pub mod errors;
 pub mod metadata;
 pub mod miner;
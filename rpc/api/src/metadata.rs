@@ -2,8 +2,46 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use jsonrpc_pubsub::{PubSubMetadata, Session};
+use parking_lot::RwLock;
 use std::sync::Arc;
 
+/// The notification setting the trace id of the following calls on the connection.
+pub const TRACE_NOTIFICATION: &str = "rpc.trace";
+/// The http header of the trace id of the request.
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+/// The max length of the trace id.
+pub const MAX_TRACE_ID_LEN: usize = 32;
+
+/// The trace id is written to the node logs, only the hex string not longer than `MAX_TRACE_ID_LEN` is accepted.
+pub fn is_valid_trace_id(trace_id: &str) -> bool {
+    !trace_id.is_empty()
+        && trace_id.len() <= MAX_TRACE_ID_LEN
+        && trace_id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The trace id of the user action the rpc calls belong to, the node logs the calls with it.
+#[derive(Clone, Default, Debug)]
+pub struct TraceId(Arc<RwLock<Option<String>>>);
+
+impl TraceId {
+    /// The invalid trace id is dropped, see `is_valid_trace_id`.
+    pub fn new(trace_id: Option<String>) -> Self {
+        Self(Arc::new(RwLock::new(
+            trace_id.filter(|trace_id| is_valid_trace_id(trace_id)),
+        )))
+    }
+
+    pub fn get(&self) -> Option<String> {
+        self.0.read().clone()
+    }
+
+    /// Set the trace id of the connection, the clones of the metadata share it.
+    /// The invalid trace id is dropped, see `is_valid_trace_id`.
+    pub fn set(&self, trace_id: Option<String>) {
+        *self.0.write() = trace_id.filter(|trace_id| is_valid_trace_id(trace_id));
+    }
+}
+
 /// RPC methods metadata.
 #[derive(Clone, Default, Debug)]
 pub struct Metadata {
@@ -12,6 +50,7 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    pub trace_id: TraceId,
 }
 
 impl Metadata {
@@ -19,6 +58,7 @@ impl Metadata {
         Self {
             session: Some(session),
             user: None,
+            trace_id: TraceId::default(),
         }
     }
 }
@@ -5,7 +5,7 @@ pub use self::gen_client::Client as MinerClient;
 use crate::types::MintedBlockView;
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{MiningWork, MintBlockEvent};
 
 #[rpc]
 pub trait MinerApi {
@@ -20,4 +20,9 @@ pub trait MinerApi {
     /// get current mining job
     #[rpc(name = "mining.get_job")]
     fn get_job(&self) -> FutureResult<Option<MintBlockEvent>>;
+    /// get current mining work for the pool worker, the worker can roll the nonce and
+    /// the extranonce region of the work's extra, then submit by `mining.submit`.
+    /// Return error if the worker id does not fit in the worker prefix of the extra.
+    #[rpc(name = "mining.get_work")]
+    fn get_work(&self, worker_id: u32) -> FutureResult<Option<MiningWork>>;
 }
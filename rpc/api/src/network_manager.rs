@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as NetworkManagerClient;
-use crate::types::StrView;
+use crate::types::{BannedPeerView, IdentityRotationView, PeerStatusView, StrView};
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
 use network_p2p_types::network_state::NetworkState;
@@ -23,6 +23,36 @@ pub trait NetworkManagerApi {
     #[rpc(name = "network_manager.add_peer")]
     fn add_peer(&self, peer: String) -> FutureResult<()>;
 
+    /// Connected peers with latency and reputation.
+    #[rpc(name = "network_manager.peers")]
+    fn peers(&self) -> FutureResult<Vec<PeerStatusView>>;
+
+    /// Ban a peer for `duration` seconds, the ban list is persisted across restarts.
+    #[rpc(name = "network_manager.ban_peer")]
+    fn ban_peer(&self, peer_id: String, duration: u64) -> FutureResult<BannedPeerView>;
+
+    /// Return true if the peer was banned.
+    #[rpc(name = "network_manager.unban_peer")]
+    fn unban_peer(&self, peer_id: String) -> FutureResult<bool>;
+
+    #[rpc(name = "network_manager.banned_peers")]
+    fn banned_peers(&self) -> FutureResult<Vec<BannedPeerView>>;
+
+    /// Add an always-connect peer, format: multiaddr/p2p/peer_id.
+    #[rpc(name = "network_manager.trust_peer")]
+    fn trust_peer(&self, peer: String) -> FutureResult<()>;
+
+    /// Return true if the peer was trusted.
+    #[rpc(name = "network_manager.untrust_peer")]
+    fn untrust_peer(&self, peer: String) -> FutureResult<bool>;
+
+    #[rpc(name = "network_manager.trusted_peers")]
+    fn trusted_peers(&self) -> FutureResult<Vec<String>>;
+
+    /// Rotate the network identity key, restart the node to use the new identity.
+    #[rpc(name = "network_manager.rotate_identity")]
+    fn rotate_identity(&self) -> FutureResult<IdentityRotationView>;
+
     /// Call peer's network rpc method.
     #[rpc(name = "network_manager.call")]
     fn call_peer(
@@ -4,7 +4,12 @@
 pub use self::gen_client::Client as NodeManagerClient;
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
+use starcoin_account_api::PendingTxnApproval;
+use starcoin_crypto::HashValue;
+use starcoin_node_api::maintenance::MaintenanceStatus;
+use starcoin_node_api::usage::UsageReport;
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use starcoin_sync_api::PendingReorg;
 
 #[rpc]
 pub trait NodeManagerApi {
@@ -22,4 +27,25 @@ pub trait NodeManagerApi {
 
     #[rpc(name = "node_manager.shutdown_system")]
     fn shutdown_system(&self) -> FutureResult<()>;
+
+    /// Get the status of the scheduled maintenance tasks of the node.
+    #[rpc(name = "node_manager.maintenance_status")]
+    fn maintenance_status(&self) -> FutureResult<MaintenanceStatus>;
+
+    /// Summarize the local usage stats of the last `last_seconds`.
+    #[rpc(name = "node_manager.usage_report")]
+    fn usage_report(&self, last_seconds: u64) -> FutureResult<UsageReport>;
+
+    /// Accept the reorg halted by the max reorg depth, and switch the main chain to its branch.
+    /// If the branch head is given, it must be the head of the pending reorg.
+    #[rpc(name = "node_manager.accept_reorg")]
+    fn accept_reorg(&self, branch_head: Option<HashValue>) -> FutureResult<PendingReorg>;
+
+    /// Approve the txn the sign policy asks for by its approval hash, so it is signed next time.
+    #[rpc(name = "node_manager.approve_txn")]
+    fn approve_txn(&self, approval_hash: HashValue) -> FutureResult<()>;
+
+    /// The txns waiting for approval of the sign policy.
+    #[rpc(name = "node_manager.pending_txn_approvals")]
+    fn pending_txn_approvals(&self) -> FutureResult<Vec<PendingTxnApproval>>;
 }
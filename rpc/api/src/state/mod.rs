@@ -6,10 +6,14 @@ use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
+    block::BlockNumber,
 };
+use starcoin_vm_types::language_storage::StructTag;
 
 pub use self::gen_client::Client as StateClient;
-use crate::types::{AccountStateSetView, StateWithProofView};
+use crate::types::{
+    AccountStateSetView, AnnotatedMoveStructView, StateRootStatusView, StateWithProofView, StrView,
+};
 
 #[rpc]
 pub trait StateApi {
@@ -38,4 +42,28 @@ pub trait StateApi {
         access_path: AccessPath,
         state_root: HashValue,
     ) -> FutureResult<StateWithProofView>;
+
+    /// Check whether the state at the `state_root` is available in the node,
+    /// list the available roots of the latest main chain blocks if it is not.
+    #[rpc(name = "state.get_state_root_status")]
+    fn get_state_root_status(&self, state_root: HashValue) -> FutureResult<StateRootStatusView>;
+
+    /// Get the resource of the `address` at the state of the main chain block `block_number`.
+    #[rpc(name = "state.get_resource_at")]
+    fn get_resource_at(
+        &self,
+        address: AccountAddress,
+        resource_type: StrView<StructTag>,
+        block_number: BlockNumber,
+    ) -> FutureResult<Option<AnnotatedMoveStructView>>;
+
+    /// Get the resource of the `address` at the states of the main chain blocks `block_numbers`,
+    /// the result is in the same order as `block_numbers`.
+    #[rpc(name = "state.get_resource_at_blocks")]
+    fn get_resource_at_blocks(
+        &self,
+        address: AccountAddress,
+        resource_type: StrView<StructTag>,
+        block_numbers: Vec<BlockNumber>,
+    ) -> FutureResult<Vec<Option<AnnotatedMoveStructView>>>;
 }
@@ -1,6 +1,9 @@
-use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
+use crate::types::{BlockView, ContractCall, TransactionArgumentView, TypeTagView};
+use starcoin_types::block::{Block, BlockBody, BlockHeaderBuilder};
 use starcoin_vm_types::token::stc::stc_type_tag;
+use starcoin_vm_types::transaction::SignedUserTransaction;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
+use std::convert::TryFrom;
 
 #[test]
 fn test_view_of_type_tag() {
@@ -32,3 +35,23 @@ fn test_deserialize() {
     let v = serde_json::from_str::<ContractCall>(s).unwrap();
     println!("{:?}", v);
 }
+
+#[test]
+fn test_block_from_view() {
+    let uncle = BlockHeaderBuilder::random().build();
+    for uncles in vec![None, Some(vec![]), Some(vec![uncle])] {
+        let body = BlockBody::new(vec![SignedUserTransaction::mock()], uncles);
+        let header = BlockHeaderBuilder::random()
+            .with_body_hash(body.hash())
+            .build();
+        let block = Block::new(header, body);
+        let view = BlockView::try_from(block.clone()).unwrap();
+        let s = serde_json::to_string(&view).unwrap();
+        let view: BlockView = serde_json::from_str(s.as_str()).unwrap();
+        assert_eq!(Block::try_from(view).unwrap(), block);
+
+        // the txns are required.
+        let thin_view = BlockView::try_from_block(block, true).unwrap();
+        assert!(Block::try_from(thin_view).is_err());
+    }
+}
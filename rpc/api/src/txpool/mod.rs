@@ -8,16 +8,44 @@ use starcoin_types::transaction::SignedUserTransaction;
 pub use self::gen_client::Client as TxPoolClient;
 use crate::types::{SignedUserTransactionView, StrView};
 use starcoin_crypto::HashValue;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_txpool_api::{TxPoolAnalytics, TxPoolStatus, TxnKeepPolicy, TxnValidation};
 use starcoin_types::account_address::AccountAddress;
 
 #[rpc]
 pub trait TxPoolApi {
+    /// Submit the txn to the txpool, the submission with an `idempotency_key` is safe to retry,
+    /// the node returns the original result of the key within the retention window.
+    /// The key is scoped by the txn sender, and only the accepted or the permanently rejected
+    /// results are kept, so the txn rejected by a transient error, such as the full pool,
+    /// can be retried with the same key.
     #[rpc(name = "txpool.submit_transaction")]
-    fn submit_transaction(&self, tx: SignedUserTransaction) -> FutureResult<HashValue>;
+    fn submit_transaction(
+        &self,
+        tx: SignedUserTransaction,
+        idempotency_key: Option<String>,
+    ) -> FutureResult<HashValue>;
 
+    /// Submit the hex encoded bcs bytes of the txn, same as `txpool.submit_transaction`.
     #[rpc(name = "txpool.submit_hex_transaction")]
-    fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue>;
+    fn submit_hex_transaction(
+        &self,
+        tx: String,
+        idempotency_key: Option<String>,
+    ) -> FutureResult<HashValue>;
+
+    /// Submit the txn and keep it until it is included in a block,
+    /// the node re-broadcasts it to peers. The re-sign policy is rejected by this api,
+    /// submit the txn by `account.submit_txn_with_policy` to re-sign it by the account of the node.
+    #[rpc(name = "txpool.submit_transaction_with_policy")]
+    fn submit_transaction_with_policy(
+        &self,
+        tx: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> FutureResult<HashValue>;
+
+    /// Run all txpool admission checks on the txn without inserting it into the pool.
+    #[rpc(name = "txpool.validate_transaction")]
+    fn validate_transaction(&self, tx: SignedUserTransaction) -> FutureResult<TxnValidation>;
 
     /// return current gas price
     #[rpc(name = "txpool.gas_price")]
@@ -43,4 +71,10 @@ pub trait TxPoolApi {
     /// or `None` if there are no pending transactions from that sender in txpool.
     #[rpc(name = "txpool.state")]
     fn state(&self) -> FutureResult<TxPoolStatus>;
+
+    /// Get the analytics of the txns in the txpool, the readiness, the gas price distribution
+    /// and the `top_senders`(default to 10, at most 100) senders of the most txns.
+    /// The result is cached for a few seconds, so it may lag behind the txpool.
+    #[rpc(name = "txpool.analytics")]
+    fn analytics(&self, top_senders: Option<u32>) -> FutureResult<TxPoolAnalytics>;
 }
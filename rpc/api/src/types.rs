@@ -16,7 +16,7 @@ use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterialStringE
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 use starcoin_service_registry::ServiceRequest;
 use starcoin_state_api::{StateProof, StateWithProof};
-use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_address::{parse_address, AccountAddress};
 use starcoin_types::block::{
     Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber, BlockSummary,
     EpochUncleSummary, UncleSummary,
@@ -33,9 +33,11 @@ use starcoin_types::transaction::{RawUserTransaction, ScriptFunction, Transactio
 use starcoin_types::vm_error::AbortLocation;
 use starcoin_types::U256;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::{core_code_address, DepositEvent};
 use starcoin_vm_types::block_metadata::BlockMetadata;
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::{FunctionId, ModuleId, StructTag};
+use starcoin_vm_types::move_resource::MoveResource;
 use starcoin_vm_types::parser::{parse_transaction_argument, parse_type_tag};
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 use starcoin_vm_types::transaction::{
@@ -387,6 +389,36 @@ impl From<BlockHeader> for BlockHeaderView {
     }
 }
 
+impl TryFrom<BlockHeaderView> for BlockHeader {
+    type Error = anyhow::Error;
+
+    fn try_from(view: BlockHeaderView) -> Result<Self, Self::Error> {
+        let header = BlockHeader::new(
+            view.parent_hash,
+            view.timestamp.0,
+            view.number.0,
+            view.author,
+            view.author_auth_key,
+            view.txn_accumulator_root,
+            view.block_accumulator_root,
+            view.state_root,
+            view.gas_used.0,
+            view.difficulty,
+            view.body_hash,
+            genesis_config::ChainId::new(view.chain_id),
+            view.nonce,
+            view.extra,
+        );
+        anyhow::ensure!(
+            header.id() == view.block_hash,
+            "The block header view {} mismatch with its hash {}",
+            view.block_hash,
+            header.id()
+        );
+        Ok(header)
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RawUserTransactionView {
     /// Sender's address.
@@ -412,6 +444,38 @@ pub struct RawUserTransactionView {
     // u64::max_value().
     pub expiration_timestamp_secs: StrView<u64>,
     pub chain_id: u8,
+    /// The payment reference of the transfer to a receipt identifier v2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_reference: Option<StrView<Vec<u8>>>,
+}
+
+/// The payment reference of the transfer to a receipt identifier v2, it is the metadata of the transfer.
+fn payment_reference_of_payload(payload: &TransactionPayload) -> Option<Vec<u8>> {
+    match payload {
+        TransactionPayload::ScriptFunction(function)
+            if function.module().address() == &core_code_address()
+                && function.module().name().as_str() == "TransferScripts"
+                && function.function().as_str() == "peer_to_peer_with_metadata" =>
+        {
+            function
+                .args()
+                .get(3)
+                .and_then(|arg| bcs_ext::from_bytes::<Vec<u8>>(arg).ok())
+                .filter(|metadata| !metadata.is_empty())
+        }
+        _ => None,
+    }
+}
+
+/// The payment reference in the metadata of the deposit event.
+fn payment_reference_of_event(event: &ContractEvent) -> Option<Vec<u8>> {
+    if event.type_tag() != &DepositEvent::type_tag() {
+        return None;
+    }
+    DepositEvent::try_from_bytes(event.event_data())
+        .ok()
+        .map(|event| event.metadata().clone())
+        .filter(|metadata| !metadata.is_empty())
 }
 
 impl TryFrom<RawUserTransaction> for RawUserTransactionView {
@@ -426,11 +490,29 @@ impl TryFrom<RawUserTransaction> for RawUserTransactionView {
             gas_token_code: origin.gas_token_code(),
             expiration_timestamp_secs: origin.expiration_timestamp_secs().into(),
             chain_id: origin.chain_id().id(),
+            payment_reference: payment_reference_of_payload(origin.payload()).map(StrView),
             payload: StrView(origin.into_payload().encode()?),
         })
     }
 }
 
+impl TryFrom<RawUserTransactionView> for RawUserTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(view: RawUserTransactionView) -> Result<Self, Self::Error> {
+        Ok(RawUserTransaction::new(
+            view.sender,
+            view.sequence_number.0,
+            TransactionPayload::decode(view.payload.0.as_slice())?,
+            view.max_gas_amount.0,
+            view.gas_unit_price.0,
+            view.expiration_timestamp_secs.0,
+            genesis_config::ChainId::new(view.chain_id),
+            view.gas_token_code,
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SignedUserTransactionView {
     pub transaction_hash: HashValue,
@@ -455,6 +537,21 @@ impl TryFrom<SignedUserTransaction> for SignedUserTransactionView {
     }
 }
 
+impl TryFrom<SignedUserTransactionView> for SignedUserTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(view: SignedUserTransactionView) -> Result<Self, Self::Error> {
+        let txn = SignedUserTransaction::new(view.raw_txn.try_into()?, view.authenticator);
+        anyhow::ensure!(
+            txn.id() == view.transaction_hash,
+            "The txn view {} mismatch with its hash {}",
+            view.transaction_hash,
+            txn.id()
+        );
+        Ok(txn)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct BlockMetadataView {
     /// Parent block hash.
@@ -637,6 +734,43 @@ impl TryFrom<Block> for BlockView {
     }
 }
 
+impl TryFrom<BlockView> for Block {
+    type Error = anyhow::Error;
+
+    /// Rebuild the block from the full block view, the hashes of the header and the txns are checked.
+    fn try_from(view: BlockView) -> Result<Self, Self::Error> {
+        let header: BlockHeader = view.header.try_into()?;
+        let transactions = match view.body {
+            BlockTransactionsView::Full(txns) => txns
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<SignedUserTransaction>, _>>()?,
+            BlockTransactionsView::Hashes(_) => {
+                anyhow::bail!("The txns of block view {} are required", header.id())
+            }
+        };
+        let uncles = view
+            .uncles
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<BlockHeader>, _>>()?;
+        // the view does not distinguish the empty uncles from none.
+        let mut body = BlockBody::new(transactions, Some(uncles));
+        if body.uncles.as_ref().map(Vec::is_empty).unwrap_or(false)
+            && body.hash() != header.body_hash()
+        {
+            body.uncles = None;
+        }
+        anyhow::ensure!(
+            body.hash() == header.body_hash(),
+            "The body of block view {} mismatch with the body hash {}",
+            header.id(),
+            header.body_hash()
+        );
+        Ok(Block::new(header, body))
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct BlockSummaryView {
     pub header: BlockHeaderView,
@@ -772,6 +906,9 @@ pub struct TransactionEventView {
     pub type_tag: TypeTag,
     pub event_key: EventKey,
     pub event_seq_number: StrView<u64>,
+    /// The payment reference of the deposit event, if the transfer is to a receipt identifier v2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_reference: Option<StrView<Vec<u8>>>,
 }
 
 impl From<ContractEventInfo> for TransactionEventView {
@@ -785,6 +922,7 @@ impl From<ContractEventInfo> for TransactionEventView {
             type_tag: info.event.type_tag().clone(),
             event_key: *info.event.key(),
             event_seq_number: info.event.sequence_number().into(),
+            payment_reference: payment_reference_of_event(&info.event).map(StrView),
         }
     }
 }
@@ -799,6 +937,7 @@ impl From<ContractEvent> for TransactionEventView {
             type_tag: event.type_tag().clone(),
             event_key: *event.key(),
             event_seq_number: event.sequence_number().into(),
+            payment_reference: payment_reference_of_event(&event).map(StrView),
         }
     }
 }
@@ -820,6 +959,7 @@ impl TransactionEventView {
             type_tag: contract_event.type_tag().clone(),
             event_key: *contract_event.key(),
             event_seq_number: contract_event.sequence_number().into(),
+            payment_reference: payment_reference_of_event(contract_event).map(StrView),
         }
     }
 }
@@ -926,6 +1066,33 @@ impl From<EpochUncleSummary> for EpochUncleSummaryView {
     }
 }
 
+/// The STC supply at the state of a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TotalSupplyView {
+    pub block_number: StrView<BlockNumber>,
+    /// The total value of the STC token info.
+    pub total_supply: StrView<u128>,
+    /// The STC balance of the treasury, None if the treasury is not initialized.
+    pub treasury_balance: Option<StrView<u128>>,
+}
+
+/// The state root of a main chain block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockStateRootView {
+    pub block_number: StrView<BlockNumber>,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+}
+
+/// Whether the state at a root is available in the node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateRootStatusView {
+    pub state_root: HashValue,
+    pub available: bool,
+    /// The available state roots of the latest main chain blocks, empty if the `state_root` is available.
+    pub latest_available_roots: Vec<BlockStateRootView>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChainInfoView {
     pub chain_id: u8,
@@ -967,6 +1134,30 @@ impl From<PeerInfo> for PeerInfoView {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerStatusView {
+    pub peer_id: PeerId,
+    /// Latest ping latency in milliseconds.
+    pub latency: Option<u64>,
+    pub reputation: Option<i32>,
+    pub trusted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BannedPeerView {
+    pub peer_id: PeerId,
+    /// Unix timestamp in milliseconds when the ban expires.
+    pub banned_until: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IdentityRotationView {
+    pub old_peer_id: PeerId,
+    pub new_peer_id: PeerId,
+    /// Unix timestamp in milliseconds of the rotation.
+    pub timestamp: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StateWithProofView {
     pub state: Option<StrView<Vec<u8>>>,
@@ -1085,7 +1276,7 @@ impl FromStr for StrView<ModuleId> {
         if parts.len() != 2 {
             anyhow::bail!("invalid module id");
         }
-        let module_addr = parts[0].parse::<AccountAddress>()?;
+        let module_addr = parse_address(parts[0])?;
         let module_name = Identifier::new(parts[1])?;
         Ok(Self(ModuleId::new(module_addr, module_name)))
     }
@@ -1263,6 +1454,9 @@ pub struct ContractCall {
     pub function_id: FunctionIdView,
     pub type_args: Vec<TypeTagView>,
     pub args: Vec<TransactionArgumentView>,
+    /// Call the function on the state of the state root, if absent, use the latest state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<HashValue>,
 }
 
 #[derive(Debug, Clone)]
@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors;
-use crate::types::{BlockView, TransactionEventView};
+use crate::types::{BlockView, StrView, TransactionEventView, TypeTagView};
 use jsonrpc_core::error::Error as JsonRpcError;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{from_value, Value};
 use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
@@ -40,6 +41,23 @@ pub enum Result {
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventView>),
     MintBlock(Box<MintBlockEvent>),
+    /// Result with the sequence number of the chain notification, for subscriptions with replay.
+    Sequenced(Box<SequencedResult>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SequencedResult {
+    pub seq: StrView<u64>,
+    pub result: Result,
+}
+
+impl SequencedResult {
+    pub fn new(seq: u64, result: Result) -> Self {
+        Self {
+            seq: seq.into(),
+            result,
+        }
+    }
 }
 
 impl Serialize for Result {
@@ -52,6 +70,7 @@ impl Serialize for Result {
             Result::Event(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
             Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::Sequenced(ref sequenced) => sequenced.serialize(serializer),
         }
     }
 }
@@ -63,6 +82,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Events(EventFilter),
+    /// Replay parameters of new heads.
+    NewHeads(NewHeadsParams),
 }
 
 impl Default for Params {
@@ -82,6 +103,9 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None);
         }
         // Err(D::Error::custom("Invalid Pub-Sub parameters"));
+        if let Ok(params) = from_value::<NewHeadsParams>(v.clone()) {
+            return Ok(Params::NewHeads(params));
+        }
         from_value(v)
             .map(Params::Events)
             .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
@@ -92,7 +116,7 @@ impl<'a> Deserialize<'a> for Params {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
 #[serde(deny_unknown_fields)]
 pub struct EventFilter {
-    /// From Block
+    /// From Block, the history events are backfilled from it, and it should be within the latest 1000 blocks.
     #[serde(default)]
     pub from_block: Option<u64>,
     /// To Block
@@ -101,9 +125,49 @@ pub struct EventFilter {
     /// Event keys
     #[serde(default)]
     pub event_keys: Vec<EventKey>,
+    /// Event emitter addresses, the address of the event key.
+    #[serde(default)]
+    pub addrs: Vec<AccountAddress>,
+    /// Event move types, such as `0x1::Account::WithdrawEvent`
+    #[serde(default)]
+    pub type_tags: Vec<TypeTagView>,
     /// Limit: from latest to oldest
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Replay the missed notifications, the results are wrapped with the notification sequence number.
+    #[serde(default)]
+    pub replay: Option<ReplayParams>,
+}
+
+impl From<NewHeadsParams> for EventFilter {
+    fn from(params: NewHeadsParams) -> Self {
+        Self {
+            from_block: None,
+            to_block: None,
+            event_keys: vec![],
+            addrs: vec![],
+            type_tags: vec![],
+            limit: None,
+            replay: Some(params.replay),
+        }
+    }
+}
+
+/// Replay parameters of subscriptions.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, Hash, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayParams {
+    /// The last notification sequence number the subscriber received,
+    /// the notifications after it are sent first if they are still in the replay window.
+    /// If none, only the new notifications are sent.
+    #[serde(default)]
+    pub since_seq: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct NewHeadsParams {
+    pub replay: ReplayParams,
 }
 
 impl TryInto<Filter> for EventFilter {
@@ -123,6 +187,8 @@ impl TryInto<Filter> for EventFilter {
             from_block: self.from_block.unwrap_or(0),
             to_block: self.to_block.unwrap_or(std::u64::MAX),
             event_keys: self.event_keys,
+            addrs: self.addrs,
+            type_tags: self.type_tags.into_iter().map(|t| t.0).collect(),
             limit: self.limit,
             reverse: true,
         })
@@ -0,0 +1,157 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{map_err, RpcClient};
+use anyhow::{format_err, Result};
+use jsonrpc_core::Params;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use starcoin_crypto::HashValue;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
+
+/// A batch of rpc calls, all the calls are sent by `send` in one round trip, by a JSON-RPC batch
+/// request for the http connection, or together through the same connection without waiting for
+/// the previous call's response for the other connections.
+/// The results are in the same order as the calls, and are got by index from `BatchResponse`.
+pub struct RpcBatch<'a> {
+    client: &'a RpcClient,
+    calls: Vec<(String, Params)>,
+    error: Option<anyhow::Error>,
+}
+
+impl<'a> RpcBatch<'a> {
+    pub(crate) fn new(client: &'a RpcClient) -> Self {
+        Self {
+            client,
+            calls: vec![],
+            error: None,
+        }
+    }
+
+    /// Add a raw api call to the batch.
+    pub fn call(mut self, method: &str, params: Params) -> Self {
+        self.calls.push((method.to_string(), params));
+        self
+    }
+
+    fn call_with_args<A: Serialize>(mut self, method: &str, args: A) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        match serde_json::to_value(args) {
+            Ok(Value::Array(args)) => self.call(method, Params::Array(args)),
+            Ok(v) => {
+                self.error = Some(format_err!(
+                    "Invalid args {} of method {}, expect array.",
+                    v,
+                    method
+                ));
+                self
+            }
+            Err(e) => {
+                self.error = Some(e.into());
+                self
+            }
+        }
+    }
+
+    pub fn node_info(self) -> Self {
+        self.call("node.info", Params::Array(vec![]))
+    }
+
+    pub fn chain_info(self) -> Self {
+        self.call("chain.info", Params::Array(vec![]))
+    }
+
+    pub fn chain_get_block_by_hash(self, hash: HashValue) -> Self {
+        self.call_with_args("chain.get_block_by_hash", (hash,))
+    }
+
+    pub fn chain_get_block_by_number(self, number: BlockNumber) -> Self {
+        self.call_with_args("chain.get_block_by_number", (number,))
+    }
+
+    pub fn chain_get_transaction(self, txn_hash: HashValue) -> Self {
+        self.call_with_args("chain.get_transaction", (txn_hash,))
+    }
+
+    pub fn chain_get_transaction_info(self, txn_hash: HashValue) -> Self {
+        self.call_with_args("chain.get_transaction_info", (txn_hash,))
+    }
+
+    pub fn account_default(self) -> Self {
+        self.call("account.default", Params::Array(vec![]))
+    }
+
+    pub fn account_get(self, address: AccountAddress) -> Self {
+        self.call_with_args("account.get", (address,))
+    }
+
+    pub fn account_accepted_tokens(self, address: AccountAddress) -> Self {
+        self.call_with_args("account.accepted_tokens", (address,))
+    }
+
+    pub fn state_get(self, access_path: AccessPath) -> Self {
+        self.call_with_args("state.get", (access_path,))
+    }
+
+    pub fn state_get_account_state(self, address: AccountAddress) -> Self {
+        self.call_with_args("state.get_account_state", (address,))
+    }
+
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Send all the calls, the error of a single call do not fail the whole batch.
+    pub fn send(self) -> Result<BatchResponse> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let results = self.client.call_batch(self.calls).map_err(map_err)?;
+        Ok(BatchResponse {
+            results: results
+                .into_iter()
+                .map(|result| result.map_err(map_err))
+                .collect(),
+        })
+    }
+}
+
+pub struct BatchResponse {
+    results: Vec<Result<Value>>,
+}
+
+impl BatchResponse {
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Get the result of the call at `index`, and deserialize it to `T`.
+    pub fn get<T: DeserializeOwned>(&self, index: usize) -> Result<T> {
+        match self.results.get(index) {
+            Some(Ok(value)) => Ok(serde_json::from_value(value.clone())?),
+            Some(Err(e)) => Err(format_err!("{}", e)),
+            None => Err(format_err!(
+                "Batch call index {} out of range, total {} calls.",
+                index,
+                self.results.len()
+            )),
+        }
+    }
+
+    pub fn into_values(self) -> Vec<Result<Value>> {
+        self.results
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use jsonrpc_client_transports::transports::duplex::duplex;
+use jsonrpc_client_transports::RpcError;
+use jsonrpc_core::{
+    Call, Error, ErrorCode, Failure, Id, MethodCall, Output, Params, Request, Response, Version,
+};
+use jsonrpc_core_client::RpcChannel;
+use parking_lot::Mutex;
+use serde_json::Value;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::metadata::TRACE_ID_HEADER;
+use std::sync::Arc;
+
+/// The max count of the http requests sending at the same time.
+const MAX_PARALLEL_REQUESTS: usize = 8;
+
+/// Connect the node by http, every request carries the current trace id of the client by the
+/// `x-trace-id` header, as the http connection can not keep it like the other connections.
+/// The http client keeps the connections alive and reuses them between calls.
+pub(crate) async fn connect(
+    client: reqwest::Client,
+    url: &str,
+    trace_id: Arc<Mutex<Option<String>>>,
+) -> Result<RpcChannel, RpcError> {
+    let url = reqwest::Url::parse(url).map_err(|e| RpcError::Other(Box::new(e)))?;
+    let (request_sender, request_receiver) = mpsc::unbounded::<String>();
+    let (response_sender, response_receiver) = mpsc::unbounded::<String>();
+    let (duplex, channel) = duplex(Box::pin(request_sender), Box::pin(response_receiver));
+    let worker = request_receiver.for_each_concurrent(MAX_PARALLEL_REQUESTS, move |request| {
+        let client = client.clone();
+        let url = url.clone();
+        let trace_id = trace_id.lock().clone();
+        let response_sender = response_sender.clone();
+        async move {
+            let response = match send_request(&client, url, trace_id, request.clone()).await {
+                Ok(response) => response,
+                Err(e) => failure_of(request.as_str(), e),
+            };
+            if let Some(response) = response {
+                let _ = response_sender.unbounded_send(response);
+            }
+        }
+    });
+    tokio::spawn(worker);
+    tokio::spawn(async move {
+        if let Err(e) = duplex.await {
+            warn!("Http rpc connection is closed by error: {:?}", e);
+        }
+    });
+    Ok(channel)
+}
+
+/// Send the calls by a JSON-RPC batch request in one http round trip, the results are in the
+/// same order as the calls, and the error of a call does not fail the others.
+pub(crate) async fn send_batch(
+    client: &reqwest::Client,
+    url: &str,
+    trace_id: Option<String>,
+    calls: Vec<(String, Params)>,
+) -> Result<Vec<Result<Value, RpcError>>, RpcError> {
+    let url = reqwest::Url::parse(url).map_err(|e| RpcError::Other(Box::new(e)))?;
+    let count = calls.len();
+    let request = Request::Batch(
+        calls
+            .into_iter()
+            .enumerate()
+            .map(|(index, (method, params))| {
+                Call::MethodCall(MethodCall {
+                    jsonrpc: Some(Version::V2),
+                    method,
+                    params,
+                    id: Id::Num(index as u64),
+                })
+            })
+            .collect(),
+    );
+    let request = serde_json::to_string(&request).map_err(|e| RpcError::Other(Box::new(e)))?;
+    let response = send_request(client, url, trace_id, request)
+        .await
+        .map_err(RpcError::Client)?
+        .ok_or_else(|| RpcError::Client("Empty response of the batch request".to_string()))?;
+    let outputs = match serde_json::from_str::<Response>(response.as_str())
+        .map_err(|e| RpcError::ParseError(response.clone(), Box::new(e)))?
+    {
+        Response::Batch(outputs) => outputs,
+        Response::Single(output) => vec![output],
+    };
+    let mut results: Vec<Option<Result<Value, RpcError>>> = (0..count).map(|_| None).collect();
+    for output in outputs {
+        if let Id::Num(index) = output.id() {
+            if let Some(result) = results.get_mut(*index as usize) {
+                *result = Some(
+                    jsonrpc_core::Result::<Value>::from(output).map_err(RpcError::JsonRpcError),
+                );
+            }
+        }
+    }
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|| {
+                Err(RpcError::Client(
+                    "Missing the response of the call in the batch".to_string(),
+                ))
+            })
+        })
+        .collect())
+}
+
+/// Post the request, return the response body, or None if the request is a notification.
+async fn send_request(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    trace_id: Option<String>,
+    request: String,
+) -> Result<Option<String>, String> {
+    let mut request_builder = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::ACCEPT, "application/json");
+    if let Some(trace_id) = trace_id {
+        request_builder = request_builder.header(TRACE_ID_HEADER, trace_id);
+    }
+    let response = request_builder
+        .body(request)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Unexpected response status code: {}", status));
+    }
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    if body.trim().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(body))
+    }
+}
+
+/// Build the failure response of the method call, so the caller gets the error instead of waiting forever.
+fn failure_of(request: &str, error: String) -> Option<String> {
+    let id = match serde_json::from_str::<Request>(request) {
+        Ok(Request::Single(Call::MethodCall(method_call))) => method_call.id,
+        _ => return None,
+    };
+    let failure = Output::Failure(Failure {
+        jsonrpc: Some(Version::V2),
+        error: Error {
+            code: ErrorCode::InternalError,
+            message: format!("Http request error: {}", error),
+            data: None,
+        },
+        id,
+    });
+    serde_json::to_string(&failure).ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::RpcClient;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Read a http request, return the header and the body.
+    pub(crate) fn read_request(stream: &mut std::net::TcpStream) -> (String, String) {
+        let mut data = vec![];
+        let mut buf = [0u8; 4096];
+        loop {
+            let len = stream.read(&mut buf).unwrap();
+            assert!(len > 0, "connection closed before the request is read");
+            data.extend_from_slice(&buf[..len]);
+            let text = String::from_utf8_lossy(&data).to_string();
+            if let Some(pos) = text.find("\r\n\r\n") {
+                let header = text[..pos].to_lowercase();
+                let content_length = header
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .map(|len| len.trim().parse::<usize>().unwrap())
+                    .unwrap_or(0);
+                let body = &text[pos + 4..];
+                if body.len() >= content_length {
+                    return (header, body.to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_http_trace_id_header() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (header, body) = read_request(&mut stream);
+            let request: serde_json::Value = serde_json::from_str(body.as_str()).unwrap();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "error": {"code": -32601, "message": "Method not found"},
+                "id": request["id"],
+            })
+            .to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            header
+        });
+        let client = RpcClient::connect_http(url.as_str()).unwrap();
+        client.set_trace_id(Some("0123abcd".to_string())).unwrap();
+        assert_eq!(client.trace_id(), Some("0123abcd".to_string()));
+        assert!(client.node_info().is_err());
+        let header = handle.join().unwrap();
+        assert!(header.contains(&format!("{}: 0123abcd", TRACE_ID_HEADER)));
+    }
+
+    #[test]
+    fn test_http_batch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (_header, body) = read_request(&mut stream);
+            let request: Vec<serde_json::Value> = serde_json::from_str(body.as_str()).unwrap();
+            // the responses of the batch may be in any order.
+            let response = serde_json::json!([
+                {
+                    "jsonrpc": "2.0",
+                    "error": {"code": -32601, "message": "Method not found"},
+                    "id": request[1]["id"],
+                },
+                {"jsonrpc": "2.0", "result": "ok", "id": request[0]["id"]},
+            ])
+            .to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            request
+                .iter()
+                .map(|call| call["method"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        });
+        let client = RpcClient::connect_http(url.as_str()).unwrap();
+        let response = client.batch().node_info().chain_info().send().unwrap();
+        assert_eq!(response.len(), 2);
+        assert_eq!(response.get::<String>(0).unwrap(), "ok");
+        assert!(response.get::<serde_json::Value>(1).is_err());
+        // all the calls are sent by one batch request.
+        assert_eq!(handle.join().unwrap(), vec!["node.info", "chain.info"]);
+    }
+
+    #[test]
+    fn test_failure_of() {
+        let failure = failure_of(
+            r#"{"jsonrpc":"2.0","method":"node.info","params":[],"id":3}"#,
+            "timeout".to_string(),
+        )
+        .unwrap();
+        let output: Output = serde_json::from_str(failure.as_str()).unwrap();
+        match output {
+            Output::Failure(failure) => {
+                assert_eq!(failure.id, jsonrpc_core::Id::Num(3));
+                assert!(failure.error.message.contains("timeout"));
+            }
+            Output::Success(_) => panic!("expect failure"),
+        }
+        assert_eq!(
+            failure_of(
+                r#"{"jsonrpc":"2.0","method":"rpc.trace","params":[]}"#,
+                "timeout".to_string()
+            ),
+            None
+        );
+    }
+}
@@ -8,23 +8,30 @@ use anyhow::anyhow;
 use futures::channel::oneshot;
 use futures::{TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
+use jsonrpc_core::Params;
 use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
 use network_api::PeerStrategy;
 use network_p2p_types::network_state::NetworkState;
 use parking_lot::Mutex;
 use serde_json::Value;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, AccountMetadataUpdate, KeystoreEntry, PendingTxnApproval};
 use starcoin_crypto::HashValue;
-use starcoin_logger::{prelude::*, LogPattern};
+use starcoin_logger::{prelude::*, LogPattern, LogSubsystem};
+use starcoin_node_api::maintenance::MaintenanceStatus;
+use starcoin_node_api::usage::UsageReport;
+#[cfg(feature = "threshold-encryption")]
+use starcoin_rpc_api::encrypted_txpool::EncryptedTxPoolClient;
+use starcoin_rpc_api::metadata::TRACE_NOTIFICATION;
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, AnnotatedMoveValueView, BlockHeaderView,
-    BlockSummaryView, BlockView, ChainId, ChainInfoView, ContractCall, DryRunTransactionRequest,
-    EpochUncleSummaryView, FactoryAction, MintedBlockView, PeerInfoView, SignedUserTransactionView,
-    StateWithProofView, StrView, TransactionInfoView, TransactionOutputView, TransactionRequest,
-    TransactionView,
+    AccountStateSetView, AnnotatedMoveStructView, AnnotatedMoveValueView, BannedPeerView,
+    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, ContractCall,
+    DryRunTransactionRequest, EpochUncleSummaryView, FactoryAction, IdentityRotationView,
+    MintedBlockView, PeerInfoView, PeerStatusView, SignedUserTransactionView, StateRootStatusView,
+    StateWithProofView, StrView, TotalSupplyView, TransactionInfoView, TransactionOutputView,
+    TransactionRequest, TransactionView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
@@ -33,30 +40,39 @@ use starcoin_rpc_api::{
     txpool::TxPoolClient, types::TransactionEventView,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
-use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_sync_api::{PeerScoreResponse, PendingReorg, SyncProgressReport};
+#[cfg(feature = "threshold-encryption")]
+use starcoin_txpool_api::{DecryptionShare, EncryptedTransaction, SealedEncryptedTransaction};
+use starcoin_txpool_api::{TxPoolAnalytics, TxPoolStatus, TxnKeepPolicy, TxnValidation};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
 use starcoin_types::block::{BlockInfo, BlockNumber};
 use starcoin_types::peer_info::{Multiaddr, PeerId};
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::token::token_code::TokenCode;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 use std::time::Duration;
 
+mod batch;
 pub mod chain_watcher;
+mod http_transport;
 mod pubsub_client;
 mod remote_state_reader;
+mod txn_relay;
 
-pub use crate::remote_state_reader::RemoteStateReader;
+pub use crate::batch::{BatchResponse, RpcBatch};
+pub use crate::remote_state_reader::{RemoteStateReader, StateUnavailableError};
+pub use crate::txn_relay::TxnRelay;
 pub use jsonrpc_core::Params;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{MiningWork, MintBlockEvent};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use tokio::runtime::Runtime;
 
@@ -64,14 +80,23 @@ use tokio::runtime::Runtime;
 enum ConnSource {
     Ipc(PathBuf),
     WebSocket(String),
+    Http(String),
     Local(Box<RpcChannel>),
 }
 
+impl ConnSource {
+    /// Http connection do not support subscription.
+    fn support_pubsub(&self) -> bool {
+        !matches!(self, ConnSource::Http(_))
+    }
+}
+
 impl std::fmt::Debug for ConnSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConnSource::Ipc(path) => write!(f, "Ipc({})", path.as_path().to_string_lossy()),
             ConnSource::WebSocket(url) => write!(f, "WebSocket({})", url),
+            ConnSource::Http(url) => write!(f, "Http({})", url),
             ConnSource::Local(_) => write!(f, "Local"),
         }
     }
@@ -83,18 +108,32 @@ pub struct RpcClient {
     chain_watcher: Addr<ChainWatcher>,
     //hold the watch thread handle.
     watcher_handle: JoinHandle<()>,
+    // submit txns through the relays instead of the connected node if set.
+    txn_relay: Option<TxnRelay>,
+    // the trace id of the current user action, resent to the node after reconnecting,
+    // and sent by every request of the http connection.
+    trace_id: Arc<Mutex<Option<String>>>,
 }
 
 struct ConnectionProvider {
     conn_source: ConnSource,
     runtime: Mutex<Runtime>,
+    trace_id: Arc<Mutex<Option<String>>>,
+    // the http client is shared by the reconnections and the batch requests, to reuse the connections.
+    http_client: reqwest::Client,
 }
 
 impl ConnectionProvider {
-    fn new(conn_source: ConnSource, runtime: Runtime) -> Self {
+    fn new(
+        conn_source: ConnSource,
+        runtime: Runtime,
+        trace_id: Arc<Mutex<Option<String>>>,
+    ) -> Self {
         Self {
             conn_source,
             runtime: Mutex::new(runtime),
+            trace_id,
+            http_client: reqwest::Client::new(),
         }
     }
 
@@ -116,6 +155,14 @@ impl ConnectionProvider {
         match self.conn_source.clone() {
             ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
             ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
+            ConnSource::Http(url) => {
+                http_transport::connect(
+                    self.http_client.clone(),
+                    url.as_str(),
+                    self.trace_id.clone(),
+                )
+                .await
+            }
             ConnSource::Local(channel) => Ok(*channel),
         }
     }
@@ -124,7 +171,8 @@ impl ConnectionProvider {
 impl RpcClient {
     pub(crate) fn new(conn_source: ConnSource) -> anyhow::Result<Self> {
         let (tx, rx) = oneshot::channel();
-        let provider = ConnectionProvider::new(conn_source, Runtime::new()?);
+        let trace_id = Arc::new(Mutex::new(None));
+        let provider = ConnectionProvider::new(conn_source, Runtime::new()?, trace_id.clone());
         let inner: RpcClientInner = provider.get_rpc_channel().map_err(map_err)?.into(); //Self::create_client_inner(conn_source.clone()).map_err(map_err)?;
         let pubsub_client = inner.pubsub_client.clone();
         let handle = std::thread::spawn(move || {
@@ -135,21 +183,67 @@ impl RpcClient {
             let _ = sys.run();
         });
         let watcher = futures::executor::block_on(rx).expect("Init chain watcher fail.");
-        watcher.do_send(StartSubscribe {
-            client: pubsub_client,
-        });
+        if provider.conn_source.support_pubsub() {
+            watcher.do_send(StartSubscribe {
+                client: pubsub_client,
+            });
+        }
         Ok(Self {
             inner: Mutex::new(Some(inner)),
             provider,
             chain_watcher: watcher,
             watcher_handle: handle,
+            txn_relay: None,
+            trace_id,
+        })
+    }
+
+    /// Submit txns through the `txn_relay` instead of the connected node.
+    pub fn with_txn_relay(mut self, txn_relay: TxnRelay) -> Self {
+        self.txn_relay = Some(txn_relay);
+        self
+    }
+
+    pub fn txn_relay(&self) -> Option<&TxnRelay> {
+        self.txn_relay.as_ref()
+    }
+
+    /// Generate a trace id for a user action.
+    pub fn new_trace_id() -> String {
+        hex::encode(&HashValue::random().to_vec()[..8])
+    }
+
+    /// Set the trace id of the following calls, the node logs the calls with it.
+    /// The trace id is kept by the connection, and sent by the `x-trace-id` header of every
+    /// request of the http connection.
+    pub fn set_trace_id(&self, trace_id: Option<String>) -> anyhow::Result<()> {
+        *self.trace_id.lock() = trace_id.clone();
+        if matches!(self.provider.conn_source, ConnSource::Http(_)) {
+            return Ok(());
+        }
+        self.call_rpc_blocking(|inner| {
+            futures::future::ready(
+                inner
+                    .raw_client
+                    .notify(TRACE_NOTIFICATION, trace_params(trace_id)),
+            )
         })
+        .map_err(map_err)
+    }
+
+    pub fn trace_id(&self) -> Option<String> {
+        self.trace_id.lock().clone()
     }
 
     pub fn connect_websocket(url: &str) -> anyhow::Result<Self> {
         Self::new(ConnSource::WebSocket(url.to_string()))
     }
 
+    /// Connect by http, the watch and subscribe api are not supported by http connection.
+    pub fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string()))
+    }
+
     pub fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
     where
         S: RpcAsyncService,
@@ -168,6 +262,7 @@ impl RpcClient {
         txn_hash: HashValue,
         timeout: Option<Duration>,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        self.ensure_pubsub()?;
         let chain_watcher = self.chain_watcher.clone();
         let f = async move {
             let r = chain_watcher.send(WatchTxn { txn_hash }).await?;
@@ -183,6 +278,7 @@ impl RpcClient {
         &self,
         block_number: BlockNumber,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        self.ensure_pubsub()?;
         let chain_watcher = self.chain_watcher.clone();
         let f = async move {
             let r = chain_watcher.send(WatchBlock(block_number)).await?;
@@ -212,6 +308,11 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_usage_report(&self, last_seconds: u64) -> anyhow::Result<UsageReport> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.usage_report(last_seconds))
+            .map_err(map_err)
+    }
+
     pub fn node_peers(&self) -> anyhow::Result<Vec<PeerInfoView>> {
         self.call_rpc_blocking(|inner| inner.node_client.peers())
             .map_err(map_err)
@@ -242,6 +343,29 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_maintenance_status(&self) -> anyhow::Result<MaintenanceStatus> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.maintenance_status())
+            .map_err(map_err)
+    }
+
+    pub fn node_accept_reorg(
+        &self,
+        branch_head: Option<HashValue>,
+    ) -> anyhow::Result<PendingReorg> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.accept_reorg(branch_head))
+            .map_err(map_err)
+    }
+
+    pub fn node_approve_txn(&self, approval_hash: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.approve_txn(approval_hash))
+            .map_err(map_err)
+    }
+
+    pub fn node_pending_txn_approvals(&self) -> anyhow::Result<Vec<PendingTxnApproval>> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.pending_txn_approvals())
+            .map_err(map_err)
+    }
+
     pub fn next_sequence_number_in_txpool(
         &self,
         address: AccountAddress,
@@ -251,7 +375,91 @@ impl RpcClient {
     }
 
     pub fn submit_transaction(&self, txn: SignedUserTransaction) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn))
+        if let Some(txn_relay) = self.txn_relay.as_ref() {
+            return txn_relay.submit_transaction(&txn, None);
+        }
+        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn, None))
+            .map_err(map_err)
+    }
+
+    /// Submit the txn with a client generated `idempotency_key`,
+    /// it is safe to retry the submission with the same key after a timeout,
+    /// the node returns the original result of the key within the retention window.
+    pub fn submit_transaction_with_idempotency_key(
+        &self,
+        txn: SignedUserTransaction,
+        idempotency_key: String,
+    ) -> anyhow::Result<HashValue> {
+        if let Some(txn_relay) = self.txn_relay.as_ref() {
+            return txn_relay.submit_transaction(&txn, Some(idempotency_key));
+        }
+        self.call_rpc_blocking(|inner| {
+            inner
+                .txpool_client
+                .submit_transaction(txn, Some(idempotency_key))
+        })
+        .map_err(map_err)
+    }
+
+    /// Submit the txn, the node keeps the txn until it is included by the `policy`.
+    pub fn submit_transaction_with_policy(
+        &self,
+        txn: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> anyhow::Result<HashValue> {
+        if let Some(txn_relay) = self.txn_relay.as_ref() {
+            return txn_relay.submit_transaction_with_policy(&txn, &policy);
+        }
+        self.call_rpc_blocking(|inner| {
+            inner
+                .txpool_client
+                .submit_transaction_with_policy(txn, policy)
+        })
+        .map_err(map_err)
+    }
+
+    /// Submit the txn encrypted to the threshold committee to the encrypted pool of the connected node.
+    #[cfg(feature = "threshold-encryption")]
+    pub fn submit_encrypted_transaction(
+        &self,
+        txn: EncryptedTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .encrypted_txpool_client
+                .submit_encrypted_transaction(txn)
+        })
+        .map_err(map_err)
+    }
+
+    #[cfg(feature = "threshold-encryption")]
+    pub fn submit_decryption_shares(
+        &self,
+        shares: Vec<DecryptionShare>,
+    ) -> anyhow::Result<Vec<HashValue>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .encrypted_txpool_client
+                .submit_decryption_shares(shares)
+        })
+        .map_err(map_err)
+    }
+
+    #[cfg(feature = "threshold-encryption")]
+    pub fn sealed_encrypted_transactions(&self) -> anyhow::Result<Vec<SealedEncryptedTransaction>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .encrypted_txpool_client
+                .sealed_encrypted_transactions()
+        })
+        .map_err(map_err)
+    }
+
+    pub fn validate_transaction(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<TxnValidation> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.validate_transaction(txn))
             .map_err(map_err)
     }
 
@@ -366,7 +574,26 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .account_client
-                .unlock(address, password, Some(duration.as_secs() as u32))
+                .unlock(address, password, Some(duration.as_secs() as u32), None)
+        })
+        .map_err(map_err)
+    }
+
+    /// Unlock the account, the txns signed in the session can spend at most `max_amount` nanoSTC.
+    pub fn account_unlock_with_limit(
+        &self,
+        address: AccountAddress,
+        password: String,
+        duration: std::time::Duration,
+        max_amount: u128,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| {
+            inner.account_client.unlock(
+                address,
+                password,
+                Some(duration.as_secs() as u32),
+                Some(StrView(max_amount)),
+            )
         })
         .map_err(map_err)
     }
@@ -388,6 +615,39 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_derive(
+        &self,
+        mnemonic: String,
+        path: String,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.derive(mnemonic, path, password))
+            .map_err(map_err)
+    }
+
+    pub fn account_restore(
+        &self,
+        mnemonic: String,
+        password: String,
+        count: u32,
+    ) -> anyhow::Result<Vec<AccountInfo>> {
+        self.call_rpc_blocking(|inner| inner.account_client.restore(mnemonic, password, count))
+            .map_err(map_err)
+    }
+
+    pub fn account_export_keystore(&self) -> anyhow::Result<Vec<KeystoreEntry>> {
+        self.call_rpc_blocking(|inner| inner.account_client.export_keystore())
+            .map_err(map_err)
+    }
+
+    pub fn account_import_keystore(
+        &self,
+        entries: Vec<KeystoreEntry>,
+    ) -> anyhow::Result<Vec<AccountInfo>> {
+        self.call_rpc_blocking(|inner| inner.account_client.import_keystore(entries))
+            .map_err(map_err)
+    }
+
     pub fn account_import_readonly(
         &self,
         address: AccountAddress,
@@ -414,6 +674,34 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_update_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.update_metadata(address, update))
+            .map_err(map_err)
+    }
+
+    /// Submit the txn to the connected node, which keeps the txn until it is included by the `policy`,
+    /// and re-signs it by the unlocked sender account if `policy.resign` is true.
+    /// The txn without re-sign is submitted through the `txn_relay` if set.
+    pub fn account_submit_txn_with_policy(
+        &self,
+        txn: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> anyhow::Result<HashValue> {
+        if let Some(txn_relay) = self.txn_relay.as_ref() {
+            anyhow::ensure!(
+                !policy.resign,
+                "The re-sign policy needs the account of the connected node, can not submit the txn through the txn relays."
+            );
+            return txn_relay.submit_transaction_with_policy(&txn, &policy);
+        }
+        self.call_rpc_blocking(|inner| inner.account_client.submit_txn_with_policy(txn, policy))
+            .map_err(map_err)
+    }
+
     pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
         let result: Option<StrView<Vec<u8>>> = self
             .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
@@ -460,11 +748,49 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn state_get_resource_at(
+        &self,
+        address: AccountAddress,
+        resource_type: StructTag,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .get_resource_at(address, StrView(resource_type), block_number)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn state_get_resource_at_blocks(
+        &self,
+        address: AccountAddress,
+        resource_type: StructTag,
+        block_numbers: Vec<BlockNumber>,
+    ) -> anyhow::Result<Vec<Option<AnnotatedMoveStructView>>> {
+        self.call_rpc_blocking(|inner| {
+            inner.state_client.get_resource_at_blocks(
+                address,
+                StrView(resource_type),
+                block_numbers,
+            )
+        })
+        .map_err(map_err)
+    }
+
     pub fn state_get_state_root(&self) -> anyhow::Result<HashValue> {
         self.call_rpc_blocking(|inner| inner.state_client.get_state_root())
             .map_err(map_err)
     }
 
+    pub fn state_get_state_root_status(
+        &self,
+        state_root: HashValue,
+    ) -> anyhow::Result<StateRootStatusView> {
+        self.call_rpc_blocking(|inner| inner.state_client.get_state_root_status(state_root))
+            .map_err(map_err)
+    }
+
     pub fn state_get_account_state(
         &self,
         address: AccountAddress,
@@ -509,6 +835,15 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn debug_log_tail(
+        &self,
+        subsystem: Option<LogSubsystem>,
+        lines: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        self.call_rpc_blocking(|inner| inner.debug_client.log_tail(subsystem, lines))
+            .map_err(map_err)
+    }
+
     pub fn debug_panic(&self) -> anyhow::Result<()> {
         self.call_rpc_blocking(|inner| inner.debug_client.panic())
             .map_err(map_err)
@@ -576,6 +911,19 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn chain_total_supply_at(&self, number: BlockNumber) -> anyhow::Result<TotalSupplyView> {
+        self.call_rpc_blocking(|inner| inner.chain_client.total_supply_at(number))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_events(
+        &self,
+        filter: EventFilter,
+    ) -> anyhow::Result<Vec<TransactionEventView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_events(filter))
+            .map_err(map_err)
+    }
+
     pub fn chain_get_block_by_hash(&self, hash: HashValue) -> anyhow::Result<Option<BlockView>> {
         self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_hash(hash))
             .map_err(map_err)
@@ -651,6 +999,15 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn chain_get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<TransactionInfoWithProof>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction_proof(block_id, txn_hash))
+            .map_err(map_err)
+    }
+
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<TransactionOutputView> {
         self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
             .map_err(map_err)
@@ -675,11 +1032,21 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn miner_get_work(&self, worker_id: u32) -> anyhow::Result<Option<MiningWork>> {
+        self.call_rpc_blocking(|inner| inner.miner_client.get_work(worker_id))
+            .map_err(map_err)
+    }
+
     pub fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
         self.call_rpc_blocking(|inner| inner.txpool_client.state())
             .map_err(map_err)
     }
 
+    pub fn txpool_analytics(&self, top_senders: Option<u32>) -> anyhow::Result<TxPoolAnalytics> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.analytics(top_senders))
+            .map_err(map_err)
+    }
+
     pub fn subscribe_events(
         &self,
         filter: EventFilter,
@@ -763,9 +1130,18 @@ impl RpcClient {
                     .await
                     .map(|c| c.into())?;
                 *(self.inner.lock()) = Some(new_inner.clone());
-                self.chain_watcher.do_send(StartSubscribe {
-                    client: new_inner.pubsub_client.clone(),
-                });
+                let trace_id = self.trace_id.lock().clone();
+                // the http connection sends the trace id by the header of every request.
+                if trace_id.is_some() && !matches!(self.provider.conn_source, ConnSource::Http(_)) {
+                    new_inner
+                        .raw_client
+                        .notify(TRACE_NOTIFICATION, trace_params(trace_id))?;
+                }
+                if self.provider.conn_source.support_pubsub() {
+                    self.chain_watcher.do_send(StartSubscribe {
+                        client: new_inner.pubsub_client.clone(),
+                    });
+                }
                 new_inner
             }
         };
@@ -832,6 +1208,50 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn network_peers(&self) -> anyhow::Result<Vec<PeerStatusView>> {
+        self.call_rpc_blocking(|inner| inner.network_client.peers())
+            .map_err(map_err)
+    }
+
+    pub fn network_ban_peer(
+        &self,
+        peer_id: String,
+        duration: u64,
+    ) -> anyhow::Result<BannedPeerView> {
+        self.call_rpc_blocking(|inner| inner.network_client.ban_peer(peer_id, duration))
+            .map_err(map_err)
+    }
+
+    pub fn network_unban_peer(&self, peer_id: String) -> anyhow::Result<bool> {
+        self.call_rpc_blocking(|inner| inner.network_client.unban_peer(peer_id))
+            .map_err(map_err)
+    }
+
+    pub fn network_banned_peers(&self) -> anyhow::Result<Vec<BannedPeerView>> {
+        self.call_rpc_blocking(|inner| inner.network_client.banned_peers())
+            .map_err(map_err)
+    }
+
+    pub fn network_trust_peer(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.network_client.trust_peer(peer))
+            .map_err(map_err)
+    }
+
+    pub fn network_untrust_peer(&self, peer: String) -> anyhow::Result<bool> {
+        self.call_rpc_blocking(|inner| inner.network_client.untrust_peer(peer))
+            .map_err(map_err)
+    }
+
+    pub fn network_trusted_peers(&self) -> anyhow::Result<Vec<String>> {
+        self.call_rpc_blocking(|inner| inner.network_client.trusted_peers())
+            .map_err(map_err)
+    }
+
+    pub fn network_rotate_identity(&self) -> anyhow::Result<IdentityRotationView> {
+        self.call_rpc_blocking(|inner| inner.network_client.rotate_identity())
+            .map_err(map_err)
+    }
+
     pub fn network_call_peer(
         &self,
         peer_id: String,
@@ -846,6 +1266,47 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    fn ensure_pubsub(&self) -> anyhow::Result<()> {
+        if !self.provider.conn_source.support_pubsub() {
+            anyhow::bail!(
+                "Watch is not supported by connection {:?}, please use ipc or websocket.",
+                self.provider.conn_source
+            );
+        }
+        Ok(())
+    }
+
+    /// Create a batch to send multi calls in one round trip.
+    pub fn batch(&self) -> RpcBatch<'_> {
+        RpcBatch::new(self)
+    }
+
+    /// Send the calls by a JSON-RPC batch request for the http connection, the other connections
+    /// keep the connection open, so the calls are sent together without waiting for the responses.
+    pub(crate) fn call_batch(
+        &self,
+        calls: Vec<(String, Params)>,
+    ) -> Result<
+        Vec<Result<Value, jsonrpc_client_transports::RpcError>>,
+        jsonrpc_client_transports::RpcError,
+    > {
+        if let ConnSource::Http(url) = &self.provider.conn_source {
+            let trace_id = self.trace_id();
+            return self.provider.block_on(http_transport::send_batch(
+                &self.provider.http_client,
+                url.as_str(),
+                trace_id,
+                calls,
+            ));
+        }
+        self.call_rpc_blocking(|inner| async move {
+            let futures = calls
+                .into_iter()
+                .map(|(method, params)| inner.raw_client.call_method(method.as_str(), params));
+            Ok(futures::future::join_all(futures).await)
+        })
+    }
+
     pub fn call_raw_api(&self, api: &str, params: Params) -> anyhow::Result<Value> {
         self.call_rpc_blocking(|inner| inner.raw_client.call_method(api, params))
             .map_err(map_err)
@@ -867,6 +1328,8 @@ pub(crate) struct RpcClientInner {
     node_client: NodeClient,
     node_manager_client: NodeManagerClient,
     txpool_client: TxPoolClient,
+    #[cfg(feature = "threshold-encryption")]
+    encrypted_txpool_client: EncryptedTxPoolClient,
     account_client: AccountClient,
     state_client: StateClient,
     debug_client: DebugClient,
@@ -885,6 +1348,8 @@ impl RpcClientInner {
             node_client: channel.clone().into(),
             node_manager_client: channel.clone().into(),
             txpool_client: channel.clone().into(),
+            #[cfg(feature = "threshold-encryption")]
+            encrypted_txpool_client: channel.clone().into(),
             account_client: channel.clone().into(),
             state_client: channel.clone().into(),
             debug_client: channel.clone().into(),
@@ -902,6 +1367,10 @@ fn map_err(rpc_err: jsonrpc_client_transports::RpcError) -> anyhow::Error {
     anyhow!(format!("{}", rpc_err))
 }
 
+fn trace_params(trace_id: Option<String>) -> Params {
+    Params::Array(vec![trace_id.map(Value::String).unwrap_or(Value::Null)])
+}
+
 impl From<RpcChannel> for RpcClientInner {
     fn from(channel: RpcChannel) -> Self {
         Self::new(channel)
@@ -4,12 +4,38 @@
 use crate::RpcClient;
 use anyhow::Result;
 use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::BlockStateRootView;
 use starcoin_state_api::{ChainStateReader, StateView, StateWithProof};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
 use starcoin_types::state_set::{AccountStateSet, ChainStateSet};
 
+/// The state at the root is pruned or never exists in the node.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateUnavailableError {
+    pub state_root: HashValue,
+    /// The available state roots of the latest main chain blocks of the node.
+    pub latest_available_roots: Vec<BlockStateRootView>,
+}
+
+impl std::fmt::Display for StateUnavailableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "State unavailable at root {}", self.state_root)?;
+        if !self.latest_available_roots.is_empty() {
+            let roots = self
+                .latest_available_roots
+                .iter()
+                .map(|root| format!("{}(block #{})", root.state_root, root.block_number.0))
+                .collect::<Vec<_>>();
+            write!(f, ", the latest available roots: {}", roots.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StateUnavailableError {}
+
 pub struct RemoteStateReader<'a> {
     //TODO add cache.
     client: &'a RpcClient,
@@ -19,11 +45,26 @@ pub struct RemoteStateReader<'a> {
 impl<'a> RemoteStateReader<'a> {
     pub fn new(client: &'a RpcClient) -> Result<Self> {
         let state_root = client.state_get_state_root()?;
-        Ok(Self::new_with_root(client, state_root))
+        Ok(Self { client, state_root })
+    }
+
+    /// Create a reader at the `state_root`, the reads return a `StateUnavailableError`
+    /// if the state at the root is not available in the node.
+    pub fn new_with_root(client: &'a RpcClient, state_root: HashValue) -> Result<Self> {
+        Ok(Self { client, state_root })
     }
 
-    pub fn new_with_root(client: &'a RpcClient, state_root: HashValue) -> Self {
-        Self { client, state_root }
+    /// Check the state root status only when a read fails, so the reads do not pay an extra round trip,
+    /// return a `StateUnavailableError` if the state at the root is not available, otherwise the error of the read.
+    fn map_read_err(&self, err: anyhow::Error) -> anyhow::Error {
+        match self.client.state_get_state_root_status(self.state_root) {
+            Ok(status) if !status.available => StateUnavailableError {
+                state_root: self.state_root,
+                latest_available_roots: status.latest_available_roots,
+            }
+            .into(),
+            _ => err,
+        }
     }
 }
 
@@ -55,7 +96,8 @@ impl<'a> StateView for RemoteStateReader<'a> {
     fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
         Ok(self
             .client
-            .state_get_with_proof_by_root(access_path.clone(), self.state_root())?
+            .state_get_with_proof_by_root(access_path.clone(), self.state_root())
+            .map_err(|e| self.map_read_err(e))?
             .state
             .map(|v| v.0))
     }
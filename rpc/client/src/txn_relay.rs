@@ -0,0 +1,193 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Proxy;
+use serde_json::Value;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_txpool_api::TxnKeepPolicy;
+use starcoin_types::transaction::SignedUserTransaction;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const RELAY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Submit transactions through remote relay nodes instead of the connected node,
+/// so the transactions can not be linked to the identity of the user's node.
+pub struct TxnRelay {
+    relays: Vec<String>,
+    next: AtomicUsize,
+    client: Client,
+}
+
+impl TxnRelay {
+    /// `relays` are the http rpc addresses of the relay nodes, they are used in round-robin.
+    /// `proxy` is the proxy to connect the relays, such as the tor socks proxy `socks5h://127.0.0.1:9050`,
+    /// the `socks5h` scheme resolves the relay address by the proxy, so the onion address is supported.
+    pub fn new(relays: Vec<String>, proxy: Option<String>) -> Result<Self> {
+        ensure!(!relays.is_empty(), "At least one txn relay is required.");
+        let mut builder = Client::builder().timeout(RELAY_TIMEOUT);
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(Proxy::all(proxy.as_str())?);
+        }
+        Ok(Self {
+            relays,
+            next: AtomicUsize::new(0),
+            client: builder.build()?,
+        })
+    }
+
+    pub fn relays(&self) -> &[String] {
+        self.relays.as_slice()
+    }
+
+    /// Submit the txn by `txpool.submit_transaction` of the relays, the `idempotency_key` is kept.
+    pub fn submit_transaction(
+        &self,
+        txn: &SignedUserTransaction,
+        idempotency_key: Option<String>,
+    ) -> Result<HashValue> {
+        self.submit(
+            "txpool.submit_transaction",
+            serde_json::json!([txn, idempotency_key]),
+        )
+    }
+
+    /// Submit the txn by `txpool.submit_transaction_with_policy` of the relays,
+    /// the relay node keeps the txn by the policy.
+    pub fn submit_transaction_with_policy(
+        &self,
+        txn: &SignedUserTransaction,
+        policy: &TxnKeepPolicy,
+    ) -> Result<HashValue> {
+        self.submit(
+            "txpool.submit_transaction_with_policy",
+            serde_json::json!([txn, policy]),
+        )
+    }
+
+    /// Call the submit method of the relays in round-robin, try the next relay if failed.
+    fn submit(&self, method: &str, params: Value) -> Result<HashValue> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut errors = vec![];
+        for i in 0..self.relays.len() {
+            let relay = &self.relays[start.wrapping_add(i) % self.relays.len()];
+            match self.submit_to(relay, method, &params) {
+                Ok(txn_hash) => {
+                    debug!("Submit txn {} by relay {}", txn_hash, relay);
+                    return Ok(txn_hash);
+                }
+                Err(e) => {
+                    warn!("Submit txn by relay {} failed: {}", relay, e);
+                    errors.push(format!("{}: {}", relay, e));
+                }
+            }
+        }
+        bail!("Submit txn by all relays failed, {}", errors.join(", "))
+    }
+
+    fn submit_to(&self, relay: &str, method: &str, params: &Value) -> Result<HashValue> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = self
+            .client
+            .post(relay)
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()?
+            .error_for_status()?;
+        let response: Value = serde_json::from_slice(&response.bytes()?)?;
+        if let Some(error) = response.get("error") {
+            bail!("{}", error);
+        }
+        let result = response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| format_err!("Invalid relay response: {}", response))?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_transport::tests::read_request;
+    use bcs_ext::Sample;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    /// Serve a relay request, return the request.
+    fn serve_relay(listener: TcpListener, txn_hash: HashValue) -> std::thread::JoinHandle<Value> {
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (_header, body) = read_request(&mut stream);
+            let request: Value = serde_json::from_str(body.as_str()).unwrap();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "result": txn_hash,
+                "id": request["id"],
+            })
+            .to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            request
+        })
+    }
+
+    /// The address of a closed port, the relay on it fails.
+    fn closed_relay() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn test_relay_submit_methods() {
+        let txn = SignedUserTransaction::sample();
+        let txn_hash = txn.id();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let relay = format!("http://{}", listener.local_addr().unwrap());
+        let handle = serve_relay(listener, txn_hash);
+        // the failed relay is skipped.
+        let txn_relay = TxnRelay::new(vec![closed_relay(), relay], None).unwrap();
+        assert_eq!(
+            txn_relay
+                .submit_transaction(&txn, Some("key".to_string()))
+                .unwrap(),
+            txn_hash
+        );
+        let request = handle.join().unwrap();
+        assert_eq!(request["method"], "txpool.submit_transaction");
+        assert_eq!(request["params"], serde_json::json!([txn, "key"]));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let relay = format!("http://{}", listener.local_addr().unwrap());
+        let handle = serve_relay(listener, txn_hash);
+        let txn_relay = TxnRelay::new(vec![relay], None).unwrap();
+        let policy = TxnKeepPolicy::default();
+        assert_eq!(
+            txn_relay
+                .submit_transaction_with_policy(&txn, &policy)
+                .unwrap(),
+            txn_hash
+        );
+        let request = handle.join().unwrap();
+        assert_eq!(request["method"], "txpool.submit_transaction_with_policy");
+        assert_eq!(request["params"], serde_json::json!([txn, policy]));
+
+        let txn_relay = TxnRelay::new(vec![closed_relay()], None).unwrap();
+        assert!(txn_relay.submit_transaction(&txn, None).is_err());
+    }
+}
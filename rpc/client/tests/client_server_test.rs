@@ -5,8 +5,11 @@ use anyhow::Result;
 use futures::{StreamExt, TryStreamExt};
 use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::node::NodeInfo;
+use starcoin_rpc_api::types::ChainInfoView;
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_vm_types::account_config::{association_address, stc_type_tag, BalanceResource};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -43,9 +46,22 @@ fn do_client_test() -> Result<()> {
         RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
     let status = ws_client.node_info()?;
     info!("ws_client node_status: {:?}", status);
+
+    let http_url = config.rpc.get_http_address().unwrap();
+    let http_client =
+        RpcClient::connect_http(http_url.to_string().as_str()).expect("connect http fail.");
+    let batch_response = http_client.batch().node_info().chain_info().send()?;
+    assert_eq!(batch_response.len(), 2);
+    let node_info = batch_response.get::<NodeInfo>(0)?;
+    assert_eq!(node_info.net, status.net);
+    let chain_info = batch_response.get::<ChainInfoView>(1)?;
+    info!("http_client chain_info: {:?}", chain_info);
+    assert!(http_client.watch_block(1).is_err());
+
     local_client.close();
     ipc_client.close();
     ws_client.close();
+    http_client.close();
     if let Err(e) = node_handle.stop() {
         error!("node stop error: {:?}", e)
     }
@@ -139,3 +155,28 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test]
+fn test_state_get_resource_at() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config)?;
+    let client = RpcClient::connect_local(node_handle.rpc_service()?)?;
+
+    let balance_type = BalanceResource::struct_tag_for_token(stc_type_tag());
+    let balance = client.state_get_resource_at(association_address(), balance_type.clone(), 0)?;
+    assert!(balance.is_some());
+    let balances = client.state_get_resource_at_blocks(
+        association_address(),
+        balance_type.clone(),
+        vec![0, 0],
+    )?;
+    assert_eq!(balances.len(), 2);
+    assert!(balances.iter().all(Option::is_some));
+    assert!(client
+        .state_get_resource_at(association_address(), balance_type, 1000)
+        .is_err());
+
+    client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
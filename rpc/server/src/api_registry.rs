@@ -2,13 +2,18 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
+use crate::trace_middleware::TraceMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use starcoin_config::{Api, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::MetricMiddleware;
 use std::collections::HashMap;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    TraceMiddleware,
+    MetricMiddleware,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
@@ -30,6 +35,7 @@ impl ApiRegistry {
         let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
         let io_handler = self.apis.entry(api_type).or_insert_with(|| {
             MetaIoHandler::<Metadata, Middlewares>::with_middleware((
+                TraceMiddleware,
                 MetricMiddleware,
                 rate_limit_middleware,
             ))
@@ -47,6 +53,7 @@ impl ApiRegistry {
             .map(|api_type| self.apis.get(&api_type))
             .fold(
                 MetaIoHandler::<Metadata, Middlewares>::with_middleware((
+                    TraceMiddleware,
                     MetricMiddleware,
                     rate_limit_middleware,
                 )),
@@ -3,7 +3,7 @@
 
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
-use starcoin_rpc_api::metadata::Metadata;
+use starcoin_rpc_api::metadata::{Metadata, TraceId, TRACE_ID_HEADER};
 use std::net::IpAddr;
 use std::sync::Arc;
 
@@ -27,9 +27,16 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        let trace_id = _req
+            .headers()
+            .get(TRACE_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
         Metadata {
             session: None,
             user: client_ip.map(|ip| ip.to_string()),
+            trace_id: TraceId::new(trace_id),
         }
     }
 }
@@ -38,6 +45,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            trace_id: TraceId::default(),
         }
     }
 }
@@ -46,6 +54,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            trace_id: TraceId::default(),
         }
     }
 }
@@ -57,6 +66,7 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         Metadata {
             session,
             user: None,
+            trace_id: TraceId::default(),
         }
     }
 }
@@ -6,3 +6,4 @@ mod extractors;
 pub mod module;
 mod rate_limit_middleware;
 pub mod service;
+mod trace_middleware;
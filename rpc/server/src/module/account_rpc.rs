@@ -3,15 +3,21 @@
 
 use crate::module::helpers::TransactionRequestFiller;
 use crate::module::map_err;
+use anyhow::format_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
-use starcoin_account_api::{AccountAsyncService, AccountInfo};
+use starcoin_account_api::{
+    AccountAsyncService, AccountInfo, AccountMetadataUpdate, KeystoreEntry,
+};
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::{StrView, TransactionRequest};
 use starcoin_rpc_api::{account::AccountApi, FutureResult};
+use starcoin_service_registry::ServiceRef;
 use starcoin_state_api::ChainStateAsyncService;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool::{SubmitTxnWithPolicyRequest, TxnKeeperService};
+use starcoin_txpool_api::{TxPoolSyncService, TxnKeepPolicy};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::SigningMessage;
@@ -32,6 +38,7 @@ where
     chain_state: State,
     chain: Chain,
     node_config: Arc<NodeConfig>,
+    txn_keeper: Option<ServiceRef<TxnKeeperService>>,
 }
 
 impl<Account, Pool, State, Chain> AccountRpcImpl<Account, Pool, State, Chain>
@@ -54,8 +61,14 @@ where
             chain_state,
             chain,
             node_config,
+            txn_keeper: None,
         }
     }
+
+    pub fn with_txn_keeper(mut self, txn_keeper: ServiceRef<TxnKeeperService>) -> Self {
+        self.txn_keeper = Some(txn_keeper);
+        self
+    }
     fn txn_request_filler(&self) -> TransactionRequestFiller<Account, Pool, State, Chain> {
         TransactionRequestFiller {
             account: Some(self.account.clone()),
@@ -173,16 +186,19 @@ where
         address: AccountAddress,
         password: String,
         duration: Option<u32>,
+        max_amount: Option<StrView<u128>>,
     ) -> FutureResult<AccountInfo> {
         let service = self.account.clone();
         let fut = async move {
-            service
-                .unlock_account(
-                    address,
-                    password,
-                    Duration::from_secs(duration.unwrap_or(u32::MAX) as u64),
-                )
-                .await
+            let duration = Duration::from_secs(duration.unwrap_or(u32::MAX) as u64);
+            match max_amount {
+                Some(max_amount) => {
+                    service
+                        .unlock_account_with_limit(address, password, duration, max_amount.0)
+                        .await
+                }
+                None => service.unlock_account(address, password, duration).await,
+            }
         }
         .map_err(map_err);
         Box::pin(fut.boxed())
@@ -212,6 +228,48 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn derive(
+        &self,
+        mnemonic: String,
+        path: String,
+        password: String,
+    ) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.derive_account(mnemonic, path, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn restore(
+        &self,
+        mnemonic: String,
+        password: String,
+        count: u32,
+    ) -> FutureResult<Vec<AccountInfo>> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.restore_accounts(mnemonic, password, count).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn export_keystore(&self) -> FutureResult<Vec<KeystoreEntry>> {
+        let service = self.account.clone();
+        let fut = async move { service.export_keystore().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn import_keystore(&self, entries: Vec<KeystoreEntry>) -> FutureResult<Vec<AccountInfo>> {
+        let service = self.account.clone();
+        let fut = async move { service.import_keystore(entries).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn import_readonly(
         &self,
         address: AccountAddress,
@@ -270,4 +328,32 @@ where
         let fut = async move { service.remove_account(address, password).await }.map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn update_metadata(
+        &self,
+        address: AccountAddress,
+        update: AccountMetadataUpdate,
+    ) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut =
+            async move { service.update_account_metadata(address, update).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn submit_txn_with_policy(
+        &self,
+        txn: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> FutureResult<HashValue> {
+        let txn_keeper = self.txn_keeper.clone();
+        let fut = async move {
+            let txn_keeper =
+                txn_keeper.ok_or_else(|| format_err!("Txn keeper service is not available."))?;
+            txn_keeper
+                .send(SubmitTxnWithPolicyRequest { txn, policy })
+                .await?
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
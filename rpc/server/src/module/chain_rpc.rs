@@ -10,11 +10,12 @@ use starcoin_rpc_api::chain::ChainApi;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
     BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    TotalSupplyView, TransactionEventView, TransactionInfoView, TransactionView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_types::block::{BlockInfo, BlockNumber};
 use starcoin_types::filter::Filter;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::TransactionInfo;
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
@@ -239,6 +240,22 @@ where
 
         Box::pin(fut.boxed())
     }
+
+    fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        transaction_hash: HashValue,
+    ) -> FutureResult<Option<TransactionInfoWithProof>> {
+        let service = self.service.clone();
+        let fut = async move {
+            service
+                .get_transaction_proof(block_hash, transaction_hash)
+                .await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
@@ -302,6 +319,20 @@ where
         Box::pin(fut.boxed().map_err(map_err))
     }
 
+    fn total_supply_at(&self, number: BlockNumber) -> FutureResult<TotalSupplyView> {
+        let service = self.service.clone();
+        let fut = async move {
+            let info = service.get_total_supply_by_number(number).await?;
+            Ok(TotalSupplyView {
+                block_number: info.block_number.into(),
+                total_supply: info.total_supply.into(),
+                treasury_balance: info.treasury_balance.map(Into::into),
+            })
+        };
+
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
     fn get_global_time_by_number(&self, number: BlockNumber) -> FutureResult<GlobalTimeOnChain> {
         let service = self.service.clone();
         let fut = async move { service.get_global_time_by_number(number).await };
@@ -116,9 +116,13 @@ where
             function_id,
             type_args,
             args,
+            state_root,
         } = call;
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = match state_root {
+                Some(state_root) => state_root,
+                None => service.state_root().await?,
+            };
             let output = playground.call_contract(
                 state_root,
                 function_id.0.module,
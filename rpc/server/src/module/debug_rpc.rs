@@ -1,12 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
+use crate::module::{map_err, to_invalid_param_err};
+use anyhow::format_err;
 use jsonrpc_core::Result;
 use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::LevelFilter;
-use starcoin_logger::{LogPattern, LoggerHandle};
+use starcoin_logger::{LogPattern, LogSubsystem, LoggerHandle};
 use starcoin_rpc_api::debug::DebugApi;
 use starcoin_rpc_api::types::FactoryAction;
 use std::str::FromStr;
@@ -47,6 +48,18 @@ impl DebugApi for DebugRpcImpl {
         Ok(())
     }
 
+    fn log_tail(&self, subsystem: Option<LogSubsystem>, lines: usize) -> Result<Vec<String>> {
+        let max_lines = self.config.rpc.log_tail_max_lines();
+        if lines > max_lines {
+            return Err(to_invalid_param_err(format_err!(
+                "The lines {} exceeds the max {}",
+                lines,
+                max_lines
+            )));
+        }
+        self.log_handle.tail(subsystem, lines).map_err(map_err)
+    }
+
     fn panic(&self) -> Result<()> {
         if !self.config.net().is_test() || self.config.net().is_dev() {
             return Err(jsonrpc_core::Error::invalid_request());
@@ -0,0 +1,74 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::module::map_err;
+use anyhow::format_err;
+use futures::{FutureExt, TryFutureExt};
+use starcoin_crypto::HashValue;
+/// Re-export the API
+pub use starcoin_rpc_api::encrypted_txpool::*;
+use starcoin_rpc_api::FutureResult;
+use starcoin_service_registry::ServiceRef;
+use starcoin_txpool::{
+    EncryptedPoolService, SealedEncryptedTxnsRequest, SubmitDecryptionSharesRequest,
+    SubmitEncryptedTxnRequest,
+};
+use starcoin_txpool_api::{DecryptionShare, EncryptedTransaction, SealedEncryptedTransaction};
+
+pub struct EncryptedTxPoolRpcImpl {
+    encrypted_pool: ServiceRef<EncryptedPoolService>,
+    mirror: bool,
+}
+
+impl EncryptedTxPoolRpcImpl {
+    pub fn new(encrypted_pool: ServiceRef<EncryptedPoolService>) -> Self {
+        Self {
+            encrypted_pool,
+            mirror: false,
+        }
+    }
+
+    /// Reject the encrypted txn submissions, the txpool of a mirror node is view-only.
+    pub fn with_mirror_mode(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+}
+
+impl EncryptedTxPoolApi for EncryptedTxPoolRpcImpl {
+    fn submit_encrypted_transaction(&self, txn: EncryptedTransaction) -> FutureResult<HashValue> {
+        let mirror = self.mirror;
+        let encrypted_pool = self.encrypted_pool.clone();
+        let fut = async move {
+            if mirror {
+                return Err(format_err!(
+                    "The txpool is in the mirror mode, the txn submission is not accepted."
+                ));
+            }
+            encrypted_pool.send(SubmitEncryptedTxnRequest(txn)).await?
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn submit_decryption_shares(
+        &self,
+        shares: Vec<DecryptionShare>,
+    ) -> FutureResult<Vec<HashValue>> {
+        let encrypted_pool = self.encrypted_pool.clone();
+        let fut = async move {
+            encrypted_pool
+                .send(SubmitDecryptionSharesRequest(shares))
+                .await?
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn sealed_encrypted_transactions(&self) -> FutureResult<Vec<SealedEncryptedTransaction>> {
+        let encrypted_pool = self.encrypted_pool.clone();
+        let fut =
+            async move { encrypted_pool.send(SealedEncryptedTxnsRequest).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+}
@@ -3,13 +3,13 @@
 
 use crate::module::map_err;
 use futures::{FutureExt, TryFutureExt};
-use starcoin_miner::{MinerService, SubmitSealRequest, UpdateSubscriberNumRequest};
+use starcoin_miner::{GetWorkRequest, MinerService, SubmitSealRequest, UpdateSubscriberNumRequest};
 use starcoin_rpc_api::miner::MinerApi;
 use starcoin_rpc_api::types::MintedBlockView;
 use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::ServiceRef;
 use starcoin_types::block::BlockHeaderExtra;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{MiningWork, MintBlockEvent};
 use std::convert::TryInto;
 
 pub struct MinerRpcImpl {
@@ -61,4 +61,11 @@ impl MinerApi for MinerRpcImpl {
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn get_work(&self, worker_id: u32) -> FutureResult<Option<MiningWork>> {
+        let miner_service = self.miner_service.clone();
+        let fut =
+            async move { miner_service.send(GetWorkRequest { worker_id }).await? }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
@@ -5,6 +5,8 @@ mod account_rpc;
 mod chain_rpc;
 mod contract_rpc;
 mod debug_rpc;
+#[cfg(feature = "threshold-encryption")]
+mod encrypted_txpool_rpc;
 mod helpers;
 mod miner_rpc;
 mod network_manager_rpc;
@@ -20,6 +22,8 @@ pub use self::account_rpc::AccountRpcImpl;
 pub use self::chain_rpc::ChainRpcImpl;
 pub use self::contract_rpc::ContractRpcImpl;
 pub use self::debug_rpc::DebugRpcImpl;
+#[cfg(feature = "threshold-encryption")]
+pub use self::encrypted_txpool_rpc::{EncryptedTxPoolApi, EncryptedTxPoolRpcImpl};
 pub use self::miner_rpc::MinerRpcImpl;
 pub use self::network_manager_rpc::NetworkManagerRpcImpl;
 pub use self::node_manager_rpc::NodeManagerRpcImpl;
@@ -35,7 +39,9 @@ use anyhow::Error;
 use hex::FromHexError;
 use jsonrpc_core::ErrorCode;
 use starcoin_account_api::error::AccountError;
+use starcoin_dev::sandbox::SandboxError;
 use starcoin_rpc_api::types::TransactionVMStatus;
+use starcoin_vm_types::account_address::AddressParseError;
 use starcoin_vm_types::transaction::{CallError, TransactionError, TransactionStatus};
 use starcoin_vm_types::vm_status::VMStatus;
 
@@ -55,6 +61,10 @@ pub fn map_err(err: anyhow::Error) -> jsonrpc_core::Error {
         err.downcast::<MailboxError>().unwrap().into()
     } else if err.is::<VMStatus>() {
         err.downcast::<VMStatus>().unwrap().into()
+    } else if err.is::<SandboxError>() {
+        err.downcast::<SandboxError>().unwrap().into()
+    } else if err.is::<AddressParseError>() {
+        err.downcast::<AddressParseError>().unwrap().into()
     } else {
         err.into()
     };
@@ -91,6 +101,7 @@ impl From<anyhow::Error> for RpcError {
 
 const TXN_ERROR_BASE: i64 = -50000;
 const ACCOUNT_ERROR_BASE: i64 = -60000;
+const SANDBOX_ERROR_BASE: i64 = -70000;
 
 impl From<AccountError> for RpcError {
     fn from(err: AccountError) -> Self {
@@ -154,6 +165,16 @@ impl From<TransactionError> for RpcError {
     }
 }
 
+impl From<AddressParseError> for RpcError {
+    fn from(err: AddressParseError) -> Self {
+        RpcError(jsonrpc_core::Error {
+            code: ErrorCode::InvalidParams,
+            message: err.to_string(),
+            data: None,
+        })
+    }
+}
+
 impl From<hex::FromHexError> for RpcError {
     fn from(err: FromHexError) -> Self {
         RpcError(jsonrpc_core::Error {
@@ -207,3 +228,39 @@ where
     let message = format!("Invalid param error: {:?}", anyhow_err);
     jsonrpc_core::Error::invalid_params(message)
 }
+
+impl From<SandboxError> for RpcError {
+    fn from(err: SandboxError) -> Self {
+        let err_message = err.to_string();
+        let (err_code, err_data) = match err {
+            SandboxError::TimeExceeded { limit_ms } => (
+                ErrorCode::ServerError(SANDBOX_ERROR_BASE),
+                serde_json::json!({ "limit": "time", "limit_ms": limit_ms }),
+            ),
+            SandboxError::StateReadExceeded {
+                limit_bytes,
+                read_bytes,
+            } => (
+                ErrorCode::ServerError(SANDBOX_ERROR_BASE + 1),
+                serde_json::json!({
+                    "limit": "state_read",
+                    "limit_bytes": limit_bytes,
+                    "read_bytes": read_bytes,
+                }),
+            ),
+            SandboxError::Aborted(reason) => (
+                ErrorCode::ServerError(SANDBOX_ERROR_BASE + 2),
+                serde_json::json!({ "reason": reason }),
+            ),
+            SandboxError::Busy { limit } => (
+                ErrorCode::ServerError(SANDBOX_ERROR_BASE + 3),
+                serde_json::json!({ "limit": "running", "max_running": limit }),
+            ),
+        };
+        RpcError(jsonrpc_core::Error {
+            code: err_code,
+            message: err_message,
+            data: Some(err_data),
+        })
+    }
+}
@@ -4,15 +4,18 @@
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use network_api::PeerProvider;
 use network_p2p_types::network_state::NetworkState;
 use network_rpc_core::RawRpcClient;
 use starcoin_network::NetworkServiceRef;
 use starcoin_rpc_api::network_manager::NetworkManagerApi;
-use starcoin_rpc_api::types::StrView;
+use starcoin_rpc_api::types::{BannedPeerView, IdentityRotationView, PeerStatusView, StrView};
 use starcoin_rpc_api::FutureResult;
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
 pub struct NetworkManagerRpcImpl {
     service: NetworkServiceRef,
@@ -58,6 +61,118 @@ impl NetworkManagerApi for NetworkManagerRpcImpl {
         Box::pin(fut.boxed())
     }
 
+    fn peers(&self) -> FutureResult<Vec<PeerStatusView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peers = service.peer_set().await?;
+            let network_state = service.network_state().await?;
+            let reputations: HashMap<PeerId, i32> = service
+                .reputations(i32::MIN)
+                .await?
+                .await?
+                .into_iter()
+                .collect();
+            let trusted_peers = service.trusted_peers().await?;
+            let result = peers
+                .into_iter()
+                .map(|peer| {
+                    let peer_id = peer.peer_id;
+                    let latency = network_state
+                        .connected_peers
+                        .get(&peer_id.to_string())
+                        .and_then(|state| state.latest_ping_time)
+                        .map(|latency| latency.as_millis() as u64);
+                    let p2p_suffix = format!("/p2p/{}", peer_id);
+                    PeerStatusView {
+                        latency,
+                        reputation: reputations.get(&peer_id).cloned(),
+                        trusted: trusted_peers
+                            .iter()
+                            .any(|trusted| trusted.ends_with(p2p_suffix.as_str())),
+                        peer_id,
+                    }
+                })
+                .collect();
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn ban_peer(&self, peer_id: String, duration: u64) -> FutureResult<BannedPeerView> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peer_id = PeerId::from_str(peer_id.as_str())?;
+            let banned_peer = service
+                .ban_peer(peer_id, Duration::from_secs(duration))
+                .await?;
+            Ok(BannedPeerView {
+                peer_id: banned_peer.peer_id,
+                banned_until: banned_peer.banned_until,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn unban_peer(&self, peer_id: String) -> FutureResult<bool> {
+        let service = self.service.clone();
+        let fut = async move {
+            let peer_id = PeerId::from_str(peer_id.as_str())?;
+            service.unban_peer(peer_id).await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn banned_peers(&self) -> FutureResult<Vec<BannedPeerView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let banned_peers = service.banned_peers().await?;
+            Ok(banned_peers
+                .into_iter()
+                .map(|banned_peer| BannedPeerView {
+                    peer_id: banned_peer.peer_id,
+                    banned_until: banned_peer.banned_until,
+                })
+                .collect())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn trust_peer(&self, peer: String) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move { service.trust_peer(peer).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn untrust_peer(&self, peer: String) -> FutureResult<bool> {
+        let service = self.service.clone();
+        let fut = async move { service.untrust_peer(peer).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn trusted_peers(&self) -> FutureResult<Vec<String>> {
+        let service = self.service.clone();
+        let fut = async move { service.trusted_peers().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn rotate_identity(&self) -> FutureResult<IdentityRotationView> {
+        let service = self.service.clone();
+        let fut = async move {
+            let linkage = service.rotate_identity().await?;
+            Ok(IdentityRotationView {
+                old_peer_id: linkage.old_peer_id(),
+                new_peer_id: linkage.new_peer_id(),
+                timestamp: linkage.linkage.timestamp,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn call_peer(
         &self,
         peer_id: String,
@@ -4,10 +4,15 @@
 use crate::module::map_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_account_api::PendingTxnApproval;
+use starcoin_crypto::HashValue;
+use starcoin_node_api::maintenance::MaintenanceStatus;
 use starcoin_node_api::node_service::NodeAsyncService;
+use starcoin_node_api::usage::UsageReport;
 use starcoin_rpc_api::node_manager::NodeManagerApi;
 use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use starcoin_sync_api::PendingReorg;
 
 pub struct NodeManagerRpcImpl<S>
 where
@@ -74,4 +79,34 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn maintenance_status(&self) -> FutureResult<MaintenanceStatus> {
+        let service = self.service.clone();
+        let fut = async move { service.maintenance_status().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn usage_report(&self, last_seconds: u64) -> FutureResult<UsageReport> {
+        let service = self.service.clone();
+        let fut = async move { service.usage_report(last_seconds).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn accept_reorg(&self, branch_head: Option<HashValue>) -> FutureResult<PendingReorg> {
+        let service = self.service.clone();
+        let fut = async move { service.accept_reorg(branch_head).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn approve_txn(&self, approval_hash: HashValue) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move { service.approve_txn(approval_hash).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn pending_txn_approvals(&self) -> FutureResult<Vec<PendingTxnApproval>> {
+        let service = self.service.clone();
+        let fut = async move { service.pending_txn_approvals().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
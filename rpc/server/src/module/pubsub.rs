@@ -1,18 +1,23 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use futures::channel::mpsc;
 use futures::future::AbortHandle;
 use futures::StreamExt;
 use jsonrpc_pubsub::typed::Subscriber;
 use jsonrpc_pubsub::SubscriptionId;
 use parking_lot::RwLock;
-use starcoin_chain_notify::message::{Event, Notification, ThinBlock};
+use starcoin_chain_notify::message::{
+    GetNotificationsSince, Notification, NotificationRecord, SequencedNotification,
+};
+use starcoin_chain_notify::ChainNotifyHandlerService;
+use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerService, UpdateSubscriberNumRequest};
 use starcoin_rpc_api::metadata::Metadata;
+use starcoin_rpc_api::types::pubsub::{ReplayParams, SequencedResult};
 use starcoin_rpc_api::types::{BlockView, TransactionEventView};
 use starcoin_rpc_api::{errors, pubsub::StarcoinPubSub, types::pubsub};
 use starcoin_service_registry::{
@@ -21,6 +26,7 @@ use starcoin_service_registry::{
 };
 use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
 use std::collections::HashMap;
@@ -29,6 +35,9 @@ use std::fmt::Debug;
 use std::sync::mpsc::TrySendError;
 use std::sync::{atomic, Arc};
 
+/// The max blocks of the history events backfilled to an events subscription.
+const MAX_BACKFILL_BLOCKS: u64 = 1000;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -57,31 +66,30 @@ fn map_send_err<T>(err: &TrySendError<T>) -> jsonrpc_core::Error {
     }
 }
 
+fn map_internal_err(err: anyhow::Error) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::InternalError,
+        message: err.to_string(),
+        data: None,
+    }
+}
+
 impl PubSubImpl {
     fn inner_subscribe(
         &self,
-        _meta: Metadata,
+        meta: Metadata,
         subscriber: Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
         params: Option<pubsub::Params>,
     ) -> Result<(), (Subscriber<pubsub::Result>, jsonrpc_core::Error)> {
         match (kind, params) {
-            (pubsub::Kind::NewHeads, None) => self
-                .service
-                .try_send(SubscribeNewHeads(subscriber))
-                .map_err(|e| {
-                    let msg = map_send_err(&e);
-                    (
-                        match e {
-                            TrySendError::Disconnected(t) => t.0,
-                            TrySendError::Full(t) => t.0,
-                        },
-                        msg,
-                    )
-                }),
+            (pubsub::Kind::NewHeads, None) => self.subscribe_new_heads(subscriber, None),
+            (pubsub::Kind::NewHeads, Some(pubsub::Params::NewHeads(params))) => {
+                self.subscribe_new_heads(subscriber, Some(params.replay))
+            }
             (pubsub::Kind::NewHeads, _) => Err((
                 subscriber,
-                errors::invalid_params("newHeads", "Expected no parameters."),
+                errors::invalid_params("newHeads", "Expected no parameters or replay parameters."),
             )),
             (pubsub::Kind::NewPendingTransactions, None) => self
                 .service
@@ -100,13 +108,33 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("newPendingTransactions", "Expected no parameters."),
             )),
+            (pubsub::Kind::Events, Some(pubsub::Params::NewHeads(params))) => self.inner_subscribe(
+                meta,
+                subscriber,
+                pubsub::Kind::Events,
+                Some(pubsub::Params::Events(params.into())),
+            ),
             (pubsub::Kind::Events, Some(pubsub::Params::Events(filter))) => {
+                // if the from_block is specified, backfill the history events before the new events.
+                let backfill = filter.from_block.is_some();
+                let replay = filter.replay;
+                if backfill && replay.is_some() {
+                    return Err((
+                        subscriber,
+                        errors::invalid_params(
+                            "events",
+                            "fromBlock can not be used together with replay.",
+                        ),
+                    ));
+                }
                 match filter.try_into() {
                     Ok(f) => self
                         .service
                         .try_send(SubscribeEvents {
                             subscriber,
                             filter: f,
+                            backfill,
+                            replay,
                         })
                         .map_err(|e| {
                             let msg = map_send_err(&e);
@@ -140,6 +168,25 @@ impl PubSubImpl {
                 }),
         }
     }
+
+    fn subscribe_new_heads(
+        &self,
+        subscriber: Subscriber<pubsub::Result>,
+        replay: Option<ReplayParams>,
+    ) -> Result<(), (Subscriber<pubsub::Result>, jsonrpc_core::Error)> {
+        self.service
+            .try_send(SubscribeNewHeads { subscriber, replay })
+            .map_err(|e| {
+                let msg = map_send_err(&e);
+                (
+                    match e {
+                        TrySendError::Disconnected(t) => t.subscriber,
+                        TrySendError::Full(t) => t.subscriber,
+                    },
+                    msg,
+                )
+            })
+    }
 }
 
 impl StarcoinPubSub for PubSubImpl {
@@ -181,7 +228,8 @@ pub struct PubSubServiceFactory;
 
 impl ServiceFactory<PubSubService> for PubSubServiceFactory {
     fn create(ctx: &mut ServiceContext<PubSubService>) -> Result<PubSubService> {
-        let miner_service = ctx.service_ref::<MinerService>()?.clone();
+        // the miner service is not started in the txpool mirror mode.
+        let miner_service = ctx.service_ref_opt::<MinerService>()?.cloned();
         Ok(PubSubService::new(
             ctx.get_shared::<TxPoolService>()?,
             miner_service,
@@ -192,16 +240,18 @@ impl ServiceFactory<PubSubService> for PubSubServiceFactory {
 pub struct PubSubService {
     subscriber_id: Arc<atomic::AtomicU64>,
     txpool: TxPoolService,
-    miner_service: ServiceRef<MinerService>,
+    miner_service: Option<ServiceRef<MinerService>>,
 
-    new_header_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewHeadNotification>>,
-    new_event_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewEventNotification>>,
+    new_header_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<SequencedNotification>>,
+    new_event_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<SequencedNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
+    /// The sequence number of the latest notification received.
+    latest_seq: u64,
 }
 
 impl PubSubService {
-    fn new(txpool: TxPoolService, miner_service: ServiceRef<MinerService>) -> Self {
+    fn new(txpool: TxPoolService, miner_service: Option<ServiceRef<MinerService>>) -> Self {
         let subscriber_id = Arc::new(atomic::AtomicU64::new(0));
         Self {
             subscriber_id,
@@ -211,41 +261,49 @@ impl PubSubService {
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
+            latest_seq: 0,
         }
     }
     fn next_id(&self) -> SubscriptionId {
         let id = self.subscriber_id.fetch_add(1, atomic::Ordering::SeqCst);
         SubscriptionId::Number(id)
     }
+
+    /// Get the chain notify service if the subscriber wants to replay from `since_seq`.
+    fn replay_service(
+        replay: Option<ReplayParams>,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Result<Option<(ServiceRef<ChainNotifyHandlerService>, u64)>> {
+        match replay.and_then(|replay| replay.since_seq) {
+            Some(since_seq) => Ok(Some((
+                ctx.service_ref::<ChainNotifyHandlerService>()?.clone(),
+                since_seq,
+            ))),
+            None => Ok(None),
+        }
+    }
 }
 
-type NewHeadNotification = Notification<ThinBlock>;
-type NewEventNotification = Notification<Arc<[Event]>>;
 // type NewTxns = Arc<[HashValue]>;
 
 impl ActorService for PubSubService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.set_mailbox_capacity(1024);
-        ctx.subscribe::<NewHeadNotification>();
-        ctx.subscribe::<NewEventNotification>();
+        ctx.subscribe::<SequencedNotification>();
         ctx.subscribe::<MintBlockEvent>();
 
         Ok(())
     }
 }
 
-impl ActorEventHandler<Self, NewHeadNotification> for PubSubService {
-    fn handle_event(&mut self, msg: NewHeadNotification, _ctx: &mut ServiceContext<PubSubService>) {
-        send_to_all(&mut self.new_header_subscribers, msg);
-    }
-}
-
-impl ActorEventHandler<Self, NewEventNotification> for PubSubService {
+impl ActorEventHandler<Self, SequencedNotification> for PubSubService {
     fn handle_event(
         &mut self,
-        msg: NewEventNotification,
+        msg: SequencedNotification,
         _ctx: &mut ServiceContext<PubSubService>,
     ) {
+        self.latest_seq = std::cmp::max(self.latest_seq, msg.0.seq);
+        send_to_all(&mut self.new_header_subscribers, msg.clone());
         send_to_all(&mut self.new_event_subscribers, msg);
     }
 }
@@ -257,7 +315,10 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
 }
 
 #[derive(Debug)]
-struct SubscribeNewHeads(Subscriber<pubsub::Result>);
+struct SubscribeNewHeads {
+    subscriber: Subscriber<pubsub::Result>,
+    replay: Option<ReplayParams>,
+}
 
 impl ServiceRequest for SubscribeNewHeads {
     type Response = ();
@@ -265,7 +326,22 @@ impl ServiceRequest for SubscribeNewHeads {
 
 impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
     fn handle(&mut self, msg: SubscribeNewHeads, ctx: &mut ServiceContext<Self>) {
-        let SubscribeNewHeads(sink) = msg;
+        let SubscribeNewHeads { subscriber, replay } = msg;
+        let replay_service = match Self::replay_service(replay, ctx) {
+            Ok(replay_service) => replay_service,
+            Err(e) => {
+                let _ = subscriber.reject(map_internal_err(e));
+                return;
+            }
+        };
+        if let Some((notify_service, since_seq)) = replay_service {
+            Self::load_replay(
+                notify_service,
+                ReplaySubscription::new(subscriber, ReplayTarget::NewHeads, since_seq),
+                ctx,
+            );
+            return;
+        }
         let (sender, receiver) = mpsc::unbounded();
         let subscriber_id = self.next_id();
         self.new_header_subscribers
@@ -273,8 +349,10 @@ impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
         ctx.spawn(run_subscription(
             receiver,
             subscriber_id,
-            sink,
-            NewHeadHandler,
+            subscriber,
+            NewHeadHandler {
+                replay: replay.map(|_| ReplayState::new(0)),
+            },
         ));
     }
 }
@@ -293,7 +371,6 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
         let subscriber_id = self.next_id();
         self.mint_block_subscribers
             .insert(subscriber_id.clone(), sender.clone());
-        let miner_service = self.miner_service.clone();
         let subscribers_num = self.mint_block_subscribers.len() as u32;
         ctx.spawn(run_subscription(
             receiver,
@@ -301,6 +378,10 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
             subscriber,
             NewMintBlockHandler,
         ));
+        let miner_service = match self.miner_service.clone() {
+            Some(miner_service) => miner_service,
+            None => return,
+        };
         ctx.spawn(async move {
             match miner_service
                 .send(UpdateSubscriberNumRequest {
@@ -324,6 +405,8 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
 struct SubscribeEvents {
     subscriber: Subscriber<pubsub::Result>,
     filter: Filter,
+    backfill: bool,
+    replay: Option<ReplayParams>,
 }
 
 impl ServiceRequest for SubscribeEvents {
@@ -332,7 +415,96 @@ impl ServiceRequest for SubscribeEvents {
 
 impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
     fn handle(&mut self, msg: SubscribeEvents, ctx: &mut ServiceContext<Self>) {
-        let SubscribeEvents { subscriber, filter } = msg;
+        let SubscribeEvents {
+            subscriber,
+            filter,
+            backfill,
+            replay,
+        } = msg;
+        if replay.is_some() {
+            self.subscribe_events_with_replay(subscriber, filter, replay, ctx);
+            return;
+        }
+        let chain_service = if backfill {
+            match ctx.service_ref::<ChainReaderService>() {
+                Ok(chain_service) => Some(chain_service.clone()),
+                Err(e) => {
+                    let _ = subscriber.reject(map_internal_err(e));
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        // register the subscriber first, the new events are buffered in the channel when backfill.
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.new_event_subscribers
+            .insert(subscriber_id.clone(), sender);
+        let chain_service = match chain_service {
+            Some(chain_service) => chain_service,
+            None => {
+                ctx.spawn(run_subscription(
+                    receiver,
+                    subscriber_id,
+                    subscriber,
+                    ContractEventHandler {
+                        filter,
+                        replay: None,
+                    },
+                ));
+                return;
+            }
+        };
+        ctx.spawn(async move {
+            match load_history_events(chain_service, filter.clone()).await {
+                Ok((head_number, history)) => {
+                    // the events before head has been sent by history.
+                    let mut filter = filter;
+                    filter.from_block = std::cmp::max(filter.from_block, head_number + 1);
+                    run_subscription_with_history(
+                        history,
+                        receiver,
+                        subscriber_id,
+                        subscriber,
+                        ContractEventHandler {
+                            filter,
+                            replay: None,
+                        },
+                    )
+                    .await
+                }
+                Err(e) => {
+                    let _ = subscriber.reject(map_internal_err(e));
+                }
+            }
+        });
+    }
+}
+
+impl PubSubService {
+    fn subscribe_events_with_replay(
+        &mut self,
+        subscriber: Subscriber<pubsub::Result>,
+        filter: Filter,
+        replay: Option<ReplayParams>,
+        ctx: &mut ServiceContext<Self>,
+    ) {
+        let replay_service = match Self::replay_service(replay, ctx) {
+            Ok(replay_service) => replay_service,
+            Err(e) => {
+                let _ = subscriber.reject(map_internal_err(e));
+                return;
+            }
+        };
+        if let Some((notify_service, since_seq)) = replay_service {
+            Self::load_replay(
+                notify_service,
+                ReplaySubscription::new(subscriber, ReplayTarget::Events(filter), since_seq),
+                ctx,
+            );
+            return;
+        }
         let (sender, receiver) = mpsc::unbounded();
         let subscriber_id = self.next_id();
         self.new_event_subscribers
@@ -341,9 +513,169 @@ impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
             receiver,
             subscriber_id,
             subscriber,
-            ContractEventHandler { filter },
+            ContractEventHandler {
+                filter,
+                replay: Some(ReplayState::new(0)),
+            },
         ));
     }
+
+    /// Load the notifications after the loaded seq of the replay subscription from the chain notify service,
+    /// then send the subscription back to the service to be recorded, the subscriber is rejected on error.
+    fn load_replay(
+        notify_service: ServiceRef<ChainNotifyHandlerService>,
+        subscription: ReplaySubscription,
+        ctx: &mut ServiceContext<Self>,
+    ) {
+        let self_ref = ctx.self_ref();
+        ctx.spawn(async move {
+            let ReplaySubscription {
+                subscriber,
+                target,
+                loaded_seq,
+                mut history,
+            } = subscription;
+            let records = match notify_service
+                .send(GetNotificationsSince {
+                    since_seq: loaded_seq,
+                })
+                .await
+            {
+                Ok(Ok(records)) => records,
+                Ok(Err(e)) | Err(e) => {
+                    let _ = subscriber.reject(map_internal_err(e));
+                    return;
+                }
+            };
+            let replay = ReplayState::new(loaded_seq + 1);
+            let next_loaded_seq = records
+                .last()
+                .map(|record| record.seq)
+                .unwrap_or(loaded_seq);
+            history.extend(
+                records
+                    .into_iter()
+                    .flat_map(|record| target.handle(replay, Notification(record)))
+                    .filter_map(|r| r.ok()),
+            );
+            let subscription = ReplaySubscription {
+                subscriber,
+                target,
+                loaded_seq: next_loaded_seq,
+                history,
+            };
+            if let Err(e) = self_ref.try_send(subscription) {
+                let msg = map_send_err(&e);
+                let subscription = match e {
+                    TrySendError::Disconnected(t) => t,
+                    TrySendError::Full(t) => t,
+                };
+                let _ = subscription.subscriber.reject(msg);
+            }
+        });
+    }
+}
+
+/// The notifications replayed by a replay subscription.
+#[derive(Clone, Debug)]
+enum ReplayTarget {
+    NewHeads,
+    Events(Filter),
+}
+
+impl ReplayTarget {
+    fn handle(
+        &self,
+        replay: ReplayState,
+        msg: SequencedNotification,
+    ) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        match self {
+            ReplayTarget::NewHeads => NewHeadHandler {
+                replay: Some(replay),
+            }
+            .handle(msg),
+            ReplayTarget::Events(filter) => ContractEventHandler {
+                filter: filter.clone(),
+                replay: Some(replay),
+            }
+            .handle(msg),
+        }
+    }
+}
+
+/// A subscription replaying the notifications since a sequence number. The missed notifications are
+/// loaded before the subscriber is recorded, so a rejected subscriber is never recorded.
+#[derive(Debug)]
+struct ReplaySubscription {
+    subscriber: Subscriber<pubsub::Result>,
+    target: ReplayTarget,
+    /// The notifications until this sequence number have been loaded to the history.
+    loaded_seq: u64,
+    history: Vec<pubsub::Result>,
+}
+
+impl ReplaySubscription {
+    fn new(subscriber: Subscriber<pubsub::Result>, target: ReplayTarget, since_seq: u64) -> Self {
+        Self {
+            subscriber,
+            target,
+            loaded_seq: since_seq,
+            history: vec![],
+        }
+    }
+}
+
+impl ServiceRequest for ReplaySubscription {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, ReplaySubscription> for PubSubService {
+    fn handle(&mut self, msg: ReplaySubscription, ctx: &mut ServiceContext<Self>) {
+        // the notifications received while loading are not sent to the subscriber, load them too.
+        if msg.loaded_seq < self.latest_seq {
+            match ctx.service_ref::<ChainNotifyHandlerService>() {
+                Ok(notify_service) => Self::load_replay(notify_service.clone(), msg, ctx),
+                Err(e) => {
+                    let _ = msg.subscriber.reject(map_internal_err(e));
+                }
+            }
+            return;
+        }
+        let ReplaySubscription {
+            subscriber,
+            target,
+            loaded_seq,
+            history,
+        } = msg;
+        // the notifications until the loaded seq are in the history, skip them.
+        let replay = Some(ReplayState::new(loaded_seq + 1));
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        match target {
+            ReplayTarget::NewHeads => {
+                self.new_header_subscribers
+                    .insert(subscriber_id.clone(), sender);
+                ctx.spawn(run_subscription_with_history(
+                    history,
+                    receiver,
+                    subscriber_id,
+                    subscriber,
+                    NewHeadHandler { replay },
+                ));
+            }
+            ReplayTarget::Events(filter) => {
+                self.new_event_subscribers
+                    .insert(subscriber_id.clone(), sender);
+                ctx.spawn(run_subscription_with_history(
+                    history,
+                    receiver,
+                    subscriber_id,
+                    subscriber,
+                    ContractEventHandler { filter, replay },
+                ));
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -396,9 +728,11 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         self.new_header_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
-        self.miner_service.do_send(UpdateSubscriberNumRequest {
-            number: Some(self.mint_block_subscribers.len() as u32),
-        });
+        if let Some(miner_service) = self.miner_service.as_ref() {
+            miner_service.do_send(UpdateSubscriberNumRequest {
+                number: Some(self.mint_block_subscribers.len() as u32),
+            });
+        }
         if let Some(h) = self.new_pending_txn_tasks.write().remove(&msg.0) {
             h.abort();
         }
@@ -438,14 +772,40 @@ async fn run_subscription<M, Handler>(
 ) where
     M: Send + 'static,
     Handler: EventHandler<M> + Send + 'static,
+{
+    run_subscription_with_history(
+        vec![],
+        msg_channel,
+        subscriber_id,
+        subscriber,
+        event_handler,
+    )
+    .await
+}
+
+/// Send the history results to the subscriber first, then the new messages.
+async fn run_subscription_with_history<M, Handler>(
+    history: Vec<pubsub::Result>,
+    msg_channel: mpsc::UnboundedReceiver<M>,
+    subscriber_id: SubscriptionId,
+    subscriber: Subscriber<pubsub::Result>,
+    event_handler: Handler,
+) where
+    M: Send + 'static,
+    Handler: EventHandler<M> + Send + 'static,
 {
     // TODO: should we use assgin_id_async?
     if let Ok(sink) = subscriber.assign_id(subscriber_id.clone()) {
-        let forward = msg_channel
-            .flat_map(move |m| {
+        let history = futures::stream::iter(
+            history
+                .into_iter()
+                .map(|r| Ok::<_, jsonrpc_pubsub::TransportError>(Ok(r))),
+        );
+        let forward = history
+            .chain(msg_channel.flat_map(move |m| {
                 let r = event_handler.handle(m);
                 futures::stream::iter(r.into_iter().map(Ok::<_, jsonrpc_pubsub::TransportError>))
-            })
+            }))
             .forward(sink)
             .await;
         if let Err(e) = forward {
@@ -454,10 +814,78 @@ async fn run_subscription<M, Handler>(
     }
 }
 
+/// Load the history events matched the filter until the current head block,
+/// return the head block number and the events.
+async fn load_history_events(
+    chain_service: ServiceRef<ChainReaderService>,
+    mut filter: Filter,
+) -> Result<(BlockNumber, Vec<pubsub::Result>)> {
+    let head_number = chain_service.main_head_header().await?.number();
+    check_backfill_range(filter.from_block, head_number)?;
+    filter.to_block = std::cmp::min(filter.to_block, head_number);
+    filter.reverse = false;
+    let events = if filter.from_block <= filter.to_block {
+        chain_service.main_events(filter).await?
+    } else {
+        vec![]
+    };
+    Ok((
+        head_number,
+        events
+            .into_iter()
+            .map(|e| pubsub::Result::Event(Box::new(e.into())))
+            .collect(),
+    ))
+}
+
+/// The history events are loaded at once, so the backfill is limited to the latest blocks.
+fn check_backfill_range(from_block: BlockNumber, head_number: BlockNumber) -> Result<()> {
+    ensure!(
+        head_number.saturating_sub(from_block) < MAX_BACKFILL_BLOCKS,
+        "The from_block {} of the backfill should be within the latest {} blocks of the head {}",
+        from_block,
+        MAX_BACKFILL_BLOCKS,
+        head_number
+    );
+    Ok(())
+}
+
 trait EventHandler<M> {
     fn handle(&self, msg: M) -> Vec<jsonrpc_core::Result<pubsub::Result>>;
 }
 
+/// Replay state of a subscription, the results are wrapped with the notification sequence number.
+#[derive(Copy, Clone, Debug)]
+pub struct ReplayState {
+    /// The notifications before this sequence number have been sent, skip them.
+    next_seq: u64,
+}
+
+impl ReplayState {
+    pub fn new(next_seq: u64) -> Self {
+        Self { next_seq }
+    }
+}
+
+fn sequenced_results(
+    replay: Option<ReplayState>,
+    seq: u64,
+    results: Vec<pubsub::Result>,
+) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+    match replay {
+        None => results.into_iter().map(Ok).collect(),
+        Some(replay) if seq < replay.next_seq => vec![],
+        Some(_) => results
+            .into_iter()
+            .map(|r| {
+                Ok(pubsub::Result::Sequenced(Box::new(SequencedResult::new(
+                    seq, r,
+                ))))
+            })
+            .collect(),
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct TxnEventHandler;
 
@@ -468,16 +896,22 @@ impl EventHandler<Arc<[HashValue]>> for TxnEventHandler {
 }
 
 #[derive(Copy, Clone, Debug)]
-pub struct NewHeadHandler;
-
-impl EventHandler<Notification<ThinBlock>> for NewHeadHandler {
-    fn handle(&self, msg: Notification<ThinBlock>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
-        let Notification(block) = msg;
-        vec![Ok(pubsub::Result::Block(Box::new(BlockView {
-            header: block.header.into(),
-            body: block.body.into(),
-            uncles: vec![],
-        })))]
+pub struct NewHeadHandler {
+    replay: Option<ReplayState>,
+}
+
+impl EventHandler<SequencedNotification> for NewHeadHandler {
+    fn handle(&self, msg: SequencedNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let Notification(NotificationRecord { seq, block, .. }) = msg;
+        sequenced_results(
+            self.replay,
+            seq,
+            vec![pubsub::Result::Block(Box::new(BlockView {
+                header: block.header.into(),
+                body: block.body.into(),
+                uncles: vec![],
+            }))],
+        )
     }
 }
 
@@ -493,11 +927,12 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
 #[derive(Clone, Debug)]
 pub struct ContractEventHandler {
     filter: Filter,
+    replay: Option<ReplayState>,
 }
 
-impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
-    fn handle(&self, msg: Notification<Arc<[Event]>>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
-        let Notification(events) = msg;
+impl EventHandler<SequencedNotification> for ContractEventHandler {
+    fn handle(&self, msg: SequencedNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let Notification(NotificationRecord { seq, events, .. }) = msg;
         let filtered = events
             .as_ref()
             .iter()
@@ -511,7 +946,7 @@ impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
             }
         };
 
-        filtered_events
+        let results = filtered_events
             .into_iter()
             .map(|e| {
                 TransactionEventView::new(
@@ -522,7 +957,8 @@ impl EventHandler<Notification<Arc<[Event]>>> for ContractEventHandler {
                     &e.contract_event,
                 )
             })
-            .map(|e| Ok(pubsub::Result::Event(Box::new(e))))
-            .collect()
+            .map(|e| pubsub::Result::Event(Box::new(e)))
+            .collect();
+        sequenced_results(self.replay, seq, results)
     }
 }
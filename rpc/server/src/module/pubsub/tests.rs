@@ -10,6 +10,7 @@ use serde_json::Value;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
+use starcoin_chain_notify::message::GetNotificationsSince;
 use starcoin_chain_notify::ChainNotifyHandlerService;
 use starcoin_consensus::Consensus;
 use starcoin_crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey};
@@ -132,6 +133,77 @@ pub async fn test_subscribe_to_events() -> Result<()> {
     Ok(())
 }
 
+#[actix_rt::test]
+pub async fn test_subscribe_to_new_heads_with_replay() -> Result<()> {
+    starcoin_logger::init_for_test();
+    let (_txpool_service, storage, config, _, registry) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let net = config.net();
+    let mut block_chain = BlockChain::new(net.time_service(), startup_info.main, storage)?;
+    let miner_account = AccountInfo::random();
+    let (block_template, _) = block_chain.create_block_template(
+        *miner_account.address(),
+        Some(miner_account.public_key.authentication_key()),
+        None,
+        vec![],
+        vec![],
+        None,
+    )?;
+    let new_block = block_chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())?;
+    let executed_block = block_chain.apply(new_block)?;
+
+    let bus = registry.service_ref::<BusService>().await?;
+    let notify_service = registry.register::<ChainNotifyHandlerService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    // the block is notified before the subscription.
+    bus.broadcast(NewHeadBlock(Arc::new(executed_block)))?;
+    let mut records = vec![];
+    for _ in 0..10 {
+        records = notify_service
+            .send(GetNotificationsSince { since_seq: 0 })
+            .await??;
+        if !records.is_empty() {
+            break;
+        }
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+    }
+    assert_eq!(records.len(), 1);
+
+    // the seq is greater than the latest seq.
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newHeads"}, {"replay": {"since_seq": 5}}], "id": 1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await.unwrap();
+    assert!(resp.contains("error"), "unexpected response: {}", resp);
+
+    // replay the missed block, the rejected subscriber is not recorded, so the id 0 is not used.
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newHeads"}, {"replay": {"since_seq": 0}}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let res = timeout(Duration::from_secs(5), receiver.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+    let r: Value = serde_json::from_str(&res).unwrap();
+    let result = r["params"]["result"].clone();
+    assert_eq!(result["seq"].as_str(), Some("1"));
+    assert_eq!(result["result"]["header"]["number"].as_str(), Some("1"));
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     // given
@@ -241,3 +313,12 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     assert_eq!(resp, Some(response.to_owned()));
     Ok(())
 }
+
+#[stest::test]
+fn test_check_backfill_range() {
+    assert!(super::check_backfill_range(0, 0).is_ok());
+    assert!(super::check_backfill_range(1001, 1000).is_ok());
+    assert!(super::check_backfill_range(1, 1000).is_ok());
+    assert!(super::check_backfill_range(0, 1000).is_err());
+    assert!(super::check_backfill_range(0, 100000).is_err());
+}
@@ -2,50 +2,169 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
+use anyhow::{ensure, format_err};
 use bcs_ext::BCSCodec;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_chain_service::ChainAsyncService;
+use starcoin_config::NodeConfig;
+use starcoin_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::state::StateApi;
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, StateWithProofView, StrView, StructTagView,
+    AccountStateSetView, AnnotatedMoveStructView, BlockStateRootView, StateRootStatusView,
+    StateWithProofView, StrView, StructTagView,
 };
 use starcoin_rpc_api::FutureResult;
-use starcoin_state_api::ChainStateAsyncService;
+use starcoin_state_api::{ChainStateAsyncService, StateView};
 use starcoin_state_tree::StateNodeStore;
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::{
-    access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_state::AccountState,
+    block::{BlockHeader, BlockNumber},
 };
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::StructTag;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-pub struct StateRpcImpl<S>
+pub struct StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService + 'static,
+    C: ChainAsyncService + 'static,
 {
+    config: Arc<NodeConfig>,
     service: S,
     state_store: Arc<dyn StateNodeStore>,
+    chain_service: C,
 }
 
-impl<S> StateRpcImpl<S>
+impl<S, C> StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    pub fn new(service: S, state_store: Arc<dyn StateNodeStore>) -> Self {
+    pub fn new(
+        config: Arc<NodeConfig>,
+        service: S,
+        state_store: Arc<dyn StateNodeStore>,
+        chain_service: C,
+    ) -> Self {
         Self {
+            config,
             service,
             state_store,
+            chain_service,
         }
     }
 }
 
-impl<S> StateApi for StateRpcImpl<S>
+/// The max count of the latest available roots in the `StateRootStatusView`.
+const MAX_LATEST_AVAILABLE_ROOTS: usize = 3;
+/// The max count of the main chain blocks to scan for the latest available roots.
+const MAX_LATEST_ROOTS_SCAN_BLOCKS: BlockNumber = 32;
+
+fn is_state_root_available(
+    state_store: &dyn StateNodeStore,
+    state_root: &HashValue,
+) -> anyhow::Result<bool> {
+    Ok(state_root == &*SPARSE_MERKLE_PLACEHOLDER_HASH || state_store.get(state_root)?.is_some())
+}
+
+/// Collect the state root of the header if it is available, return true if the roots are enough.
+fn collect_available_root(
+    state_store: &dyn StateNodeStore,
+    header: &BlockHeader,
+    roots: &mut Vec<BlockStateRootView>,
+) -> anyhow::Result<bool> {
+    if is_state_root_available(state_store, &header.state_root())? {
+        roots.push(BlockStateRootView {
+            block_number: header.number().into(),
+            block_hash: header.id(),
+            state_root: header.state_root(),
+        });
+    }
+    Ok(roots.len() >= MAX_LATEST_AVAILABLE_ROOTS)
+}
+
+/// The state root is not indexed by the block, so the available roots of the latest main chain blocks
+/// are returned if the state at the root is not available, the caller can retry at one of them.
+async fn get_state_root_status<C>(
+    chain_service: C,
+    state_store: Arc<dyn StateNodeStore>,
+    state_root: HashValue,
+) -> anyhow::Result<StateRootStatusView>
+where
+    C: ChainAsyncService,
+{
+    if is_state_root_available(state_store.as_ref(), &state_root)? {
+        return Ok(StateRootStatusView {
+            state_root,
+            available: true,
+            latest_available_roots: vec![],
+        });
+    }
+    let head_number = chain_service.main_head_header().await?.number();
+    let mut latest_available_roots = vec![];
+    let mut number = head_number;
+    loop {
+        if let Some(header) = chain_service.main_block_header_by_number(number).await? {
+            if collect_available_root(state_store.as_ref(), &header, &mut latest_available_roots)? {
+                break;
+            }
+        }
+        if number == 0 || head_number - number >= MAX_LATEST_ROOTS_SCAN_BLOCKS {
+            break;
+        }
+        number -= 1;
+    }
+    Ok(StateRootStatusView {
+        state_root,
+        available: false,
+        latest_available_roots,
+    })
+}
+
+/// Get the resource at the state of the main chain block `block_number`.
+async fn get_resource_at<C>(
+    chain_service: C,
+    state_store: Arc<dyn StateNodeStore>,
+    address: AccountAddress,
+    resource_type: StructTag,
+    block_number: BlockNumber,
+) -> anyhow::Result<Option<AnnotatedMoveStructView>>
+where
+    C: ChainAsyncService,
+{
+    let header = chain_service
+        .main_block_header_by_number(block_number)
+        .await?
+        .ok_or_else(|| format_err!("Can not find block by number {}", block_number))?;
+    let statedb = ChainStateDB::new(state_store, Some(header.state_root()));
+    let data = statedb.get(&AccessPath::resource_access_path(
+        address,
+        resource_type.clone(),
+    ))?;
+    match data {
+        None => Ok(None),
+        Some(data) => {
+            let annotator = MoveValueAnnotator::new(&statedb);
+            Ok(Some(
+                annotator
+                    .view_struct(resource_type, data.as_slice())?
+                    .into(),
+            ))
+        }
+    }
+}
+
+impl<S, C> StateApi for StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
     fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>> {
         let fut = self.service.clone().get(access_path).map_err(map_err);
@@ -142,4 +261,124 @@ where
             .map_err(map_err);
         Box::pin(fut)
     }
+
+    fn get_state_root_status(&self, state_root: HashValue) -> FutureResult<StateRootStatusView> {
+        let fut = get_state_root_status(
+            self.chain_service.clone(),
+            self.state_store.clone(),
+            state_root,
+        );
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn get_resource_at(
+        &self,
+        address: AccountAddress,
+        resource_type: StrView<StructTag>,
+        block_number: BlockNumber,
+    ) -> FutureResult<Option<AnnotatedMoveStructView>> {
+        let fut = get_resource_at(
+            self.chain_service.clone(),
+            self.state_store.clone(),
+            address,
+            resource_type.0,
+            block_number,
+        );
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn get_resource_at_blocks(
+        &self,
+        address: AccountAddress,
+        resource_type: StrView<StructTag>,
+        block_numbers: Vec<BlockNumber>,
+    ) -> FutureResult<Vec<Option<AnnotatedMoveStructView>>> {
+        let chain_service = self.chain_service.clone();
+        let state_store = self.state_store.clone();
+        let max_range = self.config.rpc.block_query_max_range();
+        let fut = async move {
+            ensure!(
+                block_numbers.len() as u64 <= max_range,
+                "The count of block numbers {} exceeds the max {}",
+                block_numbers.len(),
+                max_range
+            );
+            let mut resources = Vec::with_capacity(block_numbers.len());
+            for block_number in block_numbers {
+                resources.push(
+                    get_resource_at(
+                        chain_service.clone(),
+                        state_store.clone(),
+                        address,
+                        resource_type.0.clone(),
+                        block_number,
+                    )
+                    .await?,
+                );
+            }
+            Ok(resources)
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_state_tree::mock::MockStateNodeStore;
+    use starcoin_state_tree::StateNode;
+    use starcoin_types::block::BlockHeaderBuilder;
+
+    fn header(number: BlockNumber, state_root: HashValue) -> BlockHeader {
+        BlockHeaderBuilder::random()
+            .with_number(number)
+            .with_state_root(state_root)
+            .build()
+    }
+
+    #[test]
+    fn test_collect_available_root() {
+        let state_store = MockStateNodeStore::new();
+        let available_roots = (0..4).map(|_| HashValue::random()).collect::<Vec<_>>();
+        for root in &available_roots {
+            state_store.put(*root, StateNode(vec![])).unwrap();
+        }
+        assert!(is_state_root_available(&state_store, &available_roots[0]).unwrap());
+        assert!(is_state_root_available(&state_store, &SPARSE_MERKLE_PLACEHOLDER_HASH).unwrap());
+        assert!(!is_state_root_available(&state_store, &HashValue::random()).unwrap());
+
+        let mut roots = vec![];
+        // the pruned root is skipped.
+        assert!(!collect_available_root(
+            &state_store,
+            &header(10, HashValue::random()),
+            &mut roots
+        )
+        .unwrap());
+        assert!(roots.is_empty());
+        assert!(
+            !collect_available_root(&state_store, &header(9, available_roots[0]), &mut roots)
+                .unwrap()
+        );
+        assert!(
+            !collect_available_root(&state_store, &header(8, available_roots[1]), &mut roots)
+                .unwrap()
+        );
+        // enough roots.
+        assert!(
+            collect_available_root(&state_store, &header(7, available_roots[2]), &mut roots)
+                .unwrap()
+        );
+        assert_eq!(
+            roots
+                .iter()
+                .map(|root| (root.block_number.0, root.state_root))
+                .collect::<Vec<_>>(),
+            vec![
+                (9, available_roots[0]),
+                (8, available_roots[1]),
+                (7, available_roots[2])
+            ]
+        );
+    }
 }
@@ -2,16 +2,143 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::{convert_to_rpc_error, map_err};
+use anyhow::{ensure, format_err};
 use bcs_ext::BCSCodec;
+use futures::{FutureExt, TryFutureExt};
+use parking_lot::Mutex;
+use starcoin_config::DEFAULT_IDEMPOTENCY_KEY_RETENTION;
 use starcoin_crypto::HashValue;
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
 use starcoin_rpc_api::types::{SignedUserTransactionView, StrView};
 use starcoin_rpc_api::{txpool::TxPoolApi, FutureResult};
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_service_registry::ServiceRef;
+use starcoin_txpool::{SubmitTxnWithPolicyRequest, TxnKeeperService};
+use starcoin_txpool_api::{
+    TxPoolAnalytics, TxPoolStatus, TxPoolSyncService, TxnKeepPolicy, TxnValidation,
+};
 use starcoin_types::account_address::AccountAddress;
-use starcoin_types::transaction::SignedUserTransaction;
+use starcoin_types::transaction::{SignedUserTransaction, TransactionError};
+use std::collections::{HashMap, VecDeque};
 use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The max length of an idempotency key.
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 128;
+/// The max count of the idempotency keys kept in the retention window.
+const MAX_IDEMPOTENCY_KEYS: usize = 102400;
+/// The default count of the top senders of the txpool analytics.
+const DEFAULT_TOP_SENDERS: u32 = 10;
+/// The max count of the top senders of the txpool analytics.
+const MAX_TOP_SENDERS: u32 = 100;
+/// The txpool analytics scans the whole pool, so the result is reused within the ttl,
+/// the pool is scanned at most once per ttl no matter how often the api is called.
+const ANALYTICS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// The idempotency key is scoped by the sender of the txn,
+/// so a key of a sender never returns the result of another sender.
+type IdempotencyKey = (AccountAddress, String);
+
+enum SubmissionState {
+    /// The txn is being added to the txpool.
+    Pending,
+    /// The txn is added to the txpool, the failed submissions are not kept.
+    Added,
+}
+
+/// The submission with an idempotency key.
+struct IdempotentSubmission {
+    txn_hash: HashValue,
+    state: SubmissionState,
+    submitted_at: Instant,
+}
+
+/// The submissions in the retention window, the oldest one is evicted when the cache is full.
+#[derive(Default)]
+struct IdempotencyCache {
+    submissions: HashMap<IdempotencyKey, IdempotentSubmission>,
+    /// The keys in the submission order, the key of a removed submission is skipped on popping.
+    order: VecDeque<(IdempotencyKey, Instant)>,
+}
+
+impl IdempotencyCache {
+    fn pop_oldest(&mut self) {
+        if let Some((key, submitted_at)) = self.order.pop_front() {
+            if self
+                .submissions
+                .get(&key)
+                .map(|submission| submission.submitted_at == submitted_at)
+                .unwrap_or(false)
+            {
+                self.submissions.remove(&key);
+            }
+        }
+    }
+
+    fn expire(&mut self, retention: Duration) {
+        while self
+            .order
+            .front()
+            .map(|(_, submitted_at)| submitted_at.elapsed() >= retention)
+            .unwrap_or(false)
+        {
+            self.pop_oldest();
+        }
+    }
+
+    /// Return the txn hash of the added key, or mark the key pending and return `None`.
+    fn begin(
+        &mut self,
+        key: &IdempotencyKey,
+        txn_hash: HashValue,
+        retention: Duration,
+    ) -> Result<Option<HashValue>, jsonrpc_core::Error> {
+        self.expire(retention);
+        if let Some(submission) = self.submissions.get(key) {
+            if submission.txn_hash != txn_hash {
+                return Err(map_err(format_err!(
+                    "The idempotency key {} is used by another txn {}",
+                    key.1,
+                    submission.txn_hash
+                )));
+            }
+            return match &submission.state {
+                SubmissionState::Pending => Err(map_err(format_err!(
+                    "The txn of the idempotency key {} is being submitted, retry later.",
+                    key.1
+                ))),
+                SubmissionState::Added => Ok(Some(submission.txn_hash)),
+            };
+        }
+        while self.order.len() >= MAX_IDEMPOTENCY_KEYS {
+            self.pop_oldest();
+        }
+        let submitted_at = Instant::now();
+        self.submissions.insert(
+            key.clone(),
+            IdempotentSubmission {
+                txn_hash,
+                state: SubmissionState::Pending,
+                submitted_at,
+            },
+        );
+        self.order.push_back((key.clone(), submitted_at));
+        Ok(None)
+    }
+
+    /// Mark the txn of the key added, the later submissions of the key return its hash.
+    fn finish(&mut self, key: &IdempotencyKey) {
+        if let Some(submission) = self.submissions.get_mut(key) {
+            submission.state = SubmissionState::Added;
+        }
+    }
+
+    /// Forget the key, so the submission of the key can be retried.
+    fn forget(&mut self, key: &IdempotencyKey) {
+        self.submissions.remove(key);
+    }
+}
 
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
@@ -21,6 +148,12 @@ where
     S: TxPoolSyncService + 'static,
 {
     service: S,
+    txn_keeper: Option<ServiceRef<TxnKeeperService>>,
+    idempotency_key_retention: Duration,
+    submissions: Arc<Mutex<IdempotencyCache>>,
+    mirror: bool,
+    /// The last analytics of `MAX_TOP_SENDERS` top senders, and when it is taken.
+    analytics_cache: Arc<Mutex<Option<(Instant, TxPoolAnalytics)>>>,
 }
 
 impl<S> TxPoolRpcImpl<S>
@@ -28,7 +161,97 @@ where
     S: TxPoolSyncService,
 {
     pub fn new(service: S) -> Self {
-        Self { service }
+        Self {
+            service,
+            txn_keeper: None,
+            idempotency_key_retention: Duration::from_secs(DEFAULT_IDEMPOTENCY_KEY_RETENTION),
+            submissions: Arc::new(Mutex::new(IdempotencyCache::default())),
+            mirror: false,
+            analytics_cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn with_idempotency_key_retention(mut self, retention: Duration) -> Self {
+        self.idempotency_key_retention = retention;
+        self
+    }
+
+    pub fn with_txn_keeper(mut self, txn_keeper: ServiceRef<TxnKeeperService>) -> Self {
+        self.txn_keeper = Some(txn_keeper);
+        self
+    }
+
+    /// Reject all the txn submissions, the txpool of a mirror node is view-only.
+    pub fn with_mirror_mode(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+
+    fn ensure_not_mirror(&self) -> anyhow::Result<()> {
+        if self.mirror {
+            return Err(format_err!(
+                "The txpool is in the mirror mode, the txn submission is not accepted."
+            ));
+        }
+        Ok(())
+    }
+
+    fn add_txn(&self, txn: SignedUserTransaction) -> Result<HashValue, TransactionError> {
+        let txn_hash = txn.id();
+        self.service
+            .add_local_txns(vec![txn])
+            .pop()
+            .expect("txpool should return result")
+            .map(|_| txn_hash)
+    }
+
+    /// Add the txn to the txpool, or return the original result if the `idempotency_key`
+    /// of the sender has been submitted within the retention window.
+    fn submit_txn(
+        &self,
+        txn: SignedUserTransaction,
+        idempotency_key: Option<String>,
+    ) -> Result<HashValue, jsonrpc_core::Error> {
+        self.ensure_not_mirror().map_err(map_err)?;
+        let idempotency_key = match idempotency_key {
+            Some(idempotency_key) => idempotency_key,
+            None => return self.add_txn(txn).map_err(convert_to_rpc_error),
+        };
+        if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(map_err(format_err!(
+                "The length of idempotency key should be in [1, {}]",
+                MAX_IDEMPOTENCY_KEY_LENGTH
+            )));
+        }
+        // the key is scoped by the sender, so the txn not signed by its sender can not take the key.
+        if let Err(err) = txn.clone().check_signature() {
+            return Err(convert_to_rpc_error(TransactionError::InvalidSignature(
+                err.to_string(),
+            )));
+        }
+        let key = (txn.sender(), idempotency_key);
+        let txn_hash = txn.id();
+        // the key is marked pending, so the concurrent retries of the key are not processed twice,
+        // and the lock is not held while adding the txn.
+        if let Some(txn_hash) =
+            self.submissions
+                .lock()
+                .begin(&key, txn_hash, self.idempotency_key_retention)?
+        {
+            return Ok(txn_hash);
+        }
+        // only the added txn keeps the key, the rejected txn may be rejected by a forged sender,
+        // so the key is released for the later submissions.
+        match self.add_txn(txn) {
+            Ok(txn_hash) => {
+                self.submissions.lock().finish(&key);
+                Ok(txn_hash)
+            }
+            Err(err) => {
+                self.submissions.lock().forget(&key);
+                Err(convert_to_rpc_error(err))
+            }
+        }
     }
 }
 
@@ -36,35 +259,57 @@ impl<S> TxPoolApi for TxPoolRpcImpl<S>
 where
     S: TxPoolSyncService,
 {
-    fn submit_transaction(&self, txn: SignedUserTransaction) -> FutureResult<HashValue> {
-        let txn_hash = txn.id();
-        let result: Result<(), jsonrpc_core::Error> = self
-            .service
-            .add_txns(vec![txn])
-            .pop()
-            .expect("txpool should return result")
-            .map_err(convert_to_rpc_error);
-
-        Box::pin(futures::future::ready(result.map(|_| txn_hash)))
+    fn submit_transaction(
+        &self,
+        txn: SignedUserTransaction,
+        idempotency_key: Option<String>,
+    ) -> FutureResult<HashValue> {
+        let result = self.submit_txn(txn, idempotency_key);
+        Box::pin(futures::future::ready(result))
     }
 
-    fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue> {
+    fn submit_hex_transaction(
+        &self,
+        tx: String,
+        idempotency_key: Option<String>,
+    ) -> FutureResult<HashValue> {
         let tx = tx.strip_prefix("0x").unwrap_or_else(|| tx.as_str());
         let result = hex::decode(tx)
             .map_err(convert_to_rpc_error)
             .and_then(|txn_bytes| SignedUserTransaction::decode(&txn_bytes).map_err(map_err))
-            .and_then(|txn| {
-                let txn_hash = txn.id();
-                self.service
-                    .add_txns(vec![txn])
-                    .pop()
-                    .expect("txpool should return result")
-                    .map(|_| txn_hash)
-                    .map_err(convert_to_rpc_error)
-            });
+            .and_then(|txn| self.submit_txn(txn, idempotency_key));
         Box::pin(futures::future::ready(result))
     }
 
+    fn submit_transaction_with_policy(
+        &self,
+        txn: SignedUserTransaction,
+        policy: TxnKeepPolicy,
+    ) -> FutureResult<HashValue> {
+        let mirror = self.ensure_not_mirror();
+        let txn_keeper = self.txn_keeper.clone();
+        let fut = async move {
+            mirror?;
+            // the txpool api is public, the node should not re-sign the txns for remote callers.
+            ensure!(
+                !policy.resign,
+                "The re-sign policy is not accepted by the txpool api, use account.submit_txn_with_policy instead."
+            );
+            let txn_keeper =
+                txn_keeper.ok_or_else(|| format_err!("Txn keeper service is not available."))?;
+            txn_keeper
+                .send(SubmitTxnWithPolicyRequest { txn, policy })
+                .await?
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn validate_transaction(&self, txn: SignedUserTransaction) -> FutureResult<TxnValidation> {
+        let result = self.service.validate_txn(txn);
+        Box::pin(futures::future::ok(result))
+    }
+
     fn gas_price(&self) -> FutureResult<StrView<u64>> {
         let gas_price = 1u64;
         Box::pin(futures::future::ok(gas_price.into()))
@@ -103,6 +348,30 @@ where
         let state = self.service.status();
         Box::pin(futures::future::ok(state))
     }
+
+    fn analytics(&self, top_senders: Option<u32>) -> FutureResult<TxPoolAnalytics> {
+        let top_senders = top_senders.unwrap_or(DEFAULT_TOP_SENDERS);
+        if top_senders > MAX_TOP_SENDERS {
+            return Box::pin(futures::future::err(map_err(format_err!(
+                "The top senders should be in [0, {}]",
+                MAX_TOP_SENDERS
+            ))));
+        }
+        // the lock is held while scanning, so the concurrent calls wait for one scan.
+        let mut cache = self.analytics_cache.lock();
+        let mut analytics = match cache.as_ref() {
+            Some((taken_at, analytics)) if taken_at.elapsed() < ANALYTICS_CACHE_TTL => {
+                analytics.clone()
+            }
+            _ => {
+                let analytics = self.service.analytics(MAX_TOP_SENDERS as usize);
+                *cache = Some((Instant::now(), analytics.clone()));
+                analytics
+            }
+        };
+        analytics.top_senders.truncate(top_senders as usize);
+        Box::pin(futures::future::ok(analytics))
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +380,8 @@ mod tests {
     use futures::executor::block_on;
     use jsonrpc_core::IoHandler;
     use starcoin_txpool_mock_service::MockTxPoolService;
+    use starcoin_types::genesis_config::ChainId;
+    use starcoin_types::transaction::{RawUserTransaction, Script, TransactionPayload};
 
     #[test]
     fn test_submit_transaction() {
@@ -140,4 +411,181 @@ mod tests {
             response
         );
     }
+
+    #[test]
+    fn test_submit_transaction_with_policy_without_keeper() {
+        let mut io = IoHandler::new();
+        io.extend_with(TxPoolRpcImpl::new(MockTxPoolService::new()).to_delegate());
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","method":"txpool.submit_transaction_with_policy","params":[{},{{"resign":false}}],"id":0}}"#,
+            serde_json::to_string(&SignedUserTransaction::mock())
+                .expect("txn to json should success.")
+        );
+        let response = block_on(io.handle_request(request.as_str())).unwrap();
+        assert!(response.contains("Txn keeper service is not available."));
+    }
+
+    fn mock_txn_by_sender(sender: AccountAddress, sequence_number: u64) -> SignedUserTransaction {
+        let (private_key, public_key) = starcoin_crypto::ed25519::genesis_key_pair();
+        RawUserTransaction::new_with_default_gas_token(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            0,
+            u64::max_value(),
+            ChainId::test(),
+        )
+        .sign(&private_key, public_key)
+        .unwrap()
+        .into_inner()
+    }
+
+    #[test]
+    fn test_submit_transaction_with_idempotency_key() {
+        let rpc = TxPoolRpcImpl::new(MockTxPoolService::new());
+        let sender = AccountAddress::random();
+        let txn = mock_txn_by_sender(sender, 0);
+        let key = "retry-key".to_string();
+        let txn_hash = block_on(rpc.submit_transaction(txn.clone(), Some(key.clone()))).unwrap();
+        assert_eq!(txn_hash, txn.id());
+        assert_eq!(
+            block_on(rpc.submit_transaction(txn, Some(key.clone()))).unwrap(),
+            txn_hash
+        );
+        // the key is used by another txn of the sender.
+        let other_txn = mock_txn_by_sender(sender, 1);
+        assert!(block_on(rpc.submit_transaction(other_txn, Some(key.clone()))).is_err());
+        // the key is scoped by the sender, another sender can use the same key.
+        let other_sender_txn = SignedUserTransaction::mock();
+        assert_eq!(
+            block_on(rpc.submit_transaction(other_sender_txn.clone(), Some(key))).unwrap(),
+            other_sender_txn.id()
+        );
+        assert!(block_on(
+            rpc.submit_transaction(SignedUserTransaction::mock(), Some("".to_string()))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_idempotency_cache() {
+        let retention = Duration::from_secs(600);
+        let mut cache = IdempotencyCache::default();
+        let sender = AccountAddress::random();
+        let key = (sender, "key".to_string());
+        let txn_hash = HashValue::random();
+        assert!(cache.begin(&key, txn_hash, retention).unwrap().is_none());
+        // the concurrent retry is rejected while the key is pending.
+        assert!(cache.begin(&key, txn_hash, retention).is_err());
+        cache.finish(&key);
+        assert_eq!(
+            cache.begin(&key, txn_hash, retention).unwrap().unwrap(),
+            txn_hash
+        );
+        assert!(cache.begin(&key, HashValue::random(), retention).is_err());
+
+        // the forgotten key can be submitted again.
+        let retry_key = (sender, "retry".to_string());
+        assert!(cache
+            .begin(&retry_key, txn_hash, retention)
+            .unwrap()
+            .is_none());
+        cache.forget(&retry_key);
+        assert!(cache
+            .begin(&retry_key, txn_hash, retention)
+            .unwrap()
+            .is_none());
+
+        // the oldest key is evicted when the cache is full.
+        for i in 0..MAX_IDEMPOTENCY_KEYS {
+            let key = (sender, format!("fill-{}", i));
+            assert!(cache.begin(&key, txn_hash, retention).unwrap().is_none());
+        }
+        assert!(cache.order.len() <= MAX_IDEMPOTENCY_KEYS);
+        assert!(cache.submissions.len() <= MAX_IDEMPOTENCY_KEYS);
+        assert!(!cache.submissions.contains_key(&key));
+        assert!(cache
+            .submissions
+            .contains_key(&(sender, format!("fill-{}", MAX_IDEMPOTENCY_KEYS - 1))));
+
+        // the expired keys are removed.
+        cache.expire(Duration::from_secs(0));
+        assert!(cache.submissions.is_empty());
+        assert!(cache.order.is_empty());
+    }
+
+    #[test]
+    fn test_forged_sender_not_take_idempotency_key() {
+        let rpc = TxPoolRpcImpl::new(MockTxPoolService::new());
+        let sender = AccountAddress::random();
+        let txn = mock_txn_by_sender(sender, 0);
+        let key = "retry-key".to_string();
+        // the raw txn of the sender with the signature of another txn.
+        let forged_txn = SignedUserTransaction::new(
+            mock_txn_by_sender(sender, 1).raw_txn().clone(),
+            txn.authenticator(),
+        );
+        assert!(block_on(rpc.submit_transaction(forged_txn, Some(key.clone()))).is_err());
+        assert_eq!(
+            block_on(rpc.submit_transaction(txn.clone(), Some(key))).unwrap(),
+            txn.id()
+        );
+    }
+
+    #[test]
+    fn test_submit_transaction_with_resign_policy() {
+        let mut io = IoHandler::new();
+        io.extend_with(TxPoolRpcImpl::new(MockTxPoolService::new()).to_delegate());
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","method":"txpool.submit_transaction_with_policy","params":[{},{{"resign":true,"max_gas_price":10}}],"id":0}}"#,
+            serde_json::to_string(&SignedUserTransaction::mock())
+                .expect("txn to json should success.")
+        );
+        let response = block_on(io.handle_request(request.as_str())).unwrap();
+        assert!(response.contains("The re-sign policy is not accepted by the txpool api"));
+    }
+
+    #[test]
+    fn test_mirror_mode() {
+        let txn = SignedUserTransaction::mock();
+        let rpc = TxPoolRpcImpl::new(MockTxPoolService::new_with_txns(vec![txn.clone()]))
+            .with_mirror_mode();
+        let err =
+            block_on(rpc.submit_transaction(SignedUserTransaction::mock(), None)).unwrap_err();
+        assert!(err.message.contains("mirror mode"));
+        let hex_txn = hex::encode(txn.encode().unwrap());
+        assert!(block_on(rpc.submit_hex_transaction(hex_txn, None)).is_err());
+
+        let analytics = block_on(rpc.analytics(None)).unwrap();
+        assert_eq!(analytics.txn_count, 1);
+        assert_eq!(analytics.top_senders[0].sender, txn.sender());
+    }
+
+    #[test]
+    fn test_analytics_cache() {
+        let txn = SignedUserTransaction::mock();
+        let service = MockTxPoolService::new_with_txns(vec![txn]);
+        let rpc = TxPoolRpcImpl::new(service.clone());
+        assert_eq!(block_on(rpc.analytics(None)).unwrap().txn_count, 1);
+        // the pool is not scanned again within the ttl.
+        service.add_txns(vec![SignedUserTransaction::mock()]);
+        let analytics = block_on(rpc.analytics(Some(0))).unwrap();
+        assert_eq!(analytics.txn_count, 1);
+        assert!(analytics.top_senders.is_empty());
+        assert!(block_on(rpc.analytics(Some(MAX_TOP_SENDERS + 1))).is_err());
+
+        *rpc.analytics_cache.lock() = None;
+        assert_eq!(block_on(rpc.analytics(None)).unwrap().txn_count, 2);
+    }
+
+    #[test]
+    fn test_encrypted_txpool_api_not_in_txpool_api() {
+        // the encrypted pool api is only registered by the node built with the threshold-encryption feature.
+        let mut io = IoHandler::new();
+        io.extend_with(TxPoolRpcImpl::new(MockTxPoolService::new()).to_delegate());
+        let request = r#"{"jsonrpc":"2.0","method":"txpool.sealed_encrypted_transactions","params":[],"id":0}"#;
+        let response = block_on(io.handle_request(request)).unwrap();
+        assert!(response.contains("Method not found"));
+    }
 }
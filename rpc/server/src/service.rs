@@ -3,11 +3,12 @@
 
 use crate::api_registry::ApiRegistry;
 use crate::extractors::{RpcExtractor, WsExtractor};
+use crate::trace_middleware::TraceMiddleware;
 use anyhow::Result;
 use futures::stream::*;
 use futures::{FutureExt, StreamExt};
 use jsonrpc_core::futures::channel::mpsc;
-use jsonrpc_core::MetaIoHandler;
+use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use jsonrpc_core_client::{
     transports::{duplex, local::LocalRpc},
     RpcChannel, RpcError,
@@ -146,6 +147,15 @@ impl RpcService {
         Self::new(config, api_registry)
     }
 
+    /// Register the api not covered by `new_with_api`, such as the feature gated api,
+    /// it should be called before the service is started.
+    pub fn register_api<F>(&mut self, api_type: Api, apis: F)
+    where
+        F: IntoIterator<Item = (String, RemoteProcedure<Metadata>)>,
+    {
+        self.api_registry.register(api_type, apis);
+    }
+
     fn start_ipc(&self) -> Result<Option<jsonrpc_ipc_server::Server>> {
         Ok(if self.config.rpc.ipc.disable {
             None
@@ -236,10 +246,10 @@ impl RpcService {
     }
 }
 
-struct IoHandlerWrap(MetaIoHandler<Metadata>);
+struct IoHandlerWrap(MetaIoHandler<Metadata, TraceMiddleware>);
 
 impl Deref for IoHandlerWrap {
-    type Target = MetaIoHandler<Metadata>;
+    type Target = MetaIoHandler<Metadata, TraceMiddleware>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -248,7 +258,7 @@ impl Deref for IoHandlerWrap {
 
 /// Connects with pubsub.
 pub fn connect_local(
-    handler: MetaIoHandler<Metadata>,
+    handler: MetaIoHandler<Metadata, TraceMiddleware>,
 ) -> (
     RpcChannel,
     impl jsonrpc_core::futures::Future<Output = Result<(), RpcError>>,
@@ -265,8 +275,8 @@ impl ServiceHandler<Self, ConnectLocal> for RpcService {
     fn handle(&mut self, _msg: ConnectLocal, ctx: &mut ServiceContext<RpcService>) -> RpcChannel {
         let apis = ApiSet::All.list_apis();
         let io_handler = self.api_registry.get_apis(apis);
-        //remove middleware.
-        let mut local_io_handler = MetaIoHandler::default();
+        //remove middleware, but keep the tracing.
+        let mut local_io_handler = MetaIoHandler::with_middleware(TraceMiddleware);
         local_io_handler.extend_with(io_handler.iter().map(|(n, f)| (n.clone(), f.clone())));
         let (rpc_channel, fut) = connect_local(local_io_handler);
         ctx.spawn(fut.map(|rs| {
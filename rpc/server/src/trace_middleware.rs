@@ -0,0 +1,158 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::FutureExt;
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{Call, FutureResponse, Id, Middleware, Output, Params};
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::metadata::{Metadata, TRACE_NOTIFICATION};
+
+/// Log the calls with the trace id of the user action, so the node logs can be correlated with
+/// the bug report of the user. The trace id is set by the `x-trace-id` http header, or by the
+/// `rpc.trace` notification for the following calls on the connection.
+/// The invalid trace id is dropped, and the caller supplied fields are escaped, so the callers can not inject log lines.
+#[derive(Clone, Debug, Default)]
+pub struct TraceMiddleware;
+
+fn id_to_string(id: &Id) -> String {
+    match id {
+        Id::Null => "".to_owned(),
+        Id::Num(num) => num.to_string(),
+        Id::Str(str) => str.escape_debug().to_string(),
+    }
+}
+
+fn trace_id_of(params: &Params) -> Option<String> {
+    match params {
+        Params::Array(params) => params
+            .first()
+            .and_then(|param| param.as_str())
+            .map(|trace_id| trace_id.to_string()),
+        _ => None,
+    }
+}
+
+impl Middleware<Metadata> for TraceMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        if let Call::Notification(notification) = &call {
+            if notification.method == TRACE_NOTIFICATION {
+                meta.trace_id.set(trace_id_of(&notification.params));
+                debug!(
+                    "rpc_trace\tset the trace id of the connection: {:?}",
+                    meta.trace_id.get()
+                );
+                return Either::Left(Box::pin(futures::future::ready(None)));
+            }
+        }
+        let method_call = match &call {
+            Call::MethodCall(method_call) => Some((
+                id_to_string(&method_call.id),
+                method_call.method.escape_debug().to_string(),
+            )),
+            _ => None,
+        };
+        match (method_call, meta.trace_id.get()) {
+            (Some((id, method)), Some(trace_id)) => {
+                info!("rpc_trace\t{}\t{}\t{}\tstart", trace_id, id, method);
+                let fut = next(call, meta).map(move |output| {
+                    match &output {
+                        Some(Output::Failure(failure)) => warn!(
+                            "rpc_trace\t{}\t{}\t{}\terror: {}",
+                            trace_id, id, method, failure.error.message
+                        ),
+                        _ => info!("rpc_trace\t{}\t{}\t{}\tend", trace_id, id, method),
+                    }
+                    output
+                });
+                let box_fut: Self::CallFuture = Box::pin(fut);
+                Either::Left(box_fut)
+            }
+            _ => Either::Right(next(call, meta)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::{MetaIoHandler, Value};
+    use starcoin_rpc_api::metadata::TraceId;
+
+    fn io() -> MetaIoHandler<Metadata, TraceMiddleware> {
+        let mut io = MetaIoHandler::with_middleware(TraceMiddleware);
+        io.add_method_with_meta("trace_id", |_params, meta: Metadata| {
+            futures::future::ready(Ok(meta
+                .trace_id
+                .get()
+                .map(Value::String)
+                .unwrap_or(Value::Null)))
+        });
+        io
+    }
+
+    fn call_trace_id(io: &MetaIoHandler<Metadata, TraceMiddleware>, meta: Metadata) -> Value {
+        let response = futures::executor::block_on(io.handle_request(
+            r#"{"jsonrpc":"2.0","method":"trace_id","params":[],"id":1}"#,
+            meta,
+        ))
+        .unwrap();
+        let response: serde_json::Value = serde_json::from_str(response.as_str()).unwrap();
+        response["result"].clone()
+    }
+
+    fn set_trace_id(io: &MetaIoHandler<Metadata, TraceMiddleware>, meta: Metadata, trace_id: &str) {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": TRACE_NOTIFICATION,
+            "params": [trace_id],
+        });
+        let response =
+            futures::executor::block_on(io.handle_request(notification.to_string().as_str(), meta));
+        assert_eq!(response, None);
+    }
+
+    #[test]
+    fn test_trace_notification() {
+        let io = io();
+        let meta = Metadata::default();
+        assert_eq!(call_trace_id(&io, meta.clone()), Value::Null);
+        set_trace_id(&io, meta.clone(), "0123abcd");
+        assert_eq!(
+            call_trace_id(&io, meta.clone()),
+            Value::String("0123abcd".to_string())
+        );
+
+        // the invalid trace id is dropped.
+        set_trace_id(&io, meta.clone(), "0123\nabcd");
+        assert_eq!(call_trace_id(&io, meta.clone()), Value::Null);
+        set_trace_id(&io, meta.clone(), "a".repeat(33).as_str());
+        assert_eq!(call_trace_id(&io, meta), Value::Null);
+    }
+
+    #[test]
+    fn test_trace_header() {
+        let io = io();
+        let meta = Metadata {
+            trace_id: TraceId::new(Some("0123abcd".to_string())),
+            ..Default::default()
+        };
+        assert_eq!(
+            call_trace_id(&io, meta),
+            Value::String("0123abcd".to_string())
+        );
+        let meta = Metadata {
+            trace_id: TraceId::new(Some("not hex".to_string())),
+            ..Default::default()
+        };
+        assert_eq!(call_trace_id(&io, meta), Value::Null);
+    }
+}
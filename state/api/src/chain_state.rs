@@ -109,6 +109,12 @@ impl StateWithProof {
     pub fn get_state(&self) -> &Option<Vec<u8>> {
         &self.state
     }
+
+    /// Verify the state of `access_path` against the state root of a trusted block header.
+    pub fn verify(&self, state_root: HashValue, access_path: AccessPath) -> Result<()> {
+        self.proof
+            .verify(state_root, access_path, self.state.as_deref())
+    }
 }
 
 pub trait ChainStateReader: StateView {
@@ -1,5 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
+use crate::compression::{
+    compress_record, decompress_value, CompressionDictionary, CompressionStats,
+};
 use crate::define_storage;
 use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
 use crate::{
@@ -86,7 +89,7 @@ impl ValueCodec for Block {
     }
 
     fn decode_value(data: &[u8]) -> Result<Self> {
-        Self::decode(data)
+        Self::decode(decompress_value(data)?.as_ref())
     }
 }
 
@@ -106,7 +109,7 @@ impl ValueCodec for BlockBody {
     }
 
     fn decode_value(data: &[u8]) -> Result<Self> {
-        Self::decode(data)
+        Self::decode(decompress_value(data)?.as_ref())
     }
 }
 
@@ -180,6 +183,21 @@ impl BlockStorage {
         self.save(block)
     }
 
+    /// Rewrite the block and its body compressed.
+    pub(crate) fn compress_block(
+        &self,
+        block_id: HashValue,
+        dictionary: Option<&CompressionDictionary>,
+    ) -> Result<CompressionStats> {
+        let mut stats = compress_record(&self.block_store, block_id.to_vec(), dictionary)?;
+        stats.add(compress_record(
+            &self.body_store,
+            block_id.to_vec(),
+            dictionary,
+        )?);
+        Ok(stats)
+    }
+
     pub fn get_block_header_by_hash(&self, block_id: HashValue) -> Result<Option<BlockHeader>> {
         self.header_store.get(block_id)
     }
@@ -4,7 +4,9 @@
 use crate::storage::{ColumnFamily, InnerStorage, KVStore};
 use crate::CHAIN_INFO_PREFIX_NAME;
 use anyhow::Result;
+use byteorder::{BigEndian, ReadBytesExt};
 use crypto::HashValue;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::startup_info::StartupInfo;
 use std::convert::TryInto;
 
@@ -25,6 +27,15 @@ pub type ChainInfoStorage = InnerStorage<ChainInfoColumnFamily>;
 impl ChainInfoStorage {
     const STARTUP_INFO_KEY: &'static str = "startup_info";
     const GENESIS_KEY: &'static str = "genesis";
+    const COMPRESSION_DICTIONARY_KEY: &'static str = "compression_dictionary";
+    const COMPRESSED_BLOCK_NUMBER_KEY: &'static str = "compressed_block_number";
+    const COMPRESSION_FORMAT_VERSION_KEY: &'static str = "compression_format_version";
+    const NOTIFICATION_SEQ_KEY: &'static str = "notification_seq";
+    const NOTIFICATION_BLOCK_KEY_PREFIX: &'static str = "notification_block_";
+
+    fn notification_block_key(seq: u64) -> Vec<u8> {
+        format!("{}{}", Self::NOTIFICATION_BLOCK_KEY_PREFIX, seq).into_bytes()
+    }
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -55,4 +66,78 @@ impl ChainInfoStorage {
             genesis_block_hash.to_vec(),
         )
     }
+
+    pub fn get_compression_dictionary(&self) -> Result<Option<Vec<u8>>> {
+        self.get(Self::COMPRESSION_DICTIONARY_KEY.as_bytes())
+    }
+
+    pub fn save_compression_dictionary(&self, dictionary: Vec<u8>) -> Result<()> {
+        self.put(
+            Self::COMPRESSION_DICTIONARY_KEY.as_bytes().to_vec(),
+            dictionary,
+        )
+    }
+
+    /// The format version of the compressed records, None if no record is compressed.
+    pub fn get_compression_format_version(&self) -> Result<Option<u8>> {
+        self.get(Self::COMPRESSION_FORMAT_VERSION_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bytes.as_slice().read_u8()?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_compression_format_version(&self, version: u8) -> Result<()> {
+        self.put(
+            Self::COMPRESSION_FORMAT_VERSION_KEY.as_bytes().to_vec(),
+            vec![version],
+        )
+    }
+
+    /// The number of the last main chain block which is compressed.
+    pub fn get_compressed_block_number(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::COMPRESSED_BLOCK_NUMBER_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bytes.as_slice().read_u64::<BigEndian>()?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_compressed_block_number(&self, number: BlockNumber) -> Result<()> {
+        self.put(
+            Self::COMPRESSED_BLOCK_NUMBER_KEY.as_bytes().to_vec(),
+            number.to_be_bytes().to_vec(),
+        )
+    }
+
+    /// The sequence number of the latest chain notification.
+    pub fn get_notification_seq(&self) -> Result<Option<u64>> {
+        self.get(Self::NOTIFICATION_SEQ_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bytes.as_slice().read_u64::<BigEndian>()?)),
+                None => Ok(None),
+            })
+    }
+
+    /// The block of the chain notification with the sequence number.
+    pub fn get_notification_block(&self, seq: u64) -> Result<Option<HashValue>> {
+        self.get(Self::notification_block_key(seq).as_slice())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(HashValue::from_slice(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    /// Save the block of the chain notification, and the sequence number as the latest one.
+    pub fn save_notification(&self, seq: u64, block_id: HashValue) -> Result<()> {
+        self.put(Self::notification_block_key(seq), block_id.to_vec())?;
+        self.put(
+            Self::NOTIFICATION_SEQ_KEY.as_bytes().to_vec(),
+            seq.to_be_bytes().to_vec(),
+        )
+    }
+
+    pub fn remove_notification_block(&self, seq: u64) -> Result<()> {
+        self.remove(Self::notification_block_key(seq))
+    }
 }
@@ -0,0 +1,372 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The zstd compression of the historical block bodies and transaction infos.
+//!
+//! The records of the main chain blocks deeper than the compression depth are rewritten
+//! compressed in place, and decompressed by the value codec on read, so the readers are not
+//! aware of the compression. The records written before the compression is enabled, or which
+//! can not be smaller, are kept as is.
+//!
+//! A compressed record starts with `COMPRESSED_VALUE_MARKER`, which is a BCS sequence length
+//! beyond the max BCS sequence length. The BCS records of the compressed column families all
+//! start with a sequence, the hash of a block or a transaction info, or the transaction list
+//! of a block body, so a compressed record never looks like a valid BCS record, and the binaries
+//! without the compression fail to decode it instead of misreading it.
+//!
+//! The marker is followed by the `COMPRESSION_FORMAT_VERSION` of the record, and the version is
+//! also saved in the chain info once a record is compressed. The storage refuses to open if the
+//! saved version is newer than the supported one, so a downgraded binary stops at the startup
+//! instead of failing on the compressed records later.
+//!
+//! The compression dictionary is trained on the transaction payloads once there are enough
+//! samples, and saved in the chain info, it is registered when the storage is opened.
+
+use crate::metrics::STORAGE_COMPRESSION_BYTES;
+use crate::storage::{ColumnFamily, KVStore, SchemaStorage};
+use crate::{BlockInfoStore, BlockStore, Storage};
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use logger::prelude::*;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_types::block::BlockNumber;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::sync::Arc;
+
+const COMPRESSED_VALUE_MARKER: [u8; 5] = [0xff, 0xff, 0xff, 0xff, 0x0f];
+/// The version of the compressed record format, bump it when the format changes.
+pub const COMPRESSION_FORMAT_VERSION: u8 = 1;
+/// The marker, the format version, the dictionary id and the original length, then the zstd frame.
+const COMPRESSED_HEADER_LENGTH: usize = COMPRESSED_VALUE_MARKER.len() + 1 + 4 + 4;
+/// The dictionary id of the records compressed without dictionary.
+const NO_DICTIONARY_ID: u32 = 0;
+const COMPRESSION_LEVEL: i32 = 3;
+const DICTIONARY_MAX_SIZE: usize = 64 * 1024;
+/// The min count of the transaction payload samples to train the dictionary.
+const DICTIONARY_MIN_SAMPLES: usize = 1000;
+
+/// The registered dictionaries by id, the value codec is stateless, so the dictionaries are global.
+static DICTIONARIES: Lazy<RwLock<HashMap<u32, Arc<Vec<u8>>>>> = Lazy::new(Default::default);
+
+/// A trained compression dictionary.
+#[derive(Clone, Debug)]
+pub struct CompressionDictionary {
+    id: u32,
+    data: Arc<Vec<u8>>,
+}
+
+impl CompressionDictionary {
+    /// Create the dictionary and register it, so the records compressed by it can be decompressed.
+    pub fn new(data: Vec<u8>) -> Self {
+        let hash = HashValue::sha3_256_of(data.as_slice()).to_vec();
+        let id = u32::from_be_bytes(hash[..4].try_into().expect("hash should have 32 bytes"));
+        // the id 0 means no dictionary.
+        let id = id.max(1);
+        let data = Arc::new(data);
+        DICTIONARIES.write().insert(id, data.clone());
+        Self { id, data }
+    }
+
+    /// Unregister the dictionary, to simulate a restart in the tests.
+    #[cfg(test)]
+    pub(crate) fn unregister(&self) {
+        DICTIONARIES.write().remove(&self.id);
+    }
+
+    /// Train the dictionary from the samples, return None if there are not enough samples.
+    pub fn train(samples: &[Vec<u8>]) -> Result<Option<Self>> {
+        if samples.len() < DICTIONARY_MIN_SAMPLES {
+            return Ok(None);
+        }
+        let data = zstd::dict::from_samples(samples, DICTIONARY_MAX_SIZE)?;
+        Ok(Some(Self::new(data)))
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+}
+
+pub fn is_compressed(value: &[u8]) -> bool {
+    value.starts_with(&COMPRESSED_VALUE_MARKER)
+}
+
+/// Compress the value, return None if the compressed value is not smaller.
+pub fn compress_value(
+    value: &[u8],
+    dictionary: Option<&CompressionDictionary>,
+) -> Result<Option<Vec<u8>>> {
+    let (dictionary_id, frame) = match dictionary {
+        Some(dictionary) => (
+            dictionary.id(),
+            zstd::bulk::Compressor::with_dictionary(COMPRESSION_LEVEL, dictionary.data())?
+                .compress(value)?,
+        ),
+        None => (
+            NO_DICTIONARY_ID,
+            zstd::bulk::compress(value, COMPRESSION_LEVEL)?,
+        ),
+    };
+    if frame.len().saturating_add(COMPRESSED_HEADER_LENGTH) >= value.len() {
+        return Ok(None);
+    }
+    let original_length: u32 = value.len().try_into()?;
+    let mut compressed = Vec::with_capacity(frame.len().saturating_add(COMPRESSED_HEADER_LENGTH));
+    compressed.extend_from_slice(&COMPRESSED_VALUE_MARKER);
+    compressed.push(COMPRESSION_FORMAT_VERSION);
+    compressed.extend_from_slice(&dictionary_id.to_be_bytes());
+    compressed.extend_from_slice(&original_length.to_be_bytes());
+    compressed.extend_from_slice(frame.as_slice());
+    Ok(Some(compressed))
+}
+
+/// Decompress the value if it is compressed, otherwise return it as is.
+pub fn decompress_value(value: &[u8]) -> Result<Cow<'_, [u8]>> {
+    if !is_compressed(value) {
+        return Ok(Cow::Borrowed(value));
+    }
+    ensure!(
+        value.len() >= COMPRESSED_HEADER_LENGTH,
+        "compressed value is too short: {} bytes",
+        value.len()
+    );
+    let version = value[COMPRESSED_VALUE_MARKER.len()];
+    ensure!(
+        version == COMPRESSION_FORMAT_VERSION,
+        "unsupported compression format version {}, expect {}",
+        version,
+        COMPRESSION_FORMAT_VERSION
+    );
+    let header = &value[COMPRESSED_VALUE_MARKER.len() + 1..COMPRESSED_HEADER_LENGTH];
+    let dictionary_id = u32::from_be_bytes(header[..4].try_into()?);
+    let original_length = u32::from_be_bytes(header[4..].try_into()?) as usize;
+    let frame = &value[COMPRESSED_HEADER_LENGTH..];
+    let decompressed = if dictionary_id == NO_DICTIONARY_ID {
+        zstd::bulk::decompress(frame, original_length)?
+    } else {
+        let dictionary = DICTIONARIES
+            .read()
+            .get(&dictionary_id)
+            .cloned()
+            .ok_or_else(|| format_err!("compression dictionary {} is not loaded", dictionary_id))?;
+        zstd::bulk::Decompressor::with_dictionary(dictionary.as_slice())?
+            .decompress(frame, original_length)?
+    };
+    ensure!(
+        decompressed.len() == original_length,
+        "decompressed value has {} bytes, expect {} bytes",
+        decompressed.len(),
+        original_length
+    );
+    Ok(Cow::Owned(decompressed))
+}
+
+/// The bytes of the records before and after the compression.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct CompressionStats {
+    pub records: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+impl CompressionStats {
+    pub fn add(&mut self, other: CompressionStats) {
+        self.records = self.records.saturating_add(other.records);
+        self.original_bytes = self.original_bytes.saturating_add(other.original_bytes);
+        self.compressed_bytes = self.compressed_bytes.saturating_add(other.compressed_bytes);
+    }
+}
+
+/// Rewrite the record of the key compressed, the compressed or missing record is skipped.
+pub(crate) fn compress_record<S>(
+    storage: &S,
+    key: Vec<u8>,
+    dictionary: Option<&CompressionDictionary>,
+) -> Result<CompressionStats>
+where
+    S: SchemaStorage,
+{
+    let value = match KVStore::get(storage.get_store(), key.as_slice())? {
+        Some(value) if !is_compressed(value.as_slice()) => value,
+        _ => return Ok(CompressionStats::default()),
+    };
+    let compressed = match compress_value(value.as_slice(), dictionary)? {
+        Some(compressed) => compressed,
+        None => return Ok(CompressionStats::default()),
+    };
+    let stats = CompressionStats {
+        records: 1,
+        original_bytes: value.len() as u64,
+        compressed_bytes: compressed.len() as u64,
+    };
+    KVStore::put(storage.get_store(), key, compressed)?;
+    STORAGE_COMPRESSION_BYTES
+        .with_label_values(&[S::name(), "original"])
+        .inc_by(stats.original_bytes);
+    STORAGE_COMPRESSION_BYTES
+        .with_label_values(&[S::name(), "compressed"])
+        .inc_by(stats.compressed_bytes);
+    Ok(stats)
+}
+
+/// The result of a compression run.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompressionReport {
+    /// The count of the blocks compressed in the run.
+    pub blocks: u64,
+    /// The count of the records rewritten compressed.
+    pub records: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// The number of the last compressed main chain block.
+    pub compressed_to: Option<BlockNumber>,
+}
+
+impl Storage {
+    /// Check the compression format of the storage is supported by this binary.
+    pub(crate) fn check_compression_format(&self) -> Result<()> {
+        if let Some(version) = self.chain_info_storage.get_compression_format_version()? {
+            ensure!(
+                version <= COMPRESSION_FORMAT_VERSION,
+                "The storage is compressed by the format version {}, but this binary only supports the version {}, please upgrade the binary.",
+                version,
+                COMPRESSION_FORMAT_VERSION
+            );
+        }
+        Ok(())
+    }
+
+    /// Load and register the saved compression dictionary.
+    pub(crate) fn load_compression_dictionary(&self) -> Result<Option<CompressionDictionary>> {
+        Ok(self
+            .chain_info_storage
+            .get_compression_dictionary()?
+            .map(CompressionDictionary::new))
+    }
+
+    /// Compress the block bodies and the transaction infos of the main chain blocks up to `to_number`,
+    /// continue from the last compressed block, and compress at most `max_blocks` blocks in a call.
+    pub fn compress_blocks(
+        &self,
+        to_number: BlockNumber,
+        max_blocks: u64,
+    ) -> Result<CompressionReport> {
+        let compressed_to = self.chain_info_storage.get_compressed_block_number()?;
+        let mut report = CompressionReport {
+            compressed_to,
+            ..Default::default()
+        };
+        let from = compressed_to
+            .map(|number| number.saturating_add(1))
+            .unwrap_or(0);
+        if from > to_number || max_blocks == 0 {
+            return Ok(report);
+        }
+        let block_ids = self.main_block_ids(
+            from,
+            to_number
+                .saturating_sub(from)
+                .saturating_add(1)
+                .min(max_blocks),
+        )?;
+
+        if self
+            .chain_info_storage
+            .get_compression_format_version()?
+            .is_none()
+        {
+            self.chain_info_storage
+                .save_compression_format_version(COMPRESSION_FORMAT_VERSION)?;
+        }
+        let dictionary = match self.load_compression_dictionary()? {
+            Some(dictionary) => Some(dictionary),
+            None => self.train_compression_dictionary(block_ids.as_slice())?,
+        };
+        let mut stats = CompressionStats::default();
+        for (number, block_id) in (from..).zip(block_ids) {
+            stats.add(
+                self.block_storage
+                    .compress_block(block_id, dictionary.as_ref())?,
+            );
+            let txn_info_ids = self
+                .block_storage
+                .get_transaction_info_ids(block_id)?
+                .unwrap_or_default();
+            for txn_info_id in txn_info_ids {
+                stats.add(compress_record(
+                    &self.transaction_info_storage,
+                    txn_info_id.to_vec(),
+                    dictionary.as_ref(),
+                )?);
+            }
+            report.blocks = report.blocks.saturating_add(1);
+            report.compressed_to = Some(number);
+        }
+        if let Some(number) = report.compressed_to {
+            self.chain_info_storage
+                .save_compressed_block_number(number)?;
+        }
+        report.records = stats.records;
+        report.original_bytes = stats.original_bytes;
+        report.compressed_bytes = stats.compressed_bytes;
+        Ok(report)
+    }
+
+    /// The ids of the main chain blocks from the number, by the block accumulator of the head.
+    fn main_block_ids(&self, from: BlockNumber, count: u64) -> Result<Vec<HashValue>> {
+        let head = self
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?
+            .main;
+        let head_info = self
+            .get_block_info(head)?
+            .ok_or_else(|| format_err!("Block info of the head {} should exist.", head))?;
+        let accumulator = MerkleAccumulator::new_with_info(
+            head_info.block_accumulator_info,
+            Arc::new(self.block_accumulator_storage.clone()),
+        );
+        accumulator.get_leaves(from, false, count)
+    }
+
+    /// Train the dictionary on the transaction payloads of the blocks, and save it.
+    fn train_compression_dictionary(
+        &self,
+        block_ids: &[HashValue],
+    ) -> Result<Option<CompressionDictionary>> {
+        let mut samples = vec![];
+        for block_id in block_ids {
+            if let Some(body) = self.get_body(*block_id)? {
+                for txn in body.transactions {
+                    samples.push(txn.payload().encode()?);
+                }
+            }
+        }
+        let dictionary = match CompressionDictionary::train(samples.as_slice()) {
+            Ok(Some(dictionary)) => dictionary,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                warn!("Train the compression dictionary failed: {:?}", e);
+                return Ok(None);
+            }
+        };
+        info!(
+            "Trained the compression dictionary {} from {} transaction payloads, {} bytes.",
+            dictionary.id(),
+            samples.len(),
+            dictionary.data().len()
+        );
+        self.chain_info_storage
+            .save_compression_dictionary(dictionary.data().to_vec())?;
+        Ok(Some(dictionary))
+    }
+}
@@ -136,6 +136,23 @@ impl DBStorage {
         Ok(())
     }
 
+    /// Compact the whole key range of all column families.
+    pub fn compact_all(&self) -> Result<()> {
+        for cf_name in &self.cfs {
+            let cf_handle = self.get_cf_handle(cf_name)?;
+            self.db
+                .compact_range_cf(cf_handle, None::<&[u8]>, None::<&[u8]>);
+        }
+        Ok(())
+    }
+
+    /// Create a consistent snapshot of the db in the `path`, the path should not exist.
+    pub fn create_checkpoint(&self, path: impl AsRef<Path>) -> Result<()> {
+        let checkpoint = rocksdb::checkpoint::Checkpoint::new(&self.db)?;
+        checkpoint.create_checkpoint(path)?;
+        Ok(())
+    }
+
     /// List cf
     pub fn list_cf(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
         Ok(rocksdb::DB::list_cf(&rocksdb::Options::default(), path)?)
@@ -0,0 +1,197 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Failure injection of the storage for tests, to verify the writers recover from failed writes,
+//! slow reads and torn batches as from a crash. Only built with the `fault-injection` feature.
+
+use crate::batch::WriteBatch;
+use crate::storage::{ColumnFamilyName, InnerStore, StorageInstance};
+use anyhow::{bail, Result};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Faults {
+    /// Only inject to the column family, all column families if it's None.
+    prefix_name: Option<ColumnFamilyName>,
+    /// The writes to pass before failing.
+    writes_to_pass: u64,
+    writes_to_fail: u64,
+    /// The batches to pass before tearing.
+    batches_to_pass: u64,
+    batches_to_tear: u64,
+    read_delay: Option<Duration>,
+    injected: u64,
+}
+
+enum WriteFault {
+    None,
+    Fail,
+    Tear,
+}
+
+/// The faults injected by the `FaultInjection` storage instance, configurable while the storage
+/// is in use.
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    faults: Mutex<Faults>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only inject faults to the column family.
+    pub fn only_prefix(&self, prefix_name: Option<ColumnFamilyName>) {
+        self.faults.lock().prefix_name = prefix_name;
+    }
+
+    /// Fail `count` writes after `after` writes pass, a failed write is not applied.
+    pub fn fail_writes(&self, after: u64, count: u64) {
+        let mut faults = self.faults.lock();
+        faults.writes_to_pass = after;
+        faults.writes_to_fail = count;
+    }
+
+    /// Tear `count` batches after `after` batches pass, only the first half of a torn batch
+    /// is applied before it fails.
+    pub fn tear_batches(&self, after: u64, count: u64) {
+        let mut faults = self.faults.lock();
+        faults.batches_to_pass = after;
+        faults.batches_to_tear = count;
+    }
+
+    /// Delay every read.
+    pub fn set_read_delay(&self, delay: Option<Duration>) {
+        self.faults.lock().read_delay = delay;
+    }
+
+    /// Clear all faults, the count of the injected faults is kept.
+    pub fn reset(&self) {
+        let mut faults = self.faults.lock();
+        *faults = Faults {
+            injected: faults.injected,
+            ..Faults::default()
+        };
+    }
+
+    /// The count of the failed writes and torn batches.
+    pub fn injected_faults(&self) -> u64 {
+        self.faults.lock().injected
+    }
+
+    fn on_read(&self) {
+        let read_delay = self.faults.lock().read_delay;
+        if let Some(delay) = read_delay {
+            std::thread::sleep(delay);
+        }
+    }
+
+    fn on_write(&self, prefix_name: &str, is_batch: bool) -> WriteFault {
+        let mut faults = self.faults.lock();
+        if faults
+            .prefix_name
+            .filter(|name| *name != prefix_name)
+            .is_some()
+        {
+            return WriteFault::None;
+        }
+        if is_batch && faults.batches_to_tear > 0 {
+            if faults.batches_to_pass > 0 {
+                faults.batches_to_pass -= 1;
+            } else {
+                faults.batches_to_tear -= 1;
+                faults.injected += 1;
+                return WriteFault::Tear;
+            }
+        }
+        if faults.writes_to_fail > 0 {
+            if faults.writes_to_pass > 0 {
+                faults.writes_to_pass -= 1;
+            } else {
+                faults.writes_to_fail -= 1;
+                faults.injected += 1;
+                return WriteFault::Fail;
+            }
+        }
+        WriteFault::None
+    }
+}
+
+/// Wrap the storage instance to inject the faults of the injector.
+#[derive(Clone)]
+pub struct FaultInjectionStorage {
+    instance: Box<StorageInstance>,
+    injector: Arc<FaultInjector>,
+}
+
+impl FaultInjectionStorage {
+    pub fn new(instance: StorageInstance, injector: Arc<FaultInjector>) -> Self {
+        Self {
+            instance: Box::new(instance),
+            injector,
+        }
+    }
+
+    pub fn instance(&self) -> &StorageInstance {
+        &self.instance
+    }
+
+    pub fn injector(&self) -> Arc<FaultInjector> {
+        self.injector.clone()
+    }
+
+    fn check_write(&self, prefix_name: &str, is_batch: bool) -> Result<()> {
+        match self.injector.on_write(prefix_name, is_batch) {
+            WriteFault::Fail => bail!("Injected write failure to {}", prefix_name),
+            // a single write can not be torn.
+            WriteFault::None | WriteFault::Tear => Ok(()),
+        }
+    }
+}
+
+impl InnerStore for FaultInjectionStorage {
+    fn get(&self, prefix_name: &str, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        self.injector.on_read();
+        self.instance.get(prefix_name, key)
+    }
+
+    fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+        self.check_write(prefix_name, false)?;
+        self.instance.put(prefix_name, key, value)
+    }
+
+    fn contains_key(&self, prefix_name: &str, key: Vec<u8>) -> Result<bool> {
+        self.injector.on_read();
+        self.instance.contains_key(prefix_name, key)
+    }
+
+    fn remove(&self, prefix_name: &str, key: Vec<u8>) -> Result<()> {
+        self.check_write(prefix_name, false)?;
+        self.instance.remove(prefix_name, key)
+    }
+
+    fn write_batch(&self, prefix_name: &str, batch: WriteBatch) -> Result<()> {
+        match self.injector.on_write(prefix_name, true) {
+            WriteFault::None => self.instance.write_batch(prefix_name, batch),
+            WriteFault::Fail => bail!("Injected write failure to {}", prefix_name),
+            WriteFault::Tear => {
+                let mut rows = batch.rows;
+                rows.truncate(rows.len() / 2);
+                self.instance
+                    .write_batch(prefix_name, WriteBatch::new_with_rows(rows))?;
+                bail!("Injected torn batch to {}", prefix_name)
+            }
+        }
+    }
+
+    fn get_len(&self) -> Result<u64> {
+        self.instance.get_len()
+    }
+
+    fn keys(&self) -> Result<Vec<Vec<u8>>> {
+        self.instance.keys()
+    }
+}
@@ -36,10 +36,14 @@ pub mod block;
 pub mod block_info;
 pub mod cache_storage;
 pub mod chain_info;
+pub mod compression;
 pub mod contract_event;
 pub mod db_storage;
 pub mod errors;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod fault_injection;
 mod metrics;
+pub mod scrub;
 pub mod state_node;
 pub mod storage;
 #[cfg(test)]
@@ -65,6 +69,7 @@ pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const QUARANTINE_PREFIX_NAME: ColumnFamilyName = "quarantine";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -85,6 +90,7 @@ pub static VEC_PREFIX_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TRANSACTION_INFO_HASH_PREFIX_NAME,
         CONTRACT_EVENT_PREFIX_NAME,
         FAILED_BLOCK_PREFIX_NAME,
+        QUARANTINE_PREFIX_NAME,
     ]
 });
 
@@ -189,7 +195,7 @@ pub struct Storage {
 
 impl Storage {
     pub fn new(instance: StorageInstance) -> Result<Self> {
-        Ok(Self {
+        let storage = Self {
             transaction_info_storage: TransactionInfoStorage::new(instance.clone()),
             transaction_info_hash_storage: TransactionInfoHashStorage::new(instance.clone()),
             transaction_storage: TransactionStorage::new(instance.clone()),
@@ -203,7 +209,10 @@ impl Storage {
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
             chain_info_storage: ChainInfoStorage::new(instance),
-        })
+        };
+        storage.check_compression_format()?;
+        storage.load_compression_dictionary()?;
+        Ok(storage)
     }
 
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
@@ -215,6 +224,27 @@ impl Storage {
     ) -> AccumulatorStorage<TransactionAccumulatorStorage> {
         self.transaction_accumulator_storage.clone()
     }
+
+    /// The sequence number of the latest chain notification, it is kept across restarts.
+    pub fn get_notification_seq(&self) -> Result<Option<u64>> {
+        self.chain_info_storage.get_notification_seq()
+    }
+
+    /// The block of the chain notification with the sequence number, None if it is out of the kept window.
+    pub fn get_notification_block(&self, seq: u64) -> Result<Option<HashValue>> {
+        self.chain_info_storage.get_notification_block(seq)
+    }
+
+    /// Save the block of the latest chain notification,
+    /// and remove the notification `keep` sequence numbers before it.
+    pub fn save_notification(&self, seq: u64, block_id: HashValue, keep: u64) -> Result<()> {
+        self.chain_info_storage.save_notification(seq, block_id)?;
+        if let Some(outdated_seq) = seq.checked_sub(keep) {
+            self.chain_info_storage
+                .remove_notification_block(outdated_seq)?;
+        }
+        Ok(())
+    }
 }
 
 impl StateNodeStore for Storage {
@@ -37,6 +37,24 @@ pub static STORAGE_TIMES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static STORAGE_QUARANTINED_RECORDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "starcoin_storage_quarantined_records",
+        "Counters of the corrupted records moved to the quarantine by the scrub",
+        &["cf_name"]
+    )
+    .unwrap()
+});
+
+pub static STORAGE_COMPRESSION_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "starcoin_storage_compression_bytes",
+        "Bytes of the historical records before and after the compression",
+        &["cf_name", "type"]
+    )
+    .unwrap()
+});
+
 pub static CACHE_ITEMS: Lazy<UIntGauge> =
     Lazy::new(|| register_uint_gauge!("starcoin_cache_items", "How many items in cache").unwrap());
 
@@ -0,0 +1,258 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scan the storage for the silent corruption of the records.
+//!
+//! Every record is read with the rocksdb block checksum verified, and the records of the
+//! content addressed column families, such as blocks and state nodes, are checked against
+//! their keys. The other column families have no content hash of the record, their records are
+//! decoded, and checked against the records they refer to where possible, such as the block body
+//! against the body hash of its header. A corruption which still decodes to a valid record can
+//! not be detected there, so these column families are listed in the report as partially verified.
+//!
+//! A bad record is moved to the quarantine column family, so it is never read as a valid record
+//! again, and can be inspected or restored later. The record is removed from the live cache too,
+//! so the cache does not keep serving it.
+
+use crate::block::FailedBlock;
+use crate::cache_storage::CacheStorage;
+use crate::compression::decompress_value;
+use crate::db_storage::DBStorage;
+use crate::metrics::STORAGE_QUARANTINED_RECORDS;
+use crate::storage::{ColumnFamilyName, InnerStore};
+use crate::{
+    BLOCK_ACCUMULATOR_NODE_PREFIX_NAME, BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME,
+    BLOCK_INFO_PREFIX_NAME, BLOCK_PREFIX_NAME, BLOCK_TRANSACTIONS_PREFIX_NAME,
+    BLOCK_TRANSACTION_INFOS_PREFIX_NAME, CONTRACT_EVENT_PREFIX_NAME, DEFAULT_PREFIX_NAME,
+    FAILED_BLOCK_PREFIX_NAME, QUARANTINE_PREFIX_NAME, STATE_NODE_PREFIX_NAME,
+    TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME, TRANSACTION_INFO_HASH_PREFIX_NAME,
+    TRANSACTION_INFO_PREFIX_NAME, TRANSACTION_PREFIX_NAME,
+};
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use forkable_jellyfish_merkle::node_type::Node;
+use forkable_jellyfish_merkle::RawKey;
+use logger::prelude::*;
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::AccumulatorNode;
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockInfo};
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+
+/// The separator between the column family name and the origin key in the quarantine key.
+const QUARANTINE_KEY_SEPARATOR: u8 = b'/';
+
+/// The result of a scrub.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScrubReport {
+    /// The count of the scanned records.
+    pub scanned: u64,
+    /// The count of the bad records moved to the quarantine.
+    pub quarantined: u64,
+    /// The column families which can not be fully scanned, for the rocksdb checksum mismatch.
+    pub corrupted_cfs: Vec<String>,
+    /// The scanned column families without a content hash of the record, the records are only
+    /// decoded and cross checked, a corruption which still decodes may be missed.
+    pub partially_verified_cfs: Vec<String>,
+}
+
+impl ScrubReport {
+    pub fn is_clean(&self) -> bool {
+        self.quarantined == 0 && self.corrupted_cfs.is_empty()
+    }
+}
+
+/// The raw key of the state tree leaf, the key bytes are kept as is, so the leaf hash
+/// can be checked without knowing which tree the node belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct RawBytesKey(Vec<u8>);
+
+impl RawKey for RawBytesKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.0.clone())
+    }
+
+    fn decode_key(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(bytes.to_vec()))
+    }
+}
+
+/// Whether the records of the column family are addressed by their content hash.
+fn is_content_addressed(cf_name: ColumnFamilyName) -> bool {
+    matches!(
+        cf_name,
+        BLOCK_PREFIX_NAME
+            | BLOCK_HEADER_PREFIX_NAME
+            | TRANSACTION_PREFIX_NAME
+            | TRANSACTION_INFO_PREFIX_NAME
+            | STATE_NODE_PREFIX_NAME
+            | BLOCK_ACCUMULATOR_NODE_PREFIX_NAME
+            | TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME
+    )
+}
+
+fn ensure_key(hash: HashValue, key: &[u8]) -> Result<()> {
+    ensure!(
+        hash.to_vec().as_slice() == key,
+        "the hash {} does not match the key",
+        hash
+    );
+    Ok(())
+}
+
+/// Check the record is the content of its key for the content addressed column families,
+/// otherwise check the record decodes, and matches the record it refers to.
+fn verify_record(
+    db: &DBStorage,
+    cf_name: ColumnFamilyName,
+    key: &[u8],
+    value: &[u8],
+) -> Result<()> {
+    match cf_name {
+        BLOCK_PREFIX_NAME => {
+            ensure_key(Block::decode(decompress_value(value)?.as_ref())?.id(), key)
+        }
+        BLOCK_HEADER_PREFIX_NAME => ensure_key(BlockHeader::decode(value)?.id(), key),
+        TRANSACTION_PREFIX_NAME => ensure_key(Transaction::decode(value)?.id(), key),
+        TRANSACTION_INFO_PREFIX_NAME => ensure_key(BlockTransactionInfo::decode(value)?.id(), key),
+        STATE_NODE_PREFIX_NAME => ensure_key(Node::<RawBytesKey>::decode(value)?.hash(), key),
+        BLOCK_ACCUMULATOR_NODE_PREFIX_NAME | TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME => {
+            ensure_key(AccumulatorNode::decode(value)?.hash(), key)
+        }
+        BLOCK_INFO_PREFIX_NAME => ensure_key(BlockInfo::decode(value)?.block_id, key),
+        BLOCK_BODY_PREFIX_NAME => {
+            let body_hash = BlockBody::decode(value)?.hash();
+            // the body is keyed by the block id, its hash is kept in the header.
+            if let Some(header) = db.get(BLOCK_HEADER_PREFIX_NAME, key.to_vec())? {
+                let header = BlockHeader::decode(header.as_slice())?;
+                ensure!(
+                    header.body_hash() == body_hash,
+                    "the body hash {} does not match the body hash {} of the header",
+                    body_hash,
+                    header.body_hash()
+                );
+            }
+            Ok(())
+        }
+        BLOCK_TRANSACTIONS_PREFIX_NAME
+        | BLOCK_TRANSACTION_INFOS_PREFIX_NAME
+        | TRANSACTION_INFO_HASH_PREFIX_NAME => {
+            HashValue::from_slice(key)?;
+            bcs_ext::from_bytes::<Vec<HashValue>>(value)?;
+            Ok(())
+        }
+        CONTRACT_EVENT_PREFIX_NAME => {
+            HashValue::from_slice(key)?;
+            Vec::<ContractEvent>::decode(value)?;
+            Ok(())
+        }
+        FAILED_BLOCK_PREFIX_NAME => {
+            HashValue::from_slice(key)?;
+            FailedBlock::decode(value)?;
+            Ok(())
+        }
+        // the chain info values are of different types by the key.
+        _ => Ok(()),
+    }
+}
+
+pub fn quarantine_key(cf_name: &str, key: &[u8]) -> Vec<u8> {
+    let mut quarantine_key = cf_name.as_bytes().to_vec();
+    quarantine_key.push(QUARANTINE_KEY_SEPARATOR);
+    quarantine_key.extend_from_slice(key);
+    quarantine_key
+}
+
+/// Move the record to the quarantine column family.
+fn quarantine(
+    db: &DBStorage,
+    cache: Option<&CacheStorage>,
+    cf_name: ColumnFamilyName,
+    key: Vec<u8>,
+    value: Vec<u8>,
+) -> Result<()> {
+    // the cache is invalidated before the record is deleted, and once more after it, in case the
+    // record is read back into the cache between.
+    if let Some(cache) = cache {
+        cache.remove(cf_name, key.clone())?;
+    }
+    db.put(QUARANTINE_PREFIX_NAME, quarantine_key(cf_name, &key), value)?;
+    db.remove(cf_name, key.clone())?;
+    if let Some(cache) = cache {
+        cache.remove(cf_name, key)?;
+    }
+    STORAGE_QUARANTINED_RECORDS
+        .with_label_values(&[cf_name])
+        .inc();
+    Ok(())
+}
+
+/// Scan the column families, and quarantine the records which fail the verification.
+/// The `cache` is the live cache over the `db`, if any.
+pub fn scrub(
+    db: &DBStorage,
+    cache: Option<&CacheStorage>,
+    cf_names: &[ColumnFamilyName],
+) -> Result<ScrubReport> {
+    let mut report = ScrubReport::default();
+    for cf_name in cf_names.iter().copied() {
+        if cf_name == QUARANTINE_PREFIX_NAME || cf_name == DEFAULT_PREFIX_NAME {
+            continue;
+        }
+        if !is_content_addressed(cf_name) {
+            report.partially_verified_cfs.push(cf_name.to_string());
+        }
+        let mut iter = db.iter(cf_name)?;
+        iter.seek_to_first();
+        let mut bad_records = vec![];
+        for item in iter {
+            let (key, value) = match item {
+                Ok(item) => item,
+                Err(e) => {
+                    error!(
+                        "[scrub] Storage column family {} is corrupted, scan stopped: {}",
+                        cf_name, e
+                    );
+                    report.corrupted_cfs.push(cf_name.to_string());
+                    break;
+                }
+            };
+            report.scanned = report.scanned.saturating_add(1);
+            if let Err(e) = verify_record(db, cf_name, &key, &value) {
+                error!(
+                    "[scrub] Record {} of column family {} is corrupted, quarantine it: {}",
+                    hex::encode(&key),
+                    cf_name,
+                    e
+                );
+                bad_records.push((key, value));
+            }
+        }
+        for (key, value) in bad_records {
+            quarantine(db, cache, cf_name, key, value)?;
+            report.quarantined = report.quarantined.saturating_add(1);
+        }
+    }
+    Ok(report)
+}
+
+/// The records in the quarantine, as (column family name, key, value).
+pub fn quarantined_records(db: &DBStorage) -> Result<Vec<(String, Vec<u8>, Vec<u8>)>> {
+    let mut iter = db.iter(QUARANTINE_PREFIX_NAME)?;
+    iter.seek_to_first();
+    let mut records = vec![];
+    for item in iter {
+        let (quarantine_key, value) = item?;
+        let position = quarantine_key
+            .iter()
+            .position(|b| *b == QUARANTINE_KEY_SEPARATOR)
+            .ok_or_else(|| {
+                format_err!("Invalid quarantine key {}", hex::encode(&quarantine_key))
+            })?;
+        let cf_name = String::from_utf8(quarantine_key[..position].to_vec())?;
+        let key = quarantine_key[position.saturating_add(1)..].to_vec();
+        records.push((cf_name, key, value));
+    }
+    Ok(records)
+}
@@ -4,6 +4,8 @@
 pub use crate::batch::WriteBatch;
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
+#[cfg(any(test, feature = "fault-injection"))]
+use crate::fault_injection::{FaultInjectionStorage, FaultInjector};
 use anyhow::{bail, Result};
 use byteorder::{BigEndian, ReadBytesExt};
 use crypto::HashValue;
@@ -73,6 +75,10 @@ pub enum StorageInstance {
         cache: Arc<CacheStorage>,
         db: Arc<DBStorage>,
     },
+    #[cfg(any(test, feature = "fault-injection"))]
+    FaultInjection {
+        storage: FaultInjectionStorage,
+    },
 }
 
 impl StorageInstance {
@@ -92,11 +98,21 @@ impl StorageInstance {
         }
     }
 
+    /// Wrap the instance to inject the faults of the injector, for tests only.
+    #[cfg(any(test, feature = "fault-injection"))]
+    pub fn new_fault_injection_instance(instance: Self, injector: Arc<FaultInjector>) -> Self {
+        Self::FaultInjection {
+            storage: FaultInjectionStorage::new(instance, injector),
+        }
+    }
+
     pub fn cache(&self) -> Option<Arc<CacheStorage>> {
         match self {
             StorageInstance::CACHE { cache } | StorageInstance::CacheAndDb { cache, db: _ } => {
                 Some(cache.clone())
             }
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.instance().cache(),
             _ => None,
         }
     }
@@ -106,6 +122,8 @@ impl StorageInstance {
             StorageInstance::DB { db } | StorageInstance::CacheAndDb { cache: _, db } => {
                 Some(db.clone())
             }
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.instance().db(),
             _ => None,
         }
     }
@@ -114,6 +132,8 @@ impl StorageInstance {
 impl InnerStore for StorageInstance {
     fn get(&self, prefix_name: &str, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.get(prefix_name, key),
             StorageInstance::CACHE { cache } => cache.get(prefix_name, key),
             StorageInstance::DB { db } => db.get(prefix_name, key),
             StorageInstance::CacheAndDb { cache, db } => {
@@ -142,6 +162,8 @@ impl InnerStore for StorageInstance {
 
     fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.put(prefix_name, key, value),
             StorageInstance::CACHE { cache } => cache.put(prefix_name, key, value),
             StorageInstance::DB { db } => db.put(prefix_name, key, value),
             StorageInstance::CacheAndDb { cache, db } => db
@@ -152,6 +174,8 @@ impl InnerStore for StorageInstance {
 
     fn contains_key(&self, prefix_name: &str, key: Vec<u8>) -> Result<bool> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.contains_key(prefix_name, key),
             StorageInstance::CACHE { cache } => cache.contains_key(prefix_name, key),
             StorageInstance::DB { db } => db.contains_key(prefix_name, key),
             StorageInstance::CacheAndDb { cache, db } => {
@@ -168,6 +192,8 @@ impl InnerStore for StorageInstance {
 
     fn remove(&self, prefix_name: &str, key: Vec<u8>) -> Result<()> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.remove(prefix_name, key),
             StorageInstance::CACHE { cache } => cache.remove(prefix_name, key),
             StorageInstance::DB { db } => db.remove(prefix_name, key),
             StorageInstance::CacheAndDb { cache, db } => {
@@ -181,6 +207,8 @@ impl InnerStore for StorageInstance {
 
     fn write_batch(&self, prefix_name: &str, batch: WriteBatch) -> Result<()> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.write_batch(prefix_name, batch),
             StorageInstance::CACHE { cache } => cache.write_batch(prefix_name, batch),
             StorageInstance::DB { db } => db.write_batch(prefix_name, batch),
             StorageInstance::CacheAndDb { cache, db } => {
@@ -193,6 +221,8 @@ impl InnerStore for StorageInstance {
     }
     fn get_len(&self) -> Result<u64> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.get_len(),
             StorageInstance::CACHE { cache } => cache.get_len(),
             StorageInstance::CacheAndDb { cache, db: _ } => cache.get_len(),
             _ => bail!("DB instance not support get length method!"),
@@ -201,6 +231,8 @@ impl InnerStore for StorageInstance {
 
     fn keys(&self) -> Result<Vec<Vec<u8>>> {
         match self {
+            #[cfg(any(test, feature = "fault-injection"))]
+            StorageInstance::FaultInjection { storage } => storage.keys(),
             StorageInstance::CACHE { cache } => cache.keys(),
             StorageInstance::CacheAndDb { cache, db: _ } => cache.keys(),
             _ => bail!("DB instance not support keys method!"),
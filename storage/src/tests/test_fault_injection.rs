@@ -0,0 +1,87 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::batch::WriteBatch;
+use crate::fault_injection::FaultInjector;
+use crate::storage::{InnerStore, StorageInstance};
+use crate::{BLOCK_PREFIX_NAME, DEFAULT_PREFIX_NAME};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn key(i: u8) -> Vec<u8> {
+    vec![i]
+}
+
+#[test]
+fn test_fail_writes() {
+    let injector = Arc::new(FaultInjector::new());
+    let instance = StorageInstance::new_fault_injection_instance(
+        StorageInstance::new_cache_instance(),
+        injector.clone(),
+    );
+    injector.fail_writes(1, 2);
+    instance.put(DEFAULT_PREFIX_NAME, key(1), key(1)).unwrap();
+    assert!(instance.put(DEFAULT_PREFIX_NAME, key(2), key(2)).is_err());
+    assert!(instance.remove(DEFAULT_PREFIX_NAME, key(1)).is_err());
+    instance.put(DEFAULT_PREFIX_NAME, key(3), key(3)).unwrap();
+    assert_eq!(injector.injected_faults(), 2);
+    assert_eq!(
+        instance.get(DEFAULT_PREFIX_NAME, key(1)).unwrap(),
+        Some(key(1))
+    );
+    assert_eq!(instance.get(DEFAULT_PREFIX_NAME, key(2)).unwrap(), None);
+
+    // only fail the writes to the block column family.
+    injector.only_prefix(Some(BLOCK_PREFIX_NAME));
+    injector.fail_writes(0, 1);
+    instance.put(DEFAULT_PREFIX_NAME, key(4), key(4)).unwrap();
+    assert!(instance.put(BLOCK_PREFIX_NAME, key(4), key(4)).is_err());
+    instance.put(BLOCK_PREFIX_NAME, key(4), key(4)).unwrap();
+    assert_eq!(injector.injected_faults(), 3);
+}
+
+#[test]
+fn test_torn_batch() {
+    let injector = Arc::new(FaultInjector::new());
+    let instance = StorageInstance::new_fault_injection_instance(
+        StorageInstance::new_cache_instance(),
+        injector.clone(),
+    );
+    injector.tear_batches(0, 1);
+    let mut batch = WriteBatch::new();
+    for i in 0..4 {
+        batch.put(key(i), key(i)).unwrap();
+    }
+    assert!(instance
+        .write_batch(DEFAULT_PREFIX_NAME, batch.clone())
+        .is_err());
+    assert!(instance.get(DEFAULT_PREFIX_NAME, key(1)).unwrap().is_some());
+    assert!(instance.get(DEFAULT_PREFIX_NAME, key(2)).unwrap().is_none());
+
+    // the retry of the batch recovers the torn writes.
+    instance.write_batch(DEFAULT_PREFIX_NAME, batch).unwrap();
+    for i in 0..4 {
+        assert_eq!(
+            instance.get(DEFAULT_PREFIX_NAME, key(i)).unwrap(),
+            Some(key(i))
+        );
+    }
+    assert_eq!(injector.injected_faults(), 1);
+}
+
+#[test]
+fn test_read_delay() {
+    let injector = Arc::new(FaultInjector::new());
+    let instance = StorageInstance::new_fault_injection_instance(
+        StorageInstance::new_cache_instance(),
+        injector.clone(),
+    );
+    injector.set_read_delay(Some(Duration::from_millis(50)));
+    let now = Instant::now();
+    instance.get(DEFAULT_PREFIX_NAME, key(1)).unwrap();
+    assert!(now.elapsed() >= Duration::from_millis(50));
+    injector.reset();
+    injector.fail_writes(0, 1);
+    injector.reset();
+    instance.put(DEFAULT_PREFIX_NAME, key(1), key(1)).unwrap();
+}
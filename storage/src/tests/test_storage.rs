@@ -4,17 +4,27 @@
 extern crate chrono;
 
 use crate::cache_storage::CacheStorage;
+use crate::compression::{
+    compress_value, decompress_value, is_compressed, CompressionDictionary,
+    COMPRESSION_FORMAT_VERSION,
+};
 use crate::db_storage::DBStorage;
+use crate::scrub::{quarantined_records, scrub};
 use crate::storage::{CodecKVStore, InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
 use crate::{
-    BlockTransactionInfoStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
-    VEC_PREFIX_NAME,
+    BlockInfoStore, BlockStore, BlockTransactionInfoStore, Storage, BLOCK_INFO_PREFIX_NAME,
+    BLOCK_PREFIX_NAME, DEFAULT_PREFIX_NAME, STATE_NODE_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME, VEC_PREFIX_NAME,
 };
 use anyhow::Result;
 use crypto::HashValue;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_config::RocksdbConfig;
+use starcoin_types::block::{AccumulatorInfo, Block, BlockBody, BlockHeader, BlockInfo};
+use starcoin_types::startup_info::StartupInfo;
 use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
 use starcoin_types::vm_error::KeptVMStatus;
+use starcoin_types::U256;
+use std::sync::Arc;
 
 #[test]
 fn test_reopen() {
@@ -61,6 +71,31 @@ fn test_open_read_only() {
     assert_eq!(result, Some(value.to_vec()));
 }
 
+#[test]
+fn test_compact_and_checkpoint() {
+    let tmpdir = starcoin_config::temp_path();
+    let db = DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap();
+    let key = HashValue::random();
+    let value = HashValue::zero();
+    db.put(DEFAULT_PREFIX_NAME, key.to_vec(), value.to_vec())
+        .unwrap();
+    db.compact_all().unwrap();
+    let checkpoint_path = tmpdir.as_ref().join("checkpoint");
+    db.create_checkpoint(checkpoint_path.as_path()).unwrap();
+    assert!(db.create_checkpoint(checkpoint_path.as_path()).is_err());
+    let checkpoint = DBStorage::open_with_cfs(
+        checkpoint_path,
+        VEC_PREFIX_NAME.to_vec(),
+        true,
+        RocksdbConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        checkpoint.get(DEFAULT_PREFIX_NAME, key.to_vec()).unwrap(),
+        Some(value.to_vec())
+    );
+}
+
 #[test]
 fn test_storage() {
     let tmpdir = starcoin_config::temp_path();
@@ -218,3 +253,293 @@ fn test_missing_key_handle() -> Result<()> {
     assert_eq!(contains, false);
     Ok(())
 }
+
+#[test]
+fn test_scrub() {
+    let tmpdir = starcoin_config::temp_path();
+    let db = DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap();
+    let block = Block::new(BlockHeader::random(), BlockBody::new_empty());
+    db.put(
+        BLOCK_PREFIX_NAME,
+        block.id().to_vec(),
+        block.encode_value().unwrap(),
+    )
+    .unwrap();
+    // a flipped bit of the record makes the content hash mismatch the key.
+    let mut rotten = block.encode_value().unwrap();
+    let rotten_key = HashValue::random().to_vec();
+    db.put(BLOCK_PREFIX_NAME, rotten_key.clone(), rotten.clone())
+        .unwrap();
+    rotten[0] ^= 1;
+    let rotten_node_key = HashValue::random().to_vec();
+    db.put(STATE_NODE_PREFIX_NAME, rotten_node_key.clone(), rotten)
+        .unwrap();
+
+    let report = scrub(&db, None, VEC_PREFIX_NAME.as_slice()).unwrap();
+    assert_eq!(report.scanned, 3);
+    assert_eq!(report.quarantined, 2);
+    assert!(!report.is_clean());
+    assert!(db
+        .get(BLOCK_PREFIX_NAME, block.id().to_vec())
+        .unwrap()
+        .is_some());
+    assert!(db
+        .get(BLOCK_PREFIX_NAME, rotten_key.clone())
+        .unwrap()
+        .is_none());
+    let mut quarantined: Vec<(String, Vec<u8>)> = quarantined_records(&db)
+        .unwrap()
+        .into_iter()
+        .map(|(cf_name, key, _)| (cf_name, key))
+        .collect();
+    quarantined.sort();
+    assert_eq!(
+        quarantined,
+        vec![
+            (BLOCK_PREFIX_NAME.to_string(), rotten_key),
+            (STATE_NODE_PREFIX_NAME.to_string(), rotten_node_key),
+        ]
+    );
+
+    let report = scrub(&db, None, VEC_PREFIX_NAME.as_slice()).unwrap();
+    assert!(report.is_clean());
+    assert!(report
+        .partially_verified_cfs
+        .contains(&BLOCK_INFO_PREFIX_NAME.to_string()));
+    assert!(!report
+        .partially_verified_cfs
+        .contains(&BLOCK_PREFIX_NAME.to_string()));
+}
+
+#[test]
+fn test_scrub_invalidate_cache() {
+    let tmpdir = starcoin_config::temp_path();
+    let db = DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap();
+    let cache = CacheStorage::new();
+    // the block info is keyed by another block id.
+    let block_info = BlockInfo::new(
+        HashValue::random(),
+        U256::zero(),
+        AccumulatorInfo::default(),
+        AccumulatorInfo::default(),
+    );
+    let key = HashValue::random().to_vec();
+    let value = block_info.encode_value().unwrap();
+    db.put(BLOCK_INFO_PREFIX_NAME, key.clone(), value.clone())
+        .unwrap();
+    cache.put(BLOCK_INFO_PREFIX_NAME, key.clone(), value).unwrap();
+
+    let report = scrub(&db, Some(&cache), &[BLOCK_INFO_PREFIX_NAME]).unwrap();
+    assert_eq!(report.quarantined, 1);
+    assert!(db
+        .get(BLOCK_INFO_PREFIX_NAME, key.clone())
+        .unwrap()
+        .is_none());
+    assert!(cache.get(BLOCK_INFO_PREFIX_NAME, key).unwrap().is_none());
+}
+
+#[test]
+fn test_notification() {
+    let storage = Storage::new(StorageInstance::new_cache_instance()).unwrap();
+    assert_eq!(storage.get_notification_seq().unwrap(), None);
+    let block_ids = (0..3).map(|_| HashValue::random()).collect::<Vec<_>>();
+    for (i, block_id) in block_ids.iter().enumerate() {
+        storage
+            .save_notification(i as u64 + 1, *block_id, 2)
+            .unwrap();
+    }
+    assert_eq!(storage.get_notification_seq().unwrap(), Some(3));
+    // only the latest 2 notifications are kept.
+    assert_eq!(storage.get_notification_block(1).unwrap(), None);
+    assert_eq!(
+        storage.get_notification_block(2).unwrap(),
+        Some(block_ids[1])
+    );
+    assert_eq!(
+        storage.get_notification_block(3).unwrap(),
+        Some(block_ids[2])
+    );
+}
+
+#[test]
+fn test_compress_block() {
+    let storage = Storage::new(StorageInstance::new_cache_instance()).unwrap();
+    let uncle = BlockHeader::random();
+    let body = BlockBody::new(vec![], Some(vec![uncle.clone(); 8]));
+    let block = Block::new(BlockHeader::random(), body.clone());
+    let block_id = block.id();
+    storage.commit_block(block.clone()).unwrap();
+
+    let stats = storage
+        .block_storage
+        .compress_block(block_id, None)
+        .unwrap();
+    assert_eq!(stats.records, 2);
+    assert!(stats.compressed_bytes < stats.original_bytes);
+    assert_eq!(storage.get_block(block_id).unwrap(), Some(block));
+    assert_eq!(storage.get_body(block_id).unwrap(), Some(body));
+    // the compressed records are skipped.
+    let stats = storage
+        .block_storage
+        .compress_block(block_id, None)
+        .unwrap();
+    assert_eq!(stats.records, 0);
+
+    // a raw content dictionary.
+    let dictionary = CompressionDictionary::new(uncle.encode_value().unwrap());
+    let value = BlockBody::new(vec![], Some(vec![uncle]))
+        .encode_value()
+        .unwrap();
+    let compressed = compress_value(value.as_slice(), Some(&dictionary))
+        .unwrap()
+        .expect("the value should be compressed by the dictionary.");
+    assert!(is_compressed(compressed.as_slice()));
+    assert!(!is_compressed(value.as_slice()));
+    assert_eq!(
+        decompress_value(compressed.as_slice()).unwrap().as_ref(),
+        value.as_slice()
+    );
+    assert_eq!(
+        decompress_value(value.as_slice()).unwrap().as_ref(),
+        value.as_slice()
+    );
+}
+
+/// Commit the blocks as the main chain, every block has a transaction info.
+fn commit_main_chain(storage: &Storage, count: usize) -> Vec<(Block, BlockTransactionInfo)> {
+    let accumulator =
+        MerkleAccumulator::new_empty(Arc::new(storage.get_block_accumulator_storage()));
+    let uncle = BlockHeader::random();
+    let mut blocks = vec![];
+    for _ in 0..count {
+        let block = Block::new(
+            BlockHeader::random(),
+            BlockBody::new(vec![], Some(vec![uncle.clone(); 8])),
+        );
+        let txn_info = BlockTransactionInfo::new(
+            block.id(),
+            TransactionInfo::new(
+                HashValue::random(),
+                HashValue::zero(),
+                vec![].as_slice(),
+                0,
+                KeptVMStatus::Executed,
+            ),
+        );
+        storage.commit_block(block.clone()).unwrap();
+        storage
+            .save_transaction_infos(vec![txn_info.clone()])
+            .unwrap();
+        storage
+            .save_block_txn_info_ids(block.id(), vec![txn_info.id()])
+            .unwrap();
+        accumulator.append(&[block.id()]).unwrap();
+        blocks.push((block, txn_info));
+    }
+    accumulator.flush().unwrap();
+    let head = blocks.last().unwrap().0.id();
+    storage
+        .save_block_info(BlockInfo::new(
+            head,
+            U256::zero(),
+            AccumulatorInfo::default(),
+            accumulator.get_info(),
+        ))
+        .unwrap();
+    storage.save_startup_info(StartupInfo::new(head)).unwrap();
+    blocks
+}
+
+#[test]
+fn test_compress_blocks() {
+    let tmpdir = starcoin_config::temp_path();
+    let open = || {
+        Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+        ))
+    };
+    let (blocks, dictionary) = {
+        let storage = open().unwrap();
+        let blocks = commit_main_chain(&storage, 10);
+        // a raw content dictionary, so the transaction infos can be compressed.
+        let dictionary_data = blocks
+            .iter()
+            .flat_map(|(_, txn_info)| txn_info.encode_value().unwrap())
+            .collect::<Vec<_>>();
+        storage
+            .chain_info_storage
+            .save_compression_dictionary(dictionary_data.clone())
+            .unwrap();
+        assert_eq!(
+            storage
+                .chain_info_storage
+                .get_compression_format_version()
+                .unwrap(),
+            None
+        );
+
+        let report = storage.compress_blocks(5, 4).unwrap();
+        assert_eq!(report.blocks, 4);
+        // the block, the body and the transaction info of every block.
+        assert_eq!(report.records, 4 * 3);
+        assert!(report.compressed_bytes < report.original_bytes);
+        assert_eq!(report.compressed_to, Some(3));
+        assert_eq!(
+            storage
+                .chain_info_storage
+                .get_compressed_block_number()
+                .unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            storage
+                .chain_info_storage
+                .get_compression_format_version()
+                .unwrap(),
+            Some(COMPRESSION_FORMAT_VERSION)
+        );
+        (blocks, CompressionDictionary::new(dictionary_data))
+    };
+
+    // the dictionary is lost by the restart, and reloaded when the storage is opened.
+    dictionary.unregister();
+    let storage = open().unwrap();
+    for (block, txn_info) in &blocks[..4] {
+        assert_eq!(storage.get_block(block.id()).unwrap().as_ref(), Some(block));
+        assert_eq!(
+            storage
+                .get_transaction_info(txn_info.id())
+                .unwrap()
+                .as_ref(),
+            Some(txn_info)
+        );
+    }
+    // continue from the persisted progress, up to the number.
+    let report = storage.compress_blocks(5, 4).unwrap();
+    assert_eq!(report.blocks, 2);
+    assert_eq!(report.records, 2 * 3);
+    assert_eq!(report.compressed_to, Some(5));
+    let report = storage.compress_blocks(5, 4).unwrap();
+    assert_eq!(report.blocks, 0);
+    assert_eq!(report.compressed_to, Some(5));
+    for (block, txn_info) in &blocks {
+        assert_eq!(storage.get_block(block.id()).unwrap().as_ref(), Some(block));
+        assert_eq!(
+            storage
+                .get_transaction_info(txn_info.id())
+                .unwrap()
+                .as_ref(),
+            Some(txn_info)
+        );
+    }
+
+    // the storage compressed by a newer format is refused.
+    storage
+        .chain_info_storage
+        .save_compression_format_version(COMPRESSION_FORMAT_VERSION + 1)
+        .unwrap();
+    drop(storage);
+    assert!(open().is_err());
+    dictionary.unregister();
+}
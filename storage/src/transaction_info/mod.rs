@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::compression::decompress_value;
 use crate::define_storage;
 use crate::storage::{CodecKVStore, CodecWriteBatch, ValueCodec};
 use crate::TRANSACTION_INFO_HASH_PREFIX_NAME;
@@ -30,7 +31,7 @@ impl ValueCodec for BlockTransactionInfo {
     }
 
     fn decode_value(data: &[u8]) -> Result<Self> {
-        Self::decode(data)
+        Self::decode(decompress_value(data)?.as_ref())
     }
 }
 
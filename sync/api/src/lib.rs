@@ -38,6 +38,11 @@ impl PeerNewBlock {
     }
 }
 
+/// The response is sent after the block is connected or rejected.
+impl ServiceRequest for PeerNewBlock {
+    type Response = ();
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SyncNotify {
     ClosePeerMsg(PeerId),
@@ -112,3 +117,28 @@ impl From<Option<Vec<(PeerId, u64)>>> for PeerScoreResponse {
         Self { peers }
     }
 }
+
+/// A reorg deeper than the max reorg depth, it is halted until the operator accepts it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PendingReorg {
+    /// The head of the branch which would be the new main chain.
+    pub branch_head: BlockIdAndNumber,
+    pub branch_total_difficulty: U256,
+    pub main_head: BlockIdAndNumber,
+    pub main_total_difficulty: U256,
+    /// The common ancestor of the branch and the main chain.
+    pub ancestor: BlockIdAndNumber,
+    /// The count of the main chain blocks the reorg retracts.
+    pub depth: u64,
+}
+
+/// Accept the pending reorg and switch the main chain to the branch,
+/// if the `branch_head` is given, it must be the head of the pending reorg.
+#[derive(Debug, Clone)]
+pub struct AcceptReorgRequest {
+    pub branch_head: Option<HashValue>,
+}
+
+impl ServiceRequest for AcceptReorgRequest {
+    type Response = Result<PendingReorg>;
+}
@@ -10,9 +10,11 @@ use logger::prelude::*;
 use network::NetworkServiceRef;
 use network_api::PeerProvider;
 use starcoin_chain_api::{ConnectBlockError, WriteableChainService};
-use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
+};
 use starcoin_storage::{BlockStore, Storage};
-use starcoin_sync_api::PeerNewBlock;
+use starcoin_sync_api::{AcceptReorgRequest, PeerNewBlock, PendingReorg};
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::{MinedBlock, SyncStatusChangeEvent};
 use std::sync::Arc;
@@ -107,8 +109,8 @@ impl EventHandler<Self, SyncStatusChangeEvent> for BlockConnectorService {
     }
 }
 
-impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
-    fn handle_event(&mut self, msg: PeerNewBlock, ctx: &mut ServiceContext<Self>) {
+impl ServiceHandler<Self, PeerNewBlock> for BlockConnectorService {
+    fn handle(&mut self, msg: PeerNewBlock, ctx: &mut ServiceContext<Self>) {
         if !self.is_synced() {
             debug!("[connector] Ignore PeerNewBlock event because the node has not been synchronized yet.");
             return;
@@ -164,3 +166,13 @@ impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
         }
     }
 }
+
+impl ServiceHandler<Self, AcceptReorgRequest> for BlockConnectorService {
+    fn handle(
+        &mut self,
+        msg: AcceptReorgRequest,
+        _ctx: &mut ServiceContext<BlockConnectorService>,
+    ) -> Result<PendingReorg> {
+        self.chain_service.accept_reorg(msg.branch_head)
+    }
+}
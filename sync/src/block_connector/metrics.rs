@@ -3,8 +3,8 @@
 
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    default_registry, register_histogram_vec, register_int_gauge, HistogramOpts, HistogramVec,
-    IntGauge, Opts, PrometheusError, UIntCounterVec,
+    default_registry, register_histogram, register_histogram_vec, register_int_gauge, Histogram,
+    HistogramOpts, HistogramVec, IntGauge, Opts, PrometheusError, UIntCounterVec,
 };
 
 const SC_NS: &str = "starcoin";
@@ -18,7 +18,9 @@ pub struct ChainMetrics {
     pub block_connect_count: UIntCounterVec,
     pub exe_block_time: HistogramVec,
     pub rollback_block_size: IntGauge,
+    pub reorg_depth: Histogram,
     pub current_head_number: IntGauge,
+    pub pending_reorg_depth: IntGauge,
 }
 
 impl ChainMetrics {
@@ -38,12 +40,26 @@ impl ChainMetrics {
         )
         .namespace(SC_NS))?;
 
+        let reorg_depth = register_histogram!(HistogramOpts::new(
+            format!("{}{}", PREFIX, "reorg_depth"),
+            "reorg depth, the count of retracted blocks".to_string()
+        )
+        .namespace(SC_NS)
+        .buckets(vec![1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0, 55.0, 89.0]))?;
+
         let current_head_number = register_int_gauge!(Opts::new(
             format!("{}{}", PREFIX, "current_head_number"),
             "current head number".to_string()
         )
         .namespace(SC_NS))?;
 
+        let pending_reorg_depth = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "pending_reorg_depth"),
+            "the depth of the reorg halted by the max reorg depth, 0 if no reorg is pending"
+                .to_string()
+        )
+        .namespace(SC_NS))?;
+
         let block_connect_count = UIntCounterVec::new(
             Opts::new(
                 format!("{}{}", PREFIX, "block_connect_count"),
@@ -58,7 +74,9 @@ impl ChainMetrics {
         Ok(Self {
             exe_block_time,
             rollback_block_size,
+            reorg_depth,
             current_head_number,
+            pending_reorg_depth,
             block_connect_count,
         })
     }
@@ -7,6 +7,7 @@ use consensus::Consensus;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_chain_service::WriteableChainService;
+use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis as StarcoinGenesis;
 use starcoin_service_registry::bus::BusService;
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
@@ -22,7 +23,16 @@ pub async fn create_writeable_block_chain() -> (
     Arc<NodeConfig>,
     Arc<dyn Store>,
 ) {
-    let node_config = NodeConfig::random_for_test();
+    create_writeable_block_chain_with_config(NodeConfig::random_for_test()).await
+}
+
+pub async fn create_writeable_block_chain_with_config(
+    node_config: NodeConfig,
+) -> (
+    WriteBlockChainService<MockTxPoolService>,
+    Arc<NodeConfig>,
+    Arc<dyn Store>,
+) {
     let node_config = Arc::new(node_config);
 
     let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
@@ -217,3 +227,52 @@ async fn test_block_chain_switch_main() {
         2 * times
     );
 }
+
+#[stest::test]
+async fn test_block_chain_reorg_depth_guard() {
+    let times = 10;
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.sync.set_max_reorg_depth(3);
+    let (mut writeable_block_chain_service, node_config, _) =
+        create_writeable_block_chain_with_config(node_config).await;
+    let net = node_config.net();
+    gen_blocks(
+        times,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let main_head = writeable_block_chain_service.get_main().current_header();
+
+    // the heavier branch retracts 5 blocks, deeper than the max reorg depth.
+    gen_fork_block_chain(5, node_config, times, &mut writeable_block_chain_service);
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        main_head.id()
+    );
+    let pending_reorg = writeable_block_chain_service
+        .pending_reorg()
+        .cloned()
+        .expect("the reorg should be halted.");
+    assert_eq!(pending_reorg.depth, 5);
+    assert_eq!(pending_reorg.ancestor.number, 5);
+    assert_eq!(pending_reorg.main_head.id, main_head.id());
+
+    assert!(writeable_block_chain_service
+        .accept_reorg(Some(HashValue::random()))
+        .is_err());
+    writeable_block_chain_service
+        .accept_reorg(Some(pending_reorg.branch_head.id))
+        .unwrap();
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        pending_reorg.branch_head.id
+    );
+    assert!(writeable_block_chain_service.pending_reorg().is_none());
+    assert!(writeable_block_chain_service.accept_reorg(None).is_err());
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::metrics::WRITE_BLOCK_CHAIN_METRICS;
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use config::NodeConfig;
 use logger::prelude::*;
 use starcoin_chain::BlockChain;
@@ -11,11 +11,13 @@ use starcoin_crypto::HashValue;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
 use starcoin_storage::Store;
+use starcoin_sync_api::PendingReorg;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::{
-    block::{Block, BlockHeader, ExecutedBlock},
+    block::{Block, BlockHeader, BlockIdAndNumber, ExecutedBlock},
     startup_info::StartupInfo,
     system_events::{NewBranch, NewHeadBlock},
+    U256,
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::sync::Arc;
@@ -32,6 +34,7 @@ where
     storage: Arc<dyn Store>,
     txpool: P,
     bus: ServiceRef<BusService>,
+    pending_reorg: Option<PendingReorg>,
 }
 
 impl<P> WriteableChainService for WriteBlockChainService<P>
@@ -63,6 +66,7 @@ where
             storage,
             txpool,
             bus,
+            pending_reorg: None,
         })
     }
 
@@ -106,7 +110,53 @@ where
         &self.main
     }
 
+    /// The reorg halted by the max reorg depth, which is waiting for the operator to accept it.
+    pub fn pending_reorg(&self) -> Option<&PendingReorg> {
+        self.pending_reorg.as_ref()
+    }
+
+    /// Switch the main chain to the branch of the pending reorg, which is halted by the max reorg depth.
+    pub fn accept_reorg(&mut self, branch_head: Option<HashValue>) -> Result<PendingReorg> {
+        let pending_reorg = self
+            .pending_reorg
+            .clone()
+            .ok_or_else(|| format_err!("No reorg is pending."))?;
+        if let Some(branch_head) = branch_head {
+            ensure!(
+                branch_head == pending_reorg.branch_head.id,
+                "The branch head of the pending reorg is {}, not {}.",
+                pending_reorg.branch_head.id,
+                branch_head
+            );
+        }
+        let net = self.config.net();
+        let branch = BlockChain::new(
+            net.time_service(),
+            pending_reorg.branch_head.id,
+            self.storage.clone(),
+        )?;
+        if branch.get_total_difficulty()? <= self.main.get_total_difficulty()? {
+            self.clear_pending_reorg();
+            return Err(format_err!(
+                "The main chain is not lighter than the branch {} now, the reorg is dropped.",
+                pending_reorg.branch_head.id
+            ));
+        }
+        warn!(
+            "[chain] Accept the reorg of depth {} to branch {}.",
+            pending_reorg.depth, pending_reorg.branch_head.id
+        );
+        self.switch_head(branch, true)?;
+        Ok(pending_reorg)
+    }
+
     pub fn select_head(&mut self, new_branch: BlockChain) -> Result<()> {
+        self.switch_head(new_branch, false)
+    }
+
+    /// Select the head by the total difficulty, the reorg deeper than the max reorg depth is halted
+    /// unless it is accepted.
+    fn switch_head(&mut self, new_branch: BlockChain, reorg_accepted: bool) -> Result<()> {
         let block = new_branch.head_block();
         let block_header = block.header().clone();
         let main_total_difficulty = self.main.get_total_difficulty()?;
@@ -124,6 +174,13 @@ where
                 } else {
                     (1, vec![block], 0, vec![])
                 };
+            if let Some(max_reorg_depth) = self.config.sync.max_reorg_depth() {
+                if retracted_count > max_reorg_depth && !reorg_accepted {
+                    self.halt_reorg(&new_branch, branch_total_difficulty, retracted_count)?;
+                    self.broadcast_new_branch(executed_block);
+                    return Ok(());
+                }
+            }
             self.main = new_branch;
 
             self.do_new_head(
@@ -151,10 +208,23 @@ where
         debug_assert!(!enacted_blocks.is_empty());
         debug_assert_eq!(enacted_blocks.last().unwrap(), executed_block.block());
         self.update_startup_info(executed_block.header())?;
+        if let Some(pending_reorg) = self.pending_reorg.as_ref() {
+            if executed_block.block_info().total_difficulty >= pending_reorg.branch_total_difficulty
+            {
+                info!(
+                    "[chain] The pending reorg to branch {} is dropped, the main chain is not lighter than it now.",
+                    pending_reorg.branch_head.id
+                );
+                self.clear_pending_reorg();
+            }
+        }
         if retracted_count > 0 {
             WRITE_BLOCK_CHAIN_METRICS
                 .rollback_block_size
                 .set(retracted_count as i64);
+            WRITE_BLOCK_CHAIN_METRICS
+                .reorg_depth
+                .observe(retracted_count as f64);
         }
         self.commit_2_txpool(enacted_blocks, retracted_blocks);
         WRITE_BLOCK_CHAIN_METRICS
@@ -170,6 +240,60 @@ where
         Ok(())
     }
 
+    fn halt_reorg(
+        &mut self,
+        new_branch: &BlockChain,
+        branch_total_difficulty: U256,
+        depth: u64,
+    ) -> Result<()> {
+        let branch_head = new_branch.current_header();
+        if self
+            .pending_reorg
+            .as_ref()
+            .map(|pending_reorg| pending_reorg.branch_head.id == branch_head.id())
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+        let main_head = self.main.current_header();
+        let ancestor_number = main_head.number().saturating_sub(depth);
+        let ancestor = self
+            .main
+            .get_header_by_number(ancestor_number)?
+            .ok_or_else(|| format_err!("Can not find main block by number {}.", ancestor_number))?;
+        let pending_reorg = PendingReorg {
+            branch_head: BlockIdAndNumber::new(branch_head.id(), branch_head.number()),
+            branch_total_difficulty,
+            main_head: BlockIdAndNumber::new(main_head.id(), main_head.number()),
+            main_total_difficulty: self.main.get_total_difficulty()?,
+            ancestor: BlockIdAndNumber::new(ancestor.id(), ancestor.number()),
+            depth,
+        };
+        error!(
+            "[chain] Refuse to reorg {} blocks, deeper than the max reorg depth {:?}, keep the main chain at {}. \
+            The heavier branch head is {}, forked at {}, accept it by `node admin accept-reorg` if it is expected.",
+            depth,
+            self.config.sync.max_reorg_depth(),
+            pending_reorg.main_head.number,
+            pending_reorg.branch_head.id,
+            pending_reorg.ancestor.number,
+        );
+        WRITE_BLOCK_CHAIN_METRICS
+            .block_connect_count
+            .with_label_values(&["reorg_halted"])
+            .inc();
+        WRITE_BLOCK_CHAIN_METRICS
+            .pending_reorg_depth
+            .set(depth as i64);
+        self.pending_reorg = Some(pending_reorg);
+        Ok(())
+    }
+
+    fn clear_pending_reorg(&mut self) {
+        self.pending_reorg = None;
+        WRITE_BLOCK_CHAIN_METRICS.pending_reorg_depth.set(0);
+    }
+
     fn is_main_head(&self, parent_id: &HashValue) -> bool {
         parent_id == &self.startup_info.main
     }
@@ -192,6 +192,14 @@ impl SyncService {
                     self_ref.clone(),
                     network.clone(),
                     config.sync.max_retry_times(),
+                    config.sync.execution_concurrency(),
+                    config.sync.state_prefetch(),
+                    config
+                        .sync
+                        .checkpoints()
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
                 )?;
 
                 self_ref.notify(SyncBeginEvent {
@@ -275,6 +283,13 @@ impl SyncService {
         Ok(())
     }
 
+    fn broadcast_sync_status(&self, ctx: &mut ServiceContext<Self>) {
+        SYNC_METRICS
+            .sync_peer_lag
+            .set(self.sync_status.lag() as i64);
+        ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+    }
+
     fn task_handle(&self) -> Option<&SyncTaskHandle> {
         match &self.stage {
             SyncStage::Synchronizing(handle) => Some(handle),
@@ -409,7 +424,7 @@ impl EventHandler<Self, SyncBeginEvent> for SyncService {
                         BlockIdAndNumber::new(target.target_id.id(), target.target_id.number());
                     self.sync_status
                         .sync_begin(target_id_number, target.block_info.total_difficulty);
-                    ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+                    self.broadcast_sync_status(ctx);
                 }
             }
             SyncStage::Synchronizing(previous_handle) => {
@@ -464,7 +479,7 @@ impl EventHandler<Self, SystemStarted> for SyncService {
         // change from prepare to Synchronized
         self.sync_status.sync_done();
         ctx.notify(CheckSyncEvent::default());
-        ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+        self.broadcast_sync_status(ctx);
     }
 }
 
@@ -489,7 +504,7 @@ impl EventHandler<Self, SyncDoneEvent> for SyncService {
                     )
                 }
                 self.sync_status.sync_done();
-                ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+                self.broadcast_sync_status(ctx);
                 // check sync again
                 //TODO do not broadcast SyncDone, if node still not synchronized after check sync.
                 ctx.notify(CheckSyncEvent::default());
@@ -497,7 +512,7 @@ impl EventHandler<Self, SyncDoneEvent> for SyncService {
             SyncStage::Canceling => {
                 //continue
                 self.sync_status.sync_done();
-                ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+                self.broadcast_sync_status(ctx);
             }
         }
     }
@@ -510,7 +525,7 @@ impl EventHandler<Self, NewHeadBlock> for SyncService {
             block.header().clone(),
             block.block_info.clone(),
         )) {
-            ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+            self.broadcast_sync_status(ctx);
         }
     }
 }
@@ -1,7 +1,7 @@
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    default_registry, register_histogram_vec, HistogramOpts, HistogramVec, Opts, PrometheusError,
-    UIntCounterVec,
+    default_registry, register_histogram_vec, register_int_gauge, HistogramOpts, HistogramVec,
+    IntGauge, Opts, PrometheusError, UIntCounterVec,
 };
 
 const SC_NS: &str = "starcoin";
@@ -22,6 +22,7 @@ pub struct SyncMetrics {
     pub sync_apply_block_time: HistogramVec,
     pub sync_times: UIntCounterVec,
     pub sync_break_times: UIntCounterVec,
+    pub sync_peer_lag: IntGauge,
 }
 
 impl SyncMetrics {
@@ -59,6 +60,11 @@ impl SyncMetrics {
             .namespace(SC_NS),
             &["type"],
         )?;
+        let sync_peer_lag = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "sync_peer_lag"),
+            "block number lag behind the sync target of the best peer".to_string()
+        )
+        .namespace(SC_NS))?;
         default_registry().register(Box::new(sync_times.clone()))?;
         default_registry().register(Box::new(sync_break_times.clone()))?;
 
@@ -67,6 +73,7 @@ impl SyncMetrics {
             sync_apply_block_time,
             sync_times,
             sync_break_times,
+            sync_peer_lag,
         })
     }
 }
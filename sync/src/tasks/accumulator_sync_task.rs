@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tasks::BlockIdFetcher;
+use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::{ensure, format_err, Result};
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -10,8 +11,9 @@ use starcoin_accumulator::accumulator_info::AccumulatorInfo;
 use starcoin_accumulator::{Accumulator, AccumulatorTreeStore, MerkleAccumulator};
 use starcoin_crypto::HashValue;
 use starcoin_types::block::{BlockIdAndNumber, BlockNumber};
+use starcoin_types::peer_info::PeerId;
 use std::sync::Arc;
-use stream_task::{CollectorState, TaskResultCollector, TaskState};
+use stream_task::{CollectorState, TaskError, TaskResultCollector, TaskState};
 
 #[derive(Clone)]
 pub struct BlockAccumulatorSyncTask {
@@ -91,6 +93,10 @@ pub struct AccumulatorCollector {
     accumulator: MerkleAccumulator,
     ancestor: BlockIdAndNumber,
     target: AccumulatorInfo,
+    // trusted blocks, the collected block ids must match them.
+    checkpoints: Vec<BlockIdAndNumber>,
+    // the peers which provide the block ids.
+    peers: Vec<PeerId>,
 }
 
 impl AccumulatorCollector {
@@ -105,14 +111,45 @@ impl AccumulatorCollector {
             accumulator,
             ancestor,
             target,
+            checkpoints: vec![],
+            peers: vec![],
         }
     }
+
+    /// Anchor the collected block ids to the `checkpoints`,
+    /// the `peers` are reported if the block ids mismatch with the checkpoints.
+    pub fn with_checkpoints(
+        mut self,
+        checkpoints: Vec<BlockIdAndNumber>,
+        peers: Vec<PeerId>,
+    ) -> Self {
+        self.checkpoints = checkpoints;
+        self.peers = peers;
+        self
+    }
 }
 
 impl TaskResultCollector<HashValue> for AccumulatorCollector {
     type Output = (BlockIdAndNumber, MerkleAccumulator);
 
     fn collect(&mut self, item: HashValue) -> Result<CollectorState> {
+        // the leaf index of the block accumulator is the block number.
+        let number = self.accumulator.num_leaves();
+        if let Some(checkpoint) = self.checkpoints.iter().find(|c| c.number == number) {
+            if checkpoint.id != item {
+                return Err(TaskError::BreakError(
+                    RpcVerifyError::new_with_peers(
+                        self.peers.clone(),
+                        format!(
+                            "Block id {} at number {} mismatch with the checkpoint {}",
+                            item, number, checkpoint.id
+                        ),
+                    )
+                    .into(),
+                )
+                .into());
+            }
+        }
         self.accumulator.append(&[item])?;
         self.accumulator.flush()?;
         if self.accumulator.num_leaves() == self.target.num_leaves {
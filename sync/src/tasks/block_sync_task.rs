@@ -12,11 +12,12 @@ use network_api::PeerProvider;
 use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::{verifier::BasicVerifier, BlockChain};
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, ExecutedBlock};
+use starcoin_crypto::HashValue;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use stream_task::{CollectorState, TaskError, TaskResultCollector, TaskState};
 
@@ -187,6 +188,8 @@ pub struct BlockCollector<N, H> {
     event_handle: H,
     peer_provider: N,
     skip_pow_verify: bool,
+    // the blocks whose headers are linked to a checkpoint, so skip their pow verify.
+    trusted_ids: HashSet<HashValue>,
 }
 
 impl<N, H> BlockCollector<N, H>
@@ -209,9 +212,17 @@ where
             event_handle,
             peer_provider,
             skip_pow_verify,
+            trusted_ids: HashSet::new(),
         }
     }
 
+    /// The blocks in `trusted_ids` are linked to a checkpoint by their headers,
+    /// their pow is not verified again.
+    pub fn with_trusted_ids(mut self, trusted_ids: HashSet<HashValue>) -> Self {
+        self.trusted_ids = trusted_ids;
+        self
+    }
+
     #[cfg(test)]
     pub fn apply_block_for_test(&mut self, block: Block) -> Result<()> {
         self.apply_block(block, None)
@@ -222,7 +233,8 @@ where
             .sync_apply_block_time
             .with_label_values(&["time"])
             .start_timer();
-        if let Err(err) = if self.skip_pow_verify {
+        let trusted = self.trusted_ids.contains(&block.id());
+        if let Err(err) = if self.skip_pow_verify || trusted {
             self.chain
                 .apply_with_verifier::<BasicVerifier>(block.clone())
         } else {
@@ -1,17 +1,25 @@
 use crate::tasks::{
     AccumulatorCollector, BlockAccumulatorSyncTask, BlockCollector, BlockConnectedEventHandle,
-    BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
+    BlockFetcher, BlockHeaderFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
 };
-use anyhow::format_err;
+use crate::verified_rpc_client::RpcVerifyError;
+use anyhow::{format_err, Result};
+use futures_timer::Delay;
+use logger::prelude::*;
 use network_api::PeerProvider;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_chain::BlockChain;
+use starcoin_crypto::HashValue;
+use starcoin_network_rpc_api::MAX_BLOCK_REQUEST_SIZE;
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{BlockIdAndNumber, BlockInfo};
+use starcoin_types::peer_info::PeerId;
 use starcoin_types::time::TimeService;
 use std::cmp::min;
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 use stream_task::{
     CustomErrorHandle, Generator, TaskError, TaskEventHandle, TaskGenerator, TaskHandle, TaskState,
 };
@@ -19,7 +27,7 @@ use stream_task::{
 pub struct InnerSyncTask<H, F, N>
 where
     H: BlockConnectedEventHandle + Sync + 'static,
-    F: BlockIdFetcher + BlockFetcher + PeerOperator + 'static,
+    F: BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + PeerOperator + 'static,
     N: PeerProvider + Clone + 'static,
 {
     ancestor: BlockIdAndNumber,
@@ -31,12 +39,13 @@ where
     time_service: Arc<dyn TimeService>,
     peer_provider: N,
     custom_error_handle: Arc<dyn CustomErrorHandle>,
+    checkpoints: Vec<BlockIdAndNumber>,
 }
 
 impl<H, F, N> InnerSyncTask<H, F, N>
 where
     H: BlockConnectedEventHandle + Sync + 'static,
-    F: BlockIdFetcher + BlockFetcher + PeerOperator + 'static,
+    F: BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + PeerOperator + 'static,
     N: PeerProvider + Clone + 'static,
 {
     pub fn new(
@@ -49,6 +58,7 @@ where
         time_service: Arc<dyn TimeService>,
         peer_provider: N,
         custom_error_handle: Arc<dyn CustomErrorHandle>,
+        checkpoints: Vec<BlockIdAndNumber>,
     ) -> Self {
         Self {
             ancestor,
@@ -60,6 +70,7 @@ where
             time_service,
             peer_provider,
             custom_error_handle,
+            checkpoints,
         }
     }
 
@@ -80,10 +91,37 @@ where
         max_retry_times: u64,
         delay_milliseconds_on_error: u64,
         skip_pow_verify_when_sync: bool,
+        execution_concurrency: usize,
+        state_prefetch: bool,
     ) -> Result<(BlockChain, TaskHandle), TaskError> {
         let buffer_size = self.target.peers.len();
 
         let ancestor_block_info = self.ancestor_block_info().map_err(TaskError::BreakError)?;
+        // only the blocks whose headers are linked to the last checkpoint in the target skip
+        // the pow verify, the block ids in the accumulator come from the peers and are untrusted.
+        let target_number = self.target.target_id.number();
+        let ancestor_number = self.ancestor.number;
+        let checkpoint = self
+            .checkpoints
+            .iter()
+            .filter(|checkpoint| {
+                checkpoint.number > ancestor_number && checkpoint.number <= target_number
+            })
+            .max_by_key(|checkpoint| checkpoint.number)
+            .copied();
+        let trusted_ids = match checkpoint {
+            Some(checkpoint) => verify_checkpoint_headers(
+                self.fetcher.as_ref(),
+                self.ancestor,
+                checkpoint,
+                self.target.peers.clone(),
+                max_retry_times,
+                delay_milliseconds_on_error,
+            )
+            .await
+            .map_err(TaskError::BreakError)?,
+            None => HashSet::new(),
+        };
         let accumulator_sync_task = BlockAccumulatorSyncTask::new(
             // start_number is include, so start from ancestor.number + 1
             self.ancestor.number.saturating_add(1),
@@ -109,7 +147,8 @@ where
                 self.ancestor,
                 ancestor_block_info.clone().block_accumulator_info,
                 self.target.block_info.block_accumulator_info.clone(),
-            ),
+            )
+            .with_checkpoints(self.checkpoints.clone(), self.target.peers.clone()),
             self.event_handle.clone(),
             self.custom_error_handle.clone(),
         )
@@ -125,8 +164,10 @@ where
                 self.storage.clone(),
                 1,
             );
-            let chain =
+            let mut chain =
                 BlockChain::new(self.time_service.clone(), ancestor.id, self.storage.clone())?;
+            chain.set_execution_concurrency(execution_concurrency);
+            chain.set_state_prefetch(state_prefetch);
             let block_collector = BlockCollector::new_with_handle(
                 current_block_info.clone(),
                 self.target.clone(),
@@ -134,7 +175,8 @@ where
                 self.block_event_handle.clone(),
                 self.peer_provider.clone(),
                 skip_pow_verify_when_sync,
-            );
+            )
+            .with_trusted_ids(trusted_ids);
             Ok(TaskGenerator::new(
                 block_sync_task,
                 buffer_size,
@@ -153,3 +195,72 @@ where
         Ok((block_chain, handle))
     }
 }
+
+/// Fetch the headers backward from the `checkpoint` to the `ancestor`, every header must be
+/// the parent of the header after it, so the returned block ids are anchored by the checkpoint.
+pub async fn verify_checkpoint_headers<F>(
+    fetcher: &F,
+    ancestor: BlockIdAndNumber,
+    checkpoint: BlockIdAndNumber,
+    peers: Vec<PeerId>,
+    max_retry_times: u64,
+    delay_milliseconds_on_error: u64,
+) -> Result<HashSet<HashValue>>
+where
+    F: BlockHeaderFetcher,
+{
+    let mut trusted_ids = HashSet::new();
+    let mut expect_id = checkpoint.id;
+    let mut number = checkpoint.number;
+    let mut retry_times = 0u64;
+    while number > ancestor.number {
+        let max_size = min(
+            number.saturating_sub(ancestor.number),
+            MAX_BLOCK_REQUEST_SIZE,
+        );
+        let headers = match fetcher.fetch_block_headers(number, max_size).await {
+            Ok(headers) if !headers.is_empty() => headers,
+            Ok(_) | Err(_) if retry_times < max_retry_times => {
+                retry_times = retry_times.saturating_add(1);
+                warn!(
+                    "[sync] Fetch block headers from {} failed, retry {} times",
+                    number, retry_times
+                );
+                Delay::new(Duration::from_millis(delay_milliseconds_on_error)).await;
+                continue;
+            }
+            Ok(_) => return Err(format_err!("Fetch block headers from {} failed", number)),
+            Err(err) => return Err(err),
+        };
+        retry_times = 0;
+        for header in headers.into_iter().take(max_size as usize) {
+            let id = header.id();
+            if header.number() != number || id != expect_id {
+                return Err(RpcVerifyError::new_with_peers(
+                    peers,
+                    format!(
+                        "Block header {} at number {} is not linked to the checkpoint {}",
+                        id,
+                        header.number(),
+                        checkpoint.id
+                    ),
+                )
+                .into());
+            }
+            trusted_ids.insert(id);
+            expect_id = header.parent_hash();
+            number = number.saturating_sub(1);
+        }
+    }
+    if expect_id != ancestor.id {
+        return Err(RpcVerifyError::new_with_peers(
+            peers,
+            format!(
+                "The checkpoint {} is not linked to the ancestor {}",
+                checkpoint.id, ancestor.id
+            ),
+        )
+        .into());
+    }
+    Ok(trusted_ids)
+}
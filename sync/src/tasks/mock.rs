@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tasks::{
-    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator, SyncFetcher,
+    BlockConnectedEvent, BlockFetcher, BlockHeaderFetcher, BlockIdFetcher, BlockInfoFetcher,
+    PeerOperator, SyncFetcher,
 };
 use anyhow::{format_err, Context, Result};
 use async_std::task::JoinHandle;
@@ -22,7 +23,7 @@ use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_network_rpc_api::RPC_INFO;
 use starcoin_sync_api::SyncTarget;
-use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use std::sync::Arc;
 use std::time::Duration;
@@ -327,6 +328,29 @@ impl BlockFetcher for SyncNodeMocker {
     }
 }
 
+impl BlockHeaderFetcher for SyncNodeMocker {
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<BlockHeader>>> {
+        let result: Result<Vec<BlockHeader>> = (0..max_size)
+            .filter_map(|offset| start_number.checked_sub(offset))
+            .map(|number| {
+                self.chain()
+                    .get_header_by_number(number)?
+                    .ok_or_else(|| format_err!("Can not find block header by number: {}", number))
+            })
+            .collect();
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            result
+        }
+        .boxed()
+    }
+}
+
 impl BlockInfoFetcher for SyncNodeMocker {
     fn fetch_block_infos(
         &self,
@@ -15,10 +15,11 @@ use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::MerkleAccumulator;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_crypto::HashValue;
+use starcoin_network_rpc_api::GetBlockHeadersByNumber;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceRef};
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
-use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::U256;
@@ -32,7 +33,9 @@ use stream_task::{
     TaskHandle,
 };
 
-pub trait SyncFetcher: PeerOperator + BlockIdFetcher + BlockFetcher + BlockInfoFetcher {
+pub trait SyncFetcher:
+    PeerOperator + BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + BlockInfoFetcher
+{
     fn get_best_target(&self, min_difficulty: U256) -> Result<Option<SyncTarget>> {
         if let Some(best_peers) = self.peer_selector().bests(min_difficulty) {
             //TODO fast verify best peers by accumulator
@@ -317,6 +320,53 @@ impl BlockFetcher for VerifiedRpcClient {
     }
 }
 
+pub trait BlockHeaderFetcher: Send + Sync {
+    /// Fetch at most `max_size` main chain headers, backward from `start_number`.
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<BlockHeader>>>;
+}
+
+impl<T> BlockHeaderFetcher for Arc<T>
+where
+    T: BlockHeaderFetcher,
+{
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<BlockHeader>>> {
+        BlockHeaderFetcher::fetch_block_headers(self.as_ref(), start_number, max_size)
+    }
+}
+
+impl BlockHeaderFetcher for VerifiedRpcClient {
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<BlockHeader>>> {
+        self.get_headers_by_number(GetBlockHeadersByNumber::new(start_number, 1, max_size))
+            .and_then(|headers| async move {
+                headers
+                    .into_iter()
+                    .map(|header| {
+                        header.ok_or_else(|| {
+                            format_err!(
+                                "Get block header by number from {} failed, remote node return None",
+                                start_number
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
 pub trait BlockInfoFetcher: Send + Sync {
     fn fetch_block_infos(
         &self,
@@ -526,6 +576,9 @@ pub fn full_sync_task<H, A, F, N>(
     ancestor_event_handle: A,
     peer_provider: N,
     max_retry_times: u64,
+    execution_concurrency: usize,
+    state_prefetch: bool,
+    checkpoints: Vec<BlockIdAndNumber>,
 ) -> Result<(
     BoxFuture<'static, Result<BlockChain, TaskError>>,
     TaskHandle,
@@ -631,6 +684,7 @@ where
                 time_service.clone(),
                 peer_provider.clone(),
                 ext_error_handle.clone(),
+                checkpoints.clone(),
             );
             let start_now = Instant::now();
             let (block_chain, _) = inner
@@ -639,6 +693,8 @@ where
                     max_retry_times,
                     delay_milliseconds_on_error,
                     skip_pow_verify,
+                    execution_concurrency,
+                    state_prefetch,
                 )
                 .await?;
             let total_time = Instant::now()
@@ -3,6 +3,7 @@
 
 #![allow(clippy::integer_arithmetic)]
 use crate::tasks::block_sync_task::SyncBlockData;
+use crate::tasks::inner_sync_task::verify_checkpoint_headers;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
     full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
@@ -71,6 +72,9 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -100,6 +104,9 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -148,6 +155,9 @@ pub async fn test_sync_invalid_target() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let _join_handle = node2.process_block_connect_event(receiver_1).await;
     let sync_result = sync_task.await;
@@ -229,6 +239,9 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -260,6 +273,9 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -307,6 +323,9 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -359,6 +378,9 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -392,6 +414,9 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
 
     let join_handle = node2.process_block_connect_event(receiver).await;
@@ -442,6 +467,9 @@ pub async fn test_full_sync_cancel() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -511,6 +539,86 @@ async fn test_accumulator_sync_by_stream_task() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+async fn test_accumulator_sync_with_checkpoints() -> Result<()> {
+    let store = Arc::new(MockAccumulatorStore::new());
+    let accumulator = MerkleAccumulator::new_empty(store.clone());
+    accumulator.append(generate_hash(10).as_slice())?;
+    accumulator.flush()?;
+    let info0 = accumulator.get_info();
+    let ids = generate_hash(20);
+    accumulator.append(ids.as_slice())?;
+    accumulator.flush()?;
+    let info1 = accumulator.get_info();
+    let fetcher = MockBlockIdFetcher::new(Arc::new(accumulator));
+    let ancestor = BlockIdAndNumber::new(HashValue::random(), info0.num_leaves - 1);
+
+    let sync_with_checkpoint = |checkpoint: BlockIdAndNumber| {
+        let task_state =
+            BlockAccumulatorSyncTask::new(info0.num_leaves, info1.clone(), fetcher.clone(), 7)
+                .unwrap();
+        let store2 = MockAccumulatorStore::copy_from(store.as_ref());
+        let collector =
+            AccumulatorCollector::new(Arc::new(store2), ancestor, info0.clone(), info1.clone())
+                .with_checkpoints(vec![checkpoint], vec![PeerId::random()]);
+        TaskGenerator::new(
+            task_state,
+            5,
+            3,
+            1,
+            collector,
+            Arc::new(TaskEventCounterHandle::new()),
+            Arc::new(DefaultCustomErrorHandle),
+        )
+        .generate()
+    };
+
+    // the block 15 is the 6th block after the ancestor.
+    let (_, synced) = sync_with_checkpoint(BlockIdAndNumber::new(ids[5], 15)).await?;
+    assert_eq!(synced.get_info(), info1);
+
+    let result = sync_with_checkpoint(BlockIdAndNumber::new(HashValue::random(), 15)).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_verify_checkpoint_headers() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node = SyncNodeMocker::new(net, 1, 0)?;
+    node.produce_block(10)?;
+    let chain = node.chain();
+    let block_id = |number| -> Result<HashValue> {
+        Ok(chain
+            .get_header_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block header by number: {}", number))?
+            .id())
+    };
+    let ancestor = BlockIdAndNumber::new(block_id(2)?, 2);
+    let checkpoint = BlockIdAndNumber::new(block_id(7)?, 7);
+    let peers = vec![PeerId::random()];
+
+    let trusted_ids =
+        verify_checkpoint_headers(&node, ancestor, checkpoint, peers.clone(), 3, 1).await?;
+    assert_eq!(trusted_ids.len(), 5);
+    for number in 3..=7 {
+        assert!(trusted_ids.contains(&block_id(number)?));
+    }
+    assert!(!trusted_ids.contains(&block_id(8)?));
+
+    // a checkpoint which is not on the chain of the peer.
+    let fake_checkpoint = BlockIdAndNumber::new(HashValue::random(), 7);
+    let result =
+        verify_checkpoint_headers(&node, ancestor, fake_checkpoint, peers.clone(), 3, 1).await;
+    assert!(result.is_err());
+
+    // an ancestor which is not linked to the checkpoint.
+    let fake_ancestor = BlockIdAndNumber::new(HashValue::random(), 2);
+    let result = verify_checkpoint_headers(&node, fake_ancestor, checkpoint, peers, 3, 1).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_find_ancestor_same_number() -> Result<()> {
     let store = Arc::new(MockAccumulatorStore::new());
@@ -883,6 +991,9 @@ async fn test_net_rpc_err() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        1,
+        false,
+        vec![],
     )?;
     let _join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -0,0 +1,241 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+use starcoin_config::{ChainNetwork, NodeConfig};
+use starcoin_consensus::Consensus;
+use starcoin_crypto::HashValue;
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_executor::{Account, DEFAULT_EXPIRATION_TIME};
+use starcoin_genesis::Genesis;
+use starcoin_state_api::StateReaderExt;
+use starcoin_storage::Storage;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::association_address;
+use starcoin_types::block::{BlockHeader, ExecutedBlock};
+use starcoin_types::transaction::SignedUserTransaction;
+use std::sync::Arc;
+
+/// The initial balance of the accounts funded by `TestChain::fund_account`, in nanoSTC.
+pub const DEFAULT_FUND_AMOUNT: u128 = 1_000_000_000;
+
+/// The head of a `TestChain`, the chain can be restored to it after producing more blocks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChainSnapshot {
+    head: HashValue,
+}
+
+impl ChainSnapshot {
+    pub fn head(&self) -> HashValue {
+        self.head
+    }
+}
+
+/// An in-memory chain started from the genesis of the test network, the blocks are produced
+/// immediately by the block producer helpers instead of mining.
+pub struct TestChain {
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+    chain: BlockChain,
+    miner: AccountInfo,
+}
+
+impl TestChain {
+    pub fn new() -> Result<Self> {
+        Self::new_with_config(Arc::new(NodeConfig::random_for_test()))
+    }
+
+    pub fn new_with_config(config: Arc<NodeConfig>) -> Result<Self> {
+        let (storage, chain_info, _) = Genesis::init_storage_for_test(config.net())?;
+        let chain = BlockChain::new(
+            config.net().time_service(),
+            chain_info.head().id(),
+            storage.clone(),
+        )?;
+        Ok(Self {
+            config,
+            storage,
+            chain,
+            miner: AccountInfo::random(),
+        })
+    }
+
+    pub fn config(&self) -> Arc<NodeConfig> {
+        self.config.clone()
+    }
+
+    pub fn net(&self) -> &ChainNetwork {
+        self.config.net()
+    }
+
+    pub fn storage(&self) -> Arc<Storage> {
+        self.storage.clone()
+    }
+
+    pub fn chain(&self) -> &BlockChain {
+        &self.chain
+    }
+
+    pub fn current_header(&self) -> BlockHeader {
+        self.chain.current_header()
+    }
+
+    /// The author of the produced blocks.
+    pub fn miner(&self) -> &AccountInfo {
+        &self.miner
+    }
+
+    /// Produce a block including the txns, fail if any txn is excluded from the block.
+    pub fn produce_block(&mut self, txns: Vec<SignedUserTransaction>) -> Result<ExecutedBlock> {
+        let (block_template, excluded) = self.chain.create_block_template(
+            *self.miner.address(),
+            Some(self.miner.public_key.authentication_key()),
+            None,
+            txns,
+            vec![],
+            None,
+        )?;
+        ensure!(
+            excluded.discarded_txns.is_empty() && excluded.untouched_txns.is_empty(),
+            "Txns excluded from the block, discarded: {}, untouched: {}",
+            excluded.discarded_txns.len(),
+            excluded.untouched_txns.len()
+        );
+        let block = self
+            .chain
+            .consensus()
+            .create_block(block_template, self.net().time_service().as_ref())?;
+        self.chain.apply(block)
+    }
+
+    /// Produce empty blocks.
+    pub fn produce_blocks(&mut self, count: u64) -> Result<Vec<ExecutedBlock>> {
+        (0..count).map(|_| self.produce_block(vec![])).collect()
+    }
+
+    /// Create a new account funded by the association account in a new block.
+    pub fn fund_account(&mut self, amount: u128) -> Result<Account> {
+        Ok(self
+            .fund_accounts(1, amount)?
+            .pop()
+            .expect("funded one account"))
+    }
+
+    /// Create new accounts funded by the association account in one new block.
+    pub fn fund_accounts(&mut self, count: u64, amount: u128) -> Result<Vec<Account>> {
+        let seq_number = self.sequence_number(association_address())?;
+        let expiration = self.expiration_timestamp_secs();
+        let (accounts, txns): (Vec<_>, Vec<_>) = (0..count)
+            .map(|i| {
+                let account = Account::new();
+                let txn = create_account_txn_sent_as_association(
+                    &account,
+                    seq_number + i,
+                    amount,
+                    expiration,
+                    self.net(),
+                );
+                (account, txn)
+            })
+            .unzip();
+        self.produce_block(txns)?;
+        Ok(accounts)
+    }
+
+    /// Build a signed transfer txn with the next sequence number of the sender on chain.
+    pub fn transfer_txn(
+        &self,
+        sender: &Account,
+        receiver: &Account,
+        amount: u128,
+    ) -> Result<SignedUserTransaction> {
+        Ok(peer_to_peer_txn(
+            sender,
+            receiver,
+            self.sequence_number(*sender.address())?,
+            amount,
+            self.expiration_timestamp_secs(),
+            self.net().chain_id(),
+        ))
+    }
+
+    /// Transfer in a new block.
+    pub fn transfer(
+        &mut self,
+        sender: &Account,
+        receiver: &Account,
+        amount: u128,
+    ) -> Result<ExecutedBlock> {
+        let txn = self.transfer_txn(sender, receiver, amount)?;
+        self.produce_block(vec![txn])
+    }
+
+    /// The STC balance of the address, 0 if the account does not exist.
+    pub fn balance(&self, address: AccountAddress) -> Result<u128> {
+        Ok(self
+            .chain
+            .chain_state_reader()
+            .get_balance(address)?
+            .unwrap_or_default())
+    }
+
+    /// The sequence number of the address, 0 if the account does not exist.
+    pub fn sequence_number(&self, address: AccountAddress) -> Result<u64> {
+        Ok(self
+            .chain
+            .chain_state_reader()
+            .get_account_resource(address)?
+            .map(|resource| resource.sequence_number())
+            .unwrap_or_default())
+    }
+
+    pub fn snapshot(&self) -> ChainSnapshot {
+        ChainSnapshot {
+            head: self.chain.current_header().id(),
+        }
+    }
+
+    /// Restore the chain to the snapshot, the blocks after it stay in the storage but are no
+    /// longer on the chain.
+    pub fn restore(&mut self, snapshot: ChainSnapshot) -> Result<()> {
+        self.chain = BlockChain::new(
+            self.net().time_service(),
+            snapshot.head,
+            self.storage.clone(),
+        )?;
+        Ok(())
+    }
+
+    fn expiration_timestamp_secs(&self) -> u64 {
+        self.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[stest::test]
+    fn test_test_chain() -> Result<()> {
+        let mut chain = TestChain::new()?;
+        let alice = chain.fund_account(DEFAULT_FUND_AMOUNT)?;
+        let bob = chain.fund_account(DEFAULT_FUND_AMOUNT)?;
+        assert_eq!(chain.current_header().number(), 2);
+        assert_eq!(chain.balance(*alice.address())?, DEFAULT_FUND_AMOUNT);
+
+        let snapshot = chain.snapshot();
+        chain.transfer(&alice, &bob, 1000)?;
+        chain.produce_blocks(2)?;
+        assert_eq!(chain.current_header().number(), 5);
+        assert_eq!(chain.sequence_number(*alice.address())?, 1);
+        assert_eq!(chain.balance(*bob.address())?, DEFAULT_FUND_AMOUNT + 1000);
+
+        chain.restore(snapshot)?;
+        assert_eq!(chain.current_header().number(), 2);
+        assert_eq!(chain.sequence_number(*alice.address())?, 0);
+        assert_eq!(chain.balance(*bob.address())?, DEFAULT_FUND_AMOUNT);
+        Ok(())
+    }
+}
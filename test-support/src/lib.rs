@@ -0,0 +1,12 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Utilities for integration tests against real node components: an in-memory chain with
+//! funded accounts and a block producer, and txpool fixtures sharing its storage.
+
+mod chain;
+mod txpool;
+
+pub use chain::{ChainSnapshot, TestChain, DEFAULT_FUND_AMOUNT};
+pub use starcoin_executor::Account;
+pub use txpool::{launch_registry, register_txpool, wait_service_started, TxPoolFixture};
@@ -0,0 +1,131 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::chain::TestChain;
+use anyhow::{bail, Result};
+use starcoin_config::NodeConfig;
+use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::{
+    ActorService, RegistryAsyncService, RegistryService, ServiceRef, ServiceStatus,
+};
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_txpool::{TxPoolActorService, TxPoolService};
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::block::ExecutedBlock;
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::transaction::SignedUserTransaction;
+use std::sync::Arc;
+
+/// Launch a registry with the config, the storage and the bus shared, like the node does.
+pub async fn launch_registry(
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+) -> Result<ServiceRef<RegistryService>> {
+    let registry = RegistryService::launch();
+    registry.put_shared(config).await?;
+    registry.put_shared(storage).await?;
+    let bus = registry.service_ref::<BusService>().await?;
+    registry.put_shared(bus).await?;
+    Ok(registry)
+}
+
+/// Wait the service to be started, the data shared by the service factory is available after it.
+pub async fn wait_service_started<S>(service: &ServiceRef<S>) -> Result<()>
+where
+    S: ActorService,
+{
+    loop {
+        match service.self_status().await {
+            ServiceStatus::Started => return Ok(()),
+            // the status query timeout, the service is still starting.
+            ServiceStatus::Unavailable => continue,
+            status => bail!("Service {} is not started: {:?}", S::service_name(), status),
+        }
+    }
+}
+
+/// Register the txpool service, return after the `TxPoolService` is shared by it.
+pub async fn register_txpool(
+    registry: &ServiceRef<RegistryService>,
+) -> Result<(TxPoolService, ServiceRef<TxPoolActorService>)> {
+    let pool_actor = registry.register::<TxPoolActorService>().await?;
+    wait_service_started(&pool_actor).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+    Ok((txpool, pool_actor))
+}
+
+/// A txpool service started on the storage of a `TestChain`, from its current head.
+pub struct TxPoolFixture {
+    txpool: TxPoolService,
+    pool_actor: ServiceRef<TxPoolActorService>,
+    registry: ServiceRef<RegistryService>,
+}
+
+impl TxPoolFixture {
+    pub async fn start(chain: &TestChain) -> Result<Self> {
+        let storage = chain.storage();
+        storage.save_startup_info(StartupInfo::new(chain.current_header().id()))?;
+        let registry = launch_registry(chain.config(), storage).await?;
+        let (txpool, pool_actor) = register_txpool(&registry).await?;
+        Ok(Self {
+            txpool,
+            pool_actor,
+            registry,
+        })
+    }
+
+    pub fn txpool(&self) -> &TxPoolService {
+        &self.txpool
+    }
+
+    pub fn pool_actor(&self) -> &ServiceRef<TxPoolActorService> {
+        &self.pool_actor
+    }
+
+    pub fn registry(&self) -> &ServiceRef<RegistryService> {
+        &self.registry
+    }
+
+    /// Add the txns to the pool, fail on the first rejected txn.
+    pub fn add_txns(&self, txns: Vec<SignedUserTransaction>) -> Result<()> {
+        for result in self.txpool.add_txns(txns) {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Produce a block of the pending txns on the chain, and notify the pool of the block.
+    pub fn produce_block(&self, chain: &mut TestChain) -> Result<ExecutedBlock> {
+        let now = chain.net().time_service().now_secs();
+        let txns = self.txpool.get_pending_txns(None, Some(now));
+        let executed_block = chain.produce_block(txns)?;
+        self.txpool
+            .chain_new_block(vec![executed_block.block.clone()], vec![])?;
+        Ok(executed_block)
+    }
+
+    pub async fn shutdown(self) -> Result<()> {
+        self.registry.shutdown_system().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::DEFAULT_FUND_AMOUNT;
+
+    #[stest::test]
+    async fn test_txpool_fixture() -> Result<()> {
+        let mut chain = TestChain::new()?;
+        let alice = chain.fund_account(DEFAULT_FUND_AMOUNT)?;
+        let bob = chain.fund_account(DEFAULT_FUND_AMOUNT)?;
+        let fixture = TxPoolFixture::start(&chain).await?;
+        fixture.add_txns(vec![chain.transfer_txn(&alice, &bob, 1000)?])?;
+        assert_eq!(fixture.txpool().status().txn_count, 1);
+
+        let executed_block = fixture.produce_block(&mut chain)?;
+        assert_eq!(executed_block.block.transactions().len(), 1);
+        assert_eq!(chain.balance(*bob.address())?, DEFAULT_FUND_AMOUNT + 1000);
+        fixture.shutdown().await
+    }
+}
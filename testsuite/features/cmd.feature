@@ -94,11 +94,11 @@ Feature: cmd integration test
     Then cmd: "account unlock"
     Then cmd: "dev get_coin"
     Then cmd: "account create -p transfer"
-    Then cmd: "account transfer --blocking -v 10000 -r @$.address@ -k @$.public_key@"
+    Then cmd: "account transfer --blocking --allow-dust -v 10000 -r @$.address@ -k @$.public_key@"
     Then cmd: "chain get_txn @$.txn_hash@"
     Then cmd: "chain get_events @$.transaction_hash@"
     Then cmd: "account create -p transfer"
-    Then cmd: "account transfer --blocking -v 10000 -r @$.receipt_identifier@"
+    Then cmd: "account transfer --blocking --allow-dust -v 10000 -r @$.receipt_identifier@"
     Then cmd: "chain get_txn @$.txn_hash@"
     Then cmd: "chain get_events @$.transaction_hash@"
     Then cmd: "account create -p compat"
@@ -0,0 +1,250 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::SignedUserTransaction;
+use std::collections::{BTreeMap, HashMap};
+
+/// The txns expire within the seconds are counted as expiring soon.
+pub const EXPIRING_SOON_SECS: u64 = 60;
+
+/// The distribution of the gas unit prices.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceStats {
+    pub min: u64,
+    pub max: u64,
+    pub mean: u64,
+    pub p25: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+}
+
+impl GasPriceStats {
+    /// Return `None` if there is no gas price.
+    pub fn new(mut gas_prices: Vec<u64>) -> Option<Self> {
+        if gas_prices.is_empty() {
+            return None;
+        }
+        gas_prices.sort_unstable();
+        let len = gas_prices.len();
+        let percentile = |p: usize| gas_prices[(len - 1) * p / 100];
+        let sum: u128 = gas_prices.iter().map(|gas_price| *gas_price as u128).sum();
+        Some(Self {
+            min: gas_prices[0],
+            max: gas_prices[len - 1],
+            mean: (sum / len as u128) as u64,
+            p25: percentile(25),
+            median: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+        })
+    }
+}
+
+/// The ready txns of a gas unit price.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceBucket {
+    pub gas_price: u64,
+    pub txn_count: usize,
+    /// The sum of the max gas amount of the txns.
+    pub max_gas_amount: u64,
+    /// The sum of the max gas amount of the ready txns of this gas price or higher,
+    /// a new txn of the gas price is packaged after them.
+    pub cumulative_max_gas_amount: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SenderStats {
+    pub sender: AccountAddress,
+    pub txn_count: usize,
+    pub ready_count: usize,
+    pub min_sequence_number: u64,
+    pub max_sequence_number: u64,
+    pub max_gas_price: u64,
+}
+
+/// The snapshot analytics of the txns in the pool.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolAnalytics {
+    pub txn_count: usize,
+    /// The txns can be packaged into the next block.
+    pub ready_count: usize,
+    /// The txns wait for the txns of the lower sequence numbers of the sender.
+    pub future_count: usize,
+    pub sender_count: usize,
+    /// The txns expire within `EXPIRING_SOON_SECS` seconds.
+    pub expiring_count: usize,
+    pub gas_price: Option<GasPriceStats>,
+    pub ready_gas_price: Option<GasPriceStats>,
+    /// The ready txns grouped by the gas price, ordered by the gas price descending.
+    pub gas_price_buckets: Vec<GasPriceBucket>,
+    /// The senders of the most txns.
+    pub top_senders: Vec<SenderStats>,
+}
+
+impl TxPoolAnalytics {
+    /// Analyze the txns, the flag of a txn is true if it is ready.
+    pub fn analyze<'a>(
+        txns: impl IntoIterator<Item = (&'a SignedUserTransaction, bool)>,
+        now_secs: u64,
+        top_senders: usize,
+    ) -> Self {
+        let mut txn_count = 0usize;
+        let mut ready_count = 0usize;
+        let mut expiring_count = 0usize;
+        let mut gas_prices = vec![];
+        let mut ready_gas_prices = vec![];
+        let mut buckets: BTreeMap<u64, (usize, u64)> = BTreeMap::new();
+        let mut senders: HashMap<AccountAddress, SenderStats> = HashMap::new();
+        for (txn, ready) in txns {
+            let gas_price = txn.gas_unit_price();
+            txn_count += 1;
+            gas_prices.push(gas_price);
+            if txn.expiration_timestamp_secs() <= now_secs.saturating_add(EXPIRING_SOON_SECS) {
+                expiring_count += 1;
+            }
+            if ready {
+                ready_count += 1;
+                ready_gas_prices.push(gas_price);
+                let bucket = buckets.entry(gas_price).or_default();
+                bucket.0 += 1;
+                bucket.1 = bucket.1.saturating_add(txn.max_gas_amount());
+            }
+            let sender = txn.sender();
+            let stats = senders.entry(sender).or_insert_with(|| SenderStats {
+                sender,
+                txn_count: 0,
+                ready_count: 0,
+                min_sequence_number: u64::max_value(),
+                max_sequence_number: 0,
+                max_gas_price: 0,
+            });
+            stats.txn_count += 1;
+            if ready {
+                stats.ready_count += 1;
+            }
+            stats.min_sequence_number = stats.min_sequence_number.min(txn.sequence_number());
+            stats.max_sequence_number = stats.max_sequence_number.max(txn.sequence_number());
+            stats.max_gas_price = stats.max_gas_price.max(gas_price);
+        }
+
+        let mut cumulative_max_gas_amount = 0u64;
+        let gas_price_buckets = buckets
+            .into_iter()
+            .rev()
+            .map(|(gas_price, (txn_count, max_gas_amount))| {
+                cumulative_max_gas_amount =
+                    cumulative_max_gas_amount.saturating_add(max_gas_amount);
+                GasPriceBucket {
+                    gas_price,
+                    txn_count,
+                    max_gas_amount,
+                    cumulative_max_gas_amount,
+                }
+            })
+            .collect();
+        let sender_count = senders.len();
+        let mut senders: Vec<SenderStats> = senders.into_iter().map(|(_, stats)| stats).collect();
+        senders.sort_by(|a, b| {
+            b.txn_count
+                .cmp(&a.txn_count)
+                .then_with(|| a.sender.cmp(&b.sender))
+        });
+        senders.truncate(top_senders);
+
+        Self {
+            txn_count,
+            ready_count,
+            future_count: txn_count - ready_count,
+            sender_count,
+            expiring_count,
+            gas_price: GasPriceStats::new(gas_prices),
+            ready_gas_price: GasPriceStats::new(ready_gas_prices),
+            gas_price_buckets,
+            top_senders: senders,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::ed25519::genesis_key_pair;
+    use starcoin_types::genesis_config::ChainId;
+    use starcoin_types::transaction::{RawUserTransaction, Script, TransactionPayload};
+
+    fn txn(
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_price: u64,
+        expiration_timestamp_secs: u64,
+    ) -> SignedUserTransaction {
+        let (private_key, public_key) = genesis_key_pair();
+        RawUserTransaction::new_with_default_gas_token(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            gas_price,
+            expiration_timestamp_secs,
+            ChainId::test(),
+        )
+        .sign(&private_key, public_key)
+        .unwrap()
+        .into_inner()
+    }
+
+    #[test]
+    fn test_analyze() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let txns = vec![
+            (txn(alice, 0, 1, 1000), true),
+            (txn(alice, 1, 3, 1000), true),
+            (txn(alice, 3, 5, 100), false),
+            (txn(bob, 0, 3, 1000), true),
+        ];
+        let analytics =
+            TxPoolAnalytics::analyze(txns.iter().map(|(txn, ready)| (txn, *ready)), 100, 1);
+        assert_eq!(analytics.txn_count, 4);
+        assert_eq!(analytics.ready_count, 3);
+        assert_eq!(analytics.future_count, 1);
+        assert_eq!(analytics.sender_count, 2);
+        assert_eq!(analytics.expiring_count, 1);
+
+        let gas_price = analytics.gas_price.unwrap();
+        assert_eq!(gas_price.min, 1);
+        assert_eq!(gas_price.max, 5);
+        assert_eq!(gas_price.mean, 3);
+        assert_eq!(analytics.ready_gas_price.unwrap().max, 3);
+
+        let buckets = analytics.gas_price_buckets;
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].gas_price, 3);
+        assert_eq!(buckets[0].txn_count, 2);
+        assert_eq!(buckets[0].cumulative_max_gas_amount, 2000);
+        assert_eq!(buckets[1].gas_price, 1);
+        assert_eq!(buckets[1].cumulative_max_gas_amount, 3000);
+
+        assert_eq!(analytics.top_senders.len(), 1);
+        let top_sender = &analytics.top_senders[0];
+        assert_eq!(top_sender.sender, alice);
+        assert_eq!(top_sender.txn_count, 3);
+        assert_eq!(top_sender.ready_count, 2);
+        assert_eq!(top_sender.min_sequence_number, 0);
+        assert_eq!(top_sender.max_sequence_number, 3);
+        assert_eq!(top_sender.max_gas_price, 5);
+    }
+
+    #[test]
+    fn test_analyze_empty() {
+        let analytics =
+            TxPoolAnalytics::analyze(Vec::<(&SignedUserTransaction, bool)>::new(), 0, 10);
+        assert_eq!(analytics.txn_count, 0);
+        assert!(analytics.gas_price.is_none());
+        assert!(analytics.gas_price_buckets.is_empty());
+        assert!(analytics.top_senders.is_empty());
+    }
+}
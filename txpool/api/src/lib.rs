@@ -11,6 +11,12 @@ use starcoin_types::{
 use std::fmt::Debug;
 use std::sync::Arc;
 
+mod analytics;
+
+pub use analytics::{
+    GasPriceBucket, GasPriceStats, SenderStats, TxPoolAnalytics, EXPIRING_SOON_SECS,
+};
+
 pub type TxnStatusFullEvent = Arc<[(HashValue, transaction::TxStatus)]>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -23,12 +29,146 @@ pub struct TxPoolStatus {
     pub is_full: bool,
 }
 
+/// The txpool admission checks run by `TxPoolSyncService::validate_txn`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TxnCheck {
+    Signature,
+    ChainId,
+    GasPrice,
+    SequenceNumber,
+    Expiration,
+    BalanceForGas,
+    /// Checks run by the vm prologue, such as authentication key and gas token.
+    VMValidation,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnCheckResult {
+    pub check: TxnCheck,
+    pub passed: bool,
+    /// Why the check failed, None if passed.
+    pub reason: Option<String>,
+}
+
+impl TxnCheckResult {
+    pub fn new(check: TxnCheck, failed_reason: Option<String>) -> Self {
+        Self {
+            check,
+            passed: failed_reason.is_none(),
+            reason: failed_reason,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnValidation {
+    pub txn_hash: HashValue,
+    /// True if all checks passed.
+    pub valid: bool,
+    pub checks: Vec<TxnCheckResult>,
+}
+
+impl TxnValidation {
+    pub fn new(txn_hash: HashValue, checks: Vec<TxnCheckResult>) -> Self {
+        Self {
+            txn_hash,
+            valid: checks.iter().all(|check| check.passed),
+            checks,
+        }
+    }
+}
+
+/// Re-sign the kept txn when its expiration is within the seconds by default.
+pub const DEFAULT_RESIGN_BEFORE_EXPIRATION: u64 = 60;
+
+/// The policy to keep a submitted txn until it is included in a block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnKeepPolicy {
+    /// Re-sign the txn with a higher gas price when the expiration nears,
+    /// the sender account must be unlocked in the node.
+    #[serde(default)]
+    pub resign: bool,
+    /// The cap of the gas price of the re-signed txn, required if `resign` is true.
+    #[serde(default)]
+    pub max_gas_price: Option<u64>,
+    /// Re-sign the txn when its expiration is within the seconds.
+    #[serde(default)]
+    pub resign_before_expiration: Option<u64>,
+}
+
+impl TxnKeepPolicy {
+    pub fn resign_before_expiration(&self) -> u64 {
+        self.resign_before_expiration
+            .unwrap_or(DEFAULT_RESIGN_BEFORE_EXPIRATION)
+    }
+}
+
+/// A txn encrypted to the threshold committee of the encrypted pool,
+/// it can only be decrypted after the committee releases enough decryption shares.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct EncryptedTransaction {
+    /// The ephemeral public key of the encryption, a compressed Ristretto point.
+    pub ephemeral_key: Vec<u8>,
+    /// The encrypted bcs bytes of the `SignedUserTransaction`.
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedTransaction {
+    pub fn new(ephemeral_key: Vec<u8>, ciphertext: Vec<u8>) -> Self {
+        Self {
+            ephemeral_key,
+            ciphertext,
+        }
+    }
+
+    pub fn id(&self) -> HashValue {
+        HashValue::sha3_256_of(
+            &[self.ephemeral_key.as_slice(), self.ciphertext.as_slice()].concat(),
+        )
+    }
+}
+
+/// The decryption share of an encrypted txn released by a committee member.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    pub txn_id: HashValue,
+    /// The index of the committee member, start from 1.
+    pub index: u32,
+    /// The ephemeral key multiplied by the key share of the member, a compressed Ristretto point.
+    pub share: Vec<u8>,
+    /// The proof that the share is computed with the key share of the member.
+    pub proof: Vec<u8>,
+}
+
+/// An encrypted txn whose order is fixed, waiting for the decryption shares.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SealedEncryptedTransaction {
+    pub txn_id: HashValue,
+    /// The txn is sealed when the head block of the number is connected.
+    pub batch: u64,
+    /// The position of the txn in the batch.
+    pub position: u32,
+    pub txn: EncryptedTransaction,
+}
+
 pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     fn add_txns(
         &self,
         txns: Vec<SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>>;
 
+    /// Add the txns submitted to this node, such as by rpc,
+    /// the txpool may hold their first-hop propagation for privacy.
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        self.add_txns(txns)
+    }
+
+    /// Run all the admission checks on the txn without inserting it into the pool.
+    fn validate_txn(&self, txn: SignedUserTransaction) -> TxnValidation;
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -67,16 +207,35 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
         sender: &AccountAddress,
         max_len: Option<usize>,
     ) -> Vec<SignedUserTransaction>;
+
+    /// The analytics of the txns in the pool, with at most `top_senders` senders of the most txns.
+    fn analytics(&self, top_senders: usize) -> TxPoolAnalytics;
 }
 
 #[derive(Clone, Debug)]
 pub struct PropagateTransactions {
     txns: Vec<SignedUserTransaction>,
+    relay_peers: Option<u32>,
 }
 
 impl PropagateTransactions {
     pub fn new(txns: Vec<SignedUserTransaction>) -> Self {
-        Self { txns }
+        Self {
+            txns,
+            relay_peers: None,
+        }
+    }
+
+    /// Relay the txns to the number of random peers only, instead of broadcasting them.
+    pub fn new_with_relay_peers(txns: Vec<SignedUserTransaction>, relay_peers: u32) -> Self {
+        Self {
+            txns,
+            relay_peers: Some(relay_peers),
+        }
+    }
+
+    pub fn relay_peers(&self) -> Option<u32> {
+        self.relay_peers
     }
 
     pub fn transaction_to_propagate(&self) -> Vec<SignedUserTransaction> {
@@ -4,7 +4,7 @@
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{TxPoolAnalytics, TxPoolStatus, TxPoolSyncService, TxnValidation};
 use std::{
     iter::Iterator,
     sync::{Arc, Mutex},
@@ -42,6 +42,10 @@ impl TxPoolSyncService for MockTxPoolService {
         results
     }
 
+    fn validate_txn(&self, txn: SignedUserTransaction) -> TxnValidation {
+        TxnValidation::new(txn.id(), vec![])
+    }
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -101,6 +105,11 @@ impl TxPoolSyncService for MockTxPoolService {
     ) -> Vec<SignedUserTransaction> {
         todo!()
     }
+
+    fn analytics(&self, top_senders: usize) -> TxPoolAnalytics {
+        let pool = self.pool.lock().unwrap();
+        TxPoolAnalytics::analyze(pool.iter().map(|txn| (txn, true)), 0, top_senders)
+    }
 }
 
 #[cfg(test)]
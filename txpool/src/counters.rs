@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use prometheus::{HistogramOpts, HistogramVec, IntGauge, IntGaugeVec, Opts};
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts};
 
 pub static TXPOOL_TXNS_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
     let opts =
@@ -17,3 +17,21 @@ pub static TXPOOL_SERVICE_HISTOGRAM: Lazy<HistogramVec> = Lazy::new(|| {
         HistogramOpts::new("txpool_service", "Histogram of txpool service").namespace("starcoin");
     register_histogram_vec!(opts, &["api"]).unwrap()
 });
+
+pub static TXPOOL_TXN_EVENT_COUNTER_VEC: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "txpool_txn_event",
+        "Counter of txns replaced or evicted from txpool",
+    )
+    .namespace("starcoin");
+    register_int_counter_vec!(opts, &["event"]).unwrap()
+});
+
+pub static TXPOOL_TXN_IMPORT_COUNTER_VEC: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "txpool_txn_import",
+        "Counter of txns admitted to txpool, or rejected by reason",
+    )
+    .namespace("starcoin");
+    register_int_counter_vec!(opts, &["result"]).unwrap()
+});
@@ -0,0 +1,446 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::threshold_crypto::{KeyShare, ThresholdCommittee};
+use crate::TxPoolService;
+use anyhow::{ensure, format_err, Result};
+use crypto::HashValue;
+use starcoin_config::NodeConfig;
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRequest,
+};
+use starcoin_txpool_api::{
+    DecryptionShare, EncryptedTransaction, PropagateTransactions, SealedEncryptedTransaction,
+    TxPoolSyncService,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use types::block::BlockNumber;
+use types::system_events::NewHeadBlock;
+use types::transaction::SignedUserTransaction;
+
+/// The sealed txns are dropped if they are not decrypted in the blocks.
+const SEALED_TXN_TTL_BLOCKS: BlockNumber = 16;
+/// The max size of the ciphertext of an encrypted txn.
+const MAX_ENCRYPTED_TXN_SIZE: usize = 128 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct SubmitEncryptedTxnRequest(pub EncryptedTransaction);
+
+impl ServiceRequest for SubmitEncryptedTxnRequest {
+    type Response = Result<HashValue>;
+}
+
+/// Submit the decryption shares, return the hashes of the decrypted txns.
+#[derive(Clone, Debug)]
+pub struct SubmitDecryptionSharesRequest(pub Vec<DecryptionShare>);
+
+impl ServiceRequest for SubmitDecryptionSharesRequest {
+    type Response = Result<Vec<HashValue>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SealedEncryptedTxnsRequest;
+
+impl ServiceRequest for SealedEncryptedTxnsRequest {
+    type Response = Vec<SealedEncryptedTransaction>;
+}
+
+struct SealedTxn {
+    sealed: SealedEncryptedTransaction,
+    shares: HashMap<u32, DecryptionShare>,
+}
+
+/// The encrypted txns waiting for sealing and the sealed txns waiting for the decryption shares.
+struct EncryptedQueue {
+    committee: ThresholdCommittee,
+    max_count: usize,
+    pending: Vec<EncryptedTransaction>,
+    /// The sealed txns in the order of the batch and the position in the batch.
+    sealed: VecDeque<SealedTxn>,
+}
+
+impl EncryptedQueue {
+    fn new(committee: ThresholdCommittee, max_count: usize) -> Self {
+        Self {
+            committee,
+            max_count,
+            pending: vec![],
+            sealed: VecDeque::new(),
+        }
+    }
+
+    fn contains(&self, txn_id: &HashValue) -> bool {
+        self.pending.iter().any(|txn| txn.id() == *txn_id)
+            || self.sealed.iter().any(|txn| txn.sealed.txn_id == *txn_id)
+    }
+
+    fn submit(&mut self, txn: EncryptedTransaction) -> Result<HashValue> {
+        ensure!(
+            txn.ciphertext.len() <= MAX_ENCRYPTED_TXN_SIZE,
+            "The encrypted txn size {} exceeds the limit {}",
+            txn.ciphertext.len(),
+            MAX_ENCRYPTED_TXN_SIZE
+        );
+        let txn_id = txn.id();
+        ensure!(
+            !self.contains(&txn_id),
+            "Encrypted txn {} already exists",
+            txn_id
+        );
+        ensure!(
+            self.pending.len() + self.sealed.len() < self.max_count,
+            "The encrypted pool is full"
+        );
+        self.committee.check_encrypted_txn(&txn)?;
+        self.pending.push(txn);
+        Ok(txn_id)
+    }
+
+    /// Drop the expired sealed txns, and seal the pending txns in the arrival order,
+    /// return the newly sealed txns.
+    fn seal(&mut self, batch: BlockNumber) -> Vec<EncryptedTransaction> {
+        self.sealed.retain(|sealed_txn| {
+            let expired = sealed_txn
+                .sealed
+                .batch
+                .saturating_add(SEALED_TXN_TTL_BLOCKS)
+                < batch;
+            if expired {
+                warn!(
+                    "[encrypted-pool] Sealed txn {} is not decrypted in {} blocks, drop it.",
+                    sealed_txn.sealed.txn_id, SEALED_TXN_TTL_BLOCKS
+                );
+            }
+            !expired
+        });
+        let newly_sealed = std::mem::take(&mut self.pending);
+        for (position, txn) in newly_sealed.iter().enumerate() {
+            self.sealed.push_back(SealedTxn {
+                sealed: SealedEncryptedTransaction {
+                    txn_id: txn.id(),
+                    batch,
+                    position: position as u32,
+                    txn: txn.clone(),
+                },
+                shares: HashMap::new(),
+            });
+        }
+        newly_sealed
+    }
+
+    /// Add the decryption shares, all the shares are verified before any of them is added,
+    /// so an invalid share rejects the whole request.
+    fn add_shares(&mut self, shares: Vec<DecryptionShare>) -> Result<()> {
+        let max_shares = self
+            .sealed
+            .len()
+            .saturating_mul(self.committee.member_count() as usize);
+        ensure!(
+            shares.len() <= max_shares,
+            "Too many decryption shares {}, the sealed txns only need {}",
+            shares.len(),
+            max_shares
+        );
+        for share in shares.iter() {
+            let sealed_txn = self
+                .sealed
+                .iter()
+                .find(|txn| txn.sealed.txn_id == share.txn_id)
+                .ok_or_else(|| format_err!("Can not find sealed encrypted txn {}", share.txn_id))?;
+            self.committee.verify_share(&sealed_txn.sealed.txn, share)?;
+        }
+        for share in shares {
+            if let Some(sealed_txn) = self
+                .sealed
+                .iter_mut()
+                .find(|txn| txn.sealed.txn_id == share.txn_id)
+            {
+                sealed_txn.shares.insert(share.index, share);
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypt the sealed txns in the sealed order, a txn is decrypted only after all the txns
+    /// sealed before it are decrypted or dropped, so the order of the decrypted txns is fixed.
+    fn take_decrypted(&mut self) -> Vec<SignedUserTransaction> {
+        let threshold = self.committee.threshold() as usize;
+        let mut decrypted = vec![];
+        while let Some(sealed_txn) = self.sealed.front() {
+            if sealed_txn.shares.len() < threshold {
+                break;
+            }
+            let shares = sealed_txn.shares.values().cloned().collect::<Vec<_>>();
+            match self.committee.decrypt(&sealed_txn.sealed.txn, &shares) {
+                Ok(txn) => {
+                    info!(
+                        "[encrypted-pool] Decrypt txn {} to {}",
+                        sealed_txn.sealed.txn_id,
+                        txn.id()
+                    );
+                    decrypted.push(txn);
+                }
+                Err(e) => warn!(
+                    "[encrypted-pool] Drop sealed txn {}: {}",
+                    sealed_txn.sealed.txn_id, e
+                ),
+            }
+            self.sealed.pop_front();
+        }
+        decrypted
+    }
+}
+
+/// The experimental encrypted pool for research networks studying front-running mitigation.
+///
+/// The txns are submitted encrypted to a threshold committee and wait in the pending queue,
+/// the pending txns are sealed in the arrival order when a new head block is connected.
+/// The committee releases the decryption shares only for the sealed txns, so the content of a txn
+/// is unknown until its order is fixed, the decrypted txns are added to the txpool in the sealed order.
+/// The encrypted txns are not propagated to peers, they should be submitted to the node of the miner.
+pub struct EncryptedPoolService {
+    txpool: TxPoolService,
+    queue: EncryptedQueue,
+    key_share: Option<KeyShare>,
+}
+
+impl EncryptedPoolService {
+    /// Add the decrypted txns to the txpool in the sealed order, return the hashes of the txns.
+    fn add_decrypted_txns(&mut self, ctx: &mut ServiceContext<Self>) -> Vec<HashValue> {
+        let decrypted = self.queue.take_decrypted();
+        let txn_hashes = decrypted.iter().map(|txn| txn.id()).collect::<Vec<_>>();
+        if decrypted.is_empty() {
+            return txn_hashes;
+        }
+        let mut to_propagate = vec![];
+        for (txn, result) in decrypted
+            .iter()
+            .zip(self.txpool.add_txns(decrypted.clone()))
+        {
+            match result {
+                Ok(()) => to_propagate.push(txn.clone()),
+                Err(e) => warn!(
+                    "[encrypted-pool] Add decrypted txn {} failed: {}",
+                    txn.id(),
+                    e
+                ),
+            }
+        }
+        if !to_propagate.is_empty() {
+            ctx.broadcast(PropagateTransactions::new(to_propagate));
+        }
+        txn_hashes
+    }
+}
+
+impl ServiceFactory<Self> for EncryptedPoolService {
+    fn create(ctx: &mut ServiceContext<EncryptedPoolService>) -> Result<EncryptedPoolService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let committee_file = config.tx_pool.threshold_committee_file().ok_or_else(|| {
+            format_err!("The threshold committee file is required by the encrypted pool.")
+        })?;
+        let committee = ThresholdCommittee::load(committee_file)?;
+        let key_share = config
+            .tx_pool
+            .threshold_key_share_file()
+            .map(|path| KeyShare::load(path.as_path()))
+            .transpose()?;
+        if let Some(key_share) = key_share.as_ref() {
+            ensure!(
+                key_share.index() <= committee.member_count(),
+                "Invalid key share index {} of {} members",
+                key_share.index(),
+                committee.member_count()
+            );
+        }
+        Ok(Self {
+            txpool: ctx.get_shared::<TxPoolService>()?,
+            queue: EncryptedQueue::new(committee, config.tx_pool.max_count() as usize),
+            key_share,
+        })
+    }
+}
+
+impl ActorService for EncryptedPoolService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl ServiceHandler<Self, SubmitEncryptedTxnRequest> for EncryptedPoolService {
+    fn handle(
+        &mut self,
+        msg: SubmitEncryptedTxnRequest,
+        _ctx: &mut ServiceContext<EncryptedPoolService>,
+    ) -> Result<HashValue> {
+        self.queue.submit(msg.0)
+    }
+}
+
+impl ServiceHandler<Self, SubmitDecryptionSharesRequest> for EncryptedPoolService {
+    fn handle(
+        &mut self,
+        msg: SubmitDecryptionSharesRequest,
+        ctx: &mut ServiceContext<EncryptedPoolService>,
+    ) -> Result<Vec<HashValue>> {
+        self.queue.add_shares(msg.0)?;
+        Ok(self.add_decrypted_txns(ctx))
+    }
+}
+
+impl ServiceHandler<Self, SealedEncryptedTxnsRequest> for EncryptedPoolService {
+    fn handle(
+        &mut self,
+        _msg: SealedEncryptedTxnsRequest,
+        _ctx: &mut ServiceContext<EncryptedPoolService>,
+    ) -> Vec<SealedEncryptedTransaction> {
+        self.queue
+            .sealed
+            .iter()
+            .map(|sealed_txn| sealed_txn.sealed.clone())
+            .collect()
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for EncryptedPoolService {
+    fn handle_event(&mut self, msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
+        let batch = msg.0.block().header().number();
+        let newly_sealed = self.queue.seal(batch);
+        if newly_sealed.is_empty() {
+            return;
+        }
+        info!(
+            "[encrypted-pool] Seal encrypted txns of batch {}, sealed txns: {}",
+            batch,
+            self.queue.sealed.len()
+        );
+        if let Some(key_share) = self.key_share.as_ref() {
+            let mut own_shares = vec![];
+            for txn in newly_sealed.iter() {
+                match key_share.decryption_share(txn) {
+                    Ok(share) => own_shares.push(share),
+                    Err(e) => error!(
+                        "[encrypted-pool] Compute decryption share of txn {} failed: {}",
+                        txn.id(),
+                        e
+                    ),
+                }
+            }
+            if let Err(e) = self.queue.add_shares(own_shares) {
+                error!("[encrypted-pool] Add own decryption shares failed: {}", e);
+            }
+        }
+        self.add_decrypted_txns(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold_crypto::generate_committee;
+
+    fn shares_of(key_shares: &[KeyShare], txn: &EncryptedTransaction) -> Vec<DecryptionShare> {
+        key_shares
+            .iter()
+            .map(|key_share| key_share.decryption_share(txn).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_decrypt_in_sealed_order() {
+        let (committee, key_shares) = generate_committee(2, 3).unwrap();
+        let txns = vec![SignedUserTransaction::mock(), SignedUserTransaction::mock()];
+        let mut queue = EncryptedQueue::new(committee.clone(), 10);
+        for txn in txns.iter() {
+            queue.submit(committee.encrypt(txn).unwrap()).unwrap();
+        }
+        let sealed = queue.seal(1);
+        assert_eq!(sealed.len(), 2);
+
+        // the second txn can not be decrypted before the first one.
+        queue
+            .add_shares(shares_of(&key_shares[..2], &sealed[1]))
+            .unwrap();
+        assert!(queue.take_decrypted().is_empty());
+        queue
+            .add_shares(shares_of(&key_shares[1..], &sealed[0]))
+            .unwrap();
+        assert_eq!(queue.take_decrypted(), txns);
+        assert!(queue.sealed.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_shares_rejected() {
+        let (committee, key_shares) = generate_committee(2, 3).unwrap();
+        let mut queue = EncryptedQueue::new(committee.clone(), 10);
+        queue
+            .submit(committee.encrypt(&SignedUserTransaction::mock()).unwrap())
+            .unwrap();
+        let sealed = queue.seal(1);
+        let mut shares = shares_of(&key_shares, &sealed[0]);
+        shares[2].share = shares[1].share.clone();
+        // the valid shares are not added if any share is invalid.
+        assert!(queue.add_shares(shares.clone()).is_err());
+        assert!(queue.sealed[0].shares.is_empty());
+        // the shares of the unknown txn are rejected.
+        let unknown = committee.encrypt(&SignedUserTransaction::mock()).unwrap();
+        assert!(queue
+            .add_shares(shares_of(&key_shares[..1], &unknown))
+            .is_err());
+        // the shares more than the sealed txns need are rejected.
+        let mut too_many = shares[..2].to_vec();
+        too_many.extend(shares[..2].to_vec());
+        assert!(queue.add_shares(too_many).is_err());
+
+        queue.add_shares(shares[..2].to_vec()).unwrap();
+        assert_eq!(queue.take_decrypted().len(), 1);
+    }
+
+    #[test]
+    fn test_submit_limits() {
+        let (committee, _) = generate_committee(1, 1).unwrap();
+        let mut queue = EncryptedQueue::new(committee.clone(), 2);
+        let encrypted = committee.encrypt(&SignedUserTransaction::mock()).unwrap();
+        queue.submit(encrypted.clone()).unwrap();
+        assert!(queue.submit(encrypted).is_err());
+        let oversized = EncryptedTransaction::new(
+            committee
+                .encrypt(&SignedUserTransaction::mock())
+                .unwrap()
+                .ephemeral_key,
+            vec![0u8; MAX_ENCRYPTED_TXN_SIZE + 1],
+        );
+        assert!(queue.submit(oversized).is_err());
+        let malformed = EncryptedTransaction::new(vec![0u8; 31], vec![0u8; 32]);
+        assert!(queue.submit(malformed).is_err());
+        queue
+            .submit(committee.encrypt(&SignedUserTransaction::mock()).unwrap())
+            .unwrap();
+        // the pool is full.
+        assert!(queue
+            .submit(committee.encrypt(&SignedUserTransaction::mock()).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_sealed_txn_expired() {
+        let (committee, _) = generate_committee(1, 1).unwrap();
+        let mut queue = EncryptedQueue::new(committee.clone(), 10);
+        queue
+            .submit(committee.encrypt(&SignedUserTransaction::mock()).unwrap())
+            .unwrap();
+        queue.seal(1);
+        assert_eq!(queue.sealed.len(), 1);
+        queue.seal(1 + SEALED_TXN_TTL_BLOCKS);
+        assert_eq!(queue.sealed.len(), 1);
+        queue.seal(2 + SEALED_TXN_TTL_BLOCKS);
+        assert!(queue.sealed.is_empty());
+    }
+}
@@ -12,6 +12,11 @@ extern crate transaction_pool as tx_pool;
 
 use anyhow::{format_err, Result};
 use counters::{TXPOOL_STATUS_GAUGE_VEC, TXPOOL_TXNS_GAUGE};
+#[cfg(feature = "threshold-encryption")]
+pub use encrypted_pool::{
+    EncryptedPoolService, SealedEncryptedTxnsRequest, SubmitDecryptionSharesRequest,
+    SubmitEncryptedTxnRequest,
+};
 use network_api::messages::PeerTransactionsMessage;
 pub use pool::TxStatus;
 use starcoin_config::NodeConfig;
@@ -20,21 +25,32 @@ use starcoin_state_api::AccountStateReader;
 use starcoin_txpool_api::{PropagateTransactions, TxnStatusFullEvent};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use storage::{BlockStore, Storage};
+#[cfg(feature = "threshold-encryption")]
+pub use threshold_crypto::{
+    generate_committee, KeyShare, KeyShareConfig, ThresholdCommittee, ThresholdCommitteeConfig,
+};
 use tx_pool_service_impl::Inner;
 pub use tx_pool_service_impl::TxPoolService;
+pub use txn_keeper::{SubmitTxnWithPolicyRequest, TxnKeeperService};
 use types::{
     sync_status::SyncStatus, system_events::SyncStatusChangeEvent,
     transaction::SignedUserTransaction,
 };
 
 mod counters;
+#[cfg(feature = "threshold-encryption")]
+mod encrypted_pool;
 mod pool;
 mod pool_client;
 #[cfg(test)]
 mod test;
+#[cfg(feature = "threshold-encryption")]
+mod threshold_crypto;
 mod tx_pool_service_impl;
+mod txn_keeper;
+mod txn_propagation;
 //TODO refactor TxPoolService and rename.
 #[derive(Clone)]
 pub struct TxPoolActorService {
@@ -68,6 +84,29 @@ impl TxPoolActorService {
         }
     }
 
+    /// Relay or release the held local txns which are due.
+    fn poll_local_txns(&self, ctx: &mut ServiceContext<Self>) {
+        let local_propagation = self.inner.local_propagation();
+        let (to_relay, released) = local_propagation.poll(Instant::now());
+        if !to_relay.is_empty() {
+            let queue = self.inner.queue();
+            let txns = to_relay
+                .iter()
+                .filter_map(|txn_hash| queue.find(txn_hash))
+                .map(|txn| txn.signed().clone())
+                .collect::<Vec<_>>();
+            if !txns.is_empty() {
+                ctx.broadcast(PropagateTransactions::new_with_relay_peers(
+                    txns,
+                    local_propagation.relay_peers(),
+                ));
+            }
+        }
+        if released {
+            self.new_txs_received.store(true, Ordering::Relaxed);
+        }
+    }
+
     fn transactions_to_propagate(&self) -> Result<Vec<SignedUserTransaction>> {
         let statedb = self.inner.get_chain_reader();
         let reader = AccountStateReader::new(&statedb);
@@ -83,11 +122,13 @@ impl TxPoolActorService {
         // );
         let max_len = 100;
         let current_timestamp = reader.get_timestamp()?.seconds();
+        let local_propagation = self.inner.local_propagation();
         Ok(self
             .inner
             .get_pending(max_len, current_timestamp)
             .into_iter()
             .map(|t| t.signed().clone())
+            .filter(|txn| !local_propagation.is_held(&txn.id()))
             .collect())
     }
 }
@@ -117,6 +158,7 @@ impl ServiceFactory<Self> for TxPoolActorService {
 }
 impl TxPoolActorService {
     fn try_propagate_txns(&self, ctx: &mut ServiceContext<Self>) {
+        self.poll_local_txns(ctx);
         // only propagate when new txns enter pool.
         if self.new_txs_received.load(Ordering::Relaxed) {
             match self.transactions_to_propagate() {
@@ -4,6 +4,7 @@
 use std::{fmt, sync::Arc};
 
 use super::{TxStatus, VerifiedTransaction as Transaction};
+use crate::counters::TXPOOL_TXN_EVENT_COUNTER_VEC;
 use crypto::hash::HashValue as H256;
 use futures_channel::mpsc;
 use starcoin_logger::prelude::*;
@@ -99,6 +100,9 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         Self::log_status(tx, TxStatus::Added);
         if let Some(old) = old {
+            TXPOOL_TXN_EVENT_COUNTER_VEC
+                .with_label_values(&["replaced"])
+                .inc();
             Self::log_status(old, TxStatus::Dropped);
         }
     }
@@ -112,6 +116,10 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     }
 
     fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
+        // the txn is dropped because of the pool limit.
+        TXPOOL_TXN_EVENT_COUNTER_VEC
+            .with_label_values(&["evicted"])
+            .inc();
         Self::log_status(tx, TxStatus::Dropped);
     }
 
@@ -124,6 +132,9 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     }
 
     fn culled(&mut self, tx: &Arc<Transaction>) {
+        TXPOOL_TXN_EVENT_COUNTER_VEC
+            .with_label_values(&["culled"])
+            .inc();
         Self::log_status(tx, TxStatus::Culled);
     }
 }
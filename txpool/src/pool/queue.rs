@@ -231,13 +231,14 @@ impl TransactionQueue {
         limits: tx_pool::Options,
         verification_options: verifier::Options,
         strategy: PrioritizationStrategy,
+        gas_price_bump_percent: u64,
     ) -> Self {
         let max_count = limits.max_count;
         TransactionQueue {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
                 Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                scoring::SeqNumberAndGasPrice::new(strategy, gas_price_bump_percent),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -359,6 +360,16 @@ impl TransactionQueue {
             .collect()
     }
 
+    /// Returns all transactions in the pool, no matter they are ready or in future.
+    pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
+        // always ready
+        let ready = Expiration::new(0);
+        let pool = self.pool.read();
+        pool.senders()
+            .flat_map(|sender| pool.pending_from_sender(ready, sender))
+            .collect()
+    }
+
     /// Returns current pending transactions ordered by priority.
     ///
     /// NOTE: This may return a cached version of pending transaction set.
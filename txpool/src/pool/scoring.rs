@@ -5,16 +5,14 @@ use std::cmp;
 
 use super::{GasPrice, PrioritizationStrategy, ScoredTransaction, VerifiedTransaction};
 use tx_pool::{self, scoring};
-/// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price >= old_gas_price + old_gas_price >> SHIFT`
-const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
-
-/// TODO: re-consider this, after we settle down the unit of gas price.
 /// Calculate minimal gas price requirement.
+///
+/// Transaction with the same (sender, nonce) can be replaced only if
+/// `new_gas_price >= old_gas_price + ceil(old_gas_price * bump_percent / 100)`
 #[inline]
-fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
-    old_gp
-    // old_gp.saturating_add(old_gp >> GAS_PRICE_BUMP_SHIFT as u64)
+pub(crate) fn bump_gas_price(old_gp: GasPrice, bump_percent: u64) -> GasPrice {
+    let bump = old_gp.saturating_mul(bump_percent).saturating_add(99) / 100;
+    old_gp.saturating_add(bump)
 }
 
 /// Simple, gas-price based scoring for transactions.
@@ -22,9 +20,19 @@ fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
 #[derive(Debug, Clone)]
-pub struct SeqNumberAndGasPrice(pub PrioritizationStrategy);
+pub struct SeqNumberAndGasPrice {
+    strategy: PrioritizationStrategy,
+    gas_price_bump_percent: u64,
+}
 
 impl SeqNumberAndGasPrice {
+    pub fn new(strategy: PrioritizationStrategy, gas_price_bump_percent: u64) -> Self {
+        Self {
+            strategy,
+            gas_price_bump_percent,
+        }
+    }
+
     /// Decide if the transaction should even be considered into the pool (if the pool is full).
     ///
     /// Used by Verifier to quickly reject transactions that don't have any chance to get into the pool later on,
@@ -68,7 +76,7 @@ where
         let old_gp = old.gas_price();
         let new_gp = new.gas_price();
 
-        let min_required_gp = bump_gas_price(old_gp);
+        let min_required_gp = bump_gas_price(old_gp, self.gas_price_bump_percent);
 
         match min_required_gp.cmp(&new_gp) {
             cmp::Ordering::Greater => scoring::Choice::RejectNew,
@@ -16,7 +16,7 @@ use starcoin_executor::{
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::ChainStateWriter;
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_txpool_api::{TxPoolSyncService, TxnCheck, TxnStatusFullEvent};
 use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 use stest::actix_export::time::delay_for;
@@ -95,6 +95,61 @@ async fn test_tx_pool() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+async fn test_txn_replace() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    let bump_percent = config.tx_pool.gas_price_bump_percent();
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 100);
+    txpool_service.add_txns(vec![txn]).pop().unwrap()?;
+
+    // same sequence number, gas price not high enough.
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 100 + bump_percent - 1);
+    assert!(txpool_service.add_txns(vec![txn]).pop().unwrap().is_err());
+
+    let replace_txn = generate_txn_with_gas_price(config, 0, 100 + bump_percent);
+    let replace_txn_hash = replace_txn.id();
+    txpool_service.add_txns(vec![replace_txn]).pop().unwrap()?;
+
+    let pending_txns = txpool_service.get_pending_txns(None, Some(0));
+    assert_eq!(pending_txns.len(), 1);
+    assert_eq!(pending_txns[0].id(), replace_txn_hash);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_validate_txn() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+    let account_address = account_address::from_public_key(&public_key);
+    let txn = starcoin_executor::build_transfer_from_association(
+        account_address,
+        Some(AuthenticationKey::ed25519(&public_key)),
+        0,
+        10000,
+        config.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        config.net(),
+    );
+    let txn = txn.as_signed_user_txn()?.clone();
+    let validation = txpool_service.validate_txn(txn.clone());
+    assert!(validation.valid, "{:?}", validation);
+    assert_eq!(validation.txn_hash, txn.id());
+
+    let txn = generate_txn_with_gas_price(config, 0, 0);
+    let validation = txpool_service.validate_txn(txn);
+    assert!(!validation.valid);
+    let failed_checks = validation
+        .checks
+        .iter()
+        .filter(|check| !check.passed)
+        .map(|check| check.check)
+        .collect::<Vec<_>>();
+    assert!(failed_checks.contains(&TxnCheck::GasPrice));
+
+    // validate should not insert txn into the pool.
+    assert_eq!(txpool_service.status().txn_count, 0);
+    Ok(())
+}
+
 #[stest::test]
 async fn test_subscribe_txns() {
     let (pool, ..) = test_helper::start_txpool().await;
@@ -251,6 +306,14 @@ async fn test_txpool_actor_service() {
 }
 
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = create_signed_txn_with_association_account(
@@ -262,7 +325,7 @@ fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
         )),
         seq,
         DEFAULT_MAX_GAS_AMOUNT,
-        1,
+        gas_price,
         2,
         config.net(),
     );
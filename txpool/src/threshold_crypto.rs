@@ -0,0 +1,406 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The threshold ElGamal encryption of the encrypted pool over the Ristretto group.
+//!
+//! A txn is encrypted to the committee public key `PK = s * G`, the secret `s` is shared
+//! to the committee members by Shamir's secret sharing. Each member releases the decryption
+//! share `s_i * R` of the ephemeral key `R` with a DLEQ proof, and any `threshold` valid shares
+//! recover `s * R` to derive the symmetric key of the txn.
+
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use chacha20poly1305::aead::generic_array::GenericArray;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use starcoin_txpool_api::{DecryptionShare, EncryptedTransaction};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::path::Path;
+use types::transaction::SignedUserTransaction;
+
+const KEY_DERIVATION_DOMAIN: &[u8] = b"STARCOIN::EncryptedTransaction::Key";
+const PROOF_DOMAIN: &[u8] = b"STARCOIN::DecryptionShare::Proof";
+/// The key of a txn is used only once, so the nonce is always zero.
+const NONCE: [u8; 12] = [0u8; 12];
+
+/// The json file format of the `ThresholdCommittee`, the points are hex encoded compressed Ristretto points.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdCommitteeConfig {
+    pub threshold: u32,
+    pub public_key: String,
+    /// The verification keys of the members, the member index starts from 1.
+    pub member_keys: Vec<String>,
+}
+
+/// The json file format of the `KeyShare`, the secret is a hex encoded scalar.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyShareConfig {
+    pub index: u32,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ThresholdCommittee {
+    threshold: u32,
+    public_key: RistrettoPoint,
+    member_keys: Vec<RistrettoPoint>,
+}
+
+impl ThresholdCommittee {
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: ThresholdCommitteeConfig = serde_json::from_slice(&std::fs::read(path)?)?;
+        Self::try_from(config)
+    }
+
+    pub fn to_config(&self) -> ThresholdCommitteeConfig {
+        ThresholdCommitteeConfig {
+            threshold: self.threshold,
+            public_key: encode_point(&self.public_key),
+            member_keys: self.member_keys.iter().map(encode_point).collect(),
+        }
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn member_count(&self) -> u32 {
+        self.member_keys.len() as u32
+    }
+
+    fn member_key(&self, index: u32) -> Result<&RistrettoPoint> {
+        ensure!(index > 0, "The member index starts from 1.");
+        self.member_keys
+            .get(index as usize - 1)
+            .ok_or_else(|| format_err!("Invalid member index {}", index))
+    }
+
+    pub fn encrypt(&self, txn: &SignedUserTransaction) -> Result<EncryptedTransaction> {
+        let r = random_scalar();
+        let ephemeral_key = (r * RISTRETTO_BASEPOINT_POINT).compress();
+        let cipher = cipher(&(r * self.public_key));
+        let ciphertext = cipher
+            .encrypt(
+                GenericArray::from_slice(&NONCE),
+                Payload {
+                    msg: &txn.encode()?,
+                    aad: ephemeral_key.as_bytes(),
+                },
+            )
+            .map_err(|_| format_err!("Encrypt txn {} failed", txn.id()))?;
+        Ok(EncryptedTransaction::new(
+            ephemeral_key.as_bytes().to_vec(),
+            ciphertext,
+        ))
+    }
+
+    /// Check the encrypted txn is well formed before sealing it.
+    pub fn check_encrypted_txn(&self, txn: &EncryptedTransaction) -> Result<()> {
+        decode_point(&txn.ephemeral_key)?;
+        Ok(())
+    }
+
+    pub fn verify_share(&self, txn: &EncryptedTransaction, share: &DecryptionShare) -> Result<()> {
+        ensure!(
+            share.txn_id == txn.id(),
+            "The decryption share is not for txn {}",
+            txn.id()
+        );
+        let member_key = self.member_key(share.index)?;
+        let ephemeral_key = decode_point(&txn.ephemeral_key)?;
+        let share_point = decode_point(&share.share)?;
+        ensure!(
+            share.proof.len() == 64,
+            "Invalid decryption share proof length {}",
+            share.proof.len()
+        );
+        let c = decode_scalar(&share.proof[..32])?;
+        let z = decode_scalar(&share.proof[32..])?;
+        let a = z * RISTRETTO_BASEPOINT_POINT - c * member_key;
+        let b = z * ephemeral_key - c * share_point;
+        ensure!(
+            c == challenge(member_key, &ephemeral_key, &share_point, &a, &b),
+            "Invalid decryption share proof of member {}",
+            share.index
+        );
+        Ok(())
+    }
+
+    /// Decrypt the txn with the decryption shares, every share is verified before it is combined,
+    /// so an invalid share can not make the txn decrypt to garbage or fail silently.
+    pub fn decrypt(
+        &self,
+        txn: &EncryptedTransaction,
+        shares: &[DecryptionShare],
+    ) -> Result<SignedUserTransaction> {
+        let indexes = shares
+            .iter()
+            .map(|share| share.index)
+            .collect::<HashSet<_>>();
+        ensure!(
+            indexes.len() == shares.len(),
+            "Duplicate decryption shares of a member"
+        );
+        for share in shares {
+            self.verify_share(txn, share)?;
+        }
+        ensure!(
+            shares.len() >= self.threshold as usize,
+            "Require {} decryption shares to decrypt txn {}, but got {}",
+            self.threshold,
+            txn.id(),
+            shares.len()
+        );
+        let shares = &shares[..self.threshold as usize];
+        let mut shared_secret = RistrettoPoint::default();
+        for share in shares {
+            let lambda = lagrange_coefficient(share.index, shares.iter().map(|s| s.index));
+            shared_secret += lambda * decode_point(&share.share)?;
+        }
+        let plaintext = cipher(&shared_secret)
+            .decrypt(
+                GenericArray::from_slice(&NONCE),
+                Payload {
+                    msg: &txn.ciphertext,
+                    aad: &txn.ephemeral_key,
+                },
+            )
+            .map_err(|_| format_err!("Decrypt txn {} failed", txn.id()))?;
+        SignedUserTransaction::decode(&plaintext)
+    }
+}
+
+impl TryFrom<ThresholdCommitteeConfig> for ThresholdCommittee {
+    type Error = anyhow::Error;
+
+    fn try_from(config: ThresholdCommitteeConfig) -> Result<Self> {
+        ensure!(
+            config.threshold > 0 && config.threshold as usize <= config.member_keys.len(),
+            "Invalid threshold {} of {} members",
+            config.threshold,
+            config.member_keys.len()
+        );
+        Ok(Self {
+            threshold: config.threshold,
+            public_key: decode_point(&hex::decode(&config.public_key)?)?,
+            member_keys: config
+                .member_keys
+                .iter()
+                .map(|key| decode_point(&hex::decode(key)?))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+/// The key share of a committee member.
+#[derive(Clone)]
+pub struct KeyShare {
+    index: u32,
+    secret: Scalar,
+}
+
+impl KeyShare {
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: KeyShareConfig = serde_json::from_slice(&std::fs::read(path)?)?;
+        Self::try_from(config)
+    }
+
+    pub fn to_config(&self) -> KeyShareConfig {
+        KeyShareConfig {
+            index: self.index,
+            secret: hex::encode(self.secret.as_bytes()),
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn decryption_share(&self, txn: &EncryptedTransaction) -> Result<DecryptionShare> {
+        let ephemeral_key = decode_point(&txn.ephemeral_key)?;
+        let member_key = self.secret * RISTRETTO_BASEPOINT_POINT;
+        let share = self.secret * ephemeral_key;
+        let k = random_scalar();
+        let c = challenge(
+            &member_key,
+            &ephemeral_key,
+            &share,
+            &(k * RISTRETTO_BASEPOINT_POINT),
+            &(k * ephemeral_key),
+        );
+        let z = k + c * self.secret;
+        Ok(DecryptionShare {
+            txn_id: txn.id(),
+            index: self.index,
+            share: share.compress().as_bytes().to_vec(),
+            proof: [&c.as_bytes()[..], &z.as_bytes()[..]].concat(),
+        })
+    }
+}
+
+impl TryFrom<KeyShareConfig> for KeyShare {
+    type Error = anyhow::Error;
+
+    fn try_from(config: KeyShareConfig) -> Result<Self> {
+        ensure!(config.index > 0, "The member index starts from 1.");
+        Ok(Self {
+            index: config.index,
+            secret: decode_scalar(&hex::decode(&config.secret)?)?,
+        })
+    }
+}
+
+/// Generate a committee and the key shares of the members by a trusted dealer,
+/// it is only for research networks and tests.
+pub fn generate_committee(
+    threshold: u32,
+    members: u32,
+) -> Result<(ThresholdCommittee, Vec<KeyShare>)> {
+    ensure!(
+        threshold > 0 && threshold <= members,
+        "Invalid threshold {} of {} members",
+        threshold,
+        members
+    );
+    let coefficients = (0..threshold).map(|_| random_scalar()).collect::<Vec<_>>();
+    let key_shares = (1..=members)
+        .map(|index| {
+            let x = Scalar::from(index);
+            let secret = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, coefficient| acc * x + coefficient);
+            KeyShare { index, secret }
+        })
+        .collect::<Vec<_>>();
+    let committee = ThresholdCommittee {
+        threshold,
+        public_key: coefficients[0] * RISTRETTO_BASEPOINT_POINT,
+        member_keys: key_shares
+            .iter()
+            .map(|share| share.secret * RISTRETTO_BASEPOINT_POINT)
+            .collect(),
+    };
+    Ok((committee, key_shares))
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn cipher(shared_secret: &RistrettoPoint) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_DOMAIN);
+    hasher.update(shared_secret.compress().as_bytes());
+    ChaCha20Poly1305::new(&hasher.finalize())
+}
+
+fn challenge(
+    member_key: &RistrettoPoint,
+    ephemeral_key: &RistrettoPoint,
+    share: &RistrettoPoint,
+    a: &RistrettoPoint,
+    b: &RistrettoPoint,
+) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(PROOF_DOMAIN);
+    for point in vec![member_key, ephemeral_key, share, a, b] {
+        hasher.update(point.compress().as_bytes());
+    }
+    Scalar::from_hash(hasher)
+}
+
+/// The Lagrange coefficient of the member at x = 0.
+fn lagrange_coefficient(index: u32, indexes: impl Iterator<Item = u32>) -> Scalar {
+    let x = Scalar::from(index);
+    let (numerator, denominator) = indexes.filter(|other| *other != index).fold(
+        (Scalar::one(), Scalar::one()),
+        |(numerator, denominator), other| {
+            let other = Scalar::from(other);
+            (numerator * other, denominator * (other - x))
+        },
+    );
+    numerator * denominator.invert()
+}
+
+fn encode_point(point: &RistrettoPoint) -> String {
+    hex::encode(point.compress().as_bytes())
+}
+
+fn decode_point(bytes: &[u8]) -> Result<RistrettoPoint> {
+    ensure!(
+        bytes.len() == 32,
+        "Invalid Ristretto point length {}",
+        bytes.len()
+    );
+    CompressedRistretto::from_slice(bytes)
+        .decompress()
+        .ok_or_else(|| format_err!("Invalid Ristretto point"))
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar> {
+    let bytes = <[u8; 32]>::try_from(bytes)
+        .map_err(|_| format_err!("Invalid scalar length {}", bytes.len()))?;
+    Scalar::from_canonical_bytes(bytes).ok_or_else(|| format_err!("Invalid scalar"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_decrypt() {
+        let (committee, key_shares) = generate_committee(3, 5).unwrap();
+        let committee = ThresholdCommittee::try_from(committee.to_config()).unwrap();
+        let txn = SignedUserTransaction::mock();
+        let encrypted = committee.encrypt(&txn).unwrap();
+        let shares = key_shares
+            .iter()
+            .rev()
+            .map(|key_share| key_share.decryption_share(&encrypted).unwrap())
+            .collect::<Vec<_>>();
+        for share in &shares {
+            committee.verify_share(&encrypted, share).unwrap();
+        }
+        assert!(committee.decrypt(&encrypted, &shares[..2]).is_err());
+        assert_eq!(committee.decrypt(&encrypted, &shares[..3]).unwrap(), txn);
+        assert_eq!(committee.decrypt(&encrypted, &shares[2..]).unwrap(), txn);
+    }
+
+    #[test]
+    fn test_invalid_decryption_share() {
+        let (committee, key_shares) = generate_committee(2, 3).unwrap();
+        let encrypted = committee.encrypt(&SignedUserTransaction::mock()).unwrap();
+        let mut share = key_shares[0].decryption_share(&encrypted).unwrap();
+        share.index = 2;
+        assert!(committee.verify_share(&encrypted, &share).is_err());
+        let other = committee.encrypt(&SignedUserTransaction::mock()).unwrap();
+        let share = key_shares[0].decryption_share(&other).unwrap();
+        assert!(committee.verify_share(&encrypted, &share).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_with_invalid_share() {
+        let (committee, key_shares) = generate_committee(2, 3).unwrap();
+        let txn = SignedUserTransaction::mock();
+        let encrypted = committee.encrypt(&txn).unwrap();
+        let mut shares = key_shares
+            .iter()
+            .map(|key_share| key_share.decryption_share(&encrypted).unwrap())
+            .collect::<Vec<_>>();
+        // the share of member 2 is replaced by the share of member 3.
+        shares[1].share = shares[2].share.clone();
+        assert!(committee.decrypt(&encrypted, &shares[..2]).is_err());
+        let valid_shares = vec![shares[0].clone(), shares[2].clone()];
+        assert_eq!(committee.decrypt(&encrypted, &valid_shares).unwrap(), txn);
+    }
+}
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::TXPOOL_SERVICE_HISTOGRAM,
+    counters::{TXPOOL_SERVICE_HISTOGRAM, TXPOOL_TXN_IMPORT_COUNTER_VEC},
     pool,
     pool::{
         PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Status,
         TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
     },
     pool_client::{NonceCache, PoolClient},
+    txn_propagation::LocalTxnPropagation,
 };
 
 use crate::pool::{Client, TransactionQueue};
@@ -17,8 +18,14 @@ use crypto::hash::HashValue;
 use futures_channel::mpsc;
 use parking_lot::RwLock;
 use starcoin_config::NodeConfig;
+use starcoin_state_api::AccountStateReader;
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{
+    TxPoolAnalytics, TxPoolStatus, TxPoolSyncService, TxnCheck, TxnCheckResult, TxnValidation,
+};
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Arc;
 use storage::Store;
 use types::{
@@ -51,11 +58,14 @@ impl TxPoolService {
             },
             verifier_options,
             PrioritizationStrategy::GasPriceOnly,
+            pool_config.gas_price_bump_percent(),
         );
         let queue = Arc::new(queue);
+        let local_propagation = Arc::new(LocalTxnPropagation::new(pool_config));
         let inner = Inner {
             node_config,
             queue,
+            local_propagation,
             storage,
             chain_header: Arc::new(RwLock::new(chain_header)),
             sequence_number_cache: NonceCache::new(128),
@@ -68,6 +78,11 @@ impl TxPoolService {
         self.inner.storage.clone()
     }
 
+    /// Whether the first-hop propagation of the local txn is held for privacy.
+    pub fn is_propagation_held(&self, txn_hash: &HashValue) -> bool {
+        self.inner.local_propagation.is_held(txn_hash)
+    }
+
     pub(crate) fn from_inner(inner: Inner) -> TxPoolService {
         Self { inner }
     }
@@ -85,6 +100,30 @@ impl TxPoolService {
     }
 }
 
+/// The label of the txn import result, the rejected txns are labeled by the reason.
+fn import_result_label(result: &Result<(), transaction::TransactionError>) -> &'static str {
+    use transaction::TransactionError;
+    match result {
+        Ok(_) => "admitted",
+        Err(TransactionError::AlreadyImported) => "already_imported",
+        Err(TransactionError::Old) => "old",
+        Err(TransactionError::LimitReached) => "limit_reached",
+        Err(TransactionError::InsufficientGasPrice { .. }) => "insufficient_gas_price",
+        Err(TransactionError::TooCheapToReplace { .. }) => "too_cheap_to_replace",
+        Err(TransactionError::InsufficientGas { .. }) => "insufficient_gas",
+        Err(TransactionError::InsufficientBalance { .. }) => "insufficient_balance",
+        Err(TransactionError::GasLimitExceeded { .. }) => "gas_limit_exceeded",
+        Err(TransactionError::SenderBanned) => "sender_banned",
+        Err(TransactionError::RecipientBanned) => "recipient_banned",
+        Err(TransactionError::CodeBanned) => "code_banned",
+        Err(TransactionError::InvalidChainId) => "invalid_chain_id",
+        Err(TransactionError::NotAllowed) => "not_allowed",
+        Err(TransactionError::InvalidSignature(_)) => "invalid_signature",
+        Err(TransactionError::TooBig) => "too_big",
+        Err(TransactionError::CallErr(_)) => "call_error",
+    }
+}
+
 impl TxPoolSyncService for TxPoolService {
     fn add_txns(
         &self,
@@ -98,6 +137,34 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.import_txns(txns)
     }
 
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["add_local_txns"])
+            .start_timer();
+        // hold the txns before importing, so they are not propagated by the next propagation tick.
+        let txn_hashes = txns.iter().map(|txn| txn.id()).collect::<Vec<_>>();
+        for txn_hash in txn_hashes.iter() {
+            self.inner.local_propagation.hold(*txn_hash);
+        }
+        let results = self.inner.import_txns(txns);
+        for (txn_hash, result) in txn_hashes.iter().zip(results.iter()) {
+            if result.is_err() {
+                self.inner.local_propagation.unhold(txn_hash);
+            }
+        }
+        results
+    }
+
+    fn validate_txn(&self, txn: SignedUserTransaction) -> TxnValidation {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["validate_txn"])
+            .start_timer();
+        self.inner.validate_txn(txn)
+    }
+
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction> {
         let _timer = TXPOOL_SERVICE_HISTOGRAM
             .with_label_values(&["remove_txn"])
@@ -179,6 +246,14 @@ impl TxPoolSyncService for TxPoolService {
             .map(|t| t.signed().clone())
             .collect()
     }
+
+    fn analytics(&self, top_senders: usize) -> TxPoolAnalytics {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["analytics"])
+            .start_timer();
+        let now = self.inner.node_config.net().time_service().now_secs();
+        self.inner.analytics(top_senders, now)
+    }
 }
 
 pub(crate) type TxnQueue = TransactionQueue;
@@ -186,6 +261,7 @@ pub(crate) type TxnQueue = TransactionQueue;
 pub(crate) struct Inner {
     pub(crate) node_config: Arc<NodeConfig>,
     queue: Arc<TxnQueue>,
+    local_propagation: Arc<LocalTxnPropagation>,
     chain_header: Arc<RwLock<BlockHeader>>,
     storage: Arc<dyn Store>,
     sequence_number_cache: NonceCache,
@@ -204,6 +280,9 @@ impl Inner {
     pub(crate) fn queue(&self) -> Arc<TxnQueue> {
         self.queue.clone()
     }
+    pub(crate) fn local_propagation(&self) -> Arc<LocalTxnPropagation> {
+        self.local_propagation.clone()
+    }
     pub(crate) fn pool_status(&self) -> Status {
         self.queue.status()
     }
@@ -239,8 +318,105 @@ impl Inner {
         let txns = txns
             .into_iter()
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
-        self.queue.import(self.get_pool_client(), txns)
+        let results = self.queue.import(self.get_pool_client(), txns);
+        for result in &results {
+            TXPOOL_TXN_IMPORT_COUNTER_VEC
+                .with_label_values(&[import_result_label(result)])
+                .inc();
+        }
+        results
     }
+    pub(crate) fn validate_txn(&self, txn: SignedUserTransaction) -> TxnValidation {
+        let mut checks = vec![TxnCheckResult::new(
+            TxnCheck::Signature,
+            txn.clone().check_signature().err().map(|e| e.to_string()),
+        )];
+
+        let chain_id = self.node_config.net().chain_id();
+        checks.push(TxnCheckResult::new(
+            TxnCheck::ChainId,
+            if txn.chain_id() != chain_id {
+                Some(format!(
+                    "txn chain id {} mismatch with node chain id {}",
+                    txn.chain_id(),
+                    chain_id
+                ))
+            } else {
+                None
+            },
+        ));
+
+        let min_gas_price = self.node_config.tx_pool.min_gas_price();
+        checks.push(TxnCheckResult::new(
+            TxnCheck::GasPrice,
+            if txn.gas_unit_price() < min_gas_price {
+                Some(format!(
+                    "gas price {} is less than the min gas price {}",
+                    txn.gas_unit_price(),
+                    min_gas_price
+                ))
+            } else {
+                None
+            },
+        ));
+
+        let statedb = self.get_chain_reader();
+        let account_state_reader = AccountStateReader::new(&statedb);
+        let sequence_number_result = match account_state_reader.get_sequence_number(txn.sender()) {
+            Ok(sequence_number) if txn.sequence_number() < sequence_number => Some(format!(
+                "txn sequence number {} is too old, the account sequence number is {}",
+                txn.sequence_number(),
+                sequence_number
+            )),
+            Ok(_) => None,
+            Err(e) => Some(format!("get account sequence number error: {}", e)),
+        };
+        checks.push(TxnCheckResult::new(
+            TxnCheck::SequenceNumber,
+            sequence_number_result,
+        ));
+
+        let now = self.node_config.net().time_service().now_secs();
+        checks.push(TxnCheckResult::new(
+            TxnCheck::Expiration,
+            if txn.expiration_timestamp_secs() <= now {
+                Some(format!(
+                    "txn expired at {}, now is {}",
+                    txn.expiration_timestamp_secs(),
+                    now
+                ))
+            } else {
+                None
+            },
+        ));
+
+        let max_gas_fee = (txn.max_gas_amount() as u128) * (txn.gas_unit_price() as u128);
+        let balance_result = TokenCode::from_str(txn.gas_token_code())
+            .and_then(|token_code| {
+                account_state_reader.get_balance_by_token_code(&txn.sender(), token_code)
+            })
+            .map(|balance| balance.unwrap_or_default());
+        checks.push(TxnCheckResult::new(
+            TxnCheck::BalanceForGas,
+            match balance_result {
+                Ok(balance) if balance < max_gas_fee => Some(format!(
+                    "balance {} is not enough to pay the max gas fee {}",
+                    balance, max_gas_fee
+                )),
+                Ok(_) => None,
+                Err(e) => Some(format!("get gas token balance error: {}", e)),
+            },
+        ));
+
+        let txn_hash = txn.id();
+        checks.push(TxnCheckResult::new(
+            TxnCheck::VMValidation,
+            starcoin_executor::validate_transaction(&statedb, txn)
+                .map(|status| format!("{:?}", status)),
+        ));
+        TxnValidation::new(txn_hash, checks)
+    }
+
     pub(crate) fn remove_txn(
         &self,
         txn_hash: HashValue,
@@ -269,6 +445,24 @@ impl Inner {
         );
         self.queue.pending(self.get_pool_client(), pending_settings)
     }
+    pub(crate) fn analytics(
+        &self,
+        top_senders: usize,
+        current_timestamp_secs: u64,
+    ) -> TxPoolAnalytics {
+        let ready: HashSet<HashValue> = self
+            .get_pending(u64::max_value(), current_timestamp_secs)
+            .iter()
+            .map(|t| t.signed().id())
+            .collect();
+        let txns = self.queue.all_transactions();
+        TxPoolAnalytics::analyze(
+            txns.iter()
+                .map(|t| (t.signed(), ready.contains(&t.signed().id()))),
+            current_timestamp_secs,
+            top_senders,
+        )
+    }
     pub(crate) fn next_sequence_number(&self, address: AccountAddress) -> Option<u64> {
         self.queue
             .next_sequence_number(self.get_pool_client(), &address)
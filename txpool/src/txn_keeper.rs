@@ -0,0 +1,389 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pool::scoring::bump_gas_price;
+use crate::TxPoolService;
+use anyhow::{ensure, format_err, Result};
+use crypto::HashValue;
+use starcoin_account_api::AccountAsyncService;
+use starcoin_account_service::AccountService;
+use starcoin_config::NodeConfig;
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
+    ServiceRequest,
+};
+use starcoin_txpool_api::{PropagateTransactions, TxPoolSyncService, TxnKeepPolicy};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use types::system_events::NewHeadBlock;
+use types::transaction::{RawUserTransaction, SignedUserTransaction};
+
+const KEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub struct SubmitTxnWithPolicyRequest {
+    pub txn: SignedUserTransaction,
+    pub policy: TxnKeepPolicy,
+}
+
+impl ServiceRequest for SubmitTxnWithPolicyRequest {
+    type Response = Result<HashValue>;
+}
+
+#[derive(Clone, Debug)]
+pub struct KeepTxnsEvent;
+
+#[derive(Clone, Debug)]
+pub struct TxnResignedEvent {
+    txn_hash: HashValue,
+    /// The re-signed txn, None if the re-sign failed.
+    new_txn: Option<SignedUserTransaction>,
+}
+
+struct KeptTxn {
+    txn: SignedUserTransaction,
+    policy: TxnKeepPolicy,
+    /// The lifetime of the submitted txn, the re-signed txn has the same lifetime.
+    ttl: u64,
+    resigning: bool,
+}
+
+impl KeptTxn {
+    /// The txn to re-sign with the bumped gas price, `None` if the txn should not be re-signed now,
+    /// or the bumped gas price exceeds the max gas price of the policy.
+    fn resign_raw_txn(&self, now: u64, bump_percent: u64) -> Option<RawUserTransaction> {
+        let expiration = self.txn.expiration_timestamp_secs();
+        if !self.policy.resign
+            || self.resigning
+            || expiration.saturating_sub(now) > self.policy.resign_before_expiration()
+        {
+            return None;
+        }
+        let gas_price = bump_gas_price(self.txn.gas_unit_price(), bump_percent);
+        if gas_price > self.policy.max_gas_price.unwrap_or_default() {
+            return None;
+        }
+        let raw_txn = self.txn.raw_txn();
+        Some(RawUserTransaction::new(
+            raw_txn.sender(),
+            raw_txn.sequence_number(),
+            raw_txn.payload().clone(),
+            raw_txn.max_gas_amount(),
+            gas_price,
+            now.saturating_add(self.ttl),
+            raw_txn.chain_id(),
+            raw_txn.gas_token_code(),
+        ))
+    }
+
+    /// Stop re-signing the txn after a failed re-sign, such as the sender account is locked,
+    /// the txn is still kept and re-broadcast.
+    fn stop_resign(&mut self) {
+        self.resigning = false;
+        self.policy.resign = false;
+    }
+}
+
+/// Check the policy of the submitted txn, `can_resign` is false if the node can not sign the txn.
+fn check_policy(
+    txn: &SignedUserTransaction,
+    policy: &TxnKeepPolicy,
+    can_resign: bool,
+) -> Result<()> {
+    if policy.resign {
+        ensure!(
+            can_resign,
+            "Account service is not available to re-sign the txn."
+        );
+        let max_gas_price = policy
+            .max_gas_price
+            .ok_or_else(|| format_err!("The max gas price is required to re-sign the txn."))?;
+        ensure!(
+            max_gas_price >= txn.gas_unit_price(),
+            "The max gas price {} is lower than the gas price {} of the txn.",
+            max_gas_price,
+            txn.gas_unit_price()
+        );
+    }
+    Ok(())
+}
+
+/// Keep the txns submitted with a policy until they are included in a block,
+/// re-broadcast them to peers, and re-sign them with a higher gas price when the expiration nears.
+pub struct TxnKeeperService {
+    config: Arc<NodeConfig>,
+    txpool: TxPoolService,
+    account_service: Option<ServiceRef<AccountService>>,
+    /// The kept txns by the hash of the latest signed txn.
+    txns: HashMap<HashValue, KeptTxn>,
+}
+
+impl TxnKeeperService {
+    fn now(&self) -> u64 {
+        self.config.net().time_service().now_secs()
+    }
+
+    fn add_txn(&self, txn: SignedUserTransaction) -> Result<()> {
+        Ok(self
+            .txpool
+            .add_local_txns(vec![txn])
+            .pop()
+            .expect("txpool should return result")?)
+    }
+}
+
+impl ServiceFactory<Self> for TxnKeeperService {
+    fn create(ctx: &mut ServiceContext<TxnKeeperService>) -> Result<TxnKeeperService> {
+        Ok(Self {
+            config: ctx.get_shared::<Arc<NodeConfig>>()?,
+            txpool: ctx.get_shared::<TxPoolService>()?,
+            account_service: ctx.service_ref_opt::<AccountService>()?.cloned(),
+            txns: HashMap::new(),
+        })
+    }
+}
+
+impl ActorService for TxnKeeperService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        ctx.run_interval(KEEP_INTERVAL, |ctx| ctx.notify(KeepTxnsEvent));
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl ServiceHandler<Self, SubmitTxnWithPolicyRequest> for TxnKeeperService {
+    fn handle(
+        &mut self,
+        msg: SubmitTxnWithPolicyRequest,
+        _ctx: &mut ServiceContext<TxnKeeperService>,
+    ) -> Result<HashValue> {
+        let SubmitTxnWithPolicyRequest { txn, policy } = msg;
+        check_policy(&txn, &policy, self.account_service.is_some())?;
+        let txn_hash = txn.id();
+        let ttl = txn.expiration_timestamp_secs().saturating_sub(self.now());
+        self.add_txn(txn.clone())?;
+        self.txns.insert(
+            txn_hash,
+            KeptTxn {
+                txn,
+                policy,
+                ttl,
+                resigning: false,
+            },
+        );
+        Ok(txn_hash)
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for TxnKeeperService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<Self>) {
+        let block = msg.0.block();
+        for txn in block.transactions() {
+            if self.txns.remove(&txn.id()).is_some() {
+                info!(
+                    "[txn-keeper] Txn {} is included in block {}",
+                    txn.id(),
+                    block.id()
+                );
+            }
+        }
+    }
+}
+
+impl EventHandler<Self, KeepTxnsEvent> for TxnKeeperService {
+    fn handle_event(&mut self, _msg: KeepTxnsEvent, ctx: &mut ServiceContext<Self>) {
+        let now = self.now();
+        let bump_percent = self.config.tx_pool.gas_price_bump_percent();
+        let mut dropped = vec![];
+        let mut to_propagate = vec![];
+        for (txn_hash, kept) in self.txns.iter() {
+            if kept.txn.expiration_timestamp_secs() <= now {
+                warn!("[txn-keeper] Txn {} is expired, stop keeping it.", txn_hash);
+                dropped.push(*txn_hash);
+                continue;
+            }
+            // The txn may be removed from the pool, add it back,
+            // it fails if the txn is included or invalid.
+            if self.txpool.find_txn(txn_hash).is_none() {
+                if let Err(e) = self.add_txn(kept.txn.clone()) {
+                    info!("[txn-keeper] Stop keeping txn {}: {}", txn_hash, e);
+                    dropped.push(*txn_hash);
+                    continue;
+                }
+            }
+            if !self.txpool.is_propagation_held(txn_hash) {
+                to_propagate.push(kept.txn.clone());
+            }
+        }
+        for txn_hash in dropped {
+            self.txns.remove(&txn_hash);
+        }
+        if !to_propagate.is_empty() {
+            ctx.broadcast(PropagateTransactions::new(to_propagate));
+        }
+
+        let account_service = match self.account_service.as_ref() {
+            Some(account_service) => account_service,
+            None => return,
+        };
+        for (txn_hash, kept) in self.txns.iter_mut() {
+            let new_raw_txn = match kept.resign_raw_txn(now, bump_percent) {
+                Some(new_raw_txn) => new_raw_txn,
+                None => continue,
+            };
+            let sender = new_raw_txn.sender();
+            kept.resigning = true;
+            let txn_hash = *txn_hash;
+            let account_service = account_service.clone();
+            let self_ref = ctx.self_ref();
+            ctx.spawn(async move {
+                let new_txn = match account_service.sign_txn(new_raw_txn, sender).await {
+                    Ok(new_txn) => Some(new_txn),
+                    Err(e) => {
+                        warn!("[txn-keeper] Re-sign txn {} failed: {}", txn_hash, e);
+                        None
+                    }
+                };
+                if let Err(e) = self_ref.notify(TxnResignedEvent { txn_hash, new_txn }) {
+                    error!(
+                        "[txn-keeper] Notify re-signed txn {} failed: {}",
+                        txn_hash, e
+                    );
+                }
+            });
+        }
+    }
+}
+
+impl EventHandler<Self, TxnResignedEvent> for TxnKeeperService {
+    fn handle_event(&mut self, msg: TxnResignedEvent, ctx: &mut ServiceContext<Self>) {
+        // the txn may be included or dropped while re-signing.
+        let mut kept = match self.txns.remove(&msg.txn_hash) {
+            Some(kept) => kept,
+            None => return,
+        };
+        if let Some(new_txn) = msg.new_txn {
+            let new_txn_hash = new_txn.id();
+            match self.add_txn(new_txn.clone()) {
+                Ok(()) => {
+                    info!(
+                        "[txn-keeper] Re-sign txn {} to {} with gas price {}",
+                        msg.txn_hash,
+                        new_txn_hash,
+                        new_txn.gas_unit_price()
+                    );
+                    if !self.txpool.is_propagation_held(&new_txn_hash) {
+                        ctx.broadcast(PropagateTransactions::new(vec![new_txn.clone()]));
+                    }
+                    kept.txn = new_txn;
+                    kept.resigning = false;
+                    self.txns.insert(new_txn_hash, kept);
+                    return;
+                }
+                Err(e) => warn!(
+                    "[txn-keeper] Add re-signed txn {} failed: {}",
+                    new_txn_hash, e
+                ),
+            }
+        }
+        // Do not retry the failed re-sign, such as the account is locked.
+        kept.stop_resign();
+        self.txns.insert(msg.txn_hash, kept);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::ed25519::genesis_key_pair;
+    use types::account_address::AccountAddress;
+    use types::genesis_config::ChainId;
+    use types::transaction::{Script, TransactionPayload};
+
+    fn kept_txn(gas_price: u64, expiration: u64, policy: TxnKeepPolicy) -> KeptTxn {
+        let (private_key, public_key) = genesis_key_pair();
+        let txn = RawUserTransaction::new_with_default_gas_token(
+            AccountAddress::random(),
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            10000,
+            gas_price,
+            expiration,
+            ChainId::test(),
+        )
+        .sign(&private_key, public_key)
+        .unwrap()
+        .into_inner();
+        KeptTxn {
+            txn,
+            policy,
+            ttl: 600,
+            resigning: false,
+        }
+    }
+
+    fn resign_policy(max_gas_price: u64) -> TxnKeepPolicy {
+        TxnKeepPolicy {
+            resign: true,
+            max_gas_price: Some(max_gas_price),
+            resign_before_expiration: Some(60),
+        }
+    }
+
+    #[test]
+    fn test_resign_gas_bump() {
+        let kept = kept_txn(100, 1000, resign_policy(200));
+        // the expiration is not near.
+        assert!(kept.resign_raw_txn(900, 10).is_none());
+        let raw_txn = kept.resign_raw_txn(950, 10).unwrap();
+        assert_eq!(raw_txn.gas_unit_price(), 110);
+        assert_eq!(raw_txn.sequence_number(), kept.txn.sequence_number());
+        assert_eq!(raw_txn.sender(), kept.txn.sender());
+        assert_eq!(raw_txn.expiration_timestamp_secs(), 950 + kept.ttl);
+
+        let mut resigning = kept_txn(100, 1000, resign_policy(200));
+        resigning.resigning = true;
+        assert!(resigning.resign_raw_txn(950, 10).is_none());
+
+        let not_resign = kept_txn(100, 1000, TxnKeepPolicy::default());
+        assert!(not_resign.resign_raw_txn(950, 10).is_none());
+    }
+
+    #[test]
+    fn test_resign_max_gas_price() {
+        let kept = kept_txn(100, 1000, resign_policy(110));
+        assert_eq!(kept.resign_raw_txn(950, 10).unwrap().gas_unit_price(), 110);
+        // the bumped gas price exceeds the cap.
+        assert!(kept.resign_raw_txn(950, 11).is_none());
+
+        let txn = kept.txn.clone();
+        assert!(check_policy(&txn, &resign_policy(100), true).is_ok());
+        assert!(check_policy(&txn, &resign_policy(99), true).is_err());
+        let mut no_cap = resign_policy(100);
+        no_cap.max_gas_price = None;
+        assert!(check_policy(&txn, &no_cap, true).is_err());
+    }
+
+    #[test]
+    fn test_resign_without_unlocked_account() {
+        let kept = kept_txn(100, 1000, resign_policy(200));
+        // the node can not sign the txn without the account service.
+        assert!(check_policy(&kept.txn, &kept.policy, false).is_err());
+        assert!(check_policy(&kept.txn, &TxnKeepPolicy::default(), false).is_ok());
+
+        // the re-sign fails if the sender account is not unlocked in the node,
+        // the txn is kept without re-signing again.
+        let mut kept = kept;
+        kept.resigning = true;
+        kept.stop_resign();
+        assert!(!kept.resigning);
+        assert!(!kept.policy.resign);
+        assert!(kept.resign_raw_txn(950, 10).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::HashValue;
+use parking_lot::Mutex;
+use rand::Rng;
+use starcoin_config::TxPoolConfig;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The relayed txns are broadcast if they are still pending after the duration.
+const RELAY_FALLBACK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum HoldStage {
+    /// Wait for the propagation delay.
+    Delayed,
+    /// Relayed to a few peers, wait for the fallback.
+    Relayed,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HeldTxn {
+    stage: HoldStage,
+    until: Instant,
+}
+
+/// Hold the first-hop propagation of the locally submitted txns for privacy.
+///
+/// A held txn is excluded from the txn propagation until the propagation delay with a random jitter,
+/// then it is relayed to a few random peers if `relay_peers` is set, or broadcast as usual.
+/// The relayed txn is broadcast by this node only if it is still pending after `RELAY_FALLBACK`.
+#[derive(Debug)]
+pub(crate) struct LocalTxnPropagation {
+    delay: Duration,
+    jitter: Duration,
+    relay_peers: u32,
+    held: Mutex<HashMap<HashValue, HeldTxn>>,
+}
+
+impl LocalTxnPropagation {
+    pub fn new(config: &TxPoolConfig) -> Self {
+        Self {
+            delay: Duration::from_secs(config.propagation_delay()),
+            jitter: Duration::from_secs(config.propagation_jitter()),
+            relay_peers: config.relay_peers(),
+            held: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.delay > Duration::from_secs(0)
+            || self.jitter > Duration::from_secs(0)
+            || self.relay_peers > 0
+    }
+
+    pub fn relay_peers(&self) -> u32 {
+        self.relay_peers
+    }
+
+    pub fn hold(&self, txn_hash: HashValue) {
+        if !self.is_enabled() {
+            return;
+        }
+        let jitter_millis = self.jitter.as_millis() as u64;
+        let jitter = if jitter_millis > 0 {
+            Duration::from_millis(rand::thread_rng().gen_range(0..=jitter_millis))
+        } else {
+            Duration::from_secs(0)
+        };
+        self.held.lock().insert(
+            txn_hash,
+            HeldTxn {
+                stage: HoldStage::Delayed,
+                until: Instant::now() + self.delay + jitter,
+            },
+        );
+    }
+
+    pub fn unhold(&self, txn_hash: &HashValue) {
+        self.held.lock().remove(txn_hash);
+    }
+
+    pub fn is_held(&self, txn_hash: &HashValue) -> bool {
+        self.held.lock().contains_key(txn_hash)
+    }
+
+    /// Move the due txns to the next stage, return the txns to relay,
+    /// and whether some txns are released to the normal propagation.
+    pub fn poll(&self, now: Instant) -> (Vec<HashValue>, bool) {
+        let mut to_relay = vec![];
+        let mut released = false;
+        let relay_peers = self.relay_peers;
+        self.held.lock().retain(|txn_hash, held| {
+            if held.until > now {
+                return true;
+            }
+            match held.stage {
+                HoldStage::Delayed if relay_peers > 0 => {
+                    to_relay.push(*txn_hash);
+                    held.stage = HoldStage::Relayed;
+                    held.until = now + RELAY_FALLBACK;
+                    true
+                }
+                _ => {
+                    released = true;
+                    false
+                }
+            }
+        });
+        (to_relay, released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn propagation(delay: u64, jitter: u64, relay_peers: u32) -> LocalTxnPropagation {
+        LocalTxnPropagation {
+            delay: Duration::from_secs(delay),
+            jitter: Duration::from_secs(jitter),
+            relay_peers,
+            held: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn test_local_txn_propagation() {
+        let txn_hash = HashValue::random();
+        let disabled = propagation(0, 0, 0);
+        disabled.hold(txn_hash);
+        assert!(!disabled.is_held(&txn_hash));
+
+        let delayed = propagation(10, 5, 0);
+        let now = Instant::now();
+        delayed.hold(txn_hash);
+        assert!(delayed.is_held(&txn_hash));
+        assert_eq!(delayed.poll(now), (vec![], false));
+        assert_eq!(delayed.poll(now + Duration::from_secs(16)), (vec![], true));
+        assert!(!delayed.is_held(&txn_hash));
+
+        let relayed = propagation(10, 0, 2);
+        let now = Instant::now();
+        relayed.hold(txn_hash);
+        assert_eq!(
+            relayed.poll(now + Duration::from_secs(11)),
+            (vec![txn_hash], false)
+        );
+        assert!(relayed.is_held(&txn_hash));
+        assert_eq!(relayed.poll(now + Duration::from_secs(12)), (vec![], false));
+        assert_eq!(
+            relayed.poll(now + Duration::from_secs(12) + RELAY_FALLBACK),
+            (vec![], true)
+        );
+        assert!(!relayed.is_held(&txn_hash));
+    }
+}
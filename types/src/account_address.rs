@@ -7,7 +7,9 @@
 use crate::transaction::authenticator::AuthenticationKey;
 use starcoin_crypto::ed25519::Ed25519PublicKey;
 
-pub use starcoin_vm_types::account_address::AccountAddress;
+pub use starcoin_vm_types::account_address::{
+    looks_like_receipt_identifier, parse_address, AccountAddress, AddressParseError,
+};
 
 pub fn from_public_key(public_key: &Ed25519PublicKey) -> AccountAddress {
     AuthenticationKey::ed25519(public_key).derived_address()
@@ -271,6 +271,7 @@ impl BlockHeader {
         timestamp: u64,
         txn_accumulator_root: HashValue,
         state_root: HashValue,
+        gas_used: u64,
         difficulty: U256,
         body_hash: HashValue,
         chain_id: ChainId,
@@ -284,7 +285,7 @@ impl BlockHeader {
             txn_accumulator_root,
             *ACCUMULATOR_PLACEHOLDER_HASH,
             state_root,
-            0,
+            gas_used,
             difficulty,
             body_hash,
             chain_id,
@@ -664,14 +665,41 @@ impl Block {
         state_root: HashValue,
         difficulty: U256,
         genesis_txn: SignedUserTransaction,
+    ) -> Self {
+        Self::genesis_block_with_txns(
+            parent_hash,
+            timestamp,
+            accumulator_root,
+            state_root,
+            0,
+            difficulty,
+            genesis_txn,
+            vec![],
+        )
+    }
+
+    /// Build the genesis block with the txns executed after the genesis txn, such as the txns to fund the initial accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn genesis_block_with_txns(
+        parent_hash: HashValue,
+        timestamp: u64,
+        accumulator_root: HashValue,
+        state_root: HashValue,
+        gas_used: u64,
+        difficulty: U256,
+        genesis_txn: SignedUserTransaction,
+        txns: Vec<SignedUserTransaction>,
     ) -> Self {
         let chain_id = genesis_txn.chain_id();
-        let block_body = BlockBody::new(vec![genesis_txn], None);
+        let mut body_txns = vec![genesis_txn];
+        body_txns.extend(txns);
+        let block_body = BlockBody::new(body_txns, None);
         let header = BlockHeader::genesis_block_header(
             parent_hash,
             timestamp,
             accumulator_root,
             state_root,
+            gas_used,
             difficulty,
             block_body.hash(),
             chain_id,
@@ -817,6 +845,8 @@ pub struct BlockTemplate {
     pub state_root: HashValue,
     /// Gas used for contracts execution.
     pub gas_used: u64,
+    /// The sum of gas_used * gas_unit_price of the user txns, 0 if unknown.
+    pub total_fee: u128,
     /// hash for block body
     pub body_hash: HashValue,
     /// body of the block
@@ -835,6 +865,7 @@ impl BlockTemplate {
         accumulator_root: HashValue,
         state_root: HashValue,
         gas_used: u64,
+        total_fee: u128,
         body: BlockBody,
         chain_id: ChainId,
         difficulty: U256,
@@ -853,6 +884,7 @@ impl BlockTemplate {
             txn_accumulator_root: accumulator_root,
             state_root,
             gas_used,
+            total_fee,
             body_hash: body.hash(),
             body,
             chain_id,
@@ -945,6 +977,7 @@ impl BlockTemplate {
             txn_accumulator_root: block.header().txn_accumulator_root,
             state_root: block.header().state_root,
             gas_used: block.header().gas_used,
+            total_fee: 0,
             body: block.body,
             body_hash: block.header.body_hash,
             chain_id: block.header.chain_id,
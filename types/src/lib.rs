@@ -85,9 +85,7 @@ pub mod genesis_config {
 pub mod stress_test;
 pub mod sync_status;
 
-pub mod proof {
-    pub use forkable_jellyfish_merkle::proof::SparseMerkleProof;
-}
+pub mod proof;
 
 pub mod receipt_identifier {
     pub use starcoin_vm_types::receipt_identifier::*;
@@ -0,0 +1,51 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Proof types and helpers for light clients to verify data against a trusted block header.
+
+use crate::block::BlockHeader;
+use crate::transaction::TransactionInfo;
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+
+pub use forkable_jellyfish_merkle::proof::SparseMerkleProof;
+pub use starcoin_accumulator::proof::AccumulatorProof;
+
+/// A transaction info with its inclusion proof in the txn accumulator of a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionInfoWithProof {
+    pub transaction_info: TransactionInfo,
+    /// The leaf index of the transaction info in the txn accumulator.
+    pub transaction_global_index: u64,
+    pub proof: AccumulatorProof,
+}
+
+impl TransactionInfoWithProof {
+    pub fn new(
+        transaction_info: TransactionInfo,
+        transaction_global_index: u64,
+        proof: AccumulatorProof,
+    ) -> Self {
+        Self {
+            transaction_info,
+            transaction_global_index,
+            proof,
+        }
+    }
+
+    /// Verify the transaction info of `txn_hash` is included in the txn accumulator of the trusted `header`.
+    pub fn verify(&self, header: &BlockHeader, txn_hash: HashValue) -> Result<()> {
+        ensure!(
+            self.transaction_info.transaction_hash() == txn_hash,
+            "Transaction hash mismatch, expect: {}, got: {}",
+            txn_hash,
+            self.transaction_info.transaction_hash()
+        );
+        self.proof.verify(
+            header.txn_accumulator_root(),
+            self.transaction_info.id(),
+            self.transaction_global_index,
+        )
+    }
+}
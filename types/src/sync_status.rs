@@ -101,6 +101,16 @@ impl SyncStatus {
         }
     }
 
+    /// The count of blocks the head is behind the sync target, 0 if not synchronizing.
+    pub fn lag(&self) -> u64 {
+        match &self.state {
+            SyncState::Synchronizing { target, .. } => target
+                .number()
+                .saturating_sub(self.chain_status.head().number()),
+            _ => 0,
+        }
+    }
+
     pub fn is_synced(&self) -> bool {
         self.state.is_synced()
     }
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{Block, ExecutedBlock};
+use crate::block::{Block, BlockHeaderExtra, ExecutedBlock};
 use crate::sync_status::SyncStatus;
 use crate::U256;
 use serde::{Deserialize, Serialize};
@@ -66,3 +66,15 @@ impl MintBlockEvent {
         }
     }
 }
+
+/// A mining work for pool worker, the worker rolls the nonce and the extranonce region of `extra`,
+/// so the pool can split the nonce space across workers without requesting a new template.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiningWork {
+    pub job: MintBlockEvent,
+    pub worker_id: u32,
+    /// The extra with worker prefix, the extranonce region is zero.
+    pub extra: BlockHeaderExtra,
+    /// The byte size of extranonce region at the end of extra.
+    pub extranonce_size: u8,
+}
@@ -1,12 +1,14 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::sandbox::{run_in_sandbox, SandboxConfig};
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
 use starcoin_state_api::StateNodeStore;
 use starcoin_statedb::ChainStateDB;
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::gas_schedule::CostTable;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
 use starcoin_vm_types::state_view::StateView;
@@ -19,11 +21,20 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct PlaygroudService {
     state: Arc<dyn StateNodeStore>,
+    sandbox: SandboxConfig,
 }
 
 impl PlaygroudService {
     pub fn new(state_store: Arc<dyn StateNodeStore>) -> Self {
-        Self { state: state_store }
+        Self::new_with_sandbox(state_store, SandboxConfig::default())
+    }
+
+    /// The dry run and contract call are executed in a sandbox with the `sandbox` resource ceilings.
+    pub fn new_with_sandbox(state_store: Arc<dyn StateNodeStore>, sandbox: SandboxConfig) -> Self {
+        Self {
+            state: state_store,
+            sandbox,
+        }
     }
 }
 
@@ -34,7 +45,9 @@ impl PlaygroudService {
         txn: DryRunTransaction,
     ) -> Result<(VMStatus, TransactionOutput)> {
         let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
-        dry_run(&state_view, txn)
+        run_in_sandbox(self.sandbox, state_view, move |state_view| {
+            dry_run(state_view, txn)
+        })
     }
 
     pub fn call_contract(
@@ -46,8 +59,9 @@ impl PlaygroudService {
         args: Vec<TransactionArgument>,
     ) -> Result<Vec<AnnotatedMoveValue>> {
         let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
-        let rets = call_contract(&state_view, module_id, func.as_str(), type_args, args)?;
-        Ok(rets)
+        run_in_sandbox(self.sandbox, state_view, move |state_view| {
+            call_contract(state_view, module_id, func.as_str(), type_args, args)
+        })
     }
     pub fn view_resource(
         &self,
@@ -78,6 +92,16 @@ pub fn dry_run(
     vm.dry_run_transaction(state_view, txn)
 }
 
+/// Dry run the txn with the `gas_schedule` instead of the on chain gas schedule.
+pub fn dry_run_with_gas_schedule(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+    gas_schedule: CostTable,
+) -> Result<(VMStatus, TransactionOutput)> {
+    let mut vm = StarcoinVM::new_with_gas_schedule(gas_schedule);
+    vm.dry_run_transaction(state_view, txn)
+}
+
 pub fn call_contract(
     state_view: &dyn StateView,
     module_id: ModuleId,
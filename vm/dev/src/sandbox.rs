@@ -0,0 +1,357 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_logger::prelude::*;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::state_view::StateView;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_SANDBOX_TIME_LIMIT: Duration = Duration::from_secs(5);
+/// 64M
+pub const DEFAULT_SANDBOX_STATE_READ_LIMIT: u64 = 64 * 1024 * 1024;
+/// The max count of the sandbox threads running at the same time, include the killed executions
+/// which have not reached their next state access yet.
+pub const MAX_RUNNING_SANDBOXES: usize = 16;
+
+static RUNNING_SANDBOXES: SandboxSlots = SandboxSlots::new(MAX_RUNNING_SANDBOXES);
+
+/// Resource ceilings of a sandboxed execution, independent of gas.
+/// The limits are enforced at the state accesses of the execution,
+/// the execution without state access is only bounded by gas.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SandboxConfig {
+    /// Max wall-clock time of the execution.
+    pub time_limit: Duration,
+    /// Max bytes of state read by the execution.
+    pub state_read_limit: u64,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            time_limit: DEFAULT_SANDBOX_TIME_LIMIT,
+            state_read_limit: DEFAULT_SANDBOX_STATE_READ_LIMIT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum SandboxError {
+    #[error("Execution exceeded the time limit of {limit_ms} ms")]
+    TimeExceeded { limit_ms: u64 },
+    #[error(
+        "Execution exceeded the state read limit of {limit_bytes} bytes, read {read_bytes} bytes"
+    )]
+    StateReadExceeded { limit_bytes: u64, read_bytes: u64 },
+    #[error("Too many sandboxed executions are running, the limit is {limit}")]
+    Busy { limit: usize },
+    #[error("Execution aborted: {0}")]
+    Aborted(String),
+}
+
+/// Track the resource usage of a sandboxed execution.
+struct SandboxMonitor {
+    config: SandboxConfig,
+    start: Instant,
+    read_bytes: AtomicU64,
+    killed: AtomicBool,
+    exceeded: Mutex<Option<SandboxError>>,
+}
+
+impl SandboxMonitor {
+    fn new(config: SandboxConfig) -> Self {
+        Self {
+            config,
+            start: Instant::now(),
+            read_bytes: AtomicU64::new(0),
+            killed: AtomicBool::new(false),
+            exceeded: Mutex::new(None),
+        }
+    }
+
+    fn time_exceeded(&self) -> SandboxError {
+        SandboxError::TimeExceeded {
+            limit_ms: self.config.time_limit.as_millis() as u64,
+        }
+    }
+
+    fn record_exceeded(&self, err: SandboxError) -> SandboxError {
+        let mut exceeded = self.exceeded.lock().expect("lock should not be poisoned");
+        exceeded.get_or_insert(err).clone()
+    }
+
+    fn kill(&self) {
+        self.killed.store(true, Ordering::SeqCst);
+    }
+
+    fn check_time(&self) -> Result<(), SandboxError> {
+        if self.killed.load(Ordering::SeqCst) || self.start.elapsed() > self.config.time_limit {
+            return Err(self.record_exceeded(self.time_exceeded()));
+        }
+        Ok(())
+    }
+
+    fn consume_state_read(&self, bytes: u64) -> Result<(), SandboxError> {
+        let read_bytes = self.read_bytes.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        if read_bytes > self.config.state_read_limit {
+            return Err(self.record_exceeded(SandboxError::StateReadExceeded {
+                limit_bytes: self.config.state_read_limit,
+                read_bytes,
+            }));
+        }
+        Ok(())
+    }
+
+    fn exceeded(&self) -> Option<SandboxError> {
+        self.exceeded
+            .lock()
+            .expect("lock should not be poisoned")
+            .clone()
+    }
+}
+
+/// Count the running sandbox threads.
+struct SandboxSlots {
+    running: AtomicUsize,
+    limit: usize,
+}
+
+impl SandboxSlots {
+    const fn new(limit: usize) -> Self {
+        Self {
+            running: AtomicUsize::new(0),
+            limit,
+        }
+    }
+
+    fn acquire(&'static self) -> Result<SandboxSlot, SandboxError> {
+        let limit = self.limit;
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                if running < limit {
+                    Some(running + 1)
+                } else {
+                    None
+                }
+            })
+            .map(|_| SandboxSlot { slots: self })
+            .map_err(|_| SandboxError::Busy { limit })
+    }
+
+    fn running(&self) -> usize {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+/// The slot of a running sandbox thread, released when the thread exits.
+struct SandboxSlot {
+    slots: &'static SandboxSlots,
+}
+
+impl Drop for SandboxSlot {
+    fn drop(&mut self) {
+        self.slots.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A state view which fails the state access once the execution exceeds the limits,
+/// so the runaway execution is stopped at its next state access.
+struct LimitedStateView<S> {
+    inner: S,
+    monitor: Arc<SandboxMonitor>,
+}
+
+impl<S> StateView for LimitedStateView<S>
+where
+    S: StateView,
+{
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        self.monitor.check_time()?;
+        let value = self.inner.get(access_path)?;
+        if let Some(value) = &value {
+            self.monitor.consume_state_read(value.len() as u64)?;
+        }
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
+
+/// Run `f` on the `state_view` in a dedicated thread with the resource ceilings of `config`.
+/// If the execution exceeds the limits, a `SandboxError` is returned,
+/// the execution is killed at its next state access, and its result is discarded.
+/// The killed thread holds its slot until it exits, so at most `MAX_RUNNING_SANDBOXES`
+/// threads are running, and the new execution is rejected when all the slots are taken.
+pub fn run_in_sandbox<S, T, F>(config: SandboxConfig, state_view: S, f: F) -> Result<T>
+where
+    S: StateView + Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(&dyn StateView) -> Result<T> + Send + 'static,
+{
+    run_in_slots(&RUNNING_SANDBOXES, config, state_view, f)
+}
+
+fn run_in_slots<S, T, F>(
+    slots: &'static SandboxSlots,
+    config: SandboxConfig,
+    state_view: S,
+    f: F,
+) -> Result<T>
+where
+    S: StateView + Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(&dyn StateView) -> Result<T> + Send + 'static,
+{
+    let slot = slots.acquire()?;
+    let monitor = Arc::new(SandboxMonitor::new(config));
+    let limited_state_view = LimitedStateView {
+        inner: state_view,
+        monitor: monitor.clone(),
+    };
+    let (sender, receiver) = mpsc::sync_channel(1);
+    thread::Builder::new()
+        .name("dev-sandbox".to_string())
+        .spawn(move || {
+            let _slot = slot;
+            let result = f(&limited_state_view);
+            // the receiver may be dropped when timeout.
+            let _ = sender.send(result);
+        })?;
+    match receiver.recv_timeout(config.time_limit) {
+        Ok(result) => match monitor.exceeded() {
+            // the VM may convert the state view error to a vm status, so check the monitor first.
+            Some(err) => Err(err.into()),
+            None => result,
+        },
+        Err(RecvTimeoutError::Timeout) => {
+            warn!(
+                "Sandboxed execution exceeded the time limit {:?}, kill it, running sandboxes: {}.",
+                config.time_limit,
+                slots.running()
+            );
+            monitor.kill();
+            Err(monitor.record_exceeded(monitor.time_exceeded()).into())
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(SandboxError::Aborted("sandbox thread panicked".to_string()).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_vm_types::account_address::AccountAddress;
+    use starcoin_vm_types::identifier::Identifier;
+
+    struct MockStateView;
+
+    impl StateView for MockStateView {
+        fn get(&self, _access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+            Ok(Some(vec![0u8; 1024]))
+        }
+
+        fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+            access_paths.iter().map(|path| self.get(path)).collect()
+        }
+
+        fn is_genesis(&self) -> bool {
+            false
+        }
+    }
+
+    fn access_path() -> AccessPath {
+        AccessPath::code_access_path(AccountAddress::random(), Identifier::new("Test").unwrap())
+    }
+
+    #[test]
+    fn test_sandbox_state_read_limit() {
+        let config = SandboxConfig {
+            time_limit: Duration::from_secs(5),
+            state_read_limit: 4096,
+        };
+        let result = run_in_sandbox(config, MockStateView, |state_view| {
+            for _ in 0..4 {
+                state_view.get(&access_path())?;
+            }
+            Ok(())
+        });
+        assert!(result.is_ok());
+        let result = run_in_sandbox(config, MockStateView, |state_view| {
+            for _ in 0..5 {
+                // ignore the error as VM may do.
+                let _ = state_view.get(&access_path());
+            }
+            Ok(())
+        });
+        assert!(matches!(
+            result.unwrap_err().downcast::<SandboxError>().unwrap(),
+            SandboxError::StateReadExceeded { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sandbox_time_limit() {
+        let config = SandboxConfig {
+            time_limit: Duration::from_millis(100),
+            state_read_limit: DEFAULT_SANDBOX_STATE_READ_LIMIT,
+        };
+        let result = run_in_sandbox(config, MockStateView, |state_view| -> Result<()> {
+            loop {
+                state_view.get(&access_path())?;
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+        assert!(matches!(
+            result.unwrap_err().downcast::<SandboxError>().unwrap(),
+            SandboxError::TimeExceeded { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sandbox_busy() {
+        static SLOTS: SandboxSlots = SandboxSlots::new(1);
+        let config = SandboxConfig {
+            time_limit: Duration::from_millis(100),
+            state_read_limit: DEFAULT_SANDBOX_STATE_READ_LIMIT,
+        };
+        let (sender, receiver) = mpsc::channel::<()>();
+        let (exit_sender, exit_receiver) = mpsc::channel::<()>();
+        let result = run_in_slots(&SLOTS, config, MockStateView, move |_state_view| {
+            let _ = receiver.recv();
+            let _ = exit_sender.send(());
+            Ok(())
+        });
+        assert!(matches!(
+            result.unwrap_err().downcast::<SandboxError>().unwrap(),
+            SandboxError::TimeExceeded { .. }
+        ));
+        // the killed thread still holds its slot.
+        assert_eq!(SLOTS.running(), 1);
+        let result = run_in_slots(&SLOTS, config, MockStateView, |_state_view| Ok(()));
+        assert!(matches!(
+            result.unwrap_err().downcast::<SandboxError>().unwrap(),
+            SandboxError::Busy { limit: 1 }
+        ));
+
+        sender.send(()).unwrap();
+        exit_receiver.recv().unwrap();
+        // wait the thread to release the slot.
+        while SLOTS.running() > 0 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let result = run_in_slots(&SLOTS, config, MockStateView, |_state_view| Ok(()));
+        assert!(result.is_ok());
+    }
+}
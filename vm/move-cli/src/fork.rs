@@ -0,0 +1,209 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::StateWithProofView;
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::access_path::AccessPath;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File under the storage dir which records the remote state the storage is forked from.
+pub const FORK_CONFIG_FILE: &str = "fork.json";
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ForkConfig {
+    /// Rpc endpoint of the remote network.
+    pub rpc: String,
+    /// The block number forked at.
+    pub block_number: u64,
+    /// The state root of the forked block.
+    pub state_root: HashValue,
+    /// Access paths deleted locally, they should not be fetched from the remote again.
+    #[serde(default)]
+    pub deleted: BTreeSet<String>,
+}
+
+/// The remote state which the local storage is forked from.
+/// The state is fetched lazily on first access, and local modifications overlay it.
+pub struct ForkState {
+    config_path: PathBuf,
+    config: Mutex<ForkConfig>,
+    client: RpcClient,
+}
+
+impl ForkState {
+    /// Fork the storage in `storage_dir` from the state of the remote network at `block_number`,
+    /// the head block of the remote network is used if `block_number` is None.
+    /// If the storage is already forked, the fork source must be the same.
+    pub fn fork(storage_dir: &Path, rpc: &str, block_number: Option<u64>) -> Result<Self> {
+        let config_path = storage_dir.join(FORK_CONFIG_FILE);
+        if config_path.exists() {
+            let config = Self::load_config(&config_path)?;
+            if config.rpc != rpc || block_number.map_or(false, |n| n != config.block_number) {
+                bail!(
+                    "The storage is already forked from {} at block {}, run `move clean` before forking again",
+                    config.rpc,
+                    config.block_number
+                );
+            }
+            return Self::new(config_path, config);
+        }
+
+        let client = RpcClient::connect_http(rpc)?;
+        let block_number = match block_number {
+            Some(number) => number,
+            None => client.chain_info()?.head.number.0,
+        };
+        let block = client
+            .chain_get_block_by_number(block_number)?
+            .ok_or_else(|| format_err!("Can not find block {} from {}", block_number, rpc))?;
+        let config = ForkConfig {
+            rpc: rpc.to_string(),
+            block_number,
+            state_root: block.header.state_root,
+            deleted: BTreeSet::new(),
+        };
+        fs::write(&config_path, serde_json::to_vec_pretty(&config)?)?;
+        Ok(Self {
+            config_path,
+            config: Mutex::new(config),
+            client,
+        })
+    }
+
+    /// Load the fork of the storage in `storage_dir`, return None if the storage is not forked.
+    pub fn load(storage_dir: &Path) -> Result<Option<Self>> {
+        let config_path = storage_dir.join(FORK_CONFIG_FILE);
+        if !config_path.exists() {
+            return Ok(None);
+        }
+        let config = Self::load_config(&config_path)?;
+        Self::new(config_path, config).map(Some)
+    }
+
+    fn new(config_path: PathBuf, config: ForkConfig) -> Result<Self> {
+        let client = RpcClient::connect_http(config.rpc.as_str())?;
+        Ok(Self {
+            config_path,
+            config: Mutex::new(config),
+            client,
+        })
+    }
+
+    fn load_config(config_path: &Path) -> Result<ForkConfig> {
+        Ok(serde_json::from_slice(&fs::read(config_path)?)?)
+    }
+
+    pub fn config(&self) -> ForkConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Fetch the state at `access_path` from the remote network, return None if it is deleted locally.
+    /// The state is verified by its proof against the state root of the forked block,
+    /// so the remote node can not return a forged state.
+    pub fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let state_root = {
+            let config = self.config.lock().unwrap();
+            if config.deleted.contains(&access_path.to_string()) {
+                return Ok(None);
+            }
+            config.state_root
+        };
+        let state_with_proof = self
+            .client
+            .state_get_with_proof_by_root(access_path.clone(), state_root)?;
+        verify_state(state_root, access_path, state_with_proof)
+    }
+
+    /// Mark the state at `access_path` as deleted locally or not.
+    pub fn set_deleted(&self, access_path: &AccessPath, deleted: bool) -> Result<()> {
+        let mut config = self.config.lock().unwrap();
+        let key = access_path.to_string();
+        let changed = if deleted {
+            config.deleted.insert(key)
+        } else {
+            config.deleted.remove(&key)
+        };
+        if changed {
+            fs::write(&self.config_path, serde_json::to_vec_pretty(&*config)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Verify the fetched state by its proof against the `state_root`, return the state.
+fn verify_state(
+    state_root: HashValue,
+    access_path: &AccessPath,
+    state_with_proof: StateWithProofView,
+) -> Result<Option<Vec<u8>>> {
+    let state = state_with_proof.state.clone().map(|v| v.0);
+    state_with_proof
+        .state_proof()
+        .verify(state_root, access_path.clone(), state.as_deref())
+        .map_err(|e| {
+            format_err!(
+                "Verify the state of {} against the state root {} failed: {}",
+                access_path,
+                state_root,
+                e
+            )
+        })?;
+    Ok(state)
+}
+
+impl fmt::Debug for ForkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkState")
+            .field("config_path", &self.config_path)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_rpc_api::types::StrView;
+    use starcoin_state_api::{ChainStateReader, ChainStateWriter};
+    use starcoin_statedb::ChainStateDB;
+
+    #[test]
+    fn test_verify_state() {
+        let chain_state = ChainStateDB::mock();
+        let access_path = AccessPath::random_resource();
+        chain_state.set(&access_path, vec![1, 2, 3]).unwrap();
+        let state_root = chain_state.commit().unwrap();
+        let state_with_proof =
+            || -> StateWithProofView { chain_state.get_with_proof(&access_path).unwrap().into() };
+
+        assert_eq!(
+            verify_state(state_root, &access_path, state_with_proof()).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        // the forged state.
+        let mut forged = state_with_proof();
+        forged.state = Some(StrView(vec![3, 2, 1]));
+        assert!(verify_state(state_root, &access_path, forged).is_err());
+        // the hidden state.
+        let mut hidden = state_with_proof();
+        hidden.state = None;
+        assert!(verify_state(state_root, &access_path, hidden).is_err());
+        // the state of another root.
+        assert!(verify_state(HashValue::random(), &access_path, state_with_proof()).is_err());
+
+        // the absent state is proved too.
+        let absent_path = AccessPath::random_resource();
+        let absent: StateWithProofView = chain_state.get_with_proof(&absent_path).unwrap().into();
+        assert_eq!(
+            verify_state(state_root, &absent_path, absent).unwrap(),
+            None
+        );
+    }
+}
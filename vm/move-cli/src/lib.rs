@@ -29,6 +29,7 @@ use vm::{
 };
 
 pub(crate) mod dependencies;
+pub mod fork;
 pub mod package;
 pub mod remote_state;
 pub mod test;
@@ -45,6 +46,7 @@ pub const DEFAULT_PACKAGE_DIR: &str = "package";
 /// Default dependency inclusion mode
 pub const DEFAULT_DEP_MODE: &str = "stdlib";
 
+use crate::fork::ForkState;
 /// Default directory for build output
 pub use move_lang::command_line::DEFAULT_OUTPUT_DIR as DEFAULT_BUILD_DIR;
 use starcoin_vm_types::state_view::StateView;
@@ -63,6 +65,8 @@ const EVENTS_DIR: &str = "events";
 pub struct OnDiskStateView {
     build_dir: PathBuf,
     storage_dir: PathBuf,
+    /// The remote state forked from, the state missing on disk is fetched from it.
+    fork: Option<ForkState>,
 }
 
 impl OnDiskStateView {
@@ -83,9 +87,20 @@ impl OnDiskStateView {
             // it is important to canonicalize the path here because `is_data_path()` relies on the
             // fact that storage_dir is canonicalized.
             storage_dir: storage_dir.canonicalize()?,
+            fork: None,
         })
     }
 
+    /// Overlay the on-disk state on the remote `fork` state.
+    pub fn with_fork(mut self, fork: Option<ForkState>) -> Self {
+        self.fork = fork;
+        self
+    }
+
+    pub fn fork(&self) -> Option<&ForkState> {
+        self.fork.as_ref()
+    }
+
     pub fn interface_files_dir(&self) -> Result<String> {
         let path = self.build_dir.join(MOVE_COMPILED_INTERFACES_DIR);
         if !path.exists() {
@@ -98,6 +113,10 @@ impl OnDiskStateView {
         &self.build_dir
     }
 
+    pub fn storage_dir(&self) -> &PathBuf {
+        &self.storage_dir
+    }
+
     fn is_data_path(&self, p: &Path, parent_dir: &str) -> bool {
         if !p.exists() {
             return false;
@@ -150,23 +169,63 @@ impl OnDiskStateView {
         path.with_extension(MOVE_COMPILED_EXTENSION)
     }
 
-    /// Read the resource bytes stored on-disk at `addr`/`tag`
+    /// Read the resource bytes stored on-disk at `addr`/`tag`.
+    /// In fork mode, the resource missing on disk is fetched from the remote and cached on disk.
     pub fn get_resource_bytes(
         &self,
         addr: AccountAddress,
         tag: StructTag,
     ) -> Result<Option<Vec<u8>>> {
-        Self::get_bytes(&self.get_resource_path(addr, tag))
+        let path = self.get_resource_path(addr, tag.clone());
+        match &self.fork {
+            Some(fork) if !path.exists() => {
+                let bytes = fork.get(&AccessPath::new(addr, DataPath::Resource(tag.clone())))?;
+                if let Some(bytes) = &bytes {
+                    self.save_resource(addr, tag, bytes)?;
+                }
+                Ok(bytes)
+            }
+            _ => Self::get_bytes(&path),
+        }
     }
 
-    /// Read the resource bytes stored on-disk at `addr`/`tag`
+    /// Read the module bytes stored on-disk at `addr`/`module_id`.
+    /// In fork mode, the module missing on disk is fetched from the remote and cached on disk.
     fn get_module_bytes(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>> {
-        Self::get_bytes(&self.get_module_path(module_id))
+        let path = self.get_module_path(module_id);
+        match &self.fork {
+            Some(fork) if !path.exists() => {
+                let bytes = fork.get(&AccessPath::new(
+                    *module_id.address(),
+                    DataPath::Code(module_id.name().to_owned()),
+                ))?;
+                if let Some(bytes) = &bytes {
+                    self.save_module(module_id, bytes)?;
+                }
+                Ok(bytes)
+            }
+            _ => Self::get_bytes(&path),
+        }
     }
 
     /// Check if a module at `addr`/`module_id` exists
     pub fn has_module(&self, module_id: &ModuleId) -> bool {
         self.get_module_path(module_id).exists()
+            || (self.fork.is_some() && matches!(self.get_module_bytes(module_id), Ok(Some(_))))
+    }
+
+    /// Fetch the modules used by `source_files` from the remote in fork mode,
+    /// and re-generate mv_interfaces, so the sources can be compiled against them.
+    pub fn fetch_dependencies(&self, source_files: &[String]) -> Result<()> {
+        if self.fork.is_none() {
+            return Ok(());
+        }
+        for (addr, name) in dependencies::get_uses(source_files)? {
+            let module_id =
+                ModuleId::new(AccountAddress::new(addr.to_u8()), Identifier::new(name)?);
+            self.get_module_bytes(&module_id)?;
+        }
+        self.sync_interface_files()
     }
 
     /// Deserialize and return the module stored on-disk at `addr`/`module_id`
@@ -280,7 +339,14 @@ impl OnDiskStateView {
 
     /// Delete resource stored on disk at the path `addr`/`tag`
     pub fn delete_resource(&self, addr: AccountAddress, tag: StructTag) -> Result<()> {
-        let path = self.get_resource_path(addr, tag);
+        let path = self.get_resource_path(addr, tag.clone());
+        if let Some(fork) = &self.fork {
+            // keep the resource deleted, instead of fetching it from the remote again.
+            fork.set_deleted(&AccessPath::new(addr, DataPath::Resource(tag)), true)?;
+            if !path.exists() {
+                return Ok(());
+            }
+        }
         fs::remove_file(path)?;
 
         // delete addr directory if this address is now empty
@@ -297,7 +363,10 @@ impl OnDiskStateView {
         tag: StructTag,
         bcs_bytes: &[u8],
     ) -> Result<()> {
-        let path = self.get_resource_path(addr, tag);
+        let path = self.get_resource_path(addr, tag.clone());
+        if let Some(fork) = &self.fork {
+            fork.set_deleted(&AccessPath::new(addr, DataPath::Resource(tag)), false)?;
+        }
         if !path.exists() {
             fs::create_dir_all(path.parent().unwrap())?;
         }
@@ -3,6 +3,7 @@
 
 use anyhow::{anyhow, bail, Result};
 use errmapgen::ErrorMapping;
+use move_cli::fork::ForkState;
 use move_cli::package::DepMode;
 use move_cli::remote_state::RemoteStateView;
 use move_cli::{
@@ -70,6 +71,14 @@ pub struct Move {
         required_if("mode", "starcoin")
     )]
     starcoin_rpc: String,
+    /// Fork the storage from the state of the remote network at this rpc endpoint.
+    /// The state is fetched lazily on first access, and local modifications overlay it.
+    /// The fork is recorded in the storage, run `clean` to drop it.
+    #[structopt(long, global = true)]
+    fork_from: Option<String>,
+    /// The block number to fork at, default is the current head block of the remote network.
+    #[structopt(long, global = true, requires = "fork-from")]
+    at_block: Option<u64>,
     /// Print additional diagnostics
     #[structopt(short = "v", global = true)]
     verbose: bool,
@@ -223,11 +232,26 @@ impl Move {
     /// `view`, and `doctor`.
     pub fn prepare_state(&self, load_libraries: bool) -> Result<OnDiskStateView> {
         let state = OnDiskStateView::create(&self.build_dir, &self.storage_dir)?;
+        let fork = match &self.fork_from {
+            Some(rpc) => Some(ForkState::fork(state.storage_dir(), rpc, self.at_block)?),
+            None => ForkState::load(state.storage_dir())?,
+        };
+        if let Some(fork) = &fork {
+            if self.verbose {
+                let config = fork.config();
+                println!(
+                    "Forked from {} at block {}, state root: {}",
+                    config.rpc, config.block_number, config.state_root
+                );
+            }
+        }
+        let state = state.with_fork(fork);
 
         if load_libraries {
             self.mode.prepare(&self.get_package_dir(), false)?;
 
-            // preload the storage with library modules (if such modules do not exist yet)
+            // preload the storage with library modules (if such modules do not exist yet),
+            // in fork mode, the modules on the remote chain take precedence.
             let lib_modules = self.get_library_modules()?;
             let new_modules: Vec<_> = lib_modules
                 .into_iter()
@@ -437,6 +461,7 @@ move run` must be applied to a module inside `storage/`",
         fs::read(path)?
     } else {
         // script source file; compile first and then extract bytecode
+        state.fetch_dependencies(&[script_file.to_string()])?;
         let script_opt = compile_script(&state, script_file, verbose)?;
         match script_opt {
             Some(script) => {
@@ -1031,6 +1056,7 @@ fn main() -> Result<()> {
                 })?;
                 state.save_modules(found_modules.iter())?;
             }
+            state.fetch_dependencies(source_files)?;
             check(state, !*no_republish, &source_files, move_args.verbose)
         }
         Command::Publish {
@@ -1039,6 +1065,7 @@ fn main() -> Result<()> {
             ignore_breaking_changes,
         } => {
             let state = move_args.prepare_state(true)?;
+            state.fetch_dependencies(source_files)?;
             publish(
                 state,
                 source_files,
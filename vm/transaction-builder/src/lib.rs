@@ -142,6 +142,39 @@ pub fn build_transfer_txn_by_token_type(
     )
 }
 
+/// Build a transfer txn which puts the `metadata` into the deposit event of the receiver,
+/// such as the payment reference of the receipt identifier.
+pub fn build_transfer_txn_with_metadata_by_token_type(
+    sender: AccountAddress,
+    receiver: AccountAddress,
+    recipient_auth_key: Option<AuthenticationKey>,
+    seq_num: u64,
+    amount: u128,
+    gas_price: u64,
+    max_gas: u64,
+    token_code: TokenCode,
+    metadata: Vec<u8>,
+    expiration_timestamp_secs: u64,
+    chain_id: ChainId,
+) -> RawUserTransaction {
+    RawUserTransaction::new_with_default_gas_token(
+        sender,
+        seq_num,
+        TransactionPayload::ScriptFunction(encode_transfer_with_metadata_script_by_token_code(
+            StdlibVersion::Latest,
+            receiver,
+            recipient_auth_key,
+            amount,
+            token_code,
+            metadata,
+        )),
+        max_gas,
+        gas_price,
+        expiration_timestamp_secs,
+        chain_id,
+    )
+}
+
 pub fn build_accept_token_txn(
     sender: AccountAddress,
     seq_num: u64,
@@ -275,6 +308,30 @@ pub fn encode_transfer_script_by_token_code(
     )
 }
 
+pub fn encode_transfer_with_metadata_script_by_token_code(
+    _version: StdlibVersion,
+    recipient: AccountAddress,
+    recipient_auth_key: Option<AuthenticationKey>,
+    amount: u128,
+    token_code: TokenCode,
+    metadata: Vec<u8>,
+) -> ScriptFunction {
+    ScriptFunction::new(
+        ModuleId::new(
+            core_code_address(),
+            Identifier::new("TransferScripts").unwrap(),
+        ),
+        Identifier::new("peer_to_peer_with_metadata").unwrap(),
+        vec![token_code.into()],
+        vec![
+            bcs_ext::to_bytes(&recipient).unwrap(),
+            bcs_ext::to_bytes(&recipient_auth_key.map(|k| k.to_vec()).unwrap_or_default()).unwrap(),
+            bcs_ext::to_bytes(&amount).unwrap(),
+            bcs_ext::to_bytes(&metadata).unwrap(),
+        ],
+    )
+}
+
 pub fn peer_to_peer_txn_sent_as_association(
     recipient: AccountAddress,
     recipient_auth_key: Option<AuthenticationKey>,
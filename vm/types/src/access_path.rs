@@ -38,7 +38,7 @@
 //! On the other hand, if you want to query only <Alice>/a/*, `address` will be set to Alice and
 //! `path` will be set to "/a" and use the `get_prefix()` method from statedb
 
-use crate::account_address::AccountAddress;
+use crate::account_address::{parse_address, AccountAddress};
 use crate::identifier::Identifier;
 use crate::parser::parse_struct_tag;
 use anyhow::{bail, Result};
@@ -325,7 +325,7 @@ impl FromStr for AccessPath {
         if parts.len() != 3 {
             bail!("Invalid access_path string: {}", s);
         }
-        let address = AccountAddress::from_str(parts[0])?;
+        let address = parse_address(parts[0])?;
         let data_type = DataType::from_index(parts[1].parse()?)?;
         let data_path = match data_type {
             DataType::CODE => AccessPath::code_data_path(Identifier::new(parts[2])?),
@@ -6,6 +6,7 @@
 
 pub use move_core_types::account_address::AccountAddress;
 
+use crate::receipt_identifier::ReceiptIdentifier;
 use crate::transaction::authenticator::AuthenticationKey;
 use starcoin_crypto::ed25519::Ed25519PublicKey;
 use starcoin_crypto::hash::{CryptoHasher, HashValue};
@@ -14,6 +15,133 @@ pub fn from_public_key(public_key: &Ed25519PublicKey) -> AccountAddress {
     AuthenticationKey::ed25519(public_key).derived_address()
 }
 
+/// The hex length of an address.
+const ADDRESS_HEX_LENGTH: usize = AccountAddress::LENGTH * 2;
+/// The hex length of an ed25519 public key or an authentication key.
+const KEY_HEX_LENGTH: usize = AuthenticationKey::LENGTH * 2;
+
+/// The error of parsing an address or a receipt identifier, which explains why the input is invalid.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum AddressParseError {
+    #[error("Address is empty")]
+    Empty,
+    #[error("Invalid character '{character}' at position {position} of address {input}, an address should be hex encoded, such as 0x1")]
+    InvalidCharacter {
+        input: String,
+        character: char,
+        position: usize,
+    },
+    #[error("Address {input} has {actual} hex digits, expect {expected} hex digits, or less with the 0x prefix")]
+    WrongLength {
+        input: String,
+        actual: usize,
+        expected: usize,
+    },
+    #[error("{input} looks like a public key or an authentication key, not an address, derive the address from it by `account derive-address`")]
+    LooksLikeKey { input: String },
+    #[error("Bad checksum of receipt identifier {input}, please check it for typos{}", typo_hint(.typo_position))]
+    BadChecksum {
+        input: String,
+        /// The position of the single character typo which explains the bad checksum, if any.
+        typo_position: Option<usize>,
+    },
+    #[error(
+        "Invalid character '{character}' at position {position} of receipt identifier {input}"
+    )]
+    InvalidReceiptCharacter {
+        input: String,
+        character: char,
+        position: usize,
+    },
+    #[error("Receipt identifier {input} mixes upper case and lower case at position {position}, it should be all lower case or all upper case")]
+    MixedCase { input: String, position: usize },
+    #[error("Receipt identifier {input} has network prefix '{prefix}', expect '{expected}'")]
+    WrongNetworkPrefix {
+        input: String,
+        prefix: String,
+        expected: String,
+    },
+    #[error("Invalid receipt identifier {input}: {reason}")]
+    InvalidReceiptIdentifier { input: String, reason: String },
+}
+
+fn typo_hint(typo_position: &Option<usize>) -> String {
+    match typo_position {
+        Some(position) => format!(", the typo is likely at position {}", position),
+        None => String::new(),
+    }
+}
+
+/// Check if the input looks like a bech32 encoded receipt identifier, such as `stc1...`,
+/// rather than a hex encoded address.
+pub fn looks_like_receipt_identifier(input: &str) -> bool {
+    let input = input.trim();
+    if input.starts_with("0x") || input.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    match input.rfind('1') {
+        Some(separator) if separator > 0 => {
+            input[..separator].chars().all(|c| c.is_ascii_alphabetic())
+        }
+        _ => false,
+    }
+}
+
+/// Parse an address from one of the formats:
+/// - the hex encoded address, with the 0x or 0X prefix the leading zeros can be omitted, such as `0x1`.
+/// - the receipt identifier of the address, such as `stc1...` or `stcr1...`, the auth key and
+///   payment reference of it are ignored.
+///
+/// This is the address parser of the cli and rpc, it explains the failure by `AddressParseError`.
+pub fn parse_address(input: &str) -> Result<AccountAddress, AddressParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(AddressParseError::Empty);
+    }
+    if looks_like_receipt_identifier(input) {
+        return Ok(ReceiptIdentifier::parse(input)?.address());
+    }
+    let (prefix_len, hex_str) = match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(hex_str) => (2, hex_str),
+        None => (0, input),
+    };
+    if let Some((position, character)) =
+        hex_str.char_indices().find(|(_, c)| !c.is_ascii_hexdigit())
+    {
+        return Err(AddressParseError::InvalidCharacter {
+            input: input.to_string(),
+            character,
+            position: position.saturating_add(prefix_len),
+        });
+    }
+    if hex_str.len() == KEY_HEX_LENGTH {
+        return Err(AddressParseError::LooksLikeKey {
+            input: input.to_string(),
+        });
+    }
+    let has_prefix = prefix_len > 0;
+    if hex_str.is_empty()
+        || hex_str.len() > ADDRESS_HEX_LENGTH
+        || (!has_prefix && hex_str.len() != ADDRESS_HEX_LENGTH)
+    {
+        return Err(AddressParseError::WrongLength {
+            input: input.to_string(),
+            actual: hex_str.len(),
+            expected: ADDRESS_HEX_LENGTH,
+        });
+    }
+    let mut bytes = [0u8; AccountAddress::LENGTH];
+    hex::decode_to_slice(
+        format!("{:0>width$}", hex_str, width = ADDRESS_HEX_LENGTH),
+        &mut bytes,
+    )
+    .expect("hex digits and length have been checked");
+    Ok(AccountAddress::new(bytes))
+}
+
 // Define the Hasher used for hashing AccountAddress types. In order to properly use the
 // CryptoHasher derive macro we need to have this in its own module so that it doesn't conflict
 // with the imported `AccountAddress` from move-core-types. It needs to have the same name since
@@ -41,6 +169,81 @@ mod test {
     use super::*;
     use hex::FromHex;
 
+    #[test]
+    fn test_parse_address() {
+        let address = AccountAddress::random();
+        let hex_address = hex::encode(address.to_vec());
+        assert_eq!(parse_address(&address.to_string()).unwrap(), address);
+        assert_eq!(parse_address(&hex_address).unwrap(), address);
+        assert_eq!(
+            parse_address(&format!("0x{}", hex_address)).unwrap(),
+            address
+        );
+        assert_eq!(
+            parse_address(&format!("0X{}", hex_address.to_uppercase())).unwrap(),
+            address
+        );
+        assert_eq!(
+            parse_address(" 0x1 ").unwrap(),
+            AccountAddress::from_hex_literal("0x1").unwrap()
+        );
+        assert_eq!(
+            parse_address("0X0a").unwrap(),
+            AccountAddress::from_hex_literal("0xa").unwrap()
+        );
+        assert_eq!(parse_address(""), Err(AddressParseError::Empty));
+        assert!(matches!(
+            parse_address("0x1g"),
+            Err(AddressParseError::InvalidCharacter {
+                character: 'g',
+                position: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_address("1"),
+            Err(AddressParseError::WrongLength { actual: 1, .. })
+        ));
+        assert!(matches!(
+            parse_address(&format!("0x{}", "1".repeat(33))),
+            Err(AddressParseError::WrongLength { actual: 33, .. })
+        ));
+        assert!(matches!(
+            parse_address(&AuthenticationKey::random().to_string()),
+            Err(AddressParseError::LooksLikeKey { .. })
+        ));
+        assert!(matches!(
+            parse_address("0x"),
+            Err(AddressParseError::WrongLength { actual: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_address_from_receipt_identifier() {
+        let address = AccountAddress::random();
+        let auth_key = AuthenticationKey::random();
+        let receipts = vec![
+            ReceiptIdentifier::v1(address, None),
+            ReceiptIdentifier::v1(address, Some(auth_key)),
+            ReceiptIdentifier::v2(address, None, None).unwrap(),
+            ReceiptIdentifier::v2(address, Some(auth_key), Some(b"order-1".to_vec())).unwrap(),
+        ];
+        for receipt in receipts {
+            let receipt = receipt.to_string();
+            assert!(looks_like_receipt_identifier(&receipt));
+            assert_eq!(parse_address(&receipt).unwrap(), address);
+            assert_eq!(parse_address(&receipt.to_uppercase()).unwrap(), address);
+        }
+
+        let mut typo = ReceiptIdentifier::v1(address, None).to_string();
+        let last = typo.pop().unwrap();
+        typo.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(matches!(
+            parse_address(&typo),
+            Err(AddressParseError::BadChecksum { .. })
+        ));
+    }
+
     #[test]
     fn address_hash() {
         let address: AccountAddress = "ca843279e3427144cead5e4d5999a3d0".parse().unwrap();
@@ -10,4 +10,6 @@ mod treasury;
 pub use block_metadata::BlockMetadata;
 pub use epoch::{Epoch, EpochData, EpochInfo};
 pub use global_time::GlobalTimeOnChain;
-pub use treasury::{LinearWithdrawCapability, Treasury};
+pub use treasury::{
+    LinearWithdrawCapability, Treasury, TreasuryDepositEvent, TreasuryWithdrawEvent,
+};
@@ -40,6 +40,28 @@ impl Treasury {
     }
 }
 
+/// The treasury withdraw event.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryWithdrawEvent {
+    pub amount: u128,
+}
+
+impl MoveResource for TreasuryWithdrawEvent {
+    const MODULE_NAME: &'static str = "Treasury";
+    const STRUCT_NAME: &'static str = "WithdrawEvent";
+}
+
+/// The treasury deposit event.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryDepositEvent {
+    pub amount: u128,
+}
+
+impl MoveResource for TreasuryDepositEvent {
+    const MODULE_NAME: &'static str = "Treasury";
+    const STRUCT_NAME: &'static str = "DepositEvent";
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LinearWithdrawCapability {
     pub total: u128,
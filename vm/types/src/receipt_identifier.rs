@@ -1,9 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_address::AccountAddress;
+use crate::account_address::{AccountAddress, AddressParseError};
 use crate::transaction::authenticator::AuthenticationKey;
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use bech32::ToBase32;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -11,10 +11,23 @@ use std::convert::TryFrom;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
+/// The bech32 human readable part of the receipt identifier.
+pub const RECEIPT_IDENTIFIER_HRP: &str = "stc";
+/// The bech32 human readable part of the receipt identifier v2, which carries a payment reference.
+pub const RECEIPT_IDENTIFIER_V2_HRP: &str = "stcr";
+/// The max length of the payment reference bytes of the receipt identifier v2.
+pub const MAX_PAYMENT_REFERENCE_LENGTH: usize = 64;
+/// The characters of the bech32 data part, in the order of their values.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_SEPARATOR: char = '1';
+
 /// See sip-21
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum ReceiptIdentifier {
     V1(AccountAddress, Option<AuthenticationKey>),
+    /// V2 carries an optional payment reference, such as an order id or memo,
+    /// the reference is put into the metadata of the deposit event when transfer to it.
+    V2(AccountAddress, Option<AuthenticationKey>, Option<Vec<u8>>),
 }
 
 impl FromStr for ReceiptIdentifier {
@@ -34,14 +47,40 @@ impl ReceiptIdentifier {
     pub fn v1(address: AccountAddress, auth_key: Option<AuthenticationKey>) -> ReceiptIdentifier {
         ReceiptIdentifier::V1(address, auth_key)
     }
+
+    pub fn v2(
+        address: AccountAddress,
+        auth_key: Option<AuthenticationKey>,
+        payment_reference: Option<Vec<u8>>,
+    ) -> Result<ReceiptIdentifier> {
+        let payment_reference = payment_reference.filter(|reference| !reference.is_empty());
+        if let Some(reference) = payment_reference.as_ref() {
+            ensure!(
+                reference.len() <= MAX_PAYMENT_REFERENCE_LENGTH,
+                "payment reference is too long, expect at most {} bytes, got {} bytes",
+                MAX_PAYMENT_REFERENCE_LENGTH,
+                reference.len()
+            );
+        }
+        Ok(ReceiptIdentifier::V2(address, auth_key, payment_reference))
+    }
+
     pub fn address(&self) -> AccountAddress {
         match self {
-            ReceiptIdentifier::V1(address, _) => *address,
+            ReceiptIdentifier::V1(address, _) | ReceiptIdentifier::V2(address, _, _) => *address,
         }
     }
     pub fn auth_key(&self) -> Option<&AuthenticationKey> {
         match self {
-            ReceiptIdentifier::V1(_, auth_key) => auth_key.as_ref(),
+            ReceiptIdentifier::V1(_, auth_key) | ReceiptIdentifier::V2(_, auth_key, _) => {
+                auth_key.as_ref()
+            }
+        }
+    }
+    pub fn payment_reference(&self) -> Option<&[u8]> {
+        match self {
+            ReceiptIdentifier::V1(_, _) => None,
+            ReceiptIdentifier::V2(_, _, payment_reference) => payment_reference.as_deref(),
         }
     }
     pub fn encode(&self) -> String {
@@ -57,32 +96,177 @@ impl ReceiptIdentifier {
                 data.insert(0, bech32::u5::try_from_u8(1).unwrap());
                 bech32::encode("stc", data, bech32::Variant::Bech32).unwrap()
             }
+            ReceiptIdentifier::V2(address, auth_key, payment_reference) => {
+                // address | auth key length | auth key | payment reference
+                let mut data = vec![];
+                data.append(address.to_vec().as_mut());
+                match auth_key {
+                    Some(auth_key) => {
+                        data.push(AuthenticationKey::LENGTH as u8);
+                        data.append(auth_key.to_vec().as_mut());
+                    }
+                    None => data.push(0),
+                }
+                if let Some(payment_reference) = payment_reference {
+                    data.extend_from_slice(payment_reference.as_slice());
+                }
+
+                let mut data = data.to_base32();
+                data.insert(0, bech32::u5::try_from_u8(2).unwrap());
+                bech32::encode(RECEIPT_IDENTIFIER_V2_HRP, data, bech32::Variant::Bech32).unwrap()
+            }
         }
     }
     pub fn decode(s: impl AsRef<str>) -> Result<ReceiptIdentifier> {
-        #![allow(clippy::integer_arithmetic)]
-
-        let (hrp, data, variant) = bech32::decode(s.as_ref()).unwrap();
-
-        anyhow::ensure!(variant == bech32::Variant::Bech32, "expect bech32 encoding");
-        anyhow::ensure!(hrp.as_str() == "stc", "expect bech32 hrp to be stc");
+        Ok(Self::parse(s.as_ref())?)
+    }
 
+    /// Same as `decode`, but explains the failure by `AddressParseError`.
+    pub fn parse(input: &str) -> Result<ReceiptIdentifier, AddressParseError> {
+        let input = input.trim();
+        let invalid = |reason: String| AddressParseError::InvalidReceiptIdentifier {
+            input: input.to_string(),
+            reason,
+        };
+        Self::check_characters(input)?;
+        let (hrp, data, variant) = bech32::decode(input).map_err(|e| match e {
+            bech32::Error::InvalidChecksum => AddressParseError::BadChecksum {
+                input: input.to_string(),
+                typo_position: Self::find_typo(input),
+            },
+            e => invalid(e.to_string()),
+        })?;
+        let expect_version = match hrp.as_str() {
+            RECEIPT_IDENTIFIER_HRP => 1u8,
+            RECEIPT_IDENTIFIER_V2_HRP => 2u8,
+            _ => {
+                return Err(AddressParseError::WrongNetworkPrefix {
+                    input: input.to_string(),
+                    prefix: hrp,
+                    expected: RECEIPT_IDENTIFIER_HRP.to_string(),
+                })
+            }
+        };
+        if variant != bech32::Variant::Bech32 {
+            return Err(invalid("expect bech32 encoding, not bech32m".to_string()));
+        }
         let version = data.first().map(|u| u.to_u8());
-        anyhow::ensure!(version.filter(|v| *v == 1u8).is_some(), "expect version 1");
-
-        let data: Vec<u8> = bech32::FromBase32::from_base32(&data[1..])?;
+        if version != Some(expect_version) {
+            return Err(invalid(format!(
+                "unsupported version {:?}, expect version {}",
+                version, expect_version
+            )));
+        }
+        let data: Vec<u8> =
+            bech32::FromBase32::from_base32(&data[1..]).map_err(|e| invalid(e.to_string()))?;
+        if expect_version == 2 {
+            return Self::parse_v2_data(data.as_slice()).map_err(invalid);
+        }
 
         let (address, auth_key) = if data.len() == AccountAddress::LENGTH {
-            (AccountAddress::from_bytes(data.as_slice())?, None)
+            (
+                AccountAddress::from_bytes(data.as_slice()).map_err(|e| invalid(e.to_string()))?,
+                None,
+            )
         } else if data.len() == AccountAddress::LENGTH + AuthenticationKey::LENGTH {
-            let address = AccountAddress::from_bytes(&data[0..AccountAddress::LENGTH])?;
-            let auth_key = AuthenticationKey::try_from(&data[AccountAddress::LENGTH..])?;
+            let address = AccountAddress::from_bytes(&data[0..AccountAddress::LENGTH])
+                .map_err(|e| invalid(e.to_string()))?;
+            let auth_key = AuthenticationKey::try_from(&data[AccountAddress::LENGTH..])
+                .map_err(|e| invalid(e.to_string()))?;
             (address, Some(auth_key))
         } else {
-            anyhow::bail!("invalid data");
+            return Err(invalid(format!(
+                "expect {} or {} bytes of data, got {} bytes",
+                AccountAddress::LENGTH,
+                AccountAddress::LENGTH + AuthenticationKey::LENGTH,
+                data.len()
+            )));
         };
         Ok(ReceiptIdentifier::V1(address, auth_key))
     }
+
+    /// Check the characters before decoding, so the invalid one can be located by its position.
+    fn check_characters(input: &str) -> Result<(), AddressParseError> {
+        let data_start = input
+            .rfind(BECH32_SEPARATOR)
+            .map(|separator| separator + 1)
+            .unwrap_or_default();
+        let mut case = None;
+        for (position, (index, character)) in input.char_indices().enumerate() {
+            let valid = if index < data_start {
+                ('!'..='~').contains(&character)
+            } else {
+                BECH32_CHARSET.contains(character.to_ascii_lowercase())
+            };
+            if !valid {
+                return Err(AddressParseError::InvalidReceiptCharacter {
+                    input: input.to_string(),
+                    character,
+                    position,
+                });
+            }
+            if character.is_ascii_alphabetic() {
+                let is_upper = character.is_ascii_uppercase();
+                if *case.get_or_insert(is_upper) != is_upper {
+                    return Err(AddressParseError::MixedCase {
+                        input: input.to_string(),
+                        position,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the position of the single character typo, by trying to substitute every character of
+    /// the data part. Return None if no or more than one position can fix the checksum.
+    fn find_typo(input: &str) -> Option<usize> {
+        let input = input.to_ascii_lowercase();
+        let data_start = input.rfind(BECH32_SEPARATOR)? + 1;
+        let mut candidates = (data_start..input.len()).filter(|position| {
+            let original = input.as_bytes()[*position] as char;
+            BECH32_CHARSET
+                .chars()
+                .filter(|character| *character != original)
+                .any(|character| {
+                    let mut candidate = input.clone();
+                    candidate
+                        .replace_range(*position..=*position, character.encode_utf8(&mut [0; 4]));
+                    bech32::decode(candidate.as_str()).is_ok()
+                })
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(position), None) => Some(position),
+            _ => None,
+        }
+    }
+
+    fn parse_v2_data(data: &[u8]) -> Result<ReceiptIdentifier, String> {
+        if data.len() <= AccountAddress::LENGTH {
+            return Err(format!(
+                "expect at least {} bytes of data, got {} bytes",
+                AccountAddress::LENGTH + 1,
+                data.len()
+            ));
+        }
+        let (address, data) = data.split_at(AccountAddress::LENGTH);
+        let address = AccountAddress::from_bytes(address).map_err(|e| e.to_string())?;
+        let (auth_key, payment_reference) = match data[0] as usize {
+            0 => (None, &data[1..]),
+            AuthenticationKey::LENGTH if data.len() > AuthenticationKey::LENGTH => {
+                let auth_key = AuthenticationKey::try_from(&data[1..=AuthenticationKey::LENGTH])
+                    .map_err(|e| e.to_string())?;
+                (Some(auth_key), &data[AuthenticationKey::LENGTH + 1..])
+            }
+            len => return Err(format!("invalid auth key length {}", len)),
+        };
+        let payment_reference = if payment_reference.is_empty() {
+            None
+        } else {
+            Some(payment_reference.to_vec())
+        };
+        Self::v2(address, auth_key, payment_reference).map_err(|e| e.to_string())
+    }
 }
 
 impl<'de> Deserialize<'de> for ReceiptIdentifier {
@@ -124,6 +308,82 @@ mod test {
                 assert_eq!(decoded_address, address);
                 assert_eq!(decoded_auth_key, Some(auth_key));
             }
+            ReceiptIdentifier::V2(..) => panic!("expect receipt identifier v1"),
+        }
+    }
+
+    #[test]
+    pub fn test_receipt_identifier_v2() {
+        let address = AccountAddress::random();
+        let auth_key = AuthenticationKey::random();
+        for (auth_key, payment_reference) in vec![
+            (Some(auth_key), Some(b"order-20211016-0001".to_vec())),
+            (None, Some(vec![0u8; MAX_PAYMENT_REFERENCE_LENGTH])),
+            (Some(auth_key), None),
+            (None, None),
+        ] {
+            let receipt =
+                ReceiptIdentifier::v2(address, auth_key, payment_reference.clone()).unwrap();
+            let encoded = receipt.to_string();
+            assert!(encoded.starts_with("stcr1"));
+            let decoded = ReceiptIdentifier::parse(encoded.as_str()).unwrap();
+            assert_eq!(decoded, receipt);
+            assert_eq!(decoded.address(), address);
+            assert_eq!(decoded.auth_key(), auth_key.as_ref());
+            assert_eq!(decoded.payment_reference(), payment_reference.as_deref());
         }
+        assert!(ReceiptIdentifier::v2(
+            address,
+            None,
+            Some(vec![0u8; MAX_PAYMENT_REFERENCE_LENGTH + 1])
+        )
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_receipt_identifier() {
+        let encoded = ReceiptIdentifier::V1(AccountAddress::random(), None).to_string();
+        let mut typo = encoded.clone();
+        let last = typo.pop().unwrap();
+        typo.push(if last == 'q' { 'p' } else { 'q' });
+        assert_eq!(
+            ReceiptIdentifier::parse(&typo),
+            Err(AddressParseError::BadChecksum {
+                input: typo.clone(),
+                typo_position: Some(typo.len() - 1),
+            })
+        );
+
+        let mut invalid_character = encoded.clone();
+        invalid_character.replace_range(5..6, "b");
+        assert_eq!(
+            ReceiptIdentifier::parse(&invalid_character),
+            Err(AddressParseError::InvalidReceiptCharacter {
+                input: invalid_character.clone(),
+                character: 'b',
+                position: 5,
+            })
+        );
+
+        let mixed_case = format!("STC{}", &encoded[3..]);
+        assert!(matches!(
+            ReceiptIdentifier::parse(&mixed_case),
+            Err(AddressParseError::MixedCase { position, .. }) if position > 3
+        ));
+        assert_eq!(
+            ReceiptIdentifier::parse(&encoded.to_uppercase()),
+            ReceiptIdentifier::parse(&encoded)
+        );
+
+        let data = bech32::u5::try_from_u8(1).unwrap();
+        let other_network = bech32::encode("tb", vec![data], bech32::Variant::Bech32).unwrap();
+        assert!(matches!(
+            ReceiptIdentifier::parse(&other_network),
+            Err(AddressParseError::WrongNetworkPrefix { .. })
+        ));
+        assert!(matches!(
+            ReceiptIdentifier::parse("stc"),
+            Err(AddressParseError::InvalidReceiptIdentifier { .. })
+        ));
     }
 }
@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use prometheus::{Histogram, HistogramOpts, HistogramVec, IntCounterVec, Opts};
+use prometheus::{Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts};
 
 pub static TXN_STATUS_COUNTERS: Lazy<IntCounterVec> = Lazy::new(|| {
     let opts = Opts::new("vm_txn_stats", "Counters of executed txn").namespace("starcoin");
@@ -27,3 +27,11 @@ pub static BLOCK_UNCLES: Lazy<Histogram> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static PARALLEL_EXECUTION_CONFLICTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "vm_parallel_execution_conflicts",
+        "Counter of txns re-executed because of conflict in parallel execution"
+    )
+    .unwrap()
+});
@@ -63,6 +63,8 @@ pub struct StarcoinVM {
     move_vm: Arc<MoveVMAdapter>,
     vm_config: Option<VMConfig>,
     version: Option<Version>,
+    /// Use this gas schedule instead of the on chain one, for estimate the gas under a hypothetical schedule.
+    gas_schedule_override: Option<CostTable>,
 }
 
 impl Default for StarcoinVM {
@@ -78,6 +80,15 @@ impl StarcoinVM {
             move_vm: Arc::new(inner),
             vm_config: None,
             version: None,
+            gas_schedule_override: None,
+        }
+    }
+
+    /// Create a vm which always use the `gas_schedule`, ignore the on chain gas schedule.
+    pub fn new_with_gas_schedule(gas_schedule: CostTable) -> Self {
+        Self {
+            gas_schedule_override: Some(gas_schedule),
+            ..Self::new()
         }
     }
 
@@ -87,10 +98,15 @@ impl StarcoinVM {
                 gas_schedule: INITIAL_GAS_SCHEDULE.clone(),
             });
             self.version = Some(Version { major: 0 });
-            Ok(())
         } else {
-            self.load_configs_impl(state)
+            self.load_configs_impl(state)?;
         }
+        if let Some(gas_schedule) = self.gas_schedule_override.as_ref() {
+            self.vm_config = Some(VMConfig {
+                gas_schedule: gas_schedule.clone(),
+            });
+        }
+        Ok(())
     }
 
     fn load_configs_impl(&mut self, state: &dyn StateView) -> Result<(), Error> {